@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the calculation options of a
+//! workbook to manual calculation with iterative calculation enabled.
+
+use rust_xlsxwriter::{CalculationMode, CalculationOptions, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let options = CalculationOptions::new()
+        .set_mode(CalculationMode::Manual)
+        .set_iterative(true)
+        .set_max_iterations(500)
+        .set_max_change(0.0001);
+
+    workbook.set_calculation_options(&options);
+
+    // Add a circular reference that is resolved by iteration.
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_formula(0, 0, "=B1+1")?;
+    worksheet.write_formula(0, 1, "=IF(A1<100,A1,0)")?;
+
+    workbook.save("calculation.xlsx")?;
+
+    Ok(())
+}
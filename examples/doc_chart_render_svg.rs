@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! An example of rendering a chart to an SVG image.
+
+use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Add some data for the chart.
+    worksheet.write_column(0, 0, ["Jan", "Feb", "Mar", "Apr", "May"])?;
+    worksheet.write_column(0, 1, [50, 30, 40, 60, 35])?;
+
+    // Create a new chart.
+    let mut chart = Chart::new(ChartType::Column);
+
+    // Add a data series using Excel formula syntax to describe the range.
+    chart
+        .add_series()
+        .set_categories("Sheet1!$A$1:$A$5")
+        .set_values("Sheet1!$B$1:$B$5")
+        .set_name("Sales");
+
+    chart.title().set_name("Monthly Sales");
+
+    // Render the chart using the data in the workbook.
+    let svg = chart.render_svg(&workbook)?;
+
+    std::fs::write("chart.svg", svg)?;
+
+    Ok(())
+}
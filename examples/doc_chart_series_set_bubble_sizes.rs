@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! A chart example demonstrating a Bubble chart with bubble sizes.
+
+use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Add some data for the chart: likelihood, impact and cost.
+    worksheet.write_column(0, 0, [1, 2, 4, 5])?;
+    worksheet.write_column(0, 1, [2, 5, 3, 4])?;
+    worksheet.write_column(0, 2, [10, 40, 20, 30])?;
+
+    // Create a new chart.
+    let mut chart = Chart::new(ChartType::Bubble);
+
+    // Add a data series with X values, Y values and bubble sizes.
+    chart
+        .add_series()
+        .set_categories("Sheet1!$A$1:$A$4")
+        .set_values("Sheet1!$B$1:$B$4")
+        .set_bubble_sizes("Sheet1!$C$1:$C$4");
+
+    // Add the chart to the worksheet.
+    worksheet.insert_chart(0, 4, &chart)?;
+
+    // Save the file.
+    workbook.save("chart.xlsx")?;
+
+    Ok(())
+}
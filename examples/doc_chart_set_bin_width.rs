@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! An example of creating a Histogram chart with a fixed bin width.
+
+use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Add some data for the chart.
+    let data = [12, 17, 21, 23, 28, 31, 35, 36, 41, 44, 47, 52, 58, 63, 71];
+    worksheet.write_column(0, 0, data)?;
+
+    // Create a new histogram chart.
+    let mut chart = Chart::new(ChartType::Histogram);
+
+    // Add a data series.
+    chart.add_series().set_values("Sheet1!$A$1:$A$15");
+
+    // Group the data into bins with a width of 20.
+    chart.set_bin_width(20.0);
+
+    // Add the chart to the worksheet.
+    worksheet.insert_chart(0, 2, &chart)?;
+
+    // Save the file.
+    workbook.save("chart.xlsx")?;
+
+    Ok(())
+}
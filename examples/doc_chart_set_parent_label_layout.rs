@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! An example of creating a Treemap chart from a hierarchical category
+//! range.
+
+use rust_xlsxwriter::{Chart, ChartParentLabelLayout, ChartType, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Add some hierarchical data for the chart.
+    worksheet.write_column(0, 0, ["Fruit", "Fruit", "Fruit", "Veg", "Veg"])?;
+    worksheet.write_column(0, 1, ["Apple", "Pear", "Plum", "Leek", "Kale"])?;
+    worksheet.write_column(0, 2, [40, 25, 10, 30, 15])?;
+
+    // Create a new treemap chart.
+    let mut chart = Chart::new(ChartType::Treemap);
+
+    // Add a data series with a two level category range.
+    chart
+        .add_series()
+        .set_categories("Sheet1!$A$1:$B$5")
+        .set_values("Sheet1!$C$1:$C$5");
+
+    // Display the parent category labels as banners.
+    chart.set_parent_label_layout(ChartParentLabelLayout::Banner);
+
+    // Add the chart to the worksheet.
+    worksheet.insert_chart(0, 4, &chart)?;
+
+    // Save the file.
+    workbook.save("chart.xlsx")?;
+
+    Ok(())
+}
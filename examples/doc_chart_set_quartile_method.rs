@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! An example of creating a Box and Whisker chart with some of the
+//! statistical display options.
+
+use rust_xlsxwriter::{Chart, ChartQuartileMethod, ChartType, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Add some data for the chart.
+    worksheet.write_column(0, 0, ["Maths", "Maths", "Art", "Art", "Art"])?;
+    worksheet.write_column(0, 1, [63, 61, 46, 58, 98])?;
+    worksheet.write_column(0, 2, [53, 67, 55, 49, 84])?;
+
+    // Create a new box and whisker chart.
+    let mut chart = Chart::new(ChartType::BoxWhisker);
+
+    // Add the data series for each class.
+    chart
+        .add_series()
+        .set_categories("Sheet1!$A$1:$A$5")
+        .set_values("Sheet1!$B$1:$B$5");
+
+    chart
+        .add_series()
+        .set_categories("Sheet1!$A$1:$A$5")
+        .set_values("Sheet1!$C$1:$C$5");
+
+    // Set the statistical display options.
+    chart
+        .set_quartile_method(ChartQuartileMethod::Inclusive)
+        .set_mean_line(true)
+        .set_inner_points(true);
+
+    // Add the chart to the worksheet.
+    worksheet.insert_chart(0, 4, &chart)?;
+
+    // Save the file.
+    workbook.save("chart.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! An example of creating a Waterfall chart with a final total point.
+
+use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Add some data for the chart.
+    worksheet.write_column(0, 0, ["Start", "Sales", "Costs", "Tax", "End"])?;
+    worksheet.write_column(0, 1, [100, 50, -30, -15, 105])?;
+
+    // Create a new waterfall chart.
+    let mut chart = Chart::new(ChartType::Waterfall);
+
+    // Add a data series.
+    chart
+        .add_series()
+        .set_categories("Sheet1!$A$1:$A$5")
+        .set_values("Sheet1!$B$1:$B$5");
+
+    // Set the first and last points as totals.
+    chart.set_total_points(&[0, 4]);
+
+    // Add the chart to the worksheet.
+    worksheet.insert_chart(0, 3, &chart)?;
+
+    // Save the file.
+    workbook.save("chart.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! An example of setting the rotation and perspective of a 3D chart.
+
+use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Add some data for the chart.
+    worksheet.write_column(0, 0, [10, 40, 50, 20, 10])?;
+    worksheet.write_column(0, 1, [20, 30, 40, 30, 20])?;
+
+    // Create a new chart.
+    let mut chart = Chart::new(ChartType::Area3D);
+
+    // Add some data series.
+    chart.add_series().set_values("Sheet1!$A$1:$A$5");
+    chart.add_series().set_values("Sheet1!$B$1:$B$5");
+
+    // Set the 3D view of the chart.
+    chart
+        .set_x_rotation(30)
+        .set_y_rotation(40)
+        .set_perspective(60)
+        .set_depth_percent(200);
+
+    // Add the chart to the worksheet.
+    worksheet.insert_chart(0, 2, &chart)?;
+
+    // Save the file.
+    workbook.save("chart.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing a Polars dataframe to a
+//! worksheet as an Excel table with a number format for one of the columns.
+
+use polars::prelude::*;
+use rust_xlsxwriter::{DataFrameOptions, Format, Table, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Create a sample dataframe.
+    let df = df!(
+        "Fruit" => &["Apple", "Pear", "Plum"],
+        "Cost" => &[1.25, 0.75, 1.5],
+    )?;
+
+    // Set the dataframe options.
+    let currency = Format::new().set_num_format("$0.00");
+    let options = DataFrameOptions::new()
+        .set_table(&Table::new())
+        .set_column_format("Cost", &currency)
+        .set_autofit(true);
+
+    // Write the dataframe to the worksheet.
+    worksheet.write_dataframe(0, 0, &df, &options)?;
+
+    workbook.save("dataframe.xlsx")?;
+
+    Ok(())
+}
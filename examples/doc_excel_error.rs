@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing Excel error values to a
+//! worksheet.
+
+use rust_xlsxwriter::{ExcelError, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write(0, 0, ExcelError::NA)?;
+    worksheet.write(1, 0, ExcelError::Value)?;
+    worksheet.write(2, 0, ExcelError::Div0)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
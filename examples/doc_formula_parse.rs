@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates parsing a formula into an expression
+//! tree.
+
+use rust_xlsxwriter::{Formula, FormulaExpression, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let formula = Formula::new("=SUM(A1:A10)*2");
+
+    let expression = formula.parse()?;
+
+    if let FormulaExpression::Binary { left, .. } = expression {
+        if let FormulaExpression::Function { name, args } = *left {
+            assert_eq!(name, "SUM");
+            assert_eq!(args.len(), 1);
+        }
+    }
+
+    // Formulas with syntax errors return an error.
+    let formula = Formula::new("=SUM(A1:A10");
+    assert!(formula.parse().is_err());
+
+    Ok(())
+}
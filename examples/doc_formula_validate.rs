@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates validating formulas.
+
+use rust_xlsxwriter::Formula;
+
+fn main() {
+    // A valid formula.
+    assert!(Formula::new("=VLOOKUP(A1,B1:C10,2,FALSE)")
+        .validate()
+        .is_ok());
+
+    // A formula with unbalanced parentheses.
+    assert!(Formula::new("=SUM(A1:A10").validate().is_err());
+
+    // A formula with a misspelled function name.
+    assert!(Formula::new("=SUMM(A1:A10)").validate().is_err());
+}
@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! Example of creating a pivot table from worksheet data.
+
+use rust_xlsxwriter::{PivotDataField, PivotField, PivotFunction, PivotTable, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet with some source data for the pivot table.
+    let data = workbook.add_worksheet().set_name("Data")?;
+
+    data.write_row(0, 0, ["Region", "Product", "Quarter", "Sales"])?;
+    data.write_row(1, 0, ["East", "Apples", "Q1"])?;
+    data.write_row(2, 0, ["West", "Apples", "Q1"])?;
+    data.write_row(3, 0, ["East", "Pears", "Q2"])?;
+    data.write_row(4, 0, ["West", "Pears", "Q2"])?;
+    data.write_column(1, 3, [1000, 2500, 3000, 1500])?;
+
+    // Create a new pivot table based on the source data.
+    let pivot_table = PivotTable::new()
+        .set_source_range(("Data", 0, 0, 4, 3))
+        .set_row_fields(&[PivotField::new("Region")])
+        .set_column_fields(&[PivotField::new("Quarter")])
+        .set_data_fields(&[PivotDataField::new("Sales").set_function(PivotFunction::Sum)]);
+
+    // Add the pivot table to another worksheet.
+    let worksheet = workbook.add_worksheet();
+    worksheet.add_pivot_table(2, 0, &pivot_table)?;
+
+    // Save the file to disk.
+    workbook.save("pivot_table.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates adding a table slicer to a worksheet.
+
+use rust_xlsxwriter::{Slicer, Table, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Write some data for the table.
+    worksheet.write_row(0, 0, ["Region", "Product", "Sales"])?;
+    worksheet.write_row(1, 0, ["East", "Apples"])?;
+    worksheet.write_row(2, 0, ["West", "Apples"])?;
+    worksheet.write_row(3, 0, ["East", "Pears"])?;
+    worksheet.write_row(4, 0, ["West", "Pears"])?;
+    worksheet.write_column(1, 2, [1000, 2500, 3000, 1500])?;
+
+    // Add a named table over the data.
+    let table = Table::new().set_name("Sales");
+    worksheet.add_table(0, 0, 4, 2, &table)?;
+
+    // Add a slicer to filter the table by region.
+    let slicer = Slicer::new("Sales", "Region").set_caption("Sales Region");
+    worksheet.insert_slicer(1, 4, &slicer)?;
+
+    // Save the file to disk.
+    workbook.save("slicer.xlsx")?;
+
+    Ok(())
+}
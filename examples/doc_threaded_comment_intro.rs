@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates adding a threaded comment with a reply
+//! and an @mention to a worksheet cell.
+
+use rust_xlsxwriter::{ThreadedComment, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write(0, 0, "Budget")?;
+    worksheet.write(0, 1, 12000)?;
+
+    // Create a threaded comment with a reply.
+    let comment = ThreadedComment::new("Is this figure final? @Jane Doe")
+        .set_author("John Smith")
+        .add_mention("Jane Doe")
+        .add_reply(&ThreadedComment::new("Yes, it is approved.").set_author("Jane Doe"));
+
+    // Add the comment to a cell.
+    worksheet.insert_threaded_comment(0, 1, &comment)?;
+
+    // Save the file to disk.
+    workbook.save("threaded_comments.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates adding a threaded comment with an
+//! explicit timestamp and resolved state.
+
+use rust_xlsxwriter::{ExcelDateTime, ThreadedComment, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let datetime = ExcelDateTime::from_ymd(2025, 3, 14)?.and_hms(9, 30, 0)?;
+
+    // Create a resolved threaded comment with a timestamp.
+    let comment = ThreadedComment::new("Please check the totals.")
+        .set_author("John Smith")
+        .set_datetime(&datetime)
+        .set_resolved(true);
+
+    // Add the comment to a worksheet cell.
+    worksheet.insert_threaded_comment(2, 1, &comment)?;
+
+    // Save the file to disk.
+    workbook.save("threaded_comments.xlsx")?;
+
+    Ok(())
+}
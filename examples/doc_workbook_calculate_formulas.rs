@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates calculating the results of formulas when
+//! the file is saved.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Write some data and formulas that refer to it.
+    worksheet.write_column(0, 0, [10, 20, 30, 40])?;
+    worksheet.write_formula(4, 0, "=SUM(A1:A4)")?;
+    worksheet.write_formula(5, 0, "=AVERAGE(A1:A4)")?;
+    worksheet.write_formula(6, 0, r#"=IF(A5>50,"Over","Under")"#)?;
+
+    // Store the calculated results, 100, 25 and "Over", in the file.
+    workbook.calculate_formulas();
+
+    workbook.save("formulas.xlsx")?;
+
+    Ok(())
+}
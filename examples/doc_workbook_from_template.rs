@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates creating a new workbook from a
+//! template file that contains a chart, and updating the chart data.
+
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new workbook from an existing file.
+    let mut workbook = Workbook::from_template("examples/template.xlsx")?;
+
+    // Update the chart data in the existing worksheet.
+    let worksheet = workbook.worksheet_from_name("Sheet1")?;
+    let data = [3, 5, 2, 6, 4];
+    worksheet.write_column(0, 0, data)?;
+
+    // Add a new worksheet with a summary.
+    let bold = Format::new().set_bold();
+    let worksheet = workbook.add_worksheet().set_name("Summary")?;
+    worksheet.write_with_format(0, 0, "Total", &bold)?;
+    worksheet.write_formula(0, 1, "=SUM(Sheet1!A1:C5)")?;
+
+    // Save the file to disk.
+    workbook.save("template_output.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates protecting the structure of a workbook
+//! so that worksheets can't be added, deleted or renamed.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let _worksheet1 = workbook.add_worksheet();
+    let _worksheet2 = workbook.add_worksheet();
+
+    // Prevent the worksheets from being added, deleted or renamed.
+    workbook.protect_structure();
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting a workbook to manual
+//! calculation mode.
+
+use rust_xlsxwriter::{CalculationMode, CalculationOptions, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Turn off automatic calculation.
+    let options = CalculationOptions::new().set_mode(CalculationMode::Manual);
+    workbook.set_calculation_options(&options);
+
+    // Store the formula results since they aren't recalculated when the
+    // file is opened.
+    workbook.calculate_formulas();
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_number(0, 0, 10)?;
+    worksheet.write_formula(1, 0, "=A1*2")?;
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
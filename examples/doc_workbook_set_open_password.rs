@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates creating a workbook that is encrypted
+//! and requires a password to open it.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Confidential")?;
+
+    // Excel will prompt for this password when the file is opened.
+    workbook.set_open_password("Pa55w0rd")?;
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates protecting the structure of a workbook
+//! with a password that is stored as a SHA-512 hash.
+
+use rust_xlsxwriter::{ProtectionHash, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let _worksheet = workbook.add_worksheet();
+
+    // Protect the workbook structure with a SHA-512 password hash.
+    workbook.protect_with_password("abc123");
+    workbook.set_protection_hash(ProtectionHash::Sha512);
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates widening the worksheet tab bar for a
+//! workbook with a large number of worksheets.
+
+use rust_xlsxwriter::{Workbook, WorkbookView, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Use 80% of the horizontal space for the worksheet tabs.
+    let view = WorkbookView::new().set_tab_ratio(80.0);
+    workbook.set_workbook_view(&view);
+
+    for _ in 0..20 {
+        workbook.add_worksheet();
+    }
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting a workbook to use the 1904
+//! date epoch.
+
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Use the 1904 date system.
+    workbook.use_1904_epoch(true);
+
+    let worksheet = workbook.add_worksheet();
+    let date_format = Format::new().set_num_format("yyyy-mm-dd");
+
+    // The date is stored as the 1904 serial number 43830.
+    let date = ExcelDateTime::from_ymd(2024, 1, 1)?;
+    worksheet.write_datetime_with_format(0, 0, &date, &date_format)?;
+
+    workbook.save("workbook.xlsx")?;
+
+    Ok(())
+}
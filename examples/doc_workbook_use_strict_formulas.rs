@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates turning on strict formula checking to
+//! catch formula errors when they are written.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Turn on strict formula checking.
+    workbook.use_strict_formulas(true);
+
+    let worksheet = workbook.add_worksheet();
+
+    // Write a valid formula.
+    worksheet.write_formula(0, 0, "=SUM(B1:B5)")?;
+
+    // Write a formula with a missing closing parenthesis.
+    let result = worksheet.write_formula(1, 0, "=SUM(B1:B5");
+
+    if let Err(error) = result {
+        // Formula error: 'Sheet1!A2: Unbalanced parentheses: ...'.
+        println!("{error}");
+    }
+
+    workbook.save("formulas.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the window view properties of
+//! a workbook to hide the worksheet tabs and scrollbars.
+
+use rust_xlsxwriter::{Workbook, WorkbookView, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let view = WorkbookView::new()
+        .set_size(1200, 800)
+        .set_sheet_tabs(false)
+        .set_horizontal_scrollbar(false)
+        .set_vertical_scrollbar(false);
+
+    workbook.set_workbook_view(&view);
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Dashboard")?;
+
+    workbook.save("workbook_view.xlsx")?;
+
+    Ok(())
+}
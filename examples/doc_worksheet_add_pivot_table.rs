@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! Example of adding a pivot table with a filter field to a worksheet.
+
+use rust_xlsxwriter::{PivotDataField, PivotField, PivotFunction, PivotTable, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet with some source data for the pivot table.
+    let data = workbook.add_worksheet().set_name("Data")?;
+
+    data.write_row(0, 0, ["Region", "Product", "Sales"])?;
+    data.write_row(1, 0, ["East", "Apples"])?;
+    data.write_row(2, 0, ["West", "Apples"])?;
+    data.write_row(3, 0, ["East", "Pears"])?;
+    data.write_row(4, 0, ["West", "Pears"])?;
+    data.write_column(1, 2, [1000, 2500, 3000, 1500])?;
+
+    // Create a pivot table with a page (filter) field.
+    let pivot_table = PivotTable::new()
+        .set_source_range(("Data", 0, 0, 4, 2))
+        .set_page_fields(&[PivotField::new("Product")])
+        .set_row_fields(&[PivotField::new("Region")])
+        .set_data_fields(&[
+            PivotDataField::new("Sales"),
+            PivotDataField::new("Sales").set_function(PivotFunction::Average),
+        ]);
+
+    // Add the pivot table to a worksheet, leaving room for the filter.
+    let worksheet = workbook.add_worksheet();
+    worksheet.add_pivot_table(2, 0, &pivot_table)?;
+
+    // Save the file to disk.
+    workbook.save("pivot_table.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates serializing data to a worksheet and
+//! reading it back into structs.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+use serde::{Deserialize, Serialize};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    // Create a serializable and deserializable struct.
+    #[derive(Debug, Deserialize, Serialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Produce {
+        fruit: String,
+        cost: f64,
+        in_stock: Option<bool>,
+    }
+
+    // Create some data instances.
+    let items = [
+        Produce {
+            fruit: "Peach".to_string(),
+            cost: 1.05,
+            in_stock: Some(true),
+        },
+        Produce {
+            fruit: "Plum".to_string(),
+            cost: 0.15,
+            in_stock: None,
+        },
+    ];
+
+    // Serialize the data to the worksheet.
+    worksheet.deserialize_headers::<Produce>(0, 0)?;
+    worksheet.serialize(&items)?;
+
+    // Read the data back from the worksheet.
+    for item in worksheet.deserialize_rows::<Produce>(0, 0)? {
+        let item = item?;
+        println!("{item:?}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates reading worksheet data into a struct
+//! that uses `#[xlsx()]` attributes to rename the headers.
+
+use rust_xlsxwriter::{Workbook, XlsxError, XlsxSerialize};
+use serde::{Deserialize, Serialize};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    // Create a struct with renamed headers.
+    #[derive(Debug, Deserialize, Serialize, XlsxSerialize)]
+    struct Produce {
+        #[xlsx(rename = "Item")]
+        fruit: String,
+
+        #[xlsx(rename = "Price")]
+        cost: f64,
+    }
+
+    // Write some data to the worksheet, such as a user might enter.
+    worksheet.write_row(0, 0, ["Item", "Price"])?;
+    worksheet.write(1, 0, "Peach")?;
+    worksheet.write(1, 1, 1.05)?;
+    worksheet.write(2, 0, "Plum")?;
+    worksheet.write(2, 1, 0.15)?;
+
+    // Read the data using the header options from the `xlsx` attributes.
+    let header_options = Produce::to_serialize_field_options();
+
+    for item in worksheet.deserialize_rows_with_options::<Produce>(0, 0, &header_options)? {
+        let item = item?;
+        println!("{item:?}");
+    }
+
+    Ok(())
+}
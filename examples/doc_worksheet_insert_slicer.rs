@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! This example demonstrates adding a slicer with several columns of buttons to
+//! a worksheet.
+
+use rust_xlsxwriter::{Slicer, SlicerStyle, Table, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    // Create a new Excel file object.
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    // Write some data for the table.
+    worksheet.write_row(0, 0, ["Quarter", "Sales"])?;
+    worksheet.write_column(1, 0, ["Q1", "Q2", "Q3", "Q4"])?;
+    worksheet.write_column(1, 1, [1000, 2500, 3000, 1500])?;
+
+    // Add a table over the data. It will have the default name "Table1".
+    worksheet.add_table(0, 0, 4, 1, &Table::new())?;
+
+    // Create a slicer with a 4 column button layout.
+    let slicer = Slicer::new("Table1", "Quarter")
+        .set_column_count(4)
+        .set_style(SlicerStyle::Dark2)
+        .set_width(300)
+        .set_height(80);
+
+    // Insert the slicer in a cell.
+    worksheet.insert_slicer(1, 3, &slicer)?;
+
+    // Save the file to disk.
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
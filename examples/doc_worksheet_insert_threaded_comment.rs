@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates adding a threaded comment with replies
+//! to a worksheet cell.
+
+use rust_xlsxwriter::{ThreadedComment, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Create some replies to a comment.
+    let reply1 = ThreadedComment::new("Looks fine to me.").set_author("Jane Doe");
+    let reply2 = ThreadedComment::new("Thanks @Jane Doe, closing this.")
+        .set_author("John Smith")
+        .add_mention("Jane Doe");
+
+    // Create a resolved threaded comment with the replies.
+    let comment = ThreadedComment::new("Can someone review this value?")
+        .set_author("John Smith")
+        .add_reply(&reply1)
+        .add_reply(&reply2)
+        .set_resolved(true);
+
+    // Add the comment to a worksheet cell.
+    worksheet.insert_threaded_comment(1, 1, &comment)?;
+
+    // Save the file to disk.
+    workbook.save("threaded_comments.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates serializing map records to a worksheet.
+//! The headers are discovered from the keys of all the records and an unknown
+//! key in a later record is added as a new column.
+
+use std::collections::BTreeMap;
+
+use rust_xlsxwriter::{Format, SerializeFieldOptions, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    // Add a format for the headers.
+    let header_format = Format::new().set_bold();
+
+    // Create some map records. Not all the records have the same keys.
+    let records = vec![
+        BTreeMap::from([("Apple", 10), ("Pear", 5)]),
+        BTreeMap::from([("Apple", 8), ("Orange", 7)]),
+    ];
+
+    // Set the serialization location and headers. The headers are the union
+    // of the keys of all the records.
+    let header_options = SerializeFieldOptions::new()
+        .set_header_format(&header_format)
+        .add_unknown_keys(true);
+
+    worksheet.serialize_headers_with_options(0, 0, &records, &header_options)?;
+
+    // Serialize the data.
+    worksheet.serialize(&records)?;
+
+    // Serialize a record with a new key. It is added as a new column.
+    let record = BTreeMap::from([("Apple", 6), ("Pear", 4), ("Plum", 12)]);
+    worksheet.serialize(&record)?;
+
+    // Save the file.
+    workbook.save("serialize.xlsx")?;
+
+    Ok(())
+}
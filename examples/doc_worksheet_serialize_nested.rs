@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates serializing a struct with a nested
+//! struct to a worksheet with the nested fields flattened into columns.
+
+use rust_xlsxwriter::{CustomSerializeField, SerializeFieldOptions, Workbook, XlsxError};
+use serde::Serialize;
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    // Create serializable structs with a nested struct.
+    #[derive(Serialize)]
+    struct Customer {
+        name: &'static str,
+        id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct Order {
+        item: &'static str,
+        customer: Customer,
+        cost: f64,
+    }
+
+    // Create some data instances.
+    let orders = [
+        Order {
+            item: "Peach",
+            customer: Customer {
+                name: "Alice",
+                id: 101,
+            },
+            cost: 1.05,
+        },
+        Order {
+            item: "Plum",
+            customer: Customer {
+                name: "Bob",
+                id: 102,
+            },
+            cost: 0.15,
+        },
+    ];
+
+    // Flatten the nested struct and rename one of the nested fields. The
+    // other nested field uses the default "customer.id" header.
+    let header_options = SerializeFieldOptions::new()
+        .flatten_nested_structs(true)
+        .set_custom_headers(&[CustomSerializeField::new("customer.name").rename("Customer")]);
+
+    // Set the serialization location and headers.
+    worksheet.serialize_headers_with_options(0, 0, &orders[0], &header_options)?;
+
+    // Serialize the data.
+    worksheet.serialize(&orders)?;
+
+    // Save the file.
+    workbook.save("serialize.xlsx")?;
+
+    Ok(())
+}
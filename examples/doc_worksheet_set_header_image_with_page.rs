@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates adding a header image to the first
+//! page of a worksheet.
+
+use rust_xlsxwriter::{HeaderFooterPage, HeaderImagePosition, Image, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    let image = Image::new("examples/rust_logo.png")?
+        .set_scale_height(0.5)
+        .set_scale_width(0.5);
+
+    // Insert the logo in the header of the first page only.
+    worksheet.set_header_with_page("&L&[Picture]", HeaderFooterPage::First);
+    worksheet.set_header_image_with_page(
+        &image,
+        HeaderImagePosition::Left,
+        HeaderFooterPage::First,
+    )?;
+
+    // Increase the top margin to 1.2 for clarity. The -1.0 values are ignored.
+    worksheet.set_margins(-1.0, -1.0, 1.2, -1.0, -1.0, -1.0);
+
+    worksheet.write_string(0, 0, "Hello")?;
+    worksheet.write_string(200, 0, "Hello")?;
+    worksheet.set_view_page_layout();
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting different headers and footers
+//! for the first page and for the odd and even pages of a worksheet.
+
+use rust_xlsxwriter::{HeaderFooterPage, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    // Add a header for the cover page only.
+    worksheet.set_header_with_page("&CAccount Statement", HeaderFooterPage::First);
+
+    // Add mirrored footers for duplex printing.
+    worksheet.set_footer_with_page("&RPage &P", HeaderFooterPage::Odd);
+    worksheet.set_footer_with_page("&LPage &P", HeaderFooterPage::Even);
+
+    worksheet.write_string(0, 0, "Hello")?;
+    worksheet.write_string(200, 0, "Hello")?;
+    worksheet.set_view_page_layout();
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates setting the selections in the panes
+//! of a worksheet with frozen panes.
+
+use rust_xlsxwriter::{Workbook, WorksheetPane, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    // Freeze the top row and the first column.
+    worksheet.set_freeze_panes(1, 1)?;
+
+    // Select cells in the header row pane.
+    worksheet.set_pane_selection_ranges(WorksheetPane::TopRight, 0, 2, &[(0, 2, 0, 4)])?;
+
+    // Select cells in the main, active, pane.
+    worksheet.set_selection_ranges(4, 2, &[(4, 2, 9, 4), (14, 2, 19, 4)])?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
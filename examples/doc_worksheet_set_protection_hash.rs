@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates protecting a worksheet with a password
+//! that is stored as a SHA-512 hash.
+
+use rust_xlsxwriter::{ProtectionHash, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    // Protect the worksheet with a SHA-512 password hash.
+    worksheet.protect_with_password("abc123");
+    worksheet.set_protection_hash(ProtectionHash::Sha512);
+
+    // The hash also applies to unprotected range passwords.
+    worksheet.unprotect_range_with_options(0, 0, 9, 0, "Inputs", "xyz")?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
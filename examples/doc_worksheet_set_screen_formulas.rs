@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates displaying the formulas in a worksheet
+//! instead of their results.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write(0, 0, 10)?;
+    worksheet.write(1, 0, 20)?;
+    worksheet.write_formula(2, 0, "=SUM(A1:A2)")?;
+
+    // Display the formulas instead of the results.
+    worksheet.set_screen_formulas(true);
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
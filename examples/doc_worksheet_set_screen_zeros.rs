@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates turning off the display of zero values
+//! in a worksheet.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write(0, 0, 100)?;
+    worksheet.write(1, 0, 0)?;
+    worksheet.write(2, 0, 50)?;
+
+    // Turn off the display of zero values.
+    worksheet.set_screen_zeros(false);
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates selecting several ranges of cells in
+//! a worksheet.
+
+use rust_xlsxwriter::{Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    // Select the ranges B2:C4, E2:F4 and B7, with E2 as the active cell.
+    worksheet.set_selection_ranges(1, 4, &[(1, 1, 3, 2), (1, 4, 3, 5), (6, 1, 6, 1)])?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
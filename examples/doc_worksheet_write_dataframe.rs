@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing a Polars dataframe with several
+//! different data types to a worksheet.
+
+use polars::prelude::*;
+use rust_xlsxwriter::{DataFrameOptions, Format, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Create a dataframe with a null value and a date column. Polars dates
+    // are stored as days since 1970-01-01.
+    let mut df = df!(
+        "Region" => &["East", "West", "North"],
+        "Sales" => &[Some(1250), None, Some(1810)],
+    )?;
+
+    let dates = Column::new("Date".into(), &[19723, 19724, 19725]);
+    df.with_column(dates.cast(&DataType::Date)?)?;
+
+    // Write the dataframe with a bold header and autofit the columns.
+    let options = DataFrameOptions::new()
+        .set_header_format(&Format::new().set_bold())
+        .set_autofit(true);
+
+    worksheet.write_dataframe(0, 0, &df, &options)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing Excel error values to a
+//! worksheet.
+
+use rust_xlsxwriter::{ExcelError, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_error(0, 0, ExcelError::NA)?;
+    worksheet.write_error(1, 0, ExcelError::Div0)?;
+
+    // The error values can be detected by Excel functions.
+    worksheet.write_formula(0, 1, "=ISNA(A1)")?;
+    worksheet.write_formula(1, 1, r#"=IFERROR(A2, "Error")"#)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing formatted Excel error values
+//! to a worksheet.
+
+use rust_xlsxwriter::{ExcelError, Format, FormatAlign, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let center = Format::new().set_align(FormatAlign::Center);
+
+    // Add a worksheet to the workbook.
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_error_with_format(0, 0, ExcelError::NA, &center)?;
+    worksheet.write_error_with_format(1, 0, ExcelError::Ref, &center)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing an Arrow record batch with
+//! several different data types to a worksheet.
+
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Date32Array, Int32Array, StringArray};
+use arrow::record_batch::RecordBatch;
+use rust_xlsxwriter::{DataFrameOptions, Format, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Create a record batch with a null value and a date column. Arrow
+    // dates are stored as days since 1970-01-01.
+    let regions: ArrayRef = Arc::new(StringArray::from(vec!["East", "West", "North"]));
+    let sales: ArrayRef = Arc::new(Int32Array::from(vec![Some(1250), None, Some(1810)]));
+    let dates: ArrayRef = Arc::new(Date32Array::from(vec![19723, 19724, 19725]));
+
+    let batch =
+        RecordBatch::try_from_iter([("Region", regions), ("Sales", sales), ("Date", dates)])?;
+
+    // Write the record batch with a bold header and autofit the columns.
+    let options = DataFrameOptions::new()
+        .set_header_format(&Format::new().set_bold())
+        .set_autofit(true);
+
+    worksheet.write_record_batch(0, 0, &batch, &options)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
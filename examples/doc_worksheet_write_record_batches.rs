@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+//! The following example demonstrates writing several Arrow record batches
+//! to a worksheet.
+
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Float64Array, UInt32Array};
+use arrow::record_batch::RecordBatch;
+use rust_xlsxwriter::{DataFrameOptions, Table, Workbook, XlsxError};
+
+fn main() -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Create an iterator of record batches, similar to the output of a
+    // Parquet or Arrow IPC reader.
+    let batches = (0..4).map(|batch_num| {
+        let ids: ArrayRef = Arc::new(UInt32Array::from_iter_values(
+            (0..100).map(|n| batch_num * 100 + n),
+        ));
+        let values: ArrayRef = Arc::new(Float64Array::from_iter_values(
+            (0..100).map(|n| f64::from(n) * 0.5),
+        ));
+
+        RecordBatch::try_from_iter([("Id", ids), ("Value", values)])
+    });
+
+    // Write the record batches to the worksheet as a table.
+    let options = DataFrameOptions::new().set_table(&Table::new());
+
+    worksheet.write_record_batches(0, 0, batches, &options)?;
+
+    workbook.save("worksheet.xlsx")?;
+
+    Ok(())
+}
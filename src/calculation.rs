@@ -0,0 +1,262 @@
+// calculation - A module for representing workbook calculation options.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+use std::fmt;
+
+/// The `CalculationOptions` struct is used to set the formula calculation
+/// properties of a workbook.
+///
+/// The `CalculationOptions` struct represents the options in the Excel
+/// "Formulas" section of the "Options" dialog such as the calculation mode,
+/// iterative calculation for circular references and "Set precision as
+/// displayed".
+///
+/// It is used in conjunction with the
+/// [`Workbook::set_calculation_options()`](crate::Workbook::set_calculation_options)
+/// method.
+///
+/// # Examples
+///
+/// The following example demonstrates setting the calculation options of a
+/// workbook to manual calculation with iterative calculation enabled.
+///
+/// ```
+/// # // This code is available in examples/doc_calculation_options.rs
+/// #
+/// use rust_xlsxwriter::{CalculationMode, CalculationOptions, Workbook, XlsxError};
+///
+/// fn main() -> Result<(), XlsxError> {
+///     let mut workbook = Workbook::new();
+///
+///     let options = CalculationOptions::new()
+///         .set_mode(CalculationMode::Manual)
+///         .set_iterative(true)
+///         .set_max_iterations(500)
+///         .set_max_change(0.0001);
+///
+///     workbook.set_calculation_options(&options);
+///
+///     // Add a circular reference that is resolved by iteration.
+///     let worksheet = workbook.add_worksheet();
+///     worksheet.write_formula(0, 0, "=B1+1")?;
+///     worksheet.write_formula(0, 1, "=IF(A1<100,A1,0)")?;
+///
+///     workbook.save("calculation.xlsx")?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalculationOptions {
+    pub(crate) mode: CalculationMode,
+    pub(crate) is_iterative: bool,
+    pub(crate) max_iterations: u16,
+    pub(crate) max_change: f64,
+    pub(crate) precision_as_displayed: bool,
+    pub(crate) calculate_on_save: bool,
+}
+
+impl Default for CalculationOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CalculationOptions {
+    /// Create a new `CalculationOptions` struct.
+    ///
+    /// The defaults are the same as Excel: automatic calculation, no iterative
+    /// calculation and full precision.
+    ///
+    pub fn new() -> CalculationOptions {
+        CalculationOptions {
+            mode: CalculationMode::Automatic,
+            is_iterative: false,
+            max_iterations: 100,
+            max_change: 0.001,
+            precision_as_displayed: false,
+            calculate_on_save: true,
+        }
+    }
+
+    /// Set the calculation mode for the formulas in the workbook.
+    ///
+    /// Excel recalculates formulas automatically by default. For large models
+    /// it can be useful to set the calculation mode to manual so that the
+    /// user controls when the formulas are recalculated, via the F9 key.
+    ///
+    /// Note, in [`CalculationMode::Manual`] mode Excel still recalculates all
+    /// the formulas once when the file is opened, since `rust_xlsxwriter`
+    /// stores a default result of 0 for formulas. After that the formulas are
+    /// only recalculated when the user requests it.
+    ///
+    /// # Parameters
+    ///
+    /// - `mode`: A [`CalculationMode`] enum value.
+    ///
+    pub fn set_mode(mut self, mode: CalculationMode) -> CalculationOptions {
+        self.mode = mode;
+        self
+    }
+
+    /// Turn on iterative calculation for circular references.
+    ///
+    /// By default Excel warns about formulas with circular references, where a
+    /// formula refers directly or indirectly to its own cell. Turning on
+    /// iterative calculation allows intentional circular references which
+    /// are recalculated until the maximum number of iterations or the maximum
+    /// change is reached, see [`CalculationOptions::set_max_iterations()`] and
+    /// [`CalculationOptions::set_max_change()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    pub fn set_iterative(mut self, enable: bool) -> CalculationOptions {
+        self.is_iterative = enable;
+        self
+    }
+
+    /// Set the maximum number of iterations for iterative calculation.
+    ///
+    /// # Parameters
+    ///
+    /// - `iterations`: The maximum number of iterations in the Excel allowed
+    ///   range of 1 to 32767. The default is 100.
+    ///
+    pub fn set_max_iterations(mut self, iterations: u16) -> CalculationOptions {
+        if !(1..=32767).contains(&iterations) {
+            eprintln!(
+                "Maximum iterations {iterations} outside Excel range: 1 <= iterations <= 32767."
+            );
+            return self;
+        }
+
+        self.max_iterations = iterations;
+        self
+    }
+
+    /// Set the maximum change between iterations for iterative calculation.
+    ///
+    /// Iterative calculation stops when the results change by less than this
+    /// value between iterations.
+    ///
+    /// # Parameters
+    ///
+    /// - `max_change`: The maximum change. It must be greater than or equal
+    ///   to 0. The default is 0.001.
+    ///
+    pub fn set_max_change(mut self, max_change: f64) -> CalculationOptions {
+        if !max_change.is_finite() || max_change < 0.0 {
+            eprintln!("Maximum change {max_change} must be a number greater than or equal to 0.");
+            return self;
+        }
+
+        self.max_change = max_change;
+        self
+    }
+
+    /// Set the "Precision as displayed" option.
+    ///
+    /// Turn on Excel's "Set precision as displayed" option. When this is on
+    /// Excel uses the displayed, formatted, value of numbers in calculations
+    /// rather than the full precision stored value.
+    ///
+    /// Note, Excel permanently changes the stored values to the displayed
+    /// precision when the file is recalculated.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    pub fn set_precision_as_displayed(mut self, enable: bool) -> CalculationOptions {
+        self.precision_as_displayed = enable;
+        self
+    }
+
+    /// Set the "Recalculate workbook before saving" option.
+    ///
+    /// In manual calculation mode Excel recalculates the workbook before it is
+    /// saved by default. This option can be used to turn that off. It is
+    /// ignored in the automatic calculation modes.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_calculate_on_save(mut self, enable: bool) -> CalculationOptions {
+        self.calculate_on_save = enable;
+        self
+    }
+
+    // Get the attributes for the <calcPr> element.
+    pub(crate) fn attributes(&self) -> Vec<(&'static str, String)> {
+        let mut attributes = vec![("calcId", "124519".to_string())];
+
+        if self.mode != CalculationMode::Automatic {
+            attributes.push(("calcMode", self.mode.to_string()));
+        }
+
+        // Formulas are recalculated when the file is opened, in all modes, so
+        // that Excel doesn't display the default stored results.
+        attributes.push(("fullCalcOnLoad", "1".to_string()));
+
+        if self.is_iterative {
+            attributes.push(("iterate", "1".to_string()));
+
+            if self.max_iterations != 100 {
+                attributes.push(("iterateCount", self.max_iterations.to_string()));
+            }
+
+            if self.max_change != 0.001 {
+                attributes.push(("iterateDelta", self.max_change.to_string()));
+            }
+        }
+
+        if self.precision_as_displayed {
+            attributes.push(("fullPrecision", "0".to_string()));
+        }
+
+        if self.mode == CalculationMode::Manual && !self.calculate_on_save {
+            attributes.push(("calcOnSave", "0".to_string()));
+        }
+
+        attributes
+    }
+}
+
+/// The `CalculationMode` enum defines the formula calculation modes of a
+/// workbook.
+///
+/// It is used with [`CalculationOptions::set_mode()`].
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CalculationMode {
+    /// Recalculate formulas automatically when the data they depend on
+    /// changes. This is the default.
+    #[default]
+    Automatic,
+
+    /// Recalculate formulas automatically, except for data tables.
+    AutomaticExceptTables,
+
+    /// Only recalculate formulas when the user requests it, for example with
+    /// the F9 key.
+    Manual,
+}
+
+impl fmt::Display for CalculationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Automatic => write!(f, "auto"),
+            Self::AutomaticExceptTables => write!(f, "autoNoTable"),
+            Self::Manual => write!(f, "manual"),
+        }
+    }
+}
@@ -256,6 +256,7 @@
 //!
 //! - Area
 //! - Bar
+//! - Bubble
 //! - Column
 //! - Doughnut
 //! - Line
@@ -263,13 +264,24 @@
 //! - Radar
 //! - Stock
 //! - Scatter
+//! - Surface and Contour
 //!
-//! See [`ChartType`] for the full list and examples.
+//! The Area, Bar, Column, Line and Pie charts also have 3D variants. See
+//! [`ChartType`] for the full list and examples.
 //!
-//! Support for newer Excel chart types such as Treemap, Sunburst, Box and
-//! Whisker, Statistical Histogram, Waterfall, Funnel, and Maps is not currently
-//! planned since the underlying structure is substantially different from the
-//! original chart types above.
+//! The newer Excel 2016 chart types are also supported:
+//!
+//! - Histogram
+//! - Pareto
+//! - Box and Whisker
+//! - Waterfall
+//! - Funnel
+//! - Treemap
+//! - Sunburst
+//!
+//! These are stored in a different "chartex" format in the xlsx file and
+//! support a subset of the chart options. See [`ChartType`] for details. Map
+//! charts are not supported.
 //!
 //!
 //!
@@ -738,6 +750,7 @@
 //!
 #![warn(missing_docs)]
 
+mod svg;
 mod tests;
 
 use std::io::Cursor;
@@ -746,12 +759,12 @@ use std::{fmt, mem, sync::OnceLock};
 use crate::drawing::{DrawingObject, DrawingType};
 use crate::utility::{self, ToXmlBoolean};
 use crate::xmlwriter::{
-    xml_data_element_only, xml_declaration, xml_empty_tag, xml_empty_tag_only, xml_end_tag,
-    xml_start_tag, xml_start_tag_only,
+    xml_data_element, xml_data_element_only, xml_declaration, xml_empty_tag, xml_empty_tag_only,
+    xml_end_tag, xml_start_tag, xml_start_tag_only,
 };
 
 use crate::{
-    ColNum, Color, IntoExcelDateTime, ObjectMovement, RowNum, XlsxError, COL_MAX, ROW_MAX,
+    ColNum, Color, IntoExcelDateTime, ObjectMovement, RowNum, Workbook, XlsxError, COL_MAX, ROW_MAX,
 };
 
 // Some chart ranges are structured references or non-contiguous ranges that
@@ -838,6 +851,7 @@ pub struct Chart {
     pub(crate) y_axis: ChartAxis,
     pub(crate) x2_axis: ChartAxis,
     pub(crate) y2_axis: ChartAxis,
+    pub(crate) z_axis: ChartAxis,
     pub(crate) combined_chart: Option<Box<Chart>>,
     pub(crate) chart_area: ChartArea,
     pub(crate) plot_area: ChartPlotArea,
@@ -866,6 +880,23 @@ pub struct Chart {
     series_index: usize,
     has_secondary_axis: bool,
     has_crosses: bool,
+    bin_width: Option<f64>,
+    bin_count: Option<u16>,
+    bin_overflow: Option<f64>,
+    bin_underflow: Option<f64>,
+    quartile_method: ChartQuartileMethod,
+    show_mean_line: bool,
+    show_mean_markers: bool,
+    show_inner_points: bool,
+    show_outlier_points: bool,
+    show_connector_lines: bool,
+    total_points: Vec<usize>,
+    parent_label_layout: ChartParentLabelLayout,
+    x_rotation: i16,
+    y_rotation: u16,
+    perspective: u8,
+    depth_percent: u16,
+    right_angle_axes: bool,
 }
 
 impl Chart {
@@ -958,6 +989,7 @@ impl Chart {
             y_axis: ChartAxis::new(),
             x2_axis: ChartAxis::new(),
             y2_axis: ChartAxis::new(),
+            z_axis: ChartAxis::new(),
             legend: ChartLegend::new(),
             chart_area: ChartArea::default(),
             plot_area: ChartPlotArea::default(),
@@ -986,28 +1018,55 @@ impl Chart {
             has_crosses: true,
             is_chartsheet: false,
             protection_on: false,
+            bin_width: None,
+            bin_count: None,
+            bin_overflow: None,
+            bin_underflow: None,
+            quartile_method: ChartQuartileMethod::Exclusive,
+            show_mean_line: false,
+            show_mean_markers: true,
+            show_inner_points: false,
+            show_outlier_points: true,
+            show_connector_lines: true,
+            total_points: vec![],
+            parent_label_layout: ChartParentLabelLayout::Overlapping,
+            x_rotation: 15,
+            y_rotation: 20,
+            perspective: 30,
+            depth_percent: 100,
+            right_angle_axes: false,
         };
 
         match chart_type {
-            ChartType::Area | ChartType::AreaStacked | ChartType::AreaPercentStacked => {
-                Self::initialize_area_chart(chart)
-            }
-
-            ChartType::Bar | ChartType::BarStacked | ChartType::BarPercentStacked => {
-                Self::initialize_bar_chart(chart)
-            }
-
-            ChartType::Column | ChartType::ColumnStacked | ChartType::ColumnPercentStacked => {
-                Self::initialize_column_chart(chart)
-            }
+            ChartType::Area
+            | ChartType::AreaStacked
+            | ChartType::AreaPercentStacked
+            | ChartType::Area3D
+            | ChartType::Area3DStacked
+            | ChartType::Area3DPercentStacked => Self::initialize_area_chart(chart),
+
+            ChartType::Bar
+            | ChartType::BarStacked
+            | ChartType::BarPercentStacked
+            | ChartType::Bar3D
+            | ChartType::Bar3DStacked
+            | ChartType::Bar3DPercentStacked => Self::initialize_bar_chart(chart),
+
+            ChartType::Column
+            | ChartType::ColumnStacked
+            | ChartType::ColumnPercentStacked
+            | ChartType::Column3D
+            | ChartType::Column3DStacked
+            | ChartType::Column3DPercentStacked => Self::initialize_column_chart(chart),
 
             ChartType::Doughnut => Self::initialize_doughnut_chart(chart),
 
-            ChartType::Line | ChartType::LineStacked | ChartType::LinePercentStacked => {
-                Self::initialize_line_chart(chart)
-            }
+            ChartType::Line
+            | ChartType::LineStacked
+            | ChartType::LinePercentStacked
+            | ChartType::Line3D => Self::initialize_line_chart(chart),
 
-            ChartType::Pie => Self::initialize_pie_chart(chart),
+            ChartType::Pie | ChartType::Pie3D => Self::initialize_pie_chart(chart),
 
             ChartType::Radar | ChartType::RadarWithMarkers | ChartType::RadarFilled => {
                 Self::initialize_radar_chart(chart)
@@ -1017,9 +1076,24 @@ impl Chart {
             | ChartType::ScatterStraight
             | ChartType::ScatterStraightWithMarkers
             | ChartType::ScatterSmooth
-            | ChartType::ScatterSmoothWithMarkers => Self::initialize_scatter_chart(chart),
+            | ChartType::ScatterSmoothWithMarkers
+            | ChartType::Bubble
+            | ChartType::Bubble3D => Self::initialize_scatter_chart(chart),
 
             ChartType::Stock => Self::initialize_stock_chart(chart),
+
+            ChartType::Surface
+            | ChartType::SurfaceWireframe
+            | ChartType::Contour
+            | ChartType::ContourWireframe => Self::initialize_surface_chart(chart),
+
+            ChartType::Histogram
+            | ChartType::Pareto
+            | ChartType::BoxWhisker
+            | ChartType::Waterfall
+            | ChartType::Funnel
+            | ChartType::Treemap
+            | ChartType::Sunburst => Self::initialize_chart_ex(chart),
         }
     }
 
@@ -1416,6 +1490,19 @@ impl Chart {
         &mut self.y2_axis
     }
 
+    /// Get the chart Z-Axis object in order to set its properties.
+    ///
+    /// Get a reference to the chart's Z-Axis [`ChartAxis`] object in order to
+    /// set its properties. The Z-Axis is the series axis of 3D charts such as
+    /// [`ChartType::Area3D`], [`ChartType::Line3D`] and [`ChartType::Surface`].
+    /// It is ignored for other chart types.
+    ///
+    /// See the [`Chart::x_axis()`][Chart::x_axis] method above.
+    ///
+    pub fn z_axis(&mut self) -> &mut ChartAxis {
+        &mut self.z_axis
+    }
+
     /// Get the chart legend object in order to set its properties.
     ///
     /// Get a reference to the chart's [`ChartLegend`] object in order to set
@@ -2472,21 +2559,22 @@ impl Chart {
         self
     }
 
-    /// Set the width of the chart.
+    /// Set the bin width for a Histogram or Pareto chart.
     ///
-    /// The default width of an Excel chart is 480 pixels. The `set_width()`
-    /// method allows you to set it to some other non-zero size.
+    /// By default Excel calculates the number and width of the histogram bins
+    /// automatically from the data. This method sets a fixed width for each
+    /// bin. It overrides [`Chart::set_bin_count()`].
     ///
     /// # Parameters
     ///
-    /// - `width`: The chart width in pixels.
+    /// - `width`: The width of the histogram bins. Must be greater than 0.
     ///
     /// # Examples
     ///
-    /// A simple chart example using the `rust_xlsxwriter` library.
+    /// An example of creating a Histogram chart with a fixed bin width.
     ///
     /// ```
-    /// # // This code is available in examples/doc_chart_set_width.rs
+    /// # // This code is available in examples/doc_chart_set_bin_width.rs
     /// #
     /// # use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
     /// #
@@ -2495,21 +2583,17 @@ impl Chart {
     /// #     let worksheet = workbook.add_worksheet();
     /// #
     /// #     // Add some data for the chart.
-    /// #     worksheet.write(0, 0, 50)?;
-    /// #     worksheet.write(1, 0, 30)?;
-    /// #     worksheet.write(2, 0, 40)?;
+    /// #     let data = [12, 17, 21, 23, 28, 31, 35, 36, 41, 44, 47, 52, 58, 63, 71];
+    /// #     worksheet.write_column(0, 0, data)?;
     /// #
-    /// #     // Create a new chart.
-    ///     let mut chart = Chart::new(ChartType::Column);
-    ///
-    ///     // Add a data series using Excel formula syntax to describe the range.
-    ///     chart.add_series().set_values("Sheet1!$A$1:$A$3");
+    ///     // Create a new histogram chart.
+    ///     let mut chart = Chart::new(ChartType::Histogram);
     ///
-    ///     // Hide the legend, for clarity.
-    ///     chart.legend().set_hidden();
+    ///     // Add a data series.
+    ///     chart.add_series().set_values("Sheet1!$A$1:$A$15");
     ///
-    ///     // Resize the chart.
-    ///     chart.set_height(200).set_width(240);
+    ///     // Group the data into bins with a width of 20.
+    ///     chart.set_bin_width(20.0);
     ///
     ///     // Add the chart to the worksheet.
     ///     worksheet.insert_chart(0, 2, &chart)?;
@@ -2521,3940 +2605,5858 @@ impl Chart {
     /// # }
     /// ```
     ///
-    /// Output file:
-    ///
-    /// <img src="https://rustxlsxwriter.github.io/images/chart_set_width.png">
-    ///
-    pub fn set_width(&mut self, width: u32) -> &mut Chart {
-        if width == 0 {
-            return self;
+    pub fn set_bin_width(&mut self, width: f64) -> &mut Chart {
+        if width > 0.0 {
+            self.bin_width = Some(width);
         }
-
-        self.width = f64::from(width);
         self
     }
 
-    /// Set the height of the chart.
+    /// Set the number of bins for a Histogram or Pareto chart.
     ///
-    /// The default height of an Excel chart is 480 pixels. The `set_height()`
-    /// method allows you to set it to some other non-zero size. See the example
-    /// above.
+    /// By default Excel calculates the number and width of the histogram bins
+    /// automatically from the data. This method sets a fixed number of bins.
     ///
     /// # Parameters
     ///
-    /// - `height`: The chart height in pixels.
+    /// - `count`: The number of histogram bins. Must be greater than 0.
     ///
-    pub fn set_height(&mut self, height: u32) -> &mut Chart {
-        if height == 0 {
-            return self;
+    pub fn set_bin_count(&mut self, count: u16) -> &mut Chart {
+        if count > 0 {
+            self.bin_count = Some(count);
         }
-
-        self.height = f64::from(height);
         self
     }
 
-    /// Set the height scale for the chart.
+    /// Set the overflow bin value for a Histogram or Pareto chart.
     ///
-    /// Set the height scale for the chart relative to 1.0 (i.e. 100%). This is
-    /// a syntactic alternative to [`Chart::set_height()`].
+    /// Values greater than the overflow value are grouped together in a
+    /// single bin at the end of the chart.
     ///
     /// # Parameters
     ///
-    /// - `scale`: The scale ratio.
+    /// - `value`: The overflow bin cut-off value.
     ///
-    pub fn set_scale_height(&mut self, scale: f64) -> &mut Chart {
-        if scale <= 0.0 {
-            return self;
-        }
-
-        self.scale_height = scale;
+    pub fn set_bin_overflow(&mut self, value: f64) -> &mut Chart {
+        self.bin_overflow = Some(value);
         self
     }
 
-    /// Set the width scale for the chart.
+    /// Set the underflow bin value for a Histogram or Pareto chart.
     ///
-    /// Set the width scale for the chart relative to 1.0 (i.e. 100%). This is a
-    /// syntactic alternative to [`Chart::set_width()`].
+    /// Values less than or equal to the underflow value are grouped together
+    /// in a single bin at the start of the chart.
     ///
     /// # Parameters
     ///
-    /// - `scale`: The scale ratio.
+    /// - `value`: The underflow bin cut-off value.
     ///
-    pub fn set_scale_width(&mut self, scale: f64) -> &mut Chart {
-        if scale <= 0.0 {
-            return self;
-        }
-
-        self.scale_width = scale;
+    pub fn set_bin_underflow(&mut self, value: f64) -> &mut Chart {
+        self.bin_underflow = Some(value);
         self
     }
 
-    /// Set a user defined name for a chart.
+    /// Set the quartile calculation method for a Box and Whisker chart.
     ///
-    /// By default Excel names charts as "Chart 1", "Chart 2", etc. This name
-    /// shows up in the formula bar and can be used to find or reference a
-    /// chart.
+    /// # Parameters
     ///
-    /// The [`Chart::set_name()`] method allows you to give the chart a user
-    /// defined name.
+    /// - `method`: A [`ChartQuartileMethod`] enum value. The default is
+    ///   [`ChartQuartileMethod::Exclusive`].
     ///
-    /// # Parameters
+    /// # Examples
     ///
-    /// - `name`: A user defined name for the chart.
+    /// An example of creating a Box and Whisker chart with some of the
+    /// statistical display options.
     ///
-    pub fn set_name(&mut self, name: impl Into<String>) -> &mut Chart {
-        self.name = name.into();
-        self
-    }
-
-    /// Set the alt text for the chart.
+    /// ```
+    /// # // This code is available in examples/doc_chart_set_quartile_method.rs
+    /// #
+    /// # use rust_xlsxwriter::{Chart, ChartQuartileMethod, ChartType, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Add some data for the chart.
+    /// #     worksheet.write_column(0, 0, ["Maths", "Maths", "Art", "Art", "Art"])?;
+    /// #     worksheet.write_column(0, 1, [63, 61, 46, 58, 98])?;
+    /// #     worksheet.write_column(0, 2, [53, 67, 55, 49, 84])?;
+    /// #
+    ///     // Create a new box and whisker chart.
+    ///     let mut chart = Chart::new(ChartType::BoxWhisker);
     ///
-    /// Set the alt text for the chart to help accessibility. The alt text is
-    /// used with screen readers to help people with visual disabilities.
+    ///     // Add the data series for each class.
+    ///     chart
+    ///         .add_series()
+    ///         .set_categories("Sheet1!$A$1:$A$5")
+    ///         .set_values("Sheet1!$B$1:$B$5");
     ///
-    /// See the following Microsoft documentation on [Everything you need to
-    /// know to write effective alt
-    /// text](https://support.microsoft.com/en-us/office/everything-you-need-to-know-to-write-effective-alt-text-df98f884-ca3d-456c-807b-1a1fa82f5dc2).
+    ///     chart
+    ///         .add_series()
+    ///         .set_categories("Sheet1!$A$1:$A$5")
+    ///         .set_values("Sheet1!$C$1:$C$5");
     ///
-    /// # Parameters
+    ///     // Set the statistical display options.
+    ///     chart
+    ///         .set_quartile_method(ChartQuartileMethod::Inclusive)
+    ///         .set_mean_line(true)
+    ///         .set_inner_points(true);
     ///
-    /// - `alt_text`: The alt text string to add to the chart.
+    ///     // Add the chart to the worksheet.
+    ///     worksheet.insert_chart(0, 4, &chart)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("chart.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
     ///
-    pub fn set_alt_text(&mut self, alt_text: impl Into<String>) -> &mut Chart {
-        let alt_text = alt_text.into();
-        if alt_text.chars().count() > 255 {
-            eprintln!("Alternative text is greater than Excel's limit of 255 characters.");
-            return self;
-        }
-
-        self.alt_text = alt_text;
+    pub fn set_quartile_method(&mut self, method: ChartQuartileMethod) -> &mut Chart {
+        self.quartile_method = method;
         self
     }
 
-    /// Mark a chart as decorative.
-    ///
-    /// Charts don't always need an alt text description. Some charts may contain
-    /// little or no useful visual information. Such charts can be marked as
-    /// "decorative" so that screen readers can inform the users that they don't
-    /// contain important information.
+    /// Display a line connecting the mean values in a Box and Whisker chart.
     ///
     /// # Parameters
     ///
     /// - `enable`: Turn the property on/off. It is off by default.
     ///
-    pub fn set_decorative(&mut self, enable: bool) -> &mut Chart {
-        self.decorative = enable;
+    pub fn set_mean_line(&mut self, enable: bool) -> &mut Chart {
+        self.show_mean_line = enable;
         self
     }
 
-    /// Set the object movement options for a chart.
+    /// Display the mean value markers in a Box and Whisker chart.
     ///
-    /// Set the option to define how an chart will behave in Excel if the cells
-    /// under the chart are moved, deleted, or have their size changed. In Excel
-    /// the options are:
-    ///
-    /// 1. Move and size with cells. Default for charts.
-    /// 2. Move but don't size with cells.
-    /// 3. Don't move or size with cells.
+    /// # Parameters
     ///
-    /// <img src="https://rustxlsxwriter.github.io/images/object_movement.png">
+    /// - `enable`: Turn the property on/off. It is on by default.
     ///
-    /// These values are defined in the [`ObjectMovement`] enum.
+    pub fn set_mean_markers(&mut self, enable: bool) -> &mut Chart {
+        self.show_mean_markers = enable;
+        self
+    }
+
+    /// Display the inner (non-outlier) data points in a Box and Whisker chart.
     ///
     /// # Parameters
     ///
-    /// `option` - A [`ObjectMovement`] enum value.
+    /// - `enable`: Turn the property on/off. It is off by default.
     ///
-    pub fn set_object_movement(&mut self, option: ObjectMovement) -> &mut Chart {
-        self.object_movement = option;
+    pub fn set_inner_points(&mut self, enable: bool) -> &mut Chart {
+        self.show_inner_points = enable;
         self
     }
 
-    /// Check a chart instance for configuration errors.
-    ///
-    /// Charts are validated using this methods when they are added to a
-    /// worksheet using the
-    /// [`Worksheet::insert_chart()`](crate::Worksheet::insert_chart) or
-    /// [`Worksheet::insert_chart_with_offset()`](crate::Worksheet::insert_chart_with_offset)
-    /// methods. However, you can also call `Chart::validate()` directly if you
-    /// wish to do your own error handling.
+    /// Display the outlier data points in a Box and Whisker chart.
     ///
-    /// # Errors
+    /// # Parameters
     ///
-    /// - [`XlsxError::ChartError`] - A general error that is raised when a
-    ///   chart parameter is incorrect or a chart is configured incorrectly.
-    /// - [`XlsxError::SheetnameCannotBeBlank`] - Worksheet name in chart range
-    ///   cannot be blank.
-    /// - [`XlsxError::SheetnameLengthExceeded`] - Worksheet name in chart range
-    ///   exceeds Excel's limit of 31 characters.
-    /// - [`XlsxError::SheetnameContainsInvalidCharacter`] - Worksheet name in
-    ///   chart range cannot contain invalid characters: `[ ] : * ? / \`
-    /// - [`XlsxError::SheetnameStartsOrEndsWithApostrophe`] - Worksheet name in
-    ///   chart range cannot start or end with an apostrophe.
+    /// - `enable`: Turn the property on/off. It is on by default.
     ///
-    pub fn validate(&mut self) -> Result<&mut Chart, XlsxError> {
-        // Check for chart without series.
-        if self.series.is_empty() {
-            return Err(XlsxError::ChartError(
-                "Chart must contain at least one series".to_string(),
-            ));
-        }
-
-        for series in &self.series {
-            // Check for a series without a values range.
-            if !series.value_range.has_data() {
-                return Err(XlsxError::ChartError(
-                    "Chart series must contain a 'values' range".to_string(),
-                ));
-            }
-
-            // Check for scatter charts without category ranges. It is optional
-            // for all other types.
-            if self.chart_group_type == ChartType::Scatter && !series.category_range.has_data() {
-                return Err(XlsxError::ChartError(
-                    "Scatter style charts must contain a 'categories' range".to_string(),
-                ));
-            }
-
-            // Validate the series values range.
-            series.value_range.validate()?;
-
-            // Validate the series category range.
-            if series.category_range.has_data() {
-                series.category_range.validate()?;
-            }
-
-            // Validate Polynomial trendline range.
-            if let ChartTrendlineType::Polynomial(order) = series.trendline.trend_type {
-                if !(2..6).contains(&order) {
-                    return Err(XlsxError::ChartError(
-                        "Chart series Polynomial trendline order must be in the Excel range 2-6"
-                            .to_string(),
-                    ));
-                }
-            }
-
-            // Validate Moving Average trendline range.
-            if let ChartTrendlineType::MovingAverage(period) = series.trendline.trend_type {
-                if !(2..4).contains(&period) {
-                    return Err(XlsxError::ChartError(
-                        "Chart series Moving Average trendline period must be in the Excel range 2-4"
-                            .to_string(),
-                    ));
-                }
-            }
-        }
-
-        Ok(self)
+    pub fn set_outlier_points(&mut self, enable: bool) -> &mut Chart {
+        self.show_outlier_points = enable;
+        self
     }
 
-    /// Set the option for displaying empty cells in a chart.
-    ///
-    /// The options are:
+    /// Set the data points that represent totals in a Waterfall chart.
     ///
-    /// - [`ChartEmptyCells::Gaps`]: Show empty cells in the chart as gaps. The
-    ///   default.
-    /// - [`ChartEmptyCells::Zero`]: Show empty cells in the chart as zeroes.
-    /// - [`ChartEmptyCells::Connected`]: Show empty cells in the chart
-    ///   connected by a line to the previous point.
+    /// In a Waterfall chart the total points are drawn from the horizontal
+    /// axis instead of floating from the running total.
     ///
     /// # Parameters
     ///
-    /// `option` - A [`ChartEmptyCells`] enum value.
+    /// - `points`: A slice of zero indexed point numbers.
     ///
-    pub fn show_empty_cells_as(&mut self, option: ChartEmptyCells) -> &mut Chart {
-        self.show_empty_cells_as = Some(option);
-
-        self
-    }
-
-    /// Display #N/A on charts as blank/empty cells.
+    /// # Examples
     ///
-    pub fn show_na_as_empty_cell(&mut self) -> &mut Chart {
-        self.show_na_as_empty = true;
+    /// An example of creating a Waterfall chart with a final total point.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_chart_set_total_points.rs
+    /// #
+    /// # use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Add some data for the chart.
+    /// #     worksheet.write_column(0, 0, ["Start", "Sales", "Costs", "Tax", "End"])?;
+    /// #     worksheet.write_column(0, 1, [100, 50, -30, -15, 105])?;
+    /// #
+    ///     // Create a new waterfall chart.
+    ///     let mut chart = Chart::new(ChartType::Waterfall);
+    ///
+    ///     // Add a data series.
+    ///     chart
+    ///         .add_series()
+    ///         .set_categories("Sheet1!$A$1:$A$5")
+    ///         .set_values("Sheet1!$B$1:$B$5");
+    ///
+    ///     // Set the first and last points as totals.
+    ///     chart.set_total_points(&[0, 4]);
+    ///
+    ///     // Add the chart to the worksheet.
+    ///     worksheet.insert_chart(0, 3, &chart)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("chart.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_total_points(&mut self, points: &[usize]) -> &mut Chart {
+        self.total_points = points.to_vec();
+        self
+    }
+
+    /// Display the connector lines between the points in a Waterfall chart.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_connector_lines(&mut self, enable: bool) -> &mut Chart {
+        self.show_connector_lines = enable;
+        self
+    }
+
+    /// Set the layout of the parent category labels in a Treemap chart.
+    ///
+    /// # Parameters
+    ///
+    /// - `layout`: A [`ChartParentLabelLayout`] enum value. The default is
+    ///   [`ChartParentLabelLayout::Overlapping`].
+    ///
+    /// # Examples
+    ///
+    /// An example of creating a Treemap chart from a hierarchical category
+    /// range.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_chart_set_parent_label_layout.rs
+    /// #
+    /// # use rust_xlsxwriter::{Chart, ChartParentLabelLayout, ChartType, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Add some hierarchical data for the chart.
+    /// #     worksheet.write_column(0, 0, ["Fruit", "Fruit", "Fruit", "Veg", "Veg"])?;
+    /// #     worksheet.write_column(0, 1, ["Apple", "Pear", "Plum", "Leek", "Kale"])?;
+    /// #     worksheet.write_column(0, 2, [40, 25, 10, 30, 15])?;
+    /// #
+    ///     // Create a new treemap chart.
+    ///     let mut chart = Chart::new(ChartType::Treemap);
+    ///
+    ///     // Add a data series with a two level category range.
+    ///     chart
+    ///         .add_series()
+    ///         .set_categories("Sheet1!$A$1:$B$5")
+    ///         .set_values("Sheet1!$C$1:$C$5");
+    ///
+    ///     // Display the parent category labels as banners.
+    ///     chart.set_parent_label_layout(ChartParentLabelLayout::Banner);
+    ///
+    ///     // Add the chart to the worksheet.
+    ///     worksheet.insert_chart(0, 4, &chart)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("chart.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_parent_label_layout(&mut self, layout: ChartParentLabelLayout) -> &mut Chart {
+        self.parent_label_layout = layout;
+        self
+    }
+
+    /// Set the X rotation of a 3D chart.
+    ///
+    /// Set the rotation of a 3D chart around the horizontal axis, i.e., the
+    /// angle at which the chart is tilted towards the viewer. This applies to
+    /// the 3D chart types such as [`ChartType::Column3D`] and to Surface and
+    /// Contour charts. It is ignored for other chart types.
+    ///
+    /// # Parameters
+    ///
+    /// - `rotation`: The X rotation in degrees. The range is -90 <= `rotation`
+    ///   <= 90 and the default is 15 (30 for 3D Pie charts).
+    ///
+    /// # Examples
+    ///
+    /// An example of setting the rotation and perspective of a 3D chart.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_chart_set_x_rotation.rs
+    /// #
+    /// # use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Add some data for the chart.
+    /// #     worksheet.write_column(0, 0, [10, 40, 50, 20, 10])?;
+    /// #     worksheet.write_column(0, 1, [20, 30, 40, 30, 20])?;
+    /// #
+    /// #     // Create a new chart.
+    ///     let mut chart = Chart::new(ChartType::Area3D);
+    ///
+    ///     // Add some data series.
+    ///     chart.add_series().set_values("Sheet1!$A$1:$A$5");
+    ///     chart.add_series().set_values("Sheet1!$B$1:$B$5");
+    ///
+    ///     // Set the 3D view of the chart.
+    ///     chart
+    ///         .set_x_rotation(30)
+    ///         .set_y_rotation(40)
+    ///         .set_perspective(60)
+    ///         .set_depth_percent(200);
+    ///
+    ///     // Add the chart to the worksheet.
+    ///     worksheet.insert_chart(0, 2, &chart)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("chart.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_x_rotation(&mut self, rotation: i16) -> &mut Chart {
+        if (-90..=90).contains(&rotation) {
+            self.x_rotation = rotation;
+        }
+        self
+    }
+
+    /// Set the Y rotation of a 3D chart.
+    ///
+    /// Set the rotation of a 3D chart around the vertical axis. For 3D Pie
+    /// charts this sets the angle of the first slice. See
+    /// [`Chart::set_x_rotation()`] for an example.
+    ///
+    /// # Parameters
+    ///
+    /// - `rotation`: The Y rotation in degrees. The range is 0 <= `rotation`
+    ///   <= 360 and the default is 20 (0 for 3D Pie charts).
+    ///
+    pub fn set_y_rotation(&mut self, rotation: u16) -> &mut Chart {
+        if (0..=360).contains(&rotation) {
+            self.y_rotation = rotation;
+        }
+        self
+    }
+
+    /// Set the perspective of a 3D chart.
+    ///
+    /// Set the field of view of a 3D chart. Larger values give a more
+    /// pronounced perspective effect. The perspective is ignored by Excel if
+    /// the chart has right angle axes, see [`Chart::set_right_angle_axes()`].
+    /// See [`Chart::set_x_rotation()`] for an example.
+    ///
+    /// # Parameters
+    ///
+    /// - `perspective`: The perspective value. The range is 0 <= `perspective`
+    ///   <= 240 and the default is 30.
+    ///
+    pub fn set_perspective(&mut self, perspective: u8) -> &mut Chart {
+        if perspective <= 240 {
+            self.perspective = perspective;
+        }
+        self
+    }
+
+    /// Set the depth of a 3D chart.
+    ///
+    /// Set the depth of a 3D chart as a percentage of its width. See
+    /// [`Chart::set_x_rotation()`] for an example.
+    ///
+    /// # Parameters
+    ///
+    /// - `depth`: The depth percentage. The range is 20 <= `depth` <= 2000
+    ///   and the default is 100.
+    ///
+    pub fn set_depth_percent(&mut self, depth: u16) -> &mut Chart {
+        if (20..=2000).contains(&depth) {
+            self.depth_percent = depth;
+        }
+        self
+    }
+
+    /// Set the axes of a 3D chart to be at right angles.
+    ///
+    /// Turn on or off right angle axes for a 3D chart. When this is on the
+    /// chart is drawn without perspective. It is on by default for the 3D
+    /// Bar and Column charts and for the stacked 3D Area charts and off for
+    /// the other 3D chart types.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off.
+    ///
+    pub fn set_right_angle_axes(&mut self, enable: bool) -> &mut Chart {
+        self.right_angle_axes = enable;
+        self
+    }
+
+    /// Set the width of the chart.
+    ///
+    /// The default width of an Excel chart is 480 pixels. The `set_width()`
+    /// method allows you to set it to some other non-zero size.
+    ///
+    /// # Parameters
+    ///
+    /// - `width`: The chart width in pixels.
+    ///
+    /// # Examples
+    ///
+    /// A simple chart example using the `rust_xlsxwriter` library.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_chart_set_width.rs
+    /// #
+    /// # use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Add some data for the chart.
+    /// #     worksheet.write(0, 0, 50)?;
+    /// #     worksheet.write(1, 0, 30)?;
+    /// #     worksheet.write(2, 0, 40)?;
+    /// #
+    /// #     // Create a new chart.
+    ///     let mut chart = Chart::new(ChartType::Column);
+    ///
+    ///     // Add a data series using Excel formula syntax to describe the range.
+    ///     chart.add_series().set_values("Sheet1!$A$1:$A$3");
+    ///
+    ///     // Hide the legend, for clarity.
+    ///     chart.legend().set_hidden();
+    ///
+    ///     // Resize the chart.
+    ///     chart.set_height(200).set_width(240);
+    ///
+    ///     // Add the chart to the worksheet.
+    ///     worksheet.insert_chart(0, 2, &chart)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("chart.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Output file:
+    ///
+    /// <img src="https://rustxlsxwriter.github.io/images/chart_set_width.png">
+    ///
+    pub fn set_width(&mut self, width: u32) -> &mut Chart {
+        if width == 0 {
+            return self;
+        }
+
+        self.width = f64::from(width);
+        self
+    }
+
+    /// Set the height of the chart.
+    ///
+    /// The default height of an Excel chart is 480 pixels. The `set_height()`
+    /// method allows you to set it to some other non-zero size. See the example
+    /// above.
+    ///
+    /// # Parameters
+    ///
+    /// - `height`: The chart height in pixels.
+    ///
+    pub fn set_height(&mut self, height: u32) -> &mut Chart {
+        if height == 0 {
+            return self;
+        }
+
+        self.height = f64::from(height);
+        self
+    }
+
+    /// Set the height scale for the chart.
+    ///
+    /// Set the height scale for the chart relative to 1.0 (i.e. 100%). This is
+    /// a syntactic alternative to [`Chart::set_height()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `scale`: The scale ratio.
+    ///
+    pub fn set_scale_height(&mut self, scale: f64) -> &mut Chart {
+        if scale <= 0.0 {
+            return self;
+        }
+
+        self.scale_height = scale;
+        self
+    }
+
+    /// Set the width scale for the chart.
+    ///
+    /// Set the width scale for the chart relative to 1.0 (i.e. 100%). This is a
+    /// syntactic alternative to [`Chart::set_width()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `scale`: The scale ratio.
+    ///
+    pub fn set_scale_width(&mut self, scale: f64) -> &mut Chart {
+        if scale <= 0.0 {
+            return self;
+        }
+
+        self.scale_width = scale;
+        self
+    }
+
+    /// Set a user defined name for a chart.
+    ///
+    /// By default Excel names charts as "Chart 1", "Chart 2", etc. This name
+    /// shows up in the formula bar and can be used to find or reference a
+    /// chart.
+    ///
+    /// The [`Chart::set_name()`] method allows you to give the chart a user
+    /// defined name.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: A user defined name for the chart.
+    ///
+    pub fn set_name(&mut self, name: impl Into<String>) -> &mut Chart {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the alt text for the chart.
+    ///
+    /// Set the alt text for the chart to help accessibility. The alt text is
+    /// used with screen readers to help people with visual disabilities.
+    ///
+    /// See the following Microsoft documentation on [Everything you need to
+    /// know to write effective alt
+    /// text](https://support.microsoft.com/en-us/office/everything-you-need-to-know-to-write-effective-alt-text-df98f884-ca3d-456c-807b-1a1fa82f5dc2).
+    ///
+    /// # Parameters
+    ///
+    /// - `alt_text`: The alt text string to add to the chart.
+    ///
+    pub fn set_alt_text(&mut self, alt_text: impl Into<String>) -> &mut Chart {
+        let alt_text = alt_text.into();
+        if alt_text.chars().count() > 255 {
+            eprintln!("Alternative text is greater than Excel's limit of 255 characters.");
+            return self;
+        }
+
+        self.alt_text = alt_text;
+        self
+    }
+
+    /// Mark a chart as decorative.
+    ///
+    /// Charts don't always need an alt text description. Some charts may contain
+    /// little or no useful visual information. Such charts can be marked as
+    /// "decorative" so that screen readers can inform the users that they don't
+    /// contain important information.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    pub fn set_decorative(&mut self, enable: bool) -> &mut Chart {
+        self.decorative = enable;
+        self
+    }
+
+    /// Set the object movement options for a chart.
+    ///
+    /// Set the option to define how an chart will behave in Excel if the cells
+    /// under the chart are moved, deleted, or have their size changed. In Excel
+    /// the options are:
+    ///
+    /// 1. Move and size with cells. Default for charts.
+    /// 2. Move but don't size with cells.
+    /// 3. Don't move or size with cells.
+    ///
+    /// <img src="https://rustxlsxwriter.github.io/images/object_movement.png">
+    ///
+    /// These values are defined in the [`ObjectMovement`] enum.
+    ///
+    /// # Parameters
+    ///
+    /// `option` - A [`ObjectMovement`] enum value.
+    ///
+    pub fn set_object_movement(&mut self, option: ObjectMovement) -> &mut Chart {
+        self.object_movement = option;
+        self
+    }
+
+    /// Render an approximation of the chart as an SVG image.
+    ///
+    /// The `render_svg()` method creates an SVG image of the chart using the
+    /// series, axes, [`ChartFormat`] colors and lines, data labels and legend
+    /// position of the chart. The series data is read from the worksheet cells
+    /// referred to by the chart ranges in the same way as the data cache that
+    /// is written to the xlsx file.
+    ///
+    /// This can be used to create a preview of a chart for a web page or
+    /// report without needing Excel. The output is only an approximation of
+    /// the chart that Excel would display: fonts, text sizes and axis scaling
+    /// may differ, and 3D charts are drawn in 2D.
+    ///
+    /// # Parameters
+    ///
+    /// - `workbook`: The [`Workbook`] that contains the worksheet data
+    ///   referred to by the chart ranges.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::ChartError`] - The chart isn't valid, see
+    ///   [`Chart::validate()`], or it is an Excel 2016, Surface or Contour
+    ///   chart which aren't supported.
+    /// - [`XlsxError::UnknownWorksheetNameOrIndex`] - A chart range refers to
+    ///   a worksheet that isn't in the workbook.
+    ///
+    /// # Examples
+    ///
+    /// An example of rendering a chart to an SVG image.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_chart_render_svg.rs
+    /// #
+    /// # use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Add some data for the chart.
+    /// #     worksheet.write_column(0, 0, ["Jan", "Feb", "Mar", "Apr", "May"])?;
+    /// #     worksheet.write_column(0, 1, [50, 30, 40, 60, 35])?;
+    /// #
+    ///     // Create a new chart.
+    ///     let mut chart = Chart::new(ChartType::Column);
+    ///
+    ///     // Add a data series using Excel formula syntax to describe the range.
+    ///     chart
+    ///         .add_series()
+    ///         .set_categories("Sheet1!$A$1:$A$5")
+    ///         .set_values("Sheet1!$B$1:$B$5")
+    ///         .set_name("Sales");
+    ///
+    ///     chart.title().set_name("Monthly Sales");
+    ///
+    ///     // Render the chart using the data in the workbook.
+    ///     let svg = chart.render_svg(&workbook)?;
+    ///
+    ///     std::fs::write("chart.svg", svg)?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn render_svg(&self, workbook: &Workbook) -> Result<String, XlsxError> {
+        let mut chart = self.clone();
+        chart.validate()?;
+
+        workbook.update_chart_cache_data(&mut chart)?;
+
+        svg::render_svg(&chart)
+    }
+
+    /// Check a chart instance for configuration errors.
+    ///
+    /// Charts are validated using this methods when they are added to a
+    /// worksheet using the
+    /// [`Worksheet::insert_chart()`](crate::Worksheet::insert_chart) or
+    /// [`Worksheet::insert_chart_with_offset()`](crate::Worksheet::insert_chart_with_offset)
+    /// methods. However, you can also call `Chart::validate()` directly if you
+    /// wish to do your own error handling.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::ChartError`] - A general error that is raised when a
+    ///   chart parameter is incorrect or a chart is configured incorrectly.
+    /// - [`XlsxError::SheetnameCannotBeBlank`] - Worksheet name in chart range
+    ///   cannot be blank.
+    /// - [`XlsxError::SheetnameLengthExceeded`] - Worksheet name in chart range
+    ///   exceeds Excel's limit of 31 characters.
+    /// - [`XlsxError::SheetnameContainsInvalidCharacter`] - Worksheet name in
+    ///   chart range cannot contain invalid characters: `[ ] : * ? / \`
+    /// - [`XlsxError::SheetnameStartsOrEndsWithApostrophe`] - Worksheet name in
+    ///   chart range cannot start or end with an apostrophe.
+    ///
+    pub fn validate(&mut self) -> Result<&mut Chart, XlsxError> {
+        // Check for chart without series.
+        if self.series.is_empty() {
+            return Err(XlsxError::ChartError(
+                "Chart must contain at least one series".to_string(),
+            ));
+        }
+
+        // Check for the restrictions on the Excel 2016 chartex chart types.
+        if self.chart_type.is_chart_ex() {
+            if self.is_chartsheet {
+                return Err(XlsxError::ChartError(
+                    "Excel 2016 chart types such as Histogram or Treemap aren't supported in a chartsheet"
+                        .to_string(),
+                ));
+            }
+
+            if self.chart_type != ChartType::BoxWhisker && self.series.len() > 1 {
+                return Err(XlsxError::ChartError(
+                    "Excel 2016 chart types other than Box and Whisker only support one series"
+                        .to_string(),
+                ));
+            }
+        }
+
+        if let Some(combined_chart) = &self.combined_chart {
+            if self.chart_type.is_chart_ex() || combined_chart.chart_type.is_chart_ex() {
+                return Err(XlsxError::ChartError(
+                    "Excel 2016 chart types such as Histogram or Treemap cannot be combined with other charts"
+                        .to_string(),
+                ));
+            }
+
+            if self.chart_type.is_3d() || combined_chart.chart_type.is_3d() {
+                return Err(XlsxError::ChartError(
+                    "3D, Surface and Contour chart types cannot be combined with other charts"
+                        .to_string(),
+                ));
+            }
+        }
+
+        for series in &self.series {
+            // Check for a series without a values range.
+            if !series.value_range.has_data() {
+                return Err(XlsxError::ChartError(
+                    "Chart series must contain a 'values' range".to_string(),
+                ));
+            }
+
+            // Check for scatter charts without category ranges. It is optional
+            // for all other types.
+            if matches!(
+                self.chart_group_type,
+                ChartType::Scatter | ChartType::Bubble
+            ) && !series.category_range.has_data()
+            {
+                return Err(XlsxError::ChartError(
+                    "Scatter style charts must contain a 'categories' range".to_string(),
+                ));
+            }
+
+            // Check for bubble charts without bubble size ranges.
+            if self.chart_group_type == ChartType::Bubble && !series.bubble_size_range.has_data() {
+                return Err(XlsxError::ChartError(
+                    "Bubble charts must contain a 'bubble_sizes' range".to_string(),
+                ));
+            }
+
+            // Check for 3D charts with secondary axes, which Excel doesn't support.
+            if self.chart_type.is_3d() && series.secondary_axis {
+                return Err(XlsxError::ChartError(
+                    "3D, Surface and Contour charts don't support a secondary axis".to_string(),
+                ));
+            }
+
+            // Check for hierarchical charts without category ranges.
+            if matches!(self.chart_type, ChartType::Treemap | ChartType::Sunburst)
+                && !series.category_range.has_data()
+            {
+                return Err(XlsxError::ChartError(
+                    "Treemap and Sunburst charts must contain a 'categories' range".to_string(),
+                ));
+            }
+
+            // Validate the series values range.
+            series.value_range.validate()?;
+
+            // Validate the series category range.
+            if series.category_range.has_data() {
+                series.category_range.validate()?;
+            }
+
+            // Validate the series bubble size range.
+            if series.bubble_size_range.has_data() {
+                series.bubble_size_range.validate()?;
+            }
+
+            // Validate Polynomial trendline range.
+            if let ChartTrendlineType::Polynomial(order) = series.trendline.trend_type {
+                if !(2..6).contains(&order) {
+                    return Err(XlsxError::ChartError(
+                        "Chart series Polynomial trendline order must be in the Excel range 2-6"
+                            .to_string(),
+                    ));
+                }
+            }
+
+            // Validate Moving Average trendline range.
+            if let ChartTrendlineType::MovingAverage(period) = series.trendline.trend_type {
+                if !(2..4).contains(&period) {
+                    return Err(XlsxError::ChartError(
+                        "Chart series Moving Average trendline period must be in the Excel range 2-4"
+                            .to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Set the option for displaying empty cells in a chart.
+    ///
+    /// The options are:
+    ///
+    /// - [`ChartEmptyCells::Gaps`]: Show empty cells in the chart as gaps. The
+    ///   default.
+    /// - [`ChartEmptyCells::Zero`]: Show empty cells in the chart as zeroes.
+    /// - [`ChartEmptyCells::Connected`]: Show empty cells in the chart
+    ///   connected by a line to the previous point.
+    ///
+    /// # Parameters
+    ///
+    /// `option` - A [`ChartEmptyCells`] enum value.
+    ///
+    pub fn show_empty_cells_as(&mut self, option: ChartEmptyCells) -> &mut Chart {
+        self.show_empty_cells_as = Some(option);
+
+        self
+    }
+
+    /// Display #N/A on charts as blank/empty cells.
+    ///
+    pub fn show_na_as_empty_cell(&mut self) -> &mut Chart {
+        self.show_na_as_empty = true;
+
+        self
+    }
+
+    /// Display data on charts from hidden rows or columns.
+    ///
+    pub fn show_hidden_data(&mut self) -> &mut Chart {
+        self.show_hidden_data = true;
+
+        self
+    }
+
+    /// Set default values for the primary chart axis ids.
+    ///
+    /// This is mainly used to ensure that the primary axis ids used in testing
+    /// match the semi-randomized values in the target Excel file.
+    ///
+    /// # Parameters
+    ///
+    /// - `axis_id1`: X-axis id.
+    /// - `axis_id2`: Y-axis id.
+    ///
+    #[doc(hidden)]
+    pub fn set_axis_ids(&mut self, axis_id1: u32, axis_id2: u32) {
+        self.axis_ids = (axis_id1, axis_id2);
+    }
+
+    /// Set default values for the secondary chart axis ids.
+    ///
+    /// This is mainly used to ensure that the secondary axis ids used in
+    /// testing match the semi-randomized values in the target Excel file.
+    ///
+    /// # Parameters
+    ///
+    /// - `axis_id1`: X-axis id.
+    /// - `axis_id2`: Y-axis id.
+    ///
+    #[doc(hidden)]
+    pub fn set_axis2_ids(&mut self, axis_id1: u32, axis_id2: u32) {
+        self.axis2_ids = (axis_id1, axis_id2);
+    }
+
+    // -----------------------------------------------------------------------
+    // Crate level helper methods.
+    // -----------------------------------------------------------------------
+
+    // Check if the chart is an Excel 2016 chart type that is stored in the
+    // chartex format.
+    pub(crate) fn is_chart_ex(&self) -> bool {
+        self.chart_type.is_chart_ex()
+    }
+
+    // Set chart unique axis ids.
+    pub(crate) fn add_axis_ids(&mut self, chart_id: u32) {
+        if self.axis_ids.0 != 0 {
+            return;
+        }
+
+        let axis_id = (5000 + chart_id) * 10000 + 1;
+        self.axis_ids = (axis_id, axis_id + 1);
+
+        if self.combined_chart.is_none() {
+            self.axis2_ids = (axis_id + 2, axis_id + 3);
+        } else {
+            self.axis2_ids = (axis_id + 10_000_000, axis_id + 10_000_001);
+        }
+    }
+
+    // Check for any legend entries that have been hidden/deleted via the
+    // ChartSeries::delete_from_legend() and
+    // ChartTrendline::delete_from_legend() methods. These can in turn be
+    // overridden by the `ChartLegend::delete_entries()` method, which is
+    // checked for first.
+    fn deleted_legend_entries(&self) -> Vec<usize> {
+        // Use the user supplied entries, if available.
+        if !self.legend.deleted_entries.is_empty() {
+            return self.legend.deleted_entries.clone();
+        }
+
+        let mut deleted_entries = vec![];
+        let mut index = 0;
+
+        // Check for deleted series in legend.
+        for series in &self.series {
+            if series.delete_from_legend {
+                deleted_entries.push(index);
+            }
+
+            index += 1;
+        }
+
+        // Check for deleted trendlines in legend. These are indexed after the
+        // series they belong to.
+        for series in &self.series {
+            if series.trendline.trend_type != ChartTrendlineType::None {
+                if series.trendline.delete_from_legend {
+                    deleted_entries.push(index);
+                }
+
+                index += 1;
+            }
+        }
+
+        deleted_entries
+    }
+
+    // Check if the primary chart or optional combined chart have a secondary
+    // data series.
+    fn check_for_secondary_axis(&mut self) {
+        if let Some(combined_chart) = &self.combined_chart {
+            for series in &combined_chart.series {
+                if series.secondary_axis {
+                    self.has_secondary_axis = true;
+                }
+            }
+        }
+
+        for series in &self.series {
+            if series.secondary_axis {
+                self.has_secondary_axis = true;
+                return;
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Chart specific methods.
+    // -----------------------------------------------------------------------
+
+    // Initialize area charts.
+    fn initialize_area_chart(mut self) -> Chart {
+        self.has_crosses = false;
+
+        self.x_axis.axis_type = ChartAxisType::Category;
+        self.x_axis.axis_position = ChartAxisPosition::Bottom;
+        self.x_axis.position_between_ticks = false;
+
+        self.y_axis.axis_type = ChartAxisType::Value;
+        self.y_axis.axis_position = ChartAxisPosition::Left;
+        self.y_axis.title.is_horizontal = true;
+        self.y_axis.major_gridlines = true;
+
+        self.x2_axis.axis_type = ChartAxisType::Category;
+        self.x2_axis.position_between_ticks = false;
+        self.x2_axis.crossing = ChartAxisCrossing::Max;
+        self.x2_axis.is_hidden = true;
+        self.x2_axis.label_position = ChartAxisLabelPosition::None;
+
+        self.y2_axis.axis_type = ChartAxisType::Value;
+        self.y2_axis.axis_position = ChartAxisPosition::Left;
+
+        self.chart_group_type = ChartType::Area;
+
+        match self.chart_type {
+            ChartType::Area | ChartType::Area3D => {
+                self.grouping = ChartGrouping::Standard;
+            }
+            ChartType::AreaStacked | ChartType::Area3DStacked => {
+                self.grouping = ChartGrouping::Stacked;
+            }
+            ChartType::AreaPercentStacked | ChartType::Area3DPercentStacked => {
+                self.grouping = ChartGrouping::PercentStacked;
+                self.default_num_format = "0%".to_string();
+            }
+            _ => {}
+        }
+
+        // The stacked 3D Area charts have right angle axes by default.
+        if matches!(
+            self.chart_type,
+            ChartType::Area3DStacked | ChartType::Area3DPercentStacked
+        ) {
+            self.right_angle_axes = true;
+        }
+
+        self.default_label_position = ChartDataLabelPosition::Center;
+
+        self
+    }
+
+    // Initialize bar charts. Bar chart category/value axes are reversed in
+    // comparison to other charts. Some of the defaults reflect this.
+    fn initialize_bar_chart(mut self) -> Chart {
+        self.has_crosses = false;
+
+        self.x_axis.axis_type = ChartAxisType::Value;
+        self.x_axis.axis_position = ChartAxisPosition::Bottom;
+        self.x_axis.major_gridlines = true;
+
+        self.y_axis.axis_type = ChartAxisType::Category;
+        self.y_axis.axis_position = ChartAxisPosition::Left;
+        self.y_axis.title.is_horizontal = true;
+
+        self.x2_axis.axis_type = ChartAxisType::Category;
+        self.x2_axis.axis_position = ChartAxisPosition::Bottom;
+        self.x2_axis.crossing = ChartAxisCrossing::Automatic;
+
+        self.y2_axis.axis_type = ChartAxisType::Value;
+        self.y2_axis.axis_position = ChartAxisPosition::Left;
+        self.y2_axis.crossing = ChartAxisCrossing::Max;
+        self.y2_axis.is_hidden = true;
+        self.y2_axis.label_position = ChartAxisLabelPosition::None;
+
+        self.chart_group_type = ChartType::Bar;
+
+        match self.chart_type {
+            ChartType::Bar => {
+                self.grouping = ChartGrouping::Clustered;
+            }
+            ChartType::BarStacked => {
+                self.grouping = ChartGrouping::Stacked;
+                self.overlap = Some(100);
+            }
+            ChartType::BarPercentStacked => {
+                self.grouping = ChartGrouping::PercentStacked;
+                self.default_num_format = "0%".to_string();
+                self.overlap = Some(100);
+            }
+            ChartType::Bar3D => {
+                self.grouping = ChartGrouping::Clustered;
+                self.right_angle_axes = true;
+            }
+            ChartType::Bar3DStacked => {
+                self.grouping = ChartGrouping::Stacked;
+                self.right_angle_axes = true;
+            }
+            ChartType::Bar3DPercentStacked => {
+                self.grouping = ChartGrouping::PercentStacked;
+                self.default_num_format = "0%".to_string();
+                self.right_angle_axes = true;
+            }
+            _ => {}
+        }
+
+        self.default_label_position = ChartDataLabelPosition::OutsideEnd;
+
+        self
+    }
+
+    // Initialize column charts.
+    fn initialize_column_chart(mut self) -> Chart {
+        self.x_axis.axis_type = ChartAxisType::Category;
+        self.x_axis.axis_position = ChartAxisPosition::Bottom;
+
+        self.y_axis.axis_type = ChartAxisType::Value;
+        self.y_axis.axis_position = ChartAxisPosition::Left;
+        self.y_axis.major_gridlines = true;
+
+        self.x2_axis.axis_type = ChartAxisType::Category;
+        self.x2_axis.crossing = ChartAxisCrossing::Max;
+        self.x2_axis.is_hidden = true;
+        self.x2_axis.label_position = ChartAxisLabelPosition::None;
+
+        self.y2_axis.axis_type = ChartAxisType::Value;
+        self.y2_axis.axis_position = ChartAxisPosition::Left;
+
+        self.chart_group_type = ChartType::Column;
+
+        match self.chart_type {
+            ChartType::Column => {
+                self.grouping = ChartGrouping::Clustered;
+            }
+            ChartType::ColumnStacked => {
+                self.grouping = ChartGrouping::Stacked;
+                self.overlap = Some(100);
+            }
+            ChartType::ColumnPercentStacked => {
+                self.grouping = ChartGrouping::PercentStacked;
+                self.default_num_format = "0%".to_string();
+                self.overlap = Some(100);
+            }
+            ChartType::Column3D => {
+                self.grouping = ChartGrouping::Clustered;
+                self.right_angle_axes = true;
+            }
+            ChartType::Column3DStacked => {
+                self.grouping = ChartGrouping::Stacked;
+                self.right_angle_axes = true;
+            }
+            ChartType::Column3DPercentStacked => {
+                self.grouping = ChartGrouping::PercentStacked;
+                self.default_num_format = "0%".to_string();
+                self.right_angle_axes = true;
+            }
+            _ => {}
+        }
+
+        self.default_label_position = ChartDataLabelPosition::OutsideEnd;
+
+        self
+    }
+
+    // Initialize doughnut charts.
+    fn initialize_doughnut_chart(mut self) -> Chart {
+        self.chart_group_type = ChartType::Doughnut;
+
+        self.default_label_position = ChartDataLabelPosition::BestFit;
+
+        self
+    }
+
+    // Initialize line charts.
+    fn initialize_line_chart(mut self) -> Chart {
+        self.x_axis.axis_type = ChartAxisType::Category;
+        self.x_axis.axis_position = ChartAxisPosition::Bottom;
+
+        self.y_axis.axis_type = ChartAxisType::Value;
+        self.y_axis.axis_position = ChartAxisPosition::Left;
+        self.y_axis.title.is_horizontal = true;
+        self.y_axis.major_gridlines = true;
+
+        self.x2_axis.axis_type = ChartAxisType::Category;
+        self.x2_axis.crossing = ChartAxisCrossing::Max;
+        self.x2_axis.is_hidden = true;
+        self.x2_axis.label_position = ChartAxisLabelPosition::None;
+
+        self.y2_axis.axis_type = ChartAxisType::Value;
+        self.y2_axis.axis_position = ChartAxisPosition::Left;
+
+        self.chart_group_type = ChartType::Line;
+
+        if self.chart_type == ChartType::Line || self.chart_type == ChartType::Line3D {
+            self.grouping = ChartGrouping::Standard;
+        } else if self.chart_type == ChartType::LineStacked {
+            self.grouping = ChartGrouping::Stacked;
+        } else if self.chart_type == ChartType::LinePercentStacked {
+            self.grouping = ChartGrouping::PercentStacked;
+            self.default_num_format = "0%".to_string();
+        }
+
+        self.default_label_position = ChartDataLabelPosition::Right;
+
+        self
+    }
+
+    // Initialize pie charts.
+    fn initialize_pie_chart(mut self) -> Chart {
+        self.chart_group_type = ChartType::Pie;
+
+        if self.chart_type == ChartType::Pie3D {
+            self.x_rotation = 30;
+            self.y_rotation = 0;
+        }
+
+        self.default_label_position = ChartDataLabelPosition::BestFit;
+
+        self
+    }
+
+    // Initialize radar charts.
+    fn initialize_radar_chart(mut self) -> Chart {
+        self.x_axis.axis_type = ChartAxisType::Category;
+        self.x_axis.axis_position = ChartAxisPosition::Bottom;
+        self.x_axis.major_gridlines = true;
+
+        self.y_axis.axis_type = ChartAxisType::Value;
+        self.y_axis.axis_position = ChartAxisPosition::Left;
+        self.y_axis.major_gridlines = true;
+        self.y_axis.major_tick_type = Some(ChartAxisTickType::Cross);
+
+        self.chart_group_type = ChartType::Radar;
+
+        self.default_label_position = ChartDataLabelPosition::Center;
+
+        self
+    }
+
+    // Initialize scatter charts. Bubble charts are also initialized here since
+    // they have the same value axes as Scatter charts.
+    fn initialize_scatter_chart(mut self) -> Chart {
+        self.x_axis.axis_type = ChartAxisType::Value;
+        self.x_axis.axis_position = ChartAxisPosition::Bottom;
+        self.x_axis.position_between_ticks = false;
+
+        self.y_axis.axis_type = ChartAxisType::Value;
+        self.y_axis.axis_position = ChartAxisPosition::Left;
+        self.y_axis.position_between_ticks = false;
+        self.y_axis.title.is_horizontal = true;
+        self.y_axis.major_gridlines = true;
+
+        self.x2_axis.axis_type = ChartAxisType::Value;
+        self.x2_axis.position_between_ticks = false;
+        self.x2_axis.crossing = ChartAxisCrossing::Max;
+        self.x2_axis.is_hidden = true;
+        self.x2_axis.label_position = ChartAxisLabelPosition::None;
+
+        self.y2_axis.axis_type = ChartAxisType::Value;
+        self.y2_axis.axis_position = ChartAxisPosition::Left;
+        self.y2_axis.position_between_ticks = false;
+
+        if self.chart_type == ChartType::Bubble || self.chart_type == ChartType::Bubble3D {
+            self.chart_group_type = ChartType::Bubble;
+        } else {
+            self.chart_group_type = ChartType::Scatter;
+        }
+
+        self.default_label_position = ChartDataLabelPosition::Right;
+
+        self
+    }
+
+    // Initialize stock charts.
+    fn initialize_stock_chart(mut self) -> Chart {
+        self.has_crosses = false;
+
+        self.x_axis.axis_type = ChartAxisType::Date;
+        self.x_axis.axis_position = ChartAxisPosition::Bottom;
+        self.x_axis.automatic = true;
+
+        self.y_axis.axis_type = ChartAxisType::Value;
+        self.y_axis.axis_position = ChartAxisPosition::Left;
+        self.y_axis.title.is_horizontal = true;
+        self.y_axis.major_gridlines = true;
+
+        self.x2_axis.axis_type = ChartAxisType::Date;
+        self.x2_axis.crossing = ChartAxisCrossing::Max;
+        self.x2_axis.is_hidden = true;
+        self.x2_axis.label_position = ChartAxisLabelPosition::None;
+        self.x2_axis.automatic = true;
+
+        self.y2_axis.axis_type = ChartAxisType::Value;
+        self.y2_axis.axis_position = ChartAxisPosition::Left;
+
+        self.chart_group_type = ChartType::Stock;
+        self.default_label_position = ChartDataLabelPosition::Right;
+
+        self
+    }
+
+    // Initialize surface and contour charts.
+    fn initialize_surface_chart(mut self) -> Chart {
+        self.x_axis.axis_type = ChartAxisType::Category;
+        self.x_axis.axis_position = ChartAxisPosition::Bottom;
+        self.x_axis.position_between_ticks = false;
+
+        self.y_axis.axis_type = ChartAxisType::Value;
+        self.y_axis.axis_position = ChartAxisPosition::Left;
+        self.y_axis.major_gridlines = true;
+
+        self.z_axis.axis_type = ChartAxisType::Category;
+        self.z_axis.axis_position = ChartAxisPosition::Bottom;
+
+        self.chart_group_type = ChartType::Surface;
+
+        // Contour charts are surface charts viewed from directly above.
+        if self.chart_type == ChartType::Contour || self.chart_type == ChartType::ContourWireframe {
+            self.x_rotation = 90;
+            self.y_rotation = 0;
+            self.perspective = 0;
+            self.z_axis.is_hidden = true;
+        }
+
+        self
+    }
+
+    // Initialize the Excel 2016 chartex charts.
+    fn initialize_chart_ex(mut self) -> Chart {
+        self.drawing_type = DrawingType::ChartEx;
+        self.y_axis.major_gridlines = true;
+
+        match self.chart_type {
+            ChartType::Histogram | ChartType::Pareto => {
+                self.gap = 0;
+            }
+            ChartType::BoxWhisker => {
+                self.gap = 100;
+            }
+            ChartType::Waterfall => {
+                self.gap = 50;
+                self.default_label_position = ChartDataLabelPosition::OutsideEnd;
+            }
+            ChartType::Funnel => {
+                self.gap = 6;
+                self.default_label_position = ChartDataLabelPosition::InsideEnd;
+            }
+            ChartType::Treemap => {
+                self.default_label_position = ChartDataLabelPosition::InsideEnd;
+            }
+            ChartType::Sunburst => {
+                self.default_label_position = ChartDataLabelPosition::Center;
+            }
+            _ => {}
+        }
+
+        self
+    }
+
+    // Write the <c:areaChart> element for Column charts.
+    fn write_area_chart(&mut self, primary_axis: bool) {
+        let series = self.get_series(primary_axis);
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:areaChart");
+
+        // Write the c:grouping element.
+        self.write_grouping();
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        if self.has_drop_lines {
+            // Write the c:dropLines element.
+            self.write_drop_lines();
+        }
+
+        // Write the c:axId elements.
+        self.write_ax_ids(primary_axis);
+
+        xml_end_tag(&mut self.writer, "c:areaChart");
+    }
+
+    // Write the <c:barChart> element for Bar charts.
+    fn write_bar_chart(&mut self, primary_axis: bool) {
+        let series = self.get_series(primary_axis);
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:barChart");
+
+        // Write the c:barDir element.
+        self.write_bar_dir("bar");
+
+        // Write the c:grouping element.
+        self.write_grouping();
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        if self.gap != 150 {
+            // Write the c:gapWidth element.
+            self.write_gap_width(self.gap);
+        }
+
+        // Write the c:overlap element.
+        self.write_overlap();
+
+        // Write the c:axId elements.
+        self.write_ax_ids(primary_axis);
+
+        xml_end_tag(&mut self.writer, "c:barChart");
+    }
+
+    // Write the <c:barChart> element for Column charts.
+    fn write_column_chart(&mut self, primary_axis: bool) {
+        let series = self.get_series(primary_axis);
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:barChart");
+
+        // Write the c:barDir element.
+        self.write_bar_dir("col");
+
+        // Write the c:grouping element.
+        self.write_grouping();
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        if self.gap != 150 {
+            // Write the c:gapWidth element.
+            self.write_gap_width(self.gap);
+        }
+
+        // Write the c:overlap element.
+        self.write_overlap();
+
+        // Write the c:axId elements.
+        self.write_ax_ids(primary_axis);
+
+        xml_end_tag(&mut self.writer, "c:barChart");
+    }
+
+    // Write the <c:doughnutChart> element for Column charts.
+    fn write_doughnut_chart(&mut self, primary_axis: bool) {
+        let series = self.get_series(primary_axis);
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:doughnutChart");
+
+        // Write the c:varyColors element.
+        self.write_vary_colors();
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        // Write the c:firstSliceAng element.
+        self.write_first_slice_ang();
+
+        // Write the c:holeSize element.
+        self.write_hole_size();
+
+        xml_end_tag(&mut self.writer, "c:doughnutChart");
+    }
+
+    // Write the <c:lineChart>element.
+    fn write_line_chart(&mut self, primary_axis: bool) {
+        let series = self.get_series(primary_axis);
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:lineChart");
+
+        // Write the c:grouping element.
+        self.write_grouping();
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        if self.has_drop_lines {
+            // Write the c:dropLines element.
+            self.write_drop_lines();
+        }
+
+        if self.has_high_low_lines {
+            // Write the c:hiLowLines element.
+            self.write_hi_low_lines();
+        }
+
+        // Write the c:upDownBars element.
+        if self.has_up_down_bars {
+            self.write_up_down_bars();
+        }
+
+        // Write the c:marker element.
+        self.write_marker_value();
+
+        // Write the c:axId elements.
+        self.write_ax_ids(primary_axis);
+
+        xml_end_tag(&mut self.writer, "c:lineChart");
+    }
+
+    // Write the <c:pieChart> element for Column charts.
+    fn write_pie_chart(&mut self, primary_axis: bool) {
+        let series = self.get_series(primary_axis);
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:pieChart");
+
+        // Write the c:varyColors element.
+        self.write_vary_colors();
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        // Write the c:firstSliceAng element.
+        self.write_first_slice_ang();
+
+        xml_end_tag(&mut self.writer, "c:pieChart");
+    }
+
+    // Write the <c:radarChart>element.
+    fn write_radar_chart(&mut self, primary_axis: bool) {
+        let series = self.get_series(primary_axis);
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:radarChart");
+
+        // Write the c:radarStyle element.
+        self.write_radar_style();
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        // Write the c:axId elements.
+        self.write_ax_ids(primary_axis);
+
+        xml_end_tag(&mut self.writer, "c:radarChart");
+    }
+
+    // Write the <c:scatterChart>element.
+    fn write_scatter_chart(&mut self, primary_axis: bool) {
+        let mut series = self.get_series(primary_axis);
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:scatterChart");
+
+        // Write the c:scatterStyle element.
+        self.write_scatter_style();
+
+        // Write the c:ser elements.
+        self.write_scatter_series(&mut series);
+
+        // Write the c:axId elements.
+        self.write_ax_ids(primary_axis);
+
+        xml_end_tag(&mut self.writer, "c:scatterChart");
+    }
+
+    // Write the <c:stockChart>element.
+    fn write_stock_chart(&mut self, primary_axis: bool) {
+        let series = self.get_series(primary_axis);
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:stockChart");
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        if self.has_drop_lines {
+            // Write the c:dropLines element.
+            self.write_drop_lines();
+        }
+
+        if primary_axis && self.has_high_low_lines {
+            // Write the c:hiLowLines element.
+            self.write_hi_low_lines();
+        }
+
+        // Write the c:upDownBars element.
+        if self.has_up_down_bars {
+            self.write_up_down_bars();
+        }
+
+        // Write the c:axId elements.
+        self.write_ax_ids(primary_axis);
+
+        xml_end_tag(&mut self.writer, "c:stockChart");
+    }
+
+    // Write the <c:area3DChart> element. 3D charts don't support secondary
+    // axes so all the series are written on the primary axis.
+    fn write_area_3d_chart(&mut self) {
+        let series = self.series.clone();
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:area3DChart");
+
+        // Write the c:grouping element.
+        self.write_grouping();
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        if self.has_drop_lines {
+            // Write the c:dropLines element.
+            self.write_drop_lines();
+        }
+
+        // Write the c:axId elements.
+        self.write_ax_ids_3d();
+
+        xml_end_tag(&mut self.writer, "c:area3DChart");
+    }
+
+    // Write the <c:bar3DChart> element for 3D Bar and Column charts.
+    fn write_bar_3d_chart(&mut self, direction: &str) {
+        let series = self.series.clone();
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:bar3DChart");
+
+        // Write the c:barDir element.
+        self.write_bar_dir(direction);
+
+        // Write the c:grouping element.
+        self.write_grouping();
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        // Write the c:gapWidth element.
+        self.write_gap_width(self.gap);
+
+        // Write the c:shape element.
+        self.write_shape();
+
+        // Write the c:axId elements.
+        self.write_ax_ids_3d();
+
+        xml_end_tag(&mut self.writer, "c:bar3DChart");
+    }
+
+    // Write the <c:line3DChart> element.
+    fn write_line_3d_chart(&mut self) {
+        let series = self.series.clone();
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:line3DChart");
+
+        // Write the c:grouping element.
+        self.write_grouping();
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        if self.has_drop_lines {
+            // Write the c:dropLines element.
+            self.write_drop_lines();
+        }
+
+        // Write the c:axId elements.
+        self.write_ax_ids_3d();
+
+        xml_end_tag(&mut self.writer, "c:line3DChart");
+    }
+
+    // Write the <c:pie3DChart> element.
+    fn write_pie_3d_chart(&mut self) {
+        let series = self.series.clone();
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:pie3DChart");
+
+        // Write the c:varyColors element.
+        self.write_vary_colors();
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        xml_end_tag(&mut self.writer, "c:pie3DChart");
+    }
+
+    // Write the <c:bubbleChart> element.
+    fn write_bubble_chart(&mut self, primary_axis: bool) {
+        let series = self.get_series(primary_axis);
+
+        if series.is_empty() {
+            return;
+        }
+
+        xml_start_tag_only(&mut self.writer, "c:bubbleChart");
+
+        // Write the c:ser elements.
+        self.write_bubble_series(&series);
+
+        // Write the c:bubbleScale element.
+        self.write_bubble_scale();
+
+        // Write the c:showNegBubbles element.
+        self.write_show_neg_bubbles();
+
+        // Write the c:axId elements.
+        self.write_ax_ids(primary_axis);
+
+        xml_end_tag(&mut self.writer, "c:bubbleChart");
+    }
+
+    // Write the <c:surface3DChart> element for Surface charts or the
+    // <c:surfaceChart> element for Contour charts.
+    fn write_surface_chart(&mut self, is_3d: bool) {
+        let series = self.series.clone();
+
+        if series.is_empty() {
+            return;
+        }
+
+        let tag = if is_3d {
+            "c:surface3DChart"
+        } else {
+            "c:surfaceChart"
+        };
+
+        xml_start_tag_only(&mut self.writer, tag);
+
+        // Write the c:wireframe element.
+        self.write_wireframe();
+
+        // Write the c:ser elements.
+        self.write_series(&series);
+
+        // Write the c:axId elements.
+        self.write_ax_ids_3d();
+
+        xml_end_tag(&mut self.writer, tag);
+    }
+
+    // -----------------------------------------------------------------------
+    // XML assembly methods.
+    // -----------------------------------------------------------------------
+
+    // Assemble and generate the XML file.
+    pub(crate) fn assemble_xml_file(&mut self) {
+        // The Excel 2016 chart types use a different file format.
+        if self.chart_type.is_chart_ex() {
+            self.assemble_chart_ex_xml_file();
+            return;
+        }
+
+        xml_declaration(&mut self.writer);
+
+        // Write the c:chartSpace element.
+        self.write_chart_space();
+
+        // Write the c:lang element.
+        self.write_lang();
+
+        // Write the c:style element.
+        if self.style != 2 {
+            self.write_style();
+        }
+
+        // Write the c:protection element.
+        if self.protection_on {
+            self.write_protection();
+        }
+
+        // Write the c:chart element.
+        self.write_chart();
+
+        // Write the c:spPr element.
+        self.write_sp_pr(&self.chart_area.format.clone());
+
+        // Write the c:printSettings element.
+        if !self.is_chartsheet {
+            self.write_print_settings();
+        }
+
+        // Close the c:chartSpace tag.
+        xml_end_tag(&mut self.writer, "c:chartSpace");
+    }
+
+    // Write the <c:chartSpace> element.
+    fn write_chart_space(&mut self) {
+        let attributes = [
+            (
+                "xmlns:c",
+                "http://schemas.openxmlformats.org/drawingml/2006/chart",
+            ),
+            (
+                "xmlns:a",
+                "http://schemas.openxmlformats.org/drawingml/2006/main",
+            ),
+            (
+                "xmlns:r",
+                "http://schemas.openxmlformats.org/officeDocument/2006/relationships",
+            ),
+        ];
+
+        xml_start_tag(&mut self.writer, "c:chartSpace", &attributes);
+    }
+
+    // Write the <c:lang> element.
+    fn write_lang(&mut self) {
+        let attributes = [("val", "en-US")];
+
+        xml_empty_tag(&mut self.writer, "c:lang", &attributes);
+    }
+
+    // Write the <c:chart> element.
+    fn write_chart(&mut self) {
+        xml_start_tag_only(&mut self.writer, "c:chart");
+
+        // Write the c:title element.
+        if self.title.hidden {
+            self.write_auto_title_deleted();
+        } else {
+            self.write_chart_title(&self.title.clone());
+        }
+
+        // Write the c:view3D element.
+        if self.chart_type.is_3d() {
+            self.write_view_3d();
+        }
+
+        // Write the c:plotArea element.
+        self.write_plot_area();
+
+        // Write the c:legend element.
+        self.write_legend();
+
+        // Write the c:plotVisOnly element.
+        if !self.show_hidden_data {
+            self.write_plot_vis_only();
+        }
+
+        // Write the c:dispBlanksAs element.
+        self.write_disp_blanks_as();
+
+        // Write the dispNaAsBlank element.
+        if self.show_na_as_empty {
+            self.write_disp_na_as_blank();
+        }
+
+        xml_end_tag(&mut self.writer, "c:chart");
+    }
+
+    // Write the <c:title> element.
+    fn write_chart_title(&mut self, title: &ChartTitle) {
+        if !title.name.is_empty() {
+            self.write_title_rich(title);
+        } else if title.range.has_data() {
+            self.write_title_formula(title);
+        } else if title.format.has_formatting() {
+            self.write_title_format_only(title);
+        }
+    }
+
+    // Write the <c:title> element.
+    fn write_series_title(&mut self, title: &ChartTitle) {
+        if !title.name.is_empty() {
+            self.write_tx_value(title);
+        } else if title.range.has_data() {
+            self.write_tx_formula(title);
+        }
+    }
+
+    // Write the <c:plotArea> element.
+    fn write_plot_area(&mut self) {
+        self.series_index = 0;
+        xml_start_tag_only(&mut self.writer, "c:plotArea");
+
+        // Write the c:layout element.
+        self.write_layout(&self.plot_area.layout.clone());
+
+        // Write the <c:xxxChart> element for each chart type.
+        self.write_chart_type();
+
+        // Write the combined chart.
+        if let Some(combined_chart) = &mut self.combined_chart {
+            combined_chart.axis_ids = self.axis_ids;
+            combined_chart.axis2_ids = self.axis2_ids;
+            combined_chart.series_index = self.series.len();
+
+            mem::swap(&mut combined_chart.writer, &mut self.writer);
+            combined_chart.write_chart_type();
+            mem::swap(&mut combined_chart.writer, &mut self.writer);
+        }
+
+        let mut x_axis = self.x_axis.clone();
+        let mut y_axis = self.y_axis.clone();
+
+        // Reverse the X and Y axes for Bar charts.
+        if self.chart_group_type == ChartType::Bar {
+            std::mem::swap(&mut x_axis, &mut y_axis);
+        }
+
+        match self.chart_group_type {
+            ChartType::Pie | ChartType::Doughnut => {}
+
+            ChartType::Scatter | ChartType::Bubble => {
+                // Write the c:valAx element.
+                self.write_cat_val_ax(&x_axis, &y_axis, self.axis_ids);
+
+                // Write the c:valAx element.
+                self.write_val_ax(&x_axis, &y_axis, self.axis_ids);
+            }
+            _ => {
+                if self.x_axis.axis_type == ChartAxisType::Date {
+                    // Write the c:dateAx element.
+                    self.write_date_ax(&x_axis, &y_axis, self.axis_ids);
+                } else {
+                    // Write the c:catAx element.
+                    self.write_cat_ax(&x_axis, &y_axis, self.axis_ids);
+                }
+
+                // Write the c:valAx element.
+                self.write_val_ax(&x_axis, &y_axis, self.axis_ids);
+            }
+        }
+
+        // Write the c:serAx element.
+        if self.chart_type.has_series_axis() {
+            self.write_ser_ax(&self.z_axis.clone());
+        }
+
+        // Handle any secondary axes due to secondary data series.
+        self.check_for_secondary_axis();
+        if self.has_secondary_axis {
+            let mut x_axis = self.x2_axis.clone();
+            let mut y_axis = self.y2_axis.clone();
+            let mut chart_group_type = self.chart_group_type;
+            let mut is_combined = false;
+
+            if let Some(combined_chart) = &self.combined_chart {
+                chart_group_type = combined_chart.chart_group_type;
+                is_combined = true;
+            }
+
+            // Reverse the X and Y axes for Bar charts.
+            if chart_group_type == ChartType::Bar {
+                std::mem::swap(&mut x_axis, &mut y_axis);
+            }
+
+            match chart_group_type {
+                ChartType::Pie | ChartType::Doughnut => {}
+
+                ChartType::Scatter | ChartType::Bubble => {
+                    // The Cat and Value axes order changes depending on type.
+                    if is_combined {
+                        self.write_val_ax(&x_axis, &y_axis, self.axis2_ids);
+                        self.write_cat_val_ax(&x_axis, &y_axis, self.axis2_ids);
+                    } else {
+                        self.write_cat_val_ax(&x_axis, &y_axis, self.axis2_ids);
+                        self.write_val_ax(&x_axis, &y_axis, self.axis2_ids);
+                    }
+                }
+                _ => {
+                    // Write the c:valAx element.
+                    self.write_val_ax(&x_axis, &y_axis, self.axis2_ids);
+
+                    if self.x_axis.axis_type == ChartAxisType::Date {
+                        // Write the c:dateAx element.
+                        self.write_date_ax(&x_axis, &y_axis, self.axis2_ids);
+                    } else {
+                        // Write the c:catAx element.
+                        self.write_cat_ax(&x_axis, &y_axis, self.axis2_ids);
+                    }
+                }
+            }
+        }
+
+        // Write the c:dTable element.
+        if let Some(table) = &self.table {
+            self.write_data_table(&table.clone());
+        }
+
+        // Write the c:spPr element.
+        self.write_sp_pr(&self.plot_area.format.clone());
+
+        xml_end_tag(&mut self.writer, "c:plotArea");
+    }
+
+    // Write the <c:xxxChart> element.
+    fn write_chart_type(&mut self) {
+        match self.chart_type {
+            ChartType::Area | ChartType::AreaStacked | ChartType::AreaPercentStacked => {
+                self.write_area_chart(true);
+                self.write_area_chart(false);
+            }
+
+            ChartType::Bar | ChartType::BarStacked | ChartType::BarPercentStacked => {
+                self.write_bar_chart(true);
+                self.write_bar_chart(false);
+            }
+
+            ChartType::Column | ChartType::ColumnStacked | ChartType::ColumnPercentStacked => {
+                self.write_column_chart(true);
+                self.write_column_chart(false);
+            }
+
+            ChartType::Doughnut => {
+                self.write_doughnut_chart(true);
+                self.write_doughnut_chart(false);
+            }
+
+            ChartType::Line | ChartType::LineStacked | ChartType::LinePercentStacked => {
+                self.write_line_chart(true);
+                self.write_line_chart(false);
+            }
+
+            ChartType::Pie => {
+                self.write_pie_chart(true);
+                self.write_pie_chart(false);
+            }
+
+            ChartType::Radar | ChartType::RadarWithMarkers | ChartType::RadarFilled => {
+                self.write_radar_chart(true);
+                self.write_radar_chart(false);
+            }
+
+            ChartType::Scatter
+            | ChartType::ScatterStraight
+            | ChartType::ScatterStraightWithMarkers
+            | ChartType::ScatterSmooth
+            | ChartType::ScatterSmoothWithMarkers => {
+                self.write_scatter_chart(true);
+                self.write_scatter_chart(false);
+            }
+
+            ChartType::Stock => {
+                self.write_stock_chart(true);
+                self.write_stock_chart(false);
+            }
+
+            ChartType::Area3D | ChartType::Area3DStacked | ChartType::Area3DPercentStacked => {
+                self.write_area_3d_chart();
+            }
+
+            ChartType::Bar3D | ChartType::Bar3DStacked | ChartType::Bar3DPercentStacked => {
+                self.write_bar_3d_chart("bar");
+            }
+
+            ChartType::Column3D
+            | ChartType::Column3DStacked
+            | ChartType::Column3DPercentStacked => {
+                self.write_bar_3d_chart("col");
+            }
+
+            ChartType::Line3D => self.write_line_3d_chart(),
+
+            ChartType::Pie3D => self.write_pie_3d_chart(),
+
+            ChartType::Bubble | ChartType::Bubble3D => {
+                self.write_bubble_chart(true);
+                self.write_bubble_chart(false);
+            }
+
+            ChartType::Surface | ChartType::SurfaceWireframe => self.write_surface_chart(true),
+
+            ChartType::Contour | ChartType::ContourWireframe => self.write_surface_chart(false),
+
+            // The Excel 2016 chart types are written in the chartex format.
+            ChartType::Histogram
+            | ChartType::Pareto
+            | ChartType::BoxWhisker
+            | ChartType::Waterfall
+            | ChartType::Funnel
+            | ChartType::Treemap
+            | ChartType::Sunburst => {}
+        }
+    }
+
+    // Write the <c:layout> element.
+    fn write_layout(&mut self, layout: &ChartLayout) {
+        if layout.is_not_default() {
+            xml_start_tag_only(&mut self.writer, "c:layout");
+
+            // Write the c:manualLayout element.
+            self.write_manual_layout(layout);
+
+            xml_end_tag(&mut self.writer, "c:layout");
+        } else {
+            xml_empty_tag_only(&mut self.writer, "c:layout");
+        }
+    }
+
+    // Write the <c:manualLayout> element.
+    fn write_manual_layout(&mut self, layout: &ChartLayout) {
+        xml_start_tag_only(&mut self.writer, "c:manualLayout");
+
+        if layout.has_inner {
+            xml_empty_tag(&mut self.writer, "c:layoutTarget", &[("val", "inner")]);
+        }
+
+        xml_empty_tag(&mut self.writer, "c:xMode", &[("val", "edge")]);
+        xml_empty_tag(&mut self.writer, "c:yMode", &[("val", "edge")]);
+
+        if let Some(value) = layout.x_offset {
+            let attributes = [("val", value.to_string())];
+            xml_empty_tag(&mut self.writer, "c:x", &attributes);
+        }
+
+        if let Some(value) = layout.y_offset {
+            let attributes = [("val", value.to_string())];
+            xml_empty_tag(&mut self.writer, "c:y", &attributes);
+        }
+
+        if layout.has_dimensions {
+            if let Some(value) = layout.width {
+                let attributes = [("val", value.to_string())];
+                xml_empty_tag(&mut self.writer, "c:w", &attributes);
+            }
+
+            if let Some(value) = layout.height {
+                let attributes = [("val", value.to_string())];
+                xml_empty_tag(&mut self.writer, "c:h", &attributes);
+            }
+        }
+
+        xml_end_tag(&mut self.writer, "c:manualLayout");
+    }
+
+    // Write the <c:barDir> element.
+    fn write_bar_dir(&mut self, direction: &str) {
+        let attributes = [("val", direction.to_string())];
+
+        xml_empty_tag(&mut self.writer, "c:barDir", &attributes);
+    }
+
+    // Write the <c:grouping> element.
+    fn write_grouping(&mut self) {
+        let attributes = [("val", self.grouping.to_string())];
+
+        xml_empty_tag(&mut self.writer, "c:grouping", &attributes);
+    }
+
+    // Write the <c:scatterStyle> element.
+    fn write_scatter_style(&mut self) {
+        let mut attributes = vec![];
+
+        if self.chart_type == ChartType::ScatterSmooth
+            || self.chart_type == ChartType::ScatterSmoothWithMarkers
+        {
+            attributes.push(("val", "smoothMarker".to_string()));
+        } else {
+            attributes.push(("val", "lineMarker".to_string()));
+        }
 
-        self
+        xml_empty_tag(&mut self.writer, "c:scatterStyle", &attributes);
     }
 
-    /// Display data on charts from hidden rows or columns.
-    ///
-    pub fn show_hidden_data(&mut self) -> &mut Chart {
-        self.show_hidden_data = true;
+    // Get the primary/secondary series for the chart.
+    fn get_series(&self, primary_axis: bool) -> Vec<ChartSeries> {
+        let mut series_copy = vec![];
 
-        self
-    }
+        for each_series in &self.series {
+            if each_series.secondary_axis != primary_axis {
+                series_copy.push(each_series.clone());
+            }
+        }
 
-    /// Set default values for the primary chart axis ids.
-    ///
-    /// This is mainly used to ensure that the primary axis ids used in testing
-    /// match the semi-randomized values in the target Excel file.
-    ///
-    /// # Parameters
-    ///
-    /// - `axis_id1`: X-axis id.
-    /// - `axis_id2`: Y-axis id.
-    ///
-    #[doc(hidden)]
-    pub fn set_axis_ids(&mut self, axis_id1: u32, axis_id2: u32) {
-        self.axis_ids = (axis_id1, axis_id2);
+        series_copy
     }
 
-    /// Set default values for the secondary chart axis ids.
-    ///
-    /// This is mainly used to ensure that the secondary axis ids used in
-    /// testing match the semi-randomized values in the target Excel file.
-    ///
-    /// # Parameters
-    ///
-    /// - `axis_id1`: X-axis id.
-    /// - `axis_id2`: Y-axis id.
-    ///
-    #[doc(hidden)]
-    pub fn set_axis2_ids(&mut self, axis_id1: u32, axis_id2: u32) {
-        self.axis2_ids = (axis_id1, axis_id2);
-    }
+    // Write the <c:ser> element.
+    fn write_series(&mut self, series: &Vec<ChartSeries>) {
+        for series in series {
+            let max_points = series.value_range.number_of_points();
 
-    // -----------------------------------------------------------------------
-    // Crate level helper methods.
-    // -----------------------------------------------------------------------
+            xml_start_tag_only(&mut self.writer, "c:ser");
 
-    // Set chart unique axis ids.
-    pub(crate) fn add_axis_ids(&mut self, chart_id: u32) {
-        if self.axis_ids.0 != 0 {
-            return;
-        }
+            // Copy a series overlap to the parent chart.
+            if series.overlap.is_some() {
+                self.overlap = series.overlap;
+            }
 
-        let axis_id = (5000 + chart_id) * 10000 + 1;
-        self.axis_ids = (axis_id, axis_id + 1);
+            // Copy a series gap to the parent chart.
+            if series.gap != 150 {
+                self.gap = series.gap;
+            }
 
-        if self.combined_chart.is_none() {
-            self.axis2_ids = (axis_id + 2, axis_id + 3);
-        } else {
-            self.axis2_ids = (axis_id + 10_000_000, axis_id + 10_000_001);
-        }
-    }
+            // Write the c:idx element.
+            self.write_idx(self.series_index);
 
-    // Check for any legend entries that have been hidden/deleted via the
-    // ChartSeries::delete_from_legend() and
-    // ChartTrendline::delete_from_legend() methods. These can in turn be
-    // overridden by the `ChartLegend::delete_entries()` method, which is
-    // checked for first.
-    fn deleted_legend_entries(&self) -> Vec<usize> {
-        // Use the user supplied entries, if available.
-        if !self.legend.deleted_entries.is_empty() {
-            return self.legend.deleted_entries.clone();
-        }
+            // Write the c:order element.
+            self.write_order(self.series_index);
 
-        let mut deleted_entries = vec![];
-        let mut index = 0;
+            self.write_series_title(&series.title);
 
-        // Check for deleted series in legend.
-        for series in &self.series {
-            if series.delete_from_legend {
-                deleted_entries.push(index);
+            // Write the c:spPr element.
+            self.write_sp_pr(&series.format);
+
+            if let Some(marker) = &series.marker {
+                if !marker.automatic {
+                    // Write the c:marker element.
+                    self.write_marker(marker);
+                }
             }
 
-            index += 1;
-        }
+            // Write the c:invertIfNegative element.
+            if series.invert_if_negative {
+                self.write_invert_if_negative();
+            }
+
+            // Write the point formatting for the series.
+            if !series.points.is_empty() {
+                self.write_d_pt(&series.points, max_points);
+            }
+
+            if let Some(data_label) = &series.data_label {
+                // Write the c:dLbls element.
+                self.write_data_labels(data_label, &series.custom_data_labels, max_points);
+            }
 
-        // Check for deleted trendlines in legend. These are indexed after the
-        // series they belong to.
-        for series in &self.series {
             if series.trendline.trend_type != ChartTrendlineType::None {
-                if series.trendline.delete_from_legend {
-                    deleted_entries.push(index);
+                // Write the c:trendline element.
+                self.write_trendline(&series.trendline);
+            }
+
+            if self.chart_group_type == ChartType::Bar {
+                if let Some(error_bars) = &series.x_error_bars {
+                    // Write the c:errBars element.
+                    self.write_error_bar("", error_bars);
+                }
+            } else if self.chart_group_type == ChartType::Column {
+                if let Some(error_bars) = &series.y_error_bars {
+                    // Write the c:errBars element.
+                    self.write_error_bar("", error_bars);
                 }
+            } else if let Some(error_bars) = &series.y_error_bars {
+                // Write the c:errBars element.
+                self.write_error_bar("y", error_bars);
+            }
 
-                index += 1;
+            // Write the c:cat element.
+            if series.category_range.has_data() {
+                // We only set a default num format for numeric categories.
+                self.category_has_num_format = matches!(
+                    series.category_range.cache.cache_type,
+                    ChartRangeCacheDataType::Number | ChartRangeCacheDataType::Date
+                );
+
+                self.write_cat(&series.category_range);
             }
-        }
 
-        deleted_entries
-    }
+            // Write the c:val element.
+            self.write_val(&series.value_range);
 
-    // Check if the primary chart or optional combined chart have a secondary
-    // data series.
-    fn check_for_secondary_axis(&mut self) {
-        if let Some(combined_chart) = &self.combined_chart {
-            for series in &combined_chart.series {
-                if series.secondary_axis {
-                    self.has_secondary_axis = true;
-                }
+            if !series.inverted_color.is_auto_or_default() {
+                // Write the c:extLst element for the inverted fill color.
+                self.write_extension_list(series.inverted_color);
             }
-        }
 
-        for series in &self.series {
-            if series.secondary_axis {
-                self.has_secondary_axis = true;
-                return;
+            // Write the c:smooth element.
+            if self.chart_group_type == ChartType::Line {
+                if let Some(smooth) = series.smooth {
+                    if smooth {
+                        self.write_smooth();
+                    }
+                }
             }
+
+            self.series_index += 1;
+
+            xml_end_tag(&mut self.writer, "c:ser");
         }
     }
 
-    // -----------------------------------------------------------------------
-    // Chart specific methods.
-    // -----------------------------------------------------------------------
-
-    // Initialize area charts.
-    fn initialize_area_chart(mut self) -> Chart {
-        self.has_crosses = false;
+    // Write the <c:ser> element for scatter charts.
+    fn write_scatter_series(&mut self, series: &mut Vec<ChartSeries>) {
+        for series in series {
+            let max_points = series.value_range.number_of_points();
 
-        self.x_axis.axis_type = ChartAxisType::Category;
-        self.x_axis.axis_position = ChartAxisPosition::Bottom;
-        self.x_axis.position_between_ticks = false;
+            xml_start_tag_only(&mut self.writer, "c:ser");
 
-        self.y_axis.axis_type = ChartAxisType::Value;
-        self.y_axis.axis_position = ChartAxisPosition::Left;
-        self.y_axis.title.is_horizontal = true;
-        self.y_axis.major_gridlines = true;
+            // Write the c:idx element.
+            self.write_idx(self.series_index);
 
-        self.x2_axis.axis_type = ChartAxisType::Category;
-        self.x2_axis.position_between_ticks = false;
-        self.x2_axis.crossing = ChartAxisCrossing::Max;
-        self.x2_axis.is_hidden = true;
-        self.x2_axis.label_position = ChartAxisLabelPosition::None;
+            // Write the c:order element.
+            self.write_order(self.series_index);
 
-        self.y2_axis.axis_type = ChartAxisType::Value;
-        self.y2_axis.axis_position = ChartAxisPosition::Left;
+            self.write_series_title(&series.title);
 
-        self.chart_group_type = ChartType::Area;
+            if let Some(marker) = &series.marker {
+                if !marker.automatic {
+                    // Write the c:marker element.
+                    self.write_marker(marker);
+                }
+            }
 
-        if self.chart_type == ChartType::Area {
-            self.grouping = ChartGrouping::Standard;
-        } else if self.chart_type == ChartType::AreaStacked {
-            self.grouping = ChartGrouping::Stacked;
-        } else if self.chart_type == ChartType::AreaPercentStacked {
-            self.grouping = ChartGrouping::PercentStacked;
-            self.default_num_format = "0%".to_string();
-        }
+            // Add default scatter line formatting to the series data unless it
+            // has already been specified by the user.
+            if self.chart_type == ChartType::Scatter && series.format.line.is_none() {
+                let mut line = ChartLine::new();
+                line.set_width(2.25);
+                series.format.line = Some(line);
+            }
 
-        self.default_label_position = ChartDataLabelPosition::Center;
+            // Write the c:spPr formatting element.
+            self.write_sp_pr(&series.format);
 
-        self
-    }
+            // Write the point formatting for the series.
+            if !series.points.is_empty() {
+                self.write_d_pt(&series.points, max_points);
+            }
 
-    // Initialize bar charts. Bar chart category/value axes are reversed in
-    // comparison to other charts. Some of the defaults reflect this.
-    fn initialize_bar_chart(mut self) -> Chart {
-        self.has_crosses = false;
+            // Write the c:dLbls element.
+            if let Some(data_label) = &series.data_label {
+                self.write_data_labels(data_label, &series.custom_data_labels, max_points);
+            }
 
-        self.x_axis.axis_type = ChartAxisType::Value;
-        self.x_axis.axis_position = ChartAxisPosition::Bottom;
-        self.x_axis.major_gridlines = true;
+            // Write the c:trendline element.
+            if series.trendline.trend_type != ChartTrendlineType::None {
+                self.write_trendline(&series.trendline);
+            }
 
-        self.y_axis.axis_type = ChartAxisType::Category;
-        self.y_axis.axis_position = ChartAxisPosition::Left;
-        self.y_axis.title.is_horizontal = true;
+            // Write the X-Axis c:errBars element.
+            if let Some(error_bars) = &series.x_error_bars {
+                self.write_error_bar("x", error_bars);
+            }
 
-        self.x2_axis.axis_type = ChartAxisType::Category;
-        self.x2_axis.axis_position = ChartAxisPosition::Bottom;
-        self.x2_axis.crossing = ChartAxisCrossing::Automatic;
+            // Write the Y-Axis the c:errBars element.
+            if let Some(error_bars) = &series.y_error_bars {
+                self.write_error_bar("y", error_bars);
+            }
 
-        self.y2_axis.axis_type = ChartAxisType::Value;
-        self.y2_axis.axis_position = ChartAxisPosition::Left;
-        self.y2_axis.crossing = ChartAxisCrossing::Max;
-        self.y2_axis.is_hidden = true;
-        self.y2_axis.label_position = ChartAxisLabelPosition::None;
+            self.write_x_val(&series.category_range);
 
-        self.chart_group_type = ChartType::Bar;
+            self.write_y_val(&series.value_range);
 
-        if self.chart_type == ChartType::Bar {
-            self.grouping = ChartGrouping::Clustered;
-        } else if self.chart_type == ChartType::BarStacked {
-            self.grouping = ChartGrouping::Stacked;
-            self.overlap = Some(100);
-        } else if self.chart_type == ChartType::BarPercentStacked {
-            self.grouping = ChartGrouping::PercentStacked;
-            self.default_num_format = "0%".to_string();
-            self.overlap = Some(100);
-        }
+            // Write the c:smooth element.
+            if self.chart_group_type == ChartType::Scatter {
+                if let Some(smooth) = series.smooth {
+                    if smooth {
+                        self.write_smooth();
+                    }
+                } else if self.chart_type == ChartType::ScatterSmooth
+                    || self.chart_type == ChartType::ScatterSmoothWithMarkers
+                {
+                    // The ScatterSmooth charts have a default smooth element if
+                    // one hasn't been set/unset by the user.
+                    self.write_smooth();
+                }
+            }
 
-        self.default_label_position = ChartDataLabelPosition::OutsideEnd;
+            self.series_index += 1;
 
-        self
+            xml_end_tag(&mut self.writer, "c:ser");
+        }
     }
 
-    // Initialize column charts.
-    fn initialize_column_chart(mut self) -> Chart {
-        self.x_axis.axis_type = ChartAxisType::Category;
-        self.x_axis.axis_position = ChartAxisPosition::Bottom;
-
-        self.y_axis.axis_type = ChartAxisType::Value;
-        self.y_axis.axis_position = ChartAxisPosition::Left;
-        self.y_axis.major_gridlines = true;
+    // Write the <c:ser> element for bubble charts.
+    fn write_bubble_series(&mut self, series: &Vec<ChartSeries>) {
+        for series in series {
+            let max_points = series.value_range.number_of_points();
 
-        self.x2_axis.axis_type = ChartAxisType::Category;
-        self.x2_axis.crossing = ChartAxisCrossing::Max;
-        self.x2_axis.is_hidden = true;
-        self.x2_axis.label_position = ChartAxisLabelPosition::None;
+            xml_start_tag_only(&mut self.writer, "c:ser");
 
-        self.y2_axis.axis_type = ChartAxisType::Value;
-        self.y2_axis.axis_position = ChartAxisPosition::Left;
+            // Write the c:idx element.
+            self.write_idx(self.series_index);
 
-        self.chart_group_type = ChartType::Column;
+            // Write the c:order element.
+            self.write_order(self.series_index);
 
-        if self.chart_type == ChartType::Column {
-            self.grouping = ChartGrouping::Clustered;
-        } else if self.chart_type == ChartType::ColumnStacked {
-            self.grouping = ChartGrouping::Stacked;
-            self.overlap = Some(100);
-        } else if self.chart_type == ChartType::ColumnPercentStacked {
-            self.grouping = ChartGrouping::PercentStacked;
-            self.default_num_format = "0%".to_string();
-            self.overlap = Some(100);
-        }
+            self.write_series_title(&series.title);
 
-        self.default_label_position = ChartDataLabelPosition::OutsideEnd;
+            // Write the c:spPr formatting element.
+            self.write_sp_pr(&series.format);
 
-        self
-    }
+            // Write the c:invertIfNegative element.
+            if series.invert_if_negative {
+                self.write_invert_if_negative();
+            }
 
-    // Initialize doughnut charts.
-    fn initialize_doughnut_chart(mut self) -> Chart {
-        self.chart_group_type = ChartType::Doughnut;
+            // Write the point formatting for the series.
+            if !series.points.is_empty() {
+                self.write_d_pt(&series.points, max_points);
+            }
 
-        self.default_label_position = ChartDataLabelPosition::BestFit;
+            // Write the c:dLbls element.
+            if let Some(data_label) = &series.data_label {
+                self.write_data_labels(data_label, &series.custom_data_labels, max_points);
+            }
 
-        self
-    }
+            // Write the c:trendline element.
+            if series.trendline.trend_type != ChartTrendlineType::None {
+                self.write_trendline(&series.trendline);
+            }
 
-    // Initialize line charts.
-    fn initialize_line_chart(mut self) -> Chart {
-        self.x_axis.axis_type = ChartAxisType::Category;
-        self.x_axis.axis_position = ChartAxisPosition::Bottom;
+            // Write the X-Axis c:errBars element.
+            if let Some(error_bars) = &series.x_error_bars {
+                self.write_error_bar("x", error_bars);
+            }
 
-        self.y_axis.axis_type = ChartAxisType::Value;
-        self.y_axis.axis_position = ChartAxisPosition::Left;
-        self.y_axis.title.is_horizontal = true;
-        self.y_axis.major_gridlines = true;
+            // Write the Y-Axis the c:errBars element.
+            if let Some(error_bars) = &series.y_error_bars {
+                self.write_error_bar("y", error_bars);
+            }
 
-        self.x2_axis.axis_type = ChartAxisType::Category;
-        self.x2_axis.crossing = ChartAxisCrossing::Max;
-        self.x2_axis.is_hidden = true;
-        self.x2_axis.label_position = ChartAxisLabelPosition::None;
+            self.write_x_val(&series.category_range);
 
-        self.y2_axis.axis_type = ChartAxisType::Value;
-        self.y2_axis.axis_position = ChartAxisPosition::Left;
+            self.write_y_val(&series.value_range);
 
-        self.chart_group_type = ChartType::Line;
+            self.write_bubble_size(&series.bubble_size_range);
 
-        if self.chart_type == ChartType::Line {
-            self.grouping = ChartGrouping::Standard;
-        } else if self.chart_type == ChartType::LineStacked {
-            self.grouping = ChartGrouping::Stacked;
-        } else if self.chart_type == ChartType::LinePercentStacked {
-            self.grouping = ChartGrouping::PercentStacked;
-            self.default_num_format = "0%".to_string();
-        }
+            // Write the c:bubble3D element.
+            if self.chart_type == ChartType::Bubble3D {
+                self.write_bubble_3d();
+            }
 
-        self.default_label_position = ChartDataLabelPosition::Right;
+            self.series_index += 1;
 
-        self
+            xml_end_tag(&mut self.writer, "c:ser");
+        }
     }
 
-    // Initialize pie charts.
-    fn initialize_pie_chart(mut self) -> Chart {
-        self.chart_group_type = ChartType::Pie;
-
-        self.default_label_position = ChartDataLabelPosition::BestFit;
+    // Write the <c:dPt> element.
+    fn write_d_pt(&mut self, points: &[ChartPoint], max_points: usize) {
+        let has_marker =
+            self.chart_group_type == ChartType::Scatter || self.chart_group_type == ChartType::Line;
 
-        self
-    }
+        // Write the point formatting for the series.
+        for (index, point) in points.iter().enumerate() {
+            if index >= max_points {
+                break;
+            }
 
-    // Initialize radar charts.
-    fn initialize_radar_chart(mut self) -> Chart {
-        self.x_axis.axis_type = ChartAxisType::Category;
-        self.x_axis.axis_position = ChartAxisPosition::Bottom;
-        self.x_axis.major_gridlines = true;
+            if point.is_not_default() {
+                xml_start_tag_only(&mut self.writer, "c:dPt");
+                self.write_idx(index);
 
-        self.y_axis.axis_type = ChartAxisType::Value;
-        self.y_axis.axis_position = ChartAxisPosition::Left;
-        self.y_axis.major_gridlines = true;
-        self.y_axis.major_tick_type = Some(ChartAxisTickType::Cross);
+                if has_marker {
+                    xml_start_tag_only(&mut self.writer, "c:marker");
+                }
 
-        self.chart_group_type = ChartType::Radar;
+                // Write the c:spPr formatting element.
+                self.write_sp_pr(&point.format);
 
-        self.default_label_position = ChartDataLabelPosition::Center;
+                if has_marker {
+                    xml_end_tag(&mut self.writer, "c:marker");
+                }
 
-        self
+                xml_end_tag(&mut self.writer, "c:dPt");
+            }
+        }
     }
 
-    // Initialize scatter charts.
-    fn initialize_scatter_chart(mut self) -> Chart {
-        self.x_axis.axis_type = ChartAxisType::Value;
-        self.x_axis.axis_position = ChartAxisPosition::Bottom;
-        self.x_axis.position_between_ticks = false;
-
-        self.y_axis.axis_type = ChartAxisType::Value;
-        self.y_axis.axis_position = ChartAxisPosition::Left;
-        self.y_axis.position_between_ticks = false;
-        self.y_axis.title.is_horizontal = true;
-        self.y_axis.major_gridlines = true;
-
-        self.x2_axis.axis_type = ChartAxisType::Value;
-        self.x2_axis.position_between_ticks = false;
-        self.x2_axis.crossing = ChartAxisCrossing::Max;
-        self.x2_axis.is_hidden = true;
-        self.x2_axis.label_position = ChartAxisLabelPosition::None;
-
-        self.y2_axis.axis_type = ChartAxisType::Value;
-        self.y2_axis.axis_position = ChartAxisPosition::Left;
-        self.y2_axis.position_between_ticks = false;
+    // Write the <c:idx> element.
+    fn write_idx(&mut self, index: usize) {
+        let attributes = [("val", index.to_string())];
 
-        self.chart_group_type = ChartType::Scatter;
+        xml_empty_tag(&mut self.writer, "c:idx", &attributes);
+    }
 
-        self.default_label_position = ChartDataLabelPosition::Right;
+    // Write the <c:order> element.
+    fn write_order(&mut self, index: usize) {
+        let attributes = [("val", index.to_string())];
 
-        self
+        xml_empty_tag(&mut self.writer, "c:order", &attributes);
     }
 
-    // Initialize stock charts.
-    fn initialize_stock_chart(mut self) -> Chart {
-        self.has_crosses = false;
-
-        self.x_axis.axis_type = ChartAxisType::Date;
-        self.x_axis.axis_position = ChartAxisPosition::Bottom;
-        self.x_axis.automatic = true;
+    // Write the <c:invertIfNegative> element.
+    fn write_invert_if_negative(&mut self) {
+        let attributes = [("val", "1")];
 
-        self.y_axis.axis_type = ChartAxisType::Value;
-        self.y_axis.axis_position = ChartAxisPosition::Left;
-        self.y_axis.title.is_horizontal = true;
-        self.y_axis.major_gridlines = true;
+        xml_empty_tag(&mut self.writer, "c:invertIfNegative", &attributes);
+    }
 
-        self.x2_axis.axis_type = ChartAxisType::Date;
-        self.x2_axis.crossing = ChartAxisCrossing::Max;
-        self.x2_axis.is_hidden = true;
-        self.x2_axis.label_position = ChartAxisLabelPosition::None;
-        self.x2_axis.automatic = true;
+    // Write the <c:extLst> element for inverted fill colors.
+    fn write_extension_list(&mut self, color: Color) {
+        let attributes1 = [
+            ("uri", "{6F2FDCE9-48DA-4B69-8628-5D25D57E5C99}"),
+            (
+                "xmlns:c14",
+                "http://schemas.microsoft.com/office/drawing/2007/8/2/chart",
+            ),
+        ];
+        let attributes2 = [(
+            "xmlns:c14",
+            "http://schemas.microsoft.com/office/drawing/2007/8/2/chart",
+        )];
 
-        self.y2_axis.axis_type = ChartAxisType::Value;
-        self.y2_axis.axis_position = ChartAxisPosition::Left;
+        xml_start_tag_only(&mut self.writer, "c:extLst");
+        xml_start_tag(&mut self.writer, "c:ext", &attributes1);
+        xml_start_tag_only(&mut self.writer, "c14:invertSolidFillFmt");
+        xml_start_tag(&mut self.writer, "c14:spPr", &attributes2);
 
-        self.chart_group_type = ChartType::Stock;
-        self.default_label_position = ChartDataLabelPosition::Right;
+        self.write_a_solid_fill(color, 0);
 
-        self
+        xml_end_tag(&mut self.writer, "c14:spPr");
+        xml_end_tag(&mut self.writer, "c14:invertSolidFillFmt");
+        xml_end_tag(&mut self.writer, "c:ext");
+        xml_end_tag(&mut self.writer, "c:extLst");
     }
 
-    // Write the <c:areaChart> element for Column charts.
-    fn write_area_chart(&mut self, primary_axis: bool) {
-        let series = self.get_series(primary_axis);
+    // Write the <c:cat> element.
+    fn write_cat(&mut self, range: &ChartRange) {
+        xml_start_tag_only(&mut self.writer, "c:cat");
 
-        if series.is_empty() {
-            return;
-        }
+        self.write_cache_ref(range, false);
 
-        xml_start_tag_only(&mut self.writer, "c:areaChart");
+        xml_end_tag(&mut self.writer, "c:cat");
+    }
 
-        // Write the c:grouping element.
-        self.write_grouping();
+    // Write the <c:val> element.
+    fn write_val(&mut self, range: &ChartRange) {
+        xml_start_tag_only(&mut self.writer, "c:val");
 
-        // Write the c:ser elements.
-        self.write_series(&series);
+        self.write_cache_ref(range, true);
 
-        if self.has_drop_lines {
-            // Write the c:dropLines element.
-            self.write_drop_lines();
-        }
+        xml_end_tag(&mut self.writer, "c:val");
+    }
 
-        // Write the c:axId elements.
-        self.write_ax_ids(primary_axis);
+    // Write the <c:xVal> element for scatter charts.
+    fn write_x_val(&mut self, range: &ChartRange) {
+        xml_start_tag_only(&mut self.writer, "c:xVal");
 
-        xml_end_tag(&mut self.writer, "c:areaChart");
+        self.write_cache_ref(range, false);
+
+        xml_end_tag(&mut self.writer, "c:xVal");
     }
 
-    // Write the <c:barChart> element for Bar charts.
-    fn write_bar_chart(&mut self, primary_axis: bool) {
-        let series = self.get_series(primary_axis);
+    // Write the <c:yVal> element for scatter charts.
+    fn write_y_val(&mut self, range: &ChartRange) {
+        xml_start_tag_only(&mut self.writer, "c:yVal");
 
-        if series.is_empty() {
-            return;
-        }
+        self.write_cache_ref(range, true);
 
-        xml_start_tag_only(&mut self.writer, "c:barChart");
+        xml_end_tag(&mut self.writer, "c:yVal");
+    }
 
-        // Write the c:barDir element.
-        self.write_bar_dir("bar");
+    // Write the <c:bubbleSize> element for bubble charts.
+    fn write_bubble_size(&mut self, range: &ChartRange) {
+        xml_start_tag_only(&mut self.writer, "c:bubbleSize");
 
-        // Write the c:grouping element.
-        self.write_grouping();
+        self.write_cache_ref(range, true);
 
-        // Write the c:ser elements.
-        self.write_series(&series);
+        xml_end_tag(&mut self.writer, "c:bubbleSize");
+    }
 
-        if self.gap != 150 {
-            // Write the c:gapWidth element.
-            self.write_gap_width(self.gap);
+    // Write the <c:numRef> or <c:strRef> or <c:multiLvlStrRef> elements. Value
+    // range must be written as a numRef where strings are treated as zero.
+    fn write_cache_ref(&mut self, range: &ChartRange, is_num_only: bool) {
+        if range.cache.cache_type == ChartRangeCacheDataType::String && !is_num_only {
+            self.write_str_ref(range);
+        } else if range.cache.cache_type == ChartRangeCacheDataType::MultiLevelString {
+            self.write_multi_level_str_ref(range);
+        } else {
+            self.write_num_ref(range);
         }
+    }
 
-        // Write the c:overlap element.
-        self.write_overlap();
+    // Write the <c:numRef> element.
+    fn write_num_ref(&mut self, range: &ChartRange) {
+        xml_start_tag_only(&mut self.writer, "c:numRef");
 
-        // Write the c:axId elements.
-        self.write_ax_ids(primary_axis);
+        // Write the c:f element.
+        self.write_range_formula(&range.formula_string());
 
-        xml_end_tag(&mut self.writer, "c:barChart");
-    }
+        // Write the c:numCache element.
+        if range.cache.has_data() {
+            self.write_num_cache(&range.cache);
+        }
 
-    // Write the <c:barChart> element for Column charts.
-    fn write_column_chart(&mut self, primary_axis: bool) {
-        let series = self.get_series(primary_axis);
+        xml_end_tag(&mut self.writer, "c:numRef");
+    }
 
-        if series.is_empty() {
-            return;
-        }
+    // Write the <c:strRef> element.
+    fn write_str_ref(&mut self, range: &ChartRange) {
+        xml_start_tag_only(&mut self.writer, "c:strRef");
 
-        xml_start_tag_only(&mut self.writer, "c:barChart");
+        // Write the c:f element.
+        self.write_range_formula(&range.formula_string());
 
-        // Write the c:barDir element.
-        self.write_bar_dir("col");
+        // Write the c:strCache element.
+        if range.cache.has_data() {
+            self.write_str_cache(&range.cache);
+        }
 
-        // Write the c:grouping element.
-        self.write_grouping();
+        xml_end_tag(&mut self.writer, "c:strRef");
+    }
 
-        // Write the c:ser elements.
-        self.write_series(&series);
+    // Write the <c:numCache> element.
+    fn write_num_cache(&mut self, cache: &ChartRangeCacheData) {
+        xml_start_tag_only(&mut self.writer, "c:numCache");
 
-        if self.gap != 150 {
-            // Write the c:gapWidth element.
-            self.write_gap_width(self.gap);
+        // Write the c:formatCode element.
+        if cache.cache_type == ChartRangeCacheDataType::Date {
+            self.write_format_code("dd/mm/yyyy");
+        } else {
+            self.write_format_code("General");
         }
 
-        // Write the c:overlap element.
-        self.write_overlap();
+        // Write the c:ptCount element.
+        self.write_pt_count(cache.data.len());
 
-        // Write the c:axId elements.
-        self.write_ax_ids(primary_axis);
+        // Write the c:pt elements.
+        for (index, value) in cache.data.iter().enumerate() {
+            if !value.is_empty() {
+                // Non numeric values in value/number caches are treated as zero
+                // by Excel.
+                if value.parse::<f64>().is_err() {
+                    self.write_pt(index, "0");
+                } else {
+                    self.write_pt(index, value);
+                }
+            }
+        }
 
-        xml_end_tag(&mut self.writer, "c:barChart");
+        xml_end_tag(&mut self.writer, "c:numCache");
     }
 
-    // Write the <c:doughnutChart> element for Column charts.
-    fn write_doughnut_chart(&mut self, primary_axis: bool) {
-        let series = self.get_series(primary_axis);
+    // Write the <c:multiLvlStrRef> element.
+    fn write_multi_level_str_ref(&mut self, range: &ChartRange) {
+        xml_start_tag_only(&mut self.writer, "c:multiLvlStrRef");
 
-        if series.is_empty() {
-            return;
-        }
+        // Write the c:f element.
+        self.write_range_formula(&range.formula_string());
 
-        xml_start_tag_only(&mut self.writer, "c:doughnutChart");
+        // Write the c:strCache element.
+        if range.cache.has_data() {
+            self.write_multi_level_str_cache(&range.cache);
+        }
 
-        // Write the c:varyColors element.
-        self.write_vary_colors();
+        xml_end_tag(&mut self.writer, "c:multiLvlStrRef");
+    }
 
-        // Write the c:ser elements.
-        self.write_series(&series);
+    // Write the <c:strCache> element.
+    fn write_str_cache(&mut self, cache: &ChartRangeCacheData) {
+        xml_start_tag_only(&mut self.writer, "c:strCache");
 
-        // Write the c:firstSliceAng element.
-        self.write_first_slice_ang();
+        // Write the c:ptCount element.
+        self.write_pt_count(cache.data.len());
 
-        // Write the c:holeSize element.
-        self.write_hole_size();
+        // Write the c:pt elements.
+        for (index, value) in cache.data.iter().enumerate() {
+            self.write_pt(index, value);
+        }
 
-        xml_end_tag(&mut self.writer, "c:doughnutChart");
+        xml_end_tag(&mut self.writer, "c:strCache");
     }
 
-    // Write the <c:lineChart>element.
-    fn write_line_chart(&mut self, primary_axis: bool) {
-        let series = self.get_series(primary_axis);
+    // Write the <c:multiLvlStrCache> element.
+    fn write_multi_level_str_cache(&mut self, cache: &ChartRangeCacheData) {
+        xml_start_tag_only(&mut self.writer, "c:multiLvlStrCache");
 
-        if series.is_empty() {
-            return;
-        }
+        // Write the c:ptCount element.
+        self.write_pt_count(cache.major_dim);
 
-        xml_start_tag_only(&mut self.writer, "c:lineChart");
+        // Split the cache data into levels, in reverse order.
+        for depth in (0..cache.minor_dim).rev() {
+            xml_start_tag_only(&mut self.writer, "c:lvl");
 
-        // Write the c:grouping element.
-        self.write_grouping();
+            for index in 0..cache.major_dim {
+                let offset = depth + cache.minor_dim * index;
+                let value = &cache.data[offset];
 
-        // Write the c:ser elements.
-        self.write_series(&series);
+                if !value.is_empty() {
+                    self.write_pt(index, value);
+                }
+            }
 
-        if self.has_drop_lines {
-            // Write the c:dropLines element.
-            self.write_drop_lines();
+            xml_end_tag(&mut self.writer, "c:lvl");
         }
 
-        if self.has_high_low_lines {
-            // Write the c:hiLowLines element.
-            self.write_hi_low_lines();
-        }
+        xml_end_tag(&mut self.writer, "c:multiLvlStrCache");
+    }
 
-        // Write the c:upDownBars element.
-        if self.has_up_down_bars {
-            self.write_up_down_bars();
-        }
+    // Write the <c:f> element.
+    fn write_range_formula(&mut self, formula: &str) {
+        xml_data_element_only(&mut self.writer, "c:f", formula);
+    }
 
-        // Write the c:marker element.
-        self.write_marker_value();
+    // Write the <c:formatCode> element.
+    fn write_format_code(&mut self, format_code: &str) {
+        xml_data_element_only(&mut self.writer, "c:formatCode", format_code);
+    }
 
-        // Write the c:axId elements.
-        self.write_ax_ids(primary_axis);
+    // Write the <c:ptCount> element.
+    fn write_pt_count(&mut self, count: usize) {
+        let attributes = [("val", count.to_string())];
 
-        xml_end_tag(&mut self.writer, "c:lineChart");
+        xml_empty_tag(&mut self.writer, "c:ptCount", &attributes);
+    }
+
+    // Write the <c:pt> element.
+    fn write_pt(&mut self, index: usize, value: &str) {
+        let attributes = [("idx", index.to_string())];
+
+        xml_start_tag(&mut self.writer, "c:pt", &attributes);
+        xml_data_element_only(&mut self.writer, "c:v", value);
+        xml_end_tag(&mut self.writer, "c:pt");
+    }
+
+    // Write both <c:axId> elements.
+    fn write_ax_ids(&mut self, primary_axis: bool) {
+        if primary_axis {
+            self.write_ax_id(self.axis_ids.0);
+            self.write_ax_id(self.axis_ids.1);
+        } else {
+            self.write_ax_id(self.axis2_ids.0);
+            self.write_ax_id(self.axis2_ids.1);
+        }
     }
 
-    // Write the <c:pieChart> element for Column charts.
-    fn write_pie_chart(&mut self, primary_axis: bool) {
-        let series = self.get_series(primary_axis);
+    // Write the <c:axId> elements for 3D charts. These don't have secondary
+    // axes but they may have a third series axis. If not, Excel writes a
+    // third axis id of 0 for some chart types.
+    fn write_ax_ids_3d(&mut self) {
+        self.write_ax_id(self.axis_ids.0);
+        self.write_ax_id(self.axis_ids.1);
 
-        if series.is_empty() {
-            return;
+        if self.chart_type.has_series_axis() {
+            self.write_ax_id(self.axis_ids.0 + 4);
+        } else {
+            self.write_ax_id(0);
         }
+    }
 
-        xml_start_tag_only(&mut self.writer, "c:pieChart");
-
-        // Write the c:varyColors element.
-        self.write_vary_colors();
-
-        // Write the c:ser elements.
-        self.write_series(&series);
-
-        // Write the c:firstSliceAng element.
-        self.write_first_slice_ang();
+    // Write the <c:axId> element.
+    fn write_ax_id(&mut self, axis_id: u32) {
+        let attributes = [("val", axis_id.to_string())];
 
-        xml_end_tag(&mut self.writer, "c:pieChart");
+        xml_empty_tag(&mut self.writer, "c:axId", &attributes);
     }
 
-    // Write the <c:radarChart>element.
-    fn write_radar_chart(&mut self, primary_axis: bool) {
-        let series = self.get_series(primary_axis);
+    // -----------------------------------------------------------------------
+    // Category Axis.
+    // -----------------------------------------------------------------------
 
-        if series.is_empty() {
-            return;
-        }
+    // Write the <c:catAx> element.
+    fn write_cat_ax(&mut self, x_axis: &ChartAxis, y_axis: &ChartAxis, axis_ids: (u32, u32)) {
+        xml_start_tag_only(&mut self.writer, "c:catAx");
 
-        xml_start_tag_only(&mut self.writer, "c:radarChart");
+        self.write_ax_id(axis_ids.0);
 
-        // Write the c:radarStyle element.
-        self.write_radar_style();
+        // Write the c:scaling element.
+        self.write_scaling(x_axis);
 
-        // Write the c:ser elements.
-        self.write_series(&series);
+        if x_axis.is_hidden {
+            self.write_delete();
+        }
 
-        // Write the c:axId elements.
-        self.write_ax_ids(primary_axis);
+        // Write the c:axPos element.
+        self.write_ax_pos(x_axis.axis_position, y_axis.reverse, y_axis.crossing);
 
-        xml_end_tag(&mut self.writer, "c:radarChart");
-    }
+        self.write_major_gridlines(x_axis);
+        self.write_minor_gridlines(x_axis);
 
-    // Write the <c:scatterChart>element.
-    fn write_scatter_chart(&mut self, primary_axis: bool) {
-        let mut series = self.get_series(primary_axis);
+        // Write the c:title element.
+        self.write_chart_title(&x_axis.title);
 
-        if series.is_empty() {
-            return;
+        // Write the c:numFmt element.
+        if !x_axis.num_format.is_empty() {
+            self.write_number_format(&x_axis.num_format, x_axis.num_format_linked_to_source);
+        } else if self.category_has_num_format {
+            self.write_number_format("General", true);
         }
 
-        xml_start_tag_only(&mut self.writer, "c:scatterChart");
+        // Write the c:majorTickMark element.
+        if let Some(tick_type) = x_axis.major_tick_type {
+            self.write_major_tick_mark(tick_type);
+        }
 
-        // Write the c:scatterStyle element.
-        self.write_scatter_style();
+        // Write the c:minorTickMark element.
+        if let Some(tick_type) = x_axis.minor_tick_type {
+            self.write_minor_tick_mark(tick_type);
+        }
 
-        // Write the c:ser elements.
-        self.write_scatter_series(&mut series);
+        // Write the c:tickLblPos element.
+        self.write_tick_label_position(x_axis.label_position);
 
-        // Write the c:axId elements.
-        self.write_ax_ids(primary_axis);
+        if x_axis.format.has_formatting() {
+            // Write the c:spPr formatting element.
+            self.write_sp_pr(&x_axis.format);
+        }
 
-        xml_end_tag(&mut self.writer, "c:scatterChart");
-    }
+        // Write the axis font elements.
+        if let Some(font) = &x_axis.font {
+            self.write_axis_font(font);
+        }
 
-    // Write the <c:stockChart>element.
-    fn write_stock_chart(&mut self, primary_axis: bool) {
-        let series = self.get_series(primary_axis);
+        // Write the c:crossAx element.
+        self.write_cross_ax(axis_ids.1);
 
-        if series.is_empty() {
-            return;
+        // Write the c:crosses element. Note, the X crossing comes from the Y axis.
+        if self.has_crosses || !x_axis.is_hidden {
+            match y_axis.crossing {
+                ChartAxisCrossing::Automatic | ChartAxisCrossing::Min | ChartAxisCrossing::Max => {
+                    self.write_crosses(&y_axis.crossing.to_string());
+                }
+                ChartAxisCrossing::AxisValue(_) => {
+                    self.write_crosses_at(&y_axis.crossing.to_string());
+                }
+                ChartAxisCrossing::CategoryNumber(_) => {
+                    // Ignore Category crossing on a Value axis.
+                    self.write_crosses(&ChartAxisCrossing::Automatic.to_string());
+                }
+            }
         }
 
-        xml_start_tag_only(&mut self.writer, "c:stockChart");
+        // Write the c:auto element.
+        if !x_axis.automatic {
+            self.write_auto();
+        }
 
-        // Write the c:ser elements.
-        self.write_series(&series);
+        // Write the c:lblAlgn element.
+        self.write_lbl_algn(&x_axis.label_alignment.to_string());
 
-        if self.has_drop_lines {
-            // Write the c:dropLines element.
-            self.write_drop_lines();
-        }
+        // Write the c:lblOffset element.
+        self.write_lbl_offset();
 
-        if primary_axis && self.has_high_low_lines {
-            // Write the c:hiLowLines element.
-            self.write_hi_low_lines();
+        // Write the c:tickLblSkip element.
+        if x_axis.label_interval > 1 {
+            self.write_tick_lbl_skip(x_axis.label_interval);
         }
 
-        // Write the c:upDownBars element.
-        if self.has_up_down_bars {
-            self.write_up_down_bars();
+        // Write the c:tickMarkSkip element.
+        if x_axis.tick_interval > 1 {
+            self.write_tick_mark_skip(x_axis.tick_interval);
         }
 
-        // Write the c:axId elements.
-        self.write_ax_ids(primary_axis);
-
-        xml_end_tag(&mut self.writer, "c:stockChart");
+        xml_end_tag(&mut self.writer, "c:catAx");
     }
 
     // -----------------------------------------------------------------------
-    // XML assembly methods.
+    // Date Axis.
     // -----------------------------------------------------------------------
 
-    // Assemble and generate the XML file.
-    pub(crate) fn assemble_xml_file(&mut self) {
-        xml_declaration(&mut self.writer);
+    // Write the <c:dateAx> element.
+    fn write_date_ax(&mut self, x_axis: &ChartAxis, y_axis: &ChartAxis, axis_ids: (u32, u32)) {
+        xml_start_tag_only(&mut self.writer, "c:dateAx");
 
-        // Write the c:chartSpace element.
-        self.write_chart_space();
+        self.write_ax_id(axis_ids.0);
 
-        // Write the c:lang element.
-        self.write_lang();
+        // Write the c:scaling element.
+        self.write_scaling(x_axis);
 
-        // Write the c:style element.
-        if self.style != 2 {
-            self.write_style();
+        if x_axis.is_hidden {
+            self.write_delete();
         }
 
-        // Write the c:protection element.
-        if self.protection_on {
-            self.write_protection();
-        }
+        // Write the c:axPos element.
+        self.write_ax_pos(x_axis.axis_position, y_axis.reverse, y_axis.crossing);
 
-        // Write the c:chart element.
-        self.write_chart();
+        self.write_major_gridlines(x_axis);
+        self.write_minor_gridlines(x_axis);
 
-        // Write the c:spPr element.
-        self.write_sp_pr(&self.chart_area.format.clone());
+        // Write the c:title element.
+        self.write_chart_title(&x_axis.title);
 
-        // Write the c:printSettings element.
-        if !self.is_chartsheet {
-            self.write_print_settings();
+        // Write the c:numFmt element.
+        if !x_axis.num_format.is_empty() {
+            self.write_number_format(&x_axis.num_format, x_axis.num_format_linked_to_source);
+        } else if self.category_has_num_format {
+            self.write_number_format("dd/mm/yyyy", true);
         }
 
-        // Close the c:chartSpace tag.
-        xml_end_tag(&mut self.writer, "c:chartSpace");
-    }
+        // Write the c:majorTickMark element.
+        if let Some(tick_type) = x_axis.major_tick_type {
+            self.write_major_tick_mark(tick_type);
+        }
 
-    // Write the <c:chartSpace> element.
-    fn write_chart_space(&mut self) {
-        let attributes = [
-            (
-                "xmlns:c",
-                "http://schemas.openxmlformats.org/drawingml/2006/chart",
-            ),
-            (
-                "xmlns:a",
-                "http://schemas.openxmlformats.org/drawingml/2006/main",
-            ),
-            (
-                "xmlns:r",
-                "http://schemas.openxmlformats.org/officeDocument/2006/relationships",
-            ),
-        ];
+        // Write the c:minorTickMark element.
+        if let Some(tick_type) = x_axis.minor_tick_type {
+            self.write_minor_tick_mark(tick_type);
+        }
 
-        xml_start_tag(&mut self.writer, "c:chartSpace", &attributes);
-    }
+        // Write the c:tickLblPos element.
+        self.write_tick_label_position(x_axis.label_position);
 
-    // Write the <c:lang> element.
-    fn write_lang(&mut self) {
-        let attributes = [("val", "en-US")];
+        if x_axis.format.has_formatting() {
+            // Write the c:spPr formatting element.
+            self.write_sp_pr(&x_axis.format);
+        }
 
-        xml_empty_tag(&mut self.writer, "c:lang", &attributes);
-    }
+        // Write the axis font elements.
+        if let Some(font) = &x_axis.font {
+            self.write_axis_font(&font.clone());
+        }
 
-    // Write the <c:chart> element.
-    fn write_chart(&mut self) {
-        xml_start_tag_only(&mut self.writer, "c:chart");
+        // Write the c:crossAx element.
+        self.write_cross_ax(axis_ids.1);
 
-        // Write the c:title element.
-        if self.title.hidden {
-            self.write_auto_title_deleted();
-        } else {
-            self.write_chart_title(&self.title.clone());
+        // Write the c:crosses element. Note, the X crossing comes from the Y axis.
+        if self.has_crosses || !x_axis.is_hidden {
+            match y_axis.crossing {
+                ChartAxisCrossing::Automatic | ChartAxisCrossing::Min | ChartAxisCrossing::Max => {
+                    self.write_crosses(&y_axis.crossing.to_string());
+                }
+                ChartAxisCrossing::AxisValue(_) => {
+                    self.write_crosses_at(&y_axis.crossing.to_string());
+                }
+                ChartAxisCrossing::CategoryNumber(_) => {
+                    // Ignore Category crossing on a Value axis.
+                    self.write_crosses(&ChartAxisCrossing::Automatic.to_string());
+                }
+            }
         }
 
-        // Write the c:plotArea element.
-        self.write_plot_area();
+        // Write the c:auto element.
+        if x_axis.automatic {
+            self.write_auto();
+        }
 
-        // Write the c:legend element.
-        self.write_legend();
+        // Write the c:lblOffset element.
+        self.write_lbl_offset();
 
-        // Write the c:plotVisOnly element.
-        if !self.show_hidden_data {
-            self.write_plot_vis_only();
+        // Write the c:tickLblSkip element.
+        if x_axis.label_interval > 1 {
+            self.write_tick_lbl_skip(x_axis.label_interval);
         }
 
-        // Write the c:dispBlanksAs element.
-        self.write_disp_blanks_as();
+        // Write the c:tickMarkSkip element.
+        if x_axis.tick_interval > 1 {
+            self.write_tick_mark_skip(x_axis.tick_interval);
+        }
 
-        // Write the dispNaAsBlank element.
-        if self.show_na_as_empty {
-            self.write_disp_na_as_blank();
+        // Write the c:majorUnit element.
+        if !x_axis.major_unit.is_empty() {
+            self.write_major_unit(&x_axis.major_unit);
         }
 
-        xml_end_tag(&mut self.writer, "c:chart");
-    }
+        // Write the c:majorTimeUnit element.
+        if let Some(unit) = x_axis.major_unit_date_type {
+            self.write_major_time_unit(unit);
+        }
 
-    // Write the <c:title> element.
-    fn write_chart_title(&mut self, title: &ChartTitle) {
-        if !title.name.is_empty() {
-            self.write_title_rich(title);
-        } else if title.range.has_data() {
-            self.write_title_formula(title);
-        } else if title.format.has_formatting() {
-            self.write_title_format_only(title);
+        // Write the c:minorUnit element.
+        if !x_axis.minor_unit.is_empty() {
+            self.write_minor_unit(&x_axis.minor_unit);
         }
-    }
 
-    // Write the <c:title> element.
-    fn write_series_title(&mut self, title: &ChartTitle) {
-        if !title.name.is_empty() {
-            self.write_tx_value(title);
-        } else if title.range.has_data() {
-            self.write_tx_formula(title);
+        // Write the c:minorTimeUnit element.
+        if let Some(unit) = x_axis.minor_unit_date_type {
+            self.write_minor_time_unit(unit);
         }
+
+        xml_end_tag(&mut self.writer, "c:dateAx");
     }
 
-    // Write the <c:plotArea> element.
-    fn write_plot_area(&mut self) {
-        self.series_index = 0;
-        xml_start_tag_only(&mut self.writer, "c:plotArea");
+    // -----------------------------------------------------------------------
+    // Value Axis.
+    // -----------------------------------------------------------------------
 
-        // Write the c:layout element.
-        self.write_layout(&self.plot_area.layout.clone());
+    // Write the <c:valAx> element.
+    fn write_val_ax(&mut self, x_axis: &ChartAxis, y_axis: &ChartAxis, axis_ids: (u32, u32)) {
+        xml_start_tag_only(&mut self.writer, "c:valAx");
 
-        // Write the <c:xxxChart> element for each chart type.
-        self.write_chart_type();
+        self.write_ax_id(axis_ids.1);
 
-        // Write the combined chart.
-        if let Some(combined_chart) = &mut self.combined_chart {
-            combined_chart.axis_ids = self.axis_ids;
-            combined_chart.axis2_ids = self.axis2_ids;
-            combined_chart.series_index = self.series.len();
+        // Write the c:scaling element.
+        self.write_scaling(y_axis);
 
-            mem::swap(&mut combined_chart.writer, &mut self.writer);
-            combined_chart.write_chart_type();
-            mem::swap(&mut combined_chart.writer, &mut self.writer);
+        if y_axis.is_hidden {
+            self.write_delete();
         }
+        // Write the c:axPos element.
+        self.write_ax_pos(y_axis.axis_position, x_axis.reverse, x_axis.crossing);
 
-        let mut x_axis = self.x_axis.clone();
-        let mut y_axis = self.y_axis.clone();
+        // Write the Gridlines elements.
+        self.write_major_gridlines(y_axis);
+        self.write_minor_gridlines(y_axis);
 
-        // Reverse the X and Y axes for Bar charts.
-        if self.chart_group_type == ChartType::Bar {
-            std::mem::swap(&mut x_axis, &mut y_axis);
+        // Write the c:title element.
+        self.write_chart_title(&y_axis.title);
+
+        // Write the c:numFmt element.
+        if y_axis.num_format.is_empty() {
+            self.write_number_format(&self.default_num_format.clone(), true);
+        } else {
+            self.write_number_format(&y_axis.num_format, y_axis.num_format_linked_to_source);
         }
 
-        match self.chart_group_type {
-            ChartType::Pie | ChartType::Doughnut => {}
+        // Write the c:majorTickMark element.
+        if let Some(position) = y_axis.major_tick_type {
+            self.write_major_tick_mark(position);
+        }
 
-            ChartType::Scatter => {
-                // Write the c:valAx element.
-                self.write_cat_val_ax(&x_axis, &y_axis, self.axis_ids);
+        // Write the c:minorTickMark element.
+        if let Some(position) = y_axis.minor_tick_type {
+            self.write_minor_tick_mark(position);
+        }
 
-                // Write the c:valAx element.
-                self.write_val_ax(&x_axis, &y_axis, self.axis_ids);
-            }
-            _ => {
-                if self.x_axis.axis_type == ChartAxisType::Date {
-                    // Write the c:dateAx element.
-                    self.write_date_ax(&x_axis, &y_axis, self.axis_ids);
-                } else {
-                    // Write the c:catAx element.
-                    self.write_cat_ax(&x_axis, &y_axis, self.axis_ids);
-                }
+        // Write the c:tickLblPos element.
+        self.write_tick_label_position(y_axis.label_position);
 
-                // Write the c:valAx element.
-                self.write_val_ax(&x_axis, &y_axis, self.axis_ids);
-            }
+        if y_axis.format.has_formatting() {
+            // Write the c:spPr formatting element.
+            self.write_sp_pr(&y_axis.format);
         }
 
-        // Handle any secondary axes due to secondary data series.
-        self.check_for_secondary_axis();
-        if self.has_secondary_axis {
-            let mut x_axis = self.x2_axis.clone();
-            let mut y_axis = self.y2_axis.clone();
-            let mut chart_group_type = self.chart_group_type;
-            let mut is_combined = false;
+        // Write the axis font elements.
+        if let Some(font) = &y_axis.font {
+            self.write_axis_font(font);
+        }
 
-            if let Some(combined_chart) = &self.combined_chart {
-                chart_group_type = combined_chart.chart_group_type;
-                is_combined = true;
-            }
+        // Write the c:crossAx element.
+        self.write_cross_ax(axis_ids.0);
 
-            // Reverse the X and Y axes for Bar charts.
-            if chart_group_type == ChartType::Bar {
-                std::mem::swap(&mut x_axis, &mut y_axis);
+        // Write the c:crosses element. Note, the Y crossing comes from the X axis.
+        match x_axis.crossing {
+            ChartAxisCrossing::Automatic | ChartAxisCrossing::Min | ChartAxisCrossing::Max => {
+                self.write_crosses(&x_axis.crossing.to_string());
             }
+            ChartAxisCrossing::CategoryNumber(_) | ChartAxisCrossing::AxisValue(_) => {
+                self.write_crosses_at(&x_axis.crossing.to_string());
+            }
+        }
 
-            match chart_group_type {
-                ChartType::Pie | ChartType::Doughnut => {}
-
-                ChartType::Scatter => {
-                    // The Cat and Value axes order changes depending on type.
-                    if is_combined {
-                        self.write_val_ax(&x_axis, &y_axis, self.axis2_ids);
-                        self.write_cat_val_ax(&x_axis, &y_axis, self.axis2_ids);
-                    } else {
-                        self.write_cat_val_ax(&x_axis, &y_axis, self.axis2_ids);
-                        self.write_val_ax(&x_axis, &y_axis, self.axis2_ids);
-                    }
-                }
-                _ => {
-                    // Write the c:valAx element.
-                    self.write_val_ax(&x_axis, &y_axis, self.axis2_ids);
+        // Write the c:crossBetween element.
+        self.write_cross_between(x_axis.position_between_ticks);
 
-                    if self.x_axis.axis_type == ChartAxisType::Date {
-                        // Write the c:dateAx element.
-                        self.write_date_ax(&x_axis, &y_axis, self.axis2_ids);
-                    } else {
-                        // Write the c:catAx element.
-                        self.write_cat_ax(&x_axis, &y_axis, self.axis2_ids);
-                    }
-                }
-            }
+        // Write the c:majorUnit element.
+        if y_axis.axis_type != ChartAxisType::Category && !y_axis.major_unit.is_empty() {
+            self.write_major_unit(&y_axis.major_unit);
         }
 
-        // Write the c:dTable element.
-        if let Some(table) = &self.table {
-            self.write_data_table(&table.clone());
+        // Write the c:minorUnit element.
+        if y_axis.axis_type != ChartAxisType::Category && !y_axis.minor_unit.is_empty() {
+            self.write_minor_unit(&y_axis.minor_unit);
         }
 
-        // Write the c:spPr element.
-        self.write_sp_pr(&self.plot_area.format.clone());
+        // Write the c:dispUnits element.
+        if y_axis.display_units_type != ChartAxisDisplayUnitType::None {
+            self.write_disp_units(y_axis.display_units_type, y_axis.display_units_visible);
+        }
 
-        xml_end_tag(&mut self.writer, "c:plotArea");
+        xml_end_tag(&mut self.writer, "c:valAx");
     }
 
-    // Write the <c:xxxChart> element.
-    fn write_chart_type(&mut self) {
-        match self.chart_type {
-            ChartType::Area | ChartType::AreaStacked | ChartType::AreaPercentStacked => {
-                self.write_area_chart(true);
-                self.write_area_chart(false);
-            }
-
-            ChartType::Bar | ChartType::BarStacked | ChartType::BarPercentStacked => {
-                self.write_bar_chart(true);
-                self.write_bar_chart(false);
-            }
-
-            ChartType::Column | ChartType::ColumnStacked | ChartType::ColumnPercentStacked => {
-                self.write_column_chart(true);
-                self.write_column_chart(false);
-            }
-
-            ChartType::Doughnut => {
-                self.write_doughnut_chart(true);
-                self.write_doughnut_chart(false);
-            }
-
-            ChartType::Line | ChartType::LineStacked | ChartType::LinePercentStacked => {
-                self.write_line_chart(true);
-                self.write_line_chart(false);
-            }
-
-            ChartType::Pie => {
-                self.write_pie_chart(true);
-                self.write_pie_chart(false);
-            }
+    // -----------------------------------------------------------------------
+    // Category Value Axis. Only for Scatter charts.
+    // -----------------------------------------------------------------------
 
-            ChartType::Radar | ChartType::RadarWithMarkers | ChartType::RadarFilled => {
-                self.write_radar_chart(true);
-                self.write_radar_chart(false);
-            }
+    // Write the category <c:valAx> element for scatter charts.
+    fn write_cat_val_ax(&mut self, x_axis: &ChartAxis, y_axis: &ChartAxis, axis_ids: (u32, u32)) {
+        xml_start_tag_only(&mut self.writer, "c:valAx");
 
-            ChartType::Scatter
-            | ChartType::ScatterStraight
-            | ChartType::ScatterStraightWithMarkers
-            | ChartType::ScatterSmooth
-            | ChartType::ScatterSmoothWithMarkers => {
-                self.write_scatter_chart(true);
-                self.write_scatter_chart(false);
-            }
+        self.write_ax_id(axis_ids.0);
 
-            ChartType::Stock => {
-                self.write_stock_chart(true);
-                self.write_stock_chart(false);
-            }
+        // Write the c:scaling element.
+        self.write_scaling(x_axis);
+
+        if x_axis.is_hidden {
+            self.write_delete();
         }
-    }
 
-    // Write the <c:layout> element.
-    fn write_layout(&mut self, layout: &ChartLayout) {
-        if layout.is_not_default() {
-            xml_start_tag_only(&mut self.writer, "c:layout");
+        // Write the c:axPos element.
+        self.write_ax_pos(x_axis.axis_position, y_axis.reverse, y_axis.crossing);
 
-            // Write the c:manualLayout element.
-            self.write_manual_layout(layout);
+        // Write the Gridlines elements.
+        self.write_major_gridlines(x_axis);
+        self.write_minor_gridlines(x_axis);
 
-            xml_end_tag(&mut self.writer, "c:layout");
+        // Write the c:title element.
+        self.write_chart_title(&x_axis.title);
+
+        // Write the c:numFmt element.
+        if x_axis.num_format.is_empty() {
+            self.write_number_format(&self.default_num_format.clone(), true);
         } else {
-            xml_empty_tag_only(&mut self.writer, "c:layout");
+            self.write_number_format(&x_axis.num_format, x_axis.num_format_linked_to_source);
         }
-    }
 
-    // Write the <c:manualLayout> element.
-    fn write_manual_layout(&mut self, layout: &ChartLayout) {
-        xml_start_tag_only(&mut self.writer, "c:manualLayout");
+        // Write the c:majorTickMark element.
+        if let Some(position) = x_axis.major_tick_type {
+            self.write_major_tick_mark(position);
+        }
 
-        if layout.has_inner {
-            xml_empty_tag(&mut self.writer, "c:layoutTarget", &[("val", "inner")]);
+        // Write the c:minorTickMark element.
+        if let Some(position) = x_axis.minor_tick_type {
+            self.write_minor_tick_mark(position);
         }
 
-        xml_empty_tag(&mut self.writer, "c:xMode", &[("val", "edge")]);
-        xml_empty_tag(&mut self.writer, "c:yMode", &[("val", "edge")]);
+        // Write the c:tickLblPos element.
+        self.write_tick_label_position(x_axis.label_position);
 
-        if let Some(value) = layout.x_offset {
-            let attributes = [("val", value.to_string())];
-            xml_empty_tag(&mut self.writer, "c:x", &attributes);
+        if x_axis.format.has_formatting() {
+            // Write the c:spPr formatting element.
+            self.write_sp_pr(&x_axis.format);
         }
 
-        if let Some(value) = layout.y_offset {
-            let attributes = [("val", value.to_string())];
-            xml_empty_tag(&mut self.writer, "c:y", &attributes);
+        // Write the axis font elements.
+        if let Some(font) = &x_axis.font {
+            self.write_axis_font(font);
         }
 
-        if layout.has_dimensions {
-            if let Some(value) = layout.width {
-                let attributes = [("val", value.to_string())];
-                xml_empty_tag(&mut self.writer, "c:w", &attributes);
-            }
+        // Write the c:crossAx element.
+        self.write_cross_ax(axis_ids.1);
 
-            if let Some(value) = layout.height {
-                let attributes = [("val", value.to_string())];
-                xml_empty_tag(&mut self.writer, "c:h", &attributes);
+        // Write the c:crosses element. Note, the X crossing comes from the Y axis.
+        match y_axis.crossing {
+            ChartAxisCrossing::Automatic | ChartAxisCrossing::Min | ChartAxisCrossing::Max => {
+                self.write_crosses(&y_axis.crossing.to_string());
+            }
+            ChartAxisCrossing::CategoryNumber(_) | ChartAxisCrossing::AxisValue(_) => {
+                self.write_crosses_at(&y_axis.crossing.to_string());
             }
         }
 
-        xml_end_tag(&mut self.writer, "c:manualLayout");
-    }
-
-    // Write the <c:barDir> element.
-    fn write_bar_dir(&mut self, direction: &str) {
-        let attributes = [("val", direction.to_string())];
-
-        xml_empty_tag(&mut self.writer, "c:barDir", &attributes);
-    }
-
-    // Write the <c:grouping> element.
-    fn write_grouping(&mut self) {
-        let attributes = [("val", self.grouping.to_string())];
+        // Write the c:crossBetween element.
+        self.write_cross_between(y_axis.position_between_ticks);
 
-        xml_empty_tag(&mut self.writer, "c:grouping", &attributes);
-    }
+        // Write the c:majorUnit element.
+        if x_axis.axis_type != ChartAxisType::Category && !x_axis.major_unit.is_empty() {
+            self.write_major_unit(&x_axis.major_unit);
+        }
 
-    // Write the <c:scatterStyle> element.
-    fn write_scatter_style(&mut self) {
-        let mut attributes = vec![];
+        // Write the c:minorUnit element.
+        if x_axis.axis_type != ChartAxisType::Category && !x_axis.minor_unit.is_empty() {
+            self.write_minor_unit(&x_axis.minor_unit);
+        }
 
-        if self.chart_type == ChartType::ScatterSmooth
-            || self.chart_type == ChartType::ScatterSmoothWithMarkers
-        {
-            attributes.push(("val", "smoothMarker".to_string()));
-        } else {
-            attributes.push(("val", "lineMarker".to_string()));
+        // Write the c:dispUnits element.
+        if x_axis.display_units_type != ChartAxisDisplayUnitType::None {
+            self.write_disp_units(x_axis.display_units_type, x_axis.display_units_visible);
         }
 
-        xml_empty_tag(&mut self.writer, "c:scatterStyle", &attributes);
+        xml_end_tag(&mut self.writer, "c:valAx");
     }
 
-    // Get the primary/secondary series for the chart.
-    fn get_series(&self, primary_axis: bool) -> Vec<ChartSeries> {
-        let mut series_copy = vec![];
-
-        for each_series in &self.series {
-            if each_series.secondary_axis != primary_axis {
-                series_copy.push(each_series.clone());
-            }
-        }
+    // -----------------------------------------------------------------------
+    // Series Axis. Only for 3D charts.
+    // -----------------------------------------------------------------------
 
-        series_copy
-    }
+    // Write the <c:serAx> element.
+    fn write_ser_ax(&mut self, z_axis: &ChartAxis) {
+        xml_start_tag_only(&mut self.writer, "c:serAx");
 
-    // Write the <c:ser> element.
-    fn write_series(&mut self, series: &Vec<ChartSeries>) {
-        for series in series {
-            let max_points = series.value_range.number_of_points();
+        self.write_ax_id(self.axis_ids.0 + 4);
 
-            xml_start_tag_only(&mut self.writer, "c:ser");
+        // Write the c:scaling element.
+        self.write_scaling(z_axis);
 
-            // Copy a series overlap to the parent chart.
-            if series.overlap.is_some() {
-                self.overlap = series.overlap;
-            }
+        if z_axis.is_hidden {
+            self.write_delete();
+        }
 
-            // Copy a series gap to the parent chart.
-            if series.gap != 150 {
-                self.gap = series.gap;
-            }
+        // Write the c:axPos element.
+        self.write_ax_pos(z_axis.axis_position, false, ChartAxisCrossing::Automatic);
 
-            // Write the c:idx element.
-            self.write_idx(self.series_index);
+        // Write the Gridlines elements.
+        self.write_major_gridlines(z_axis);
+        self.write_minor_gridlines(z_axis);
 
-            // Write the c:order element.
-            self.write_order(self.series_index);
+        // Write the c:title element.
+        self.write_chart_title(&z_axis.title);
 
-            self.write_series_title(&series.title);
+        // Write the c:majorTickMark element.
+        if let Some(tick_type) = z_axis.major_tick_type {
+            self.write_major_tick_mark(tick_type);
+        }
 
-            // Write the c:spPr element.
-            self.write_sp_pr(&series.format);
+        // Write the c:minorTickMark element.
+        if let Some(tick_type) = z_axis.minor_tick_type {
+            self.write_minor_tick_mark(tick_type);
+        }
 
-            if let Some(marker) = &series.marker {
-                if !marker.automatic {
-                    // Write the c:marker element.
-                    self.write_marker(marker);
-                }
-            }
+        // Write the c:tickLblPos element.
+        self.write_tick_label_position(z_axis.label_position);
 
-            // Write the c:invertIfNegative element.
-            if series.invert_if_negative {
-                self.write_invert_if_negative();
-            }
+        if z_axis.format.has_formatting() {
+            // Write the c:spPr formatting element.
+            self.write_sp_pr(&z_axis.format);
+        }
 
-            // Write the point formatting for the series.
-            if !series.points.is_empty() {
-                self.write_d_pt(&series.points, max_points);
-            }
+        // Write the axis font elements.
+        if let Some(font) = &z_axis.font {
+            self.write_axis_font(font);
+        }
 
-            if let Some(data_label) = &series.data_label {
-                // Write the c:dLbls element.
-                self.write_data_labels(data_label, &series.custom_data_labels, max_points);
-            }
+        // Write the c:crossAx element.
+        self.write_cross_ax(self.axis_ids.1);
 
-            if series.trendline.trend_type != ChartTrendlineType::None {
-                // Write the c:trendline element.
-                self.write_trendline(&series.trendline);
-            }
+        // Write the c:crosses element.
+        self.write_crosses(&ChartAxisCrossing::Automatic.to_string());
 
-            if self.chart_group_type == ChartType::Bar {
-                if let Some(error_bars) = &series.x_error_bars {
-                    // Write the c:errBars element.
-                    self.write_error_bar("", error_bars);
-                }
-            } else if self.chart_group_type == ChartType::Column {
-                if let Some(error_bars) = &series.y_error_bars {
-                    // Write the c:errBars element.
-                    self.write_error_bar("", error_bars);
-                }
-            } else if let Some(error_bars) = &series.y_error_bars {
-                // Write the c:errBars element.
-                self.write_error_bar("y", error_bars);
-            }
+        // Write the c:tickLblSkip element.
+        if z_axis.label_interval > 1 {
+            self.write_tick_lbl_skip(z_axis.label_interval);
+        }
 
-            // Write the c:cat element.
-            if series.category_range.has_data() {
-                // We only set a default num format for numeric categories.
-                self.category_has_num_format = matches!(
-                    series.category_range.cache.cache_type,
-                    ChartRangeCacheDataType::Number | ChartRangeCacheDataType::Date
-                );
+        // Write the c:tickMarkSkip element.
+        if z_axis.tick_interval > 1 {
+            self.write_tick_mark_skip(z_axis.tick_interval);
+        }
 
-                self.write_cat(&series.category_range);
-            }
+        xml_end_tag(&mut self.writer, "c:serAx");
+    }
 
-            // Write the c:val element.
-            self.write_val(&series.value_range);
+    // Write the <c:scaling> element.
+    fn write_scaling(&mut self, axis: &ChartAxis) {
+        xml_start_tag_only(&mut self.writer, "c:scaling");
 
-            if !series.inverted_color.is_auto_or_default() {
-                // Write the c:extLst element for the inverted fill color.
-                self.write_extension_list(series.inverted_color);
-            }
+        // Write the c:logBase element.
+        if axis.axis_type != ChartAxisType::Category && axis.log_base >= 2 {
+            self.write_log_base(axis.log_base);
+        }
 
-            // Write the c:smooth element.
-            if self.chart_group_type == ChartType::Line {
-                if let Some(smooth) = series.smooth {
-                    if smooth {
-                        self.write_smooth();
-                    }
-                }
-            }
+        // Write the c:orientation element.
+        self.write_orientation(axis.reverse);
 
-            self.series_index += 1;
+        // Write the c:max element.
+        if axis.axis_type != ChartAxisType::Category && !axis.max.is_empty() {
+            self.write_max(&axis.max);
+        }
 
-            xml_end_tag(&mut self.writer, "c:ser");
+        // Write the c:min element.
+        if axis.axis_type != ChartAxisType::Category && !axis.min.is_empty() {
+            self.write_min(&axis.min);
         }
-    }
 
-    // Write the <c:ser> element for scatter charts.
-    fn write_scatter_series(&mut self, series: &mut Vec<ChartSeries>) {
-        for series in series {
-            let max_points = series.value_range.number_of_points();
+        xml_end_tag(&mut self.writer, "c:scaling");
+    }
 
-            xml_start_tag_only(&mut self.writer, "c:ser");
+    // Write the <c:logBase> element.
+    fn write_log_base(&mut self, base: u16) {
+        let attributes = [("val", base.to_string())];
 
-            // Write the c:idx element.
-            self.write_idx(self.series_index);
+        xml_empty_tag(&mut self.writer, "c:logBase", &attributes);
+    }
 
-            // Write the c:order element.
-            self.write_order(self.series_index);
+    // Write the <c:orientation> element.
+    fn write_orientation(&mut self, reverse: bool) {
+        let attributes = if reverse {
+            [("val", "maxMin")]
+        } else {
+            [("val", "minMax")]
+        };
 
-            self.write_series_title(&series.title);
+        xml_empty_tag(&mut self.writer, "c:orientation", &attributes);
+    }
 
-            if let Some(marker) = &series.marker {
-                if !marker.automatic {
-                    // Write the c:marker element.
-                    self.write_marker(marker);
-                }
-            }
+    // Write the <c:max> element.
+    fn write_max(&mut self, max: &str) {
+        let attributes = [("val", max.to_string())];
 
-            // Add default scatter line formatting to the series data unless it
-            // has already been specified by the user.
-            if self.chart_type == ChartType::Scatter && series.format.line.is_none() {
-                let mut line = ChartLine::new();
-                line.set_width(2.25);
-                series.format.line = Some(line);
-            }
+        xml_empty_tag(&mut self.writer, "c:max", &attributes);
+    }
 
-            // Write the c:spPr formatting element.
-            self.write_sp_pr(&series.format);
+    // Write the <c:min> element.
+    fn write_min(&mut self, min: &str) {
+        let attributes = [("val", min.to_string())];
 
-            // Write the point formatting for the series.
-            if !series.points.is_empty() {
-                self.write_d_pt(&series.points, max_points);
-            }
+        xml_empty_tag(&mut self.writer, "c:min", &attributes);
+    }
 
-            // Write the c:dLbls element.
-            if let Some(data_label) = &series.data_label {
-                self.write_data_labels(data_label, &series.custom_data_labels, max_points);
-            }
+    // Write the <c:axPos> element.
+    fn write_ax_pos(
+        &mut self,
+        position: ChartAxisPosition,
+        reverse: bool,
+        crossing: ChartAxisCrossing,
+    ) {
+        let mut position = position;
 
-            // Write the c:trendline element.
-            if series.trendline.trend_type != ChartTrendlineType::None {
-                self.write_trendline(&series.trendline);
-            }
+        if reverse || crossing == ChartAxisCrossing::Max {
+            position = position.reverse();
+        }
 
-            // Write the X-Axis c:errBars element.
-            if let Some(error_bars) = &series.x_error_bars {
-                self.write_error_bar("x", error_bars);
-            }
+        let attributes = [("val", position.to_string())];
 
-            // Write the Y-Axis the c:errBars element.
-            if let Some(error_bars) = &series.y_error_bars {
-                self.write_error_bar("y", error_bars);
-            }
+        xml_empty_tag(&mut self.writer, "c:axPos", &attributes);
+    }
 
-            self.write_x_val(&series.category_range);
+    // Write the <c:numFmt> element.
+    fn write_number_format(&mut self, format: &str, linked: bool) {
+        let attributes = [
+            ("formatCode", format.to_string()),
+            ("sourceLinked", linked.to_xml_bool()),
+        ];
 
-            self.write_y_val(&series.value_range);
+        xml_empty_tag(&mut self.writer, "c:numFmt", &attributes);
+    }
 
-            // Write the c:smooth element.
-            if self.chart_group_type == ChartType::Scatter {
-                if let Some(smooth) = series.smooth {
-                    if smooth {
-                        self.write_smooth();
-                    }
-                } else if self.chart_type == ChartType::ScatterSmooth
-                    || self.chart_type == ChartType::ScatterSmoothWithMarkers
-                {
-                    // The ScatterSmooth charts have a default smooth element if
-                    // one hasn't been set/unset by the user.
-                    self.write_smooth();
-                }
-            }
+    // Write the <c:majorGridlines> element.
+    fn write_major_gridlines(&mut self, axis: &ChartAxis) {
+        if axis.major_gridlines {
+            if let Some(line) = &axis.major_gridlines_line {
+                xml_start_tag_only(&mut self.writer, "c:majorGridlines");
+                xml_start_tag_only(&mut self.writer, "c:spPr");
 
-            self.series_index += 1;
+                // Write the a:ln element.
+                self.write_a_ln(line);
 
-            xml_end_tag(&mut self.writer, "c:ser");
+                xml_end_tag(&mut self.writer, "c:spPr");
+                xml_end_tag(&mut self.writer, "c:majorGridlines");
+            } else {
+                xml_empty_tag_only(&mut self.writer, "c:majorGridlines");
+            }
         }
     }
 
-    // Write the <c:dPt> element.
-    fn write_d_pt(&mut self, points: &[ChartPoint], max_points: usize) {
-        let has_marker =
-            self.chart_group_type == ChartType::Scatter || self.chart_group_type == ChartType::Line;
+    // Write the <c:minorGridlines> element.
+    fn write_minor_gridlines(&mut self, axis: &ChartAxis) {
+        if axis.minor_gridlines {
+            if let Some(line) = &axis.minor_gridlines_line {
+                xml_start_tag_only(&mut self.writer, "c:minorGridlines");
+                xml_start_tag_only(&mut self.writer, "c:spPr");
 
-        // Write the point formatting for the series.
-        for (index, point) in points.iter().enumerate() {
-            if index >= max_points {
-                break;
-            }
+                // Write the a:ln element.
+                self.write_a_ln(line);
 
-            if point.is_not_default() {
-                xml_start_tag_only(&mut self.writer, "c:dPt");
-                self.write_idx(index);
+                xml_end_tag(&mut self.writer, "c:spPr");
+                xml_end_tag(&mut self.writer, "c:minorGridlines");
+            } else {
+                xml_empty_tag_only(&mut self.writer, "c:minorGridlines");
+            }
+        }
+    }
 
-                if has_marker {
-                    xml_start_tag_only(&mut self.writer, "c:marker");
-                }
+    // Write the <c:tickLblPos> element.
+    fn write_tick_label_position(&mut self, position: ChartAxisLabelPosition) {
+        let attributes = [("val", position.to_string())];
 
-                // Write the c:spPr formatting element.
-                self.write_sp_pr(&point.format);
+        xml_empty_tag(&mut self.writer, "c:tickLblPos", &attributes);
+    }
 
-                if has_marker {
-                    xml_end_tag(&mut self.writer, "c:marker");
-                }
+    // Write the <c:crossAx> element.
+    fn write_cross_ax(&mut self, axis_id: u32) {
+        let attributes = [("val", axis_id.to_string())];
 
-                xml_end_tag(&mut self.writer, "c:dPt");
-            }
-        }
+        xml_empty_tag(&mut self.writer, "c:crossAx", &attributes);
     }
 
-    // Write the <c:idx> element.
-    fn write_idx(&mut self, index: usize) {
-        let attributes = [("val", index.to_string())];
+    // Write the <c:crosses> element.
+    fn write_crosses(&mut self, crossing: &str) {
+        let attributes = [("val", crossing)];
 
-        xml_empty_tag(&mut self.writer, "c:idx", &attributes);
+        xml_empty_tag(&mut self.writer, "c:crosses", &attributes);
     }
 
-    // Write the <c:order> element.
-    fn write_order(&mut self, index: usize) {
-        let attributes = [("val", index.to_string())];
+    // Write the <c:crossesAt> element.
+    fn write_crosses_at(&mut self, crossing: &str) {
+        let attributes = [("val", crossing)];
 
-        xml_empty_tag(&mut self.writer, "c:order", &attributes);
+        xml_empty_tag(&mut self.writer, "c:crossesAt", &attributes);
     }
 
-    // Write the <c:invertIfNegative> element.
-    fn write_invert_if_negative(&mut self) {
+    // Write the <c:auto> element.
+    fn write_auto(&mut self) {
         let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:invertIfNegative", &attributes);
+        xml_empty_tag(&mut self.writer, "c:auto", &attributes);
     }
 
-    // Write the <c:extLst> element for inverted fill colors.
-    fn write_extension_list(&mut self, color: Color) {
-        let attributes1 = [
-            ("uri", "{6F2FDCE9-48DA-4B69-8628-5D25D57E5C99}"),
-            (
-                "xmlns:c14",
-                "http://schemas.microsoft.com/office/drawing/2007/8/2/chart",
-            ),
-        ];
-        let attributes2 = [(
-            "xmlns:c14",
-            "http://schemas.microsoft.com/office/drawing/2007/8/2/chart",
-        )];
+    // Write the <c:lblAlgn> element.
+    fn write_lbl_algn(&mut self, position: &str) {
+        let attributes = [("val", position)];
 
-        xml_start_tag_only(&mut self.writer, "c:extLst");
-        xml_start_tag(&mut self.writer, "c:ext", &attributes1);
-        xml_start_tag_only(&mut self.writer, "c14:invertSolidFillFmt");
-        xml_start_tag(&mut self.writer, "c14:spPr", &attributes2);
+        xml_empty_tag(&mut self.writer, "c:lblAlgn", &attributes);
+    }
 
-        self.write_a_solid_fill(color, 0);
+    // Write the <c:lblOffset> element.
+    fn write_lbl_offset(&mut self) {
+        let attributes = [("val", "100")];
 
-        xml_end_tag(&mut self.writer, "c14:spPr");
-        xml_end_tag(&mut self.writer, "c14:invertSolidFillFmt");
-        xml_end_tag(&mut self.writer, "c:ext");
-        xml_end_tag(&mut self.writer, "c:extLst");
+        xml_empty_tag(&mut self.writer, "c:lblOffset", &attributes);
     }
 
-    // Write the <c:cat> element.
-    fn write_cat(&mut self, range: &ChartRange) {
-        xml_start_tag_only(&mut self.writer, "c:cat");
-
-        self.write_cache_ref(range, false);
+    // Write the <c:crossBetween> element.
+    fn write_cross_between(&mut self, position_between_ticks: bool) {
+        let attributes = if position_between_ticks {
+            [("val", "between")]
+        } else {
+            [("val", "midCat")]
+        };
 
-        xml_end_tag(&mut self.writer, "c:cat");
+        xml_empty_tag(&mut self.writer, "c:crossBetween", &attributes);
     }
 
-    // Write the <c:val> element.
-    fn write_val(&mut self, range: &ChartRange) {
-        xml_start_tag_only(&mut self.writer, "c:val");
-
-        self.write_cache_ref(range, true);
+    // Write the <c:tickLblSkip> element.
+    fn write_tick_lbl_skip(&mut self, units: u16) {
+        let attributes = [("val", units.to_string())];
 
-        xml_end_tag(&mut self.writer, "c:val");
+        xml_empty_tag(&mut self.writer, "c:tickLblSkip", &attributes);
     }
 
-    // Write the <c:xVal> element for scatter charts.
-    fn write_x_val(&mut self, range: &ChartRange) {
-        xml_start_tag_only(&mut self.writer, "c:xVal");
-
-        self.write_cache_ref(range, false);
+    // Write the <c:tickMarkSkip> element.
+    fn write_tick_mark_skip(&mut self, units: u16) {
+        let attributes = [("val", units.to_string())];
 
-        xml_end_tag(&mut self.writer, "c:xVal");
+        xml_empty_tag(&mut self.writer, "c:tickMarkSkip", &attributes);
     }
 
-    // Write the <c:yVal> element for scatter charts.
-    fn write_y_val(&mut self, range: &ChartRange) {
-        xml_start_tag_only(&mut self.writer, "c:yVal");
-
-        self.write_cache_ref(range, true);
+    // Write the <c:majorUnit> element.
+    fn write_major_unit(&mut self, value: &str) {
+        let attributes = [("val", value)];
 
-        xml_end_tag(&mut self.writer, "c:yVal");
+        xml_empty_tag(&mut self.writer, "c:majorUnit", &attributes);
     }
 
-    // Write the <c:numRef> or <c:strRef> or <c:multiLvlStrRef> elements. Value
-    // range must be written as a numRef where strings are treated as zero.
-    fn write_cache_ref(&mut self, range: &ChartRange, is_num_only: bool) {
-        if range.cache.cache_type == ChartRangeCacheDataType::String && !is_num_only {
-            self.write_str_ref(range);
-        } else if range.cache.cache_type == ChartRangeCacheDataType::MultiLevelString {
-            self.write_multi_level_str_ref(range);
-        } else {
-            self.write_num_ref(range);
-        }
+    // Write the <c:minorUnit> element.
+    fn write_minor_unit(&mut self, value: &str) {
+        let attributes = [("val", value)];
+
+        xml_empty_tag(&mut self.writer, "c:minorUnit", &attributes);
     }
 
-    // Write the <c:numRef> element.
-    fn write_num_ref(&mut self, range: &ChartRange) {
-        xml_start_tag_only(&mut self.writer, "c:numRef");
+    // Write the <c:majorTimeUnit> element.
+    fn write_major_time_unit(&mut self, units: ChartAxisDateUnitType) {
+        let attributes = [("val", units.to_string())];
 
-        // Write the c:f element.
-        self.write_range_formula(&range.formula_string());
+        xml_empty_tag(&mut self.writer, "c:majorTimeUnit", &attributes);
+    }
 
-        // Write the c:numCache element.
-        if range.cache.has_data() {
-            self.write_num_cache(&range.cache);
-        }
+    // Write the <c:minorTimeUnit> element.
+    fn write_minor_time_unit(&mut self, units: ChartAxisDateUnitType) {
+        let attributes = [("val", units.to_string())];
 
-        xml_end_tag(&mut self.writer, "c:numRef");
+        xml_empty_tag(&mut self.writer, "c:minorTimeUnit", &attributes);
     }
 
-    // Write the <c:strRef> element.
-    fn write_str_ref(&mut self, range: &ChartRange) {
-        xml_start_tag_only(&mut self.writer, "c:strRef");
+    // Write the <c:dispUnits> element.
+    fn write_disp_units(&mut self, units: ChartAxisDisplayUnitType, visible: bool) {
+        xml_start_tag_only(&mut self.writer, "c:dispUnits");
 
-        // Write the c:f element.
-        self.write_range_formula(&range.formula_string());
+        // Write the c:builtInUnit element.
+        self.write_built_in_unit(units);
 
-        // Write the c:strCache element.
-        if range.cache.has_data() {
-            self.write_str_cache(&range.cache);
+        // Write the c:dispUnitsLbl element.
+        if visible {
+            self.write_disp_units_lbl();
         }
 
-        xml_end_tag(&mut self.writer, "c:strRef");
+        xml_end_tag(&mut self.writer, "c:dispUnits");
     }
 
-    // Write the <c:numCache> element.
-    fn write_num_cache(&mut self, cache: &ChartRangeCacheData) {
-        xml_start_tag_only(&mut self.writer, "c:numCache");
+    // Write the <c:builtInUnit> element.
+    fn write_built_in_unit(&mut self, units: ChartAxisDisplayUnitType) {
+        let attributes = [("val", units.to_string())];
 
-        // Write the c:formatCode element.
-        if cache.cache_type == ChartRangeCacheDataType::Date {
-            self.write_format_code("dd/mm/yyyy");
-        } else {
-            self.write_format_code("General");
-        }
+        xml_empty_tag(&mut self.writer, "c:builtInUnit", &attributes);
+    }
 
-        // Write the c:ptCount element.
-        self.write_pt_count(cache.data.len());
+    // Write the <c:dispUnitsLbl> element.
+    fn write_disp_units_lbl(&mut self) {
+        xml_start_tag_only(&mut self.writer, "c:dispUnitsLbl");
 
-        // Write the c:pt elements.
-        for (index, value) in cache.data.iter().enumerate() {
-            if !value.is_empty() {
-                // Non numeric values in value/number caches are treated as zero
-                // by Excel.
-                if value.parse::<f64>().is_err() {
-                    self.write_pt(index, "0");
-                } else {
-                    self.write_pt(index, value);
-                }
-            }
-        }
+        // Write the c:layout element.
+        let layout = ChartLayout::default();
+        self.write_layout(&layout);
 
-        xml_end_tag(&mut self.writer, "c:numCache");
+        xml_end_tag(&mut self.writer, "c:dispUnitsLbl");
     }
 
-    // Write the <c:multiLvlStrRef> element.
-    fn write_multi_level_str_ref(&mut self, range: &ChartRange) {
-        xml_start_tag_only(&mut self.writer, "c:multiLvlStrRef");
-
-        // Write the c:f element.
-        self.write_range_formula(&range.formula_string());
-
-        // Write the c:strCache element.
-        if range.cache.has_data() {
-            self.write_multi_level_str_cache(&range.cache);
+    // Write the <c:legend> element.
+    fn write_legend(&mut self) {
+        if self.legend.hidden {
+            return;
         }
 
-        xml_end_tag(&mut self.writer, "c:multiLvlStrRef");
-    }
+        xml_start_tag_only(&mut self.writer, "c:legend");
 
-    // Write the <c:strCache> element.
-    fn write_str_cache(&mut self, cache: &ChartRangeCacheData) {
-        xml_start_tag_only(&mut self.writer, "c:strCache");
+        // Write the c:legendPos element.
+        self.write_legend_pos();
 
-        // Write the c:ptCount element.
-        self.write_pt_count(cache.data.len());
+        // Check for series and trendlines that should be deleted/hidden from
+        // the legend.
+        let deleted_entries = self.deleted_legend_entries();
 
-        // Write the c:pt elements.
-        for (index, value) in cache.data.iter().enumerate() {
-            self.write_pt(index, value);
+        if !deleted_entries.is_empty() {
+            for index in deleted_entries {
+                // Write the c:legendEntry element.
+                self.write_legend_entry(index);
+            }
         }
 
-        xml_end_tag(&mut self.writer, "c:strCache");
-    }
-
-    // Write the <c:multiLvlStrCache> element.
-    fn write_multi_level_str_cache(&mut self, cache: &ChartRangeCacheData) {
-        xml_start_tag_only(&mut self.writer, "c:multiLvlStrCache");
-
-        // Write the c:ptCount element.
-        self.write_pt_count(cache.major_dim);
+        // Write the c:layout element.
+        self.write_layout(&self.legend.layout.clone());
 
-        // Split the cache data into levels, in reverse order.
-        for depth in (0..cache.minor_dim).rev() {
-            xml_start_tag_only(&mut self.writer, "c:lvl");
+        // Write the c:spPr formatting element.
+        self.write_sp_pr(&self.legend.format.clone());
 
-            for index in 0..cache.major_dim {
-                let offset = depth + cache.minor_dim * index;
-                let value = &cache.data[offset];
+        // Write the c:overlay element.
+        if self.legend.has_overlay {
+            self.write_overlay();
+        }
 
-                if !value.is_empty() {
-                    self.write_pt(index, value);
+        // Pie/Doughnut charts set the "rtl" flag to "0" in the legend font even
+        // though "0" is implied. To match Excel output we set it if it hasn't
+        // been set by the user.
+        if matches!(
+            self.chart_type,
+            ChartType::Pie | ChartType::Pie3D | ChartType::Doughnut
+        ) {
+            match &mut self.legend.font {
+                Some(font) => {
+                    if font.right_to_left.is_none() {
+                        font.set_right_to_left(false);
+                    }
+                }
+                None => {
+                    let mut font = ChartFont::new();
+                    font.set_right_to_left(false);
+                    self.legend.font = Some(font);
                 }
             }
+        }
 
-            xml_end_tag(&mut self.writer, "c:lvl");
+        if let Some(font) = &self.legend.font {
+            // Write the c:txPr element.
+            self.write_tx_pr(&font.clone(), false);
         }
 
-        xml_end_tag(&mut self.writer, "c:multiLvlStrCache");
+        xml_end_tag(&mut self.writer, "c:legend");
     }
 
-    // Write the <c:f> element.
-    fn write_range_formula(&mut self, formula: &str) {
-        xml_data_element_only(&mut self.writer, "c:f", formula);
-    }
+    // Write the <c:legendPos> element.
+    fn write_legend_pos(&mut self) {
+        let attributes = [("val", self.legend.position.to_string())];
 
-    // Write the <c:formatCode> element.
-    fn write_format_code(&mut self, format_code: &str) {
-        xml_data_element_only(&mut self.writer, "c:formatCode", format_code);
+        xml_empty_tag(&mut self.writer, "c:legendPos", &attributes);
     }
 
-    // Write the <c:ptCount> element.
-    fn write_pt_count(&mut self, count: usize) {
-        let attributes = [("val", count.to_string())];
+    // Write the <c:legendEntry> element.
+    fn write_legend_entry(&mut self, index: usize) {
+        xml_start_tag_only(&mut self.writer, "c:legendEntry");
 
-        xml_empty_tag(&mut self.writer, "c:ptCount", &attributes);
-    }
+        // Write the c:idx element.
+        self.write_idx(index);
 
-    // Write the <c:pt> element.
-    fn write_pt(&mut self, index: usize, value: &str) {
-        let attributes = [("idx", index.to_string())];
+        // Write the c:delete element.
+        self.write_delete();
 
-        xml_start_tag(&mut self.writer, "c:pt", &attributes);
-        xml_data_element_only(&mut self.writer, "c:v", value);
-        xml_end_tag(&mut self.writer, "c:pt");
+        xml_end_tag(&mut self.writer, "c:legendEntry");
     }
 
-    // Write both <c:axId> elements.
-    fn write_ax_ids(&mut self, primary_axis: bool) {
-        if primary_axis {
-            self.write_ax_id(self.axis_ids.0);
-            self.write_ax_id(self.axis_ids.1);
-        } else {
-            self.write_ax_id(self.axis2_ids.0);
-            self.write_ax_id(self.axis2_ids.1);
-        }
+    // Write the <c:overlay> element.
+    fn write_overlay(&mut self) {
+        xml_empty_tag(&mut self.writer, "c:overlay", &[("val", "1")]);
     }
 
-    // Write the <c:axId> element.
-    fn write_ax_id(&mut self, axis_id: u32) {
-        let attributes = [("val", axis_id.to_string())];
+    // Write the <c:plotVisOnly> element.
+    fn write_plot_vis_only(&mut self) {
+        let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:axId", &attributes);
+        xml_empty_tag(&mut self.writer, "c:plotVisOnly", &attributes);
     }
 
-    // -----------------------------------------------------------------------
-    // Category Axis.
-    // -----------------------------------------------------------------------
+    // Write the <c:printSettings> element.
+    fn write_print_settings(&mut self) {
+        xml_start_tag_only(&mut self.writer, "c:printSettings");
 
-    // Write the <c:catAx> element.
-    fn write_cat_ax(&mut self, x_axis: &ChartAxis, y_axis: &ChartAxis, axis_ids: (u32, u32)) {
-        xml_start_tag_only(&mut self.writer, "c:catAx");
+        // Write the c:headerFooter element.
+        self.write_header_footer();
 
-        self.write_ax_id(axis_ids.0);
+        // Write the c:pageMargins element.
+        self.write_page_margins();
 
-        // Write the c:scaling element.
-        self.write_scaling(x_axis);
+        // Write the c:pageSetup element.
+        self.write_page_setup();
+
+        xml_end_tag(&mut self.writer, "c:printSettings");
+    }
+
+    // Write the <c:headerFooter> element.
+    fn write_header_footer(&mut self) {
+        xml_empty_tag_only(&mut self.writer, "c:headerFooter");
+    }
+
+    // Write the <c:pageMargins> element.
+    fn write_page_margins(&mut self) {
+        let attributes = [
+            ("b", "0.75"),
+            ("l", "0.7"),
+            ("r", "0.7"),
+            ("t", "0.75"),
+            ("header", "0.3"),
+            ("footer", "0.3"),
+        ];
 
-        if x_axis.is_hidden {
-            self.write_delete();
-        }
+        xml_empty_tag(&mut self.writer, "c:pageMargins", &attributes);
+    }
 
-        // Write the c:axPos element.
-        self.write_ax_pos(x_axis.axis_position, y_axis.reverse, y_axis.crossing);
+    // Write the <c:pageSetup> element.
+    fn write_page_setup(&mut self) {
+        xml_empty_tag_only(&mut self.writer, "c:pageSetup");
+    }
 
-        self.write_major_gridlines(x_axis);
-        self.write_minor_gridlines(x_axis);
+    // Write the <c:marker> element.
+    fn write_marker_value(&mut self) {
+        let attributes = [("val", "1")];
 
-        // Write the c:title element.
-        self.write_chart_title(&x_axis.title);
+        xml_empty_tag(&mut self.writer, "c:marker", &attributes);
+    }
 
-        // Write the c:numFmt element.
-        if !x_axis.num_format.is_empty() {
-            self.write_number_format(&x_axis.num_format, x_axis.num_format_linked_to_source);
-        } else if self.category_has_num_format {
-            self.write_number_format("General", true);
-        }
+    // Write the <c:marker> element.
+    fn write_marker(&mut self, marker: &ChartMarker) {
+        xml_start_tag_only(&mut self.writer, "c:marker");
 
-        // Write the c:majorTickMark element.
-        if let Some(tick_type) = x_axis.major_tick_type {
-            self.write_major_tick_mark(tick_type);
-        }
+        // Write the c:symbol element.
+        self.write_symbol(marker);
 
-        // Write the c:minorTickMark element.
-        if let Some(tick_type) = x_axis.minor_tick_type {
-            self.write_minor_tick_mark(tick_type);
+        if marker.size != 0 {
+            // Write the c:size element.
+            self.write_size(marker.size);
         }
 
-        // Write the c:tickLblPos element.
-        self.write_tick_label_position(x_axis.label_position);
-
-        if x_axis.format.has_formatting() {
+        if marker.format.has_formatting() {
             // Write the c:spPr formatting element.
-            self.write_sp_pr(&x_axis.format);
+            self.write_sp_pr(&marker.format);
         }
 
-        // Write the axis font elements.
-        if let Some(font) = &x_axis.font {
-            self.write_axis_font(font);
-        }
+        xml_end_tag(&mut self.writer, "c:marker");
+    }
 
-        // Write the c:crossAx element.
-        self.write_cross_ax(axis_ids.1);
+    // Write the <c:dLbls> element.
+    fn write_data_labels(
+        &mut self,
+        data_label: &ChartDataLabel,
+        custom_data_labels: &[ChartDataLabel],
+        max_points: usize,
+    ) {
+        xml_start_tag_only(&mut self.writer, "c:dLbls");
 
-        // Write the c:crosses element. Note, the X crossing comes from the Y axis.
-        if self.has_crosses || !x_axis.is_hidden {
-            match y_axis.crossing {
-                ChartAxisCrossing::Automatic | ChartAxisCrossing::Min | ChartAxisCrossing::Max => {
-                    self.write_crosses(&y_axis.crossing.to_string());
-                }
-                ChartAxisCrossing::AxisValue(_) => {
-                    self.write_crosses_at(&y_axis.crossing.to_string());
-                }
-                ChartAxisCrossing::CategoryNumber(_) => {
-                    // Ignore Category crossing on a Value axis.
-                    self.write_crosses(&ChartAxisCrossing::Automatic.to_string());
-                }
-            }
+        if !custom_data_labels.is_empty() {
+            self.write_custom_data_labels(custom_data_labels, max_points);
         }
 
-        // Write the c:auto element.
-        if !x_axis.automatic {
-            self.write_auto();
-        }
+        // Write the main elements of a data label.
+        self.write_data_label(data_label);
 
-        // Write the c:lblAlgn element.
-        self.write_lbl_algn(&x_axis.label_alignment.to_string());
+        xml_end_tag(&mut self.writer, "c:dLbls");
+    }
 
-        // Write the c:lblOffset element.
-        self.write_lbl_offset();
+    // Write the <c:dLbl> element.
+    fn write_custom_data_labels(&mut self, data_labels: &[ChartDataLabel], max_points: usize) {
+        // Write the point formatting for the series.
+        for (index, data_label) in data_labels.iter().enumerate() {
+            let mut write_layout = true;
 
-        // Write the c:tickLblSkip element.
-        if x_axis.label_interval > 1 {
-            self.write_tick_lbl_skip(x_axis.label_interval);
-        }
+            if index >= max_points {
+                break;
+            }
 
-        // Write the c:tickMarkSkip element.
-        if x_axis.tick_interval > 1 {
-            self.write_tick_mark_skip(x_axis.tick_interval);
-        }
+            if data_label.is_default() {
+                continue;
+            }
 
-        xml_end_tag(&mut self.writer, "c:catAx");
-    }
+            xml_start_tag_only(&mut self.writer, "c:dLbl");
+            self.write_idx(index);
 
-    // -----------------------------------------------------------------------
-    // Date Axis.
-    // -----------------------------------------------------------------------
+            if data_label.is_hidden {
+                // Write the c:delete element.
+                self.write_delete();
+            } else {
+                // Add empty "c:spPr", as required, for Excel compatibility.
+                if !data_label.format.has_formatting() {
+                    if let Some(font) = &data_label.font {
+                        if font.color.is_auto_or_default() {
+                            xml_empty_tag_only(&mut self.writer, "c:spPr");
+                        }
+                    }
+                }
 
-    // Write the <c:dateAx> element.
-    fn write_date_ax(&mut self, x_axis: &ChartAxis, y_axis: &ChartAxis, axis_ids: (u32, u32)) {
-        xml_start_tag_only(&mut self.writer, "c:dateAx");
+                // If a custom point has a font then it may need to be applied
+                // to the title and/or the label.
+                let mut data_label = data_label.clone();
+                data_label.is_custom = true;
 
-        self.write_ax_id(axis_ids.0);
+                if let Some(font) = &mut data_label.font {
+                    font.has_baseline = false;
+                    write_layout = false;
+                }
 
-        // Write the c:scaling element.
-        self.write_scaling(x_axis);
+                if !data_label.title.name.is_empty() || data_label.title.range.has_data() {
+                    if let Some(font) = &data_label.font {
+                        data_label.title.set_font(font);
+                        data_label.title.font.has_baseline = false;
 
-        if x_axis.is_hidden {
-            self.write_delete();
-        }
+                        if !data_label.title.name.is_empty() {
+                            data_label.font = None;
+                        }
 
-        // Write the c:axPos element.
-        self.write_ax_pos(x_axis.axis_position, y_axis.reverse, y_axis.crossing);
+                        write_layout = true;
+                    }
+                }
 
-        self.write_major_gridlines(x_axis);
-        self.write_minor_gridlines(x_axis);
+                if write_layout {
+                    // Write the c:layout element.
+                    let layout = ChartLayout::default();
+                    self.write_layout(&layout);
+                }
 
-        // Write the c:title element.
-        self.write_chart_title(&x_axis.title);
+                // Write the c:tx element.
+                if !data_label.title.name.is_empty() {
+                    self.write_tx_rich(&data_label.title);
+                } else if data_label.title.range.has_data() {
+                    self.write_tx_formula(&data_label.title);
+                }
 
-        // Write the c:numFmt element.
-        if !x_axis.num_format.is_empty() {
-            self.write_number_format(&x_axis.num_format, x_axis.num_format_linked_to_source);
-        } else if self.category_has_num_format {
-            self.write_number_format("dd/mm/yyyy", true);
-        }
+                // Write the main elements of a data label.
+                self.write_data_label(&data_label);
+            }
 
-        // Write the c:majorTickMark element.
-        if let Some(tick_type) = x_axis.major_tick_type {
-            self.write_major_tick_mark(tick_type);
+            xml_end_tag(&mut self.writer, "c:dLbl");
         }
+    }
 
-        // Write the c:minorTickMark element.
-        if let Some(tick_type) = x_axis.minor_tick_type {
-            self.write_minor_tick_mark(tick_type);
+    fn write_data_label(&mut self, data_label: &ChartDataLabel) {
+        if !data_label.num_format.is_empty() {
+            // Write the c:numFmt element.
+            self.write_number_format(&data_label.num_format, false);
         }
 
-        // Write the c:tickLblPos element.
-        self.write_tick_label_position(x_axis.label_position);
-
-        if x_axis.format.has_formatting() {
-            // Write the c:spPr formatting element.
-            self.write_sp_pr(&x_axis.format);
-        }
+        // Write the c:spPr formatting element.
+        self.write_sp_pr(&data_label.format);
 
-        // Write the axis font elements.
-        if let Some(font) = &x_axis.font {
-            self.write_axis_font(&font.clone());
+        if let Some(font) = &data_label.font {
+            // Write the c:txPr element.
+            self.write_tx_pr(&font.clone(), false);
         }
 
-        // Write the c:crossAx element.
-        self.write_cross_ax(axis_ids.1);
-
-        // Write the c:crosses element. Note, the X crossing comes from the Y axis.
-        if self.has_crosses || !x_axis.is_hidden {
-            match y_axis.crossing {
-                ChartAxisCrossing::Automatic | ChartAxisCrossing::Min | ChartAxisCrossing::Max => {
-                    self.write_crosses(&y_axis.crossing.to_string());
-                }
-                ChartAxisCrossing::AxisValue(_) => {
-                    self.write_crosses_at(&y_axis.crossing.to_string());
-                }
-                ChartAxisCrossing::CategoryNumber(_) => {
-                    // Ignore Category crossing on a Value axis.
-                    self.write_crosses(&ChartAxisCrossing::Automatic.to_string());
-                }
-            }
+        if data_label.position != ChartDataLabelPosition::Default
+            && data_label.position != self.default_label_position
+        {
+            // Write the c:dLblPos element.
+            self.write_d_lbl_pos(data_label.position);
         }
 
-        // Write the c:auto element.
-        if x_axis.automatic {
-            self.write_auto();
+        if data_label.show_legend_key {
+            // Write the c:showLegendKey element.
+            self.write_show_legend_key();
         }
 
-        // Write the c:lblOffset element.
-        self.write_lbl_offset();
-
-        // Write the c:tickLblSkip element.
-        if x_axis.label_interval > 1 {
-            self.write_tick_lbl_skip(x_axis.label_interval);
+        // Ensure at least one display option is set.
+        if data_label.show_value
+            || (!data_label.is_custom
+                && !data_label.show_category_name
+                && !data_label.show_percentage)
+        {
+            // Write the c:showVal element.
+            self.write_show_val();
         }
 
-        // Write the c:tickMarkSkip element.
-        if x_axis.tick_interval > 1 {
-            self.write_tick_mark_skip(x_axis.tick_interval);
+        if data_label.show_category_name {
+            // Write the c:showCatName element.
+            self.write_show_category_name();
         }
 
-        // Write the c:majorUnit element.
-        if !x_axis.major_unit.is_empty() {
-            self.write_major_unit(&x_axis.major_unit);
+        if data_label.show_series_name {
+            // Write the c:showSerName element.
+            self.write_show_series_name();
         }
 
-        // Write the c:majorTimeUnit element.
-        if let Some(unit) = x_axis.major_unit_date_type {
-            self.write_major_time_unit(unit);
+        if data_label.show_percentage {
+            // Write the c:showPercent element.
+            self.write_show_percent();
         }
 
-        // Write the c:minorUnit element.
-        if !x_axis.minor_unit.is_empty() {
-            self.write_minor_unit(&x_axis.minor_unit);
+        if data_label.separator != ',' {
+            // Write the c:separator element.
+            self.write_separator(data_label.separator);
         }
 
-        // Write the c:minorTimeUnit element.
-        if let Some(unit) = x_axis.minor_unit_date_type {
-            self.write_minor_time_unit(unit);
+        if data_label.show_leader_lines {
+            match self.chart_group_type {
+                // Write the c:showLeaderLines element.
+                ChartType::Pie | ChartType::Doughnut => {
+                    self.write_show_leader_lines_2007();
+                }
+                _ => {
+                    self.write_show_leader_lines_2015();
+                }
+            }
         }
-
-        xml_end_tag(&mut self.writer, "c:dateAx");
     }
 
-    // -----------------------------------------------------------------------
-    // Value Axis.
-    // -----------------------------------------------------------------------
+    // Write the <c:trendline> element.
+    fn write_trendline(&mut self, trendline: &ChartTrendline) {
+        xml_start_tag_only(&mut self.writer, "c:trendline");
 
-    // Write the <c:valAx> element.
-    fn write_val_ax(&mut self, x_axis: &ChartAxis, y_axis: &ChartAxis, axis_ids: (u32, u32)) {
-        xml_start_tag_only(&mut self.writer, "c:valAx");
+        if !trendline.name.is_empty() {
+            // Write the c:name element.
+            self.write_trendline_name(&trendline.name);
+        }
 
-        self.write_ax_id(axis_ids.1);
+        // Write the c:spPr formatting element.
+        self.write_sp_pr(&trendline.format);
 
-        // Write the c:scaling element.
-        self.write_scaling(y_axis);
+        // Write the c:trendlineType element.
+        self.write_trendline_type(trendline);
 
-        if y_axis.is_hidden {
-            self.write_delete();
+        if let ChartTrendlineType::Polynomial(order) = trendline.trend_type {
+            self.write_order(order as usize);
         }
-        // Write the c:axPos element.
-        self.write_ax_pos(y_axis.axis_position, x_axis.reverse, x_axis.crossing);
-
-        // Write the Gridlines elements.
-        self.write_major_gridlines(y_axis);
-        self.write_minor_gridlines(y_axis);
-
-        // Write the c:title element.
-        self.write_chart_title(&y_axis.title);
 
-        // Write the c:numFmt element.
-        if y_axis.num_format.is_empty() {
-            self.write_number_format(&self.default_num_format.clone(), true);
-        } else {
-            self.write_number_format(&y_axis.num_format, y_axis.num_format_linked_to_source);
+        if let ChartTrendlineType::MovingAverage(period) = trendline.trend_type {
+            // Write the c:period element.
+            self.write_trendline_period(period);
         }
 
-        // Write the c:majorTickMark element.
-        if let Some(position) = y_axis.major_tick_type {
-            self.write_major_tick_mark(position);
+        if trendline.forward_period > 0.0 {
+            // Write the c:forward element.
+            self.write_trendline_forward(trendline.forward_period);
         }
 
-        // Write the c:minorTickMark element.
-        if let Some(position) = y_axis.minor_tick_type {
-            self.write_minor_tick_mark(position);
+        if trendline.backward_period > 0.0 {
+            // Write the c:backward element.
+            self.write_trendline_backward(trendline.backward_period);
         }
 
-        // Write the c:tickLblPos element.
-        self.write_tick_label_position(y_axis.label_position);
+        if let Some(intercept) = trendline.intercept {
+            // Write the c:intercept element.
+            self.write_trendline_intercept(intercept);
+        }
 
-        if y_axis.format.has_formatting() {
-            // Write the c:spPr formatting element.
-            self.write_sp_pr(&y_axis.format);
+        if trendline.display_r_squared {
+            // Write the c:dispRSqr element.
+            self.write_disp_rsqr();
         }
 
-        // Write the axis font elements.
-        if let Some(font) = &y_axis.font {
-            self.write_axis_font(font);
+        if trendline.display_equation {
+            // Write the c:dispEq element.
+            self.write_trendline_display_equation(trendline);
         }
 
-        // Write the c:crossAx element.
-        self.write_cross_ax(axis_ids.0);
+        xml_end_tag(&mut self.writer, "c:trendline");
+    }
 
-        // Write the c:crosses element. Note, the Y crossing comes from the X axis.
-        match x_axis.crossing {
-            ChartAxisCrossing::Automatic | ChartAxisCrossing::Min | ChartAxisCrossing::Max => {
-                self.write_crosses(&x_axis.crossing.to_string());
-            }
-            ChartAxisCrossing::CategoryNumber(_) | ChartAxisCrossing::AxisValue(_) => {
-                self.write_crosses_at(&x_axis.crossing.to_string());
-            }
-        }
+    // Write the <c:name> element.
+    fn write_trendline_name(&mut self, name: &str) {
+        xml_data_element_only(&mut self.writer, "c:name", name);
+    }
 
-        // Write the c:crossBetween element.
-        self.write_cross_between(x_axis.position_between_ticks);
+    // Write the <c:trendlineType> element.
+    fn write_trendline_type(&mut self, trendline: &ChartTrendline) {
+        let attributes = [("val", trendline.trend_type.to_string())];
 
-        // Write the c:majorUnit element.
-        if y_axis.axis_type != ChartAxisType::Category && !y_axis.major_unit.is_empty() {
-            self.write_major_unit(&y_axis.major_unit);
-        }
+        xml_empty_tag(&mut self.writer, "c:trendlineType", &attributes);
+    }
 
-        // Write the c:minorUnit element.
-        if y_axis.axis_type != ChartAxisType::Category && !y_axis.minor_unit.is_empty() {
-            self.write_minor_unit(&y_axis.minor_unit);
-        }
+    // Write the <c:forward> element.
+    fn write_trendline_forward(&mut self, value: f64) {
+        let attributes = [("val", value.to_string())];
 
-        // Write the c:dispUnits element.
-        if y_axis.display_units_type != ChartAxisDisplayUnitType::None {
-            self.write_disp_units(y_axis.display_units_type, y_axis.display_units_visible);
-        }
+        xml_empty_tag(&mut self.writer, "c:forward", &attributes);
+    }
 
-        xml_end_tag(&mut self.writer, "c:valAx");
+    // Write the <c:backward> element.
+    fn write_trendline_backward(&mut self, value: f64) {
+        let attributes = [("val", value.to_string())];
+
+        xml_empty_tag(&mut self.writer, "c:backward", &attributes);
     }
 
-    // -----------------------------------------------------------------------
-    // Category Value Axis. Only for Scatter charts.
-    // -----------------------------------------------------------------------
+    // Write the <c:dispRSqr> element.
+    fn write_disp_rsqr(&mut self) {
+        let attributes = [("val", "1")];
 
-    // Write the category <c:valAx> element for scatter charts.
-    fn write_cat_val_ax(&mut self, x_axis: &ChartAxis, y_axis: &ChartAxis, axis_ids: (u32, u32)) {
-        xml_start_tag_only(&mut self.writer, "c:valAx");
+        xml_empty_tag(&mut self.writer, "c:dispRSqr", &attributes);
+    }
 
-        self.write_ax_id(axis_ids.0);
+    // Write the <c:dispEq> element.
+    fn write_trendline_display_equation(&mut self, trendline: &ChartTrendline) {
+        let attributes = [("val", "1")];
 
-        // Write the c:scaling element.
-        self.write_scaling(x_axis);
+        xml_empty_tag(&mut self.writer, "c:dispEq", &attributes);
 
-        if x_axis.is_hidden {
-            self.write_delete();
-        }
+        // Write the c:trendlineLbl element.
+        self.write_trendline_label(trendline);
+    }
 
-        // Write the c:axPos element.
-        self.write_ax_pos(x_axis.axis_position, y_axis.reverse, y_axis.crossing);
+    // Write the <c:trendlineLbl> element.
+    fn write_trendline_label(&mut self, trendline: &ChartTrendline) {
+        xml_start_tag_only(&mut self.writer, "c:trendlineLbl");
 
-        // Write the Gridlines elements.
-        self.write_major_gridlines(x_axis);
-        self.write_minor_gridlines(x_axis);
+        // Write the c:layout element.
+        let layout = ChartLayout::default();
+        self.write_layout(&layout);
 
-        // Write the c:title element.
-        self.write_chart_title(&x_axis.title);
+        self.write_number_format("General", false);
 
-        // Write the c:numFmt element.
-        if x_axis.num_format.is_empty() {
-            self.write_number_format(&self.default_num_format.clone(), true);
-        } else {
-            self.write_number_format(&x_axis.num_format, x_axis.num_format_linked_to_source);
-        }
+        // Write the c:spPr formatting element.
+        self.write_sp_pr(&trendline.label_format);
 
-        // Write the c:majorTickMark element.
-        if let Some(position) = x_axis.major_tick_type {
-            self.write_major_tick_mark(position);
+        // Write the trendline label font elements.
+        if let Some(font) = &trendline.label_font {
+            self.write_axis_font(font);
         }
 
-        // Write the c:minorTickMark element.
-        if let Some(position) = x_axis.minor_tick_type {
-            self.write_minor_tick_mark(position);
-        }
+        xml_end_tag(&mut self.writer, "c:trendlineLbl");
+    }
 
-        // Write the c:tickLblPos element.
-        self.write_tick_label_position(x_axis.label_position);
+    // Write the <c:period> element.
+    fn write_trendline_period(&mut self, value: u8) {
+        let attributes = [("val", value.to_string())];
 
-        if x_axis.format.has_formatting() {
-            // Write the c:spPr formatting element.
-            self.write_sp_pr(&x_axis.format);
-        }
+        xml_empty_tag(&mut self.writer, "c:period", &attributes);
+    }
 
-        // Write the axis font elements.
-        if let Some(font) = &x_axis.font {
-            self.write_axis_font(font);
-        }
+    // Write the <c:intercept> element.
+    fn write_trendline_intercept(&mut self, value: f64) {
+        let attributes = [("val", value.to_string())];
 
-        // Write the c:crossAx element.
-        self.write_cross_ax(axis_ids.1);
+        xml_empty_tag(&mut self.writer, "c:intercept", &attributes);
+    }
 
-        // Write the c:crosses element. Note, the X crossing comes from the Y axis.
-        match y_axis.crossing {
-            ChartAxisCrossing::Automatic | ChartAxisCrossing::Min | ChartAxisCrossing::Max => {
-                self.write_crosses(&y_axis.crossing.to_string());
-            }
-            ChartAxisCrossing::CategoryNumber(_) | ChartAxisCrossing::AxisValue(_) => {
-                self.write_crosses_at(&y_axis.crossing.to_string());
-            }
-        }
+    // Write the <c:errBars> element.
+    fn write_error_bar(&mut self, axis: &str, error_bars: &ChartErrorBars) {
+        xml_start_tag_only(&mut self.writer, "c:errBars");
+
+        // Write the c:errDir element.
+        self.write_error_bar_direction(axis);
+
+        // Write the c:errBarType element.
+        self.write_error_bar_type(error_bars.direction);
 
-        // Write the c:crossBetween element.
-        self.write_cross_between(y_axis.position_between_ticks);
+        // Write the c:errValType element.
+        self.write_err_direction_type(&error_bars.error_type);
 
-        // Write the c:majorUnit element.
-        if x_axis.axis_type != ChartAxisType::Category && !x_axis.major_unit.is_empty() {
-            self.write_major_unit(&x_axis.major_unit);
+        if !error_bars.has_end_cap {
+            // Write the c:noEndCap element.
+            self.write_error_bar_no_end_cap();
         }
 
-        // Write the c:minorUnit element.
-        if x_axis.axis_type != ChartAxisType::Category && !x_axis.minor_unit.is_empty() {
-            self.write_minor_unit(&x_axis.minor_unit);
+        match &error_bars.error_type {
+            ChartErrorBarsType::FixedValue(value)
+            | ChartErrorBarsType::Percentage(value)
+            | ChartErrorBarsType::StandardDeviation(value) => {
+                // Write the c:val element.
+                self.write_error_value(*value);
+            }
+            ChartErrorBarsType::Custom(_, _) => self.write_custom_error_bar_values(error_bars),
+            ChartErrorBarsType::StandardError => {}
         }
 
-        // Write the c:dispUnits element.
-        if x_axis.display_units_type != ChartAxisDisplayUnitType::None {
-            self.write_disp_units(x_axis.display_units_type, x_axis.display_units_visible);
-        }
+        // Write the c:spPr formatting element.
+        self.write_sp_pr(&error_bars.format);
 
-        xml_end_tag(&mut self.writer, "c:valAx");
+        xml_end_tag(&mut self.writer, "c:errBars");
     }
 
-    // Write the <c:scaling> element.
-    fn write_scaling(&mut self, axis: &ChartAxis) {
-        xml_start_tag_only(&mut self.writer, "c:scaling");
-
-        // Write the c:logBase element.
-        if axis.axis_type != ChartAxisType::Category && axis.log_base >= 2 {
-            self.write_log_base(axis.log_base);
-        }
-
-        // Write the c:orientation element.
-        self.write_orientation(axis.reverse);
-
-        // Write the c:max element.
-        if axis.axis_type != ChartAxisType::Category && !axis.max.is_empty() {
-            self.write_max(&axis.max);
+    // Write the <c:errDir> element.
+    fn write_error_bar_direction(&mut self, axis: &str) {
+        if !axis.is_empty() {
+            let attributes = vec![("val", axis.to_string())];
+            xml_empty_tag(&mut self.writer, "c:errDir", &attributes);
         }
+    }
 
-        // Write the c:min element.
-        if axis.axis_type != ChartAxisType::Category && !axis.min.is_empty() {
-            self.write_min(&axis.min);
-        }
+    // Write the <c:errBarType> element.
+    fn write_error_bar_type(&mut self, direction: ChartErrorBarsDirection) {
+        let attributes = vec![("val", direction.to_string())];
 
-        xml_end_tag(&mut self.writer, "c:scaling");
+        xml_empty_tag(&mut self.writer, "c:errBarType", &attributes);
     }
 
-    // Write the <c:logBase> element.
-    fn write_log_base(&mut self, base: u16) {
-        let attributes = [("val", base.to_string())];
+    // Write the <c:errValType> element.
+    fn write_err_direction_type(&mut self, bar_type: &ChartErrorBarsType) {
+        let attributes = vec![("val", bar_type.to_string())];
 
-        xml_empty_tag(&mut self.writer, "c:logBase", &attributes);
+        xml_empty_tag(&mut self.writer, "c:errValType", &attributes);
     }
 
-    // Write the <c:orientation> element.
-    fn write_orientation(&mut self, reverse: bool) {
-        let attributes = if reverse {
-            [("val", "maxMin")]
-        } else {
-            [("val", "minMax")]
-        };
+    // Write the <c:noEndCap> element.
+    fn write_error_bar_no_end_cap(&mut self) {
+        let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:orientation", &attributes);
+        xml_empty_tag(&mut self.writer, "c:noEndCap", &attributes);
     }
 
-    // Write the <c:max> element.
-    fn write_max(&mut self, max: &str) {
-        let attributes = [("val", max.to_string())];
+    // Write the <c:val> element.
+    fn write_error_value(&mut self, value: f64) {
+        let attributes = [("val", value.to_string())];
 
-        xml_empty_tag(&mut self.writer, "c:max", &attributes);
+        xml_empty_tag(&mut self.writer, "c:val", &attributes);
     }
 
-    // Write the <c:min> element.
-    fn write_min(&mut self, min: &str) {
-        let attributes = [("val", min.to_string())];
+    // Write the custom error sub-elements
+    fn write_custom_error_bar_values(&mut self, error_bars: &ChartErrorBars) {
+        xml_start_tag_only(&mut self.writer, "c:plus");
+        self.write_cache_ref(&error_bars.plus_range, true);
+        xml_end_tag(&mut self.writer, "c:plus");
 
-        xml_empty_tag(&mut self.writer, "c:min", &attributes);
+        xml_start_tag_only(&mut self.writer, "c:minus");
+        self.write_cache_ref(&error_bars.minus_range, true);
+        xml_end_tag(&mut self.writer, "c:minus");
     }
 
-    // Write the <c:axPos> element.
-    fn write_ax_pos(
-        &mut self,
-        position: ChartAxisPosition,
-        reverse: bool,
-        crossing: ChartAxisCrossing,
-    ) {
-        let mut position = position;
-
-        if reverse || crossing == ChartAxisCrossing::Max {
-            position = position.reverse();
-        }
+    // Write the <c:upDownBars> element.
+    fn write_up_down_bars(&mut self) {
+        xml_start_tag_only(&mut self.writer, "c:upDownBars");
 
-        let attributes = [("val", position.to_string())];
+        // Write the c:gapWidth element.
+        self.write_gap_width(150);
 
-        xml_empty_tag(&mut self.writer, "c:axPos", &attributes);
-    }
+        // Write the c:upBars element.
+        self.write_up_bars();
 
-    // Write the <c:numFmt> element.
-    fn write_number_format(&mut self, format: &str, linked: bool) {
-        let attributes = [
-            ("formatCode", format.to_string()),
-            ("sourceLinked", linked.to_xml_bool()),
-        ];
+        // Write the c:downBars element.
+        self.write_down_bars();
 
-        xml_empty_tag(&mut self.writer, "c:numFmt", &attributes);
+        xml_end_tag(&mut self.writer, "c:upDownBars");
     }
 
-    // Write the <c:majorGridlines> element.
-    fn write_major_gridlines(&mut self, axis: &ChartAxis) {
-        if axis.major_gridlines {
-            if let Some(line) = &axis.major_gridlines_line {
-                xml_start_tag_only(&mut self.writer, "c:majorGridlines");
-                xml_start_tag_only(&mut self.writer, "c:spPr");
+    // Write the <c:upBars> element.
+    fn write_up_bars(&mut self) {
+        if self.up_bar_format.has_formatting() {
+            xml_start_tag_only(&mut self.writer, "c:upBars");
 
-                // Write the a:ln element.
-                self.write_a_ln(line);
+            // Write the c:spPr element.
+            self.write_sp_pr(&self.up_bar_format.clone());
 
-                xml_end_tag(&mut self.writer, "c:spPr");
-                xml_end_tag(&mut self.writer, "c:majorGridlines");
-            } else {
-                xml_empty_tag_only(&mut self.writer, "c:majorGridlines");
-            }
+            xml_end_tag(&mut self.writer, "c:upBars");
+        } else {
+            xml_empty_tag_only(&mut self.writer, "c:upBars");
         }
     }
 
-    // Write the <c:minorGridlines> element.
-    fn write_minor_gridlines(&mut self, axis: &ChartAxis) {
-        if axis.minor_gridlines {
-            if let Some(line) = &axis.minor_gridlines_line {
-                xml_start_tag_only(&mut self.writer, "c:minorGridlines");
-                xml_start_tag_only(&mut self.writer, "c:spPr");
+    // Write the <c:downBars> element.
+    fn write_down_bars(&mut self) {
+        if self.down_bar_format.has_formatting() {
+            xml_start_tag_only(&mut self.writer, "c:downBars");
 
-                // Write the a:ln element.
-                self.write_a_ln(line);
+            // Write the c:spPr element.
+            self.write_sp_pr(&self.down_bar_format.clone());
 
-                xml_end_tag(&mut self.writer, "c:spPr");
-                xml_end_tag(&mut self.writer, "c:minorGridlines");
-            } else {
-                xml_empty_tag_only(&mut self.writer, "c:minorGridlines");
-            }
+            xml_end_tag(&mut self.writer, "c:downBars");
+        } else {
+            xml_empty_tag_only(&mut self.writer, "c:downBars");
         }
     }
 
-    // Write the <c:tickLblPos> element.
-    fn write_tick_label_position(&mut self, position: ChartAxisLabelPosition) {
-        let attributes = [("val", position.to_string())];
-
-        xml_empty_tag(&mut self.writer, "c:tickLblPos", &attributes);
-    }
+    // Write the <c:hiLowLines> element.
+    fn write_hi_low_lines(&mut self) {
+        if self.high_low_lines_format.has_formatting() {
+            xml_start_tag_only(&mut self.writer, "c:hiLowLines");
 
-    // Write the <c:crossAx> element.
-    fn write_cross_ax(&mut self, axis_id: u32) {
-        let attributes = [("val", axis_id.to_string())];
+            // Write the c:spPr element.
+            self.write_sp_pr(&self.high_low_lines_format.clone());
 
-        xml_empty_tag(&mut self.writer, "c:crossAx", &attributes);
+            xml_end_tag(&mut self.writer, "c:hiLowLines");
+        } else {
+            xml_empty_tag_only(&mut self.writer, "c:hiLowLines");
+        }
     }
 
-    // Write the <c:crosses> element.
-    fn write_crosses(&mut self, crossing: &str) {
-        let attributes = [("val", crossing)];
+    // Write the <c:dropLines> element.
+    fn write_drop_lines(&mut self) {
+        if self.drop_lines_format.has_formatting() {
+            xml_start_tag_only(&mut self.writer, "c:dropLines");
 
-        xml_empty_tag(&mut self.writer, "c:crosses", &attributes);
+            // Write the c:spPr element.
+            self.write_sp_pr(&self.drop_lines_format.clone());
+
+            xml_end_tag(&mut self.writer, "c:dropLines");
+        } else {
+            xml_empty_tag_only(&mut self.writer, "c:dropLines");
+        }
     }
 
-    // Write the <c:crossesAt> element.
-    fn write_crosses_at(&mut self, crossing: &str) {
-        let attributes = [("val", crossing)];
+    // Write the <c:dTable> element.
+    fn write_data_table(&mut self, table: &ChartDataTable) {
+        xml_start_tag_only(&mut self.writer, "c:dTable");
 
-        xml_empty_tag(&mut self.writer, "c:crossesAt", &attributes);
-    }
+        // Write the c:showHorzBorder element.
+        if table.show_horizontal_borders {
+            self.write_show_horz_border();
+        }
 
-    // Write the <c:auto> element.
-    fn write_auto(&mut self) {
-        let attributes = [("val", "1")];
+        // Write the c:showVertBorder element.
+        if table.show_vertical_borders {
+            self.write_show_vert_border();
+        }
 
-        xml_empty_tag(&mut self.writer, "c:auto", &attributes);
-    }
+        // Write the c:showOutline element.
+        if table.show_outline_borders {
+            self.write_show_outline();
+        }
 
-    // Write the <c:lblAlgn> element.
-    fn write_lbl_algn(&mut self, position: &str) {
-        let attributes = [("val", position)];
+        // Write the c:showKeys element.
+        if table.show_legend_keys {
+            self.write_show_keys();
+        }
 
-        xml_empty_tag(&mut self.writer, "c:lblAlgn", &attributes);
-    }
+        // Write the c:spPr element.
+        self.write_sp_pr(&table.format);
 
-    // Write the <c:lblOffset> element.
-    fn write_lbl_offset(&mut self) {
-        let attributes = [("val", "100")];
+        // Write the trendline label font elements.
+        if let Some(font) = &table.font {
+            self.write_axis_font(font);
+        }
 
-        xml_empty_tag(&mut self.writer, "c:lblOffset", &attributes);
+        xml_end_tag(&mut self.writer, "c:dTable");
     }
 
-    // Write the <c:crossBetween> element.
-    fn write_cross_between(&mut self, position_between_ticks: bool) {
-        let attributes = if position_between_ticks {
-            [("val", "between")]
-        } else {
-            [("val", "midCat")]
-        };
+    // Write the <c:showKeys> element.
+    fn write_show_keys(&mut self) {
+        let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:crossBetween", &attributes);
+        xml_empty_tag(&mut self.writer, "c:showKeys", &attributes);
     }
 
-    // Write the <c:tickLblSkip> element.
-    fn write_tick_lbl_skip(&mut self, units: u16) {
-        let attributes = [("val", units.to_string())];
+    // Write the <c:showHorzBorder> element.
+    fn write_show_horz_border(&mut self) {
+        let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:tickLblSkip", &attributes);
+        xml_empty_tag(&mut self.writer, "c:showHorzBorder", &attributes);
     }
 
-    // Write the <c:tickMarkSkip> element.
-    fn write_tick_mark_skip(&mut self, units: u16) {
-        let attributes = [("val", units.to_string())];
+    // Write the <c:showVertBorder> element.
+    fn write_show_vert_border(&mut self) {
+        let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:tickMarkSkip", &attributes);
+        xml_empty_tag(&mut self.writer, "c:showVertBorder", &attributes);
     }
 
-    // Write the <c:majorUnit> element.
-    fn write_major_unit(&mut self, value: &str) {
-        let attributes = [("val", value)];
+    // Write the <c:showOutline> element.
+    fn write_show_outline(&mut self) {
+        let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:majorUnit", &attributes);
+        xml_empty_tag(&mut self.writer, "c:showOutline", &attributes);
     }
 
-    // Write the <c:minorUnit> element.
-    fn write_minor_unit(&mut self, value: &str) {
-        let attributes = [("val", value)];
+    // Write the <c:showVal> element.
+    fn write_show_val(&mut self) {
+        let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:minorUnit", &attributes);
+        xml_empty_tag(&mut self.writer, "c:showVal", &attributes);
     }
 
-    // Write the <c:majorTimeUnit> element.
-    fn write_major_time_unit(&mut self, units: ChartAxisDateUnitType) {
-        let attributes = [("val", units.to_string())];
+    // Write the <c:showCatName> element.
+    fn write_show_category_name(&mut self) {
+        let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:majorTimeUnit", &attributes);
+        xml_empty_tag(&mut self.writer, "c:showCatName", &attributes);
     }
 
-    // Write the <c:minorTimeUnit> element.
-    fn write_minor_time_unit(&mut self, units: ChartAxisDateUnitType) {
-        let attributes = [("val", units.to_string())];
+    // Write the <c:showSerName> element.
+    fn write_show_series_name(&mut self) {
+        let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:minorTimeUnit", &attributes);
+        xml_empty_tag(&mut self.writer, "c:showSerName", &attributes);
     }
 
-    // Write the <c:dispUnits> element.
-    fn write_disp_units(&mut self, units: ChartAxisDisplayUnitType, visible: bool) {
-        xml_start_tag_only(&mut self.writer, "c:dispUnits");
-
-        // Write the c:builtInUnit element.
-        self.write_built_in_unit(units);
+    // Write the <c:showPercent> element.
+    fn write_show_percent(&mut self) {
+        let attributes = [("val", "1")];
 
-        // Write the c:dispUnitsLbl element.
-        if visible {
-            self.write_disp_units_lbl();
-        }
+        xml_empty_tag(&mut self.writer, "c:showPercent", &attributes);
+    }
 
-        xml_end_tag(&mut self.writer, "c:dispUnits");
+    // Write the <c:separator> element.
+    fn write_separator(&mut self, separator: char) {
+        xml_data_element_only(&mut self.writer, "c:separator", &format!("{separator} "));
     }
 
-    // Write the <c:builtInUnit> element.
-    fn write_built_in_unit(&mut self, units: ChartAxisDisplayUnitType) {
-        let attributes = [("val", units.to_string())];
+    // Write the <c:showLeaderLines> element for Excel 2007 (mainly only for Pie
+    // and Doughnut).
+    fn write_show_leader_lines_2007(&mut self) {
+        let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:builtInUnit", &attributes);
+        xml_empty_tag(&mut self.writer, "c:showLeaderLines", &attributes);
     }
 
-    // Write the <c:dispUnitsLbl> element.
-    fn write_disp_units_lbl(&mut self) {
-        xml_start_tag_only(&mut self.writer, "c:dispUnitsLbl");
+    // Write the <c:showLeaderLines> element for Excel 2015+ (mainly for charts
+    // that aren't Pie or Doughnut).
+    fn write_show_leader_lines_2015(&mut self) {
+        let attributes = [
+            ("uri", "{CE6537A1-D6FC-4f65-9D91-7224C49458BB}"),
+            (
+                "xmlns:c15",
+                "http://schemas.microsoft.com/office/drawing/2012/chart",
+            ),
+        ];
 
-        // Write the c:layout element.
-        let layout = ChartLayout::default();
-        self.write_layout(&layout);
+        xml_start_tag_only(&mut self.writer, "c:extLst");
+        xml_start_tag(&mut self.writer, "c:ext", &attributes);
 
-        xml_end_tag(&mut self.writer, "c:dispUnitsLbl");
+        xml_empty_tag(&mut self.writer, "c15:showLeaderLines", &[("val", "1")]);
+        xml_end_tag(&mut self.writer, "c:ext");
+        xml_end_tag(&mut self.writer, "c:extLst");
     }
 
-    // Write the <c:legend> element.
-    fn write_legend(&mut self) {
-        if self.legend.hidden {
-            return;
-        }
-
-        xml_start_tag_only(&mut self.writer, "c:legend");
-
-        // Write the c:legendPos element.
-        self.write_legend_pos();
+    // Write the <c:showLegendKey> element.
+    fn write_show_legend_key(&mut self) {
+        let attributes = [("val", "1")];
 
-        // Check for series and trendlines that should be deleted/hidden from
-        // the legend.
-        let deleted_entries = self.deleted_legend_entries();
+        xml_empty_tag(&mut self.writer, "c:showLegendKey", &attributes);
+    }
 
-        if !deleted_entries.is_empty() {
-            for index in deleted_entries {
-                // Write the c:legendEntry element.
-                self.write_legend_entry(index);
-            }
-        }
+    // Write the <c:dLblPos> element.
+    fn write_d_lbl_pos(&mut self, position: ChartDataLabelPosition) {
+        let attributes = [("val", position.to_string())];
 
-        // Write the c:layout element.
-        self.write_layout(&self.legend.layout.clone());
+        xml_empty_tag(&mut self.writer, "c:dLblPos", &attributes);
+    }
 
-        // Write the c:spPr formatting element.
-        self.write_sp_pr(&self.legend.format.clone());
+    // Write the <c:delete> element.
+    fn write_delete(&mut self) {
+        let attributes = [("val", "1")];
 
-        // Write the c:overlay element.
-        if self.legend.has_overlay {
-            self.write_overlay();
-        }
+        xml_empty_tag(&mut self.writer, "c:delete", &attributes);
+    }
 
-        // Pie/Doughnut charts set the "rtl" flag to "0" in the legend font even
-        // though "0" is implied. To match Excel output we set it if it hasn't
-        // been set by the user.
-        if self.chart_type == ChartType::Pie || self.chart_type == ChartType::Doughnut {
-            match &mut self.legend.font {
-                Some(font) => {
-                    if font.right_to_left.is_none() {
-                        font.set_right_to_left(false);
-                    }
-                }
-                None => {
-                    let mut font = ChartFont::new();
-                    font.set_right_to_left(false);
-                    self.legend.font = Some(font);
-                }
-            }
-        }
+    // Write the <c:symbol> element.
+    fn write_symbol(&mut self, marker: &ChartMarker) {
+        let mut attributes = vec![];
 
-        if let Some(font) = &self.legend.font {
-            // Write the c:txPr element.
-            self.write_tx_pr(&font.clone(), false);
+        if let Some(marker_type) = marker.marker_type {
+            attributes.push(("val", marker_type.to_string()));
+        } else if marker.none {
+            attributes.push(("val", "none".to_string()));
         }
 
-        xml_end_tag(&mut self.writer, "c:legend");
+        xml_empty_tag(&mut self.writer, "c:symbol", &attributes);
     }
 
-    // Write the <c:legendPos> element.
-    fn write_legend_pos(&mut self) {
-        let attributes = [("val", self.legend.position.to_string())];
+    // Write the <c:size> element.
+    fn write_size(&mut self, size: u8) {
+        let attributes = [("val", size.to_string())];
 
-        xml_empty_tag(&mut self.writer, "c:legendPos", &attributes);
+        xml_empty_tag(&mut self.writer, "c:size", &attributes);
     }
 
-    // Write the <c:legendEntry> element.
-    fn write_legend_entry(&mut self, index: usize) {
-        xml_start_tag_only(&mut self.writer, "c:legendEntry");
-
-        // Write the c:idx element.
-        self.write_idx(index);
-
-        // Write the c:delete element.
-        self.write_delete();
-
-        xml_end_tag(&mut self.writer, "c:legendEntry");
-    }
+    // Write the <c:varyColors> element.
+    fn write_vary_colors(&mut self) {
+        let attributes = [("val", "1")];
 
-    // Write the <c:overlay> element.
-    fn write_overlay(&mut self) {
-        xml_empty_tag(&mut self.writer, "c:overlay", &[("val", "1")]);
+        xml_empty_tag(&mut self.writer, "c:varyColors", &attributes);
     }
 
-    // Write the <c:plotVisOnly> element.
-    fn write_plot_vis_only(&mut self) {
-        let attributes = [("val", "1")];
+    // Write the <c:firstSliceAng> element.
+    fn write_first_slice_ang(&mut self) {
+        let attributes = [("val", self.rotation.to_string())];
 
-        xml_empty_tag(&mut self.writer, "c:plotVisOnly", &attributes);
+        xml_empty_tag(&mut self.writer, "c:firstSliceAng", &attributes);
     }
 
-    // Write the <c:printSettings> element.
-    fn write_print_settings(&mut self) {
-        xml_start_tag_only(&mut self.writer, "c:printSettings");
+    // Write the <c:holeSize> element.
+    fn write_hole_size(&mut self) {
+        let attributes = [("val", self.hole_size.to_string())];
 
-        // Write the c:headerFooter element.
-        self.write_header_footer();
+        xml_empty_tag(&mut self.writer, "c:holeSize", &attributes);
+    }
 
-        // Write the c:pageMargins element.
-        self.write_page_margins();
+    // Write the <c:txPr> element.
+    fn write_axis_font(&mut self, font: &ChartFont) {
+        self.write_font_properties("c:txPr", font);
+    }
 
-        // Write the c:pageSetup element.
-        self.write_page_setup();
+    // Write the <c:txPr> or <cx:txPr> text properties element.
+    fn write_font_properties(&mut self, tag: &str, font: &ChartFont) {
+        xml_start_tag_only(&mut self.writer, tag);
 
-        xml_end_tag(&mut self.writer, "c:printSettings");
-    }
+        // Write the a:bodyPr element.
+        self.write_a_body_pr(font, false);
 
-    // Write the <c:headerFooter> element.
-    fn write_header_footer(&mut self) {
-        xml_empty_tag_only(&mut self.writer, "c:headerFooter");
-    }
+        // Write the a:lstStyle element.
+        self.write_a_lst_style();
 
-    // Write the <c:pageMargins> element.
-    fn write_page_margins(&mut self) {
-        let attributes = [
-            ("b", "0.75"),
-            ("l", "0.7"),
-            ("r", "0.7"),
-            ("t", "0.75"),
-            ("header", "0.3"),
-            ("footer", "0.3"),
-        ];
+        xml_start_tag_only(&mut self.writer, "a:p");
 
-        xml_empty_tag(&mut self.writer, "c:pageMargins", &attributes);
-    }
+        // Write the a:pPr element.
+        self.write_a_p_pr_rich(font);
 
-    // Write the <c:pageSetup> element.
-    fn write_page_setup(&mut self) {
-        xml_empty_tag_only(&mut self.writer, "c:pageSetup");
-    }
+        // Write the a:endParaRPr element.
+        self.write_a_end_para_rpr();
 
-    // Write the <c:marker> element.
-    fn write_marker_value(&mut self) {
-        let attributes = [("val", "1")];
+        xml_end_tag(&mut self.writer, "a:p");
 
-        xml_empty_tag(&mut self.writer, "c:marker", &attributes);
+        xml_end_tag(&mut self.writer, tag);
     }
 
-    // Write the <c:marker> element.
-    fn write_marker(&mut self, marker: &ChartMarker) {
-        xml_start_tag_only(&mut self.writer, "c:marker");
+    // Write the <c:txPr> element.
+    fn write_tx_pr(&mut self, font: &ChartFont, is_horizontal: bool) {
+        xml_start_tag_only(&mut self.writer, "c:txPr");
 
-        // Write the c:symbol element.
-        self.write_symbol(marker);
+        // Write the a:bodyPr element.
+        self.write_a_body_pr(font, is_horizontal);
 
-        if marker.size != 0 {
-            // Write the c:size element.
-            self.write_size(marker.size);
-        }
+        // Write the a:lstStyle element.
+        self.write_a_lst_style();
 
-        if marker.format.has_formatting() {
-            // Write the c:spPr formatting element.
-            self.write_sp_pr(&marker.format);
-        }
+        // Write the a:p element.
+        self.write_a_p_formula(font);
 
-        xml_end_tag(&mut self.writer, "c:marker");
+        xml_end_tag(&mut self.writer, "c:txPr");
     }
 
-    // Write the <c:dLbls> element.
-    fn write_data_labels(
-        &mut self,
-        data_label: &ChartDataLabel,
-        custom_data_labels: &[ChartDataLabel],
-        max_points: usize,
-    ) {
-        xml_start_tag_only(&mut self.writer, "c:dLbls");
+    // Write the <a:p> element.
+    fn write_a_p_formula(&mut self, font: &ChartFont) {
+        xml_start_tag_only(&mut self.writer, "a:p");
 
-        if !custom_data_labels.is_empty() {
-            self.write_custom_data_labels(custom_data_labels, max_points);
-        }
+        // Write the a:pPr element.
+        self.write_a_p_pr(font);
 
-        // Write the main elements of a data label.
-        self.write_data_label(data_label);
+        // Write the a:endParaRPr element.
+        self.write_a_end_para_rpr();
 
-        xml_end_tag(&mut self.writer, "c:dLbls");
+        xml_end_tag(&mut self.writer, "a:p");
     }
 
-    // Write the <c:dLbl> element.
-    fn write_custom_data_labels(&mut self, data_labels: &[ChartDataLabel], max_points: usize) {
-        // Write the point formatting for the series.
-        for (index, data_label) in data_labels.iter().enumerate() {
-            let mut write_layout = true;
+    // Write the <a:pPr> element.
+    fn write_a_p_pr(&mut self, font: &ChartFont) {
+        let mut attributes = vec![];
 
-            if index >= max_points {
-                break;
-            }
+        if let Some(right_to_left) = font.right_to_left {
+            attributes.push(("rtl", right_to_left.to_xml_bool()));
+        }
 
-            if data_label.is_default() {
-                continue;
-            }
+        xml_start_tag(&mut self.writer, "a:pPr", &attributes);
 
-            xml_start_tag_only(&mut self.writer, "c:dLbl");
-            self.write_idx(index);
+        // Write the a:defRPr element.
+        self.write_a_def_rpr(font);
 
-            if data_label.is_hidden {
-                // Write the c:delete element.
-                self.write_delete();
-            } else {
-                // Add empty "c:spPr", as required, for Excel compatibility.
-                if !data_label.format.has_formatting() {
-                    if let Some(font) = &data_label.font {
-                        if font.color.is_auto_or_default() {
-                            xml_empty_tag_only(&mut self.writer, "c:spPr");
-                        }
-                    }
-                }
+        xml_end_tag(&mut self.writer, "a:pPr");
+    }
 
-                // If a custom point has a font then it may need to be applied
-                // to the title and/or the label.
-                let mut data_label = data_label.clone();
-                data_label.is_custom = true;
+    // Write the <a:bodyPr> element.
+    fn write_a_body_pr(&mut self, font: &ChartFont, is_horizontal: bool) {
+        let mut attributes = vec![];
 
-                if let Some(font) = &mut data_label.font {
-                    font.has_baseline = false;
-                    write_layout = false;
+        let rotation = match font.rotation {
+            Some(rotation) => rotation,
+            None => {
+                // Handle defaults for vertical and horizontal rotations.
+                if is_horizontal {
+                    -90
+                } else {
+                    360 // To distinguish from user defined 0.
                 }
+            }
+        };
 
-                if !data_label.title.name.is_empty() || data_label.title.range.has_data() {
-                    if let Some(font) = &data_label.font {
-                        data_label.title.set_font(font);
-                        data_label.title.font.has_baseline = false;
+        match rotation {
+            360 => {}
+            270 => {
+                // Stacked text.
+                attributes.push(("rot", "0".to_string()));
+                attributes.push(("vert", "wordArtVert".to_string()));
+            }
+            271 => {
+                // East Asian vertical.
+                attributes.push(("rot", "0".to_string()));
+                attributes.push(("vert", "eaVert".to_string()));
+            }
+            _ => {
+                // Convert the rotation angle to Excel's units.
+                let rotation = i32::from(rotation) * 60_000;
+                attributes.push(("rot", rotation.to_string()));
+                attributes.push(("vert", "horz".to_string()));
+            }
+        }
 
-                        if !data_label.title.name.is_empty() {
-                            data_label.font = None;
-                        }
+        xml_empty_tag(&mut self.writer, "a:bodyPr", &attributes);
+    }
 
-                        write_layout = true;
-                    }
-                }
+    // Write the <a:lstStyle> element.
+    fn write_a_lst_style(&mut self) {
+        xml_empty_tag_only(&mut self.writer, "a:lstStyle");
+    }
 
-                if write_layout {
-                    // Write the c:layout element.
-                    let layout = ChartLayout::default();
-                    self.write_layout(&layout);
-                }
+    // Write the <a:defRPr> element.
+    fn write_a_def_rpr(&mut self, font: &ChartFont) {
+        self.write_font_elements("a:defRPr", font);
+    }
 
-                // Write the c:tx element.
-                if !data_label.title.name.is_empty() {
-                    self.write_tx_rich(&data_label.title);
-                } else if data_label.title.range.has_data() {
-                    self.write_tx_formula(&data_label.title);
-                }
+    // Write the <a:rPr> element.
+    fn write_a_r_pr(&mut self, font: &ChartFont) {
+        self.write_font_elements("a:rPr", font);
+    }
 
-                // Write the main elements of a data label.
-                self.write_data_label(&data_label);
-            }
+    // Write font sub-elements shared between <a:defRPr> and <a:rPr> elements.
+    fn write_font_elements(&mut self, tag: &str, font: &ChartFont) {
+        let mut attributes = vec![];
 
-            xml_end_tag(&mut self.writer, "c:dLbl");
+        if tag == "a:rPr" {
+            attributes.push(("lang", "en-US".to_string()));
         }
-    }
 
-    fn write_data_label(&mut self, data_label: &ChartDataLabel) {
-        if !data_label.num_format.is_empty() {
-            // Write the c:numFmt element.
-            self.write_number_format(&data_label.num_format, false);
+        if font.size > 0.0 {
+            attributes.push(("sz", font.size.to_string()));
         }
 
-        // Write the c:spPr formatting element.
-        self.write_sp_pr(&data_label.format);
-
-        if let Some(font) = &data_label.font {
-            // Write the c:txPr element.
-            self.write_tx_pr(&font.clone(), false);
+        if let Some(boolean) = font.bold {
+            attributes.push(("b", boolean.to_xml_bool()));
         }
 
-        if data_label.position != ChartDataLabelPosition::Default
-            && data_label.position != self.default_label_position
-        {
-            // Write the c:dLblPos element.
-            self.write_d_lbl_pos(data_label.position);
+        if font.italic || (font.bold.is_some() && !font.has_default_bold) {
+            attributes.push(("i", font.italic.to_xml_bool()));
         }
 
-        if data_label.show_legend_key {
-            // Write the c:showLegendKey element.
-            self.write_show_legend_key();
+        if font.underline {
+            attributes.push(("u", "sng".to_string()));
         }
 
-        // Ensure at least one display option is set.
-        if data_label.show_value
-            || (!data_label.is_custom
-                && !data_label.show_category_name
-                && !data_label.show_percentage)
-        {
-            // Write the c:showVal element.
-            self.write_show_val();
+        if font.has_baseline {
+            attributes.push(("baseline", "0".to_string()));
         }
 
-        if data_label.show_category_name {
-            // Write the c:showCatName element.
-            self.write_show_category_name();
-        }
+        if font.is_latin() || !font.color.is_auto_or_default() {
+            xml_start_tag(&mut self.writer, tag, &attributes);
 
-        if data_label.show_series_name {
-            // Write the c:showSerName element.
-            self.write_show_series_name();
-        }
+            if !font.color.is_auto_or_default() {
+                self.write_a_solid_fill(font.color, 0);
+            }
 
-        if data_label.show_percentage {
-            // Write the c:showPercent element.
-            self.write_show_percent();
-        }
+            if font.is_latin() {
+                // Write the a:latin element.
+                self.write_a_latin(font);
+            }
 
-        if data_label.separator != ',' {
-            // Write the c:separator element.
-            self.write_separator(data_label.separator);
+            xml_end_tag(&mut self.writer, tag);
+        } else {
+            xml_empty_tag(&mut self.writer, tag, &attributes);
         }
+    }
 
-        if data_label.show_leader_lines {
-            match self.chart_group_type {
-                // Write the c:showLeaderLines element.
-                ChartType::Pie | ChartType::Doughnut => {
-                    self.write_show_leader_lines_2007();
-                }
-                _ => {
-                    self.write_show_leader_lines_2015();
-                }
-            }
+    // Write the <a:latin> element.
+    fn write_a_latin(&mut self, font: &ChartFont) {
+        let mut attributes = vec![];
+
+        if !font.name.is_empty() {
+            attributes.push(("typeface", font.name.clone()));
         }
-    }
 
-    // Write the <c:trendline> element.
-    fn write_trendline(&mut self, trendline: &ChartTrendline) {
-        xml_start_tag_only(&mut self.writer, "c:trendline");
+        if font.pitch_family > 0 {
+            attributes.push(("pitchFamily", font.pitch_family.to_string()));
+        }
 
-        if !trendline.name.is_empty() {
-            // Write the c:name element.
-            self.write_trendline_name(&trendline.name);
+        if font.character_set > 0 || font.pitch_family > 0 {
+            attributes.push(("charset", font.character_set.to_string()));
         }
 
-        // Write the c:spPr formatting element.
-        self.write_sp_pr(&trendline.format);
+        xml_empty_tag(&mut self.writer, "a:latin", &attributes);
+    }
 
-        // Write the c:trendlineType element.
-        self.write_trendline_type(trendline);
+    // Write the <a:t> element.
+    fn write_a_t(&mut self, name: &str) {
+        xml_data_element_only(&mut self.writer, "a:t", name);
+    }
 
-        if let ChartTrendlineType::Polynomial(order) = trendline.trend_type {
-            self.write_order(order as usize);
-        }
+    // Write the <a:endParaRPr> element.
+    fn write_a_end_para_rpr(&mut self) {
+        let attributes = [("lang", "en-US")];
 
-        if let ChartTrendlineType::MovingAverage(period) = trendline.trend_type {
-            // Write the c:period element.
-            self.write_trendline_period(period);
-        }
+        xml_empty_tag(&mut self.writer, "a:endParaRPr", &attributes);
+    }
 
-        if trendline.forward_period > 0.0 {
-            // Write the c:forward element.
-            self.write_trendline_forward(trendline.forward_period);
-        }
+    // Write the <c:spPr> element.
+    fn write_sp_pr(&mut self, format: &ChartFormat) {
+        self.write_shape_properties("c:spPr", format);
+    }
 
-        if trendline.backward_period > 0.0 {
-            // Write the c:backward element.
-            self.write_trendline_backward(trendline.backward_period);
+    // Write the <c:spPr> or <cx:spPr> shape properties element.
+    fn write_shape_properties(&mut self, tag: &str, format: &ChartFormat) {
+        if !format.has_formatting() {
+            return;
         }
 
-        if let Some(intercept) = trendline.intercept {
-            // Write the c:intercept element.
-            self.write_trendline_intercept(intercept);
-        }
+        xml_start_tag_only(&mut self.writer, tag);
 
-        if trendline.display_r_squared {
-            // Write the c:dispRSqr element.
-            self.write_disp_rsqr();
+        if format.no_fill {
+            xml_empty_tag_only(&mut self.writer, "a:noFill");
+        } else if let Some(solid_fill) = &format.solid_fill {
+            // Write the a:solidFill element.
+            self.write_a_solid_fill(solid_fill.color, solid_fill.transparency);
+        } else if let Some(pattern_fill) = &format.pattern_fill {
+            // Write the a:pattFill element.
+            self.write_a_patt_fill(pattern_fill);
+        } else if let Some(gradient_fill) = &format.gradient_fill {
+            // Write the a:gradFill element.
+            self.write_gradient_fill(gradient_fill);
         }
-
-        if trendline.display_equation {
-            // Write the c:dispEq element.
-            self.write_trendline_display_equation(trendline);
+        if format.no_line {
+            // Write a default line with no fill.
+            self.write_a_ln_none();
+        } else if let Some(line) = &format.line {
+            // Write the a:ln element.
+            self.write_a_ln(line);
         }
 
-        xml_end_tag(&mut self.writer, "c:trendline");
+        xml_end_tag(&mut self.writer, tag);
     }
 
-    // Write the <c:name> element.
-    fn write_trendline_name(&mut self, name: &str) {
-        xml_data_element_only(&mut self.writer, "c:name", name);
-    }
+    // Write the <a:ln> element.
+    fn write_a_ln(&mut self, line: &ChartLine) {
+        let mut attributes = vec![];
 
-    // Write the <c:trendlineType> element.
-    fn write_trendline_type(&mut self, trendline: &ChartTrendline) {
-        let attributes = [("val", trendline.trend_type.to_string())];
+        if let Some(width) = &line.width {
+            // Round width to nearest 0.25, like Excel.
+            let width = ((*width + 0.125) * 4.0).floor() / 4.0;
 
-        xml_empty_tag(&mut self.writer, "c:trendlineType", &attributes);
-    }
+            // Convert to Excel internal units.
+            let width = (12700.0 * width).ceil() as u32;
 
-    // Write the <c:forward> element.
-    fn write_trendline_forward(&mut self, value: f64) {
-        let attributes = [("val", value.to_string())];
+            attributes.push(("w", width.to_string()));
+        }
 
-        xml_empty_tag(&mut self.writer, "c:forward", &attributes);
-    }
+        if line.color != Color::Default || line.dash_type != ChartLineDashType::Solid || line.hidden
+        {
+            xml_start_tag(&mut self.writer, "a:ln", &attributes);
 
-    // Write the <c:backward> element.
-    fn write_trendline_backward(&mut self, value: f64) {
-        let attributes = [("val", value.to_string())];
+            if line.hidden {
+                // Write the a:noFill element.
+                self.write_a_no_fill();
+            } else {
+                if line.color != Color::Default {
+                    // Write the a:solidFill element.
+                    self.write_a_solid_fill(line.color, line.transparency);
+                }
 
-        xml_empty_tag(&mut self.writer, "c:backward", &attributes);
+                if line.dash_type != ChartLineDashType::Solid {
+                    // Write the a:prstDash element.
+                    self.write_a_prst_dash(line);
+                }
+            }
+
+            xml_end_tag(&mut self.writer, "a:ln");
+        } else {
+            xml_empty_tag(&mut self.writer, "a:ln", &attributes);
+        }
     }
 
-    // Write the <c:dispRSqr> element.
-    fn write_disp_rsqr(&mut self) {
-        let attributes = [("val", "1")];
+    // Write the <a:ln> element.
+    fn write_a_ln_none(&mut self) {
+        xml_start_tag_only(&mut self.writer, "a:ln");
 
-        xml_empty_tag(&mut self.writer, "c:dispRSqr", &attributes);
+        // Write the a:noFill element.
+        self.write_a_no_fill();
+
+        xml_end_tag(&mut self.writer, "a:ln");
     }
 
-    // Write the <c:dispEq> element.
-    fn write_trendline_display_equation(&mut self, trendline: &ChartTrendline) {
-        let attributes = [("val", "1")];
+    // Write the <a:solidFill> element.
+    fn write_a_solid_fill(&mut self, color: Color, transparency: u8) {
+        xml_start_tag_only(&mut self.writer, "a:solidFill");
 
-        xml_empty_tag(&mut self.writer, "c:dispEq", &attributes);
+        // Write the color element.
+        self.write_color(color, transparency);
 
-        // Write the c:trendlineLbl element.
-        self.write_trendline_label(trendline);
+        xml_end_tag(&mut self.writer, "a:solidFill");
     }
 
-    // Write the <c:trendlineLbl> element.
-    fn write_trendline_label(&mut self, trendline: &ChartTrendline) {
-        xml_start_tag_only(&mut self.writer, "c:trendlineLbl");
-
-        // Write the c:layout element.
-        let layout = ChartLayout::default();
-        self.write_layout(&layout);
+    // Write the <a:pattFill> element.
+    fn write_a_patt_fill(&mut self, fill: &ChartPatternFill) {
+        let attributes = [("prst", fill.pattern.to_string())];
 
-        self.write_number_format("General", false);
+        xml_start_tag(&mut self.writer, "a:pattFill", &attributes);
 
-        // Write the c:spPr formatting element.
-        self.write_sp_pr(&trendline.label_format);
+        if fill.foreground_color != Color::Default {
+            // Write the <a:fgClr> element.
+            xml_start_tag_only(&mut self.writer, "a:fgClr");
+            self.write_color(fill.foreground_color, 0);
+            xml_end_tag(&mut self.writer, "a:fgClr");
+        }
 
-        // Write the trendline label font elements.
-        if let Some(font) = &trendline.label_font {
-            self.write_axis_font(font);
+        if fill.background_color != Color::Default {
+            // Write the <a:bgClr> element.
+            xml_start_tag_only(&mut self.writer, "a:bgClr");
+            self.write_color(fill.background_color, 0);
+            xml_end_tag(&mut self.writer, "a:bgClr");
+        } else if fill.background_color == Color::Default && fill.foreground_color != Color::Default
+        {
+            // If there is a foreground color but no background color then we
+            // need to write a default background color.
+            xml_start_tag_only(&mut self.writer, "a:bgClr");
+            self.write_color(Color::White, 0);
+            xml_end_tag(&mut self.writer, "a:bgClr");
         }
 
-        xml_end_tag(&mut self.writer, "c:trendlineLbl");
+        xml_end_tag(&mut self.writer, "a:pattFill");
     }
 
-    // Write the <c:period> element.
-    fn write_trendline_period(&mut self, value: u8) {
-        let attributes = [("val", value.to_string())];
+    // Write the <a:gradFill> element.
+    fn write_gradient_fill(&mut self, fill: &ChartGradientFill) {
+        let mut attributes = vec![];
 
-        xml_empty_tag(&mut self.writer, "c:period", &attributes);
-    }
+        if fill.gradient_type != ChartGradientFillType::Linear {
+            attributes.push(("flip", "none"));
+            attributes.push(("rotWithShape", "1"));
+        }
 
-    // Write the <c:intercept> element.
-    fn write_trendline_intercept(&mut self, value: f64) {
-        let attributes = [("val", value.to_string())];
+        xml_start_tag(&mut self.writer, "a:gradFill", &attributes);
+        xml_start_tag_only(&mut self.writer, "a:gsLst");
 
-        xml_empty_tag(&mut self.writer, "c:intercept", &attributes);
-    }
+        for gradient_stop in &fill.gradient_stops {
+            // Write the a:gs element.
+            self.write_gradient_stop(gradient_stop);
+        }
 
-    // Write the <c:errBars> element.
-    fn write_error_bar(&mut self, axis: &str, error_bars: &ChartErrorBars) {
-        xml_start_tag_only(&mut self.writer, "c:errBars");
+        xml_end_tag(&mut self.writer, "a:gsLst");
 
-        // Write the c:errDir element.
-        self.write_error_bar_direction(axis);
+        if fill.gradient_type == ChartGradientFillType::Linear {
+            // Write the a:lin element.
+            self.write_gradient_fill_angle(fill.angle);
+        } else {
+            // Write the a:path element.
+            self.write_gradient_path(fill.gradient_type);
+        }
 
-        // Write the c:errBarType element.
-        self.write_error_bar_type(error_bars.direction);
+        xml_end_tag(&mut self.writer, "a:gradFill");
+    }
 
-        // Write the c:errValType element.
-        self.write_err_direction_type(&error_bars.error_type);
+    // Write the <a:gs> element.
+    fn write_gradient_stop(&mut self, gradient_stop: &ChartGradientStop) {
+        let position = 1000 * u32::from(gradient_stop.position);
+        let attributes = [("pos", position.to_string())];
 
-        if !error_bars.has_end_cap {
-            // Write the c:noEndCap element.
-            self.write_error_bar_no_end_cap();
-        }
+        xml_start_tag(&mut self.writer, "a:gs", &attributes);
+        self.write_color(gradient_stop.color, 0);
 
-        match &error_bars.error_type {
-            ChartErrorBarsType::FixedValue(value)
-            | ChartErrorBarsType::Percentage(value)
-            | ChartErrorBarsType::StandardDeviation(value) => {
-                // Write the c:val element.
-                self.write_error_value(*value);
-            }
-            ChartErrorBarsType::Custom(_, _) => self.write_custom_error_bar_values(error_bars),
-            ChartErrorBarsType::StandardError => {}
-        }
+        xml_end_tag(&mut self.writer, "a:gs");
+    }
 
-        // Write the c:spPr formatting element.
-        self.write_sp_pr(&error_bars.format);
+    // Write the <a:lin> element.
+    fn write_gradient_fill_angle(&mut self, angle: u16) {
+        let angle = 60_000 * u32::from(angle);
+        let attributes = [("ang", angle.to_string()), ("scaled", "0".to_string())];
 
-        xml_end_tag(&mut self.writer, "c:errBars");
+        xml_empty_tag(&mut self.writer, "a:lin", &attributes);
     }
 
-    // Write the <c:errDir> element.
-    fn write_error_bar_direction(&mut self, axis: &str) {
-        if !axis.is_empty() {
-            let attributes = vec![("val", axis.to_string())];
-            xml_empty_tag(&mut self.writer, "c:errDir", &attributes);
+    // Write the <a:path> element.
+    fn write_gradient_path(&mut self, gradient_type: ChartGradientFillType) {
+        let mut attributes = vec![];
+
+        match gradient_type {
+            ChartGradientFillType::Radial => attributes.push(("path", "circle")),
+            ChartGradientFillType::Rectangular => attributes.push(("path", "rect")),
+            ChartGradientFillType::Path => attributes.push(("path", "shape")),
+            ChartGradientFillType::Linear => {}
         }
-    }
 
-    // Write the <c:errBarType> element.
-    fn write_error_bar_type(&mut self, direction: ChartErrorBarsDirection) {
-        let attributes = vec![("val", direction.to_string())];
+        xml_start_tag(&mut self.writer, "a:path", &attributes);
 
-        xml_empty_tag(&mut self.writer, "c:errBarType", &attributes);
-    }
+        // Write the a:fillToRect element.
+        self.write_a_fill_to_rect(gradient_type);
 
-    // Write the <c:errValType> element.
-    fn write_err_direction_type(&mut self, bar_type: &ChartErrorBarsType) {
-        let attributes = vec![("val", bar_type.to_string())];
+        xml_end_tag(&mut self.writer, "a:path");
 
-        xml_empty_tag(&mut self.writer, "c:errValType", &attributes);
+        // Write the a:tileRect element.
+        self.write_a_tile_rect(gradient_type);
     }
 
-    // Write the <c:noEndCap> element.
-    fn write_error_bar_no_end_cap(&mut self) {
-        let attributes = [("val", "1")];
-
-        xml_empty_tag(&mut self.writer, "c:noEndCap", &attributes);
-    }
+    // Write the <a:fillToRect> element.
+    fn write_a_fill_to_rect(&mut self, gradient_type: ChartGradientFillType) {
+        let mut attributes = vec![];
 
-    // Write the <c:val> element.
-    fn write_error_value(&mut self, value: f64) {
-        let attributes = [("val", value.to_string())];
+        match gradient_type {
+            ChartGradientFillType::Path => {
+                attributes.push(("l", "50000"));
+                attributes.push(("t", "50000"));
+                attributes.push(("r", "50000"));
+                attributes.push(("b", "50000"));
+            }
+            _ => {
+                attributes.push(("l", "100000"));
+                attributes.push(("t", "100000"));
+            }
+        }
 
-        xml_empty_tag(&mut self.writer, "c:val", &attributes);
+        xml_empty_tag(&mut self.writer, "a:fillToRect", &attributes);
     }
 
-    // Write the custom error sub-elements
-    fn write_custom_error_bar_values(&mut self, error_bars: &ChartErrorBars) {
-        xml_start_tag_only(&mut self.writer, "c:plus");
-        self.write_cache_ref(&error_bars.plus_range, true);
-        xml_end_tag(&mut self.writer, "c:plus");
+    // Write the <a:tileRect> element.
+    fn write_a_tile_rect(&mut self, gradient_type: ChartGradientFillType) {
+        let mut attributes = vec![];
 
-        xml_start_tag_only(&mut self.writer, "c:minus");
-        self.write_cache_ref(&error_bars.minus_range, true);
-        xml_end_tag(&mut self.writer, "c:minus");
+        match gradient_type {
+            ChartGradientFillType::Rectangular | ChartGradientFillType::Radial => {
+                attributes.push(("r", "-100000"));
+                attributes.push(("b", "-100000"));
+            }
+            _ => {}
+        }
+
+        xml_empty_tag(&mut self.writer, "a:tileRect", &attributes);
     }
 
-    // Write the <c:upDownBars> element.
-    fn write_up_down_bars(&mut self) {
-        xml_start_tag_only(&mut self.writer, "c:upDownBars");
+    // Write the <a:srgbClr> element.
+    fn write_color(&mut self, color: Color, transparency: u8) {
+        match color {
+            Color::Theme(_, _) => {
+                let (scheme, lum_mod, lum_off) = color.chart_scheme();
+                if !scheme.is_empty() {
+                    // Write the a:schemeClr element.
+                    self.write_a_scheme_clr(scheme, lum_mod, lum_off, transparency);
+                }
+            }
+            Color::Automatic => {
+                let attributes = [("val", "window"), ("lastClr", "FFFFFF")];
 
-        // Write the c:gapWidth element.
-        self.write_gap_width(150);
+                xml_empty_tag(&mut self.writer, "a:sysClr", &attributes);
+            }
+            _ => {
+                let attributes = [("val", color.rgb_hex_value())];
 
-        // Write the c:upBars element.
-        self.write_up_bars();
+                if transparency > 0 {
+                    xml_start_tag(&mut self.writer, "a:srgbClr", &attributes);
 
-        // Write the c:downBars element.
-        self.write_down_bars();
+                    // Write the a:alpha element.
+                    self.write_a_alpha(transparency);
 
-        xml_end_tag(&mut self.writer, "c:upDownBars");
+                    xml_end_tag(&mut self.writer, "a:srgbClr");
+                } else {
+                    xml_empty_tag(&mut self.writer, "a:srgbClr", &attributes);
+                }
+            }
+        }
     }
 
-    // Write the <c:upBars> element.
-    fn write_up_bars(&mut self) {
-        if self.up_bar_format.has_formatting() {
-            xml_start_tag_only(&mut self.writer, "c:upBars");
+    // Write the <a:schemeClr> element.
+    fn write_a_scheme_clr(&mut self, scheme: String, lum_mod: u32, lum_off: u32, transparency: u8) {
+        let attributes = [("val", scheme)];
 
-            // Write the c:spPr element.
-            self.write_sp_pr(&self.up_bar_format.clone());
+        if lum_mod > 0 || lum_off > 0 || transparency > 0 {
+            xml_start_tag(&mut self.writer, "a:schemeClr", &attributes);
 
-            xml_end_tag(&mut self.writer, "c:upBars");
-        } else {
-            xml_empty_tag_only(&mut self.writer, "c:upBars");
-        }
-    }
+            if lum_mod > 0 {
+                // Write the a:lumMod element.
+                self.write_a_lum_mod(lum_mod);
+            }
 
-    // Write the <c:downBars> element.
-    fn write_down_bars(&mut self) {
-        if self.down_bar_format.has_formatting() {
-            xml_start_tag_only(&mut self.writer, "c:downBars");
+            if lum_off > 0 {
+                // Write the a:lumOff element.
+                self.write_a_lum_off(lum_off);
+            }
 
-            // Write the c:spPr element.
-            self.write_sp_pr(&self.down_bar_format.clone());
+            if transparency > 0 {
+                // Write the a:alpha element.
+                self.write_a_alpha(transparency);
+            }
 
-            xml_end_tag(&mut self.writer, "c:downBars");
+            xml_end_tag(&mut self.writer, "a:schemeClr");
         } else {
-            xml_empty_tag_only(&mut self.writer, "c:downBars");
+            xml_empty_tag(&mut self.writer, "a:schemeClr", &attributes);
         }
     }
 
-    // Write the <c:hiLowLines> element.
-    fn write_hi_low_lines(&mut self) {
-        if self.high_low_lines_format.has_formatting() {
-            xml_start_tag_only(&mut self.writer, "c:hiLowLines");
+    // Write the <a:lumMod> element.
+    fn write_a_lum_mod(&mut self, lum_mod: u32) {
+        let attributes = [("val", lum_mod.to_string())];
 
-            // Write the c:spPr element.
-            self.write_sp_pr(&self.high_low_lines_format.clone());
+        xml_empty_tag(&mut self.writer, "a:lumMod", &attributes);
+    }
 
-            xml_end_tag(&mut self.writer, "c:hiLowLines");
-        } else {
-            xml_empty_tag_only(&mut self.writer, "c:hiLowLines");
-        }
+    // Write the <a:lumOff> element.
+    fn write_a_lum_off(&mut self, lum_off: u32) {
+        let attributes = [("val", lum_off.to_string())];
+
+        xml_empty_tag(&mut self.writer, "a:lumOff", &attributes);
     }
 
-    // Write the <c:dropLines> element.
-    fn write_drop_lines(&mut self) {
-        if self.drop_lines_format.has_formatting() {
-            xml_start_tag_only(&mut self.writer, "c:dropLines");
+    // Write the <a:alpha> element.
+    fn write_a_alpha(&mut self, transparency: u8) {
+        let transparency = u32::from(100 - transparency) * 1000;
 
-            // Write the c:spPr element.
-            self.write_sp_pr(&self.drop_lines_format.clone());
+        let attributes = [("val", transparency.to_string())];
 
-            xml_end_tag(&mut self.writer, "c:dropLines");
-        } else {
-            xml_empty_tag_only(&mut self.writer, "c:dropLines");
-        }
+        xml_empty_tag(&mut self.writer, "a:alpha", &attributes);
     }
 
-    // Write the <c:dTable> element.
-    fn write_data_table(&mut self, table: &ChartDataTable) {
-        xml_start_tag_only(&mut self.writer, "c:dTable");
+    // Write the <a:noFill> element.
+    fn write_a_no_fill(&mut self) {
+        xml_empty_tag_only(&mut self.writer, "a:noFill");
+    }
 
-        // Write the c:showHorzBorder element.
-        if table.show_horizontal_borders {
-            self.write_show_horz_border();
-        }
+    // Write the <a:prstDash> element.
+    fn write_a_prst_dash(&mut self, line: &ChartLine) {
+        let attributes = [("val", line.dash_type.to_string())];
 
-        // Write the c:showVertBorder element.
-        if table.show_vertical_borders {
-            self.write_show_vert_border();
-        }
+        xml_empty_tag(&mut self.writer, "a:prstDash", &attributes);
+    }
 
-        // Write the c:showOutline element.
-        if table.show_outline_borders {
-            self.write_show_outline();
-        }
+    // Write the <c:radarStyle> element.
+    fn write_radar_style(&mut self) {
+        let mut attributes = vec![];
 
-        // Write the c:showKeys element.
-        if table.show_legend_keys {
-            self.write_show_keys();
+        if self.chart_type == ChartType::RadarFilled {
+            attributes.push(("val", "filled".to_string()));
+        } else {
+            attributes.push(("val", "marker".to_string()));
         }
 
-        // Write the c:spPr element.
-        self.write_sp_pr(&table.format);
+        xml_empty_tag(&mut self.writer, "c:radarStyle", &attributes);
+    }
 
-        // Write the trendline label font elements.
-        if let Some(font) = &table.font {
-            self.write_axis_font(font);
-        }
+    // Write the <c:majorTickMark> element.
+    fn write_major_tick_mark(&mut self, position: ChartAxisTickType) {
+        let attributes = [("val", position.to_string())];
 
-        xml_end_tag(&mut self.writer, "c:dTable");
+        xml_empty_tag(&mut self.writer, "c:majorTickMark", &attributes);
     }
 
-    // Write the <c:showKeys> element.
-    fn write_show_keys(&mut self) {
-        let attributes = [("val", "1")];
+    // Write the <c:minorTickMark> element.
+    fn write_minor_tick_mark(&mut self, tick_type: ChartAxisTickType) {
+        let attributes = [("val", tick_type.to_string())];
 
-        xml_empty_tag(&mut self.writer, "c:showKeys", &attributes);
+        xml_empty_tag(&mut self.writer, "c:minorTickMark", &attributes);
     }
 
-    // Write the <c:showHorzBorder> element.
-    fn write_show_horz_border(&mut self) {
-        let attributes = [("val", "1")];
+    // Write the <c:gapWidth> element.
+    fn write_gap_width(&mut self, gap: u16) {
+        let attributes = [("val", gap.to_string())];
+
+        xml_empty_tag(&mut self.writer, "c:gapWidth", &attributes);
+    }
+
+    // Write the <c:overlap> element.
+    fn write_overlap(&mut self) {
+        if let Some(overlap) = &self.overlap {
+            let attributes = [("val", overlap.to_string())];
 
-        xml_empty_tag(&mut self.writer, "c:showHorzBorder", &attributes);
+            xml_empty_tag(&mut self.writer, "c:overlap", &attributes);
+        }
     }
 
-    // Write the <c:showVertBorder> element.
-    fn write_show_vert_border(&mut self) {
+    // Write the <c:smooth> element.
+    fn write_smooth(&mut self) {
         let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:showVertBorder", &attributes);
+        xml_empty_tag(&mut self.writer, "c:smooth", &attributes);
     }
 
-    // Write the <c:showOutline> element.
-    fn write_show_outline(&mut self) {
-        let attributes = [("val", "1")];
+    // Write the <c:shape> element.
+    fn write_shape(&mut self) {
+        let attributes = [("val", "box")];
 
-        xml_empty_tag(&mut self.writer, "c:showOutline", &attributes);
+        xml_empty_tag(&mut self.writer, "c:shape", &attributes);
     }
 
-    // Write the <c:showVal> element.
-    fn write_show_val(&mut self) {
+    // Write the <c:bubble3D> element.
+    fn write_bubble_3d(&mut self) {
         let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:showVal", &attributes);
+        xml_empty_tag(&mut self.writer, "c:bubble3D", &attributes);
     }
 
-    // Write the <c:showCatName> element.
-    fn write_show_category_name(&mut self) {
-        let attributes = [("val", "1")];
+    // Write the <c:bubbleScale> element.
+    fn write_bubble_scale(&mut self) {
+        let attributes = [("val", "100")];
 
-        xml_empty_tag(&mut self.writer, "c:showCatName", &attributes);
+        xml_empty_tag(&mut self.writer, "c:bubbleScale", &attributes);
     }
 
-    // Write the <c:showSerName> element.
-    fn write_show_series_name(&mut self) {
-        let attributes = [("val", "1")];
+    // Write the <c:showNegBubbles> element.
+    fn write_show_neg_bubbles(&mut self) {
+        let attributes = [("val", "0")];
 
-        xml_empty_tag(&mut self.writer, "c:showSerName", &attributes);
+        xml_empty_tag(&mut self.writer, "c:showNegBubbles", &attributes);
     }
 
-    // Write the <c:showPercent> element.
-    fn write_show_percent(&mut self) {
-        let attributes = [("val", "1")];
+    // Write the <c:wireframe> element.
+    fn write_wireframe(&mut self) {
+        let is_wireframe = matches!(
+            self.chart_type,
+            ChartType::SurfaceWireframe | ChartType::ContourWireframe
+        );
 
-        xml_empty_tag(&mut self.writer, "c:showPercent", &attributes);
-    }
+        let attributes = [("val", is_wireframe.to_xml_bool())];
 
-    // Write the <c:separator> element.
-    fn write_separator(&mut self, separator: char) {
-        xml_data_element_only(&mut self.writer, "c:separator", &format!("{separator} "));
+        xml_empty_tag(&mut self.writer, "c:wireframe", &attributes);
     }
 
-    // Write the <c:showLeaderLines> element for Excel 2007 (mainly only for Pie
-    // and Doughnut).
-    fn write_show_leader_lines_2007(&mut self) {
-        let attributes = [("val", "1")];
+    // Write the <c:view3D> element.
+    fn write_view_3d(&mut self) {
+        xml_start_tag_only(&mut self.writer, "c:view3D");
 
-        xml_empty_tag(&mut self.writer, "c:showLeaderLines", &attributes);
-    }
+        // Write the c:rotX element.
+        let attributes = [("val", self.x_rotation.to_string())];
+        xml_empty_tag(&mut self.writer, "c:rotX", &attributes);
 
-    // Write the <c:showLeaderLines> element for Excel 2015+ (mainly for charts
-    // that aren't Pie or Doughnut).
-    fn write_show_leader_lines_2015(&mut self) {
-        let attributes = [
-            ("uri", "{CE6537A1-D6FC-4f65-9D91-7224C49458BB}"),
-            (
-                "xmlns:c15",
-                "http://schemas.microsoft.com/office/drawing/2012/chart",
-            ),
-        ];
+        // Write the c:rotY element.
+        let attributes = [("val", self.y_rotation.to_string())];
+        xml_empty_tag(&mut self.writer, "c:rotY", &attributes);
 
-        xml_start_tag_only(&mut self.writer, "c:extLst");
-        xml_start_tag(&mut self.writer, "c:ext", &attributes);
+        // Write the c:depthPercent element.
+        if self.depth_percent != 100 {
+            let attributes = [("val", self.depth_percent.to_string())];
+            xml_empty_tag(&mut self.writer, "c:depthPercent", &attributes);
+        }
 
-        xml_empty_tag(&mut self.writer, "c15:showLeaderLines", &[("val", "1")]);
-        xml_end_tag(&mut self.writer, "c:ext");
-        xml_end_tag(&mut self.writer, "c:extLst");
-    }
+        // Write the c:rAngAx element.
+        let attributes = [("val", self.right_angle_axes.to_xml_bool())];
+        xml_empty_tag(&mut self.writer, "c:rAngAx", &attributes);
 
-    // Write the <c:showLegendKey> element.
-    fn write_show_legend_key(&mut self) {
-        let attributes = [("val", "1")];
+        // Write the c:perspective element. It is ignored by Excel when the axes
+        // are at right angles.
+        if !self.right_angle_axes && self.perspective != 30 {
+            let attributes = [("val", self.perspective.to_string())];
+            xml_empty_tag(&mut self.writer, "c:perspective", &attributes);
+        }
 
-        xml_empty_tag(&mut self.writer, "c:showLegendKey", &attributes);
+        xml_end_tag(&mut self.writer, "c:view3D");
     }
 
-    // Write the <c:dLblPos> element.
-    fn write_d_lbl_pos(&mut self, position: ChartDataLabelPosition) {
-        let attributes = [("val", position.to_string())];
+    // Write the <c:style> element.
+    fn write_style(&mut self) {
+        let attributes = [("val", self.style.to_string())];
 
-        xml_empty_tag(&mut self.writer, "c:dLblPos", &attributes);
+        xml_empty_tag(&mut self.writer, "c:style", &attributes);
     }
 
-    // Write the <c:delete> element.
-    fn write_delete(&mut self) {
+    // Write the <c:autoTitleDeleted> element.
+    fn write_auto_title_deleted(&mut self) {
         let attributes = [("val", "1")];
 
-        xml_empty_tag(&mut self.writer, "c:delete", &attributes);
+        xml_empty_tag(&mut self.writer, "c:autoTitleDeleted", &attributes);
     }
 
-    // Write the <c:symbol> element.
-    fn write_symbol(&mut self, marker: &ChartMarker) {
-        let mut attributes = vec![];
+    // Write the <c:title> element.
+    fn write_title_formula(&mut self, title: &ChartTitle) {
+        xml_start_tag_only(&mut self.writer, "c:title");
 
-        if let Some(marker_type) = marker.marker_type {
-            attributes.push(("val", marker_type.to_string()));
-        } else if marker.none {
-            attributes.push(("val", "none".to_string()));
-        }
+        // Write the c:tx element.
+        self.write_tx_formula(title);
 
-        xml_empty_tag(&mut self.writer, "c:symbol", &attributes);
-    }
+        // Write the c:layout element.
+        self.write_layout(&title.layout);
 
-    // Write the <c:size> element.
-    fn write_size(&mut self, size: u8) {
-        let attributes = [("val", size.to_string())];
+        // Write the c:overlay element.
+        if title.has_overlay {
+            self.write_overlay();
+        }
 
-        xml_empty_tag(&mut self.writer, "c:size", &attributes);
-    }
+        // Write the c:spPr formatting element.
+        self.write_sp_pr(&title.format.clone());
 
-    // Write the <c:varyColors> element.
-    fn write_vary_colors(&mut self) {
-        let attributes = [("val", "1")];
+        // Write the c:txPr element.
+        self.write_tx_pr(&title.font, title.is_horizontal);
 
-        xml_empty_tag(&mut self.writer, "c:varyColors", &attributes);
+        xml_end_tag(&mut self.writer, "c:title");
     }
 
-    // Write the <c:firstSliceAng> element.
-    fn write_first_slice_ang(&mut self) {
-        let attributes = [("val", self.rotation.to_string())];
-
-        xml_empty_tag(&mut self.writer, "c:firstSliceAng", &attributes);
-    }
+    // Write the <c:tx> element.
+    fn write_tx_formula(&mut self, title: &ChartTitle) {
+        xml_start_tag_only(&mut self.writer, "c:tx");
 
-    // Write the <c:holeSize> element.
-    fn write_hole_size(&mut self) {
-        let attributes = [("val", self.hole_size.to_string())];
+        // Title is always a string type.
+        self.write_str_ref(&title.range);
 
-        xml_empty_tag(&mut self.writer, "c:holeSize", &attributes);
+        xml_end_tag(&mut self.writer, "c:tx");
     }
 
-    // Write the <c:txPr> element.
-    fn write_axis_font(&mut self, font: &ChartFont) {
-        xml_start_tag_only(&mut self.writer, "c:txPr");
-
-        // Write the a:bodyPr element.
-        self.write_a_body_pr(font, false);
-
-        // Write the a:lstStyle element.
-        self.write_a_lst_style();
+    // Write the <c:title> element.
+    fn write_title_rich(&mut self, title: &ChartTitle) {
+        xml_start_tag_only(&mut self.writer, "c:title");
 
-        xml_start_tag_only(&mut self.writer, "a:p");
+        // Write the c:tx element.
+        self.write_tx_rich(title);
 
-        // Write the a:pPr element.
-        self.write_a_p_pr_rich(font);
+        // Write the c:layout element.
+        self.write_layout(&title.layout);
 
-        // Write the a:endParaRPr element.
-        self.write_a_end_para_rpr();
+        // Write the c:overlay element.
+        if title.has_overlay {
+            self.write_overlay();
+        }
 
-        xml_end_tag(&mut self.writer, "a:p");
+        if title.format.has_formatting() {
+            // Write the c:spPr element.
+            self.write_sp_pr(&title.format.clone());
+        }
 
-        xml_end_tag(&mut self.writer, "c:txPr");
+        xml_end_tag(&mut self.writer, "c:title");
     }
 
-    // Write the <c:txPr> element.
-    fn write_tx_pr(&mut self, font: &ChartFont, is_horizontal: bool) {
-        xml_start_tag_only(&mut self.writer, "c:txPr");
+    // Write the <c:title> element.
+    fn write_title_format_only(&mut self, title: &ChartTitle) {
+        xml_start_tag_only(&mut self.writer, "c:title");
 
-        // Write the a:bodyPr element.
-        self.write_a_body_pr(font, is_horizontal);
+        // Write the c:layout element.
+        self.write_layout(&title.layout);
 
-        // Write the a:lstStyle element.
-        self.write_a_lst_style();
+        // Write the c:overlay element.
+        if title.has_overlay {
+            self.write_overlay();
+        }
 
-        // Write the a:p element.
-        self.write_a_p_formula(font);
+        // Write the c:spPr element.
+        self.write_sp_pr(&title.format.clone());
 
-        xml_end_tag(&mut self.writer, "c:txPr");
+        xml_end_tag(&mut self.writer, "c:title");
     }
 
-    // Write the <a:p> element.
-    fn write_a_p_formula(&mut self, font: &ChartFont) {
-        xml_start_tag_only(&mut self.writer, "a:p");
-
-        // Write the a:pPr element.
-        self.write_a_p_pr(font);
+    // Write the <c:tx> element.
+    fn write_tx_rich(&mut self, title: &ChartTitle) {
+        xml_start_tag_only(&mut self.writer, "c:tx");
 
-        // Write the a:endParaRPr element.
-        self.write_a_end_para_rpr();
+        // Write the c:rich element.
+        self.write_rich(title);
 
-        xml_end_tag(&mut self.writer, "a:p");
+        xml_end_tag(&mut self.writer, "c:tx");
     }
 
-    // Write the <a:pPr> element.
-    fn write_a_p_pr(&mut self, font: &ChartFont) {
-        let mut attributes = vec![];
-
-        if let Some(right_to_left) = font.right_to_left {
-            attributes.push(("rtl", right_to_left.to_xml_bool()));
-        }
-
-        xml_start_tag(&mut self.writer, "a:pPr", &attributes);
+    // Write the <c:tx> element.
+    fn write_tx_value(&mut self, title: &ChartTitle) {
+        xml_start_tag_only(&mut self.writer, "c:tx");
 
-        // Write the a:defRPr element.
-        self.write_a_def_rpr(font);
+        xml_data_element_only(&mut self.writer, "c:v", &title.name);
 
-        xml_end_tag(&mut self.writer, "a:pPr");
+        xml_end_tag(&mut self.writer, "c:tx");
     }
 
-    // Write the <a:bodyPr> element.
-    fn write_a_body_pr(&mut self, font: &ChartFont, is_horizontal: bool) {
-        let mut attributes = vec![];
+    // Write the <c:rich> element.
+    fn write_rich(&mut self, title: &ChartTitle) {
+        xml_start_tag_only(&mut self.writer, "c:rich");
 
-        let rotation = match font.rotation {
-            Some(rotation) => rotation,
-            None => {
-                // Handle defaults for vertical and horizontal rotations.
-                if is_horizontal {
-                    -90
-                } else {
-                    360 // To distinguish from user defined 0.
-                }
-            }
-        };
+        // Write the a:bodyPr element.
+        self.write_a_body_pr(&title.font, title.is_horizontal);
 
-        match rotation {
-            360 => {}
-            270 => {
-                // Stacked text.
-                attributes.push(("rot", "0".to_string()));
-                attributes.push(("vert", "wordArtVert".to_string()));
-            }
-            271 => {
-                // East Asian vertical.
-                attributes.push(("rot", "0".to_string()));
-                attributes.push(("vert", "eaVert".to_string()));
-            }
-            _ => {
-                // Convert the rotation angle to Excel's units.
-                let rotation = i32::from(rotation) * 60_000;
-                attributes.push(("rot", rotation.to_string()));
-                attributes.push(("vert", "horz".to_string()));
-            }
-        }
+        // Write the a:lstStyle element.
+        self.write_a_lst_style();
 
-        xml_empty_tag(&mut self.writer, "a:bodyPr", &attributes);
-    }
+        // Write the a:p element.
+        self.write_a_p_rich(title);
 
-    // Write the <a:lstStyle> element.
-    fn write_a_lst_style(&mut self) {
-        xml_empty_tag_only(&mut self.writer, "a:lstStyle");
+        xml_end_tag(&mut self.writer, "c:rich");
     }
 
-    // Write the <a:defRPr> element.
-    fn write_a_def_rpr(&mut self, font: &ChartFont) {
-        self.write_font_elements("a:defRPr", font);
-    }
+    // Write the <a:p> element.
+    fn write_a_p_rich(&mut self, title: &ChartTitle) {
+        xml_start_tag_only(&mut self.writer, "a:p");
 
-    // Write the <a:rPr> element.
-    fn write_a_r_pr(&mut self, font: &ChartFont) {
-        self.write_font_elements("a:rPr", font);
+        if !title.ignore_rich_para {
+            // Write the a:pPr element.
+            self.write_a_p_pr_rich(&title.font);
+        }
+
+        // Write the a:r element.
+        self.write_a_r(title);
+
+        xml_end_tag(&mut self.writer, "a:p");
     }
 
-    // Write font sub-elements shared between <a:defRPr> and <a:rPr> elements.
-    fn write_font_elements(&mut self, tag: &str, font: &ChartFont) {
+    // Write the <a:pPr> element.
+    fn write_a_p_pr_rich(&mut self, font: &ChartFont) {
         let mut attributes = vec![];
 
-        if tag == "a:rPr" {
-            attributes.push(("lang", "en-US".to_string()));
+        if let Some(right_to_left) = font.right_to_left {
+            attributes.push(("rtl", right_to_left.to_xml_bool()));
         }
 
-        if font.size > 0.0 {
-            attributes.push(("sz", font.size.to_string()));
-        }
+        xml_start_tag(&mut self.writer, "a:pPr", &attributes);
 
-        if let Some(boolean) = font.bold {
-            attributes.push(("b", boolean.to_xml_bool()));
-        }
+        // Write the a:defRPr element.
+        self.write_a_def_rpr(font);
 
-        if font.italic || (font.bold.is_some() && !font.has_default_bold) {
-            attributes.push(("i", font.italic.to_xml_bool()));
-        }
+        xml_end_tag(&mut self.writer, "a:pPr");
+    }
 
-        if font.underline {
-            attributes.push(("u", "sng".to_string()));
-        }
+    // Write the <a:r> element.
+    fn write_a_r(&mut self, title: &ChartTitle) {
+        xml_start_tag_only(&mut self.writer, "a:r");
 
-        if font.has_baseline {
-            attributes.push(("baseline", "0".to_string()));
-        }
+        // Write the a:rPr element.
+        self.write_a_r_pr(&title.font);
 
-        if font.is_latin() || !font.color.is_auto_or_default() {
-            xml_start_tag(&mut self.writer, tag, &attributes);
+        // Write the a:t element.
+        self.write_a_t(&title.name);
 
-            if !font.color.is_auto_or_default() {
-                self.write_a_solid_fill(font.color, 0);
-            }
+        xml_end_tag(&mut self.writer, "a:r");
+    }
 
-            if font.is_latin() {
-                // Write the a:latin element.
-                self.write_a_latin(font);
-            }
+    // Write the <c:dispBlanksAs> element.
+    fn write_disp_blanks_as(&mut self) {
+        if let Some(show_empty_cells) = self.show_empty_cells_as {
+            let attributes = [("val", show_empty_cells.to_string())];
 
-            xml_end_tag(&mut self.writer, tag);
-        } else {
-            xml_empty_tag(&mut self.writer, tag, &attributes);
+            xml_empty_tag(&mut self.writer, "c:dispBlanksAs", &attributes);
         }
     }
 
-    // Write the <a:latin> element.
-    fn write_a_latin(&mut self, font: &ChartFont) {
-        let mut attributes = vec![];
+    // Write the <dispNaAsBlank> element. This is an Excel 16 extension.
+    fn write_disp_na_as_blank(&mut self) {
+        let attributes = [
+            ("uri", "{56B9EC1D-385E-4148-901F-78D8002777C0}"),
+            (
+                "xmlns:c16r3",
+                "http://schemas.microsoft.com/office/drawing/2017/03/chart",
+            ),
+        ];
 
-        if !font.name.is_empty() {
-            attributes.push(("typeface", font.name.clone()));
-        }
+        xml_start_tag_only(&mut self.writer, "c:extLst");
+        xml_start_tag(&mut self.writer, "c:ext", &attributes);
+        xml_start_tag_only(&mut self.writer, "c16r3:dataDisplayOptions16");
 
-        if font.pitch_family > 0 {
-            attributes.push(("pitchFamily", font.pitch_family.to_string()));
-        }
+        xml_empty_tag(&mut self.writer, "c16r3:dispNaAsBlank", &[("val", "1")]);
 
-        if font.character_set > 0 || font.pitch_family > 0 {
-            attributes.push(("charset", font.character_set.to_string()));
-        }
+        xml_end_tag(&mut self.writer, "c16r3:dataDisplayOptions16");
+        xml_end_tag(&mut self.writer, "c:ext");
+        xml_end_tag(&mut self.writer, "c:extLst");
+    }
 
-        xml_empty_tag(&mut self.writer, "a:latin", &attributes);
+    fn write_protection(&mut self) {
+        xml_empty_tag_only(&mut self.writer, "c:protection");
     }
 
-    // Write the <a:t> element.
-    fn write_a_t(&mut self, name: &str) {
-        xml_data_element_only(&mut self.writer, "a:t", name);
+    // -----------------------------------------------------------------------
+    // Chartex XML assembly methods.
+    // -----------------------------------------------------------------------
+
+    // Assemble and generate the XML file for the Excel 2016 chart types. These
+    // use the "chartex" format which has a different structure to the
+    // original chart types.
+    fn assemble_chart_ex_xml_file(&mut self) {
+        xml_declaration(&mut self.writer);
+
+        // Write the cx:chartSpace element.
+        self.write_cx_chart_space();
+
+        // Write the cx:chartData element.
+        self.write_cx_chart_data();
+
+        // Write the cx:chart element.
+        self.write_cx_chart();
+
+        // Write the cx:spPr element.
+        self.write_shape_properties("cx:spPr", &self.chart_area.format.clone());
+
+        // Close the cx:chartSpace tag.
+        xml_end_tag(&mut self.writer, "cx:chartSpace");
     }
 
-    // Write the <a:endParaRPr> element.
-    fn write_a_end_para_rpr(&mut self) {
-        let attributes = [("lang", "en-US")];
+    // Write the <cx:chartSpace> element.
+    fn write_cx_chart_space(&mut self) {
+        let attributes = [
+            (
+                "xmlns:a",
+                "http://schemas.openxmlformats.org/drawingml/2006/main",
+            ),
+            (
+                "xmlns:r",
+                "http://schemas.openxmlformats.org/officeDocument/2006/relationships",
+            ),
+            (
+                "xmlns:cx",
+                "http://schemas.microsoft.com/office/drawing/2014/chartex",
+            ),
+        ];
 
-        xml_empty_tag(&mut self.writer, "a:endParaRPr", &attributes);
+        xml_start_tag(&mut self.writer, "cx:chartSpace", &attributes);
     }
 
-    // Write the <c:spPr> element.
-    fn write_sp_pr(&mut self, format: &ChartFormat) {
-        if !format.has_formatting() {
-            return;
+    // Write the <cx:chartData> element.
+    fn write_cx_chart_data(&mut self) {
+        xml_start_tag_only(&mut self.writer, "cx:chartData");
+
+        for (index, series) in self.series.clone().iter().enumerate() {
+            // Write the cx:data element.
+            self.write_cx_data(index, series);
         }
 
-        xml_start_tag_only(&mut self.writer, "c:spPr");
+        xml_end_tag(&mut self.writer, "cx:chartData");
+    }
 
-        if format.no_fill {
-            xml_empty_tag_only(&mut self.writer, "a:noFill");
-        } else if let Some(solid_fill) = &format.solid_fill {
-            // Write the a:solidFill element.
-            self.write_a_solid_fill(solid_fill.color, solid_fill.transparency);
-        } else if let Some(pattern_fill) = &format.pattern_fill {
-            // Write the a:pattFill element.
-            self.write_a_patt_fill(pattern_fill);
-        } else if let Some(gradient_fill) = &format.gradient_fill {
-            // Write the a:gradFill element.
-            self.write_gradient_fill(gradient_fill);
-        }
-        if format.no_line {
-            // Write a default line with no fill.
-            self.write_a_ln_none();
-        } else if let Some(line) = &format.line {
-            // Write the a:ln element.
-            self.write_a_ln(line);
+    // Write the <cx:data> element.
+    fn write_cx_data(&mut self, index: usize, series: &ChartSeries) {
+        let attributes = [("id", index.to_string())];
+
+        xml_start_tag(&mut self.writer, "cx:data", &attributes);
+
+        // Write the cx:strDim element for the categories.
+        if series.category_range.has_data() {
+            self.write_cx_str_dim(&series.category_range);
         }
 
-        xml_end_tag(&mut self.writer, "c:spPr");
+        // Write the cx:numDim element for the values. The hierarchical charts
+        // use the values for the size of each element.
+        let dimension_type = match self.chart_type {
+            ChartType::Treemap | ChartType::Sunburst => "size",
+            _ => "val",
+        };
+
+        self.write_cx_num_dim(&series.value_range, dimension_type);
+
+        xml_end_tag(&mut self.writer, "cx:data");
     }
 
-    // Write the <a:ln> element.
-    fn write_a_ln(&mut self, line: &ChartLine) {
-        let mut attributes = vec![];
+    // Write the <cx:strDim> element.
+    fn write_cx_str_dim(&mut self, range: &ChartRange) {
+        let attributes = [("type", "cat")];
 
-        if let Some(width) = &line.width {
-            // Round width to nearest 0.25, like Excel.
-            let width = ((*width + 0.125) * 4.0).floor() / 4.0;
+        xml_start_tag(&mut self.writer, "cx:strDim", &attributes);
 
-            // Convert to Excel internal units.
-            let width = (12700.0 * width).ceil() as u32;
+        // Write the cx:f element.
+        xml_data_element_only(&mut self.writer, "cx:f", &range.formula_string());
 
-            attributes.push(("w", width.to_string()));
-        }
+        let cache = &range.cache;
+        if cache.has_data() {
+            // Split the cache data into levels, in reverse order, like a
+            // multi-level category range in a standard chart.
+            for depth in (0..cache.minor_dim).rev() {
+                let attributes = [("ptCount", cache.major_dim.to_string())];
 
-        if line.color != Color::Default || line.dash_type != ChartLineDashType::Solid || line.hidden
-        {
-            xml_start_tag(&mut self.writer, "a:ln", &attributes);
+                xml_start_tag(&mut self.writer, "cx:lvl", &attributes);
 
-            if line.hidden {
-                // Write the a:noFill element.
-                self.write_a_no_fill();
-            } else {
-                if line.color != Color::Default {
-                    // Write the a:solidFill element.
-                    self.write_a_solid_fill(line.color, line.transparency);
-                }
+                for index in 0..cache.major_dim {
+                    let offset = depth + cache.minor_dim * index;
 
-                if line.dash_type != ChartLineDashType::Solid {
-                    // Write the a:prstDash element.
-                    self.write_a_prst_dash(line);
+                    if let Some(value) = cache.data.get(offset) {
+                        if !value.is_empty() {
+                            self.write_cx_pt(index, value);
+                        }
+                    }
                 }
-            }
 
-            xml_end_tag(&mut self.writer, "a:ln");
-        } else {
-            xml_empty_tag(&mut self.writer, "a:ln", &attributes);
+                xml_end_tag(&mut self.writer, "cx:lvl");
+            }
         }
+
+        xml_end_tag(&mut self.writer, "cx:strDim");
     }
 
-    // Write the <a:ln> element.
-    fn write_a_ln_none(&mut self) {
-        xml_start_tag_only(&mut self.writer, "a:ln");
+    // Write the <cx:numDim> element.
+    fn write_cx_num_dim(&mut self, range: &ChartRange, dimension_type: &str) {
+        let attributes = [("type", dimension_type)];
 
-        // Write the a:noFill element.
-        self.write_a_no_fill();
+        xml_start_tag(&mut self.writer, "cx:numDim", &attributes);
 
-        xml_end_tag(&mut self.writer, "a:ln");
-    }
+        // Write the cx:f element.
+        xml_data_element_only(&mut self.writer, "cx:f", &range.formula_string());
 
-    // Write the <a:solidFill> element.
-    fn write_a_solid_fill(&mut self, color: Color, transparency: u8) {
-        xml_start_tag_only(&mut self.writer, "a:solidFill");
+        let cache = &range.cache;
+        if cache.has_data() {
+            let format_code = if cache.cache_type == ChartRangeCacheDataType::Date {
+                "dd/mm/yyyy"
+            } else {
+                "General"
+            };
 
-        // Write the color element.
-        self.write_color(color, transparency);
+            let attributes = [
+                ("ptCount", cache.data.len().to_string()),
+                ("formatCode", format_code.to_string()),
+            ];
 
-        xml_end_tag(&mut self.writer, "a:solidFill");
+            xml_start_tag(&mut self.writer, "cx:lvl", &attributes);
+
+            for (index, value) in cache.data.iter().enumerate() {
+                if !value.is_empty() {
+                    // Non numeric values in value/number caches are treated as
+                    // zero by Excel.
+                    if value.parse::<f64>().is_err() {
+                        self.write_cx_pt(index, "0");
+                    } else {
+                        self.write_cx_pt(index, value);
+                    }
+                }
+            }
+
+            xml_end_tag(&mut self.writer, "cx:lvl");
+        }
+
+        xml_end_tag(&mut self.writer, "cx:numDim");
     }
 
-    // Write the <a:pattFill> element.
-    fn write_a_patt_fill(&mut self, fill: &ChartPatternFill) {
-        let attributes = [("prst", fill.pattern.to_string())];
+    // Write the <cx:pt> element.
+    fn write_cx_pt(&mut self, index: usize, value: &str) {
+        let attributes = [("idx", index.to_string())];
 
-        xml_start_tag(&mut self.writer, "a:pattFill", &attributes);
+        xml_data_element(&mut self.writer, "cx:pt", value, &attributes);
+    }
 
-        if fill.foreground_color != Color::Default {
-            // Write the <a:fgClr> element.
-            xml_start_tag_only(&mut self.writer, "a:fgClr");
-            self.write_color(fill.foreground_color, 0);
-            xml_end_tag(&mut self.writer, "a:fgClr");
+    // Write the <cx:chart> element.
+    fn write_cx_chart(&mut self) {
+        xml_start_tag_only(&mut self.writer, "cx:chart");
+
+        // Write the cx:title element.
+        if !self.title.hidden {
+            self.write_cx_title("cx:title", &self.title.clone());
         }
 
-        if fill.background_color != Color::Default {
-            // Write the <a:bgClr> element.
-            xml_start_tag_only(&mut self.writer, "a:bgClr");
-            self.write_color(fill.background_color, 0);
-            xml_end_tag(&mut self.writer, "a:bgClr");
-        } else if fill.background_color == Color::Default && fill.foreground_color != Color::Default
-        {
-            // If there is a foreground color but no background color then we
-            // need to write a default background color.
-            xml_start_tag_only(&mut self.writer, "a:bgClr");
-            self.write_color(Color::White, 0);
-            xml_end_tag(&mut self.writer, "a:bgClr");
+        // Write the cx:plotArea element.
+        self.write_cx_plot_area();
+
+        // Write the cx:legend element.
+        if !self.legend.hidden {
+            self.write_cx_legend();
         }
 
-        xml_end_tag(&mut self.writer, "a:pattFill");
+        xml_end_tag(&mut self.writer, "cx:chart");
     }
 
-    // Write the <a:gradFill> element.
-    fn write_gradient_fill(&mut self, fill: &ChartGradientFill) {
-        let mut attributes = vec![];
-
-        if fill.gradient_type != ChartGradientFillType::Linear {
-            attributes.push(("flip", "none"));
-            attributes.push(("rotWithShape", "1"));
+    // Write the <cx:title> element for the chart or an axis.
+    fn write_cx_title(&mut self, tag: &str, title: &ChartTitle) {
+        if title.name.is_empty() && !title.range.has_data() {
+            return;
         }
 
-        xml_start_tag(&mut self.writer, "a:gradFill", &attributes);
-        xml_start_tag_only(&mut self.writer, "a:gsLst");
+        let mut attributes = vec![];
 
-        for gradient_stop in &fill.gradient_stops {
-            // Write the a:gs element.
-            self.write_gradient_stop(gradient_stop);
+        if tag == "cx:title" {
+            attributes.push(("pos", "t"));
+            attributes.push(("align", "ctr"));
+
+            if title.has_overlay {
+                attributes.push(("overlay", "1"));
+            } else {
+                attributes.push(("overlay", "0"));
+            }
         }
 
-        xml_end_tag(&mut self.writer, "a:gsLst");
+        xml_start_tag(&mut self.writer, tag, &attributes);
 
-        if fill.gradient_type == ChartGradientFillType::Linear {
-            // Write the a:lin element.
-            self.write_gradient_fill_angle(fill.angle);
+        // Write the cx:tx element.
+        if title.name.is_empty() {
+            self.write_cx_tx_data(title);
         } else {
-            // Write the a:path element.
-            self.write_gradient_path(fill.gradient_type);
+            self.write_cx_tx_rich(title);
         }
 
-        xml_end_tag(&mut self.writer, "a:gradFill");
+        // Write the cx:spPr element.
+        self.write_shape_properties("cx:spPr", &title.format);
+
+        // Write the cx:txPr element.
+        if title.name.is_empty() {
+            self.write_font_properties("cx:txPr", &title.font);
+        }
+
+        xml_end_tag(&mut self.writer, tag);
     }
 
-    // Write the <a:gs> element.
-    fn write_gradient_stop(&mut self, gradient_stop: &ChartGradientStop) {
-        let position = 1000 * u32::from(gradient_stop.position);
-        let attributes = [("pos", position.to_string())];
+    // Write the <cx:tx> element for a rich string title.
+    fn write_cx_tx_rich(&mut self, title: &ChartTitle) {
+        xml_start_tag_only(&mut self.writer, "cx:tx");
+        xml_start_tag_only(&mut self.writer, "cx:rich");
 
-        xml_start_tag(&mut self.writer, "a:gs", &attributes);
-        self.write_color(gradient_stop.color, 0);
+        // Write the a:bodyPr element.
+        self.write_a_body_pr(&title.font, false);
 
-        xml_end_tag(&mut self.writer, "a:gs");
-    }
+        // Write the a:lstStyle element.
+        self.write_a_lst_style();
 
-    // Write the <a:lin> element.
-    fn write_gradient_fill_angle(&mut self, angle: u16) {
-        let angle = 60_000 * u32::from(angle);
-        let attributes = [("ang", angle.to_string()), ("scaled", "0".to_string())];
+        // Write the a:p element.
+        self.write_a_p_rich(title);
 
-        xml_empty_tag(&mut self.writer, "a:lin", &attributes);
+        xml_end_tag(&mut self.writer, "cx:rich");
+        xml_end_tag(&mut self.writer, "cx:tx");
     }
 
-    // Write the <a:path> element.
-    fn write_gradient_path(&mut self, gradient_type: ChartGradientFillType) {
-        let mut attributes = vec![];
+    // Write the <cx:tx> element for a title or series name from a cell range.
+    fn write_cx_tx_data(&mut self, title: &ChartTitle) {
+        xml_start_tag_only(&mut self.writer, "cx:tx");
+        xml_start_tag_only(&mut self.writer, "cx:txData");
 
-        match gradient_type {
-            ChartGradientFillType::Radial => attributes.push(("path", "circle")),
-            ChartGradientFillType::Rectangular => attributes.push(("path", "rect")),
-            ChartGradientFillType::Path => attributes.push(("path", "shape")),
-            ChartGradientFillType::Linear => {}
+        // Write the cx:f element.
+        xml_data_element_only(&mut self.writer, "cx:f", &title.range.formula_string());
+
+        // Write the cx:v element.
+        if let Some(value) = title.range.cache.data.first() {
+            xml_data_element_only(&mut self.writer, "cx:v", value);
         }
 
-        xml_start_tag(&mut self.writer, "a:path", &attributes);
+        xml_end_tag(&mut self.writer, "cx:txData");
+        xml_end_tag(&mut self.writer, "cx:tx");
+    }
 
-        // Write the a:fillToRect element.
-        self.write_a_fill_to_rect(gradient_type);
+    // Write the <cx:tx> element for a series name.
+    fn write_cx_series_name(&mut self, title: &ChartTitle) {
+        if !title.name.is_empty() {
+            xml_start_tag_only(&mut self.writer, "cx:tx");
+            xml_start_tag_only(&mut self.writer, "cx:txData");
+            xml_data_element_only(&mut self.writer, "cx:v", &title.name);
+            xml_end_tag(&mut self.writer, "cx:txData");
+            xml_end_tag(&mut self.writer, "cx:tx");
+        } else if title.range.has_data() {
+            self.write_cx_tx_data(title);
+        }
+    }
 
-        xml_end_tag(&mut self.writer, "a:path");
+    // Write the <cx:plotArea> element.
+    fn write_cx_plot_area(&mut self) {
+        xml_start_tag_only(&mut self.writer, "cx:plotArea");
+        xml_start_tag_only(&mut self.writer, "cx:plotAreaRegion");
 
-        // Write the a:tileRect element.
-        self.write_a_tile_rect(gradient_type);
-    }
+        // Write the cx:plotSurface element.
+        if self.plot_area.format.has_formatting() {
+            xml_start_tag_only(&mut self.writer, "cx:plotSurface");
+            self.write_shape_properties("cx:spPr", &self.plot_area.format.clone());
+            xml_end_tag(&mut self.writer, "cx:plotSurface");
+        }
 
-    // Write the <a:fillToRect> element.
-    fn write_a_fill_to_rect(&mut self, gradient_type: ChartGradientFillType) {
-        let mut attributes = vec![];
+        for (index, series) in self.series.clone().iter().enumerate() {
+            // Write the cx:series element.
+            self.write_cx_series(index, series);
+        }
 
-        match gradient_type {
-            ChartGradientFillType::Path => {
-                attributes.push(("l", "50000"));
-                attributes.push(("t", "50000"));
-                attributes.push(("r", "50000"));
-                attributes.push(("b", "50000"));
+        // Write the secondary cumulative percentage line for Pareto charts.
+        if self.chart_type == ChartType::Pareto {
+            self.write_cx_pareto_line();
+        }
+
+        xml_end_tag(&mut self.writer, "cx:plotAreaRegion");
+
+        // Write the cx:axis elements. Treemap and Sunburst charts don't have
+        // axes and Funnel charts only have a category axis.
+        match self.chart_type {
+            ChartType::Treemap | ChartType::Sunburst => {}
+            ChartType::Funnel => {
+                self.write_cx_category_axis(&self.x_axis.clone());
             }
             _ => {
-                attributes.push(("l", "100000"));
-                attributes.push(("t", "100000"));
+                self.write_cx_category_axis(&self.x_axis.clone());
+                self.write_cx_value_axis(&self.y_axis.clone());
             }
         }
 
-        xml_empty_tag(&mut self.writer, "a:fillToRect", &attributes);
-    }
-
-    // Write the <a:tileRect> element.
-    fn write_a_tile_rect(&mut self, gradient_type: ChartGradientFillType) {
-        let mut attributes = vec![];
-
-        match gradient_type {
-            ChartGradientFillType::Rectangular | ChartGradientFillType::Radial => {
-                attributes.push(("r", "-100000"));
-                attributes.push(("b", "-100000"));
-            }
-            _ => {}
+        if self.chart_type == ChartType::Pareto {
+            self.write_cx_percentage_axis();
         }
 
-        xml_empty_tag(&mut self.writer, "a:tileRect", &attributes);
+        xml_end_tag(&mut self.writer, "cx:plotArea");
     }
 
-    // Write the <a:srgbClr> element.
-    fn write_color(&mut self, color: Color, transparency: u8) {
-        match color {
-            Color::Theme(_, _) => {
-                let (scheme, lum_mod, lum_off) = color.chart_scheme();
-                if !scheme.is_empty() {
-                    // Write the a:schemeClr element.
-                    self.write_a_scheme_clr(scheme, lum_mod, lum_off, transparency);
-                }
-            }
-            Color::Automatic => {
-                let attributes = [("val", "window"), ("lastClr", "FFFFFF")];
-
-                xml_empty_tag(&mut self.writer, "a:sysClr", &attributes);
-            }
-            _ => {
-                let attributes = [("val", color.rgb_hex_value())];
+    // Write the <cx:series> element.
+    fn write_cx_series(&mut self, index: usize, series: &ChartSeries) {
+        let layout_id = match self.chart_type {
+            ChartType::BoxWhisker => "boxWhisker",
+            ChartType::Waterfall => "waterfall",
+            ChartType::Funnel => "funnel",
+            ChartType::Treemap => "treemap",
+            ChartType::Sunburst => "sunburst",
+            _ => "clusteredColumn",
+        };
 
-                if transparency > 0 {
-                    xml_start_tag(&mut self.writer, "a:srgbClr", &attributes);
+        let attributes = [
+            ("layoutId", layout_id.to_string()),
+            ("uniqueId", self.cx_unique_id(index)),
+        ];
 
-                    // Write the a:alpha element.
-                    self.write_a_alpha(transparency);
+        xml_start_tag(&mut self.writer, "cx:series", &attributes);
 
-                    xml_end_tag(&mut self.writer, "a:srgbClr");
-                } else {
-                    xml_empty_tag(&mut self.writer, "a:srgbClr", &attributes);
-                }
-            }
-        }
-    }
+        // Write the cx:tx element.
+        self.write_cx_series_name(&series.title);
 
-    // Write the <a:schemeClr> element.
-    fn write_a_scheme_clr(&mut self, scheme: String, lum_mod: u32, lum_off: u32, transparency: u8) {
-        let attributes = [("val", scheme)];
+        // Write the cx:spPr element.
+        self.write_shape_properties("cx:spPr", &series.format);
 
-        if lum_mod > 0 || lum_off > 0 || transparency > 0 {
-            xml_start_tag(&mut self.writer, "a:schemeClr", &attributes);
+        // Write the cx:dataPt elements.
+        for (point_index, point) in series.points.iter().enumerate() {
+            if point.format.has_formatting() {
+                let attributes = [("idx", point_index.to_string())];
 
-            if lum_mod > 0 {
-                // Write the a:lumMod element.
-                self.write_a_lum_mod(lum_mod);
+                xml_start_tag(&mut self.writer, "cx:dataPt", &attributes);
+                self.write_shape_properties("cx:spPr", &point.format);
+                xml_end_tag(&mut self.writer, "cx:dataPt");
             }
+        }
 
-            if lum_off > 0 {
-                // Write the a:lumOff element.
-                self.write_a_lum_off(lum_off);
-            }
+        // Write the cx:dataLabels element.
+        if let Some(data_label) = self.cx_data_label(series) {
+            self.write_cx_data_labels(&data_label);
+        }
 
-            if transparency > 0 {
-                // Write the a:alpha element.
-                self.write_a_alpha(transparency);
-            }
+        // Write the cx:dataId element.
+        let attributes = [("val", index.to_string())];
+        xml_empty_tag(&mut self.writer, "cx:dataId", &attributes);
 
-            xml_end_tag(&mut self.writer, "a:schemeClr");
-        } else {
-            xml_empty_tag(&mut self.writer, "a:schemeClr", &attributes);
+        // Write the cx:layoutPr element.
+        self.write_cx_layout_pr(series);
+
+        // Pareto charts link the column series to the primary value axis.
+        if self.chart_type == ChartType::Pareto {
+            let attributes = [("val", "1")];
+            xml_empty_tag(&mut self.writer, "cx:axisId", &attributes);
         }
+
+        xml_end_tag(&mut self.writer, "cx:series");
     }
 
-    // Write the <a:lumMod> element.
-    fn write_a_lum_mod(&mut self, lum_mod: u32) {
-        let attributes = [("val", lum_mod.to_string())];
+    // Write the <cx:series> element for the Pareto cumulative percentage line.
+    fn write_cx_pareto_line(&mut self) {
+        let attributes = [
+            ("layoutId", "paretoLine".to_string()),
+            ("ownerIdx", "0".to_string()),
+            ("uniqueId", self.cx_unique_id(self.series.len())),
+        ];
 
-        xml_empty_tag(&mut self.writer, "a:lumMod", &attributes);
-    }
+        xml_start_tag(&mut self.writer, "cx:series", &attributes);
 
-    // Write the <a:lumOff> element.
-    fn write_a_lum_off(&mut self, lum_off: u32) {
-        let attributes = [("val", lum_off.to_string())];
+        let attributes = [("val", "2")];
+        xml_empty_tag(&mut self.writer, "cx:axisId", &attributes);
 
-        xml_empty_tag(&mut self.writer, "a:lumOff", &attributes);
+        xml_end_tag(&mut self.writer, "cx:series");
     }
 
-    // Write the <a:alpha> element.
-    fn write_a_alpha(&mut self, transparency: u8) {
-        let transparency = u32::from(100 - transparency) * 1000;
+    // Create a deterministic GUID style unique id for a chartex series.
+    fn cx_unique_id(&self, index: usize) -> String {
+        format!("{{{:08X}-0000-4000-8000-{:012X}}}", self.id, index + 1)
+    }
 
-        let attributes = [("val", transparency.to_string())];
+    // Get the user defined data label for a series or the default for the
+    // chart type, if any.
+    fn cx_data_label(&self, series: &ChartSeries) -> Option<ChartDataLabel> {
+        if let Some(data_label) = &series.data_label {
+            if data_label.is_hidden {
+                return None;
+            }
 
-        xml_empty_tag(&mut self.writer, "a:alpha", &attributes);
-    }
+            let mut data_label = data_label.clone();
+            if data_label.position == ChartDataLabelPosition::Default {
+                data_label.position = self.default_label_position;
+            }
 
-    // Write the <a:noFill> element.
-    fn write_a_no_fill(&mut self) {
-        xml_empty_tag_only(&mut self.writer, "a:noFill");
-    }
+            return Some(data_label);
+        }
 
-    // Write the <a:prstDash> element.
-    fn write_a_prst_dash(&mut self, line: &ChartLine) {
-        let attributes = [("val", line.dash_type.to_string())];
+        let mut data_label = ChartDataLabel::new();
+        data_label.set_position(self.default_label_position);
 
-        xml_empty_tag(&mut self.writer, "a:prstDash", &attributes);
+        match self.chart_type {
+            ChartType::Waterfall | ChartType::Funnel => {
+                data_label.show_value();
+            }
+            ChartType::Treemap | ChartType::Sunburst => {
+                data_label.show_category_name();
+            }
+            _ => return None,
+        }
+
+        Some(data_label)
     }
 
-    // Write the <c:radarStyle> element.
-    fn write_radar_style(&mut self) {
+    // Write the <cx:dataLabels> element.
+    fn write_cx_data_labels(&mut self, data_label: &ChartDataLabel) {
         let mut attributes = vec![];
 
-        if self.chart_type == ChartType::RadarFilled {
-            attributes.push(("val", "filled".to_string()));
-        } else {
-            attributes.push(("val", "marker".to_string()));
+        if data_label.position != ChartDataLabelPosition::Default {
+            attributes.push(("pos", data_label.position.to_string()));
         }
 
-        xml_empty_tag(&mut self.writer, "c:radarStyle", &attributes);
-    }
+        xml_start_tag(&mut self.writer, "cx:dataLabels", &attributes);
 
-    // Write the <c:majorTickMark> element.
-    fn write_major_tick_mark(&mut self, position: ChartAxisTickType) {
-        let attributes = [("val", position.to_string())];
+        // Write the cx:numFmt element.
+        if !data_label.num_format.is_empty() {
+            let attributes = [
+                ("formatCode", data_label.num_format.clone()),
+                ("sourceLinked", "0".to_string()),
+            ];
 
-        xml_empty_tag(&mut self.writer, "c:majorTickMark", &attributes);
-    }
+            xml_empty_tag(&mut self.writer, "cx:numFmt", &attributes);
+        }
 
-    // Write the <c:minorTickMark> element.
-    fn write_minor_tick_mark(&mut self, tick_type: ChartAxisTickType) {
-        let attributes = [("val", tick_type.to_string())];
+        // Write the cx:spPr element.
+        self.write_shape_properties("cx:spPr", &data_label.format);
 
-        xml_empty_tag(&mut self.writer, "c:minorTickMark", &attributes);
-    }
+        // Write the cx:txPr element.
+        if let Some(font) = &data_label.font {
+            self.write_font_properties("cx:txPr", font);
+        }
 
-    // Write the <c:gapWidth> element.
-    fn write_gap_width(&mut self, gap: u16) {
-        let attributes = [("val", gap.to_string())];
+        // Write the cx:visibility element. Ensure at least one display option
+        // is set.
+        let show_value = data_label.show_value
+            || (!data_label.show_category_name && !data_label.show_series_name);
 
-        xml_empty_tag(&mut self.writer, "c:gapWidth", &attributes);
-    }
+        let attributes = [
+            ("seriesName", data_label.show_series_name.to_xml_bool()),
+            ("categoryName", data_label.show_category_name.to_xml_bool()),
+            ("value", show_value.to_xml_bool()),
+        ];
 
-    // Write the <c:overlap> element.
-    fn write_overlap(&mut self) {
-        if let Some(overlap) = &self.overlap {
-            let attributes = [("val", overlap.to_string())];
+        xml_empty_tag(&mut self.writer, "cx:visibility", &attributes);
 
-            xml_empty_tag(&mut self.writer, "c:overlap", &attributes);
+        // Write the cx:separator element.
+        if data_label.separator != ',' {
+            xml_data_element_only(
+                &mut self.writer,
+                "cx:separator",
+                &format!("{} ", data_label.separator),
+            );
         }
+
+        xml_end_tag(&mut self.writer, "cx:dataLabels");
     }
 
-    // Write the <c:smooth> element.
-    fn write_smooth(&mut self) {
-        let attributes = [("val", "1")];
+    // Write the <cx:layoutPr> element with the chart type specific options.
+    fn write_cx_layout_pr(&mut self, series: &ChartSeries) {
+        match self.chart_type {
+            ChartType::Histogram | ChartType::Pareto => {
+                xml_start_tag_only(&mut self.writer, "cx:layoutPr");
+
+                let has_bins = self.bin_width.is_some()
+                    || self.bin_count.is_some()
+                    || self.bin_overflow.is_some()
+                    || self.bin_underflow.is_some();
+
+                // Data with categories is aggregated by category instead of
+                // being grouped into bins.
+                if series.category_range.has_data() && !has_bins {
+                    xml_empty_tag_only(&mut self.writer, "cx:aggregation");
+                } else {
+                    self.write_cx_binning();
+                }
 
-        xml_empty_tag(&mut self.writer, "c:smooth", &attributes);
-    }
+                xml_end_tag(&mut self.writer, "cx:layoutPr");
+            }
 
-    // Write the <c:style> element.
-    fn write_style(&mut self) {
-        let attributes = [("val", self.style.to_string())];
+            ChartType::BoxWhisker => {
+                xml_start_tag_only(&mut self.writer, "cx:layoutPr");
 
-        xml_empty_tag(&mut self.writer, "c:style", &attributes);
-    }
+                let attributes = [
+                    ("meanLine", self.show_mean_line.to_xml_bool()),
+                    ("meanMarker", self.show_mean_markers.to_xml_bool()),
+                    ("nonoutliers", self.show_inner_points.to_xml_bool()),
+                    ("outliers", self.show_outlier_points.to_xml_bool()),
+                ];
+                xml_empty_tag(&mut self.writer, "cx:visibility", &attributes);
 
-    // Write the <c:autoTitleDeleted> element.
-    fn write_auto_title_deleted(&mut self) {
-        let attributes = [("val", "1")];
+                let attributes = [("quartileMethod", self.quartile_method.to_string())];
+                xml_empty_tag(&mut self.writer, "cx:statistics", &attributes);
 
-        xml_empty_tag(&mut self.writer, "c:autoTitleDeleted", &attributes);
-    }
+                xml_end_tag(&mut self.writer, "cx:layoutPr");
+            }
 
-    // Write the <c:title> element.
-    fn write_title_formula(&mut self, title: &ChartTitle) {
-        xml_start_tag_only(&mut self.writer, "c:title");
+            ChartType::Waterfall => {
+                if self.show_connector_lines && self.total_points.is_empty() {
+                    return;
+                }
 
-        // Write the c:tx element.
-        self.write_tx_formula(title);
+                xml_start_tag_only(&mut self.writer, "cx:layoutPr");
 
-        // Write the c:layout element.
-        self.write_layout(&title.layout);
+                if !self.show_connector_lines {
+                    let attributes = [("connectorLines", "0")];
+                    xml_empty_tag(&mut self.writer, "cx:visibility", &attributes);
+                }
 
-        // Write the c:overlay element.
-        if title.has_overlay {
-            self.write_overlay();
-        }
+                if !self.total_points.is_empty() {
+                    xml_start_tag_only(&mut self.writer, "cx:subtotals");
 
-        // Write the c:spPr formatting element.
-        self.write_sp_pr(&title.format.clone());
+                    for point in self.total_points.clone() {
+                        let attributes = [("val", point.to_string())];
+                        xml_empty_tag(&mut self.writer, "cx:idx", &attributes);
+                    }
 
-        // Write the c:txPr element.
-        self.write_tx_pr(&title.font, title.is_horizontal);
+                    xml_end_tag(&mut self.writer, "cx:subtotals");
+                }
+
+                xml_end_tag(&mut self.writer, "cx:layoutPr");
+            }
 
-        xml_end_tag(&mut self.writer, "c:title");
-    }
+            ChartType::Treemap => {
+                xml_start_tag_only(&mut self.writer, "cx:layoutPr");
 
-    // Write the <c:tx> element.
-    fn write_tx_formula(&mut self, title: &ChartTitle) {
-        xml_start_tag_only(&mut self.writer, "c:tx");
+                let attributes = [("val", self.parent_label_layout.to_string())];
+                xml_empty_tag(&mut self.writer, "cx:parentLabelLayout", &attributes);
 
-        // Title is always a string type.
-        self.write_str_ref(&title.range);
+                xml_end_tag(&mut self.writer, "cx:layoutPr");
+            }
 
-        xml_end_tag(&mut self.writer, "c:tx");
+            _ => {}
+        }
     }
 
-    // Write the <c:title> element.
-    fn write_title_rich(&mut self, title: &ChartTitle) {
-        xml_start_tag_only(&mut self.writer, "c:title");
+    // Write the <cx:binning> element.
+    fn write_cx_binning(&mut self) {
+        let mut attributes = vec![("intervalClosed", "r".to_string())];
 
-        // Write the c:tx element.
-        self.write_tx_rich(title);
+        if let Some(value) = self.bin_underflow {
+            attributes.push(("underflow", value.to_string()));
+        }
 
-        // Write the c:layout element.
-        self.write_layout(&title.layout);
+        if let Some(value) = self.bin_overflow {
+            attributes.push(("overflow", value.to_string()));
+        }
 
-        // Write the c:overlay element.
-        if title.has_overlay {
-            self.write_overlay();
+        if self.bin_width.is_none() && self.bin_count.is_none() {
+            xml_empty_tag(&mut self.writer, "cx:binning", &attributes);
+            return;
         }
 
-        if title.format.has_formatting() {
-            // Write the c:spPr element.
-            self.write_sp_pr(&title.format.clone());
+        xml_start_tag(&mut self.writer, "cx:binning", &attributes);
+
+        if let Some(width) = self.bin_width {
+            let attributes = [("val", width.to_string())];
+            xml_empty_tag(&mut self.writer, "cx:binSize", &attributes);
+        } else if let Some(count) = self.bin_count {
+            let attributes = [("val", count.to_string())];
+            xml_empty_tag(&mut self.writer, "cx:binCount", &attributes);
         }
 
-        xml_end_tag(&mut self.writer, "c:title");
+        xml_end_tag(&mut self.writer, "cx:binning");
     }
 
-    // Write the <c:title> element.
-    fn write_title_format_only(&mut self, title: &ChartTitle) {
-        xml_start_tag_only(&mut self.writer, "c:title");
-
-        // Write the c:layout element.
-        self.write_layout(&title.layout);
+    // Write the <cx:axis> element for the category axis.
+    fn write_cx_category_axis(&mut self, axis: &ChartAxis) {
+        self.write_cx_axis_start(0, axis);
 
-        // Write the c:overlay element.
-        if title.has_overlay {
-            self.write_overlay();
-        }
+        // Write the cx:catScaling element. The gap is stored as a percentage.
+        let gap = match self.series.first() {
+            Some(series) if series.gap != 150 => series.gap,
+            _ => self.gap,
+        };
 
-        // Write the c:spPr element.
-        self.write_sp_pr(&title.format.clone());
+        let attributes = [("gapWidth", (f64::from(gap) / 100.0).to_string())];
+        xml_empty_tag(&mut self.writer, "cx:catScaling", &attributes);
 
-        xml_end_tag(&mut self.writer, "c:title");
+        self.write_cx_axis_end(axis);
     }
 
-    // Write the <c:tx> element.
-    fn write_tx_rich(&mut self, title: &ChartTitle) {
-        xml_start_tag_only(&mut self.writer, "c:tx");
+    // Write the <cx:axis> element for the value axis.
+    fn write_cx_value_axis(&mut self, axis: &ChartAxis) {
+        self.write_cx_axis_start(1, axis);
 
-        // Write the c:rich element.
-        self.write_rich(title);
+        // Write the cx:valScaling element.
+        let mut attributes = vec![];
 
-        xml_end_tag(&mut self.writer, "c:tx");
-    }
+        if !axis.max.is_empty() {
+            attributes.push(("max", axis.max.clone()));
+        }
 
-    // Write the <c:tx> element.
-    fn write_tx_value(&mut self, title: &ChartTitle) {
-        xml_start_tag_only(&mut self.writer, "c:tx");
+        if !axis.min.is_empty() {
+            attributes.push(("min", axis.min.clone()));
+        }
 
-        xml_data_element_only(&mut self.writer, "c:v", &title.name);
+        if !axis.major_unit.is_empty() {
+            attributes.push(("majorUnit", axis.major_unit.clone()));
+        }
 
-        xml_end_tag(&mut self.writer, "c:tx");
+        if !axis.minor_unit.is_empty() {
+            attributes.push(("minorUnit", axis.minor_unit.clone()));
+        }
+
+        xml_empty_tag(&mut self.writer, "cx:valScaling", &attributes);
+
+        self.write_cx_axis_end(axis);
     }
 
-    // Write the <c:rich> element.
-    fn write_rich(&mut self, title: &ChartTitle) {
-        xml_start_tag_only(&mut self.writer, "c:rich");
+    // Write the <cx:axis> element for the Pareto percentage axis.
+    fn write_cx_percentage_axis(&mut self) {
+        let attributes = [("id", "2")];
 
-        // Write the a:bodyPr element.
-        self.write_a_body_pr(&title.font, title.is_horizontal);
+        xml_start_tag(&mut self.writer, "cx:axis", &attributes);
 
-        // Write the a:lstStyle element.
-        self.write_a_lst_style();
+        let attributes = [("max", "1"), ("min", "0")];
+        xml_empty_tag(&mut self.writer, "cx:valScaling", &attributes);
 
-        // Write the a:p element.
-        self.write_a_p_rich(title);
+        let attributes = [("unit", "percentage")];
+        xml_empty_tag(&mut self.writer, "cx:units", &attributes);
 
-        xml_end_tag(&mut self.writer, "c:rich");
+        xml_empty_tag_only(&mut self.writer, "cx:tickLabels");
+
+        xml_end_tag(&mut self.writer, "cx:axis");
     }
 
-    // Write the <a:p> element.
-    fn write_a_p_rich(&mut self, title: &ChartTitle) {
-        xml_start_tag_only(&mut self.writer, "a:p");
+    // Write the start of the <cx:axis> element.
+    fn write_cx_axis_start(&mut self, id: u8, axis: &ChartAxis) {
+        let mut attributes = vec![("id", id.to_string())];
 
-        if !title.ignore_rich_para {
-            // Write the a:pPr element.
-            self.write_a_p_pr_rich(&title.font);
+        if axis.is_hidden {
+            attributes.push(("hidden", "1".to_string()));
         }
 
-        // Write the a:r element.
-        self.write_a_r(title);
-
-        xml_end_tag(&mut self.writer, "a:p");
+        xml_start_tag(&mut self.writer, "cx:axis", &attributes);
     }
 
-    // Write the <a:pPr> element.
-    fn write_a_p_pr_rich(&mut self, font: &ChartFont) {
-        let mut attributes = vec![];
+    // Write the common child elements and the end of the <cx:axis> element.
+    fn write_cx_axis_end(&mut self, axis: &ChartAxis) {
+        // Write the cx:title element.
+        self.write_cx_title("cx:title", &axis.title);
 
-        if let Some(right_to_left) = font.right_to_left {
-            attributes.push(("rtl", right_to_left.to_xml_bool()));
+        // Write the cx:majorGridlines element.
+        if axis.major_gridlines {
+            self.write_cx_gridlines("cx:majorGridlines", axis.major_gridlines_line.as_ref());
         }
 
-        xml_start_tag(&mut self.writer, "a:pPr", &attributes);
+        // Write the cx:minorGridlines element.
+        if axis.minor_gridlines {
+            self.write_cx_gridlines("cx:minorGridlines", axis.minor_gridlines_line.as_ref());
+        }
 
-        // Write the a:defRPr element.
-        self.write_a_def_rpr(font);
+        // Write the cx:tickLabels element.
+        if !matches!(axis.label_position, ChartAxisLabelPosition::None) {
+            xml_empty_tag_only(&mut self.writer, "cx:tickLabels");
+        }
 
-        xml_end_tag(&mut self.writer, "a:pPr");
-    }
+        // Write the cx:numFmt element.
+        if !axis.num_format.is_empty() {
+            let attributes = [
+                ("formatCode", axis.num_format.clone()),
+                (
+                    "sourceLinked",
+                    axis.num_format_linked_to_source.to_xml_bool(),
+                ),
+            ];
 
-    // Write the <a:r> element.
-    fn write_a_r(&mut self, title: &ChartTitle) {
-        xml_start_tag_only(&mut self.writer, "a:r");
+            xml_empty_tag(&mut self.writer, "cx:numFmt", &attributes);
+        }
 
-        // Write the a:rPr element.
-        self.write_a_r_pr(&title.font);
+        // Write the cx:spPr element.
+        self.write_shape_properties("cx:spPr", &axis.format);
 
-        // Write the a:t element.
-        self.write_a_t(&title.name);
+        // Write the cx:txPr element.
+        if let Some(font) = &axis.font {
+            self.write_font_properties("cx:txPr", font);
+        }
 
-        xml_end_tag(&mut self.writer, "a:r");
+        xml_end_tag(&mut self.writer, "cx:axis");
     }
 
-    // Write the <c:dispBlanksAs> element.
-    fn write_disp_blanks_as(&mut self) {
-        if let Some(show_empty_cells) = self.show_empty_cells_as {
-            let attributes = [("val", show_empty_cells.to_string())];
+    // Write the <cx:majorGridlines> or <cx:minorGridlines> element.
+    fn write_cx_gridlines(&mut self, tag: &str, line: Option<&ChartLine>) {
+        if let Some(line) = line {
+            xml_start_tag_only(&mut self.writer, tag);
+            xml_start_tag_only(&mut self.writer, "cx:spPr");
 
-            xml_empty_tag(&mut self.writer, "c:dispBlanksAs", &attributes);
+            // Write the a:ln element.
+            self.write_a_ln(line);
+
+            xml_end_tag(&mut self.writer, "cx:spPr");
+            xml_end_tag(&mut self.writer, tag);
+        } else {
+            xml_empty_tag_only(&mut self.writer, tag);
         }
     }
 
-    // Write the <dispNaAsBlank> element. This is an Excel 16 extension.
-    fn write_disp_na_as_blank(&mut self) {
+    // Write the <cx:legend> element.
+    fn write_cx_legend(&mut self) {
+        // The chartex legend doesn't support the top right position.
+        let position = match self.legend.position {
+            ChartLegendPosition::TopRight => ChartLegendPosition::Right,
+            position => position,
+        };
+
         let attributes = [
-            ("uri", "{56B9EC1D-385E-4148-901F-78D8002777C0}"),
-            (
-                "xmlns:c16r3",
-                "http://schemas.microsoft.com/office/drawing/2017/03/chart",
-            ),
+            ("pos", position.to_string()),
+            ("align", "ctr".to_string()),
+            ("overlay", self.legend.has_overlay.to_xml_bool()),
         ];
 
-        xml_start_tag_only(&mut self.writer, "c:extLst");
-        xml_start_tag(&mut self.writer, "c:ext", &attributes);
-        xml_start_tag_only(&mut self.writer, "c16r3:dataDisplayOptions16");
+        let has_formatting = self.legend.format.has_formatting() || self.legend.font.is_some();
 
-        xml_empty_tag(&mut self.writer, "c16r3:dispNaAsBlank", &[("val", "1")]);
+        if !has_formatting {
+            xml_empty_tag(&mut self.writer, "cx:legend", &attributes);
+            return;
+        }
 
-        xml_end_tag(&mut self.writer, "c16r3:dataDisplayOptions16");
-        xml_end_tag(&mut self.writer, "c:ext");
-        xml_end_tag(&mut self.writer, "c:extLst");
-    }
+        xml_start_tag(&mut self.writer, "cx:legend", &attributes);
 
-    fn write_protection(&mut self) {
-        xml_empty_tag_only(&mut self.writer, "c:protection");
+        // Write the cx:spPr element.
+        self.write_shape_properties("cx:spPr", &self.legend.format.clone());
+
+        // Write the cx:txPr element.
+        if let Some(font) = &self.legend.font.clone() {
+            self.write_font_properties("cx:txPr", font);
+        }
+
+        xml_end_tag(&mut self.writer, "cx:legend");
     }
 }
 
@@ -6721,6 +8723,7 @@ impl DrawingObject for Chart {
 pub struct ChartSeries {
     pub(crate) value_range: ChartRange,
     pub(crate) category_range: ChartRange,
+    pub(crate) bubble_size_range: ChartRange,
     pub(crate) title: ChartTitle,
     pub(crate) format: ChartFormat,
     pub(crate) marker: Option<ChartMarker>,
@@ -6833,6 +8836,7 @@ impl ChartSeries {
         ChartSeries {
             value_range: ChartRange::default(),
             category_range: ChartRange::default(),
+            bubble_size_range: ChartRange::default(),
             title: ChartTitle::new(),
             format: ChartFormat::default(),
             marker: None,
@@ -7015,6 +9019,73 @@ impl ChartSeries {
         self
     }
 
+    /// Add a bubble size range to a Bubble chart series.
+    ///
+    /// Bubble charts are a variant of Scatter charts where each data point has
+    /// a third value that determines the size of the bubble. This method sets
+    /// the range of those sizes. It is required for Bubble charts and is
+    /// ignored for other chart types.
+    ///
+    /// As with Scatter charts the X values are set via
+    /// [`ChartSeries::set_categories()`] and the Y values are set via
+    /// [`ChartSeries::set_values()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `range`: The range property which can be one of two generic types:
+    ///    - A string with an Excel like range formula such as
+    ///      `"Sheet1!$C$1:$C$3"`.
+    ///    - A tuple that can be used to create the range programmatically using
+    ///      a sheet name and zero indexed row and column values like:
+    ///      `("Sheet1", 0, 2, 2, 2)` (this gives the same range as the previous
+    ///      string value).
+    ///
+    /// # Examples
+    ///
+    /// A chart example demonstrating a Bubble chart with bubble sizes.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_chart_series_set_bubble_sizes.rs
+    /// #
+    /// # use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Add some data for the chart: likelihood, impact and cost.
+    /// #     worksheet.write_column(0, 0, [1, 2, 4, 5])?;
+    /// #     worksheet.write_column(0, 1, [2, 5, 3, 4])?;
+    /// #     worksheet.write_column(0, 2, [10, 40, 20, 30])?;
+    /// #
+    /// #     // Create a new chart.
+    ///     let mut chart = Chart::new(ChartType::Bubble);
+    ///
+    ///     // Add a data series with X values, Y values and bubble sizes.
+    ///     chart
+    ///         .add_series()
+    ///         .set_categories("Sheet1!$A$1:$A$4")
+    ///         .set_values("Sheet1!$B$1:$B$4")
+    ///         .set_bubble_sizes("Sheet1!$C$1:$C$4");
+    ///
+    ///     // Add the chart to the worksheet.
+    ///     worksheet.insert_chart(0, 4, &chart)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("chart.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_bubble_sizes<T>(&mut self, range: T) -> &mut ChartSeries
+    where
+        T: IntoChartRange,
+    {
+        self.bubble_size_range = range.new_chart_range();
+        self
+    }
+
     /// Plot the chart series on the secondary axis.
     ///
     /// It is possible to add a secondary axis of the same type to a chart by
@@ -8718,12 +10789,20 @@ pub(crate) enum ChartRangeCacheDataType {
 #[derive(Clone, Copy, PartialEq, Eq)]
 /// The `ChartType` enum define the type of a [`Chart`] object.
 ///
-/// The main original chart types are supported, see below.
+/// The main original chart types and their 3D variants are supported, see
+/// below, as well as the newer Excel 2016 chart types such as Histogram, Pareto, Box and Whisker,
+/// Waterfall, Funnel, Treemap and Sunburst.
 ///
-/// Support for newer Excel chart types such as Treemap, Sunburst, Box and
-/// Whisker, Statistical Histogram, Waterfall, Funnel and Maps is not currently
-/// planned since the underlying structure is substantially different from the
-/// implemented chart types.
+/// The 3D, Surface and Contour chart types can have their view adjusted via
+/// [`Chart::set_x_rotation()`], [`Chart::set_y_rotation()`],
+/// [`Chart::set_perspective()`], [`Chart::set_depth_percent()`] and
+/// [`Chart::set_right_angle_axes()`].
+///
+/// The newer chart types are stored in a different "chartex" format in the
+/// xlsx file. They support a subset of the [`Chart`] and [`ChartSeries`]
+/// options: series names, values and categories, formatting, data labels,
+/// chart and axis titles and the legend. They cannot be combined with other
+/// charts or used in a chartsheet. Map charts are not supported.
 ///
 pub enum ChartType {
     /// An Area chart type.
@@ -8741,6 +10820,15 @@ pub enum ChartType {
     /// <img src="https://rustxlsxwriter.github.io/images/chart_type_area_percent_stacked.png">
     AreaPercentStacked,
 
+    /// A 3D Area chart type.
+    Area3D,
+
+    /// A stacked 3D Area chart type.
+    Area3DStacked,
+
+    /// A percent stacked 3D Area chart type.
+    Area3DPercentStacked,
+
     /// A Bar (horizontal histogram) chart type.
     ///
     /// <img src="https://rustxlsxwriter.github.io/images/chart_type_bar.png">
@@ -8756,6 +10844,24 @@ pub enum ChartType {
     /// <img src="https://rustxlsxwriter.github.io/images/chart_type_bar_percent_stacked.png">
     BarPercentStacked,
 
+    /// A clustered 3D Bar chart type.
+    Bar3D,
+
+    /// A stacked 3D Bar chart type.
+    Bar3DStacked,
+
+    /// A percent stacked 3D Bar chart type.
+    Bar3DPercentStacked,
+
+    /// A Bubble chart type. Bubble charts are a variant of Scatter charts
+    /// where each point has a third value, set via
+    /// [`ChartSeries::set_bubble_sizes()`], that determines the size of the
+    /// bubble.
+    Bubble,
+
+    /// A Bubble chart type with a 3D effect applied to the bubbles.
+    Bubble3D,
+
     /// A Column (vertical histogram) chart type.
     ///
     /// <img src="https://rustxlsxwriter.github.io/images/chart_type_column.png">
@@ -8771,6 +10877,15 @@ pub enum ChartType {
     /// <img src="https://rustxlsxwriter.github.io/images/chart_type_column_percent_stacked.png">
     ColumnPercentStacked,
 
+    /// A clustered 3D Column chart type.
+    Column3D,
+
+    /// A stacked 3D Column chart type.
+    Column3DStacked,
+
+    /// A percent stacked 3D Column chart type.
+    Column3DPercentStacked,
+
     /// A Doughnut chart type.
     ///
     /// <img src="https://rustxlsxwriter.github.io/images/chart_type_doughnut.png">
@@ -8791,11 +10906,17 @@ pub enum ChartType {
     /// <img src="https://rustxlsxwriter.github.io/images/chart_type_line_percent_stacked.png">
     LinePercentStacked,
 
+    /// A 3D Line chart type.
+    Line3D,
+
     /// A Pie chart type.
     ///
     /// <img src="https://rustxlsxwriter.github.io/images/chart_type_pie.png">
     Pie,
 
+    /// A 3D Pie chart type.
+    Pie3D,
+
     /// A Radar chart type.
     ///
     /// <img src="https://rustxlsxwriter.github.io/images/chart_type_radar.png">
@@ -8849,6 +10970,118 @@ pub enum ChartType {
     ///
     /// <img src="https://rustxlsxwriter.github.io/images/chart_type_stock.png">
     Stock,
+
+    /// A 3D Surface chart type. The series values are plotted as a surface
+    /// against the categories and a series axis.
+    Surface,
+
+    /// A 3D Surface chart type shown as a wireframe without fill colors.
+    SurfaceWireframe,
+
+    /// A Contour chart type. This is a Surface chart viewed from above.
+    Contour,
+
+    /// A Contour chart type shown as a wireframe without fill colors.
+    ContourWireframe,
+
+    /// A statistical Histogram chart type. The series values are grouped into
+    /// bins which are calculated automatically by Excel or set via
+    /// [`Chart::set_bin_width()`] or [`Chart::set_bin_count()`]. If the series
+    /// has a category range the values are aggregated by category instead.
+    ///
+    /// This is an Excel 2016 "chartex" chart type.
+    Histogram,
+
+    /// A Pareto chart type. This is a histogram sorted in descending order
+    /// with an additional line showing the cumulative percentage of the total.
+    ///
+    /// This is an Excel 2016 "chartex" chart type.
+    Pareto,
+
+    /// A Box and Whisker chart type. It shows the distribution of the series
+    /// values in quartiles with the mean and outliers highlighted. See
+    /// [`Chart::set_quartile_method()`] for the related options.
+    ///
+    /// This is an Excel 2016 "chartex" chart type.
+    BoxWhisker,
+
+    /// A Waterfall chart type. It shows a running total as values are added or
+    /// subtracted. Points that represent totals can be set via
+    /// [`Chart::set_total_points()`].
+    ///
+    /// This is an Excel 2016 "chartex" chart type.
+    Waterfall,
+
+    /// A Funnel chart type. It shows values across the stages of a process.
+    ///
+    /// This is an Excel 2016 "chartex" chart type.
+    Funnel,
+
+    /// A Treemap chart type. It shows hierarchical data as nested rectangles.
+    /// The hierarchy levels are taken from the columns of a multi-column
+    /// category range.
+    ///
+    /// This is an Excel 2016 "chartex" chart type.
+    Treemap,
+
+    /// A Sunburst chart type. It shows hierarchical data as concentric rings.
+    /// The hierarchy levels are taken from the columns of a multi-column
+    /// category range.
+    ///
+    /// This is an Excel 2016 "chartex" chart type.
+    Sunburst,
+}
+
+impl ChartType {
+    // Check if the chart type is one of the Excel 2016 chart types that are
+    // stored in the "chartex" format.
+    pub(crate) fn is_chart_ex(self) -> bool {
+        matches!(
+            self,
+            ChartType::Histogram
+                | ChartType::Pareto
+                | ChartType::BoxWhisker
+                | ChartType::Waterfall
+                | ChartType::Funnel
+                | ChartType::Treemap
+                | ChartType::Sunburst
+        )
+    }
+
+    // Check if the chart type is drawn in 3D and has a <c:view3D> element.
+    pub(crate) fn is_3d(self) -> bool {
+        matches!(
+            self,
+            ChartType::Area3D
+                | ChartType::Area3DStacked
+                | ChartType::Area3DPercentStacked
+                | ChartType::Bar3D
+                | ChartType::Bar3DStacked
+                | ChartType::Bar3DPercentStacked
+                | ChartType::Column3D
+                | ChartType::Column3DStacked
+                | ChartType::Column3DPercentStacked
+                | ChartType::Line3D
+                | ChartType::Pie3D
+                | ChartType::Surface
+                | ChartType::SurfaceWireframe
+                | ChartType::Contour
+                | ChartType::ContourWireframe
+        )
+    }
+
+    // Check if the chart type has a third, series, axis.
+    pub(crate) fn has_series_axis(self) -> bool {
+        matches!(
+            self,
+            ChartType::Area3D
+                | ChartType::Line3D
+                | ChartType::Surface
+                | ChartType::SurfaceWireframe
+                | ChartType::Contour
+                | ChartType::ContourWireframe
+        )
+    }
 }
 
 // -----------------------------------------------------------------------
@@ -12771,7 +15004,7 @@ impl fmt::Display for ChartAxisDateUnitType {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ChartGrouping {
     Stacked,
     Standard,
@@ -13385,6 +15618,59 @@ impl fmt::Display for ChartEmptyCells {
     }
 }
 
+/// The `ChartQuartileMethod` enum defines the quartile calculation method for
+/// a Box and Whisker [`Chart`].
+///
+/// These options can be set using the [`Chart::set_quartile_method()`] method.
+///
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ChartQuartileMethod {
+    /// Calculate the quartiles excluding the median value. This is the same as
+    /// the Excel `QUARTILE.EXC()` function. The default.
+    Exclusive,
+
+    /// Calculate the quartiles including the median value. This is the same as
+    /// the Excel `QUARTILE.INC()` function.
+    Inclusive,
+}
+
+impl fmt::Display for ChartQuartileMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exclusive => write!(f, "exclusive"),
+            Self::Inclusive => write!(f, "inclusive"),
+        }
+    }
+}
+
+/// The `ChartParentLabelLayout` enum defines the layout of the parent category
+/// labels in a Treemap [`Chart`].
+///
+/// These options can be set using the [`Chart::set_parent_label_layout()`]
+/// method.
+///
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ChartParentLabelLayout {
+    /// Overlap the parent labels with the child rectangles. The default.
+    Overlapping,
+
+    /// Display the parent labels as a banner above the child rectangles.
+    Banner,
+
+    /// Don't display the parent labels.
+    None,
+}
+
+impl fmt::Display for ChartParentLabelLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overlapping => write!(f, "overlapping"),
+            Self::Banner => write!(f, "banner"),
+            Self::None => write!(f, "none"),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------
 // ChartFormat
 // -----------------------------------------------------------------------
@@ -0,0 +1,2230 @@
+// chart svg - A module for rendering an approximation of a chart as SVG.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+use crate::chart::{
+    Chart, ChartAxis, ChartDataLabel, ChartFont, ChartFormat, ChartGrouping, ChartLegendPosition,
+    ChartRange, ChartRangeCacheDataType, ChartSeries, ChartTitle, ChartType,
+};
+use crate::xmlwriter::escape_xml_data;
+use crate::{Color, XlsxError};
+
+// The default Excel series colors.
+const PALETTE: [&str; 12] = [
+    "#4472C4", "#ED7D31", "#A5A5A5", "#FFC000", "#5B9BD5", "#70AD47", "#264478", "#9E480E",
+    "#636363", "#997300", "#255E91", "#43682B",
+];
+
+// The default Excel theme colors used by `Color::Theme()`.
+const THEME_COLORS: [u32; 10] = [
+    0xFFFFFF, 0x000000, 0xE7E6E6, 0x44546A, 0x4472C4, 0xED7D31, 0xA5A5A5, 0xFFC000, 0x5B9BD5,
+    0x70AD47,
+];
+
+const FONT_FAMILY: &str = "Calibri, Arial, sans-serif";
+const TEXT_COLOR: &str = "#595959";
+const AXIS_COLOR: &str = "#BFBFBF";
+const GRIDLINE_COLOR: &str = "#D9D9D9";
+const MARGIN: f64 = 8.0;
+
+// Render a chart, with its range caches already populated, to an SVG string.
+pub(crate) fn render_svg(chart: &Chart) -> Result<String, XlsxError> {
+    check_chart_type(chart.chart_type)?;
+
+    if let Some(combined_chart) = &chart.combined_chart {
+        check_chart_type(combined_chart.chart_type)?;
+    }
+
+    let renderer = SvgRenderer::new(chart);
+
+    Ok(renderer.render())
+}
+
+// Check for chart types that don't have an SVG approximation.
+fn check_chart_type(chart_type: ChartType) -> Result<(), XlsxError> {
+    if chart_type.is_chart_ex()
+        || matches!(
+            chart_type,
+            ChartType::Surface
+                | ChartType::SurfaceWireframe
+                | ChartType::Contour
+                | ChartType::ContourWireframe
+        )
+    {
+        return Err(XlsxError::ChartError(
+            "Excel 2016, Surface and Contour chart types cannot be rendered to SVG".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// -----------------------------------------------------------------------
+// SvgRenderer.
+// -----------------------------------------------------------------------
+
+struct SvgRenderer<'a> {
+    chart: &'a Chart,
+    series: Vec<SeriesData<'a>>,
+    svg: String,
+    width: f64,
+    height: f64,
+}
+
+impl<'a> SvgRenderer<'a> {
+    fn new(chart: &'a Chart) -> SvgRenderer<'a> {
+        let mut series = vec![];
+
+        for each_series in &chart.series {
+            series.push(SeriesData::new(chart, each_series, series.len()));
+        }
+
+        if let Some(combined_chart) = &chart.combined_chart {
+            for each_series in &combined_chart.series {
+                series.push(SeriesData::new(combined_chart, each_series, series.len()));
+            }
+        }
+
+        SvgRenderer {
+            chart,
+            series,
+            svg: String::with_capacity(8192),
+            width: (chart.width * chart.scale_width).round(),
+            height: (chart.height * chart.scale_height).round(),
+        }
+    }
+
+    // Render the chart elements, from the outside in.
+    fn render(mut self) -> String {
+        let _ = writeln!(
+            self.svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" font-family="{FONT_FAMILY}">"#,
+            num(self.width),
+            num(self.height),
+            num(self.width),
+            num(self.height),
+        );
+
+        // Write the chart area background and border.
+        let format = &self.chart.chart_area.format;
+        let fill = format_fill(format).unwrap_or_else(|| "#FFFFFF".to_string());
+        let stroke = format_line(format).unwrap_or_else(|| GRIDLINE_COLOR.to_string());
+        write_rect(
+            &mut self.svg,
+            Rect::new(0.5, 0.5, self.width - 1.0, self.height - 1.0),
+            &fill,
+            &stroke,
+        );
+
+        let mut area = Rect::new(
+            MARGIN,
+            MARGIN,
+            self.width - 2.0 * MARGIN,
+            self.height - 2.0 * MARGIN,
+        );
+
+        self.write_title(&mut area);
+
+        if !self.chart.legend.hidden {
+            self.write_legend(&mut area);
+        }
+
+        match self.chart.chart_group_type {
+            ChartType::Pie | ChartType::Doughnut => self.write_pie(area),
+            ChartType::Radar => self.write_radar(area),
+            _ => self.write_plot(area),
+        }
+
+        self.svg.push_str("</svg>\n");
+        self.svg
+    }
+
+    // -----------------------------------------------------------------------
+    // Title and legend.
+    // -----------------------------------------------------------------------
+
+    // Write the chart title, if any, and reduce the available area.
+    fn write_title(&mut self, area: &mut Rect) {
+        let title = match title_text(&self.chart.title) {
+            Some(title) => title,
+            // Excel uses the series name as the default title of single
+            // series Pie and Doughnut charts.
+            None if self.has_default_title() => self.series[0].name.clone(),
+            None => return,
+        };
+
+        let style = TextStyle::new(Some(&self.chart.title.font), 14.0, true);
+        let y = area.y + style.size;
+        write_text(
+            &mut self.svg,
+            area.x + area.width / 2.0,
+            y,
+            &title,
+            &style.anchor("middle"),
+        );
+
+        area.y += style.size * 1.6;
+        area.height -= style.size * 1.6;
+    }
+
+    // Check if a chart without a user defined title gets a default one.
+    fn has_default_title(&self) -> bool {
+        !self.chart.title.hidden
+            && self.series.len() == 1
+            && self.chart.chart_group_type == ChartType::Pie
+            && self.series[0].series.title.range.has_data()
+    }
+
+    // Write the chart legend and reduce the available area.
+    fn write_legend(&mut self, area: &mut Rect) {
+        let entries = self.legend_entries();
+
+        if entries.is_empty() {
+            return;
+        }
+
+        let style = TextStyle::new(self.chart.legend.font.as_ref(), 9.0, false);
+        let row_height = style.size * 1.5;
+        let key_width = 14.0;
+        let entry_widths: Vec<f64> = entries
+            .iter()
+            .map(|entry| key_width + 6.0 + text_width(&entry.name, style.size))
+            .collect();
+
+        let max_width = entry_widths.iter().copied().fold(0.0, f64::max);
+        let total_width: f64 = entry_widths.iter().map(|width| width + 12.0).sum();
+
+        // Get the position of each legend key and reduce the plot area.
+        let mut positions = vec![];
+        match self.chart.legend.position {
+            ChartLegendPosition::Right
+            | ChartLegendPosition::TopRight
+            | ChartLegendPosition::Left => {
+                let height = row_height * entries.len() as f64;
+                let x = if self.chart.legend.position == ChartLegendPosition::Left {
+                    area.x
+                } else {
+                    area.x + area.width - max_width
+                };
+                let mut y = if self.chart.legend.position == ChartLegendPosition::TopRight {
+                    area.y
+                } else {
+                    area.y + (area.height - height).max(0.0) / 2.0
+                };
+
+                for _ in &entries {
+                    positions.push((x, y));
+                    y += row_height;
+                }
+
+                if self.chart.legend.position == ChartLegendPosition::Left {
+                    area.x += max_width + MARGIN;
+                }
+                area.width -= max_width + MARGIN;
+            }
+            ChartLegendPosition::Top | ChartLegendPosition::Bottom => {
+                let mut x = area.x + (area.width - total_width).max(0.0) / 2.0;
+                let y = if self.chart.legend.position == ChartLegendPosition::Top {
+                    area.y
+                } else {
+                    area.y + area.height - row_height
+                };
+
+                for width in &entry_widths {
+                    positions.push((x, y));
+                    x += width + 12.0;
+                }
+
+                if self.chart.legend.position == ChartLegendPosition::Top {
+                    area.y += row_height + MARGIN;
+                }
+                area.height -= row_height + MARGIN;
+            }
+        }
+
+        let _ = writeln!(self.svg, r#"<g class="legend">"#);
+
+        for (entry, (x, y)) in entries.iter().zip(positions) {
+            let middle = y + row_height / 2.0;
+
+            match entry.key {
+                LegendKey::Fill => {
+                    write_rect(
+                        &mut self.svg,
+                        Rect::new(x + 3.0, middle - 4.0, 8.0, 8.0),
+                        &entry.color,
+                        "none",
+                    );
+                }
+                LegendKey::Line => {
+                    write_line(
+                        &mut self.svg,
+                        x,
+                        middle,
+                        x + key_width,
+                        middle,
+                        &entry.color,
+                        2.0,
+                    );
+                }
+                LegendKey::Marker => {
+                    write_circle(
+                        &mut self.svg,
+                        x + key_width / 2.0,
+                        middle,
+                        3.5,
+                        &entry.color,
+                    );
+                }
+            }
+
+            write_text(
+                &mut self.svg,
+                x + key_width + 6.0,
+                middle + style.size * 0.35,
+                &entry.name,
+                &style,
+            );
+        }
+
+        let _ = writeln!(self.svg, "</g>");
+    }
+
+    // Get the legend entries. Pie and Doughnut charts have one entry per
+    // category while other charts have one entry per series.
+    fn legend_entries(&self) -> Vec<LegendEntry> {
+        let mut entries = vec![];
+
+        if matches!(
+            self.chart.chart_group_type,
+            ChartType::Pie | ChartType::Doughnut
+        ) {
+            if let Some(data) = self.series.first() {
+                let categories = self.categories();
+
+                for (index, _) in data.values.iter().enumerate() {
+                    entries.push(LegendEntry {
+                        name: categories
+                            .get(index)
+                            .cloned()
+                            .unwrap_or_else(|| (index + 1).to_string()),
+                        color: data.point_fill(index, true),
+                        key: LegendKey::Fill,
+                    });
+                }
+            }
+
+            return entries;
+        }
+
+        let deleted_entries = self.chart.deleted_legend_entries();
+
+        for data in &self.series {
+            if deleted_entries.contains(&data.index) {
+                continue;
+            }
+
+            let key = if data.has_line() {
+                LegendKey::Line
+            } else if data.has_markers() {
+                LegendKey::Marker
+            } else {
+                LegendKey::Fill
+            };
+
+            let color = match key {
+                LegendKey::Line => data.line_color(),
+                _ => data.fill_color(),
+            };
+
+            entries.push(LegendEntry {
+                name: data.name.clone(),
+                color,
+                key,
+            });
+        }
+
+        entries
+    }
+
+    // -----------------------------------------------------------------------
+    // Plots with category or value axes.
+    // -----------------------------------------------------------------------
+
+    // Write the plot area, axes and series for charts with X/Y axes.
+    fn write_plot(&mut self, area: Rect) {
+        let chart = self.chart;
+        let is_horizontal = chart.chart_group_type == ChartType::Bar;
+        let is_xy = matches!(
+            chart.chart_group_type,
+            ChartType::Scatter | ChartType::Bubble
+        );
+
+        // Bar charts have the category and value axes reversed.
+        let (category_axis, value_axis, value2_axis) = if is_horizontal {
+            (&chart.y_axis, &chart.x_axis, &chart.x2_axis)
+        } else {
+            (&chart.x_axis, &chart.y_axis, &chart.y2_axis)
+        };
+
+        let categories = self.categories();
+        let num_categories = self
+            .series
+            .iter()
+            .map(|data| data.values.len())
+            .chain([categories.len()])
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let has_secondary = self.series.iter().any(|data| data.series.secondary_axis);
+
+        // Set up the value axis scales.
+        let value_scale = self.value_scale(false, value_axis);
+        let value2_scale = if has_secondary {
+            Some(self.value_scale(true, value2_axis))
+        } else {
+            None
+        };
+        let x_scale = if is_xy {
+            let (min, max) = self.x_value_extent();
+            Some(ValueScale::new(min, max, &chart.x_axis, "General"))
+        } else {
+            None
+        };
+
+        let label_style = TextStyle::new(value_axis.font.as_ref(), 9.0, false);
+        let category_style = TextStyle::new(category_axis.font.as_ref(), 9.0, false);
+        let title_style = |axis: &ChartAxis| TextStyle::new(Some(&axis.title.font), 10.0, true);
+
+        // Calculate the space required for the axis labels and titles.
+        let value_labels: Vec<String> = value_scale
+            .ticks()
+            .iter()
+            .map(|value| format_number(*value, &value_scale.num_format))
+            .collect();
+        let value2_labels: Vec<String> = value2_scale.as_ref().map_or(vec![], |scale| {
+            scale
+                .ticks()
+                .iter()
+                .map(|value| format_number(*value, &scale.num_format))
+                .collect()
+        });
+
+        let max_label_width = |labels: &[String], size: f64| {
+            labels
+                .iter()
+                .map(|label| text_width(label, size))
+                .fold(0.0, f64::max)
+        };
+
+        let mut plot = area;
+        let horizontal_labels_height = category_style.size * 1.6;
+        let vertical_labels_width = if is_horizontal {
+            max_label_width(&categories, category_style.size) + 6.0
+        } else {
+            max_label_width(&value_labels, label_style.size) + 6.0
+        };
+
+        let (vertical_axis, horizontal_axis) = if is_horizontal {
+            (category_axis, value_axis)
+        } else if is_xy {
+            (&chart.y_axis, &chart.x_axis)
+        } else {
+            (value_axis, category_axis)
+        };
+
+        if !vertical_axis.is_hidden {
+            plot.x += vertical_labels_width;
+            plot.width -= vertical_labels_width;
+        }
+        if !horizontal_axis.is_hidden {
+            plot.height -= horizontal_labels_height;
+        }
+        if title_text(&vertical_axis.title).is_some() {
+            let size = title_style(vertical_axis).size * 1.6;
+            plot.x += size;
+            plot.width -= size;
+        }
+        if title_text(&horizontal_axis.title).is_some() {
+            plot.height -= title_style(horizontal_axis).size * 1.6;
+        }
+        if has_secondary && !value2_axis.is_hidden {
+            plot.width -= max_label_width(&value2_labels, label_style.size) + 6.0;
+        }
+
+        plot.x += 4.0;
+        plot.y += 4.0;
+        plot.width = (plot.width - 8.0).max(1.0);
+        plot.height = (plot.height - 8.0).max(1.0);
+
+        // Write the plot area background.
+        let format = &chart.plot_area.format;
+        if let Some(fill) = format_fill(format) {
+            let stroke = format_line(format).unwrap_or_else(|| "none".to_string());
+            write_rect(&mut self.svg, plot, &fill, &stroke);
+        }
+
+        // Set up the mappings from values to plot coordinates.
+        let value_map = if is_horizontal {
+            AxisMap::new(plot.x, plot.width)
+        } else {
+            AxisMap::new(plot.y + plot.height, -plot.height)
+        };
+        let category_map = if is_horizontal {
+            AxisMap::new(plot.y + plot.height, -plot.height)
+        } else {
+            AxisMap::new(plot.x, plot.width)
+        };
+        let categories_between =
+            chart.x_axis.position_between_ticks || chart.chart_group_type == ChartType::Bar;
+        let category_scale = CategoryScale {
+            count: num_categories,
+            between: categories_between,
+            map: category_map,
+        };
+
+        // Write the gridlines.
+        let _ = writeln!(self.svg, r#"<g class="gridlines">"#);
+        if value_axis.major_gridlines {
+            let color = gridline_color(value_axis);
+            for tick in value_scale.ticks() {
+                let position = value_map.position(&value_scale, tick);
+                if is_horizontal {
+                    write_line(
+                        &mut self.svg,
+                        position,
+                        plot.y,
+                        position,
+                        plot.y + plot.height,
+                        &color,
+                        1.0,
+                    );
+                } else {
+                    write_line(
+                        &mut self.svg,
+                        plot.x,
+                        position,
+                        plot.x + plot.width,
+                        position,
+                        &color,
+                        1.0,
+                    );
+                }
+            }
+        }
+        if let Some(x_scale) = &x_scale {
+            if chart.x_axis.major_gridlines {
+                let color = gridline_color(&chart.x_axis);
+                let x_map = AxisMap::new(plot.x, plot.width);
+                for tick in x_scale.ticks() {
+                    let x = x_map.position(x_scale, tick);
+                    write_line(
+                        &mut self.svg,
+                        x,
+                        plot.y,
+                        x,
+                        plot.y + plot.height,
+                        &color,
+                        1.0,
+                    );
+                }
+            }
+        } else if category_axis.major_gridlines {
+            let color = gridline_color(category_axis);
+            for index in 0..=num_categories {
+                let position = category_scale.edge(index);
+                if is_horizontal {
+                    write_line(
+                        &mut self.svg,
+                        plot.x,
+                        position,
+                        plot.x + plot.width,
+                        position,
+                        &color,
+                        1.0,
+                    );
+                } else {
+                    write_line(
+                        &mut self.svg,
+                        position,
+                        plot.y,
+                        position,
+                        plot.y + plot.height,
+                        &color,
+                        1.0,
+                    );
+                }
+            }
+        }
+        let _ = writeln!(self.svg, "</g>");
+
+        // Write the series data.
+        let plot_info = PlotInfo {
+            plot,
+            is_horizontal,
+            value_map,
+            category_scale,
+            x_scale,
+        };
+
+        let _ = writeln!(self.svg, r#"<g class="series">"#);
+        for group in [ChartType::Area, ChartType::Column, ChartType::Bar] {
+            self.write_series_group(group, &plot_info, &value_scale, value2_scale.as_ref());
+        }
+        for group in [
+            ChartType::Line,
+            ChartType::Stock,
+            ChartType::Scatter,
+            ChartType::Bubble,
+        ] {
+            self.write_series_group(group, &plot_info, &value_scale, value2_scale.as_ref());
+        }
+        let _ = writeln!(self.svg, "</g>");
+
+        // Write the axes lines and labels.
+        let _ = writeln!(self.svg, r#"<g class="axes">"#);
+        let zero = value_map.position(
+            &value_scale,
+            0.0_f64.clamp(value_scale.min, value_scale.max),
+        );
+
+        if is_horizontal {
+            write_line(
+                &mut self.svg,
+                zero,
+                plot.y,
+                zero,
+                plot.y + plot.height,
+                AXIS_COLOR,
+                1.0,
+            );
+        } else if let Some(x_scale) = &plot_info.x_scale {
+            let x_map = AxisMap::new(plot.x, plot.width);
+            let x_zero = x_map.position(x_scale, 0.0_f64.clamp(x_scale.min, x_scale.max));
+            write_line(
+                &mut self.svg,
+                plot.x,
+                zero,
+                plot.x + plot.width,
+                zero,
+                AXIS_COLOR,
+                1.0,
+            );
+            write_line(
+                &mut self.svg,
+                x_zero,
+                plot.y,
+                x_zero,
+                plot.y + plot.height,
+                AXIS_COLOR,
+                1.0,
+            );
+        } else {
+            write_line(
+                &mut self.svg,
+                plot.x,
+                zero,
+                plot.x + plot.width,
+                zero,
+                AXIS_COLOR,
+                1.0,
+            );
+        }
+
+        // Write the value axis labels.
+        if !value_axis.is_hidden {
+            for (tick, label) in value_scale.ticks().iter().zip(&value_labels) {
+                let position = value_map.position(&value_scale, *tick);
+                if is_horizontal {
+                    write_text(
+                        &mut self.svg,
+                        position,
+                        plot.y + plot.height + label_style.size * 1.3,
+                        label,
+                        &label_style.anchor("middle"),
+                    );
+                } else {
+                    write_text(
+                        &mut self.svg,
+                        plot.x - 6.0,
+                        position + label_style.size * 0.35,
+                        label,
+                        &label_style.anchor("end"),
+                    );
+                }
+            }
+        }
+
+        // Write the secondary value axis labels.
+        if let Some(value2_scale) = &value2_scale {
+            if !value2_axis.is_hidden {
+                for (tick, label) in value2_scale.ticks().iter().zip(&value2_labels) {
+                    let position = value_map.position(value2_scale, *tick);
+                    if is_horizontal {
+                        write_text(
+                            &mut self.svg,
+                            position,
+                            plot.y - 4.0,
+                            label,
+                            &label_style.anchor("middle"),
+                        );
+                    } else {
+                        write_text(
+                            &mut self.svg,
+                            plot.x + plot.width + 6.0,
+                            position + label_style.size * 0.35,
+                            label,
+                            &label_style,
+                        );
+                    }
+                }
+            }
+        }
+
+        // Write the category or X value axis labels.
+        if let Some(x_scale) = &plot_info.x_scale {
+            if !chart.x_axis.is_hidden {
+                let x_map = AxisMap::new(plot.x, plot.width);
+                for tick in x_scale.ticks() {
+                    let label = format_number(tick, &x_scale.num_format);
+                    write_text(
+                        &mut self.svg,
+                        x_map.position(x_scale, tick),
+                        plot.y + plot.height + category_style.size * 1.3,
+                        &label,
+                        &category_style.anchor("middle"),
+                    );
+                }
+            }
+        } else if !category_axis.is_hidden {
+            let interval = usize::from(category_axis.label_interval.max(1));
+            for index in (0..num_categories).step_by(interval) {
+                let label = categories
+                    .get(index)
+                    .cloned()
+                    .unwrap_or_else(|| (index + 1).to_string());
+                let position = category_scale.center(index);
+
+                if is_horizontal {
+                    write_text(
+                        &mut self.svg,
+                        plot.x - 6.0,
+                        position + category_style.size * 0.35,
+                        &label,
+                        &category_style.anchor("end"),
+                    );
+                } else {
+                    write_text(
+                        &mut self.svg,
+                        position,
+                        plot.y + plot.height + category_style.size * 1.3,
+                        &label,
+                        &category_style.anchor("middle"),
+                    );
+                }
+            }
+        }
+
+        // Write the axis titles.
+        if let Some(title) = title_text(&horizontal_axis.title) {
+            let style = title_style(horizontal_axis).anchor("middle");
+            write_text(
+                &mut self.svg,
+                plot.x + plot.width / 2.0,
+                area.y + area.height - style.size * 0.3,
+                &title,
+                &style,
+            );
+        }
+        if let Some(title) = title_text(&vertical_axis.title) {
+            let style = title_style(vertical_axis).anchor("middle");
+            let x = area.x + style.size;
+            let y = plot.y + plot.height / 2.0;
+            let _ = writeln!(
+                self.svg,
+                r#"<g transform="rotate(-90 {} {})">"#,
+                num(x),
+                num(y)
+            );
+            write_text(&mut self.svg, x, y, &title, &style);
+            let _ = writeln!(self.svg, "</g>");
+        }
+
+        let _ = writeln!(self.svg, "</g>");
+    }
+
+    // Write all the series of a chart group type.
+    fn write_series_group(
+        &mut self,
+        group: ChartType,
+        plot_info: &PlotInfo,
+        value_scale: &ValueScale,
+        value2_scale: Option<&ValueScale>,
+    ) {
+        for is_secondary in [false, true] {
+            let scale = if is_secondary {
+                match value2_scale {
+                    Some(scale) => scale,
+                    None => continue,
+                }
+            } else {
+                value_scale
+            };
+
+            let indices: Vec<usize> = self
+                .series
+                .iter()
+                .enumerate()
+                .filter(|(_, data)| {
+                    data.chart.chart_group_type == group
+                        && data.series.secondary_axis == is_secondary
+                })
+                .map(|(index, _)| index)
+                .collect();
+
+            if indices.is_empty() {
+                continue;
+            }
+
+            let chart = self.series[indices[0]].chart;
+            let stack = Stack::new(&self.series, &indices, chart.grouping);
+
+            match group {
+                ChartType::Column | ChartType::Bar => {
+                    self.write_bars(&indices, &stack, plot_info, scale);
+                }
+                ChartType::Area => self.write_areas(&indices, &stack, plot_info, scale),
+                ChartType::Scatter | ChartType::Bubble => {
+                    self.write_xy_series(&indices, plot_info, scale);
+                }
+                _ => self.write_lines(&indices, &stack, plot_info, scale),
+            }
+        }
+    }
+
+    // Write Column and Bar chart series.
+    fn write_bars(
+        &mut self,
+        indices: &[usize],
+        stack: &Stack,
+        plot_info: &PlotInfo,
+        scale: &ValueScale,
+    ) {
+        let chart = self.series[indices[0]].chart;
+        let is_stacked = chart.grouping != ChartGrouping::Clustered;
+        let num_bars = if is_stacked {
+            1.0
+        } else {
+            indices.len() as f64
+        };
+        let gap = f64::from(self.series[indices[0]].series.gap.max(chart.gap)) / 100.0;
+        let slot = plot_info.category_scale.slot().abs();
+        let bar_width = slot / (num_bars + gap);
+
+        let mut labels = vec![];
+
+        for (position, index) in indices.iter().enumerate() {
+            let data = &self.series[*index];
+
+            for (point, value) in data.values.iter().enumerate() {
+                let Some(value) = value else { continue };
+                let (start, end) = stack.span(position, point, *value);
+
+                let center = plot_info.category_scale.center(point);
+                let offset = if is_stacked {
+                    -bar_width / 2.0
+                } else {
+                    -num_bars * bar_width / 2.0 + position as f64 * bar_width
+                };
+
+                let value_start = plot_info.value_map.position(scale, scale.clamp(start));
+                let value_end = plot_info.value_map.position(scale, scale.clamp(end));
+
+                let rect = if plot_info.is_horizontal {
+                    Rect::new(
+                        value_start.min(value_end),
+                        center - offset - bar_width,
+                        (value_end - value_start).abs(),
+                        bar_width,
+                    )
+                } else {
+                    Rect::new(
+                        center + offset,
+                        value_start.min(value_end),
+                        bar_width,
+                        (value_end - value_start).abs(),
+                    )
+                };
+
+                let fill = data.point_fill(point, false);
+                let stroke = data.point_line(point);
+                labels.push((
+                    *index,
+                    point,
+                    *value,
+                    rect.center_of_end(plot_info.is_horizontal, end < start),
+                ));
+
+                let _ = writeln!(
+                    self.svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="{}"/>"#,
+                    num(rect.x),
+                    num(rect.y),
+                    num(rect.width),
+                    num(rect.height),
+                    fill,
+                    stroke,
+                );
+            }
+        }
+
+        for (index, point, value, (x, y)) in labels {
+            self.series[index].write_data_label(&mut self.svg, point, value, x, y);
+        }
+    }
+
+    // Write Area chart series.
+    fn write_areas(
+        &mut self,
+        indices: &[usize],
+        stack: &Stack,
+        plot_info: &PlotInfo,
+        scale: &ValueScale,
+    ) {
+        for (position, index) in indices.iter().enumerate() {
+            let data = &self.series[*index];
+            let mut top = vec![];
+            let mut bottom = vec![];
+
+            for (point, value) in data.values.iter().enumerate() {
+                let value = value.unwrap_or(0.0);
+                let (start, end) = stack.span(position, point, value);
+                let center = plot_info.category_scale.center(point);
+
+                top.push(plot_info.point(center, scale, end));
+                bottom.push(plot_info.point(center, scale, start));
+            }
+
+            bottom.reverse();
+
+            let points: Vec<String> = top
+                .iter()
+                .chain(&bottom)
+                .map(|(x, y)| format!("{},{}", num(*x), num(*y)))
+                .collect();
+
+            let fill = data.fill_color();
+            let stroke = format_line(&data.series.format).unwrap_or_else(|| "none".to_string());
+
+            let _ = writeln!(
+                self.svg,
+                r#"<polygon points="{}" fill="{}" stroke="{}"/>"#,
+                points.join(" "),
+                fill,
+                stroke
+            );
+        }
+    }
+
+    // Write Line and Stock chart series.
+    fn write_lines(
+        &mut self,
+        indices: &[usize],
+        stack: &Stack,
+        plot_info: &PlotInfo,
+        scale: &ValueScale,
+    ) {
+        for (position, index) in indices.iter().enumerate() {
+            let data = &self.series[*index];
+            let mut points = vec![];
+
+            for (point, value) in data.values.iter().enumerate() {
+                let center = plot_info.category_scale.center(point);
+
+                points.push(value.map(|value| {
+                    let (_, end) = stack.span(position, point, value);
+                    plot_info.point(center, scale, end)
+                }));
+            }
+
+            self.write_series_points(*index, &points);
+        }
+    }
+
+    // Write Scatter and Bubble chart series.
+    fn write_xy_series(&mut self, indices: &[usize], plot_info: &PlotInfo, scale: &ValueScale) {
+        let max_size = indices
+            .iter()
+            .flat_map(|index| self.series[*index].sizes.iter().flatten())
+            .map(|size| size.abs())
+            .fold(0.0, f64::max);
+
+        let max_radius = plot_info.plot.width.min(plot_info.plot.height) / 8.0;
+
+        for index in indices {
+            let data = &self.series[*index];
+            let mut points = vec![];
+
+            for (point, value) in data.values.iter().enumerate() {
+                let x = match &plot_info.x_scale {
+                    Some(x_scale) => {
+                        let x_value = data.x_values.get(point).copied().flatten().unwrap_or(0.0);
+                        AxisMap::new(plot_info.plot.x, plot_info.plot.width)
+                            .position(x_scale, x_value)
+                    }
+                    None => plot_info.category_scale.center(point),
+                };
+
+                points.push(value.map(|value| plot_info.point(x, scale, value)));
+            }
+
+            if data.chart.chart_group_type == ChartType::Bubble {
+                let color = data.fill_color();
+
+                for (point, position) in points.iter().enumerate() {
+                    let Some((x, y)) = position else { continue };
+                    let size = data.sizes.get(point).copied().flatten().unwrap_or(0.0);
+                    let radius = if max_size > 0.0 {
+                        (size.abs() / max_size).sqrt() * max_radius
+                    } else {
+                        0.0
+                    };
+
+                    let _ = writeln!(
+                        self.svg,
+                        r#"<circle cx="{}" cy="{}" r="{}" fill="{}" fill-opacity="0.75" stroke="{}"/>"#,
+                        num(*x),
+                        num(*y),
+                        num(radius),
+                        color,
+                        color
+                    );
+                }
+
+                for (point, position) in points.iter().enumerate() {
+                    if let (Some((x, y)), Some(Some(value))) = (position, data.values.get(point)) {
+                        data.write_data_label(&mut self.svg, point, *value, *x, *y);
+                    }
+                }
+            } else {
+                self.write_series_points(*index, &points);
+            }
+        }
+    }
+
+    // Write the lines, markers and data labels for a series of points. Empty
+    // points break the line.
+    fn write_series_points(&mut self, index: usize, points: &[Option<(f64, f64)>]) {
+        let data = &self.series[index];
+
+        if data.has_line() {
+            let color = data.line_color();
+            let width = format_line_width(&data.series.format).unwrap_or(2.25 * 4.0 / 3.0);
+
+            for segment in points.split(Option::is_none) {
+                let segment: Vec<String> = segment
+                    .iter()
+                    .flatten()
+                    .map(|(x, y)| format!("{},{}", num(*x), num(*y)))
+                    .collect();
+
+                if segment.len() > 1 {
+                    let _ = writeln!(
+                        self.svg,
+                        r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linejoin="round"/>"#,
+                        segment.join(" "),
+                        color,
+                        num(width)
+                    );
+                }
+            }
+        }
+
+        if data.has_markers() {
+            let color = data.marker_color();
+            let radius = match &data.series.marker {
+                Some(marker) if marker.size > 0 => f64::from(marker.size) / 2.0 + 1.0,
+                _ => 3.5,
+            };
+
+            for (x, y) in points.iter().flatten() {
+                write_circle(&mut self.svg, *x, *y, radius, &color);
+            }
+        }
+
+        for (point, position) in points.iter().enumerate() {
+            if let (Some((x, y)), Some(Some(value))) = (position, data.values.get(point)) {
+                data.write_data_label(&mut self.svg, point, *value, *x, *y);
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Pie and Radar plots.
+    // -----------------------------------------------------------------------
+
+    // Write a Pie or Doughnut chart. Only the first series is shown.
+    fn write_pie(&mut self, area: Rect) {
+        let Some(data) = self.series.first() else {
+            return;
+        };
+
+        let cx = area.x + area.width / 2.0;
+        let cy = area.y + area.height / 2.0;
+        let radius = (area.width.min(area.height) / 2.0 - 4.0).max(1.0);
+        let inner_radius = if self.chart.chart_group_type == ChartType::Doughnut {
+            radius * f64::from(self.chart.hole_size) / 100.0
+        } else {
+            0.0
+        };
+
+        let values: Vec<f64> = data
+            .values
+            .iter()
+            .map(|value| value.unwrap_or(0.0).abs())
+            .collect();
+        let total: f64 = values.iter().sum();
+
+        if total == 0.0 {
+            return;
+        }
+
+        let rotation = if self.chart.chart_type == ChartType::Pie3D {
+            self.chart.y_rotation
+        } else {
+            self.chart.rotation
+        };
+
+        // Angles are measured clockwise from 12 o'clock.
+        let point_at = |angle: f64, r: f64| {
+            let radians = (angle - 90.0) * PI / 180.0;
+            (cx + r * radians.cos(), cy + r * radians.sin())
+        };
+
+        let mut angle = f64::from(rotation);
+        let mut labels = vec![];
+
+        for (point, value) in values.iter().enumerate() {
+            let sweep = value / total * 360.0;
+            let fill = data.point_fill(point, true);
+            let stroke = data.point_line(point);
+            let stroke = if stroke == "none" {
+                "#FFFFFF".to_string()
+            } else {
+                stroke
+            };
+
+            if sweep >= 359.99 {
+                let _ = writeln!(
+                    self.svg,
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="{}" stroke="{}"/>"#,
+                    num(cx),
+                    num(cy),
+                    num(radius),
+                    fill,
+                    stroke
+                );
+                if inner_radius > 0.0 {
+                    write_circle(&mut self.svg, cx, cy, inner_radius, "#FFFFFF");
+                }
+            } else if sweep > 0.0 {
+                let large_arc = u8::from(sweep > 180.0);
+                let (x1, y1) = point_at(angle, radius);
+                let (x2, y2) = point_at(angle + sweep, radius);
+
+                let path = if inner_radius > 0.0 {
+                    let (x3, y3) = point_at(angle + sweep, inner_radius);
+                    let (x4, y4) = point_at(angle, inner_radius);
+                    format!(
+                        "M{},{} A{},{} 0 {large_arc} 1 {},{} L{},{} A{},{} 0 {large_arc} 0 {},{} Z",
+                        num(x1),
+                        num(y1),
+                        num(radius),
+                        num(radius),
+                        num(x2),
+                        num(y2),
+                        num(x3),
+                        num(y3),
+                        num(inner_radius),
+                        num(inner_radius),
+                        num(x4),
+                        num(y4)
+                    )
+                } else {
+                    format!(
+                        "M{},{} L{},{} A{},{} 0 {large_arc} 1 {},{} Z",
+                        num(cx),
+                        num(cy),
+                        num(x1),
+                        num(y1),
+                        num(radius),
+                        num(radius),
+                        num(x2),
+                        num(y2)
+                    )
+                };
+
+                let _ = writeln!(
+                    self.svg,
+                    r#"<path d="{path}" fill="{fill}" stroke="{stroke}"/>"#
+                );
+            }
+
+            let label_radius = if inner_radius > 0.0 {
+                (radius + inner_radius) / 2.0
+            } else {
+                radius * 0.65
+            };
+            labels.push((point, point_at(angle + sweep / 2.0, label_radius)));
+
+            angle += sweep;
+        }
+
+        for (point, (x, y)) in labels {
+            data.write_pie_data_label(&mut self.svg, point, total, x, y);
+        }
+    }
+
+    // Write a Radar chart.
+    fn write_radar(&mut self, area: Rect) {
+        let categories = self.categories();
+        let num_categories = self
+            .series
+            .iter()
+            .map(|data| data.values.len())
+            .max()
+            .unwrap_or(0)
+            .max(3);
+
+        let style = TextStyle::new(self.chart.x_axis.font.as_ref(), 9.0, false);
+        let cx = area.x + area.width / 2.0;
+        let cy = area.y + area.height / 2.0;
+        let radius = (area.width.min(area.height) / 2.0 - style.size * 1.6).max(1.0);
+
+        let (min, max) = self.value_extent(false);
+        let scale = ValueScale::new(min, max, &self.chart.y_axis, &self.chart.default_num_format);
+
+        let point_at = |index: usize, value: f64| {
+            let r = (value - scale.min) / (scale.max - scale.min) * radius;
+            let radians = (index as f64 / num_categories as f64 * 360.0 - 90.0) * PI / 180.0;
+            (cx + r * radians.cos(), cy + r * radians.sin())
+        };
+
+        // Write the web gridlines and spokes.
+        for tick in scale.ticks() {
+            let points: Vec<String> = (0..num_categories)
+                .map(|index| {
+                    let (x, y) = point_at(index, tick);
+                    format!("{},{}", num(x), num(y))
+                })
+                .collect();
+
+            let _ = writeln!(
+                self.svg,
+                r#"<polygon points="{}" fill="none" stroke="{GRIDLINE_COLOR}"/>"#,
+                points.join(" ")
+            );
+        }
+
+        for index in 0..num_categories {
+            let (x, y) = point_at(index, scale.max);
+            write_line(&mut self.svg, cx, cy, x, y, GRIDLINE_COLOR, 1.0);
+
+            if !self.chart.x_axis.is_hidden {
+                let label = categories
+                    .get(index)
+                    .cloned()
+                    .unwrap_or_else(|| (index + 1).to_string());
+                let (lx, ly) = point_at(index, scale.max + (scale.max - scale.min) * 0.08);
+                write_text(
+                    &mut self.svg,
+                    lx,
+                    ly + style.size * 0.35,
+                    &label,
+                    &style.anchor("middle"),
+                );
+            }
+        }
+
+        // Write the series data.
+        for index in 0..self.series.len() {
+            let data = &self.series[index];
+            let points: Vec<Option<(f64, f64)>> = data
+                .values
+                .iter()
+                .enumerate()
+                .map(|(point, value)| Some(point_at(point, scale.clamp(value.unwrap_or(0.0)))))
+                .collect();
+
+            if data.chart.chart_type == ChartType::RadarFilled {
+                let color = data.fill_color();
+                let points: Vec<String> = points
+                    .iter()
+                    .flatten()
+                    .map(|(x, y)| format!("{},{}", num(*x), num(*y)))
+                    .collect();
+
+                let _ = writeln!(
+                    self.svg,
+                    r#"<polygon points="{}" fill="{color}" fill-opacity="0.75" stroke="{color}"/>"#,
+                    points.join(" ")
+                );
+            } else {
+                // Close the radar line back to the first point.
+                let mut points = points;
+                if let Some(first) = points.first().copied() {
+                    points.push(first);
+                }
+                self.write_series_points(index, &points[..points.len().saturating_sub(1)]);
+
+                if self.series[index].has_line() && points.len() > 2 {
+                    let color = self.series[index].line_color();
+                    if let (Some(Some((x1, y1))), Some(Some((x2, y2)))) =
+                        (points.get(points.len() - 2), points.last())
+                    {
+                        write_line(&mut self.svg, *x1, *y1, *x2, *y2, &color, 2.25 * 4.0 / 3.0);
+                    }
+                }
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Data helper methods.
+    // -----------------------------------------------------------------------
+
+    // Get the category names from the first series that has them.
+    fn categories(&self) -> Vec<String> {
+        self.series
+            .iter()
+            .find(|data| data.series.category_range.has_data())
+            .map_or(vec![], |data| range_strings(&data.series.category_range))
+    }
+
+    // Get the min and max values for a primary or secondary axis, allowing for
+    // stacked charts.
+    fn value_extent(&self, is_secondary: bool) -> (f64, f64) {
+        let mut min = f64::MAX;
+        let mut max = f64::MIN;
+
+        let mut charts = vec![self.chart];
+        if let Some(combined_chart) = &self.chart.combined_chart {
+            charts.push(combined_chart);
+        }
+
+        for chart in charts {
+            let indices: Vec<usize> = self
+                .series
+                .iter()
+                .enumerate()
+                .filter(|(_, data)| {
+                    std::ptr::eq(data.chart, chart) && data.series.secondary_axis == is_secondary
+                })
+                .map(|(index, _)| index)
+                .collect();
+
+            if indices.is_empty() {
+                continue;
+            }
+
+            let stack = Stack::new(&self.series, &indices, chart.grouping);
+
+            for (position, index) in indices.iter().enumerate() {
+                for (point, value) in self.series[*index].values.iter().enumerate() {
+                    if let Some(value) = value {
+                        let (start, end) = stack.span(position, point, *value);
+                        min = min.min(start.min(end));
+                        max = max.max(start.max(end));
+                    }
+                }
+            }
+        }
+
+        if min > max {
+            (0.0, 1.0)
+        } else {
+            (min, max)
+        }
+    }
+
+    // Get the min and max X values for Scatter and Bubble charts.
+    fn x_value_extent(&self) -> (f64, f64) {
+        let values: Vec<f64> = self
+            .series
+            .iter()
+            .flat_map(|data| data.x_values.iter().flatten().copied())
+            .collect();
+
+        if values.is_empty() {
+            (0.0, 1.0)
+        } else {
+            (
+                values.iter().copied().fold(f64::MAX, f64::min),
+                values.iter().copied().fold(f64::MIN, f64::max),
+            )
+        }
+    }
+
+    // Get the value scale for the primary or secondary axis.
+    fn value_scale(&self, is_secondary: bool, axis: &ChartAxis) -> ValueScale {
+        let (min, max) = self.value_extent(is_secondary);
+
+        ValueScale::new(min, max, axis, &self.chart.default_num_format)
+    }
+}
+
+// -----------------------------------------------------------------------
+// SeriesData.
+// -----------------------------------------------------------------------
+
+// The data and formatting for a series, resolved from the range caches.
+struct SeriesData<'a> {
+    chart: &'a Chart,
+    series: &'a ChartSeries,
+    index: usize,
+    name: String,
+    values: Vec<Option<f64>>,
+    x_values: Vec<Option<f64>>,
+    sizes: Vec<Option<f64>>,
+}
+
+impl<'a> SeriesData<'a> {
+    fn new(chart: &'a Chart, series: &'a ChartSeries, index: usize) -> SeriesData<'a> {
+        let name = title_text(&series.title).unwrap_or_else(|| format!("Series{}", index + 1));
+
+        let x_values = if matches!(
+            chart.chart_group_type,
+            ChartType::Scatter | ChartType::Bubble
+        ) {
+            let x_values = range_numbers(&series.category_range);
+
+            // Excel uses 1, 2, 3, ... for missing or non-numeric X values.
+            if x_values.iter().all(Option::is_none) {
+                (1..=series.value_range.cache.data.len())
+                    .map(|value| Some(value as f64))
+                    .collect()
+            } else {
+                x_values
+            }
+        } else {
+            vec![]
+        };
+
+        SeriesData {
+            chart,
+            series,
+            index,
+            name,
+            values: range_numbers(&series.value_range),
+            x_values,
+            sizes: range_numbers(&series.bubble_size_range),
+        }
+    }
+
+    // Get the default color of the series from the Excel palette.
+    fn palette_color(&self, index: usize) -> String {
+        PALETTE[index % PALETTE.len()].to_string()
+    }
+
+    // Get the fill color of the series.
+    fn fill_color(&self) -> String {
+        format_fill(&self.series.format)
+            .or_else(|| format_line(&self.series.format))
+            .unwrap_or_else(|| self.palette_color(self.index))
+    }
+
+    // Get the line color of the series.
+    fn line_color(&self) -> String {
+        format_line(&self.series.format)
+            .or_else(|| format_fill(&self.series.format))
+            .unwrap_or_else(|| self.palette_color(self.index))
+    }
+
+    // Get the marker color of the series.
+    fn marker_color(&self) -> String {
+        self.series
+            .marker
+            .as_ref()
+            .and_then(|marker| format_fill(&marker.format))
+            .filter(|color| color != "none")
+            .unwrap_or_else(|| self.palette_color(self.index))
+    }
+
+    // Get the fill color for a point. Pie charts vary the colors by point.
+    fn point_fill(&self, point: usize, vary_colors: bool) -> String {
+        if let Some(color) = self
+            .series
+            .points
+            .get(point)
+            .and_then(|point| format_fill(&point.format))
+        {
+            return color;
+        }
+
+        if vary_colors {
+            self.palette_color(point)
+        } else {
+            self.fill_color()
+        }
+    }
+
+    // Get the border color for a point.
+    fn point_line(&self, point: usize) -> String {
+        self.series
+            .points
+            .get(point)
+            .and_then(|point| format_line(&point.format))
+            .or_else(|| format_line(&self.series.format))
+            .unwrap_or_else(|| "none".to_string())
+    }
+
+    // Check if the series is drawn with a line.
+    fn has_line(&self) -> bool {
+        match self.chart.chart_group_type {
+            ChartType::Line | ChartType::Stock | ChartType::Scatter | ChartType::Radar => {
+                format_line(&self.series.format).as_deref() != Some("none")
+            }
+            _ => false,
+        }
+    }
+
+    // Check if the series is drawn with markers.
+    fn has_markers(&self) -> bool {
+        if !matches!(
+            self.chart.chart_group_type,
+            ChartType::Line | ChartType::Stock | ChartType::Scatter | ChartType::Radar
+        ) {
+            return false;
+        }
+
+        match &self.series.marker {
+            Some(marker) => !marker.none,
+            None => matches!(
+                self.chart.chart_type,
+                ChartType::Scatter
+                    | ChartType::ScatterStraightWithMarkers
+                    | ChartType::ScatterSmoothWithMarkers
+                    | ChartType::RadarWithMarkers
+            ),
+        }
+    }
+
+    // Get the data label for a point, allowing for custom data labels.
+    fn data_label(&self, point: usize) -> Option<&ChartDataLabel> {
+        let data_label = self.series.data_label.as_ref()?;
+
+        match self.series.custom_data_labels.get(point) {
+            Some(custom) if custom.is_hidden => None,
+            Some(custom) if custom.is_custom => Some(custom),
+            _ if data_label.is_hidden => None,
+            _ => Some(data_label),
+        }
+    }
+
+    // Get the data label font for a point.
+    fn data_label_font(&self, point: usize) -> Option<&ChartFont> {
+        self.data_label(point)
+            .and_then(|data_label| data_label.font.as_ref())
+    }
+
+    // Get the text of the data label for a point, if any.
+    fn data_label_text(&self, point: usize, value: f64, total: Option<f64>) -> Option<String> {
+        let data_label = self.data_label(point)?;
+
+        if let Some(text) = title_text(&data_label.title) {
+            return Some(text);
+        }
+
+        let num_format = if data_label.num_format.is_empty() {
+            "General"
+        } else {
+            &data_label.num_format
+        };
+
+        let mut parts = vec![];
+
+        if data_label.show_series_name {
+            parts.push(self.name.clone());
+        }
+
+        if data_label.show_category_name {
+            let categories = range_strings(&self.series.category_range);
+            parts.push(
+                categories
+                    .get(point)
+                    .cloned()
+                    .unwrap_or_else(|| (point + 1).to_string()),
+            );
+        }
+
+        if data_label.show_value {
+            parts.push(format_number(value, num_format));
+        }
+
+        if data_label.show_percentage {
+            if let Some(total) = total {
+                parts.push(format_number(value.abs() / total, "0%"));
+            }
+        }
+
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(&format!("{} ", data_label.separator)))
+        }
+    }
+
+    // Write the data label for a point, if required.
+    fn write_data_label(&self, svg: &mut String, point: usize, value: f64, x: f64, y: f64) {
+        let Some(text) = self.data_label_text(point, value, None) else {
+            return;
+        };
+
+        let style = TextStyle::new(self.data_label_font(point), 9.0, false).anchor("middle");
+        write_text(svg, x, y - 4.0, &text, &style);
+    }
+
+    // Write the data label for a Pie or Doughnut slice, if required.
+    fn write_pie_data_label(&self, svg: &mut String, point: usize, total: f64, x: f64, y: f64) {
+        let value = self.values[point].unwrap_or(0.0);
+        let Some(text) = self.data_label_text(point, value, Some(total)) else {
+            return;
+        };
+
+        let style = TextStyle::new(self.data_label_font(point), 9.0, false).anchor("middle");
+        write_text(svg, x, y + style.size * 0.35, &text, &style);
+    }
+}
+
+// -----------------------------------------------------------------------
+// Layout helper structs.
+// -----------------------------------------------------------------------
+
+// A rectangular area of the chart.
+#[derive(Clone, Copy)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Rect {
+    fn new(x: f64, y: f64, width: f64, height: f64) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    // Get the center of the outer end of a bar, used to position labels.
+    fn center_of_end(&self, is_horizontal: bool, is_negative: bool) -> (f64, f64) {
+        match (is_horizontal, is_negative) {
+            (true, false) => (self.x + self.width + 12.0, self.y + self.height / 2.0 + 4.0),
+            (true, true) => (self.x - 12.0, self.y + self.height / 2.0 + 4.0),
+            (false, false) => (self.x + self.width / 2.0, self.y),
+            (false, true) => (self.x + self.width / 2.0, self.y + self.height + 14.0),
+        }
+    }
+}
+
+// A mapping from an axis scale to a start position and signed length in the
+// SVG coordinates.
+#[derive(Clone, Copy)]
+struct AxisMap {
+    start: f64,
+    length: f64,
+}
+
+impl AxisMap {
+    fn new(start: f64, length: f64) -> AxisMap {
+        AxisMap { start, length }
+    }
+
+    fn position(&self, scale: &ValueScale, value: f64) -> f64 {
+        self.start + (value - scale.min) / (scale.max - scale.min) * self.length
+    }
+}
+
+// The positions of the categories along a category axis.
+#[derive(Clone, Copy)]
+struct CategoryScale {
+    count: usize,
+    between: bool,
+    map: AxisMap,
+}
+
+impl CategoryScale {
+    // The distance between categories.
+    fn slot(&self) -> f64 {
+        if self.between || self.count < 2 {
+            self.map.length / self.count as f64
+        } else {
+            self.map.length / (self.count - 1) as f64
+        }
+    }
+
+    // The center position of a category.
+    fn center(&self, index: usize) -> f64 {
+        if self.between {
+            self.map.start + (index as f64 + 0.5) * self.slot()
+        } else if self.count < 2 {
+            self.map.start + self.map.length / 2.0
+        } else {
+            self.map.start + index as f64 * self.slot()
+        }
+    }
+
+    // The edge position of a category, for gridlines.
+    fn edge(&self, index: usize) -> f64 {
+        if self.between {
+            self.map.start + index as f64 * self.slot()
+        } else {
+            self.center(index.min(self.count.saturating_sub(1)))
+        }
+    }
+}
+
+// The plot area and axis mappings used when writing the series.
+struct PlotInfo {
+    plot: Rect,
+    is_horizontal: bool,
+    value_map: AxisMap,
+    category_scale: CategoryScale,
+    x_scale: Option<ValueScale>,
+}
+
+impl PlotInfo {
+    // Get the SVG coordinates for a category position and value.
+    fn point(&self, category_position: f64, scale: &ValueScale, value: f64) -> (f64, f64) {
+        let value_position = self.value_map.position(scale, scale.clamp(value));
+
+        if self.is_horizontal {
+            (value_position, category_position)
+        } else {
+            (category_position, value_position)
+        }
+    }
+}
+
+// A value axis scale with Excel-like automatic min, max and major units.
+struct ValueScale {
+    min: f64,
+    max: f64,
+    major: f64,
+    num_format: String,
+}
+
+impl ValueScale {
+    fn new(data_min: f64, data_max: f64, axis: &ChartAxis, default_num_format: &str) -> ValueScale {
+        let mut min = data_min.min(0.0);
+        let mut max = data_max.max(0.0);
+
+        if let Ok(value) = axis.min.parse::<f64>() {
+            min = value;
+        }
+        if let Ok(value) = axis.max.parse::<f64>() {
+            max = value;
+        }
+        if max <= min {
+            max = min + 1.0;
+        }
+
+        let major = match axis.major_unit.parse::<f64>() {
+            Ok(value) if value > 0.0 && (max - min) / value <= 1000.0 => value,
+            _ => nice_unit((max - min) / 6.0),
+        };
+
+        if axis.min.is_empty() {
+            min = (min / major).floor() * major;
+        }
+        if axis.max.is_empty() {
+            max = (max / major).ceil() * major;
+        }
+
+        let num_format = if axis.num_format.is_empty() {
+            default_num_format.to_string()
+        } else {
+            axis.num_format.clone()
+        };
+
+        ValueScale {
+            min,
+            max,
+            major,
+            num_format,
+        }
+    }
+
+    // Get the major tick values, rounded to avoid floating point noise.
+    fn ticks(&self) -> Vec<f64> {
+        let count = ((self.max - self.min) / self.major).round() as usize;
+
+        (0..=count)
+            .map(|index| {
+                let value = self.min + index as f64 * self.major;
+                (value / self.major).round() * self.major
+            })
+            .map(|value| (value * 1e10).round() / 1e10)
+            .collect()
+    }
+
+    fn clamp(&self, value: f64) -> f64 {
+        value.clamp(self.min, self.max)
+    }
+}
+
+// The stacked start and end values of each point in a group of series.
+struct Stack {
+    spans: Vec<Vec<(f64, f64)>>,
+}
+
+impl Stack {
+    fn new(series: &[SeriesData], indices: &[usize], grouping: ChartGrouping) -> Stack {
+        let num_points = indices
+            .iter()
+            .map(|index| series[*index].values.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut spans = vec![];
+
+        match grouping {
+            ChartGrouping::Stacked | ChartGrouping::PercentStacked => {
+                let mut totals = vec![0.0; num_points];
+
+                if grouping == ChartGrouping::PercentStacked {
+                    for index in indices {
+                        for (point, value) in series[*index].values.iter().enumerate() {
+                            totals[point] += value.unwrap_or(0.0).abs();
+                        }
+                    }
+                }
+
+                let mut positive = vec![0.0; num_points];
+                let mut negative = vec![0.0; num_points];
+
+                for index in indices {
+                    let mut series_spans = vec![];
+
+                    for (point, value) in series[*index].values.iter().enumerate() {
+                        let mut value = value.unwrap_or(0.0);
+
+                        if grouping == ChartGrouping::PercentStacked && totals[point] > 0.0 {
+                            value /= totals[point];
+                        }
+
+                        let base = if value < 0.0 {
+                            &mut negative[point]
+                        } else {
+                            &mut positive[point]
+                        };
+
+                        series_spans.push((*base, *base + value));
+                        *base += value;
+                    }
+
+                    spans.push(series_spans);
+                }
+            }
+            _ => {
+                for index in indices {
+                    spans.push(
+                        series[*index]
+                            .values
+                            .iter()
+                            .map(|value| (0.0, value.unwrap_or(0.0)))
+                            .collect(),
+                    );
+                }
+            }
+        }
+
+        Stack { spans }
+    }
+
+    // Get the start and end values of a point in a series, by its position
+    // in the group.
+    fn span(&self, position: usize, point: usize, value: f64) -> (f64, f64) {
+        self.spans
+            .get(position)
+            .and_then(|spans| spans.get(point))
+            .copied()
+            .unwrap_or((0.0, value))
+    }
+}
+
+// A text style derived from an optional chart font.
+struct TextStyle {
+    size: f64,
+    color: String,
+    bold: bool,
+    italic: bool,
+    anchor: &'static str,
+}
+
+impl TextStyle {
+    // Create a text style from a font. Sizes are converted from points to
+    // pixels.
+    fn new(font: Option<&ChartFont>, default_size: f64, default_bold: bool) -> TextStyle {
+        let size = font
+            .map(|font| font.size)
+            .filter(|size| *size > 0.0)
+            .unwrap_or(default_size);
+
+        let color = font
+            .and_then(|font| svg_color(font.color))
+            .unwrap_or_else(|| TEXT_COLOR.to_string());
+
+        TextStyle {
+            size: size * 4.0 / 3.0,
+            color,
+            bold: font.and_then(|font| font.bold).unwrap_or(default_bold),
+            italic: font.is_some_and(|font| font.italic),
+            anchor: "start",
+        }
+    }
+
+    fn anchor(&self, anchor: &'static str) -> TextStyle {
+        TextStyle {
+            size: self.size,
+            color: self.color.clone(),
+            bold: self.bold,
+            italic: self.italic,
+            anchor,
+        }
+    }
+}
+
+// The type of key shown for a legend entry.
+#[derive(Clone, Copy)]
+enum LegendKey {
+    Fill,
+    Line,
+    Marker,
+}
+
+struct LegendEntry {
+    name: String,
+    color: String,
+    key: LegendKey,
+}
+
+// -----------------------------------------------------------------------
+// Helper functions.
+// -----------------------------------------------------------------------
+
+// Write an SVG <rect> element.
+fn write_rect(svg: &mut String, rect: Rect, fill: &str, stroke: &str) {
+    let _ = writeln!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{fill}" stroke="{stroke}"/>"#,
+        num(rect.x),
+        num(rect.y),
+        num(rect.width),
+        num(rect.height),
+    );
+}
+
+// Write an SVG <line> element.
+fn write_line(svg: &mut String, x1: f64, y1: f64, x2: f64, y2: f64, stroke: &str, width: f64) {
+    let _ = writeln!(
+        svg,
+        r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{stroke}" stroke-width="{}"/>"#,
+        num(x1),
+        num(y1),
+        num(x2),
+        num(y2),
+        num(width),
+    );
+}
+
+// Write an SVG <circle> element.
+fn write_circle(svg: &mut String, cx: f64, cy: f64, radius: f64, fill: &str) {
+    let _ = writeln!(
+        svg,
+        r#"<circle cx="{}" cy="{}" r="{}" fill="{fill}"/>"#,
+        num(cx),
+        num(cy),
+        num(radius),
+    );
+}
+
+// Write an SVG <text> element.
+fn write_text(svg: &mut String, x: f64, y: f64, text: &str, style: &TextStyle) {
+    let mut attributes = format!(
+        r#"x="{}" y="{}" font-size="{}" fill="{}""#,
+        num(x),
+        num(y),
+        num(style.size),
+        style.color
+    );
+
+    if style.anchor != "start" {
+        let _ = write!(attributes, r#" text-anchor="{}""#, style.anchor);
+    }
+    if style.bold {
+        attributes.push_str(r#" font-weight="bold""#);
+    }
+    if style.italic {
+        attributes.push_str(r#" font-style="italic""#);
+    }
+
+    let _ = writeln!(svg, "<text {attributes}>{}</text>", escape_xml_data(text));
+}
+
+// Get the text of a chart, axis or series title, if any.
+fn title_text(title: &ChartTitle) -> Option<String> {
+    if title.hidden {
+        None
+    } else if !title.name.is_empty() {
+        Some(title.name.clone())
+    } else if title.range.has_data() && title.range.cache.has_data() {
+        Some(range_strings(&title.range).join(" "))
+    } else {
+        None
+    }
+}
+
+// Get the string values from a range cache. For multi-level category ranges
+// the innermost level, i.e., the last column, is used.
+fn range_strings(range: &ChartRange) -> Vec<String> {
+    if range.cache.cache_type == ChartRangeCacheDataType::MultiLevelString {
+        let num_cols = (range.last_col - range.first_col + 1) as usize;
+
+        return range
+            .cache
+            .data
+            .chunks(num_cols)
+            .filter_map(|row| row.last().cloned())
+            .collect();
+    }
+
+    range.cache.data.clone()
+}
+
+// Get the numeric values from a range cache. Blank and string cells are
+// treated as empty.
+fn range_numbers(range: &ChartRange) -> Vec<Option<f64>> {
+    range
+        .cache
+        .data
+        .iter()
+        .map(|value| value.parse::<f64>().ok())
+        .collect()
+}
+
+// Get the fill color of a chart element, if one has been set.
+fn format_fill(format: &ChartFormat) -> Option<String> {
+    if format.no_fill {
+        return Some("none".to_string());
+    }
+
+    if let Some(fill) = &format.solid_fill {
+        return svg_color(fill.color);
+    }
+
+    if let Some(fill) = &format.pattern_fill {
+        return svg_color(fill.foreground_color);
+    }
+
+    if let Some(fill) = &format.gradient_fill {
+        return fill
+            .gradient_stops
+            .first()
+            .and_then(|stop| svg_color(stop.color));
+    }
+
+    None
+}
+
+// Get the line color of a chart element, if one has been set.
+fn format_line(format: &ChartFormat) -> Option<String> {
+    if format.no_line {
+        return Some("none".to_string());
+    }
+
+    match &format.line {
+        Some(line) if line.hidden => Some("none".to_string()),
+        Some(line) => svg_color(line.color),
+        None => None,
+    }
+}
+
+// Get the line width of a chart element in pixels, if one has been set.
+fn format_line_width(format: &ChartFormat) -> Option<f64> {
+    format
+        .line
+        .as_ref()
+        .and_then(|line| line.width)
+        .map(|width| width * 4.0 / 3.0)
+}
+
+// Get the color of the major gridlines of an axis.
+fn gridline_color(axis: &ChartAxis) -> String {
+    axis.major_gridlines_line
+        .as_ref()
+        .and_then(|line| svg_color(line.color))
+        .unwrap_or_else(|| GRIDLINE_COLOR.to_string())
+}
+
+// Convert a `Color` to an SVG "#RRGGBB" color. Default and automatic colors
+// return `None` so that the caller can apply the Excel default.
+fn svg_color(color: Color) -> Option<String> {
+    match color {
+        Color::Default | Color::Automatic => None,
+        Color::Theme(color, shade) => {
+            let rgb = THEME_COLORS[usize::from(color).min(THEME_COLORS.len() - 1)];
+
+            // Approximate the Excel theme tints and shades.
+            let tint = match (color, shade) {
+                (_, 0) => 0.0,
+                (0, _) => [-0.05, -0.15, -0.25, -0.35, -0.5][usize::from(shade.min(5)) - 1],
+                (1, _) => [0.5, 0.35, 0.25, 0.15, 0.05][usize::from(shade.min(5)) - 1],
+                _ => [0.8, 0.6, 0.4, -0.25, -0.5][usize::from(shade.min(5)) - 1],
+            };
+
+            Some(format!("#{:06X}", apply_tint(rgb, tint)))
+        }
+        _ => Some(format!("#{}", color.rgb_hex_value())),
+    }
+}
+
+// Lighten (positive tint) or darken (negative tint) an RGB color.
+fn apply_tint(rgb: u32, tint: f64) -> u32 {
+    let adjust = |component: u32| {
+        let component = f64::from(component);
+        let adjusted = if tint >= 0.0 {
+            component + (255.0 - component) * tint
+        } else {
+            component * (1.0 + tint)
+        };
+
+        adjusted.round().clamp(0.0, 255.0) as u32
+    };
+
+    (adjust(rgb >> 16 & 0xFF) << 16) | (adjust(rgb >> 8 & 0xFF) << 8) | adjust(rgb & 0xFF)
+}
+
+// Get a "nice" axis unit of 1, 2 or 5 times a power of 10.
+fn nice_unit(raw_unit: f64) -> f64 {
+    if raw_unit <= 0.0 || !raw_unit.is_finite() {
+        return 1.0;
+    }
+
+    let magnitude = 10_f64.powf(raw_unit.log10().floor());
+    let residual = raw_unit / magnitude;
+
+    let nice = if residual > 5.0 {
+        10.0
+    } else if residual > 2.0 {
+        5.0
+    } else if residual > 1.0 {
+        2.0
+    } else {
+        1.0
+    };
+
+    nice * magnitude
+}
+
+// Format a number using a simplified version of an Excel number format. Only
+// the number of decimal places, thousands separators, percentages and simple
+// prefixes like currency symbols are supported.
+fn format_number(value: f64, num_format: &str) -> String {
+    let format = num_format.split(';').next().unwrap_or_default();
+
+    if format.is_empty() || format.eq_ignore_ascii_case("General") {
+        return format_general(value);
+    }
+
+    let is_percent = format.contains('%');
+    let value = if is_percent { value * 100.0 } else { value };
+
+    let decimals = format.split('.').nth(1).map_or(0, |decimals| {
+        decimals
+            .chars()
+            .take_while(|char| *char == '0' || *char == '#')
+            .count()
+    });
+
+    let mut number = format!("{:.*}", decimals, value.abs());
+
+    if format.contains(',') {
+        let (integer, fraction) = match number.split_once('.') {
+            Some((integer, fraction)) => (integer.to_string(), format!(".{fraction}")),
+            None => (number.clone(), String::new()),
+        };
+
+        let mut grouped = String::new();
+        for (index, char) in integer.chars().enumerate() {
+            if index > 0 && (integer.len() - index) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(char);
+        }
+
+        number = grouped + &fraction;
+    }
+
+    let prefix: String = format
+        .chars()
+        .take_while(|char| !matches!(char, '0' | '#' | '?'))
+        .filter(|char| !matches!(char, '"' | '\\' | '_' | '*' | ' '))
+        .collect();
+
+    let sign = if value < 0.0 && number.chars().any(|char| matches!(char, '1'..='9')) {
+        "-"
+    } else {
+        ""
+    };
+
+    let suffix = if is_percent { "%" } else { "" };
+
+    format!("{sign}{prefix}{number}{suffix}")
+}
+
+// Format a number like the Excel "General" format, with up to 10 significant
+// digits.
+fn format_general(value: f64) -> String {
+    if value == value.trunc() && value.abs() < 1e15 {
+        return format!("{}", value as i64);
+    }
+
+    let magnitude = value.abs().log10().floor() as i32;
+    let decimals = (9 - magnitude).clamp(0, 15) as usize;
+    let number = format!("{value:.decimals$}");
+
+    if number.contains('.') {
+        number
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    } else {
+        number
+    }
+}
+
+// Estimate the width of a string of text in pixels.
+fn text_width(text: &str, size: f64) -> f64 {
+    text.chars().count() as f64 * size * 0.55
+}
+
+// Format an SVG coordinate with up to 2 decimal places.
+fn num(value: f64) -> String {
+    let value = (value * 100.0).round() / 100.0;
+
+    if value == 0.0 {
+        "0".to_string()
+    } else {
+        format!("{value}")
+    }
+}
@@ -7,9 +7,12 @@
 #[cfg(test)]
 mod chart_tests {
 
-    use crate::chart::{Chart, ChartRange, ChartSeries, ChartType, XlsxError};
+    use crate::chart::{
+        Chart, ChartDataLabel, ChartFormat, ChartLegendPosition, ChartRange, ChartSeries,
+        ChartSolidFill, ChartType, XlsxError,
+    };
     use crate::test_functions::xml_to_vec;
-    use crate::{xmlwriter, ChartRangeCacheDataType};
+    use crate::{xmlwriter, ChartRangeCacheDataType, Color, Workbook};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -277,6 +280,588 @@ mod chart_tests {
         assert_eq!(expected, got);
     }
 
+    #[test]
+    fn test_chart_ex_validation() {
+        // Check for chartex chart in a chartsheet.
+        let mut chart = Chart::new(ChartType::Histogram);
+        chart.add_series().set_values("Sheet1!$A$1:$A$5");
+        chart.is_chartsheet = true;
+        let result = chart.validate();
+        assert!(matches!(result, Err(XlsxError::ChartError(_))));
+
+        // Check for chartex chart combined with a standard chart.
+        let mut chart = Chart::new(ChartType::Column);
+        chart.add_series().set_values("Sheet1!$A$1:$A$5");
+        let mut waterfall_chart = Chart::new(ChartType::Waterfall);
+        waterfall_chart.add_series().set_values("Sheet1!$B$1:$B$5");
+        chart.combine(&waterfall_chart);
+        let result = chart.validate();
+        assert!(matches!(result, Err(XlsxError::ChartError(_))));
+
+        // Check for single series chartex chart with more than one series.
+        let mut chart = Chart::new(ChartType::Funnel);
+        chart.add_series().set_values("Sheet1!$A$1:$A$5");
+        chart.add_series().set_values("Sheet1!$B$1:$B$5");
+        let result = chart.validate();
+        assert!(matches!(result, Err(XlsxError::ChartError(_))));
+
+        // Box and whisker charts support more than one series.
+        let mut chart = Chart::new(ChartType::BoxWhisker);
+        chart.add_series().set_values("Sheet1!$A$1:$A$5");
+        chart.add_series().set_values("Sheet1!$B$1:$B$5");
+        let result = chart.validate();
+        assert!(result.is_ok());
+
+        // Check for Treemap chart without categories.
+        let mut chart = Chart::new(ChartType::Treemap);
+        chart.add_series().set_values("Sheet1!$A$1:$A$5");
+        let result = chart.validate();
+        assert!(matches!(result, Err(XlsxError::ChartError(_))));
+    }
+
+    #[test]
+    fn test_assemble_chart_ex() {
+        let mut range1 = ChartRange::new_from_string("Sheet1!$A$1:$A$3");
+        range1.set_cache(&["Start", "Costs", "End"], ChartRangeCacheDataType::String);
+
+        let mut range2 = ChartRange::new_from_string("Sheet1!$B$1:$B$3");
+        range2.set_cache(&["100", "-30", "70"], ChartRangeCacheDataType::Number);
+
+        let mut chart = Chart::new(ChartType::Waterfall);
+        chart
+            .add_series()
+            .set_categories(&range1)
+            .set_values(&range2)
+            .set_name("Profit");
+
+        chart.title().set_name("Summary");
+        chart.set_total_points(&[2]);
+        chart.id = 1;
+
+        chart.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&chart.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <cx:chartSpace xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:cx="http://schemas.microsoft.com/office/drawing/2014/chartex">
+                  <cx:chartData>
+                    <cx:data id="0">
+                      <cx:strDim type="cat">
+                        <cx:f>Sheet1!$A$1:$A$3</cx:f>
+                        <cx:lvl ptCount="3">
+                          <cx:pt idx="0">Start</cx:pt>
+                          <cx:pt idx="1">Costs</cx:pt>
+                          <cx:pt idx="2">End</cx:pt>
+                        </cx:lvl>
+                      </cx:strDim>
+                      <cx:numDim type="val">
+                        <cx:f>Sheet1!$B$1:$B$3</cx:f>
+                        <cx:lvl ptCount="3" formatCode="General">
+                          <cx:pt idx="0">100</cx:pt>
+                          <cx:pt idx="1">-30</cx:pt>
+                          <cx:pt idx="2">70</cx:pt>
+                        </cx:lvl>
+                      </cx:numDim>
+                    </cx:data>
+                  </cx:chartData>
+                  <cx:chart>
+                    <cx:title pos="t" align="ctr" overlay="0">
+                      <cx:tx>
+                        <cx:rich>
+                          <a:bodyPr/>
+                          <a:lstStyle/>
+                          <a:p>
+                            <a:pPr>
+                              <a:defRPr/>
+                            </a:pPr>
+                            <a:r>
+                              <a:rPr lang="en-US"/>
+                              <a:t>Summary</a:t>
+                            </a:r>
+                          </a:p>
+                        </cx:rich>
+                      </cx:tx>
+                    </cx:title>
+                    <cx:plotArea>
+                      <cx:plotAreaRegion>
+                        <cx:series layoutId="waterfall" uniqueId="{00000001-0000-4000-8000-000000000001}">
+                          <cx:tx>
+                            <cx:txData>
+                              <cx:v>Profit</cx:v>
+                            </cx:txData>
+                          </cx:tx>
+                          <cx:dataLabels pos="outEnd">
+                            <cx:visibility seriesName="0" categoryName="0" value="1"/>
+                          </cx:dataLabels>
+                          <cx:dataId val="0"/>
+                          <cx:layoutPr>
+                            <cx:subtotals>
+                              <cx:idx val="2"/>
+                            </cx:subtotals>
+                          </cx:layoutPr>
+                        </cx:series>
+                      </cx:plotAreaRegion>
+                      <cx:axis id="0">
+                        <cx:catScaling gapWidth="0.5"/>
+                        <cx:tickLabels/>
+                      </cx:axis>
+                      <cx:axis id="1">
+                        <cx:valScaling/>
+                        <cx:majorGridlines/>
+                        <cx:tickLabels/>
+                      </cx:axis>
+                    </cx:plotArea>
+                    <cx:legend pos="r" align="ctr" overlay="0"/>
+                  </cx:chart>
+                </cx:chartSpace>
+                "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_pareto_chart() {
+        let mut range = ChartRange::new_from_string("Sheet1!$A$1:$A$2");
+        range.set_cache(&["3", "5"], ChartRangeCacheDataType::Number);
+
+        let mut chart = Chart::new(ChartType::Pareto);
+        chart.add_series().set_values(&range);
+        chart.set_bin_count(4).set_bin_overflow(10.0);
+        chart.legend().set_hidden();
+        chart.id = 2;
+
+        chart.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&chart.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <cx:chartSpace xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:cx="http://schemas.microsoft.com/office/drawing/2014/chartex">
+                  <cx:chartData>
+                    <cx:data id="0">
+                      <cx:numDim type="val">
+                        <cx:f>Sheet1!$A$1:$A$2</cx:f>
+                        <cx:lvl ptCount="2" formatCode="General">
+                          <cx:pt idx="0">3</cx:pt>
+                          <cx:pt idx="1">5</cx:pt>
+                        </cx:lvl>
+                      </cx:numDim>
+                    </cx:data>
+                  </cx:chartData>
+                  <cx:chart>
+                    <cx:plotArea>
+                      <cx:plotAreaRegion>
+                        <cx:series layoutId="clusteredColumn" uniqueId="{00000002-0000-4000-8000-000000000001}">
+                          <cx:dataId val="0"/>
+                          <cx:layoutPr>
+                            <cx:binning intervalClosed="r" overflow="10">
+                              <cx:binCount val="4"/>
+                            </cx:binning>
+                          </cx:layoutPr>
+                          <cx:axisId val="1"/>
+                        </cx:series>
+                        <cx:series layoutId="paretoLine" ownerIdx="0" uniqueId="{00000002-0000-4000-8000-000000000002}">
+                          <cx:axisId val="2"/>
+                        </cx:series>
+                      </cx:plotAreaRegion>
+                      <cx:axis id="0">
+                        <cx:catScaling gapWidth="0"/>
+                        <cx:tickLabels/>
+                      </cx:axis>
+                      <cx:axis id="1">
+                        <cx:valScaling/>
+                        <cx:majorGridlines/>
+                        <cx:tickLabels/>
+                      </cx:axis>
+                      <cx:axis id="2">
+                        <cx:valScaling max="1" min="0"/>
+                        <cx:units unit="percentage"/>
+                        <cx:tickLabels/>
+                      </cx:axis>
+                    </cx:plotArea>
+                  </cx:chart>
+                </cx:chartSpace>
+                "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_chart_3d_validation() {
+        // Check for bubble chart without bubble sizes.
+        let mut chart = Chart::new(ChartType::Bubble);
+        chart
+            .add_series()
+            .set_categories("Sheet1!$A$1:$A$5")
+            .set_values("Sheet1!$B$1:$B$5");
+        let result = chart.validate();
+        assert!(matches!(result, Err(XlsxError::ChartError(_))));
+
+        // Check for bubble chart without categories.
+        let mut chart = Chart::new(ChartType::Bubble);
+        chart
+            .add_series()
+            .set_values("Sheet1!$B$1:$B$5")
+            .set_bubble_sizes("Sheet1!$C$1:$C$5");
+        let result = chart.validate();
+        assert!(matches!(result, Err(XlsxError::ChartError(_))));
+
+        // Check for 3D chart combined with a standard chart.
+        let mut chart = Chart::new(ChartType::Column3D);
+        chart.add_series().set_values("Sheet1!$A$1:$A$5");
+        let mut line_chart = Chart::new(ChartType::Line);
+        line_chart.add_series().set_values("Sheet1!$B$1:$B$5");
+        chart.combine(&line_chart);
+        let result = chart.validate();
+        assert!(matches!(result, Err(XlsxError::ChartError(_))));
+
+        // Check for 3D chart with a secondary axis.
+        let mut chart = Chart::new(ChartType::Surface);
+        chart.add_series().set_values("Sheet1!$A$1:$A$5");
+        chart
+            .add_series()
+            .set_values("Sheet1!$B$1:$B$5")
+            .set_secondary_axis(true);
+        let result = chart.validate();
+        assert!(matches!(result, Err(XlsxError::ChartError(_))));
+    }
+
+    #[test]
+    fn test_assemble_bubble_chart() {
+        let mut range1 = ChartRange::new_from_string("Sheet1!$A$1:$A$2");
+        range1.set_cache(&["1", "2"], ChartRangeCacheDataType::Number);
+
+        let mut range2 = ChartRange::new_from_string("Sheet1!$B$1:$B$2");
+        range2.set_cache(&["3", "4"], ChartRangeCacheDataType::Number);
+
+        let mut range3 = ChartRange::new_from_string("Sheet1!$C$1:$C$2");
+        range3.set_cache(&["10", "20"], ChartRangeCacheDataType::Number);
+
+        let mut chart = Chart::new(ChartType::Bubble3D);
+        chart
+            .add_series()
+            .set_categories(&range1)
+            .set_values(&range2)
+            .set_bubble_sizes(&range3);
+
+        chart.set_axis_ids(64052224, 64055552);
+
+        chart.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&chart.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+                  <c:lang val="en-US"/>
+                  <c:chart>
+                    <c:plotArea>
+                      <c:layout/>
+                      <c:bubbleChart>
+                        <c:ser>
+                          <c:idx val="0"/>
+                          <c:order val="0"/>
+                          <c:xVal>
+                            <c:numRef>
+                              <c:f>Sheet1!$A$1:$A$2</c:f>
+                              <c:numCache>
+                                <c:formatCode>General</c:formatCode>
+                                <c:ptCount val="2"/>
+                                <c:pt idx="0">
+                                  <c:v>1</c:v>
+                                </c:pt>
+                                <c:pt idx="1">
+                                  <c:v>2</c:v>
+                                </c:pt>
+                              </c:numCache>
+                            </c:numRef>
+                          </c:xVal>
+                          <c:yVal>
+                            <c:numRef>
+                              <c:f>Sheet1!$B$1:$B$2</c:f>
+                              <c:numCache>
+                                <c:formatCode>General</c:formatCode>
+                                <c:ptCount val="2"/>
+                                <c:pt idx="0">
+                                  <c:v>3</c:v>
+                                </c:pt>
+                                <c:pt idx="1">
+                                  <c:v>4</c:v>
+                                </c:pt>
+                              </c:numCache>
+                            </c:numRef>
+                          </c:yVal>
+                          <c:bubbleSize>
+                            <c:numRef>
+                              <c:f>Sheet1!$C$1:$C$2</c:f>
+                              <c:numCache>
+                                <c:formatCode>General</c:formatCode>
+                                <c:ptCount val="2"/>
+                                <c:pt idx="0">
+                                  <c:v>10</c:v>
+                                </c:pt>
+                                <c:pt idx="1">
+                                  <c:v>20</c:v>
+                                </c:pt>
+                              </c:numCache>
+                            </c:numRef>
+                          </c:bubbleSize>
+                          <c:bubble3D val="1"/>
+                        </c:ser>
+                        <c:bubbleScale val="100"/>
+                        <c:showNegBubbles val="0"/>
+                        <c:axId val="64052224"/>
+                        <c:axId val="64055552"/>
+                      </c:bubbleChart>
+                      <c:valAx>
+                        <c:axId val="64052224"/>
+                        <c:scaling>
+                          <c:orientation val="minMax"/>
+                        </c:scaling>
+                        <c:axPos val="b"/>
+                        <c:numFmt formatCode="General" sourceLinked="1"/>
+                        <c:tickLblPos val="nextTo"/>
+                        <c:crossAx val="64055552"/>
+                        <c:crosses val="autoZero"/>
+                        <c:crossBetween val="midCat"/>
+                      </c:valAx>
+                      <c:valAx>
+                        <c:axId val="64055552"/>
+                        <c:scaling>
+                          <c:orientation val="minMax"/>
+                        </c:scaling>
+                        <c:axPos val="l"/>
+                        <c:majorGridlines/>
+                        <c:numFmt formatCode="General" sourceLinked="1"/>
+                        <c:tickLblPos val="nextTo"/>
+                        <c:crossAx val="64052224"/>
+                        <c:crosses val="autoZero"/>
+                        <c:crossBetween val="midCat"/>
+                      </c:valAx>
+                    </c:plotArea>
+                    <c:legend>
+                      <c:legendPos val="r"/>
+                      <c:layout/>
+                    </c:legend>
+                    <c:plotVisOnly val="1"/>
+                  </c:chart>
+                  <c:printSettings>
+                    <c:headerFooter/>
+                    <c:pageMargins b="0.75" l="0.7" r="0.7" t="0.75" header="0.3" footer="0.3"/>
+                    <c:pageSetup/>
+                  </c:printSettings>
+                </c:chartSpace>
+                "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_line_3d_chart() {
+        let mut range1 = ChartRange::new_from_string("Sheet1!$A$1:$A$2");
+        range1.set_cache(&["3", "4"], ChartRangeCacheDataType::Number);
+
+        let mut chart = Chart::new(ChartType::Line3D);
+        chart.add_series().set_values(&range1);
+        chart.set_perspective(60).set_depth_percent(150);
+
+        chart.set_axis_ids(64052224, 64055552);
+
+        chart.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&chart.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+                  <c:lang val="en-US"/>
+                  <c:chart>
+                    <c:view3D>
+                      <c:rotX val="15"/>
+                      <c:rotY val="20"/>
+                      <c:depthPercent val="150"/>
+                      <c:rAngAx val="0"/>
+                      <c:perspective val="60"/>
+                    </c:view3D>
+                    <c:plotArea>
+                      <c:layout/>
+                      <c:line3DChart>
+                        <c:grouping val="standard"/>
+                        <c:ser>
+                          <c:idx val="0"/>
+                          <c:order val="0"/>
+                          <c:marker>
+                            <c:symbol val="none"/>
+                          </c:marker>
+                          <c:val>
+                            <c:numRef>
+                              <c:f>Sheet1!$A$1:$A$2</c:f>
+                              <c:numCache>
+                                <c:formatCode>General</c:formatCode>
+                                <c:ptCount val="2"/>
+                                <c:pt idx="0">
+                                  <c:v>3</c:v>
+                                </c:pt>
+                                <c:pt idx="1">
+                                  <c:v>4</c:v>
+                                </c:pt>
+                              </c:numCache>
+                            </c:numRef>
+                          </c:val>
+                        </c:ser>
+                        <c:axId val="64052224"/>
+                        <c:axId val="64055552"/>
+                        <c:axId val="64052228"/>
+                      </c:line3DChart>
+                      <c:catAx>
+                        <c:axId val="64052224"/>
+                        <c:scaling>
+                          <c:orientation val="minMax"/>
+                        </c:scaling>
+                        <c:axPos val="b"/>
+                        <c:tickLblPos val="nextTo"/>
+                        <c:crossAx val="64055552"/>
+                        <c:crosses val="autoZero"/>
+                        <c:auto val="1"/>
+                        <c:lblAlgn val="ctr"/>
+                        <c:lblOffset val="100"/>
+                      </c:catAx>
+                      <c:valAx>
+                        <c:axId val="64055552"/>
+                        <c:scaling>
+                          <c:orientation val="minMax"/>
+                        </c:scaling>
+                        <c:axPos val="l"/>
+                        <c:majorGridlines/>
+                        <c:numFmt formatCode="General" sourceLinked="1"/>
+                        <c:tickLblPos val="nextTo"/>
+                        <c:crossAx val="64052224"/>
+                        <c:crosses val="autoZero"/>
+                        <c:crossBetween val="between"/>
+                      </c:valAx>
+                      <c:serAx>
+                        <c:axId val="64052228"/>
+                        <c:scaling>
+                          <c:orientation val="minMax"/>
+                        </c:scaling>
+                        <c:axPos val="b"/>
+                        <c:tickLblPos val="nextTo"/>
+                        <c:crossAx val="64055552"/>
+                        <c:crosses val="autoZero"/>
+                      </c:serAx>
+                    </c:plotArea>
+                    <c:legend>
+                      <c:legendPos val="r"/>
+                      <c:layout/>
+                    </c:legend>
+                    <c:plotVisOnly val="1"/>
+                  </c:chart>
+                  <c:printSettings>
+                    <c:headerFooter/>
+                    <c:pageMargins b="0.75" l="0.7" r="0.7" t="0.75" header="0.3" footer="0.3"/>
+                    <c:pageSetup/>
+                  </c:printSettings>
+                </c:chartSpace>
+                "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_column(0, 0, ["Jan", "Feb", "Mar"]).unwrap();
+        worksheet.write_column(0, 1, [10, 20, 30]).unwrap();
+
+        let mut chart = Chart::new(ChartType::Column);
+        chart
+            .add_series()
+            .set_categories("Sheet1!$A$1:$A$3")
+            .set_values("Sheet1!$B$1:$B$3")
+            .set_name("Sales")
+            .set_format(
+                ChartFormat::new()
+                    .set_solid_fill(ChartSolidFill::new().set_color(Color::RGB(0xFF0000))),
+            )
+            .set_data_label(ChartDataLabel::new().show_value());
+
+        chart.title().set_name("Monthly Sales");
+        chart.legend().set_position(ChartLegendPosition::Bottom);
+
+        let svg = chart.render_svg(&workbook).unwrap();
+
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="480" height="288""#)
+        );
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(">Monthly Sales</text>"));
+        assert!(svg.contains(">Sales</text>"));
+        assert!(svg.contains(">Feb</text>"));
+        assert!(svg.contains(">30</text>"));
+        assert_eq!(
+            svg.matches(r##"fill="#FF0000" stroke="none"/>"##).count(),
+            4
+        );
+
+        // The legend is below the plot area.
+        let legend = svg.find(r#"<g class="legend">"#).unwrap();
+        assert!(svg[legend..]
+            .contains(r##"<text x="227.5" y="275.2" font-size="12" fill="#595959">Sales</text>"##));
+
+        // Check for unsupported chart types.
+        let mut chart = Chart::new(ChartType::Surface);
+        chart.add_series().set_values("Sheet1!$B$1:$B$3");
+        let result = chart.render_svg(&workbook);
+        assert!(matches!(result, Err(XlsxError::ChartError(_))));
+
+        // Check for unknown worksheets in the chart ranges.
+        let mut chart = Chart::new(ChartType::Pie);
+        chart.add_series().set_values("Sheet2!$B$1:$B$3");
+        let result = chart.render_svg(&workbook);
+        assert!(matches!(
+            result,
+            Err(XlsxError::UnknownWorksheetNameOrIndex(_))
+        ));
+    }
+
+    #[test]
+    fn test_render_svg_pie() {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_column(0, 0, ["A", "B"]).unwrap();
+        worksheet.write_column(0, 1, [1, 3]).unwrap();
+
+        let mut chart = Chart::new(ChartType::Pie);
+        chart
+            .add_series()
+            .set_categories("Sheet1!$A$1:$A$2")
+            .set_values("Sheet1!$B$1:$B$2")
+            .set_data_label(ChartDataLabel::new().show_percentage());
+
+        let svg = chart.render_svg(&workbook).unwrap();
+
+        // The slices use the default Excel palette.
+        assert_eq!(svg.matches("<path ").count(), 2);
+        assert!(svg.contains(r##"fill="#4472C4" stroke="#FFFFFF"/>"##));
+        assert!(svg.contains(r##"fill="#ED7D31" stroke="#FFFFFF"/>"##));
+        assert!(svg.contains(">25%</text>"));
+        assert!(svg.contains(">75%</text>"));
+    }
+
     #[test]
     fn test_range_from_string() {
         let range_string = "=Sheet1!$A$1:$A$5";
@@ -966,6 +966,15 @@ pub trait ConditionalFormat {
 
     /// Clone a reference into a concrete Box type.
     fn box_clone(&self) -> Box<dyn ConditionalFormat + Sync + Send>;
+
+    /// Set the date epoch of any date values in the conditional format.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::DateTimeRangeError`] - A date is before 1904-01-01 and
+    ///   can't be used with the 1904 epoch.
+    #[doc(hidden)]
+    fn set_1904_epoch(&mut self, is_1904: bool) -> Result<(), XlsxError>;
 }
 
 macro_rules! generate_conditional_format_impls {
@@ -1009,6 +1018,14 @@ macro_rules! generate_conditional_format_impls {
             fn box_clone(&self) -> Box<dyn ConditionalFormat + Sync + Send> {
                 Box::new(self.clone())
             }
+
+            fn set_1904_epoch(&mut self, is_1904: bool) -> Result<(), XlsxError> {
+                for value in ConditionalFormatDateValues::date_values_as_mut(self) {
+                    value.set_1904_epoch(is_1904)?;
+                }
+
+                Ok(())
+            }
         }
     )*)
 }
@@ -6607,6 +6624,7 @@ impl ConditionalFormatCustomIcon {
 pub struct ConditionalFormatValue {
     value: String,
     pub(crate) is_string: bool,
+    datetime: Option<f64>,
 }
 
 impl ConditionalFormatValue {
@@ -6614,7 +6632,34 @@ impl ConditionalFormatValue {
         ConditionalFormatValue {
             value: value.into(),
             is_string: false,
+            datetime: None,
+        }
+    }
+
+    // Store a date/time value. The 1900 epoch serial datetime is kept so that
+    // the value can be converted to the workbook date epoch when it is saved.
+    pub(crate) fn new_from_datetime(datetime: f64) -> ConditionalFormatValue {
+        ConditionalFormatValue {
+            value: datetime.to_string(),
+            is_string: false,
+            datetime: Some(datetime),
+        }
+    }
+
+    // Set the value of a date/time to the 1900 or 1904 date epoch.
+    pub(crate) fn set_1904_epoch(&mut self, is_1904: bool) -> Result<(), XlsxError> {
+        if let Some(datetime) = self.datetime {
+            let datetime = if is_1904 {
+                ExcelDateTime::check_1904_epoch(datetime)?;
+                ExcelDateTime::serial_to_1904_epoch(datetime)
+            } else {
+                datetime
+            };
+
+            self.value = datetime.to_string();
         }
+
+        Ok(())
     }
 
     // Helper method to account for the fact that Excel requires that strings in
@@ -6671,15 +6716,13 @@ impl From<Formula> for ConditionalFormatValue {
 
 impl From<ExcelDateTime> for ConditionalFormatValue {
     fn from(value: ExcelDateTime) -> ConditionalFormatValue {
-        let value = value.to_excel().to_string();
-        ConditionalFormatValue::new_from_string(value)
+        ConditionalFormatValue::new_from_datetime(value.to_excel())
     }
 }
 
 impl From<&ExcelDateTime> for ConditionalFormatValue {
     fn from(value: &ExcelDateTime) -> ConditionalFormatValue {
-        let value = value.to_excel().to_string();
-        ConditionalFormatValue::new_from_string(value)
+        ConditionalFormatValue::new_from_datetime(value.to_excel())
     }
 }
 
@@ -6693,8 +6736,7 @@ impl From<bool> for ConditionalFormatValue {
 #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
 impl From<&NaiveDate> for ConditionalFormatValue {
     fn from(value: &NaiveDate) -> ConditionalFormatValue {
-        let value = ExcelDateTime::chrono_date_to_excel(value).to_string();
-        ConditionalFormatValue::new_from_string(value)
+        ConditionalFormatValue::new_from_datetime(ExcelDateTime::chrono_date_to_excel(value))
     }
 }
 
@@ -6702,8 +6744,7 @@ impl From<&NaiveDate> for ConditionalFormatValue {
 #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
 impl From<&NaiveDateTime> for ConditionalFormatValue {
     fn from(value: &NaiveDateTime) -> ConditionalFormatValue {
-        let value = ExcelDateTime::chrono_datetime_to_excel(value).to_string();
-        ConditionalFormatValue::new_from_string(value)
+        ConditionalFormatValue::new_from_datetime(ExcelDateTime::chrono_datetime_to_excel(value))
     }
 }
 
@@ -6711,8 +6752,7 @@ impl From<&NaiveDateTime> for ConditionalFormatValue {
 #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
 impl From<&NaiveTime> for ConditionalFormatValue {
     fn from(value: &NaiveTime) -> ConditionalFormatValue {
-        let value = ExcelDateTime::chrono_time_to_excel(value).to_string();
-        ConditionalFormatValue::new_from_string(value)
+        ConditionalFormatValue::new_from_datetime(ExcelDateTime::chrono_time_to_excel(value))
     }
 }
 
@@ -6720,8 +6760,7 @@ impl From<&NaiveTime> for ConditionalFormatValue {
 #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
 impl From<NaiveDate> for ConditionalFormatValue {
     fn from(value: NaiveDate) -> ConditionalFormatValue {
-        let value = ExcelDateTime::chrono_date_to_excel(&value).to_string();
-        ConditionalFormatValue::new_from_string(value)
+        ConditionalFormatValue::new_from_datetime(ExcelDateTime::chrono_date_to_excel(&value))
     }
 }
 
@@ -6729,8 +6768,7 @@ impl From<NaiveDate> for ConditionalFormatValue {
 #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
 impl From<NaiveDateTime> for ConditionalFormatValue {
     fn from(value: NaiveDateTime) -> ConditionalFormatValue {
-        let value = ExcelDateTime::chrono_datetime_to_excel(&value).to_string();
-        ConditionalFormatValue::new_from_string(value)
+        ConditionalFormatValue::new_from_datetime(ExcelDateTime::chrono_datetime_to_excel(&value))
     }
 }
 
@@ -6738,8 +6776,7 @@ impl From<NaiveDateTime> for ConditionalFormatValue {
 #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
 impl From<NaiveTime> for ConditionalFormatValue {
     fn from(value: NaiveTime) -> ConditionalFormatValue {
-        let value = ExcelDateTime::chrono_time_to_excel(&value).to_string();
-        ConditionalFormatValue::new_from_string(value)
+        ConditionalFormatValue::new_from_datetime(ExcelDateTime::chrono_time_to_excel(&value))
     }
 }
 
@@ -6747,8 +6784,7 @@ impl From<NaiveTime> for ConditionalFormatValue {
 #[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
 impl From<jiff::civil::DateTime> for ConditionalFormatValue {
     fn from(value: jiff::civil::DateTime) -> ConditionalFormatValue {
-        let value = ExcelDateTime::jiff_datetime_to_excel(&value).to_string();
-        ConditionalFormatValue::new_from_string(value)
+        ConditionalFormatValue::new_from_datetime(ExcelDateTime::jiff_datetime_to_excel(&value))
     }
 }
 
@@ -6756,8 +6792,7 @@ impl From<jiff::civil::DateTime> for ConditionalFormatValue {
 #[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
 impl From<jiff::civil::Date> for ConditionalFormatValue {
     fn from(value: jiff::civil::Date) -> ConditionalFormatValue {
-        let value = ExcelDateTime::jiff_date_to_excel(&value).to_string();
-        ConditionalFormatValue::new_from_string(value)
+        ConditionalFormatValue::new_from_datetime(ExcelDateTime::jiff_date_to_excel(&value))
     }
 }
 
@@ -6765,8 +6800,7 @@ impl From<jiff::civil::Date> for ConditionalFormatValue {
 #[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
 impl From<jiff::civil::Time> for ConditionalFormatValue {
     fn from(value: jiff::civil::Time) -> ConditionalFormatValue {
-        let value = ExcelDateTime::jiff_time_to_excel(&value).to_string();
-        ConditionalFormatValue::new_from_string(value)
+        ConditionalFormatValue::new_from_datetime(ExcelDateTime::jiff_time_to_excel(&value))
     }
 }
 
@@ -6817,6 +6851,71 @@ conditional_format_value_from_type!(jiff::civil::DateTime jiff::civil::Date jiff
 
 // -----------------------------------------------------------------------
 // ConditionalFormatCellRule
+// Internal trait to get the values of a conditional format that may contain
+// dates, so that they can be converted to the workbook date epoch.
+trait ConditionalFormatDateValues {
+    fn date_values_as_mut(&mut self) -> Vec<&mut ConditionalFormatValue> {
+        vec![]
+    }
+}
+
+impl ConditionalFormatDateValues for ConditionalFormatAverage {}
+impl ConditionalFormatDateValues for ConditionalFormatBlank {}
+impl ConditionalFormatDateValues for ConditionalFormatDate {}
+impl ConditionalFormatDateValues for ConditionalFormatDuplicate {}
+impl ConditionalFormatDateValues for ConditionalFormatError {}
+impl ConditionalFormatDateValues for ConditionalFormatFormula {}
+impl ConditionalFormatDateValues for ConditionalFormatText {}
+impl ConditionalFormatDateValues for ConditionalFormatTop {}
+
+impl ConditionalFormatDateValues for ConditionalFormatCell {
+    fn date_values_as_mut(&mut self) -> Vec<&mut ConditionalFormatValue> {
+        match &mut self.rule {
+            Some(
+                ConditionalFormatCellRule::EqualTo(value)
+                | ConditionalFormatCellRule::NotEqualTo(value)
+                | ConditionalFormatCellRule::GreaterThan(value)
+                | ConditionalFormatCellRule::GreaterThanOrEqualTo(value)
+                | ConditionalFormatCellRule::LessThan(value)
+                | ConditionalFormatCellRule::LessThanOrEqualTo(value),
+            ) => vec![value],
+            Some(
+                ConditionalFormatCellRule::Between(min, max)
+                | ConditionalFormatCellRule::NotBetween(min, max),
+            ) => vec![min, max],
+            None => vec![],
+        }
+    }
+}
+
+impl ConditionalFormatDateValues for ConditionalFormat2ColorScale {
+    fn date_values_as_mut(&mut self) -> Vec<&mut ConditionalFormatValue> {
+        vec![&mut self.min_value, &mut self.max_value]
+    }
+}
+
+impl ConditionalFormatDateValues for ConditionalFormat3ColorScale {
+    fn date_values_as_mut(&mut self) -> Vec<&mut ConditionalFormatValue> {
+        vec![
+            &mut self.min_value,
+            &mut self.mid_value,
+            &mut self.max_value,
+        ]
+    }
+}
+
+impl ConditionalFormatDateValues for ConditionalFormatDataBar {
+    fn date_values_as_mut(&mut self) -> Vec<&mut ConditionalFormatValue> {
+        vec![&mut self.min_value, &mut self.max_value]
+    }
+}
+
+impl ConditionalFormatDateValues for ConditionalFormatIconSet {
+    fn date_values_as_mut(&mut self) -> Vec<&mut ConditionalFormatValue> {
+        self.icons.iter_mut().map(|icon| &mut icon.value).collect()
+    }
+}
+
 // -----------------------------------------------------------------------
 
 /// The `ConditionalFormatCellRule` enum defines the conditional format rule for
@@ -58,8 +58,17 @@ impl ContentTypes {
         }
     }
 
-    // Add elements to the ContentTypes defaults.
+    // Add elements to the ContentTypes defaults. Duplicate extensions, which
+    // can occur when merging the defaults from a template file, are ignored.
     pub(crate) fn add_default(&mut self, extension: &str, content_type: &str) {
+        if self
+            .defaults
+            .iter()
+            .any(|(default, _)| default.eq_ignore_ascii_case(extension))
+        {
+            return;
+        }
+
         self.defaults
             .push((extension.to_string(), content_type.to_string()));
     }
@@ -104,6 +113,14 @@ impl ContentTypes {
         self.add_override(&part_name, content_type);
     }
 
+    // Add the name of a chartex chart to the ContentTypes overrides.
+    pub(crate) fn add_chart_ex_name(&mut self, index: u16) {
+        let content_type = "application/vnd.ms-office.chartex+xml";
+        let part_name = format!("/xl/charts/chartEx{index}.xml");
+
+        self.add_override(&part_name, content_type);
+    }
+
     // Add the name of a table to the ContentTypes overrides.
     pub(crate) fn add_table_name(&mut self, index: u16) {
         let content_type = "application/vnd.openxmlformats-officedocument.spreadsheetml.table+xml";
@@ -112,6 +129,41 @@ impl ContentTypes {
         self.add_override(&part_name, content_type);
     }
 
+    // Add the names of the pivot table and pivot cache files to the
+    // ContentTypes overrides.
+    pub(crate) fn add_pivot_table_name(&mut self, index: u16) {
+        let content_type =
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.pivotTable+xml";
+        let part_name = format!("/xl/pivotTables/pivotTable{index}.xml");
+        self.add_override(&part_name, content_type);
+
+        let content_type =
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.pivotCacheDefinition+xml";
+        let part_name = format!("/xl/pivotCache/pivotCacheDefinition{index}.xml");
+        self.add_override(&part_name, content_type);
+
+        let content_type =
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.pivotCacheRecords+xml";
+        let part_name = format!("/xl/pivotCache/pivotCacheRecords{index}.xml");
+        self.add_override(&part_name, content_type);
+    }
+
+    // Add the name of a slicer file to the ContentTypes overrides.
+    pub(crate) fn add_slicer_name(&mut self, index: u16) {
+        let content_type = "application/vnd.ms-excel.slicer+xml";
+        let part_name = format!("/xl/slicers/slicer{index}.xml");
+
+        self.add_override(&part_name, content_type);
+    }
+
+    // Add the name of a slicer cache file to the ContentTypes overrides.
+    pub(crate) fn add_slicer_cache_name(&mut self, index: u16) {
+        let content_type = "application/vnd.ms-excel.slicerCache+xml";
+        let part_name = format!("/xl/slicerCaches/slicerCache{index}.xml");
+
+        self.add_override(&part_name, content_type);
+    }
+
     // Add the name of a comment file to the ContentTypes overrides.
     pub(crate) fn add_comments_name(&mut self, index: u16) {
         let content_type =
@@ -121,6 +173,22 @@ impl ContentTypes {
         self.add_override(&part_name, content_type);
     }
 
+    // Add the name of a threaded comment file to the ContentTypes overrides.
+    pub(crate) fn add_threaded_comments_name(&mut self, index: u16) {
+        let content_type = "application/vnd.ms-excel.threadedcomments+xml";
+        let part_name = format!("/xl/threadedComments/threadedComment{index}.xml");
+
+        self.add_override(&part_name, content_type);
+    }
+
+    // Add the persons file to the ContentTypes overrides.
+    pub(crate) fn add_persons(&mut self) {
+        self.add_override(
+            "/xl/persons/person.xml",
+            "application/vnd.ms-excel.person+xml",
+        );
+    }
+
     // Add the sharedStrings link to the ContentTypes overrides.
     pub(crate) fn add_share_strings(&mut self) {
         self.add_override(
@@ -0,0 +1,281 @@
+// dataframe - A module for representing the options for writing Polars
+// dataframes and Arrow record batches.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+
+#[cfg(feature = "polars")]
+use polars::prelude::TimeUnit;
+
+#[cfg(feature = "polars")]
+use crate::datetime::ColumnTimeUnit;
+
+use crate::datetime::{
+    column_date_format, column_datetime_format, column_duration_format, column_time_format,
+};
+use crate::{Format, Table};
+
+/// The `DataFrameOptions` struct is used to set the options for writing a
+/// Polars dataframe or Arrow record batches to a worksheet.
+///
+/// The `DataFrameOptions` struct is used in conjunction with the
+/// `Worksheet::write_dataframe()` method, for Polars dataframes, and the
+/// `Worksheet::write_record_batch()` and `Worksheet::write_record_batches()`
+/// methods, for Arrow record batches, to control how the tabular data is
+/// written. It allows you to turn off the header row, add a header format,
+/// wrap the data in a worksheet [`Table`], autofit the columns, and set
+/// formats for the data based on the column name or the column data type.
+/// The options have the same meaning for both types of data.
+///
+/// This requires the `polars` or `arrow` feature to be enabled.
+///
+/// # Examples
+///
+/// The following example demonstrates writing a Polars dataframe to a
+/// worksheet as an Excel table with a number format for one of the columns.
+///
+/// ```
+/// # // This code is available in examples/doc_dataframe_options.rs
+/// #
+/// use polars::prelude::*;
+/// use rust_xlsxwriter::{DataFrameOptions, Format, Table, Workbook, XlsxError};
+///
+/// fn main() -> Result<(), XlsxError> {
+///     let mut workbook = Workbook::new();
+///     let worksheet = workbook.add_worksheet();
+///
+///     // Create a sample dataframe.
+///     let df = df!(
+///         "Fruit" => &["Apple", "Pear", "Plum"],
+///         "Cost" => &[1.25, 0.75, 1.5],
+///     )?;
+///
+///     // Set the dataframe options.
+///     let currency = Format::new().set_num_format("$0.00");
+///     let options = DataFrameOptions::new()
+///         .set_table(&Table::new())
+///         .set_column_format("Cost", &currency)
+///         .set_autofit(true);
+///
+///     // Write the dataframe to the worksheet.
+///     worksheet.write_dataframe(0, 0, &df, &options)?;
+///
+///     workbook.save("dataframe.xlsx")?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+#[derive(Clone)]
+pub struct DataFrameOptions {
+    pub(crate) has_header: bool,
+    pub(crate) header_format: Option<Format>,
+    pub(crate) table: Option<Table>,
+    pub(crate) autofit: bool,
+    pub(crate) null_value: Option<String>,
+    pub(crate) column_formats: HashMap<String, Format>,
+    pub(crate) date_format: Format,
+    pub(crate) datetime_format: Format,
+    pub(crate) time_format: Format,
+    pub(crate) duration_format: Format,
+}
+
+impl Default for DataFrameOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataFrameOptions {
+    /// Create a new `DataFrameOptions` struct.
+    ///
+    /// The defaults are to write the column names as a header row, with no
+    /// table or autofit, and with the default date and time formats
+    /// described below.
+    ///
+    pub fn new() -> DataFrameOptions {
+        DataFrameOptions {
+            has_header: true,
+            header_format: None,
+            table: None,
+            autofit: false,
+            null_value: None,
+            column_formats: HashMap::new(),
+            date_format: column_date_format(),
+            datetime_format: column_datetime_format(),
+            time_format: column_time_format(),
+            duration_format: column_duration_format(),
+        }
+    }
+
+    /// Turn on/off the dataframe header row.
+    ///
+    /// By default the dataframe column names, or the record batch field names,
+    /// are written as a header row above the data. This option can be used to
+    /// turn that off.
+    ///
+    /// If a table is added via [`DataFrameOptions::set_table()`] then the
+    /// table header row setting is used instead, see
+    /// [`Table::set_header_row()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_header(mut self, enable: bool) -> DataFrameOptions {
+        self.has_header = enable;
+        self
+    }
+
+    /// Set the format for the dataframe header row.
+    ///
+    /// # Parameters
+    ///
+    /// - `format`: The [`Format`] property for the header cells.
+    ///
+    pub fn set_header_format(mut self, format: &Format) -> DataFrameOptions {
+        self.header_format = Some(format.clone());
+        self
+    }
+
+    /// Add a worksheet table around the dataframe data.
+    ///
+    /// The dataframe column names, or the record batch field names, are used
+    /// as the table column headers. The table range is extended by a row if
+    /// the table has a total row.
+    ///
+    /// # Parameters
+    ///
+    /// - `table`: A [`Table`] struct reference.
+    ///
+    pub fn set_table(mut self, table: &Table) -> DataFrameOptions {
+        self.table = Some(table.clone());
+        self
+    }
+
+    /// Autofit the column widths after writing the dataframe.
+    ///
+    /// Only the dataframe columns are autofitted, based on the dataframe
+    /// data. The widths of other columns in the worksheet aren't changed. See
+    /// [`Worksheet::autofit()`](crate::Worksheet::autofit) for details on the
+    /// limitations of autofit.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    pub fn set_autofit(mut self, enable: bool) -> DataFrameOptions {
+        self.autofit = enable;
+        self
+    }
+
+    /// Set a string value to write for null values in the data.
+    ///
+    /// By default null values are written as blank cells, or are skipped if
+    /// there isn't a column format. This option can be used to write a
+    /// string such as `"NULL"` or `"N/A"` instead.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: The string to write for null values.
+    ///
+    pub fn set_null_value(mut self, value: impl Into<String>) -> DataFrameOptions {
+        self.null_value = Some(value.into());
+        self
+    }
+
+    /// Set the format for the data in a dataframe or record batch column.
+    ///
+    /// The column format takes precedence over the date, time, duration and
+    /// decimal formats so for those data types it should include a suitable
+    /// number format.
+    ///
+    /// # Parameters
+    ///
+    /// - `column_name`: The name of the dataframe column or record batch
+    ///   field.
+    /// - `format`: The [`Format`] property for the column data.
+    ///
+    pub fn set_column_format(
+        mut self,
+        column_name: impl Into<String>,
+        format: &Format,
+    ) -> DataFrameOptions {
+        self.column_formats
+            .insert(column_name.into(), format.clone());
+        self
+    }
+
+    /// Set the format for date columns: Polars `Date` and Arrow `Date32` and
+    /// `Date64`.
+    ///
+    /// The default format is `yyyy-mm-dd`.
+    ///
+    /// # Parameters
+    ///
+    /// - `format`: The [`Format`] property with a date number format.
+    ///
+    pub fn set_date_format(mut self, format: &Format) -> DataFrameOptions {
+        self.date_format = format.clone();
+        self
+    }
+
+    /// Set the format for datetime columns: Polars `Datetime` and Arrow
+    /// `Timestamp`.
+    ///
+    /// The default format is `yyyy-mm-dd hh:mm:ss`.
+    ///
+    /// # Parameters
+    ///
+    /// - `format`: The [`Format`] property with a datetime number format.
+    ///
+    pub fn set_datetime_format(mut self, format: &Format) -> DataFrameOptions {
+        self.datetime_format = format.clone();
+        self
+    }
+
+    /// Set the format for time columns: Polars `Time` and Arrow `Time32` and
+    /// `Time64`.
+    ///
+    /// The default format is `hh:mm:ss`.
+    ///
+    /// # Parameters
+    ///
+    /// - `format`: The [`Format`] property with a time number format.
+    ///
+    pub fn set_time_format(mut self, format: &Format) -> DataFrameOptions {
+        self.time_format = format.clone();
+        self
+    }
+
+    /// Set the format for `Duration` columns.
+    ///
+    /// The default format is `[h]:mm:ss`, which displays the elapsed time in
+    /// hours and can exceed 24 hours.
+    ///
+    /// # Parameters
+    ///
+    /// - `format`: The [`Format`] property with a time number format.
+    ///
+    pub fn set_duration_format(mut self, format: &Format) -> DataFrameOptions {
+        self.duration_format = format.clone();
+        self
+    }
+}
+
+// Map the Polars time units to the units used for the date conversions.
+#[cfg(feature = "polars")]
+impl From<&TimeUnit> for ColumnTimeUnit {
+    fn from(unit: &TimeUnit) -> ColumnTimeUnit {
+        match unit {
+            TimeUnit::Milliseconds => ColumnTimeUnit::Milliseconds,
+            TimeUnit::Microseconds => ColumnTimeUnit::Microseconds,
+            TimeUnit::Nanoseconds => ColumnTimeUnit::Nanoseconds,
+        }
+    }
+}
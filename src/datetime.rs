@@ -965,18 +965,50 @@ impl ExcelDateTime {
     /// Excel users occasionally use the 1904 epoch to enable negative time
     /// values.
     ///
-    /// There is some internal support for the 1904 epoch in `ExcelDateTime`
-    /// since it was implemented for the Python version of the library. However,
-    /// it was almost never used/needed by users so I am omitting it from the
-    /// Rust version for now. I won't accept pull requests to implement/unhide
-    /// it but I will consider feature requests with a good use
-    /// case/justification.
-    #[allow(dead_code)]
+    /// At the API level the 1904 epoch is set for the whole workbook with
+    /// `Workbook::use_1904_epoch()` and dates are converted when the file is
+    /// saved, see `serial_to_1904_epoch()` below. This method is only used to
+    /// test the internal 1904 conversion against the Excel reference values.
+    #[cfg(test)]
     pub(crate) fn set_1904_date(mut self) -> ExcelDateTime {
         self.is_1904_date = true;
         self
     }
 
+    // Convert a serial datetime in the default 1900 epoch to the 1904 epoch.
+    // The 1904 epoch starts 1462 days later. Times without a date part are the
+    // same in both epochs.
+    pub(crate) fn serial_to_1904_epoch(serial_datetime: f64) -> f64 {
+        if serial_datetime >= 1.0 {
+            serial_datetime - 1462.0
+        } else {
+            serial_datetime
+        }
+    }
+
+    // Check that a serial datetime in the default 1900 epoch can be represented
+    // in the 1904 epoch. Dates from 1900-01-01 to 1903-12-31 would be converted
+    // to zero or negative serial numbers which Excel displays as "####".
+    pub(crate) fn check_1904_epoch(serial_datetime: f64) -> Result<(), XlsxError> {
+        if (1.0..1462.0).contains(&serial_datetime) {
+            return Err(XlsxError::DateTimeRangeError(format!(
+                "Date serial '{serial_datetime}' is before 1904-01-01 and can't be used with the 1904 epoch"
+            )));
+        }
+
+        Ok(())
+    }
+
+    // Convert a serial datetime in the 1904 epoch to the default 1900 epoch.
+    #[cfg(feature = "serde")]
+    pub(crate) fn serial_from_1904_epoch(serial_datetime: f64) -> f64 {
+        if serial_datetime >= 1.0 {
+            serial_datetime + 1462.0
+        } else {
+            serial_datetime
+        }
+    }
+
     // Common validation routine for year, month, day methods.
     fn validate_ymd(year: u16, month: u8, day: u8) -> Result<(), XlsxError> {
         let mut months = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
@@ -1165,11 +1197,9 @@ impl ExcelDateTime {
 
     // Convert a Unix time to it date components.
     //
-    // The calculation is deceptively tricky since simple division doesn't work
-    // due to the 4/100/400 year leap day changes. The basic approach is to
-    // divide the range into 400 year blocks, 100 year blocks, 4 year blocks
-    // and 1 year block to calculate the year (relative to the epoch). The
-    // remaining days and seconds are used to calculate the year day and time.
+    // The date is calculated from the number of days since a 1600 epoch, see
+    // `epoch_1600_days_to_ymd()`. The remaining seconds are used to calculate
+    // the time.
     //
     // Works in the range 1970-1-1 to 9999-12-31.
     //
@@ -1177,8 +1207,6 @@ impl ExcelDateTime {
     //
     #[allow(clippy::cast_precision_loss)]
     pub(crate) fn unix_time_to_date_parts(timestamp: u64) -> (u16, u8, u8, u16, u8, f64) {
-        let mut months = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
-
         // Convert the seconds to a whole number of days.
         let mut days = timestamp / DAY_SECONDS;
 
@@ -1186,6 +1214,85 @@ impl ExcelDateTime {
         // calculations easier. This is the closest 400 year epoch before 1970.
         days += 135_140;
 
+        let (year, month, day) = Self::epoch_1600_days_to_ymd(days);
+
+        // Get the number of seconds in the day.
+        let seconds = timestamp % DAY_SECONDS;
+
+        // Calculate the hours, minutes and seconds in the day.
+        let hour = seconds / HOUR_SECONDS;
+        let min = (seconds - hour * HOUR_SECONDS) / MINUTE_SECONDS;
+        let sec = (seconds - hour * HOUR_SECONDS - min * MINUTE_SECONDS) % MINUTE_SECONDS;
+
+        // Return the date components.
+        (year, month, day, hour as u16, min as u8, sec as f64)
+    }
+
+    // Convert an Excel serial datetime to its date components.
+    //
+    // This is the inverse of `to_excel_from_ymd_hms()`. The serial date is
+    // converted to a number of days from the 1600 epoch used by
+    // `epoch_1600_days_to_ymd()`. Excel's fictitious 1900-02-29 leap day and
+    // the 1900-01-00 zero date are handled as special cases.
+    //
+    // Works in the range 1900-01-00 to 9999-12-31.
+    //
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn serial_to_date_parts(
+        number: f64,
+        is_1904_date: bool,
+    ) -> (u16, u8, u8, u16, u8, f64) {
+        let number = number.max(0.0);
+        let mut serial_days = number.trunc() as u64;
+
+        // Round the time to the nearest millisecond to avoid float artifacts.
+        let mut millis = ((number - number.trunc()) * DAY_SECONDS as f64 * 1000.0).round() as u64;
+        if millis >= DAY_SECONDS * 1000 {
+            millis -= DAY_SECONDS * 1000;
+            serial_days += 1;
+        }
+
+        // Convert the time in milliseconds to hours, minutes and seconds.
+        let hour = millis / (HOUR_SECONDS * 1000);
+        let min = (millis - hour * HOUR_SECONDS * 1000) / (MINUTE_SECONDS * 1000);
+        let sec =
+            (millis - hour * HOUR_SECONDS * 1000 - min * MINUTE_SECONDS * 1000) as f64 / 1000.0;
+
+        let (year, month, day) = if is_1904_date {
+            // Days from 1600-01-01 to 1904-01-01.
+            Self::epoch_1600_days_to_ymd(111_033 + serial_days)
+        } else if serial_days == 0 {
+            // The Excel 1900 epoch, 1900-01-00.
+            (1900, 1, 0)
+        } else if serial_days == 60 {
+            // The Excel false leap day.
+            (1900, 2, 29)
+        } else if serial_days < 60 {
+            // Days from 1600-01-01 to 1899-12-31.
+            Self::epoch_1600_days_to_ymd(109_572 + serial_days)
+        } else {
+            // Days from 1600-01-01 to 1899-12-30.
+            Self::epoch_1600_days_to_ymd(109_571 + serial_days)
+        };
+
+        (year, month, day, hour as u16, min as u8, sec)
+    }
+
+    // Convert a number of days since 1600-01-01 to year, month and day
+    // values.
+    //
+    // The calculation is deceptively tricky since simple division doesn't work
+    // due to the 4/100/400 year leap day changes. The basic approach is to
+    // divide the range into 400 year blocks, 100 year blocks, 4 year blocks
+    // and 1 year block to calculate the year (relative to the epoch). The
+    // remaining days are used to calculate the month and day.
+    //
+    // A day value of 0 is 1600-01-01.
+    //
+    fn epoch_1600_days_to_ymd(days: u64) -> (u16, u8, u8) {
+        let mut months = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
         // Get the number of 400 year blocks.
         let year_days_400 = days / YEAR_DAYS_400;
         let mut days = days % YEAR_DAYS_400;
@@ -1266,23 +1373,7 @@ impl ExcelDateTime {
         // The final remainder is the month day.
         let day = days;
 
-        // Get the number of seconds in the day.
-        let seconds = timestamp % DAY_SECONDS;
-
-        // Calculate the hours, minutes and seconds in the day.
-        let hour = seconds / HOUR_SECONDS;
-        let min = (seconds - hour * HOUR_SECONDS) / MINUTE_SECONDS;
-        let sec = (seconds - hour * HOUR_SECONDS - min * MINUTE_SECONDS) % MINUTE_SECONDS;
-
-        // Return the date components.
-        (
-            year as u16,
-            month as u8,
-            day as u8,
-            hour as u16,
-            min as u8,
-            sec as f64,
-        )
+        (year as u16, month as u8, day as u8)
     }
 
     // Check if a year is a leap year.
@@ -1304,7 +1395,7 @@ impl ExcelDateTime {
         target_arch = "wasm32",
         not(any(target_os = "emscripten", target_os = "wasi"))
     )))]
-    fn system_now() -> u64 {
+    pub(crate) fn system_now() -> u64 {
         let timestamp = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .expect("SystemTime::now() is before Unix epoch");
@@ -1317,7 +1408,7 @@ impl ExcelDateTime {
         target_arch = "wasm32",
         not(any(target_os = "emscripten", target_os = "wasi"))
     ))]
-    fn system_now() -> u64 {
+    pub(crate) fn system_now() -> u64 {
         let timestamp = js_sys::Date::now();
         (timestamp / 1000.0) as u64
     }
@@ -1619,21 +1710,147 @@ impl Serialize for ExcelDateTime {
         S: Serializer,
     {
         let serial_datetime = self.to_excel();
-        serializer.serialize_f64(serial_datetime)
+        crate::serializer::serialize_excel_datetime(serializer, serial_datetime)
     }
 }
 
 /// Implementation of the `serde::Deserialize` trait for `ExcelDateTime`.
 ///
-/// This is a non-functional implementation to allow `ExcelDateTime` types to be
-/// included in a struct that derives `Deserialize`.
+/// This allows `ExcelDateTime` types to be included in a struct that derives
+/// `Deserialize`. The datetime is deserialized from an Excel serial datetime
+/// number, such as the cell values read by
+/// [`Worksheet::deserialize_rows()`](crate::Worksheet::deserialize_rows).
 ///
 #[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for ExcelDateTime {
-    fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // The datetime is requested as a newtype struct so that worksheet
+        // dates in the 1904 epoch can be converted by the deserializer.
+        let number = deserializer.deserialize_newtype_struct(
+            crate::serializer::SERDE_DATETIME_NAME,
+            SerialDateTimeVisitor,
+        )?;
+
+        ExcelDateTime::from_serial_datetime(number).map_err(serde::de::Error::custom)
+    }
+}
+
+// A visitor for an Excel serial datetime number, which may be wrapped in a
+// newtype struct.
+#[cfg(feature = "serde")]
+struct SerialDateTimeVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for SerialDateTimeVisitor {
+    type Value = f64;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an Excel serial datetime number")
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<f64, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<f64, E> {
+        Ok(value as f64)
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<f64, E> {
+        Ok(value as f64)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<f64, D::Error>
     where
         D: Deserializer<'de>,
     {
-        unimplemented!()
+        f64::deserialize(deserializer)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers for the date and time types in Polars dataframes and Arrow record
+// batches.
+// -----------------------------------------------------------------------
+
+// The Excel serial date of the Unix epoch, 1970-01-01. The dates and datetimes
+// in dataframes and record batches are relative to the Unix epoch.
+#[cfg(any(feature = "arrow", feature = "polars"))]
+const UNIX_EPOCH_SERIAL: f64 = 25569.0;
+
+// The units of the datetime, time and duration values in dataframes and record
+// batches.
+#[cfg(any(feature = "arrow", feature = "polars"))]
+#[derive(Clone, Copy)]
+pub(crate) enum ColumnTimeUnit {
+    #[cfg_attr(not(feature = "arrow"), allow(dead_code))]
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+#[cfg(any(feature = "arrow", feature = "polars"))]
+impl ColumnTimeUnit {
+    // Convert a number of time units since the Unix epoch to an Excel serial
+    // datetime.
+    pub(crate) fn unix_time_to_serial(self, value: f64) -> f64 {
+        self.to_days(value) + UNIX_EPOCH_SERIAL
+    }
+
+    // Convert a time, or duration, in time units to days.
+    pub(crate) fn to_days(self, value: f64) -> f64 {
+        let units_per_day = match self {
+            ColumnTimeUnit::Seconds => 86_400.0,
+            ColumnTimeUnit::Milliseconds => 86_400_000.0,
+            ColumnTimeUnit::Microseconds => 86_400_000_000.0,
+            ColumnTimeUnit::Nanoseconds => 86_400_000_000_000.0,
+        };
+
+        value / units_per_day
+    }
+}
+
+// Convert a number of days since the Unix epoch to an Excel serial date.
+#[cfg(any(feature = "arrow", feature = "polars"))]
+pub(crate) fn unix_days_to_serial(days: f64) -> f64 {
+    days + UNIX_EPOCH_SERIAL
+}
+
+// The default formats for the date, datetime, time and duration columns in
+// dataframes and record batches. Durations are written as numbers of days
+// since they may be negative or outside the Excel date range.
+#[cfg(any(feature = "arrow", feature = "polars"))]
+pub(crate) fn column_date_format() -> crate::Format {
+    crate::Format::new().set_num_format("yyyy\\-mm\\-dd;@")
+}
+
+#[cfg(any(feature = "arrow", feature = "polars"))]
+pub(crate) fn column_datetime_format() -> crate::Format {
+    crate::Format::new().set_num_format("yyyy\\-mm\\-dd\\ hh:mm:ss")
+}
+
+#[cfg(any(feature = "arrow", feature = "polars"))]
+pub(crate) fn column_time_format() -> crate::Format {
+    crate::Format::new().set_num_format("hh:mm:ss;@")
+}
+
+#[cfg(any(feature = "arrow", feature = "polars"))]
+pub(crate) fn column_duration_format() -> crate::Format {
+    crate::Format::new().set_num_format("[h]:mm:ss")
+}
+
+// The default format for decimal columns in dataframes and record batches. The
+// decimals are written as numbers, converted to f64, so the format displays the
+// same number of decimal places as the decimal scale.
+#[cfg(any(feature = "arrow", feature = "polars"))]
+pub(crate) fn column_decimal_format(scale: usize) -> Option<crate::Format> {
+    if scale == 0 {
+        return None;
     }
+
+    Some(crate::Format::new().set_num_format(format!("0.{}", "0".repeat(scale))))
 }
@@ -2528,4 +2528,89 @@ mod datetime_tests {
             assert!(diff < 0.00000000001);
         }
     }
+
+    #[test]
+    fn serial_dates_round_trip() {
+        // Check the conversion of Excel serial dates back to date parts.
+        for year in 1900..=9999 {
+            for (month, day) in [(1, 1), (2, 28), (3, 1), (12, 31)] {
+                let serial = ExcelDateTime::from_ymd(year, month, day)
+                    .unwrap()
+                    .to_excel();
+                let got = ExcelDateTime::serial_to_date_parts(serial, false);
+                assert_eq!((year, month, day, 0, 0, 0.0), got);
+            }
+        }
+
+        // Excel's epoch and false leap day.
+        assert_eq!(
+            (1900, 1, 0, 0, 0, 0.0),
+            ExcelDateTime::serial_to_date_parts(0.0, false)
+        );
+        assert_eq!(
+            (1900, 2, 28, 0, 0, 0.0),
+            ExcelDateTime::serial_to_date_parts(59.0, false)
+        );
+        assert_eq!(
+            (1900, 2, 29, 0, 0, 0.0),
+            ExcelDateTime::serial_to_date_parts(60.0, false)
+        );
+        assert_eq!(
+            (1900, 3, 1, 0, 0, 0.0),
+            ExcelDateTime::serial_to_date_parts(61.0, false)
+        );
+
+        // The 1904 epoch.
+        assert_eq!(
+            (1904, 1, 1, 0, 0, 0.0),
+            ExcelDateTime::serial_to_date_parts(0.0, true)
+        );
+        assert_eq!(
+            (2023, 1, 1, 0, 0, 0.0),
+            ExcelDateTime::serial_to_date_parts(43465.0, true)
+        );
+
+        // Times.
+        assert_eq!(
+            (2023, 1, 25, 12, 30, 15.5),
+            ExcelDateTime::serial_to_date_parts(44951.52101273148, false)
+        );
+        assert_eq!(
+            (1900, 1, 0, 23, 59, 59.999),
+            ExcelDateTime::serial_to_date_parts(0.999_999_988_425_925_9, false)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serialize_to_other_formats() {
+        use serde_test::{assert_ser_tokens, Token};
+
+        // Serializers other than the worksheet serializer get a plain f64.
+        let datetime = ExcelDateTime::from_ymd(2024, 1, 1).unwrap();
+        assert_ser_tokens(&datetime, &[Token::F64(45292.0)]);
+
+        let time = ExcelDateTime::from_hms(12, 0, 0).unwrap();
+        assert_ser_tokens(&time, &[Token::F64(0.5)]);
+    }
+
+    #[test]
+    fn check_1904_epoch() {
+        let dates = vec![
+            (1900, 1, 1, false),
+            (1903, 12, 31, false),
+            (1904, 1, 1, true),
+            (2024, 1, 1, true),
+        ];
+
+        for (year, month, day, is_valid) in dates {
+            let datetime = ExcelDateTime::from_ymd(year, month, day).unwrap();
+            let result = ExcelDateTime::check_1904_epoch(datetime.to_excel());
+            assert_eq!(is_valid, result.is_ok());
+        }
+
+        // Times without a date aren't affected.
+        let time = ExcelDateTime::from_hms(12, 0, 0).unwrap();
+        assert!(ExcelDateTime::check_1904_epoch(time.to_excel()).is_ok());
+    }
 }
@@ -0,0 +1,349 @@
+// deserializer - A module for deserializing worksheet rows of cell data into
+// Serde structs.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use serde::de::value::StringDeserializer;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, MapAccess, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::serializer::SERDE_DATETIME_NAME;
+use crate::{ExcelDateTime, XlsxError};
+
+// The value of a worksheet cell, converted to one of the types that can be
+// deserialized. Dates and times are read as Excel serial numbers.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum CellValue {
+    String(String),
+    Number(f64),
+    // A number stored in a file that uses the 1904 epoch. It is converted to
+    // the default 1900 epoch if it is deserialized as an `ExcelDateTime`.
+    Number1904(f64),
+    Boolean(bool),
+    Error(String),
+}
+
+impl CellValue {
+    // Create a number cell value in the 1900 or 1904 epoch.
+    pub(crate) fn number(number: f64, is_1904_date: bool) -> CellValue {
+        if is_1904_date {
+            CellValue::Number1904(number)
+        } else {
+            CellValue::Number(number)
+        }
+    }
+
+    // Convert a formula result, which is stored as a string, to a cell value.
+    pub(crate) fn from_formula_result(result: &str, is_1904_date: bool) -> Option<CellValue> {
+        match result {
+            "" => None,
+            "TRUE" => Some(CellValue::Boolean(true)),
+            "FALSE" => Some(CellValue::Boolean(false)),
+            _ if result.starts_with('#') => Some(CellValue::Error(result.to_string())),
+            _ => match result.parse::<f64>() {
+                Ok(number) => Some(CellValue::number(number, is_1904_date)),
+                Err(_) => Some(CellValue::String(result.to_string())),
+            },
+        }
+    }
+
+    // Convert the cell value to a string, for example to use as a header.
+    // Numbers are converted without a trailing ".0", as Excel displays them.
+    pub(crate) fn to_string_value(&self) -> String {
+        match self {
+            CellValue::String(string) | CellValue::Error(string) => string.clone(),
+            CellValue::Number(number) | CellValue::Number1904(number) => {
+                if number.fract() == 0.0 && number.abs() < 1e15 {
+                    format!("{number:.0}")
+                } else {
+                    number.to_string()
+                }
+            }
+            CellValue::Boolean(boolean) => {
+                if *boolean {
+                    "TRUE".to_string()
+                } else {
+                    "FALSE".to_string()
+                }
+            }
+        }
+    }
+
+    // Get the numeric value of the cell, if any. Strings are parsed to allow
+    // numbers that were entered as text.
+    fn to_number(&self) -> Option<f64> {
+        match self {
+            CellValue::Number(number) | CellValue::Number1904(number) => Some(*number),
+            CellValue::String(string) => string.trim().parse::<f64>().ok(),
+            _ => None,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------
+// RowDeserializer. A deserializer for a worksheet row. The row is presented
+// to Serde as a map of field names and cell values. Blank cells are omitted
+// so that they are deserialized as `None` for `Option` fields, or as the
+// default value for fields with `#[serde(default)]`.
+// -----------------------------------------------------------------------
+pub(crate) struct RowDeserializer {
+    cells: std::vec::IntoIter<(String, CellValue)>,
+    value: Option<CellValue>,
+}
+
+impl RowDeserializer {
+    // Create a new RowDeserializer struct.
+    pub(crate) fn new(cells: Vec<(String, CellValue)>) -> RowDeserializer {
+        RowDeserializer {
+            cells: cells.into_iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for RowDeserializer {
+    type Error = XlsxError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> MapAccess<'de> for RowDeserializer {
+    type Error = XlsxError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, XlsxError>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.cells.next() {
+            Some((field_name, value)) => {
+                self.value = Some(value);
+                let key: StringDeserializer<XlsxError> = field_name.into_deserializer();
+                seed.deserialize(key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, XlsxError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(value) => seed.deserialize(CellDeserializer(value)),
+            None => Err(XlsxError::SerdeError(
+                "Cell value requested before field name".to_string(),
+            )),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.cells.len())
+    }
+}
+
+// -----------------------------------------------------------------------
+// CellDeserializer. A deserializer for a worksheet cell value. The cell types
+// are converted to the requested type where there is a reasonable mapping,
+// such as a number to a string, or a string of digits to a number.
+// -----------------------------------------------------------------------
+pub(crate) struct CellDeserializer(CellValue);
+
+impl CellDeserializer {
+    // Visit a number as an integer, if it has an integer value, or as a float.
+    fn visit_number<'de, V>(number: f64, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        if number.fract() == 0.0 && number >= i64::MIN as f64 && number < i64::MAX as f64 {
+            visitor.visit_i64(number as i64)
+        } else {
+            visitor.visit_f64(number)
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for CellDeserializer {
+    type Error = XlsxError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            CellValue::String(string) | CellValue::Error(string) => visitor.visit_string(string),
+            CellValue::Number(number) | CellValue::Number1904(number) => {
+                Self::visit_number(number, visitor)
+            }
+            CellValue::Boolean(boolean) => visitor.visit_bool(boolean),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        match &self.0 {
+            CellValue::Number(number) | CellValue::Number1904(number) => {
+                visitor.visit_bool(*number != 0.0)
+            }
+            CellValue::String(string) if string.eq_ignore_ascii_case("true") => {
+                visitor.visit_bool(true)
+            }
+            CellValue::String(string) if string.eq_ignore_ascii_case("false") => {
+                visitor.visit_bool(false)
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0.to_number() {
+            Some(number) => Self::visit_number(number, visitor),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.0.to_string_value())
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    // Dates in the 1904 epoch are converted to the 1900 epoch used by
+    // `ExcelDateTime`.
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            CellValue::Number1904(number) if name == SERDE_DATETIME_NAME => {
+                visitor.visit_f64(ExcelDateTime::serial_from_1904_epoch(number))
+            }
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    // Only unit variants are supported, via the variant name.
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        let variant: StringDeserializer<XlsxError> = self.0.to_string_value().into_deserializer();
+        visitor.visit_enum(variant)
+    }
+
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, XlsxError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 bytes byte_buf unit unit_struct seq tuple tuple_struct map
+        struct identifier ignored_any
+    }
+}
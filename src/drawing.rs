@@ -114,6 +114,8 @@ impl Drawing {
 
                 self.write_sp(index, drawing_info, &shape);
             }
+            DrawingType::ChartEx => self.write_chart_ex_alternate_content(index, drawing_info),
+            DrawingType::Slicer => self.write_slicer_alternate_content(index, drawing_info),
             DrawingType::ChartSheet | DrawingType::Vml => {}
         }
 
@@ -493,6 +495,208 @@ impl Drawing {
         xml_empty_tag(&mut self.writer, "c:chart", &attributes);
     }
 
+    // Write the <mc:AlternateContent> element for an Excel 2016 chartex chart.
+    // Excel versions that don't support these chart types display the
+    // fallback shape instead.
+    fn write_chart_ex_alternate_content(&mut self, index: u32, drawing_info: &DrawingInfo) {
+        let attributes = [(
+            "xmlns:mc",
+            "http://schemas.openxmlformats.org/markup-compatibility/2006",
+        )];
+
+        xml_start_tag(&mut self.writer, "mc:AlternateContent", &attributes);
+
+        let attributes = [
+            (
+                "xmlns:cx1",
+                "http://schemas.microsoft.com/office/drawing/2015/9/8/chartex",
+            ),
+            ("Requires", "cx1"),
+        ];
+
+        xml_start_tag(&mut self.writer, "mc:Choice", &attributes);
+
+        // Write the <xdr:graphicFrame> element.
+        self.write_chart_ex_graphic_frame(index, drawing_info);
+
+        xml_end_tag(&mut self.writer, "mc:Choice");
+
+        let attributes = [("xmlns", "")];
+
+        xml_start_tag(&mut self.writer, "mc:Fallback", &attributes);
+
+        // Write the fallback <xdr:sp> element.
+        self.write_fallback_sp(
+            drawing_info,
+            "This chart isn't available in your version of Excel. Editing this shape or saving this workbook into a different file format will permanently break the chart.",
+        );
+
+        xml_end_tag(&mut self.writer, "mc:Fallback");
+        xml_end_tag(&mut self.writer, "mc:AlternateContent");
+    }
+
+    // Write the <xdr:graphicFrame> element for a chartex chart.
+    fn write_chart_ex_graphic_frame(&mut self, index: u32, drawing_info: &DrawingInfo) {
+        let attributes = [("macro", "")];
+
+        xml_start_tag(&mut self.writer, "xdr:graphicFrame", &attributes);
+
+        // Write the <xdr:nvGraphicFramePr> element.
+        self.write_nv_graphic_frame_pr(index, drawing_info);
+
+        // Write the <xdr:xfrm> element.
+        self.write_xfrm();
+
+        xml_start_tag_only(&mut self.writer, "a:graphic");
+
+        let attributes = [(
+            "uri",
+            "http://schemas.microsoft.com/office/drawing/2014/chartex",
+        )];
+
+        xml_start_tag(&mut self.writer, "a:graphicData", &attributes);
+
+        // Write the <cx:chart> element.
+        let attributes = [
+            (
+                "xmlns:cx",
+                "http://schemas.microsoft.com/office/drawing/2014/chartex".to_string(),
+            ),
+            (
+                "xmlns:r",
+                "http://schemas.openxmlformats.org/officeDocument/2006/relationships".to_string(),
+            ),
+            ("r:id", format!("rId{}", drawing_info.rel_id)),
+        ];
+
+        xml_empty_tag(&mut self.writer, "cx:chart", &attributes);
+
+        xml_end_tag(&mut self.writer, "a:graphicData");
+        xml_end_tag(&mut self.writer, "a:graphic");
+        xml_end_tag(&mut self.writer, "xdr:graphicFrame");
+    }
+
+    // Write the <mc:AlternateContent> element for a slicer. Excel versions that
+    // don't support table slicers display the fallback shape instead.
+    fn write_slicer_alternate_content(&mut self, index: u32, drawing_info: &DrawingInfo) {
+        let attributes = [(
+            "xmlns:mc",
+            "http://schemas.openxmlformats.org/markup-compatibility/2006",
+        )];
+
+        xml_start_tag(&mut self.writer, "mc:AlternateContent", &attributes);
+
+        let attributes = [
+            (
+                "xmlns:sle15",
+                "http://schemas.microsoft.com/office/drawing/2012/slicer",
+            ),
+            ("Requires", "sle15"),
+        ];
+
+        xml_start_tag(&mut self.writer, "mc:Choice", &attributes);
+
+        // Write the <xdr:graphicFrame> element.
+        self.write_slicer_graphic_frame(index, drawing_info);
+
+        xml_end_tag(&mut self.writer, "mc:Choice");
+
+        let attributes = [("xmlns", "")];
+
+        xml_start_tag(&mut self.writer, "mc:Fallback", &attributes);
+
+        // Write the fallback <xdr:sp> element.
+        self.write_fallback_sp(
+            drawing_info,
+            "This shape represents a table slicer. Table slicers are not supported in this version of Excel.",
+        );
+
+        xml_end_tag(&mut self.writer, "mc:Fallback");
+        xml_end_tag(&mut self.writer, "mc:AlternateContent");
+    }
+
+    // Write the <xdr:graphicFrame> element for a slicer.
+    fn write_slicer_graphic_frame(&mut self, index: u32, drawing_info: &DrawingInfo) {
+        let attributes = [("macro", "")];
+
+        xml_start_tag(&mut self.writer, "xdr:graphicFrame", &attributes);
+
+        xml_start_tag_only(&mut self.writer, "xdr:nvGraphicFramePr");
+
+        // Write the <xdr:cNvPr> element.
+        self.write_c_nv_pr(index, drawing_info, "Slicer");
+
+        // Write the <xdr:cNvGraphicFramePr> element.
+        self.write_c_nv_graphic_frame_pr(drawing_info.drawing_type);
+
+        xml_end_tag(&mut self.writer, "xdr:nvGraphicFramePr");
+
+        // Write the <xdr:xfrm> element.
+        self.write_xfrm();
+
+        xml_start_tag_only(&mut self.writer, "a:graphic");
+
+        let attributes = [(
+            "uri",
+            "http://schemas.microsoft.com/office/drawing/2010/slicer",
+        )];
+
+        xml_start_tag(&mut self.writer, "a:graphicData", &attributes);
+
+        // Write the <sle:slicer> element.
+        let attributes = [
+            (
+                "xmlns:sle",
+                "http://schemas.microsoft.com/office/drawing/2010/slicer".to_string(),
+            ),
+            ("name", drawing_info.name.clone()),
+        ];
+
+        xml_empty_tag(&mut self.writer, "sle:slicer", &attributes);
+
+        xml_end_tag(&mut self.writer, "a:graphicData");
+        xml_end_tag(&mut self.writer, "a:graphic");
+        xml_end_tag(&mut self.writer, "xdr:graphicFrame");
+    }
+
+    // Write the fallback <xdr:sp> element for a slicer or chartex chart.
+    fn write_fallback_sp(&mut self, drawing_info: &DrawingInfo, text: &str) {
+        let attributes = [("macro", ""), ("textlink", "")];
+
+        xml_start_tag(&mut self.writer, "xdr:sp", &attributes);
+
+        xml_start_tag_only(&mut self.writer, "xdr:nvSpPr");
+        xml_empty_tag(&mut self.writer, "xdr:cNvPr", &[("id", "0"), ("name", "")]);
+        xml_start_tag_only(&mut self.writer, "xdr:cNvSpPr");
+        xml_empty_tag(&mut self.writer, "a:spLocks", &[("noTextEdit", "1")]);
+        xml_end_tag(&mut self.writer, "xdr:cNvSpPr");
+        xml_end_tag(&mut self.writer, "xdr:nvSpPr");
+
+        // Write the <xdr:spPr> element.
+        self.write_sp_pr(drawing_info);
+
+        xml_start_tag_only(&mut self.writer, "xdr:txBody");
+        xml_empty_tag(
+            &mut self.writer,
+            "a:bodyPr",
+            &[("vertOverflow", "clip"), ("horzOverflow", "clip")],
+        );
+        xml_empty_tag_only(&mut self.writer, "a:lstStyle");
+        xml_start_tag_only(&mut self.writer, "a:p");
+        xml_start_tag_only(&mut self.writer, "a:r");
+        xml_empty_tag(
+            &mut self.writer,
+            "a:rPr",
+            &[("lang", "en-US"), ("sz", "1100")],
+        );
+        xml_data_element_only(&mut self.writer, "a:t", text);
+        xml_end_tag(&mut self.writer, "a:r");
+        xml_end_tag(&mut self.writer, "a:p");
+        xml_end_tag(&mut self.writer, "xdr:txBody");
+
+        xml_end_tag(&mut self.writer, "xdr:sp");
+    }
+
     // Write the <xdr:sp> element.
     fn write_sp(&mut self, index: u32, drawing_info: &DrawingInfo, shape: &Shape) {
         let mut attributes = vec![("macro", String::new())];
@@ -1295,9 +1499,11 @@ pub(crate) struct DrawingInfo {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum DrawingType {
     Chart,
+    ChartEx,
     ChartSheet,
     Image,
     Shape,
+    Slicer,
     Vml,
 }
 
@@ -0,0 +1,467 @@
+// encryption - A module for encrypting an xlsx file with a password using the
+// ECMA-376 Agile Encryption scheme.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+// The Agile Encryption scheme is described in the [MS-OFFCRYPTO]
+// specification:
+// https://learn.microsoft.com/en-us/openspecs/office_file_formats/ms-offcrypto
+//
+// The xlsx (zip) file is encrypted with a random AES-256 key in 4096 byte
+// segments and stored in an "EncryptedPackage" stream in an OLE Compound File.
+// The random key is in turn encrypted with a key derived from the password and
+// stored, along with the encryption parameters, in an "EncryptionInfo" stream.
+
+mod tests;
+
+use std::io::{Cursor, Write};
+
+use aes::Aes256;
+use cbc::cipher::{block_padding::NoPadding, BlockEncryptMut, KeyIvInit};
+use cfb::{CompoundFile, Version};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha512};
+
+use crate::utility::{base64_encode, random_bytes};
+use crate::xmlwriter::{
+    xml_declaration, xml_empty_tag, xml_end_tag, xml_start_tag, xml_start_tag_only,
+};
+use crate::XlsxError;
+
+// Encryption parameters. These are the same as the Excel defaults.
+const SALT_SIZE: usize = 16;
+const BLOCK_SIZE: usize = 16;
+const KEY_SIZE: usize = 32;
+const HASH_SIZE: usize = 64;
+const SPIN_COUNT: u32 = 100_000;
+const SEGMENT_SIZE: usize = 4096;
+
+// Block keys used to derive the keys and initialization vectors for the
+// different encrypted values, see [MS-OFFCRYPTO] 2.3.4.11 and 2.3.4.14.
+const VERIFIER_HASH_INPUT_BLOCK_KEY: [u8; 8] = [0xfe, 0xa7, 0xd2, 0x76, 0x3b, 0x4b, 0x9e, 0x79];
+const VERIFIER_HASH_VALUE_BLOCK_KEY: [u8; 8] = [0xd7, 0xaa, 0x0f, 0x6d, 0x30, 0x61, 0x34, 0x4e];
+const ENCRYPTED_KEY_VALUE_BLOCK_KEY: [u8; 8] = [0x14, 0x6e, 0x0b, 0xe7, 0xab, 0xac, 0xd0, 0xd6];
+const HMAC_KEY_BLOCK_KEY: [u8; 8] = [0x5f, 0xb2, 0xad, 0x01, 0x0c, 0xb9, 0xe1, 0xf6];
+const HMAC_VALUE_BLOCK_KEY: [u8; 8] = [0xa0, 0x67, 0x7f, 0x02, 0xb2, 0x2c, 0x84, 0x33];
+
+// The transform identifiers used in the DataSpaces streams.
+const TRANSFORM_ID: &str = "{FF9A3F03-56EF-4613-BDD5-5A41C1D07246}";
+const TRANSFORM_NAME: &str = "Microsoft.Container.EncryptionTransform";
+const DATA_SPACE_NAME: &str = "StrongEncryptionDataSpace";
+const TRANSFORM_REFERENCE: &str = "StrongEncryptionTransform";
+
+type Aes256CbcEncryptor = cbc::Encryptor<Aes256>;
+
+pub(crate) struct Encryptor {
+    pub(crate) writer: Cursor<Vec<u8>>,
+    password: String,
+    key_data_salt: Vec<u8>,
+    password_salt: Vec<u8>,
+    secret_key: Vec<u8>,
+    verifier_hash_input: Vec<u8>,
+    hmac_key: Vec<u8>,
+}
+
+impl Encryptor {
+    // -----------------------------------------------------------------------
+    // Crate public methods.
+    // -----------------------------------------------------------------------
+
+    // Create a new Encryptor struct with random salts and keys.
+    pub(crate) fn new(password: &str) -> Result<Encryptor, XlsxError> {
+        Ok(Encryptor {
+            writer: Cursor::new(Vec::with_capacity(2048)),
+            password: password.to_string(),
+            key_data_salt: random_bytes(SALT_SIZE)?,
+            password_salt: random_bytes(SALT_SIZE)?,
+            secret_key: random_bytes(KEY_SIZE)?,
+            verifier_hash_input: random_bytes(SALT_SIZE)?,
+            hmac_key: random_bytes(HASH_SIZE)?,
+        })
+    }
+
+    // Encrypt an xlsx file and return it as an OLE Compound File.
+    pub(crate) fn encrypt(&mut self, xlsx_data: &[u8]) -> Result<Vec<u8>, XlsxError> {
+        let encrypted_package = self.encrypted_package(xlsx_data);
+        let encryption_info = self.encryption_info(&encrypted_package);
+
+        let mut compound_file =
+            CompoundFile::create_with_version(Version::V3, Cursor::new(Vec::new()))?;
+
+        compound_file.create_storage("/\u{6}DataSpaces")?;
+        compound_file.create_storage("/\u{6}DataSpaces/DataSpaceInfo")?;
+        compound_file.create_storage("/\u{6}DataSpaces/TransformInfo")?;
+        compound_file.create_storage("/\u{6}DataSpaces/TransformInfo/StrongEncryptionTransform")?;
+
+        let streams = [
+            ("/\u{6}DataSpaces/Version", Self::data_space_version()),
+            ("/\u{6}DataSpaces/DataSpaceMap", Self::data_space_map()),
+            (
+                "/\u{6}DataSpaces/DataSpaceInfo/StrongEncryptionDataSpace",
+                Self::data_space_definition(),
+            ),
+            (
+                "/\u{6}DataSpaces/TransformInfo/StrongEncryptionTransform/\u{6}Primary",
+                Self::transform_info(),
+            ),
+            ("/EncryptionInfo", encryption_info),
+            ("/EncryptedPackage", encrypted_package),
+        ];
+
+        for (path, data) in streams {
+            let mut stream = compound_file.create_stream(path)?;
+            stream.write_all(&data)?;
+        }
+
+        compound_file.flush()?;
+
+        Ok(compound_file.into_inner().into_inner())
+    }
+
+    // -----------------------------------------------------------------------
+    // Encryption methods.
+    // -----------------------------------------------------------------------
+
+    // Encrypt the xlsx data in segments, prefixed by the unencrypted size.
+    fn encrypted_package(&self, xlsx_data: &[u8]) -> Vec<u8> {
+        let mut package = Vec::with_capacity(xlsx_data.len() + 2 * BLOCK_SIZE);
+        package.extend_from_slice(&(xlsx_data.len() as u64).to_le_bytes());
+
+        for (index, segment) in xlsx_data.chunks(SEGMENT_SIZE).enumerate() {
+            let block_key = (index as u32).to_le_bytes();
+            let iv = Self::derive_iv(&self.key_data_salt, &block_key);
+
+            package.extend(aes_cbc_encrypt(&self.secret_key, &iv, segment));
+        }
+
+        package
+    }
+
+    // Create the EncryptionInfo stream. This is a version header followed by
+    // an XML description of the encryption parameters and values.
+    fn encryption_info(&mut self, encrypted_package: &[u8]) -> Vec<u8> {
+        // Encrypt the password verifier and the secret key with keys derived
+        // from the password.
+        let password_hash = self.password_hash();
+        let verifier_hash_value = Sha512::digest(&self.verifier_hash_input).to_vec();
+
+        let encrypted_verifier_hash_input = aes_cbc_encrypt(
+            &Self::derive_key(&password_hash, &VERIFIER_HASH_INPUT_BLOCK_KEY),
+            &self.password_salt,
+            &self.verifier_hash_input,
+        );
+
+        let encrypted_verifier_hash_value = aes_cbc_encrypt(
+            &Self::derive_key(&password_hash, &VERIFIER_HASH_VALUE_BLOCK_KEY),
+            &self.password_salt,
+            &verifier_hash_value,
+        );
+
+        let encrypted_key_value = aes_cbc_encrypt(
+            &Self::derive_key(&password_hash, &ENCRYPTED_KEY_VALUE_BLOCK_KEY),
+            &self.password_salt,
+            &self.secret_key,
+        );
+
+        // Create and encrypt the data integrity HMAC of the encrypted package.
+        let mut hmac = Hmac::<Sha512>::new_from_slice(&self.hmac_key)
+            .expect("HMAC can take a key of any size");
+        hmac.update(encrypted_package);
+        let hmac_value = hmac.finalize().into_bytes().to_vec();
+
+        let encrypted_hmac_key = aes_cbc_encrypt(
+            &self.secret_key,
+            &Self::derive_iv(&self.key_data_salt, &HMAC_KEY_BLOCK_KEY),
+            &self.hmac_key,
+        );
+
+        let encrypted_hmac_value = aes_cbc_encrypt(
+            &self.secret_key,
+            &Self::derive_iv(&self.key_data_salt, &HMAC_VALUE_BLOCK_KEY),
+            &hmac_value,
+        );
+
+        self.assemble_xml_file(
+            &encrypted_verifier_hash_input,
+            &encrypted_verifier_hash_value,
+            &encrypted_key_value,
+            &encrypted_hmac_key,
+            &encrypted_hmac_value,
+        );
+
+        // Version 4.4 and the Agile encryption flag.
+        let mut data = vec![0x04, 0x00, 0x04, 0x00, 0x40, 0x00, 0x00, 0x00];
+        data.extend_from_slice(self.writer.get_ref());
+
+        data
+    }
+
+    // Hash the password with the salt and iterate the hash, see
+    // [MS-OFFCRYPTO] 2.3.4.11.
+    fn password_hash(&self) -> Vec<u8> {
+        let password: Vec<u8> = self
+            .password
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+
+        let mut hash = Sha512::new()
+            .chain_update(&self.password_salt)
+            .chain_update(&password)
+            .finalize();
+
+        for iterator in 0..SPIN_COUNT {
+            hash = Sha512::new()
+                .chain_update(iterator.to_le_bytes())
+                .chain_update(hash)
+                .finalize();
+        }
+
+        hash.to_vec()
+    }
+
+    // Derive an encryption key from the password hash and a block key.
+    pub(crate) fn derive_key(password_hash: &[u8], block_key: &[u8]) -> Vec<u8> {
+        let mut key = Sha512::new()
+            .chain_update(password_hash)
+            .chain_update(block_key)
+            .finalize()
+            .to_vec();
+
+        key.truncate(KEY_SIZE);
+        key
+    }
+
+    // Derive an initialization vector from the key data salt and a block key.
+    pub(crate) fn derive_iv(salt: &[u8], block_key: &[u8]) -> Vec<u8> {
+        let mut iv = Sha512::new()
+            .chain_update(salt)
+            .chain_update(block_key)
+            .finalize()
+            .to_vec();
+
+        iv.truncate(BLOCK_SIZE);
+        iv
+    }
+
+    // -----------------------------------------------------------------------
+    // DataSpaces stream methods. See [MS-OFFCRYPTO] 2.1.
+    // -----------------------------------------------------------------------
+
+    // Create the \x06DataSpaces/Version stream.
+    fn data_space_version() -> Vec<u8> {
+        let mut data = vec![];
+        write_unicode_string(&mut data, "Microsoft.Container.DataSpaces");
+
+        // Reader, updater and writer versions, all 1.0.
+        for _ in 0..3 {
+            data.extend_from_slice(&[0x01, 0x00, 0x00, 0x00]);
+        }
+
+        data
+    }
+
+    // Create the \x06DataSpaces/DataSpaceMap stream.
+    fn data_space_map() -> Vec<u8> {
+        let mut entry = vec![];
+
+        // Reference component count, type (stream) and name.
+        entry.extend_from_slice(&1u32.to_le_bytes());
+        entry.extend_from_slice(&0u32.to_le_bytes());
+        write_unicode_string(&mut entry, "EncryptedPackage");
+        write_unicode_string(&mut entry, DATA_SPACE_NAME);
+
+        // Header length and entry count followed by the length prefixed entry.
+        let mut data = vec![];
+        data.extend_from_slice(&8u32.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&(entry.len() as u32 + 4).to_le_bytes());
+        data.extend(entry);
+
+        data
+    }
+
+    // Create the \x06DataSpaces/DataSpaceInfo/StrongEncryptionDataSpace stream.
+    fn data_space_definition() -> Vec<u8> {
+        let mut data = vec![];
+
+        // Header length and transform reference count.
+        data.extend_from_slice(&8u32.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        write_unicode_string(&mut data, TRANSFORM_REFERENCE);
+
+        data
+    }
+
+    // Create the \x06DataSpaces/TransformInfo/StrongEncryptionTransform/\x06Primary
+    // stream.
+    fn transform_info() -> Vec<u8> {
+        let mut transform_id = vec![];
+        write_unicode_string(&mut transform_id, TRANSFORM_ID);
+
+        // Transform header length, type and id.
+        let mut data = vec![];
+        data.extend_from_slice(&(transform_id.len() as u32 + 8).to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend(transform_id);
+        write_unicode_string(&mut data, TRANSFORM_NAME);
+
+        // Reader, updater and writer versions, all 1.0.
+        for _ in 0..3 {
+            data.extend_from_slice(&[0x01, 0x00, 0x00, 0x00]);
+        }
+
+        // Encryption name, block size and cipher mode, which are all unused,
+        // and the reserved value.
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&4u32.to_le_bytes());
+
+        data
+    }
+
+    // -----------------------------------------------------------------------
+    // XML assembly methods.
+    // -----------------------------------------------------------------------
+
+    // Assemble and generate the EncryptionInfo XML.
+    fn assemble_xml_file(
+        &mut self,
+        encrypted_verifier_hash_input: &[u8],
+        encrypted_verifier_hash_value: &[u8],
+        encrypted_key_value: &[u8],
+        encrypted_hmac_key: &[u8],
+        encrypted_hmac_value: &[u8],
+    ) {
+        xml_declaration(&mut self.writer);
+
+        // Write the <encryption> element.
+        self.write_encryption();
+
+        // Write the <keyData> element.
+        self.write_key_data();
+
+        // Write the <dataIntegrity> element.
+        self.write_data_integrity(encrypted_hmac_key, encrypted_hmac_value);
+
+        xml_start_tag_only(&mut self.writer, "keyEncryptors");
+
+        // Write the <keyEncryptor> element.
+        let attributes = [(
+            "uri",
+            "http://schemas.microsoft.com/office/2006/keyEncryptor/password",
+        )];
+        xml_start_tag(&mut self.writer, "keyEncryptor", &attributes);
+
+        // Write the <p:encryptedKey> element.
+        self.write_encrypted_key(
+            encrypted_verifier_hash_input,
+            encrypted_verifier_hash_value,
+            encrypted_key_value,
+        );
+
+        xml_end_tag(&mut self.writer, "keyEncryptor");
+        xml_end_tag(&mut self.writer, "keyEncryptors");
+
+        // Close the final tag.
+        xml_end_tag(&mut self.writer, "encryption");
+    }
+
+    // Write the <encryption> element.
+    fn write_encryption(&mut self) {
+        let schema = "http://schemas.microsoft.com/office/2006";
+        let attributes = [
+            ("xmlns", format!("{schema}/encryption")),
+            ("xmlns:p", format!("{schema}/keyEncryptor/password")),
+            ("xmlns:c", format!("{schema}/keyEncryptor/certificate")),
+        ];
+
+        xml_start_tag(&mut self.writer, "encryption", &attributes);
+    }
+
+    // Write the <keyData> element.
+    fn write_key_data(&mut self) {
+        let mut attributes = Self::cipher_attributes();
+        attributes.push(("saltValue", base64_encode(&self.key_data_salt)));
+
+        xml_empty_tag(&mut self.writer, "keyData", &attributes);
+    }
+
+    // Write the <dataIntegrity> element.
+    fn write_data_integrity(&mut self, encrypted_hmac_key: &[u8], encrypted_hmac_value: &[u8]) {
+        let attributes = [
+            ("encryptedHmacKey", base64_encode(encrypted_hmac_key)),
+            ("encryptedHmacValue", base64_encode(encrypted_hmac_value)),
+        ];
+
+        xml_empty_tag(&mut self.writer, "dataIntegrity", &attributes);
+    }
+
+    // Write the <p:encryptedKey> element.
+    fn write_encrypted_key(
+        &mut self,
+        encrypted_verifier_hash_input: &[u8],
+        encrypted_verifier_hash_value: &[u8],
+        encrypted_key_value: &[u8],
+    ) {
+        let mut attributes = vec![("spinCount", SPIN_COUNT.to_string())];
+        attributes.extend(Self::cipher_attributes());
+        attributes.push(("saltValue", base64_encode(&self.password_salt)));
+        attributes.push((
+            "encryptedVerifierHashInput",
+            base64_encode(encrypted_verifier_hash_input),
+        ));
+        attributes.push((
+            "encryptedVerifierHashValue",
+            base64_encode(encrypted_verifier_hash_value),
+        ));
+        attributes.push(("encryptedKeyValue", base64_encode(encrypted_key_value)));
+
+        xml_empty_tag(&mut self.writer, "p:encryptedKey", &attributes);
+    }
+
+    // The cipher attributes that are common to the <keyData> and
+    // <p:encryptedKey> elements.
+    fn cipher_attributes() -> Vec<(&'static str, String)> {
+        vec![
+            ("saltSize", SALT_SIZE.to_string()),
+            ("blockSize", BLOCK_SIZE.to_string()),
+            ("keyBits", (KEY_SIZE * 8).to_string()),
+            ("hashSize", HASH_SIZE.to_string()),
+            ("cipherAlgorithm", "AES".to_string()),
+            ("cipherChaining", "ChainingModeCBC".to_string()),
+            ("hashAlgorithm", "SHA512".to_string()),
+        ]
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helper functions.
+// -----------------------------------------------------------------------
+
+// Encrypt data using AES-256 in CBC mode. The data is padded with zeros to a
+// multiple of the block size.
+pub(crate) fn aes_cbc_encrypt(key: &[u8], iv: &[u8], data: &[u8]) -> Vec<u8> {
+    let padded_size = data.len().div_ceil(BLOCK_SIZE) * BLOCK_SIZE;
+    let mut buffer = data.to_vec();
+    buffer.resize(padded_size, 0);
+
+    Aes256CbcEncryptor::new(key.into(), iv.into())
+        .encrypt_padded_mut::<NoPadding>(&mut buffer, padded_size)
+        .expect("data is padded to the block size");
+
+    buffer
+}
+
+// Write a UTF-16 string prefixed by its length and padded to a 4 byte
+// boundary, the UNICODE-LP-P4 structure in [MS-OFFCRYPTO] 2.1.2.
+fn write_unicode_string(data: &mut Vec<u8>, string: &str) {
+    let bytes: Vec<u8> = string.encode_utf16().flat_map(u16::to_le_bytes).collect();
+
+    data.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    data.extend_from_slice(&bytes);
+    data.resize(data.len() + (4 - bytes.len() % 4) % 4, 0);
+}
@@ -0,0 +1,285 @@
+// Encryption unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod encryption_tests {
+
+    use std::io::{Cursor, Read};
+
+    use aes::Aes256;
+    use cbc::cipher::{block_padding::NoPadding, BlockDecryptMut, KeyIvInit};
+    use cfb::CompoundFile;
+    use hmac::{Hmac, Mac};
+    use sha2::{Digest, Sha512};
+
+    use crate::encryption::{aes_cbc_encrypt, Encryptor};
+    use crate::{Workbook, XlsxError};
+
+    // Decrypt AES-256-CBC data with no padding.
+    fn aes_cbc_decrypt(key: &[u8], iv: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut buffer = data.to_vec();
+
+        cbc::Decryptor::<Aes256>::new(key.into(), iv.into())
+            .decrypt_padded_mut::<NoPadding>(&mut buffer)
+            .unwrap();
+
+        buffer
+    }
+
+    // Simple Base64 decoder for the test data.
+    fn base64_decode(encoded: &str) -> Vec<u8> {
+        let alphabet = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let mut bits = 0u32;
+        let mut bit_count = 0;
+        let mut decoded = vec![];
+
+        for char in encoded.chars().filter(|char| *char != '=') {
+            bits = bits << 6 | alphabet.find(char).unwrap() as u32;
+            bit_count += 6;
+
+            if bit_count >= 8 {
+                bit_count -= 8;
+                decoded.push((bits >> bit_count) as u8);
+            }
+        }
+
+        decoded
+    }
+
+    // Get the decoded value of an attribute in the EncryptionInfo XML.
+    fn attribute(xml: &str, element: &str, name: &str) -> Vec<u8> {
+        let start = xml.find(&format!("<{element} ")).unwrap();
+        let xml = &xml[start..];
+        let start = xml.find(&format!(" {name}=\"")).unwrap() + name.len() + 3;
+        let end = start + xml[start..].find('"').unwrap();
+
+        base64_decode(&xml[start..end])
+    }
+
+    // Create an Encryptor with fixed salts and keys for known answer tests.
+    fn fixed_encryptor(password: &str) -> Encryptor {
+        Encryptor {
+            writer: Cursor::new(vec![]),
+            password: password.to_string(),
+            password_salt: (0..16).collect(),
+            key_data_salt: (16..32).collect(),
+            secret_key: (32..64).collect(),
+            verifier_hash_input: (64..80).collect(),
+            hmac_key: (0..64).collect(),
+        }
+    }
+
+    fn to_hex(data: &[u8]) -> String {
+        data.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // Read a stream from a compound file.
+    fn read_stream(compound_file: &mut CompoundFile<Cursor<Vec<u8>>>, path: &str) -> Vec<u8> {
+        let mut data = vec![];
+        compound_file
+            .open_stream(path)
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+
+        data
+    }
+
+    // Decrypt an encrypted file using the password, following the steps that
+    // Excel uses to open it.
+    fn decrypt(data: Vec<u8>, password: &str) -> Option<Vec<u8>> {
+        let mut compound_file = CompoundFile::open(Cursor::new(data)).unwrap();
+
+        assert!(compound_file.is_stream("/\u{6}DataSpaces/DataSpaceMap"));
+        assert!(compound_file
+            .is_stream("/\u{6}DataSpaces/TransformInfo/StrongEncryptionTransform/\u{6}Primary"));
+
+        let info = read_stream(&mut compound_file, "/EncryptionInfo");
+        let package = read_stream(&mut compound_file, "/EncryptedPackage");
+
+        assert_eq!(info[..8], [0x04, 0x00, 0x04, 0x00, 0x40, 0x00, 0x00, 0x00]);
+        let xml = std::str::from_utf8(&info[8..]).unwrap();
+
+        let key_data_salt = attribute(xml, "keyData", "saltValue");
+        let password_salt = attribute(xml, "p:encryptedKey", "saltValue");
+
+        // Derive the password hash.
+        let password: Vec<u8> = password.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let mut hash = Sha512::digest([password_salt.clone(), password].concat()).to_vec();
+        for iterator in 0..100_000u32 {
+            hash = Sha512::digest([iterator.to_le_bytes().to_vec(), hash].concat()).to_vec();
+        }
+
+        // Check the password verifier.
+        let key = Encryptor::derive_key(&hash, &[0xfe, 0xa7, 0xd2, 0x76, 0x3b, 0x4b, 0x9e, 0x79]);
+        let verifier_input = aes_cbc_decrypt(
+            &key,
+            &password_salt,
+            &attribute(xml, "p:encryptedKey", "encryptedVerifierHashInput"),
+        );
+
+        let key = Encryptor::derive_key(&hash, &[0xd7, 0xaa, 0x0f, 0x6d, 0x30, 0x61, 0x34, 0x4e]);
+        let verifier_hash = aes_cbc_decrypt(
+            &key,
+            &password_salt,
+            &attribute(xml, "p:encryptedKey", "encryptedVerifierHashValue"),
+        );
+
+        if Sha512::digest(&verifier_input).to_vec() != verifier_hash {
+            return None;
+        }
+
+        // Decrypt the secret key.
+        let key = Encryptor::derive_key(&hash, &[0x14, 0x6e, 0x0b, 0xe7, 0xab, 0xac, 0xd0, 0xd6]);
+        let secret_key = aes_cbc_decrypt(
+            &key,
+            &password_salt,
+            &attribute(xml, "p:encryptedKey", "encryptedKeyValue"),
+        );
+
+        // Check the data integrity HMAC.
+        let iv = Encryptor::derive_iv(
+            &key_data_salt,
+            &[0x5f, 0xb2, 0xad, 0x01, 0x0c, 0xb9, 0xe1, 0xf6],
+        );
+        let hmac_key = aes_cbc_decrypt(
+            &secret_key,
+            &iv,
+            &attribute(xml, "dataIntegrity", "encryptedHmacKey"),
+        );
+
+        let iv = Encryptor::derive_iv(
+            &key_data_salt,
+            &[0xa0, 0x67, 0x7f, 0x02, 0xb2, 0x2c, 0x84, 0x33],
+        );
+        let hmac_value = aes_cbc_decrypt(
+            &secret_key,
+            &iv,
+            &attribute(xml, "dataIntegrity", "encryptedHmacValue"),
+        );
+
+        let mut hmac = Hmac::<Sha512>::new_from_slice(&hmac_key).unwrap();
+        hmac.update(&package);
+        assert_eq!(hmac.finalize().into_bytes().to_vec(), hmac_value);
+
+        // Decrypt the package.
+        let size = u64::from_le_bytes(package[..8].try_into().unwrap()) as usize;
+        let mut decrypted = vec![];
+
+        for (index, segment) in package[8..].chunks(4096).enumerate() {
+            let iv = Encryptor::derive_iv(&key_data_salt, &(index as u32).to_le_bytes());
+            decrypted.extend(aes_cbc_decrypt(&secret_key, &iv, segment));
+        }
+        decrypted.truncate(size);
+
+        Some(decrypted)
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        // Data that spans several segments with a partial final segment.
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+
+        let mut encryptor = Encryptor::new("Secret").unwrap();
+        let encrypted = encryptor.encrypt(&data).unwrap();
+
+        assert_eq!(
+            encrypted[..8],
+            [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]
+        );
+        assert_eq!(decrypt(encrypted.clone(), "Secret"), Some(data));
+        assert_eq!(decrypt(encrypted, "secret"), None);
+    }
+
+    #[test]
+    fn test_password_hash_known_answer() {
+        // The expected values were calculated independently, following the
+        // MS-OFFCRYPTO agile encryption steps, with the same fixed salts.
+        let encryptor = fixed_encryptor("Secret");
+        let hash = encryptor.password_hash();
+
+        assert_eq!(
+            to_hex(&hash),
+            "5916d522d7e1fb42e313b254e8ef079fdf59965bf3c5daf8f3a4cddaa17d492a\
+             7f81a1c1953153c32beed018d03f5a3d72acf8ca260cbfd2bd03b51a24048a73"
+        );
+
+        let key = Encryptor::derive_key(&hash, &[0xfe, 0xa7, 0xd2, 0x76, 0x3b, 0x4b, 0x9e, 0x79]);
+        assert_eq!(
+            to_hex(&aes_cbc_encrypt(
+                &key,
+                &encryptor.password_salt,
+                &encryptor.verifier_hash_input
+            )),
+            "9bbad91521dc74e96b51a73a454bfe73"
+        );
+
+        let key = Encryptor::derive_key(&hash, &[0x14, 0x6e, 0x0b, 0xe7, 0xab, 0xac, 0xd0, 0xd6]);
+        assert_eq!(
+            to_hex(&aes_cbc_encrypt(
+                &key,
+                &encryptor.password_salt,
+                &encryptor.secret_key
+            )),
+            "0a690349039d0a6b5dfe4a8e3e043eaf5afb5b1d98bfa57c1049525b41e2dec7"
+        );
+    }
+
+    #[test]
+    fn test_encrypted_package_known_answer() {
+        let encryptor = fixed_encryptor("Secret");
+        let package = encryptor.encrypted_package(b"Hello, World!");
+
+        assert_eq!(package[..8], 13u64.to_le_bytes());
+        assert_eq!(to_hex(&package[8..]), "ad61c1e80aa383e0b3147facd7bfe63a");
+    }
+
+    #[test]
+    fn test_aes_cbc_encrypt_known_answer() {
+        // Test vector from NIST SP 800-38A, F.2.5 CBC-AES256.Encrypt.
+        let key = from_hex("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4");
+        let iv = from_hex("000102030405060708090a0b0c0d0e0f");
+        let plaintext =
+            from_hex("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51");
+
+        assert_eq!(
+            to_hex(&aes_cbc_encrypt(&key, &iv, &plaintext)),
+            "f58c4c04d6e5f1ba779eabfb5f7bfbd69cfc4e967edb808d679f777bc6702c7d"
+        );
+    }
+
+    #[test]
+    fn test_set_open_password_length() {
+        let mut workbook = Workbook::new();
+
+        assert!(workbook.set_open_password(&"a".repeat(255)).is_ok());
+        assert!(matches!(
+            workbook.set_open_password(&"a".repeat(256)),
+            Err(XlsxError::ParameterError(_))
+        ));
+    }
+
+    #[test]
+    fn test_workbook_set_open_password() {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_string(0, 0, "Hello").unwrap();
+
+        workbook.set_open_password("Pässwörd").unwrap();
+        let encrypted = workbook.save_to_buffer().unwrap();
+
+        let decrypted = decrypt(encrypted, "Pässwörd").unwrap();
+        let mut zip = zip::ZipArchive::new(Cursor::new(decrypted)).unwrap();
+        assert!(zip.by_name("xl/worksheets/sheet1.xml").is_ok());
+    }
+}
@@ -11,6 +11,9 @@ mod tests;
 use std::error::Error;
 use std::fmt;
 
+#[cfg(feature = "arrow")]
+use arrow::error::ArrowError;
+
 #[cfg(feature = "polars")]
 use polars::prelude::polars_err;
 
@@ -161,6 +164,14 @@ pub enum XlsxError {
     /// prohibited by Excel.
     AutofilterRangeOverlaps(String, String),
 
+    /// A general error that is raised when a pivot table parameter is
+    /// incorrect, or a pivot table is configured incorrectly.
+    PivotTableError(String),
+
+    /// A general error that is raised when a slicer parameter is incorrect,
+    /// or a slicer refers to an unknown table or column.
+    SlicerError(String),
+
     /// A general error that is raised when a conditional format parameter is
     /// incorrect or missing.
     ConditionalFormatError(String),
@@ -183,6 +194,15 @@ pub enum XlsxError {
     /// Excel limits the maximum worksheet group level to 8 levels.
     MaxGroupLevelExceeded,
 
+    /// A formula syntax error or unknown function. This is raised by
+    /// [`Formula::parse()`](crate::Formula::parse),
+    /// [`Formula::validate()`](crate::Formula::validate) and by worksheet
+    /// formula methods when
+    /// [`Workbook::use_strict_formulas()`](crate::Workbook::use_strict_formulas)
+    /// is enabled. In strict mode the error message contains the location of
+    /// the formula cell.
+    FormulaError(String),
+
     /// An error that is raised when setting the default format for a workbook
     /// or worksheet.
     DefaultFormatError(String),
@@ -190,6 +210,10 @@ pub enum XlsxError {
     /// An error that is raised when setting the theme for a workbook.
     ThemeError(String),
 
+    /// An error that is raised when reading or saving a workbook created from
+    /// a template file with [`Workbook::from_template()`](crate::Workbook::from_template).
+    TemplateError(String),
+
     /// A customizable error that can be used by third parties to raise errors
     /// or as a conversion target for other error types.
     CustomError(String),
@@ -218,6 +242,13 @@ pub enum XlsxError {
     #[cfg(feature = "polars")]
     #[cfg_attr(docsrs, doc(cfg(feature = "polars")))]
     PolarsError(PolarsError),
+
+    /// Wrapper for a variety of [`arrow::error::ArrowError`] errors that can
+    /// occur when reading or converting Arrow `RecordBatch` data. This
+    /// requires the `arrow` feature to be enabled.
+    #[cfg(feature = "arrow")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    ArrowError(ArrowError),
 }
 
 impl Error for XlsxError {}
@@ -345,6 +376,14 @@ impl fmt::Display for XlsxError {
                 write!(f, "Table error: '{error}'.")
             }
 
+            XlsxError::PivotTableError(error) => {
+                write!(f, "Pivot table error: '{error}'.")
+            }
+
+            XlsxError::SlicerError(error) => {
+                write!(f, "Slicer error: '{error}'.")
+            }
+
             XlsxError::ConditionalFormatError(error) => {
                 write!(f, "Conditional format error: '{error}'.")
             }
@@ -364,6 +403,10 @@ impl fmt::Display for XlsxError {
                 )
             }
 
+            XlsxError::FormulaError(error) => {
+                write!(f, "Formula error: '{error}'.")
+            }
+
             XlsxError::DefaultFormatError(error) => {
                 write!(f, "Default format error: '{error}'.")
             }
@@ -372,6 +415,10 @@ impl fmt::Display for XlsxError {
                 write!(f, "Theme error: '{error}'.")
             }
 
+            XlsxError::TemplateError(error) => {
+                write!(f, "Template error: '{error}'.")
+            }
+
             XlsxError::CustomError(error) => {
                 write!(f, "{error}")
             }
@@ -393,6 +440,11 @@ impl fmt::Display for XlsxError {
             XlsxError::PolarsError(error) => {
                 write!(f, "{error}")
             }
+
+            #[cfg(feature = "arrow")]
+            XlsxError::ArrowError(error) => {
+                write!(f, "{error}")
+            }
         }
     }
 }
@@ -427,6 +479,22 @@ impl From<XlsxError> for PolarsError {
     }
 }
 
+// Convert from Arrow errors to XlsxError to allow easier interoperability.
+#[cfg(feature = "arrow")]
+impl From<ArrowError> for XlsxError {
+    fn from(e: ArrowError) -> XlsxError {
+        XlsxError::ArrowError(e)
+    }
+}
+
+// Convert from XlsxError to Arrow errors to allow easier interoperability.
+#[cfg(feature = "arrow")]
+impl From<XlsxError> for ArrowError {
+    fn from(e: XlsxError) -> ArrowError {
+        ArrowError::ExternalError(Box::new(e))
+    }
+}
+
 // Convert from XlsxError to JsValue errors to allow easier interoperability.
 #[cfg(all(
     feature = "wasm",
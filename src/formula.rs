@@ -8,8 +8,17 @@
 
 mod tests;
 
+pub(crate) mod evaluator;
+pub(crate) mod parser;
+
+pub use parser::{
+    FormulaBinaryOperator, FormulaExpression, FormulaReference, FormulaUnaryOperator,
+};
+
 use std::{collections::HashMap, sync::OnceLock};
 
+use crate::XlsxError;
+
 /// The `Formula` struct is used to define a worksheet formula.
 ///
 /// The `Formula` struct creates a formula type that can be used to write
@@ -733,6 +742,13 @@ use std::{collections::HashMap, sync::OnceLock};
 ///             <f>SUM(1, 2, 3)</f>
 /// ```
 ///
+/// Some of these errors can also be caught before the file is created using
+/// the [`Formula::validate()`] method, which checks the syntax of a formula and
+/// the names of the functions that it uses. The
+/// [`Workbook::use_strict_formulas()`](crate::Workbook::use_strict_formulas)
+/// mode applies the same checks to every formula written to the workbook, and
+/// also checks that worksheet references refer to existing worksheets.
+///
 #[derive(Clone, PartialEq)]
 pub struct Formula {
     pub(crate) formula_string: String,
@@ -834,6 +850,111 @@ impl Formula {
         self
     }
 
+    /// Parse the formula into an expression tree.
+    ///
+    /// The `parse()` method tokenizes and parses the formula into a
+    /// [`FormulaExpression`] abstract syntax tree (AST). This can be used to
+    /// check that a formula is syntactically correct or to inspect the
+    /// functions, references and operators that it uses.
+    ///
+    /// The parser follows the Excel formula grammar in the en-US locale. It
+    /// doesn't check that functions exist. Use [`Formula::validate()`] for
+    /// that.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::FormulaError`] - The formula contains a syntax error such
+    ///   as unbalanced parentheses, an unterminated string or an invalid cell
+    ///   reference. The error message contains the position of the error in
+    ///   the formula.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates parsing a formula into an
+    /// expression tree.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_formula_parse.rs
+    /// #
+    /// # use rust_xlsxwriter::{Formula, FormulaExpression, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let formula = Formula::new("=SUM(A1:A10)*2");
+    ///
+    ///     let expression = formula.parse()?;
+    ///
+    ///     if let FormulaExpression::Binary { left, .. } = expression {
+    ///         if let FormulaExpression::Function { name, args } = *left {
+    ///             assert_eq!(name, "SUM");
+    ///             assert_eq!(args.len(), 1);
+    ///         }
+    ///     }
+    ///
+    ///     // Formulas with syntax errors return an error.
+    ///     let formula = Formula::new("=SUM(A1:A10");
+    ///     assert!(formula.parse().is_err());
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn parse(&self) -> Result<FormulaExpression, XlsxError> {
+        parser::parse(&self.formula_string)
+            .map_err(|error| XlsxError::FormulaError(error.description(&self.formula_string)))
+    }
+
+    /// Validate the syntax and function names of the formula.
+    ///
+    /// The `validate()` method parses the formula, see [`Formula::parse()`],
+    /// and also checks that the functions that it uses are Excel worksheet
+    /// functions. This catches common errors that would otherwise only appear
+    /// when the file is opened in Excel, such as unbalanced parentheses, bad
+    /// ranges like `A1:XFE1` or misspelled function names.
+    ///
+    /// Function names are checked against the standard Excel functions,
+    /// including the Excel 2010+ future functions. Functions defined using
+    /// `LAMBDA()` and a defined name aren't known to the formula and are
+    /// reported as unknown. User defined functions with the `_xludf.` prefix
+    /// are allowed.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::FormulaError`] - The formula contains a syntax error or
+    ///   an unknown function.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates validating formulas.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_formula_validate.rs
+    /// #
+    /// # use rust_xlsxwriter::Formula;
+    /// #
+    /// # fn main() {
+    ///     // A valid formula.
+    ///     assert!(Formula::new("=VLOOKUP(A1,B1:C10,2,FALSE)").validate().is_ok());
+    ///
+    ///     // A formula with unbalanced parentheses.
+    ///     assert!(Formula::new("=SUM(A1:A10").validate().is_err());
+    ///
+    ///     // A formula with a misspelled function name.
+    ///     assert!(Formula::new("=SUMM(A1:A10)").validate().is_err());
+    /// # }
+    /// ```
+    ///
+    pub fn validate(&self) -> Result<(), XlsxError> {
+        let expression = self.parse()?;
+
+        match expression.unknown_function(&[]) {
+            Some(name) => Err(XlsxError::FormulaError(format!(
+                "Unknown function '{name}' in formula '{}'",
+                self.formula_string
+            ))),
+            None => Ok(()),
+        }
+    }
+
     // Prefix any "future" functions in a formula with "_xlfn.". We parse the
     // string to avoid replacements in string literal within the formula.
     fn escape_formula(formula: &str) -> (String, bool) {
@@ -0,0 +1,3948 @@
+// formula evaluator - A module for calculating the results of worksheet
+// formulas.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::datetime::ExcelDateTime;
+use crate::formula::parser::{
+    self, FormulaBinaryOperator, FormulaExpression, FormulaReference, FormulaUnaryOperator,
+};
+use crate::worksheet::{DefinedName, DefinedNameType};
+use crate::{utility, ColNum, ExcelError, RowNum, Worksheet, COL_MAX, ROW_MAX};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+// The value of a worksheet cell, as seen by the formula evaluator.
+pub(crate) enum CellInput<'a> {
+    Blank,
+    Number(f64),
+    String(&'a str),
+    Boolean(bool),
+    Error(ExcelError),
+    Formula(&'a str),
+}
+
+// Calculate the results of all the formulas in the worksheets. The results are
+// returned as the worksheet index, cell location and result string. Formulas
+// that use unsupported functions or features are omitted so that their
+// existing results are retained.
+pub(crate) fn calculate_formula_results(
+    worksheets: &[Worksheet],
+    defined_names: &[DefinedName],
+    is_1904_date: bool,
+) -> Vec<(usize, RowNum, ColNum, String)> {
+    let mut evaluator = Evaluator::new(worksheets, defined_names, is_1904_date);
+    let mut results = vec![];
+
+    for (sheet, worksheet) in worksheets.iter().enumerate() {
+        // Rows that have already been flushed can't be calculated.
+        if worksheet.use_constant_memory {
+            continue;
+        }
+
+        for (row, col) in worksheet.formula_cells() {
+            if let Some(value) = evaluator.calculate_cell(sheet, row, col) {
+                results.push((sheet, row, col, value.to_result_string()));
+            }
+        }
+    }
+
+    results
+}
+
+// -----------------------------------------------------------------------
+// Evaluator value types.
+// -----------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Blank,
+    Number(f64),
+    String(String),
+    Boolean(bool),
+    Error(ExcelError),
+    Array(Array),
+    Reference(Range),
+}
+
+impl Value {
+    // Convert a calculated value to the string format used to store formula
+    // results in a worksheet.
+    fn to_result_string(&self) -> String {
+        match self {
+            Value::Blank => "0".to_string(),
+            Value::Number(number) if number.is_finite() => {
+                // Avoid writing "-0".
+                if *number == 0.0 {
+                    "0".to_string()
+                } else {
+                    number.to_string()
+                }
+            }
+            Value::Number(_) => ExcelError::Num.as_str().to_string(),
+            Value::String(string) => string.clone(),
+            Value::Boolean(boolean) => bool_to_string(*boolean).to_string(),
+            Value::Error(error) => error.as_str().to_string(),
+            Value::Array(array) => array.get(0, 0).to_result_string(),
+            Value::Reference(_) => ExcelError::Value.as_str().to_string(),
+        }
+    }
+
+    fn is_blank(&self) -> bool {
+        matches!(self, Value::Blank)
+    }
+}
+
+// A 2D array of values, stored in row major order.
+#[derive(Clone, Debug, PartialEq)]
+struct Array {
+    rows: usize,
+    cols: usize,
+    values: Vec<Value>,
+}
+
+impl Array {
+    fn new(rows: usize, cols: usize, values: Vec<Value>) -> Array {
+        Array { rows, cols, values }
+    }
+
+    fn from_rows(rows: Vec<Vec<Value>>) -> Array {
+        let num_rows = rows.len();
+        let num_cols = rows.first().map_or(0, Vec::len);
+
+        Array::new(num_rows, num_cols, rows.into_iter().flatten().collect())
+    }
+
+    fn get(&self, row: usize, col: usize) -> &Value {
+        self.values
+            .get(row * self.cols + col)
+            .unwrap_or(&Value::Blank)
+    }
+
+    // Get a value with broadcasting of single row or column arrays.
+    fn broadcast(&self, row: usize, col: usize) -> Value {
+        let row = if self.rows == 1 { 0 } else { row };
+        let col = if self.cols == 1 { 0 } else { col };
+
+        if row >= self.rows || col >= self.cols {
+            Value::Error(ExcelError::NA)
+        } else {
+            self.get(row, col).clone()
+        }
+    }
+
+    fn row(&self, row: usize) -> Vec<Value> {
+        self.values[row * self.cols..(row + 1) * self.cols].to_vec()
+    }
+
+    fn col(&self, col: usize) -> Vec<Value> {
+        (0..self.rows)
+            .map(|row| self.get(row, col).clone())
+            .collect()
+    }
+
+    fn transpose(&self) -> Array {
+        let mut values = Vec::with_capacity(self.values.len());
+
+        for col in 0..self.cols {
+            for row in 0..self.rows {
+                values.push(self.get(row, col).clone());
+            }
+        }
+
+        Array::new(self.cols, self.rows, values)
+    }
+
+    fn into_value(self) -> Value {
+        if self.rows == 1 && self.cols == 1 {
+            self.values.into_iter().next().unwrap_or(Value::Blank)
+        } else {
+            Value::Array(self)
+        }
+    }
+
+    // Check if the array is a single row or column.
+    fn is_vector(&self) -> bool {
+        self.rows == 1 || self.cols == 1
+    }
+}
+
+// A reference resolved to a worksheet index.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Range {
+    sheet: usize,
+    first_row: RowNum,
+    first_col: ColNum,
+    last_row: RowNum,
+    last_col: ColNum,
+}
+
+impl Range {
+    fn is_single_cell(&self) -> bool {
+        self.first_row == self.last_row && self.first_col == self.last_col
+    }
+}
+
+// The reasons that a formula evaluation can stop. Excel errors are converted
+// to error values at function and operator boundaries. Unsupported functions
+// or features stop the evaluation of the formula. Formulas that depend on a
+// cell beyond the maximum reference depth are deferred until that cell has
+// been calculated.
+#[derive(Debug)]
+enum EvalError {
+    Excel(ExcelError),
+    Unsupported,
+    Deferred(usize, RowNum, ColNum),
+}
+
+impl From<ExcelError> for EvalError {
+    fn from(error: ExcelError) -> EvalError {
+        EvalError::Excel(error)
+    }
+}
+
+type EvalResult<T> = Result<T, EvalError>;
+
+// The maximum depth of formula cell references that are calculated
+// recursively. Deeper references are calculated separately to avoid
+// overflowing the stack for long chains of dependent formulas.
+const MAX_FORMULA_DEPTH: usize = 64;
+
+// The state of a formula cell calculation.
+enum CellState {
+    InProgress,
+    Done(Value),
+    Unsupported,
+}
+
+// The context of the formula being evaluated.
+struct Context {
+    sheet: usize,
+    row: RowNum,
+    col: ColNum,
+    names: Vec<(String, Value)>,
+}
+
+// -----------------------------------------------------------------------
+// Evaluator.
+// -----------------------------------------------------------------------
+
+struct Evaluator<'a> {
+    worksheets: &'a [Worksheet],
+    defined_names: &'a [DefinedName],
+    extents: Vec<Option<(RowNum, ColNum)>>,
+    cells: HashMap<(usize, RowNum, ColNum), CellState>,
+    depth: usize,
+    is_1904_date: bool,
+}
+
+impl<'a> Evaluator<'a> {
+    fn new(
+        worksheets: &'a [Worksheet],
+        defined_names: &'a [DefinedName],
+        is_1904_date: bool,
+    ) -> Evaluator<'a> {
+        Evaluator {
+            worksheets,
+            defined_names,
+            extents: worksheets
+                .iter()
+                .map(Worksheet::formula_data_extent)
+                .collect(),
+            cells: HashMap::new(),
+            depth: 0,
+            is_1904_date,
+        }
+    }
+
+    // Calculate the value of a formula cell. Returns None if the formula
+    // can't be calculated. Dependencies that are too deep to calculate
+    // recursively are added to a stack of pending cells and calculated first.
+    fn calculate_cell(&mut self, sheet: usize, row: RowNum, col: ColNum) -> Option<Value> {
+        let mut pending = vec![(sheet, row, col)];
+
+        while let Some(&(sheet, row, col)) = pending.last() {
+            match self.formula_cell_value(sheet, row, col) {
+                Err(EvalError::Deferred(sheet, row, col)) => {
+                    // A long circular reference can't be resolved in order.
+                    if pending.contains(&(sheet, row, col)) {
+                        self.cells.insert((sheet, row, col), CellState::Unsupported);
+                    } else {
+                        pending.push((sheet, row, col));
+                    }
+                }
+                _ => {
+                    pending.pop();
+                }
+            }
+        }
+
+        self.formula_cell_value(sheet, row, col).ok()
+    }
+
+    // Calculate the value of a formula cell, or get the cached value.
+    fn formula_cell_value(&mut self, sheet: usize, row: RowNum, col: ColNum) -> EvalResult<Value> {
+        let key = (sheet, row, col);
+
+        match self.cells.get(&key) {
+            Some(CellState::Done(value)) => return Ok(value.clone()),
+            Some(CellState::Unsupported) => return Err(EvalError::Unsupported),
+            // Excel stores 0 as the result of circular references.
+            Some(CellState::InProgress) => return Ok(Value::Number(0.0)),
+            None => {}
+        }
+
+        if self.depth >= MAX_FORMULA_DEPTH {
+            return Err(EvalError::Deferred(sheet, row, col));
+        }
+
+        let CellInput::Formula(formula) = self.worksheets[sheet].formula_input(row, col) else {
+            return Err(EvalError::Unsupported);
+        };
+
+        let Ok(expr) = parser::parse(formula) else {
+            self.cells.insert(key, CellState::Unsupported);
+            return Err(EvalError::Unsupported);
+        };
+
+        self.cells.insert(key, CellState::InProgress);
+
+        let mut context = Context {
+            sheet,
+            row,
+            col,
+            names: vec![],
+        };
+
+        self.depth += 1;
+        let result = self
+            .eval(&expr, &mut context)
+            .and_then(|value| self.operand(value));
+        self.depth -= 1;
+
+        match result {
+            Ok(value) => {
+                self.cells.insert(key, CellState::Done(value.clone()));
+                Ok(value)
+            }
+            // Leave the cell uncalculated until the dependency is available.
+            Err(EvalError::Deferred(sheet, row, col)) => {
+                self.cells.remove(&key);
+                Err(EvalError::Deferred(sheet, row, col))
+            }
+            Err(_) => {
+                self.cells.insert(key, CellState::Unsupported);
+                Err(EvalError::Unsupported)
+            }
+        }
+    }
+
+    // Get the value of a worksheet cell, calculating it if it is a formula.
+    fn cell_value(&mut self, sheet: usize, row: RowNum, col: ColNum) -> EvalResult<Value> {
+        let value = match self.worksheets[sheet].formula_input(row, col) {
+            CellInput::Blank => Value::Blank,
+            CellInput::Number(number) => Value::Number(number),
+            CellInput::String(string) => Value::String(string.to_string()),
+            CellInput::Boolean(boolean) => Value::Boolean(boolean),
+            CellInput::Error(error) => Value::Error(error),
+            CellInput::Formula(_) => match self.formula_cell_value(sheet, row, col)? {
+                Value::Array(array) => array.get(0, 0).clone(),
+                value => value,
+            },
+        };
+
+        Ok(value)
+    }
+
+    // Get the values in a range. Full row and column ranges are limited to the
+    // populated area of the worksheet.
+    fn range_values(&mut self, range: &Range) -> EvalResult<Array> {
+        let mut last_row = range.last_row;
+        let mut last_col = range.last_col;
+
+        if last_row == ROW_MAX - 1 || last_col == COL_MAX - 1 {
+            let (extent_row, extent_col) = self.extents[range.sheet].unwrap_or((0, 0));
+
+            if last_row == ROW_MAX - 1 {
+                last_row = extent_row.max(range.first_row);
+            }
+            if last_col == COL_MAX - 1 {
+                last_col = extent_col.max(range.first_col);
+            }
+        }
+
+        let rows = (last_row - range.first_row + 1) as usize;
+        let cols = (last_col - range.first_col + 1) as usize;
+        let mut values = Vec::with_capacity(rows * cols);
+
+        for row in range.first_row..=last_row {
+            for col in range.first_col..=last_col {
+                values.push(self.cell_value(range.sheet, row, col)?);
+            }
+        }
+
+        Ok(Array::new(rows, cols, values))
+    }
+
+    // Convert a reference to its values. Single cells are returned as scalars.
+    fn operand(&mut self, value: Value) -> EvalResult<Value> {
+        match value {
+            Value::Reference(range) if range.is_single_cell() => {
+                self.cell_value(range.sheet, range.first_row, range.first_col)
+            }
+            Value::Reference(range) => Ok(Value::Array(self.range_values(&range)?)),
+            value => Ok(value),
+        }
+    }
+
+    // Convert a value to a single scalar value. Arrays use their first value.
+    fn scalar(&mut self, value: Value) -> EvalResult<Value> {
+        match self.operand(value)? {
+            Value::Array(array) => Ok(array.get(0, 0).clone()),
+            value => Ok(value),
+        }
+    }
+
+    // Evaluate an expression. Excel errors are returned as error values.
+    fn eval(&mut self, expr: &FormulaExpression, context: &mut Context) -> EvalResult<Value> {
+        match self.eval_expression(expr, context) {
+            Err(EvalError::Excel(error)) => Ok(Value::Error(error)),
+            result => result,
+        }
+    }
+
+    fn eval_expression(
+        &mut self,
+        expr: &FormulaExpression,
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        match expr {
+            FormulaExpression::Number(number) => Ok(Value::Number(*number)),
+            FormulaExpression::String(string) => Ok(Value::String(string.clone())),
+            FormulaExpression::Boolean(boolean) => Ok(Value::Boolean(*boolean)),
+            FormulaExpression::Error(error) => match ExcelError::from_excel_str(error) {
+                Some(error) => Ok(Value::Error(error)),
+                None => Err(EvalError::Unsupported),
+            },
+            FormulaExpression::Empty => Ok(Value::Blank),
+            FormulaExpression::Reference(reference) => self.resolve_reference(reference, context),
+            FormulaExpression::Name(name) => self.resolve_name(name, context),
+            FormulaExpression::Array(rows) => {
+                let mut values = vec![];
+                for row in rows {
+                    let mut row_values = vec![];
+                    for expr in row {
+                        row_values.push(self.eval_expression(expr, context)?);
+                    }
+                    values.push(row_values);
+                }
+                Ok(Value::Array(Array::from_rows(values)))
+            }
+            FormulaExpression::Function { name, args } => self.call_function(name, args, context),
+            FormulaExpression::Unary { op, expr } => self.eval_unary(*op, expr, context),
+            FormulaExpression::Binary { op, left, right } => {
+                self.eval_binary(*op, left, right, context)
+            }
+            FormulaExpression::StructuredReference(_) => Err(EvalError::Unsupported),
+        }
+    }
+
+    fn resolve_reference(
+        &self,
+        reference: &FormulaReference,
+        context: &Context,
+    ) -> EvalResult<Value> {
+        // 3D references aren't supported.
+        if reference.last_sheet.is_some() {
+            return Err(EvalError::Unsupported);
+        }
+
+        let sheet = match &reference.sheet {
+            Some(name) => match self.sheet_index(name) {
+                Some(sheet) => sheet,
+                None => return Ok(Value::Error(ExcelError::Ref)),
+            },
+            None => context.sheet,
+        };
+
+        Ok(Value::Reference(Range {
+            sheet,
+            first_row: reference.first_row,
+            first_col: reference.first_col,
+            last_row: reference.last_row,
+            last_col: reference.last_col,
+        }))
+    }
+
+    fn sheet_index(&self, name: &str) -> Option<usize> {
+        self.worksheets
+            .iter()
+            .position(|worksheet| worksheet.name.eq_ignore_ascii_case(name))
+    }
+
+    // Resolve a LET variable or a workbook/worksheet defined name.
+    fn resolve_name(&mut self, name: &str, context: &mut Context) -> EvalResult<Value> {
+        if let Some((_, value)) = context
+            .names
+            .iter()
+            .rev()
+            .find(|(variable, _)| variable.eq_ignore_ascii_case(name))
+        {
+            return Ok(value.clone());
+        }
+
+        let sheet_name = &self.worksheets[context.sheet].name;
+
+        // Worksheet local names take precedence over global names.
+        let defined_name = self
+            .defined_names
+            .iter()
+            .filter(|defined_name| defined_name.name.eq_ignore_ascii_case(name))
+            .min_by_key(|defined_name| match defined_name.name_type {
+                DefinedNameType::Local
+                    if utility::unquote_sheetname(&defined_name.quoted_sheet_name)
+                        == *sheet_name =>
+                {
+                    0
+                }
+                DefinedNameType::Global => 1,
+                _ => 2,
+            })
+            .filter(|defined_name| {
+                defined_name.name_type == DefinedNameType::Global
+                    || utility::unquote_sheetname(&defined_name.quoted_sheet_name) == *sheet_name
+            });
+
+        let Some(defined_name) = defined_name else {
+            return Ok(Value::Error(ExcelError::Name));
+        };
+
+        let Ok(expr) = parser::parse(&defined_name.range) else {
+            return Err(EvalError::Unsupported);
+        };
+
+        self.eval(&expr, context)
+    }
+
+    fn eval_unary(
+        &mut self,
+        op: FormulaUnaryOperator,
+        expr: &FormulaExpression,
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        match op {
+            FormulaUnaryOperator::Spill => {
+                let value = self.eval(expr, context)?;
+                match value {
+                    Value::Reference(range) if range.is_single_cell() => {
+                        match self.worksheets[range.sheet]
+                            .formula_input(range.first_row, range.first_col)
+                        {
+                            CellInput::Formula(_) => self.formula_cell_value(
+                                range.sheet,
+                                range.first_row,
+                                range.first_col,
+                            ),
+                            _ => Ok(Value::Error(ExcelError::Ref)),
+                        }
+                    }
+                    _ => Ok(Value::Error(ExcelError::Ref)),
+                }
+            }
+            FormulaUnaryOperator::ImplicitIntersection => {
+                let value = self.eval(expr, context)?;
+                match value {
+                    Value::Reference(range) => {
+                        let row = if range.first_row == range.last_row {
+                            range.first_row
+                        } else if (range.first_row..=range.last_row).contains(&context.row) {
+                            context.row
+                        } else {
+                            return Ok(Value::Error(ExcelError::Value));
+                        };
+                        let col = if range.first_col == range.last_col {
+                            range.first_col
+                        } else if (range.first_col..=range.last_col).contains(&context.col) {
+                            context.col
+                        } else {
+                            return Ok(Value::Error(ExcelError::Value));
+                        };
+                        self.cell_value(range.sheet, row, col)
+                    }
+                    value => self.scalar(value),
+                }
+            }
+            FormulaUnaryOperator::Plus => {
+                let value = self.eval(expr, context)?;
+                self.operand(value)
+            }
+            FormulaUnaryOperator::Minus | FormulaUnaryOperator::Percent => {
+                let value = self.eval(expr, context)?;
+                let value = self.operand(value)?;
+
+                Ok(map_values(&[value], &|values| {
+                    let number = to_number(&values[0])?;
+                    if op == FormulaUnaryOperator::Minus {
+                        Ok(Value::Number(-number))
+                    } else {
+                        Ok(Value::Number(number / 100.0))
+                    }
+                }))
+            }
+        }
+    }
+
+    fn eval_binary(
+        &mut self,
+        op: FormulaBinaryOperator,
+        left: &FormulaExpression,
+        right: &FormulaExpression,
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        let left = self.eval(left, context)?;
+        let right = self.eval(right, context)?;
+
+        if op == FormulaBinaryOperator::Range {
+            return match (left, right) {
+                (Value::Reference(first), Value::Reference(second))
+                    if first.sheet == second.sheet =>
+                {
+                    Ok(Value::Reference(Range {
+                        sheet: first.sheet,
+                        first_row: first.first_row.min(second.first_row),
+                        first_col: first.first_col.min(second.first_col),
+                        last_row: first.last_row.max(second.last_row),
+                        last_col: first.last_col.max(second.last_col),
+                    }))
+                }
+                (Value::Error(error), _) | (_, Value::Error(error)) => Ok(Value::Error(error)),
+                _ => Ok(Value::Error(ExcelError::Value)),
+            };
+        }
+
+        let left = self.operand(left)?;
+        let right = self.operand(right)?;
+
+        Ok(map_values(&[left, right], &|values| {
+            binary_operation(op, &values[0], &values[1])
+        }))
+    }
+
+    // -----------------------------------------------------------------------
+    // Argument helpers.
+    // -----------------------------------------------------------------------
+
+    // Evaluate an argument to a value, with references converted to values.
+    fn arg(
+        &mut self,
+        args: &[FormulaExpression],
+        index: usize,
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        match args.get(index) {
+            Some(expr) => {
+                let value = self.eval(expr, context)?;
+                self.operand(value)
+            }
+            None => Ok(Value::Blank),
+        }
+    }
+
+    // Evaluate an argument to a scalar value.
+    fn arg_scalar(
+        &mut self,
+        args: &[FormulaExpression],
+        index: usize,
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        let value = self.arg(args, index, context)?;
+        self.scalar(value)
+    }
+
+    // Evaluate an optional number argument.
+    fn arg_number(
+        &mut self,
+        args: &[FormulaExpression],
+        index: usize,
+        default: f64,
+        context: &mut Context,
+    ) -> EvalResult<f64> {
+        match args.get(index) {
+            None | Some(FormulaExpression::Empty) => Ok(default),
+            Some(_) => Ok(to_number(&self.arg_scalar(args, index, context)?)?),
+        }
+    }
+
+    // Evaluate an optional boolean argument.
+    fn arg_bool(
+        &mut self,
+        args: &[FormulaExpression],
+        index: usize,
+        default: bool,
+        context: &mut Context,
+    ) -> EvalResult<bool> {
+        match args.get(index) {
+            None | Some(FormulaExpression::Empty) => Ok(default),
+            Some(_) => Ok(to_bool(&self.arg_scalar(args, index, context)?)?),
+        }
+    }
+
+    // Evaluate an argument as an array. Scalars are converted to 1x1 arrays.
+    fn arg_array(
+        &mut self,
+        args: &[FormulaExpression],
+        index: usize,
+        context: &mut Context,
+    ) -> EvalResult<Array> {
+        match self.arg(args, index, context)? {
+            Value::Array(array) => Ok(array),
+            Value::Error(error) => Err(error.into()),
+            value => Ok(Array::new(1, 1, vec![value])),
+        }
+    }
+
+    // Evaluate all the arguments and apply a scalar function to them, lifting
+    // it over any array arguments.
+    fn lifted(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+        function: &dyn Fn(&[Value]) -> Result<Value, ExcelError>,
+    ) -> EvalResult<Value> {
+        let mut values = Vec::with_capacity(args.len());
+
+        for index in 0..args.len() {
+            values.push(self.arg(args, index, context)?);
+        }
+
+        Ok(map_values(&values, function))
+    }
+
+    // Collect the numbers from the arguments of aggregate functions like
+    // SUM(). Numbers in references and arrays are used and other types are
+    // ignored. Direct arguments are converted to numbers.
+    fn collect_numbers(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Vec<f64>> {
+        let mut numbers = vec![];
+
+        for expr in args {
+            if matches!(expr, FormulaExpression::Empty) {
+                numbers.push(0.0);
+                continue;
+            }
+
+            let value = self.eval(expr, context)?;
+
+            match value {
+                Value::Reference(range) => {
+                    let array = self.range_values(&range)?;
+                    collect_array_numbers(&array, &mut numbers)?;
+                }
+                Value::Array(array) => collect_array_numbers(&array, &mut numbers)?,
+                Value::Blank => {}
+                value => numbers.push(to_number(&value)?),
+            }
+        }
+
+        Ok(numbers)
+    }
+
+    // Collect all the values from the arguments, flattening references and
+    // arrays.
+    fn collect_values(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Vec<Value>> {
+        let mut values = vec![];
+
+        for expr in args {
+            match self.arg(std::slice::from_ref(expr), 0, context)? {
+                Value::Array(array) => values.extend(array.values),
+                value => values.push(value),
+            }
+        }
+
+        Ok(values)
+    }
+
+    // -----------------------------------------------------------------------
+    // Functions.
+    // -----------------------------------------------------------------------
+
+    fn call_function(
+        &mut self,
+        name: &str,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        match self.call_function_inner(name, args, context) {
+            Err(EvalError::Excel(error)) => Ok(Value::Error(error)),
+            result => result,
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn call_function_inner(
+        &mut self,
+        name: &str,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        let is_1904 = self.is_1904_date;
+
+        match name {
+            // Logical functions.
+            "IF" => self.function_if(args, context),
+            "IFS" => self.function_ifs(args, context),
+            "IFERROR" | "IFNA" => {
+                check_args(args, 2, 2)?;
+                let value = self.arg(args, 0, context)?;
+                let is_na_only = name == "IFNA";
+
+                let fallback = |value: &Value| match value {
+                    Value::Error(ExcelError::NA) => true,
+                    Value::Error(_) => !is_na_only,
+                    _ => false,
+                };
+
+                match value {
+                    Value::Array(array) => {
+                        let replacement = self.arg(args, 1, context)?;
+                        Ok(map_values(&[Value::Array(array), replacement], &|values| {
+                            if fallback(&values[0]) {
+                                Ok(values[1].clone())
+                            } else {
+                                Ok(values[0].clone())
+                            }
+                        }))
+                    }
+                    value if fallback(&value) => self.arg(args, 1, context),
+                    value => Ok(value),
+                }
+            }
+            "AND" | "OR" | "XOR" => {
+                check_args(args, 1, 255)?;
+                let values = self.collect_values(args, context)?;
+                let mut booleans = vec![];
+
+                for value in &values {
+                    match value {
+                        Value::Error(error) => return Err((*error).into()),
+                        Value::Blank | Value::String(_) => {}
+                        value => booleans.push(to_bool(value)?),
+                    }
+                }
+
+                if booleans.is_empty() {
+                    return Err(ExcelError::Value.into());
+                }
+
+                let result = match name {
+                    "AND" => booleans.iter().all(|boolean| *boolean),
+                    "OR" => booleans.iter().any(|boolean| *boolean),
+                    _ => booleans.iter().filter(|boolean| **boolean).count() % 2 == 1,
+                };
+
+                Ok(Value::Boolean(result))
+            }
+            "NOT" => {
+                check_args(args, 1, 1)?;
+                self.lifted(args, context, &|values| {
+                    Ok(Value::Boolean(!to_bool(&values[0])?))
+                })
+            }
+            "TRUE" => Ok(Value::Boolean(true)),
+            "FALSE" => Ok(Value::Boolean(false)),
+            "SWITCH" => self.function_switch(args, context),
+            "CHOOSE" => {
+                check_args(args, 2, 255)?;
+                let index = to_number(&self.arg_scalar(args, 0, context)?)?.trunc();
+                if index < 1.0 || index >= args.len() as f64 {
+                    return Err(ExcelError::Value.into());
+                }
+                self.eval(&args[index as usize], context)
+            }
+            "LET" => self.function_let(args, context),
+
+            // Information functions.
+            "ISBLANK" => {
+                check_args(args, 1, 1)?;
+                self.lifted(args, context, &|values| {
+                    Ok(Value::Boolean(values[0].is_blank()))
+                })
+            }
+            "ISNUMBER" | "ISTEXT" | "ISNONTEXT" | "ISLOGICAL" | "ISERROR" | "ISERR" | "ISNA" => {
+                check_args(args, 1, 1)?;
+                self.lifted(args, context, &|values| {
+                    let value = &values[0];
+                    let result = match name {
+                        "ISNUMBER" => matches!(value, Value::Number(_)),
+                        "ISTEXT" => matches!(value, Value::String(_)),
+                        "ISNONTEXT" => !matches!(value, Value::String(_)),
+                        "ISLOGICAL" => matches!(value, Value::Boolean(_)),
+                        "ISERROR" => matches!(value, Value::Error(_)),
+                        "ISERR" => matches!(value, Value::Error(error) if *error != ExcelError::NA),
+                        _ => matches!(value, Value::Error(ExcelError::NA)),
+                    };
+                    Ok(Value::Boolean(result))
+                })
+            }
+            "ISEVEN" | "ISODD" => {
+                check_args(args, 1, 1)?;
+                self.lifted(args, context, &|values| {
+                    let number = to_number(&values[0])?.trunc();
+                    let is_even = number % 2.0 == 0.0;
+                    Ok(Value::Boolean(is_even == (name == "ISEVEN")))
+                })
+            }
+            "NA" => Ok(Value::Error(ExcelError::NA)),
+
+            // Math functions.
+            "SUM" => {
+                let numbers = self.collect_numbers(args, context)?;
+                Ok(Value::Number(numbers.iter().sum()))
+            }
+            "PRODUCT" => {
+                let numbers = self.collect_numbers(args, context)?;
+                Ok(Value::Number(numbers.iter().product()))
+            }
+            "SUMSQ" => {
+                let numbers = self.collect_numbers(args, context)?;
+                Ok(Value::Number(
+                    numbers.iter().map(|number| number * number).sum(),
+                ))
+            }
+            "SUMPRODUCT" => self.function_sumproduct(args, context),
+            "ABS" => self.lifted_math(args, context, 1, &|n| Ok(n[0].abs())),
+            "SIGN" => self.lifted_math(args, context, 1, &|n| {
+                Ok(if n[0] > 0.0 {
+                    1.0
+                } else if n[0] < 0.0 {
+                    -1.0
+                } else {
+                    0.0
+                })
+            }),
+            "INT" => self.lifted_math(args, context, 1, &|n| Ok(n[0].floor())),
+            "TRUNC" => self.lifted_math(args, context, 1, &|n| {
+                let digits = n.get(1).copied().unwrap_or(0.0);
+                Ok(round_with(n[0], digits, f64::trunc))
+            }),
+            "ROUND" => self.lifted_math(args, context, 2, &|n| {
+                Ok(round_with(n[0], n[1], f64::round))
+            }),
+            "ROUNDUP" => self.lifted_math(args, context, 2, &|n| {
+                Ok(round_with(n[0], n[1], |x| x.abs().ceil() * x.signum()))
+            }),
+            "ROUNDDOWN" => self.lifted_math(args, context, 2, &|n| {
+                Ok(round_with(n[0], n[1], f64::trunc))
+            }),
+            "MROUND" => self.lifted_math(args, context, 2, &|n| {
+                if n[1] == 0.0 {
+                    return Ok(0.0);
+                }
+                if n[0] * n[1] < 0.0 {
+                    return Err(ExcelError::Num);
+                }
+                Ok(round_significant((n[0] / n[1]).round() * n[1]))
+            }),
+            "CEILING" | "FLOOR" | "CEILING.MATH" | "FLOOR.MATH" | "CEILING.PRECISE"
+            | "FLOOR.PRECISE" => {
+                let min_args = if name.contains('.') { 1 } else { 2 };
+                self.lifted_math(args, context, min_args, &|n| {
+                    let number = n[0];
+                    let significance = n.get(1).copied().unwrap_or(1.0);
+                    let is_ceiling = name.starts_with("CEILING");
+
+                    if significance == 0.0 {
+                        return Ok(0.0);
+                    }
+                    if !name.contains('.') && number > 0.0 && significance < 0.0 {
+                        return Err(ExcelError::Num);
+                    }
+
+                    let significance = significance.abs();
+                    let mode = n.get(2).copied().unwrap_or(0.0);
+                    let quotient = round_significant(number / significance);
+
+                    // Negative numbers round towards zero unless a mode is set.
+                    let away_from_zero = number < 0.0 && mode != 0.0 && name.ends_with("MATH");
+                    let rounded = if is_ceiling != away_from_zero {
+                        quotient.ceil()
+                    } else {
+                        quotient.floor()
+                    };
+
+                    Ok(round_significant(rounded * significance))
+                })
+            }
+            "EVEN" | "ODD" => self.lifted_math(args, context, 1, &|n| {
+                let number = n[0].abs().ceil();
+                let result = if name == "EVEN" {
+                    (number / 2.0).ceil() * 2.0
+                } else if number % 2.0 == 0.0 {
+                    number + 1.0
+                } else {
+                    number
+                };
+                Ok(result.max(if name == "ODD" { 1.0 } else { 0.0 }) * sign_or_one(n[0]))
+            }),
+            "MOD" => self.lifted_math(args, context, 2, &|n| {
+                if n[1] == 0.0 {
+                    return Err(ExcelError::Div0);
+                }
+                Ok(round_significant(n[0] - n[1] * (n[0] / n[1]).floor()))
+            }),
+            "QUOTIENT" => self.lifted_math(args, context, 2, &|n| {
+                if n[1] == 0.0 {
+                    return Err(ExcelError::Div0);
+                }
+                Ok((n[0] / n[1]).trunc())
+            }),
+            "POWER" => self.lifted_math(args, context, 2, &|n| power(n[0], n[1])),
+            "SQRT" => self.lifted_math(args, context, 1, &|n| {
+                if n[0] < 0.0 {
+                    Err(ExcelError::Num)
+                } else {
+                    Ok(n[0].sqrt())
+                }
+            }),
+            "EXP" => self.lifted_math(args, context, 1, &|n| Ok(n[0].exp())),
+            "LN" | "LOG10" | "LOG" => self.lifted_math(args, context, 1, &|n| {
+                let base = match name {
+                    "LN" => std::f64::consts::E,
+                    "LOG10" => 10.0,
+                    _ => n.get(1).copied().unwrap_or(10.0),
+                };
+                if n[0] <= 0.0 || base <= 0.0 || base == 1.0 {
+                    return Err(ExcelError::Num);
+                }
+                Ok(n[0].ln() / base.ln())
+            }),
+            "PI" => Ok(Value::Number(std::f64::consts::PI)),
+            "RADIANS" => self.lifted_math(args, context, 1, &|n| Ok(n[0].to_radians())),
+            "DEGREES" => self.lifted_math(args, context, 1, &|n| Ok(n[0].to_degrees())),
+            "SIN" => self.lifted_math(args, context, 1, &|n| Ok(n[0].sin())),
+            "COS" => self.lifted_math(args, context, 1, &|n| Ok(n[0].cos())),
+            "TAN" => self.lifted_math(args, context, 1, &|n| Ok(n[0].tan())),
+            "ASIN" | "ACOS" => self.lifted_math(args, context, 1, &|n| {
+                if n[0].abs() > 1.0 {
+                    return Err(ExcelError::Num);
+                }
+                Ok(if name == "ASIN" {
+                    n[0].asin()
+                } else {
+                    n[0].acos()
+                })
+            }),
+            "ATAN" => self.lifted_math(args, context, 1, &|n| Ok(n[0].atan())),
+            "ATAN2" => self.lifted_math(args, context, 2, &|n| {
+                if n[0] == 0.0 && n[1] == 0.0 {
+                    return Err(ExcelError::Div0);
+                }
+                Ok(n[1].atan2(n[0]))
+            }),
+            "FACT" => self.lifted_math(args, context, 1, &|n| {
+                if n[0] < 0.0 {
+                    return Err(ExcelError::Num);
+                }
+                Ok((1..=n[0].trunc() as u64).map(|x| x as f64).product())
+            }),
+
+            // Statistical functions.
+            "COUNT" => {
+                let mut count = 0;
+                for expr in args {
+                    count += self.count_numbers(expr, context)?;
+                }
+                Ok(Value::Number(f64::from(count)))
+            }
+            "COUNTA" => {
+                let mut count = 0;
+                for expr in args {
+                    if matches!(expr, FormulaExpression::Empty) {
+                        count += 1;
+                        continue;
+                    }
+                    let values = self.collect_values(std::slice::from_ref(expr), context)?;
+                    count += values.iter().filter(|value| !value.is_blank()).count();
+                }
+                Ok(Value::Number(count as f64))
+            }
+            "COUNTBLANK" => {
+                check_args(args, 1, 1)?;
+                let values = self.collect_values(args, context)?;
+                let count = values
+                    .iter()
+                    .filter(|value| match value {
+                        Value::Blank => true,
+                        Value::String(string) => string.is_empty(),
+                        _ => false,
+                    })
+                    .count();
+                Ok(Value::Number(count as f64))
+            }
+            "AVERAGE" => {
+                let numbers = self.collect_numbers(args, context)?;
+                if numbers.is_empty() {
+                    return Err(ExcelError::Div0.into());
+                }
+                Ok(Value::Number(
+                    numbers.iter().sum::<f64>() / numbers.len() as f64,
+                ))
+            }
+            "MIN" | "MAX" => {
+                let numbers = self.collect_numbers(args, context)?;
+                let result = if name == "MIN" {
+                    numbers.iter().copied().reduce(f64::min)
+                } else {
+                    numbers.iter().copied().reduce(f64::max)
+                };
+                Ok(Value::Number(result.unwrap_or(0.0)))
+            }
+            "MEDIAN" => {
+                let mut numbers = self.collect_numbers(args, context)?;
+                if numbers.is_empty() {
+                    return Err(ExcelError::Num.into());
+                }
+                numbers.sort_by(f64::total_cmp);
+                let middle = numbers.len() / 2;
+                let median = if numbers.len() % 2 == 0 {
+                    (numbers[middle - 1] + numbers[middle]) / 2.0
+                } else {
+                    numbers[middle]
+                };
+                Ok(Value::Number(median))
+            }
+            "STDEV" | "STDEV.S" | "STDEVP" | "STDEV.P" | "VAR" | "VAR.S" | "VARP" | "VAR.P" => {
+                let numbers = self.collect_numbers(args, context)?;
+                let is_population = name.ends_with('P');
+                let count = numbers.len() as f64;
+
+                if count < 1.0 || (!is_population && count < 2.0) {
+                    return Err(ExcelError::Div0.into());
+                }
+
+                let mean = numbers.iter().sum::<f64>() / count;
+                let squares: f64 = numbers.iter().map(|number| (number - mean).powi(2)).sum();
+                let variance = squares / if is_population { count } else { count - 1.0 };
+
+                if name.starts_with("STDEV") {
+                    Ok(Value::Number(variance.sqrt()))
+                } else {
+                    Ok(Value::Number(variance))
+                }
+            }
+            "LARGE" | "SMALL" => {
+                check_args(args, 2, 2)?;
+                let mut numbers = self.collect_numbers(&args[..1], context)?;
+                let k = to_number(&self.arg_scalar(args, 1, context)?)?.ceil();
+
+                if k < 1.0 || k > numbers.len() as f64 {
+                    return Err(ExcelError::Num.into());
+                }
+
+                numbers.sort_by(f64::total_cmp);
+                let index = k as usize - 1;
+                if name == "LARGE" {
+                    Ok(Value::Number(numbers[numbers.len() - 1 - index]))
+                } else {
+                    Ok(Value::Number(numbers[index]))
+                }
+            }
+            "RANK" | "RANK.EQ" => {
+                check_args(args, 2, 3)?;
+                let number = to_number(&self.arg_scalar(args, 0, context)?)?;
+                let numbers = self.collect_numbers(&args[1..2], context)?;
+                let is_ascending = self.arg_number(args, 2, 0.0, context)? != 0.0;
+
+                if !numbers.contains(&number) {
+                    return Err(ExcelError::NA.into());
+                }
+
+                let rank = numbers
+                    .iter()
+                    .filter(|other| {
+                        if is_ascending {
+                            **other < number
+                        } else {
+                            **other > number
+                        }
+                    })
+                    .count();
+
+                Ok(Value::Number(rank as f64 + 1.0))
+            }
+            "COUNTIF" | "COUNTIFS" | "SUMIF" | "SUMIFS" | "AVERAGEIF" | "AVERAGEIFS" | "MAXIFS"
+            | "MINIFS" => self.function_conditional(name, args, context),
+
+            // Lookup and reference functions.
+            "VLOOKUP" | "HLOOKUP" => self.function_vlookup(name == "HLOOKUP", args, context),
+            "MATCH" => self.function_match(args, context),
+            "XMATCH" | "XLOOKUP" => self.function_xlookup(name == "XMATCH", args, context),
+            "INDEX" => self.function_index(args, context),
+            "ROW" | "COLUMN" => {
+                check_args(args, 0, 1)?;
+                let is_row = name == "ROW";
+
+                if args.is_empty() {
+                    let index = if is_row {
+                        context.row
+                    } else {
+                        RowNum::from(context.col)
+                    };
+                    return Ok(Value::Number(f64::from(index + 1)));
+                }
+
+                match self.eval(&args[0], context)? {
+                    Value::Reference(range) => {
+                        let array = if is_row {
+                            let rows = (range.first_row..=range.last_row)
+                                .map(|row| vec![Value::Number(f64::from(row + 1))])
+                                .collect();
+                            Array::from_rows(rows)
+                        } else {
+                            let cols = (range.first_col..=range.last_col)
+                                .map(|col| Value::Number(f64::from(col + 1)))
+                                .collect();
+                            Array::from_rows(vec![cols])
+                        };
+                        Ok(array.into_value())
+                    }
+                    Value::Error(error) => Err(error.into()),
+                    _ => Err(ExcelError::Value.into()),
+                }
+            }
+            "ROWS" | "COLUMNS" => {
+                check_args(args, 1, 1)?;
+                let value = self.eval(&args[0], context)?;
+                let (rows, cols) = match value {
+                    Value::Reference(range) => (
+                        (range.last_row - range.first_row + 1) as usize,
+                        usize::from(range.last_col - range.first_col + 1),
+                    ),
+                    Value::Array(array) => (array.rows, array.cols),
+                    Value::Error(error) => return Err(error.into()),
+                    _ => (1, 1),
+                };
+                Ok(Value::Number(
+                    if name == "ROWS" { rows } else { cols } as f64
+                ))
+            }
+
+            // Dynamic array functions.
+            "SEQUENCE" => {
+                check_args(args, 1, 4)?;
+                let rows = self.arg_number(args, 0, 1.0, context)?.trunc();
+                let cols = self.arg_number(args, 1, 1.0, context)?.trunc();
+                let start = self.arg_number(args, 2, 1.0, context)?;
+                let step = self.arg_number(args, 3, 1.0, context)?;
+
+                if rows < 1.0 || cols < 1.0 {
+                    return Err(ExcelError::Value.into());
+                }
+                if rows * cols > f64::from(ROW_MAX) {
+                    return Err(EvalError::Unsupported);
+                }
+
+                let count = (rows * cols) as usize;
+                let values = (0..count)
+                    .map(|index| Value::Number(start + step * index as f64))
+                    .collect();
+
+                Ok(Array::new(rows as usize, cols as usize, values).into_value())
+            }
+            "TRANSPOSE" => {
+                check_args(args, 1, 1)?;
+                Ok(self.arg_array(args, 0, context)?.transpose().into_value())
+            }
+            "FILTER" => self.function_filter(args, context),
+            "SORT" | "SORTBY" => self.function_sort(name == "SORTBY", args, context),
+            "UNIQUE" => self.function_unique(args, context),
+            "VSTACK" | "HSTACK" => {
+                check_args(args, 1, 254)?;
+                let mut arrays = vec![];
+                for index in 0..args.len() {
+                    let array = self.arg_array(args, index, context)?;
+                    arrays.push(if name == "HSTACK" {
+                        array.transpose()
+                    } else {
+                        array
+                    });
+                }
+
+                let cols = arrays.iter().map(|array| array.cols).max().unwrap_or(1);
+                let mut rows = vec![];
+                for array in &arrays {
+                    for row in 0..array.rows {
+                        let mut values = array.row(row);
+                        values.resize(cols, Value::Error(ExcelError::NA));
+                        rows.push(values);
+                    }
+                }
+
+                let array = Array::from_rows(rows);
+                if name == "HSTACK" {
+                    Ok(array.transpose().into_value())
+                } else {
+                    Ok(array.into_value())
+                }
+            }
+            "TAKE" | "DROP" => {
+                check_args(args, 2, 3)?;
+                let array = self.arg_array(args, 0, context)?;
+                let rows = self
+                    .arg_number(args, 1, array.rows as f64, context)?
+                    .trunc();
+                let cols = self
+                    .arg_number(args, 2, array.cols as f64, context)?
+                    .trunc();
+                let is_take = name == "TAKE";
+
+                let row_range = take_drop_range(is_take, rows, array.rows, args.len() > 1);
+                let col_range = take_drop_range(is_take, cols, array.cols, args.len() > 2);
+
+                if row_range.is_empty() || col_range.is_empty() {
+                    return Err(ExcelError::Calc.into());
+                }
+
+                let rows = row_range
+                    .map(|row| {
+                        col_range
+                            .clone()
+                            .map(|col| array.get(row, col).clone())
+                            .collect()
+                    })
+                    .collect();
+
+                Ok(Array::from_rows(rows).into_value())
+            }
+            "TOCOL" | "TOROW" => {
+                check_args(args, 1, 3)?;
+                let array = self.arg_array(args, 0, context)?;
+                let ignore = self.arg_number(args, 1, 0.0, context)?;
+                let by_col = self.arg_bool(args, 2, false, context)?;
+                let array = if by_col { array.transpose() } else { array };
+
+                let values: Vec<Value> = array
+                    .values
+                    .into_iter()
+                    .filter(|value| match value {
+                        Value::Blank => ignore != 1.0 && ignore != 3.0,
+                        Value::Error(_) => ignore != 2.0 && ignore != 3.0,
+                        _ => true,
+                    })
+                    .collect();
+
+                if values.is_empty() {
+                    return Err(ExcelError::Calc.into());
+                }
+
+                let count = values.len();
+                if name == "TOCOL" {
+                    Ok(Array::new(count, 1, values).into_value())
+                } else {
+                    Ok(Array::new(1, count, values).into_value())
+                }
+            }
+            "CHOOSECOLS" | "CHOOSEROWS" => {
+                check_args(args, 2, 255)?;
+                let array = self.arg_array(args, 0, context)?;
+                let is_cols = name == "CHOOSECOLS";
+                let array = if is_cols { array.transpose() } else { array };
+                let mut rows = vec![];
+
+                for index in 1..args.len() {
+                    let number = to_number(&self.arg_scalar(args, index, context)?)?.trunc();
+                    let count = array.rows as f64;
+
+                    let row = if number >= 1.0 && number <= count {
+                        number as usize - 1
+                    } else if number <= -1.0 && number >= -count {
+                        (count + number) as usize
+                    } else {
+                        return Err(ExcelError::Value.into());
+                    };
+
+                    rows.push(array.row(row));
+                }
+
+                let array = Array::from_rows(rows);
+                if is_cols {
+                    Ok(array.transpose().into_value())
+                } else {
+                    Ok(array.into_value())
+                }
+            }
+
+            // Text functions.
+            "LEN" => {
+                check_args(args, 1, 1)?;
+                self.lifted(args, context, &|values| {
+                    Ok(Value::Number(to_text(&values[0])?.chars().count() as f64))
+                })
+            }
+            "LEFT" | "RIGHT" => {
+                check_args(args, 1, 2)?;
+                let has_count = args.len() > 1;
+                self.lifted(args, context, &|values| {
+                    let text = to_text(&values[0])?;
+                    let count = if has_count {
+                        to_number(&values[1])?
+                    } else {
+                        1.0
+                    };
+                    if count < 0.0 {
+                        return Err(ExcelError::Value);
+                    }
+
+                    let chars: Vec<char> = text.chars().collect();
+                    let count = (count as usize).min(chars.len());
+                    let result: String = if name == "LEFT" {
+                        chars[..count].iter().collect()
+                    } else {
+                        chars[chars.len() - count..].iter().collect()
+                    };
+
+                    Ok(Value::String(result))
+                })
+            }
+            "MID" => {
+                check_args(args, 3, 3)?;
+                self.lifted(args, context, &|values| {
+                    let text = to_text(&values[0])?;
+                    let start = to_number(&values[1])?;
+                    let count = to_number(&values[2])?;
+                    if start < 1.0 || count < 0.0 {
+                        return Err(ExcelError::Value);
+                    }
+                    Ok(Value::String(
+                        text.chars()
+                            .skip(start as usize - 1)
+                            .take(count as usize)
+                            .collect(),
+                    ))
+                })
+            }
+            "UPPER" | "LOWER" | "PROPER" | "TRIM" | "CLEAN" => {
+                check_args(args, 1, 1)?;
+                self.lifted(args, context, &|values| {
+                    let text = to_text(&values[0])?;
+                    let result = match name {
+                        "UPPER" => text.to_uppercase(),
+                        "LOWER" => text.to_lowercase(),
+                        "PROPER" => proper_case(&text),
+                        "TRIM" => text
+                            .split(' ')
+                            .filter(|word| !word.is_empty())
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                        _ => text.chars().filter(|char| u32::from(*char) >= 32).collect(),
+                    };
+                    Ok(Value::String(result))
+                })
+            }
+            "CONCATENATE" => {
+                check_args(args, 1, 255)?;
+                self.lifted(args, context, &|values| {
+                    let mut result = String::new();
+                    for value in values {
+                        result.push_str(&to_text(value)?);
+                    }
+                    Ok(Value::String(result))
+                })
+            }
+            "CONCAT" => {
+                check_args(args, 1, 253)?;
+                let values = self.collect_values(args, context)?;
+                let mut result = String::new();
+                for value in &values {
+                    result.push_str(&to_text(value)?);
+                }
+                Ok(Value::String(result))
+            }
+            "TEXTJOIN" => {
+                check_args(args, 3, 252)?;
+                let delimiter = to_text(&self.arg_scalar(args, 0, context)?)?;
+                let ignore_empty = self.arg_bool(args, 1, true, context)?;
+                let values = self.collect_values(&args[2..], context)?;
+                let mut strings = vec![];
+
+                for value in &values {
+                    let text = to_text(value)?;
+                    if !(ignore_empty && text.is_empty()) {
+                        strings.push(text);
+                    }
+                }
+
+                Ok(Value::String(strings.join(&delimiter)))
+            }
+            "TEXT" => {
+                check_args(args, 2, 2)?;
+                self.lifted(args, context, &|values| {
+                    let format = to_text(&values[1])?;
+                    match &values[0] {
+                        Value::String(string) => match parse_number_text(string, is_1904) {
+                            Some(number) => {
+                                Ok(Value::String(format_number(number, &format, is_1904)))
+                            }
+                            None => Ok(Value::String(string.clone())),
+                        },
+                        value => {
+                            let number = to_number(value)?;
+                            Ok(Value::String(format_number(number, &format, is_1904)))
+                        }
+                    }
+                })
+            }
+            "VALUE" | "NUMBERVALUE" => {
+                check_args(args, 1, 1)?;
+                self.lifted(args, context, &|values| match &values[0] {
+                    Value::String(string) => parse_number_text(string, is_1904)
+                        .map(Value::Number)
+                        .ok_or(ExcelError::Value),
+                    value => Ok(Value::Number(to_number(value)?)),
+                })
+            }
+            "DATEVALUE" | "TIMEVALUE" => {
+                check_args(args, 1, 1)?;
+                self.lifted(args, context, &|values| {
+                    let text = to_text(&values[0])?;
+                    let number = parse_date_text(&text, is_1904).ok_or(ExcelError::Value)?;
+                    if name == "DATEVALUE" {
+                        Ok(Value::Number(number.trunc()))
+                    } else {
+                        Ok(Value::Number(number.fract()))
+                    }
+                })
+            }
+            "FIND" | "SEARCH" => {
+                check_args(args, 2, 3)?;
+                let has_start = args.len() > 2;
+                self.lifted(args, context, &|values| {
+                    let needle = to_text(&values[0])?;
+                    let haystack = to_text(&values[1])?;
+                    let start = if has_start {
+                        to_number(&values[2])?
+                    } else {
+                        1.0
+                    };
+                    let chars: Vec<char> = haystack.chars().collect();
+
+                    if start < 1.0 || start as usize > chars.len() + 1 {
+                        return Err(ExcelError::Value);
+                    }
+
+                    let position = if name == "FIND" {
+                        find_chars(
+                            &chars,
+                            &needle.chars().collect::<Vec<_>>(),
+                            start as usize - 1,
+                        )
+                    } else {
+                        search_chars(&chars, &needle, start as usize - 1)
+                    };
+
+                    position
+                        .map(|position| Value::Number(position as f64 + 1.0))
+                        .ok_or(ExcelError::Value)
+                })
+            }
+            "SUBSTITUTE" => {
+                check_args(args, 3, 4)?;
+                let has_instance = args.len() > 3;
+                self.lifted(args, context, &|values| {
+                    let text = to_text(&values[0])?;
+                    let old = to_text(&values[1])?;
+                    let new = to_text(&values[2])?;
+
+                    if old.is_empty() {
+                        return Ok(Value::String(text));
+                    }
+
+                    if !has_instance {
+                        return Ok(Value::String(text.replace(&old, &new)));
+                    }
+
+                    let instance = to_number(&values[3])?;
+                    if instance < 1.0 {
+                        return Err(ExcelError::Value);
+                    }
+
+                    match text.match_indices(&old).nth(instance as usize - 1) {
+                        Some((index, _)) => Ok(Value::String(format!(
+                            "{}{}{}",
+                            &text[..index],
+                            new,
+                            &text[index + old.len()..]
+                        ))),
+                        None => Ok(Value::String(text)),
+                    }
+                })
+            }
+            "REPLACE" => {
+                check_args(args, 4, 4)?;
+                self.lifted(args, context, &|values| {
+                    let chars: Vec<char> = to_text(&values[0])?.chars().collect();
+                    let start = to_number(&values[1])?;
+                    let count = to_number(&values[2])?;
+                    let new = to_text(&values[3])?;
+
+                    if start < 1.0 || count < 0.0 {
+                        return Err(ExcelError::Value);
+                    }
+
+                    let start = (start as usize - 1).min(chars.len());
+                    let end = (start + count as usize).min(chars.len());
+                    let mut result: String = chars[..start].iter().collect();
+                    result.push_str(&new);
+                    result.extend(&chars[end..]);
+
+                    Ok(Value::String(result))
+                })
+            }
+            "REPT" => {
+                check_args(args, 2, 2)?;
+                self.lifted(args, context, &|values| {
+                    let text = to_text(&values[0])?;
+                    let count = to_number(&values[1])?;
+                    if count < 0.0 || text.len() as f64 * count > 32767.0 {
+                        return Err(ExcelError::Value);
+                    }
+                    Ok(Value::String(text.repeat(count as usize)))
+                })
+            }
+            "EXACT" => {
+                check_args(args, 2, 2)?;
+                self.lifted(args, context, &|values| {
+                    Ok(Value::Boolean(to_text(&values[0])? == to_text(&values[1])?))
+                })
+            }
+            "CHAR" | "UNICHAR" => {
+                check_args(args, 1, 1)?;
+                self.lifted(args, context, &|values| {
+                    let number = to_number(&values[0])?;
+                    let limit = if name == "CHAR" { 255.0 } else { 1_114_111.0 };
+                    if number < 1.0 || number > limit {
+                        return Err(ExcelError::Value);
+                    }
+                    char::from_u32(number as u32)
+                        .map(|char| Value::String(char.to_string()))
+                        .ok_or(ExcelError::Value)
+                })
+            }
+            "CODE" | "UNICODE" => {
+                check_args(args, 1, 1)?;
+                self.lifted(args, context, &|values| {
+                    let text = to_text(&values[0])?;
+                    text.chars()
+                        .next()
+                        .map(|char| Value::Number(f64::from(u32::from(char))))
+                        .ok_or(ExcelError::Value)
+                })
+            }
+            "T" => {
+                check_args(args, 1, 1)?;
+                match self.arg_scalar(args, 0, context)? {
+                    Value::String(string) => Ok(Value::String(string)),
+                    Value::Error(error) => Err(error.into()),
+                    _ => Ok(Value::String(String::new())),
+                }
+            }
+            "N" => {
+                check_args(args, 1, 1)?;
+                match self.arg_scalar(args, 0, context)? {
+                    Value::Number(number) => Ok(Value::Number(number)),
+                    Value::Boolean(boolean) => Ok(Value::Number(f64::from(u8::from(boolean)))),
+                    Value::Error(error) => Err(error.into()),
+                    _ => Ok(Value::Number(0.0)),
+                }
+            }
+            "TEXTBEFORE" | "TEXTAFTER" => {
+                check_args(args, 2, 3)?;
+                let has_instance = args.len() > 2;
+                self.lifted(args, context, &|values| {
+                    let text = to_text(&values[0])?;
+                    let delimiter = to_text(&values[1])?;
+                    let instance = if has_instance {
+                        to_number(&values[2])?
+                    } else {
+                        1.0
+                    };
+
+                    if instance == 0.0 {
+                        return Err(ExcelError::Value);
+                    }
+
+                    let matches: Vec<usize> = text
+                        .match_indices(&delimiter)
+                        .map(|(index, _)| index)
+                        .collect();
+                    let index = if instance > 0.0 {
+                        matches.get(instance as usize - 1)
+                    } else {
+                        matches
+                            .len()
+                            .checked_sub((-instance) as usize)
+                            .and_then(|index| matches.get(index))
+                    };
+
+                    match index {
+                        Some(index) if name == "TEXTBEFORE" => {
+                            Ok(Value::String(text[..*index].to_string()))
+                        }
+                        Some(index) => {
+                            Ok(Value::String(text[index + delimiter.len()..].to_string()))
+                        }
+                        None => Err(ExcelError::NA),
+                    }
+                })
+            }
+            "FIXED" => {
+                check_args(args, 1, 3)?;
+                let has_decimals = args.len() > 1;
+                let has_no_commas = args.len() > 2;
+                self.lifted(args, context, &|values| {
+                    let number = to_number(&values[0])?;
+                    let decimals = if has_decimals {
+                        to_number(&values[1])?.trunc()
+                    } else {
+                        2.0
+                    };
+                    let no_commas = has_no_commas && to_bool(&values[2])?;
+                    let number = round_with(number, decimals, f64::round);
+                    let decimals = decimals.max(0.0) as usize;
+                    let mut format = if no_commas {
+                        "0".to_string()
+                    } else {
+                        "#,##0".to_string()
+                    };
+                    if decimals > 0 {
+                        format.push('.');
+                        format.push_str(&"0".repeat(decimals));
+                    }
+                    Ok(Value::String(format_number(number, &format, is_1904)))
+                })
+            }
+
+            // Date and time functions.
+            "DATE" => {
+                check_args(args, 3, 3)?;
+                self.lifted(args, context, &|values| {
+                    let mut year = to_number(&values[0])?.trunc();
+                    let month = to_number(&values[1])?.trunc();
+                    let day = to_number(&values[2])?.trunc();
+
+                    if year < 1900.0 {
+                        year += 1900.0;
+                    }
+
+                    date_to_serial(year as i64, month as i64, day as i64, is_1904)
+                        .map(Value::Number)
+                        .ok_or(ExcelError::Num)
+                })
+            }
+            "TIME" => {
+                check_args(args, 3, 3)?;
+                self.lifted(args, context, &|values| {
+                    let hour = to_number(&values[0])?.trunc();
+                    let minute = to_number(&values[1])?.trunc();
+                    let second = to_number(&values[2])?.trunc();
+                    let seconds = hour * 3600.0 + minute * 60.0 + second;
+
+                    if seconds < 0.0 {
+                        return Err(ExcelError::Num);
+                    }
+
+                    Ok(Value::Number((seconds % SECONDS_PER_DAY) / SECONDS_PER_DAY))
+                })
+            }
+            "YEAR" | "MONTH" | "DAY" => {
+                check_args(args, 1, 1)?;
+                self.lifted(args, context, &|values| {
+                    let serial = to_date_number(&values[0], is_1904)?;
+                    let (year, month, day) = serial_to_ymd(serial, is_1904);
+                    let result = match name {
+                        "YEAR" => f64::from(year),
+                        "MONTH" => f64::from(month),
+                        _ => f64::from(day),
+                    };
+                    Ok(Value::Number(result))
+                })
+            }
+            "HOUR" | "MINUTE" | "SECOND" => {
+                check_args(args, 1, 1)?;
+                self.lifted(args, context, &|values| {
+                    let serial = to_date_number(&values[0], is_1904)?;
+                    let seconds = (serial.fract() * SECONDS_PER_DAY).round() % SECONDS_PER_DAY;
+                    let result = match name {
+                        "HOUR" => (seconds / 3600.0).floor(),
+                        "MINUTE" => ((seconds % 3600.0) / 60.0).floor(),
+                        _ => seconds % 60.0,
+                    };
+                    Ok(Value::Number(result))
+                })
+            }
+            "WEEKDAY" => {
+                check_args(args, 1, 2)?;
+                let has_type = args.len() > 1;
+                self.lifted(args, context, &|values| {
+                    let serial = to_date_number(&values[0], is_1904)?.floor();
+                    let return_type = if has_type {
+                        to_number(&values[1])?
+                    } else {
+                        1.0
+                    };
+                    let sunday_index = weekday_index(serial, is_1904);
+
+                    let result = match return_type as i64 {
+                        1 | 17 => sunday_index + 1,
+                        2 | 11 => (sunday_index + 6) % 7 + 1,
+                        3 => (sunday_index + 6) % 7,
+                        12..=16 => (sunday_index + 7 - (return_type as i64 - 10) % 7) % 7 + 1,
+                        _ => return Err(ExcelError::Num),
+                    };
+
+                    Ok(Value::Number(result as f64))
+                })
+            }
+            "WEEKNUM" | "ISOWEEKNUM" => {
+                check_args(args, 1, 2)?;
+                let has_type = args.len() > 1;
+                self.lifted(args, context, &|values| {
+                    let serial = to_date_number(&values[0], is_1904)?.floor();
+                    let return_type = if has_type {
+                        to_number(&values[1])?
+                    } else {
+                        1.0
+                    };
+                    let (year, _, _) = serial_to_ymd(serial, is_1904);
+
+                    if name == "ISOWEEKNUM" || return_type == 21.0 {
+                        return Ok(Value::Number(iso_week_number(serial, is_1904)));
+                    }
+
+                    // Week 1 contains January 1, with weeks starting on Sunday
+                    // for type 1 and Monday for type 2.
+                    let first_day = if return_type == 2.0 { 1 } else { 0 };
+                    let jan_1 =
+                        date_to_serial(i64::from(year), 1, 1, is_1904).ok_or(ExcelError::Num)?;
+                    let offset = (weekday_index(jan_1, is_1904) + 7 - first_day) % 7;
+
+                    Ok(Value::Number(
+                        ((serial - jan_1 + offset as f64) / 7.0).floor() + 1.0,
+                    ))
+                })
+            }
+            "EDATE" | "EOMONTH" => {
+                check_args(args, 2, 2)?;
+                self.lifted(args, context, &|values| {
+                    let serial = to_date_number(&values[0], is_1904)?;
+                    let months = to_number(&values[1])?.trunc() as i64;
+                    let (year, month, day) = serial_to_ymd(serial, is_1904);
+                    let month = i64::from(month) + months;
+
+                    let result = if name == "EOMONTH" {
+                        date_to_serial(i64::from(year), month + 1, 0, is_1904)
+                    } else {
+                        let last_day =
+                            days_in_month(i64::from(year), month).ok_or(ExcelError::Num)?;
+                        date_to_serial(
+                            i64::from(year),
+                            month,
+                            i64::from(day).min(last_day),
+                            is_1904,
+                        )
+                    };
+
+                    result.map(Value::Number).ok_or(ExcelError::Num)
+                })
+            }
+            "DAYS" => {
+                check_args(args, 2, 2)?;
+                self.lifted(args, context, &|values| {
+                    let end = to_date_number(&values[0], is_1904)?.trunc();
+                    let start = to_date_number(&values[1], is_1904)?.trunc();
+                    Ok(Value::Number(end - start))
+                })
+            }
+            "DATEDIF" => {
+                check_args(args, 3, 3)?;
+                self.lifted(args, context, &|values| {
+                    let start = to_date_number(&values[0], is_1904)?.trunc();
+                    let end = to_date_number(&values[1], is_1904)?.trunc();
+                    let unit = to_text(&values[2])?.to_uppercase();
+                    date_difference(start, end, &unit, is_1904).map(Value::Number)
+                })
+            }
+            "NETWORKDAYS" | "WORKDAY" => self.function_workdays(name == "WORKDAY", args, context),
+            "TODAY" | "NOW" => {
+                check_args(args, 0, 0)?;
+                let now = current_serial(is_1904);
+                if name == "TODAY" {
+                    Ok(Value::Number(now.trunc()))
+                } else {
+                    Ok(Value::Number(now))
+                }
+            }
+
+            _ => Err(EvalError::Unsupported),
+        }
+    }
+
+    // Apply a numeric function to the arguments, lifted over arrays.
+    fn lifted_math(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+        min_args: usize,
+        function: &dyn Fn(&[f64]) -> Result<f64, ExcelError>,
+    ) -> EvalResult<Value> {
+        check_args(args, min_args, min_args.max(3))?;
+
+        self.lifted(args, context, &|values| {
+            let mut numbers = Vec::with_capacity(values.len());
+            for value in values {
+                numbers.push(to_number(value)?);
+            }
+
+            let result = function(&numbers)?;
+            if result.is_finite() {
+                Ok(Value::Number(result))
+            } else {
+                Err(ExcelError::Num)
+            }
+        })
+    }
+
+    // Count the numbers in a COUNT() argument.
+    fn count_numbers(
+        &mut self,
+        expr: &FormulaExpression,
+        context: &mut Context,
+    ) -> EvalResult<u32> {
+        let value = self.eval(expr, context)?;
+
+        let count = match value {
+            Value::Reference(range) => {
+                let array = self.range_values(&range)?;
+                count_array_numbers(&array)
+            }
+            Value::Array(array) => count_array_numbers(&array),
+            Value::Number(_) | Value::Boolean(_) => 1,
+            Value::String(string) => {
+                u32::from(parse_number_text(&string, self.is_1904_date).is_some())
+            }
+            _ => 0,
+        };
+
+        Ok(count)
+    }
+
+    fn function_if(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        check_args(args, 1, 3)?;
+        let condition = self.arg(args, 0, context)?;
+
+        if let Value::Array(_) = condition {
+            let when_true = self.if_branch(args, 1, true, context)?;
+            let when_false = self.if_branch(args, 2, false, context)?;
+
+            return Ok(map_values(&[condition, when_true, when_false], &|values| {
+                if to_bool(&values[0])? {
+                    Ok(values[1].clone())
+                } else {
+                    Ok(values[2].clone())
+                }
+            }));
+        }
+
+        if to_bool(&condition)? {
+            self.if_branch(args, 1, true, context)
+        } else {
+            self.if_branch(args, 2, false, context)
+        }
+    }
+
+    // Get an IF() branch value. Missing branches return the condition value
+    // and empty branches return 0.
+    fn if_branch(
+        &mut self,
+        args: &[FormulaExpression],
+        index: usize,
+        condition: bool,
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        match args.get(index) {
+            None => Ok(Value::Boolean(condition)),
+            Some(FormulaExpression::Empty) => Ok(Value::Number(0.0)),
+            Some(expr) => self.eval(expr, context),
+        }
+    }
+
+    fn function_ifs(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        check_args(args, 2, 254)?;
+
+        if !args.len().is_multiple_of(2) {
+            return Err(EvalError::Unsupported);
+        }
+
+        for pair in args.chunks(2) {
+            if to_bool(&self.arg_scalar(pair, 0, context)?)? {
+                return self.eval(&pair[1], context);
+            }
+        }
+
+        Err(ExcelError::NA.into())
+    }
+
+    fn function_switch(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        check_args(args, 3, 254)?;
+        let value = self.arg_scalar(args, 0, context)?;
+
+        if let Value::Error(error) = value {
+            return Err(error.into());
+        }
+
+        let cases = &args[1..];
+        for pair in cases.chunks(2) {
+            if pair.len() == 1 {
+                // The default value.
+                return self.eval(&pair[0], context);
+            }
+
+            let case = self.arg_scalar(pair, 0, context)?;
+            if compare_values(&value, &case)? == Ordering::Equal {
+                return self.eval(&pair[1], context);
+            }
+        }
+
+        Err(ExcelError::NA.into())
+    }
+
+    fn function_let(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        check_args(args, 3, 253)?;
+
+        if args.len().is_multiple_of(2) {
+            return Err(EvalError::Unsupported);
+        }
+
+        let scope_start = context.names.len();
+
+        for pair in args[..args.len() - 1].chunks(2) {
+            let FormulaExpression::Name(name) = &pair[0] else {
+                return Err(EvalError::Unsupported);
+            };
+
+            let value = self.eval(&pair[1], context)?;
+            context.names.push((name.clone(), value));
+        }
+
+        let result = self.eval(&args[args.len() - 1], context);
+        context.names.truncate(scope_start);
+
+        result
+    }
+
+    fn function_sumproduct(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        check_args(args, 1, 255)?;
+        let mut arrays = vec![];
+
+        for index in 0..args.len() {
+            arrays.push(self.arg_array(args, index, context)?);
+        }
+
+        let (rows, cols) = (arrays[0].rows, arrays[0].cols);
+        if arrays
+            .iter()
+            .any(|array| array.rows != rows || array.cols != cols)
+        {
+            return Err(ExcelError::Value.into());
+        }
+
+        let mut sum = 0.0;
+        for index in 0..rows * cols {
+            let mut product = 1.0;
+            for array in &arrays {
+                product *= match &array.values[index] {
+                    Value::Number(number) => *number,
+                    Value::Error(error) => return Err((*error).into()),
+                    _ => 0.0,
+                };
+            }
+            sum += product;
+        }
+
+        Ok(Value::Number(sum))
+    }
+
+    // Handle the COUNTIF(), SUMIFS() and other conditional functions.
+    fn function_conditional(
+        &mut self,
+        name: &str,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        let is_multiple = name.ends_with('S');
+        let is_count = name.starts_with("COUNT");
+
+        // Get the range/criteria pairs and the range to aggregate.
+        let (pairs, value_index) = if is_count {
+            if is_multiple {
+                check_args(args, 2, 254)?;
+            } else {
+                check_args(args, 2, 2)?;
+            }
+            (args, None)
+        } else if is_multiple {
+            check_args(args, 3, 255)?;
+            (&args[1..], Some(0))
+        } else {
+            check_args(args, 2, 3)?;
+            (&args[..2], Some(if args.len() > 2 { 2 } else { 0 }))
+        };
+
+        if pairs.len() % 2 != 0 {
+            return Err(EvalError::Unsupported);
+        }
+
+        let mut conditions = vec![];
+        for pair in pairs.chunks(2) {
+            let range = self.arg_array(pair, 0, context)?;
+            let criteria = Criteria::new(&self.arg_scalar(pair, 1, context)?, self.is_1904_date);
+            conditions.push((range, criteria));
+        }
+
+        let (rows, cols) = (conditions[0].0.rows, conditions[0].0.cols);
+        if conditions
+            .iter()
+            .any(|(range, _)| range.rows != rows || range.cols != cols)
+        {
+            return Err(ExcelError::Value.into());
+        }
+
+        let values = match value_index {
+            Some(index) => Some(self.arg_array(args, index, context)?),
+            None => None,
+        };
+
+        let mut count = 0;
+        let mut numbers = vec![];
+
+        for row in 0..rows {
+            for col in 0..cols {
+                if !conditions
+                    .iter()
+                    .all(|(range, criteria)| criteria.matches(range.get(row, col)))
+                {
+                    continue;
+                }
+
+                count += 1;
+
+                if let Some(values) = &values {
+                    match values.get(row, col) {
+                        Value::Number(number) => numbers.push(*number),
+                        Value::Error(error) => return Err((*error).into()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let result = match name {
+            "COUNTIF" | "COUNTIFS" => f64::from(count),
+            "SUMIF" | "SUMIFS" => numbers.iter().sum(),
+            "AVERAGEIF" | "AVERAGEIFS" => {
+                if numbers.is_empty() {
+                    return Err(ExcelError::Div0.into());
+                }
+                numbers.iter().sum::<f64>() / numbers.len() as f64
+            }
+            "MAXIFS" => numbers.iter().copied().reduce(f64::max).unwrap_or(0.0),
+            _ => numbers.iter().copied().reduce(f64::min).unwrap_or(0.0),
+        };
+
+        Ok(Value::Number(result))
+    }
+
+    fn function_vlookup(
+        &mut self,
+        is_horizontal: bool,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        check_args(args, 3, 4)?;
+        let lookup = self.arg_scalar(args, 0, context)?;
+        let table = self.arg_array(args, 1, context)?;
+        let index = to_number(&self.arg_scalar(args, 2, context)?)?.trunc();
+        let is_approximate = self.arg_bool(args, 3, true, context)?;
+
+        if let Value::Error(error) = lookup {
+            return Err(error.into());
+        }
+
+        let table = if is_horizontal {
+            table.transpose()
+        } else {
+            table
+        };
+
+        if index < 1.0 {
+            return Err(ExcelError::Value.into());
+        }
+        if index > table.cols as f64 {
+            return Err(ExcelError::Ref.into());
+        }
+
+        let keys = table.col(0);
+        let row = if is_approximate {
+            approximate_match(&lookup, &keys, MatchType::NextSmaller)
+        } else {
+            exact_match(&lookup, &keys, true, false)
+        };
+
+        match row {
+            Some(row) => Ok(table.get(row, index as usize - 1).clone()),
+            None => Err(ExcelError::NA.into()),
+        }
+    }
+
+    fn function_match(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        check_args(args, 2, 3)?;
+        let lookup = self.arg_scalar(args, 0, context)?;
+        let array = self.arg_array(args, 1, context)?;
+        let match_type = self.arg_number(args, 2, 1.0, context)?;
+
+        if let Value::Error(error) = lookup {
+            return Err(error.into());
+        }
+        if !array.is_vector() {
+            return Err(ExcelError::NA.into());
+        }
+
+        let position = if match_type == 0.0 {
+            exact_match(&lookup, &array.values, true, false)
+        } else if match_type > 0.0 {
+            approximate_match(&lookup, &array.values, MatchType::NextSmaller)
+        } else {
+            approximate_match(&lookup, &array.values, MatchType::NextLarger)
+        };
+
+        position
+            .map(|position| Value::Number(position as f64 + 1.0))
+            .ok_or_else(|| ExcelError::NA.into())
+    }
+
+    fn function_xlookup(
+        &mut self,
+        is_xmatch: bool,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        let option_index = if is_xmatch {
+            check_args(args, 2, 4)?;
+            2
+        } else {
+            check_args(args, 3, 6)?;
+            4
+        };
+
+        let lookup = self.arg_scalar(args, 0, context)?;
+        let lookup_array = self.arg_array(args, 1, context)?;
+        let match_mode = self.arg_number(args, option_index, 0.0, context)?;
+        let search_mode = self.arg_number(args, option_index + 1, 1.0, context)?;
+
+        if let Value::Error(error) = lookup {
+            return Err(error.into());
+        }
+        if !lookup_array.is_vector() {
+            return Err(ExcelError::Value.into());
+        }
+
+        let is_reverse = search_mode < 0.0;
+        let mut keys = lookup_array.values.clone();
+        if is_reverse {
+            keys.reverse();
+        }
+
+        let position = match match_mode as i64 {
+            0 => exact_match(&lookup, &keys, false, false),
+            2 => exact_match(&lookup, &keys, true, false),
+            -1 => exact_match(&lookup, &keys, false, false)
+                .or_else(|| nearest_match(&lookup, &keys, MatchType::NextSmaller)),
+            1 => exact_match(&lookup, &keys, false, false)
+                .or_else(|| nearest_match(&lookup, &keys, MatchType::NextLarger)),
+            _ => return Err(ExcelError::Value.into()),
+        };
+
+        let position = position.map(|position| {
+            if is_reverse {
+                keys.len() - 1 - position
+            } else {
+                position
+            }
+        });
+
+        if is_xmatch {
+            return position
+                .map(|position| Value::Number(position as f64 + 1.0))
+                .ok_or_else(|| ExcelError::NA.into());
+        }
+
+        let Some(position) = position else {
+            return match args.get(3) {
+                None | Some(FormulaExpression::Empty) => Err(ExcelError::NA.into()),
+                Some(expr) => self.eval(expr, context),
+            };
+        };
+
+        let return_array = self.arg_array(args, 2, context)?;
+
+        // Return the matching row or column of the return array.
+        let result = if lookup_array.cols == 1 && lookup_array.rows > 1 {
+            if position >= return_array.rows {
+                return Err(ExcelError::Value.into());
+            }
+            Array::from_rows(vec![return_array.row(position)])
+        } else {
+            if position >= return_array.cols {
+                return Err(ExcelError::Value.into());
+            }
+            Array::from_rows(
+                return_array
+                    .col(position)
+                    .into_iter()
+                    .map(|value| vec![value])
+                    .collect(),
+            )
+        };
+
+        Ok(result.into_value())
+    }
+
+    fn function_index(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        check_args(args, 2, 3)?;
+        let array = self.arg_array(args, 0, context)?;
+        let row = self.arg_number(args, 1, 0.0, context)?.trunc();
+        let col = self.arg_number(args, 2, 0.0, context)?.trunc();
+
+        if row < 0.0 || col < 0.0 {
+            return Err(ExcelError::Value.into());
+        }
+
+        // A single row array can be indexed by the column in the row argument.
+        let (row, col) = if array.rows == 1 && args.len() == 2 {
+            (1.0, row)
+        } else {
+            (row, col)
+        };
+
+        if row > array.rows as f64 || col > array.cols as f64 {
+            return Err(ExcelError::Ref.into());
+        }
+
+        let result = match (row as usize, col as usize) {
+            (0, 0) => array,
+            (0, col) => Array::from_rows(
+                array
+                    .col(col - 1)
+                    .into_iter()
+                    .map(|value| vec![value])
+                    .collect(),
+            ),
+            (row, 0) => Array::from_rows(vec![array.row(row - 1)]),
+            (row, col) => return Ok(array.get(row - 1, col - 1).clone()),
+        };
+
+        Ok(result.into_value())
+    }
+
+    fn function_filter(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        check_args(args, 2, 3)?;
+        let array = self.arg_array(args, 0, context)?;
+        let include = self.arg_array(args, 1, context)?;
+
+        let by_rows = include.cols == 1 && include.rows == array.rows;
+        let by_cols = include.rows == 1 && include.cols == array.cols;
+
+        if !by_rows && !by_cols {
+            return Err(ExcelError::Value.into());
+        }
+
+        let mut keep = vec![];
+        for value in &include.values {
+            if let Value::Error(error) = value {
+                return Err((*error).into());
+            }
+            keep.push(to_bool(value).unwrap_or(false));
+        }
+
+        let array = if by_rows { array } else { array.transpose() };
+        let rows: Vec<Vec<Value>> = (0..array.rows)
+            .filter(|row| keep[*row])
+            .map(|row| array.row(row))
+            .collect();
+
+        if rows.is_empty() {
+            return match args.get(2) {
+                None | Some(FormulaExpression::Empty) => Err(ExcelError::Calc.into()),
+                Some(expr) => self.eval(expr, context),
+            };
+        }
+
+        let array = Array::from_rows(rows);
+        if by_rows {
+            Ok(array.into_value())
+        } else {
+            Ok(array.transpose().into_value())
+        }
+    }
+
+    fn function_sort(
+        &mut self,
+        is_sortby: bool,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        let array = if is_sortby {
+            check_args(args, 2, 255)?;
+            self.arg_array(args, 0, context)?
+        } else {
+            check_args(args, 1, 4)?;
+            self.arg_array(args, 0, context)?
+        };
+
+        // Get the sort keys as (values, is_descending) pairs.
+        let mut by_col = false;
+        let mut keys = vec![];
+
+        if is_sortby {
+            for pair in args[1..].chunks(2) {
+                let by = self.arg_array(pair, 0, context)?;
+                let order = self.arg_number(pair, 1, 1.0, context)?;
+
+                if by.cols == 1 && by.rows == array.rows {
+                    keys.push((by.values, order < 0.0));
+                } else if by.rows == 1 && by.cols == array.cols {
+                    by_col = true;
+                    keys.push((by.values, order < 0.0));
+                } else {
+                    return Err(ExcelError::Value.into());
+                }
+            }
+        } else {
+            let index = self.arg_number(args, 1, 1.0, context)?.trunc();
+            let order = self.arg_number(args, 2, 1.0, context)?;
+            by_col = self.arg_bool(args, 3, false, context)?;
+
+            let source = if by_col {
+                array.transpose()
+            } else {
+                array.clone()
+            };
+            if index < 1.0 || index > source.cols as f64 {
+                return Err(ExcelError::Value.into());
+            }
+
+            keys.push((source.col(index as usize - 1), order < 0.0));
+        }
+
+        let array = if by_col { array.transpose() } else { array };
+        let mut order: Vec<usize> = (0..array.rows).collect();
+
+        order.sort_by(|a, b| {
+            for (values, is_descending) in &keys {
+                let ordering = sort_order(&values[*a], &values[*b]);
+                let ordering = if *is_descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            Ordering::Equal
+        });
+
+        let array = Array::from_rows(order.into_iter().map(|row| array.row(row)).collect());
+        if by_col {
+            Ok(array.transpose().into_value())
+        } else {
+            Ok(array.into_value())
+        }
+    }
+
+    fn function_unique(
+        &mut self,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        check_args(args, 1, 3)?;
+        let array = self.arg_array(args, 0, context)?;
+        let by_col = self.arg_bool(args, 1, false, context)?;
+        let exactly_once = self.arg_bool(args, 2, false, context)?;
+
+        let array = if by_col { array.transpose() } else { array };
+        let rows: Vec<Vec<Value>> = (0..array.rows).map(|row| array.row(row)).collect();
+
+        let same_row = |a: &[Value], b: &[Value]| {
+            a.iter()
+                .zip(b)
+                .all(|(a, b)| matches!(compare_values(a, b), Ok(Ordering::Equal)))
+        };
+
+        let mut unique: Vec<(Vec<Value>, usize)> = vec![];
+        for row in rows {
+            match unique.iter_mut().find(|(other, _)| same_row(other, &row)) {
+                Some((_, count)) => *count += 1,
+                None => unique.push((row, 1)),
+            }
+        }
+
+        let rows: Vec<Vec<Value>> = unique
+            .into_iter()
+            .filter(|(_, count)| !exactly_once || *count == 1)
+            .map(|(row, _)| row)
+            .collect();
+
+        if rows.is_empty() {
+            return Err(ExcelError::Calc.into());
+        }
+
+        let array = Array::from_rows(rows);
+        if by_col {
+            Ok(array.transpose().into_value())
+        } else {
+            Ok(array.into_value())
+        }
+    }
+
+    fn function_workdays(
+        &mut self,
+        is_workday: bool,
+        args: &[FormulaExpression],
+        context: &mut Context,
+    ) -> EvalResult<Value> {
+        check_args(args, 2, 3)?;
+        let is_1904 = self.is_1904_date;
+        let start = to_date_number(&self.arg_scalar(args, 0, context)?, is_1904)?.trunc();
+        let second = to_number(&self.arg_scalar(args, 1, context)?)?.trunc();
+
+        let mut holidays = vec![];
+        if args.len() > 2 {
+            for value in self.collect_values(&args[2..], context)? {
+                match value {
+                    Value::Blank => {}
+                    value => holidays.push(to_date_number(&value, is_1904)?.trunc()),
+                }
+            }
+        }
+
+        let is_working_day = |serial: f64| {
+            let weekday = weekday_index(serial, is_1904);
+            weekday != 0 && weekday != 6 && !holidays.contains(&serial)
+        };
+
+        if is_workday {
+            let step = if second < 0.0 { -1.0 } else { 1.0 };
+            let mut remaining = second.abs();
+            let mut serial = start;
+
+            while remaining > 0.0 {
+                serial += step;
+                if is_working_day(serial) {
+                    remaining -= 1.0;
+                }
+            }
+
+            return Ok(Value::Number(serial));
+        }
+
+        let (first, last, sign) = if start <= second {
+            (start, second, 1.0)
+        } else {
+            (second, start, -1.0)
+        };
+
+        let mut count = 0.0;
+        let mut serial = first;
+        while serial <= last {
+            if is_working_day(serial) {
+                count += 1.0;
+            }
+            serial += 1.0;
+        }
+
+        Ok(Value::Number(count * sign))
+    }
+}
+
+// -----------------------------------------------------------------------
+// Value helper functions.
+// -----------------------------------------------------------------------
+
+// Check the number of function arguments. Excel doesn't allow a formula with
+// the wrong number of arguments so we don't try to calculate it.
+fn check_args(args: &[FormulaExpression], min: usize, max: usize) -> EvalResult<()> {
+    if args.len() < min || args.len() > max {
+        Err(EvalError::Unsupported)
+    } else {
+        Ok(())
+    }
+}
+
+// Apply a scalar function to a set of values, broadcasting it over any
+// arrays.
+fn map_values(values: &[Value], function: &dyn Fn(&[Value]) -> Result<Value, ExcelError>) -> Value {
+    let call = |values: &[Value]| function(values).unwrap_or_else(Value::Error);
+
+    let arrays: Vec<&Array> = values
+        .iter()
+        .filter_map(|value| match value {
+            Value::Array(array) => Some(array),
+            _ => None,
+        })
+        .collect();
+
+    if arrays.is_empty() {
+        return call(values);
+    }
+
+    let rows = arrays.iter().map(|array| array.rows).max().unwrap_or(1);
+    let cols = arrays.iter().map(|array| array.cols).max().unwrap_or(1);
+    let mut results = Vec::with_capacity(rows * cols);
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let scalars: Vec<Value> = values
+                .iter()
+                .map(|value| match value {
+                    Value::Array(array) => array.broadcast(row, col),
+                    value => value.clone(),
+                })
+                .collect();
+
+            results.push(call(&scalars));
+        }
+    }
+
+    Array::new(rows, cols, results).into_value()
+}
+
+// Apply an Excel binary operator to two scalar values.
+fn binary_operation(
+    op: FormulaBinaryOperator,
+    left: &Value,
+    right: &Value,
+) -> Result<Value, ExcelError> {
+    if let Value::Error(error) = left {
+        return Err(*error);
+    }
+    if let Value::Error(error) = right {
+        return Err(*error);
+    }
+
+    let result = match op {
+        FormulaBinaryOperator::Concat => Value::String(to_text(left)? + &to_text(right)?),
+        FormulaBinaryOperator::Equal => {
+            Value::Boolean(compare_values(left, right)? == Ordering::Equal)
+        }
+        FormulaBinaryOperator::NotEqual => {
+            Value::Boolean(compare_values(left, right)? != Ordering::Equal)
+        }
+        FormulaBinaryOperator::Less => {
+            Value::Boolean(compare_values(left, right)? == Ordering::Less)
+        }
+        FormulaBinaryOperator::LessEqual => {
+            Value::Boolean(compare_values(left, right)? != Ordering::Greater)
+        }
+        FormulaBinaryOperator::Greater => {
+            Value::Boolean(compare_values(left, right)? == Ordering::Greater)
+        }
+        FormulaBinaryOperator::GreaterEqual => {
+            Value::Boolean(compare_values(left, right)? != Ordering::Less)
+        }
+        _ => {
+            let left = to_number(left)?;
+            let right = to_number(right)?;
+
+            let result = match op {
+                FormulaBinaryOperator::Add => left + right,
+                FormulaBinaryOperator::Subtract => left - right,
+                FormulaBinaryOperator::Multiply => left * right,
+                FormulaBinaryOperator::Divide => {
+                    if right == 0.0 {
+                        return Err(ExcelError::Div0);
+                    }
+                    left / right
+                }
+                _ => power(left, right)?,
+            };
+
+            if !result.is_finite() {
+                return Err(ExcelError::Num);
+            }
+
+            Value::Number(result)
+        }
+    };
+
+    Ok(result)
+}
+
+fn power(base: f64, exponent: f64) -> Result<f64, ExcelError> {
+    if base == 0.0 && exponent == 0.0 {
+        return Err(ExcelError::Num);
+    }
+    if base == 0.0 && exponent < 0.0 {
+        return Err(ExcelError::Div0);
+    }
+
+    let result = base.powf(exponent);
+    if result.is_finite() {
+        Ok(result)
+    } else {
+        Err(ExcelError::Num)
+    }
+}
+
+// Compare two scalar values using the Excel rules. Numbers are less than
+// strings which are less than booleans. Strings are compared case
+// insensitively and blank cells are equal to 0, "" and FALSE.
+fn compare_values(left: &Value, right: &Value) -> Result<Ordering, ExcelError> {
+    let rank = |value: &Value| match value {
+        Value::Number(_) => 0,
+        Value::String(_) => 1,
+        Value::Boolean(_) => 2,
+        _ => 3,
+    };
+
+    let ordering = match (left, right) {
+        (Value::Error(error), _) | (_, Value::Error(error)) => return Err(*error),
+        (Value::Blank, Value::Blank) => Ordering::Equal,
+        (Value::Blank, other) => compare_values(&blank_like(other), other)?,
+        (other, Value::Blank) => compare_values(other, &blank_like(other))?,
+        (Value::Number(left), Value::Number(right)) => left.total_cmp(right),
+        (Value::String(left), Value::String(right)) => {
+            left.to_lowercase().cmp(&right.to_lowercase())
+        }
+        (Value::Boolean(left), Value::Boolean(right)) => left.cmp(right),
+        (left, right) => rank(left).cmp(&rank(right)),
+    };
+
+    Ok(ordering)
+}
+
+// Get the value of a blank cell in the same type as another value.
+fn blank_like(value: &Value) -> Value {
+    match value {
+        Value::String(_) => Value::String(String::new()),
+        Value::Boolean(_) => Value::Boolean(false),
+        _ => Value::Number(0.0),
+    }
+}
+
+// Get the ordering used by SORT(), where blanks and errors sort last.
+fn sort_order(left: &Value, right: &Value) -> Ordering {
+    match compare_values(left, right) {
+        Ok(ordering) => ordering,
+        Err(_) => {
+            let is_error = |value: &Value| matches!(value, Value::Error(_));
+            is_error(left).cmp(&is_error(right))
+        }
+    }
+}
+
+// Convert a value to a number using the Excel coercion rules.
+fn to_number(value: &Value) -> Result<f64, ExcelError> {
+    match value {
+        Value::Number(number) => Ok(*number),
+        Value::Boolean(boolean) => Ok(f64::from(u8::from(*boolean))),
+        Value::Blank => Ok(0.0),
+        Value::String(string) => parse_number_text(string, false).ok_or(ExcelError::Value),
+        Value::Error(error) => Err(*error),
+        Value::Array(array) => to_number(array.get(0, 0)),
+        Value::Reference(_) => Err(ExcelError::Value),
+    }
+}
+
+// Convert a value to a date serial number, allowing date strings.
+fn to_date_number(value: &Value, is_1904_date: bool) -> Result<f64, ExcelError> {
+    let number = match value {
+        Value::String(string) => {
+            parse_number_text(string, is_1904_date).ok_or(ExcelError::Value)?
+        }
+        value => to_number(value)?,
+    };
+
+    if number < 0.0 {
+        Err(ExcelError::Num)
+    } else {
+        Ok(number)
+    }
+}
+
+// Convert a value to a string using the Excel coercion rules.
+fn to_text(value: &Value) -> Result<String, ExcelError> {
+    match value {
+        Value::Number(number) => Ok(number_to_text(*number)),
+        Value::Boolean(boolean) => Ok(bool_to_string(*boolean).to_string()),
+        Value::Blank => Ok(String::new()),
+        Value::String(string) => Ok(string.clone()),
+        Value::Error(error) => Err(*error),
+        Value::Array(array) => to_text(array.get(0, 0)),
+        Value::Reference(_) => Err(ExcelError::Value),
+    }
+}
+
+// Convert a value to a boolean using the Excel coercion rules.
+fn to_bool(value: &Value) -> Result<bool, ExcelError> {
+    match value {
+        Value::Number(number) => Ok(*number != 0.0),
+        Value::Boolean(boolean) => Ok(*boolean),
+        Value::Blank => Ok(false),
+        Value::String(string) if string.eq_ignore_ascii_case("TRUE") => Ok(true),
+        Value::String(string) if string.eq_ignore_ascii_case("FALSE") => Ok(false),
+        Value::String(_) | Value::Reference(_) => Err(ExcelError::Value),
+        Value::Error(error) => Err(*error),
+        Value::Array(array) => to_bool(array.get(0, 0)),
+    }
+}
+
+fn bool_to_string(boolean: bool) -> &'static str {
+    if boolean {
+        "TRUE"
+    } else {
+        "FALSE"
+    }
+}
+
+fn collect_array_numbers(array: &Array, numbers: &mut Vec<f64>) -> Result<(), ExcelError> {
+    for value in &array.values {
+        match value {
+            Value::Number(number) => numbers.push(*number),
+            Value::Error(error) => return Err(*error),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn count_array_numbers(array: &Array) -> u32 {
+    array
+        .values
+        .iter()
+        .filter(|value| matches!(value, Value::Number(_)))
+        .count() as u32
+}
+
+// Convert a number to a string in the same way as Excel, with up to 15
+// significant digits.
+fn number_to_text(number: f64) -> String {
+    if number == 0.0 {
+        return "0".to_string();
+    }
+
+    let scientific = format!("{number:.14e}");
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    let is_negative = mantissa.starts_with('-');
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    let digits = digits.trim_end_matches('0');
+    let sign = if is_negative { "-" } else { "" };
+
+    if !(-10..21).contains(&exponent) {
+        let (first, rest) = digits.split_at(1);
+        let fraction = if rest.is_empty() {
+            String::new()
+        } else {
+            format!(".{rest}")
+        };
+        let exponent_sign = if exponent < 0 { '-' } else { '+' };
+        return format!(
+            "{sign}{first}{fraction}E{exponent_sign}{:02}",
+            exponent.abs()
+        );
+    }
+
+    let point = exponent + 1;
+    let text = if point <= 0 {
+        format!("0.{}{}", "0".repeat((-point) as usize), digits)
+    } else if point as usize >= digits.len() {
+        format!("{}{}", digits, "0".repeat(point as usize - digits.len()))
+    } else {
+        format!(
+            "{}.{}",
+            &digits[..point as usize],
+            &digits[point as usize..]
+        )
+    };
+
+    format!("{sign}{text}")
+}
+
+// Parse a string to a number using the Excel coercion rules. This includes
+// percentages and date/time strings.
+fn parse_number_text(text: &str, is_1904_date: bool) -> Option<f64> {
+    let text = text.trim();
+
+    if text.is_empty() {
+        return None;
+    }
+
+    if let Some(number) = text.strip_suffix('%') {
+        return parse_number_text(number, is_1904_date).map(|number| number / 100.0);
+    }
+
+    let is_numeric = text
+        .chars()
+        .all(|char| char.is_ascii_digit() || matches!(char, '.' | '-' | '+' | 'e' | 'E' | ','));
+
+    if is_numeric {
+        let number = if text.contains(',') {
+            text.replace(',', "")
+        } else {
+            text.to_string()
+        };
+
+        if let Ok(number) = number.parse::<f64>() {
+            return Some(number);
+        }
+    }
+
+    parse_date_text(text, is_1904_date)
+}
+
+// Round a number to 15 significant digits, to match Excel's precision.
+fn round_significant(number: f64) -> f64 {
+    if number == 0.0 || !number.is_finite() {
+        return number;
+    }
+
+    format!("{number:.14e}").parse().unwrap_or(number)
+}
+
+// Round a number to a number of digits using a rounding function.
+fn round_with(number: f64, digits: f64, function: fn(f64) -> f64) -> f64 {
+    let digits = digits.trunc().clamp(-15.0, 15.0) as i32;
+    let scale = 10_f64.powi(digits.abs());
+
+    if digits >= 0 {
+        function(round_significant(number * scale)) / scale
+    } else {
+        function(round_significant(number / scale)) * scale
+    }
+}
+
+fn sign_or_one(number: f64) -> f64 {
+    if number < 0.0 {
+        -1.0
+    } else {
+        1.0
+    }
+}
+
+// Capitalize the first letter of each word.
+fn proper_case(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut is_word_start = true;
+
+    for char in text.chars() {
+        if is_word_start {
+            result.extend(char.to_uppercase());
+        } else {
+            result.extend(char.to_lowercase());
+        }
+        is_word_start = !char.is_alphabetic();
+    }
+
+    result
+}
+
+// Find a sub-string, case sensitively, returning the char position.
+fn find_chars(haystack: &[char], needle: &[char], start: usize) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(start);
+    }
+
+    (start..=haystack.len().saturating_sub(needle.len()))
+        .find(|index| haystack[*index..].starts_with(needle))
+}
+
+// Find a sub-string, case insensitively and with wildcards, returning the
+// char position.
+fn search_chars(haystack: &[char], needle: &str, start: usize) -> Option<usize> {
+    let pattern = Wildcard::new(&format!("{needle}*"));
+
+    (start..=haystack.len()).find(|index| {
+        let text: String = haystack[*index..].iter().collect();
+        pattern.matches(&text)
+    })
+}
+
+// -----------------------------------------------------------------------
+// Criteria and lookup matching.
+// -----------------------------------------------------------------------
+
+// A simple case insensitive wildcard pattern using the Excel "*", "?" and
+// "~" escape characters.
+struct Wildcard {
+    tokens: Vec<WildcardToken>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum WildcardToken {
+    Char(char),
+    Any,
+    AnySequence,
+}
+
+impl Wildcard {
+    fn new(pattern: &str) -> Wildcard {
+        let mut tokens = vec![];
+        let mut chars = pattern.chars();
+
+        while let Some(char) = chars.next() {
+            let token = match char {
+                '*' => WildcardToken::AnySequence,
+                '?' => WildcardToken::Any,
+                '~' => match chars.next() {
+                    Some(char) => WildcardToken::Char(char),
+                    None => WildcardToken::Char('~'),
+                },
+                char => WildcardToken::Char(char),
+            };
+
+            tokens.extend(match token {
+                WildcardToken::Char(char) => char.to_lowercase().map(WildcardToken::Char).collect(),
+                token => vec![token],
+            });
+        }
+
+        Wildcard { tokens }
+    }
+
+    fn has_wildcards(pattern: &str) -> bool {
+        pattern.contains(['*', '?', '~'])
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+        let tokens = &self.tokens;
+
+        // Dynamic programming match of the tokens against the text.
+        let mut matched = vec![false; text.len() + 1];
+        matched[0] = true;
+
+        for token in tokens {
+            let mut next = vec![false; text.len() + 1];
+
+            for index in 0..=text.len() {
+                match token {
+                    WildcardToken::AnySequence => {
+                        next[index] = matched[index] || (index > 0 && next[index - 1]);
+                    }
+                    WildcardToken::Any => {
+                        next[index] = index > 0 && matched[index - 1];
+                    }
+                    WildcardToken::Char(char) => {
+                        next[index] = index > 0 && matched[index - 1] && text[index - 1] == *char;
+                    }
+                }
+            }
+
+            matched = next;
+        }
+
+        matched[text.len()]
+    }
+}
+
+// The criteria used by the COUNTIF() family of functions.
+struct Criteria {
+    op: FormulaBinaryOperator,
+    value: Value,
+    pattern: Option<Wildcard>,
+}
+
+impl Criteria {
+    fn new(criteria: &Value, is_1904_date: bool) -> Criteria {
+        let Value::String(text) = criteria else {
+            return Criteria {
+                op: FormulaBinaryOperator::Equal,
+                value: criteria.clone(),
+                pattern: None,
+            };
+        };
+
+        let (op, operand) = [
+            ("<=", FormulaBinaryOperator::LessEqual),
+            (">=", FormulaBinaryOperator::GreaterEqual),
+            ("<>", FormulaBinaryOperator::NotEqual),
+            ("<", FormulaBinaryOperator::Less),
+            (">", FormulaBinaryOperator::Greater),
+            ("=", FormulaBinaryOperator::Equal),
+        ]
+        .into_iter()
+        .find_map(|(prefix, op)| text.strip_prefix(prefix).map(|operand| (op, operand)))
+        .unwrap_or((FormulaBinaryOperator::Equal, text.as_str()));
+
+        let value = if operand.is_empty() {
+            Value::Blank
+        } else if let Some(number) = parse_number_text(operand, is_1904_date) {
+            Value::Number(number)
+        } else if operand.eq_ignore_ascii_case("TRUE") {
+            Value::Boolean(true)
+        } else if operand.eq_ignore_ascii_case("FALSE") {
+            Value::Boolean(false)
+        } else {
+            Value::String(operand.to_string())
+        };
+
+        let pattern = match &value {
+            Value::String(string)
+                if matches!(
+                    op,
+                    FormulaBinaryOperator::Equal | FormulaBinaryOperator::NotEqual
+                ) && Wildcard::has_wildcards(string) =>
+            {
+                Some(Wildcard::new(string))
+            }
+            _ => None,
+        };
+
+        Criteria { op, value, pattern }
+    }
+
+    fn matches(&self, value: &Value) -> bool {
+        // Empty criteria match blank cells and empty strings.
+        if self.value.is_blank() {
+            let is_empty = match value {
+                Value::Blank => true,
+                Value::String(string) => string.is_empty(),
+                _ => false,
+            };
+
+            return match self.op {
+                FormulaBinaryOperator::Equal => is_empty,
+                FormulaBinaryOperator::NotEqual => !is_empty,
+                _ => false,
+            };
+        }
+
+        if let Some(pattern) = &self.pattern {
+            let is_match = matches!(value, Value::String(string) if pattern.matches(string));
+            return is_match == (self.op == FormulaBinaryOperator::Equal);
+        }
+
+        // Values only match criteria of the same type.
+        let is_same_type = matches!(
+            (value, &self.value),
+            (Value::Number(_), Value::Number(_))
+                | (Value::String(_), Value::String(_))
+                | (Value::Boolean(_), Value::Boolean(_))
+        );
+
+        if !is_same_type {
+            return self.op == FormulaBinaryOperator::NotEqual;
+        }
+
+        let Ok(ordering) = compare_values(value, &self.value) else {
+            return false;
+        };
+
+        match self.op {
+            FormulaBinaryOperator::Equal => ordering == Ordering::Equal,
+            FormulaBinaryOperator::NotEqual => ordering != Ordering::Equal,
+            FormulaBinaryOperator::Less => ordering == Ordering::Less,
+            FormulaBinaryOperator::LessEqual => ordering != Ordering::Greater,
+            FormulaBinaryOperator::Greater => ordering == Ordering::Greater,
+            _ => ordering != Ordering::Less,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum MatchType {
+    NextSmaller,
+    NextLarger,
+}
+
+// Find the first exact match for a lookup value, optionally with wildcards.
+fn exact_match(lookup: &Value, values: &[Value], use_wildcards: bool, _: bool) -> Option<usize> {
+    if let Value::String(string) = lookup {
+        if use_wildcards && Wildcard::has_wildcards(string) {
+            let pattern = Wildcard::new(string);
+            return values
+                .iter()
+                .position(|value| matches!(value, Value::String(text) if pattern.matches(text)));
+        }
+    }
+
+    values.iter().position(|value| {
+        same_type(lookup, value) && matches!(compare_values(lookup, value), Ok(Ordering::Equal))
+    })
+}
+
+// Find an approximate match in sorted data, like the MATCH() and VLOOKUP()
+// default modes. This returns the last position of the largest value that is
+// less than or equal to the lookup value, or the reverse for NextLarger.
+fn approximate_match(lookup: &Value, values: &[Value], match_type: MatchType) -> Option<usize> {
+    let mut position = None;
+
+    for (index, value) in values.iter().enumerate() {
+        if !same_type(lookup, value) {
+            continue;
+        }
+
+        let Ok(ordering) = compare_values(value, lookup) else {
+            continue;
+        };
+
+        let is_candidate = match match_type {
+            MatchType::NextSmaller => ordering != Ordering::Greater,
+            MatchType::NextLarger => ordering != Ordering::Less,
+        };
+
+        if is_candidate {
+            position = Some(index);
+        } else {
+            break;
+        }
+    }
+
+    position
+}
+
+// Find the nearest value that is smaller or larger than the lookup value in
+// unsorted data, like the XLOOKUP() match modes.
+fn nearest_match(lookup: &Value, values: &[Value], match_type: MatchType) -> Option<usize> {
+    let mut best: Option<usize> = None;
+
+    for (index, value) in values.iter().enumerate() {
+        if !same_type(lookup, value) {
+            continue;
+        }
+
+        let Ok(ordering) = compare_values(value, lookup) else {
+            continue;
+        };
+
+        let is_candidate = match match_type {
+            MatchType::NextSmaller => ordering == Ordering::Less,
+            MatchType::NextLarger => ordering == Ordering::Greater,
+        };
+
+        if !is_candidate {
+            continue;
+        }
+
+        let is_better = match best {
+            None => true,
+            Some(best) => matches!(
+                (compare_values(value, &values[best]), match_type),
+                (Ok(Ordering::Greater), MatchType::NextSmaller)
+                    | (Ok(Ordering::Less), MatchType::NextLarger)
+            ),
+        };
+
+        if is_better {
+            best = Some(index);
+        }
+    }
+
+    best
+}
+
+fn same_type(left: &Value, right: &Value) -> bool {
+    std::mem::discriminant(left) == std::mem::discriminant(right)
+}
+
+// -----------------------------------------------------------------------
+// Date helper functions.
+// -----------------------------------------------------------------------
+
+// Convert a year, month and day to an Excel serial date. Out of range months
+// and days are normalized in the same way as the Excel DATE() function. The
+// serial date of the first day of the month is calculated by ExcelDateTime so
+// that the 1900 false leap day and the 1904 epoch are handled in the same way
+// as written dates.
+fn date_to_serial(year: i64, month: i64, day: i64, is_1904_date: bool) -> Option<f64> {
+    let year = year + (month - 1).div_euclid(12);
+    let month = (month - 1).rem_euclid(12) + 1;
+
+    let first_day = ExcelDateTime::from_ymd(u16::try_from(year).ok()?, month as u8, 1).ok()?;
+    let serial = first_day.to_excel() + (day - 1) as f64;
+
+    // Check the range from 1900-01-00 to 9999-12-31.
+    if !(0.0..=2_958_465.0).contains(&serial) {
+        return None;
+    }
+
+    if is_1904_date {
+        // Dates before 1904-01-01 aren't valid in the 1904 epoch.
+        let serial_1904 = ExcelDateTime::serial_to_1904_epoch(serial);
+        (serial >= 1.0 && serial_1904 >= 0.0).then_some(serial_1904)
+    } else {
+        Some(serial)
+    }
+}
+
+fn serial_to_ymd(serial: f64, is_1904_date: bool) -> (u16, u8, u8) {
+    let (year, month, day, _, _, _) = ExcelDateTime::serial_to_date_parts(serial, is_1904_date);
+    (year, month, day)
+}
+
+// Get the number of days in a month, including the Excel 1900 false leap day.
+// Out of range months are normalized.
+fn days_in_month(year: i64, month: i64) -> Option<i64> {
+    let days = date_to_serial(year, month + 1, 1, false)? - date_to_serial(year, month, 1, false)?;
+    Some(days as i64)
+}
+
+// Get the day of the week for a serial date, with Sunday as 0.
+fn weekday_index(serial: f64, is_1904_date: bool) -> i64 {
+    let serial = serial.floor() as i64;
+
+    if is_1904_date {
+        // 1904-01-01 was a Friday.
+        (serial + 5).rem_euclid(7)
+    } else {
+        // Excel treats 1900-01-01 as a Sunday.
+        (serial - 1).rem_euclid(7)
+    }
+}
+
+fn iso_week_number(serial: f64, is_1904_date: bool) -> f64 {
+    // Move to the Thursday of the same ISO week.
+    let monday_index = (weekday_index(serial, is_1904_date) + 6) % 7;
+    let thursday = serial - monday_index as f64 + 3.0;
+    let (year, _, _) = serial_to_ymd(thursday, is_1904_date);
+    let jan_1 = date_to_serial(i64::from(year), 1, 1, is_1904_date).unwrap_or(thursday);
+
+    ((thursday - jan_1) / 7.0).floor() + 1.0
+}
+
+// Calculate the DATEDIF() difference between two dates.
+fn date_difference(
+    start: f64,
+    end: f64,
+    unit: &str,
+    is_1904_date: bool,
+) -> Result<f64, ExcelError> {
+    if start > end {
+        return Err(ExcelError::Num);
+    }
+
+    let (year1, month1, day1) = serial_to_ymd(start, is_1904_date);
+    let (year2, month2, day2) = serial_to_ymd(end, is_1904_date);
+    let (year1, month1, day1) = (i64::from(year1), i64::from(month1), i64::from(day1));
+    let (year2, month2, day2) = (i64::from(year2), i64::from(month2), i64::from(day2));
+
+    let mut months = (year2 - year1) * 12 + month2 - month1;
+    if day2 < day1 {
+        months -= 1;
+    }
+
+    let result = match unit {
+        "Y" => months / 12,
+        "M" => months,
+        "D" => (end - start) as i64,
+        "YM" => months % 12,
+        "MD" => {
+            if day2 >= day1 {
+                day2 - day1
+            } else {
+                day2 + days_in_month(year2, month2 - 1).ok_or(ExcelError::Num)? - day1
+            }
+        }
+        "YD" => {
+            let year = if (month2, day2) >= (month1, day1) {
+                year2
+            } else {
+                year2 - 1
+            };
+            let start = date_to_serial(year, month1, day1, is_1904_date).ok_or(ExcelError::Num)?;
+            (end - start) as i64
+        }
+        _ => return Err(ExcelError::Num),
+    };
+
+    Ok(result as f64)
+}
+
+// Parse common date and time strings like "2024-01-31", "1/31/2024",
+// "31-Jan-2024", "12:30" and "2024-01-31 12:30:00" to a serial date.
+fn parse_date_text(text: &str, is_1904_date: bool) -> Option<f64> {
+    let text = text.trim();
+    let (date_part, time_part) = match text.split_once([' ', 'T']) {
+        Some((date, time)) if date.contains(['-', '/']) => (Some(date), Some(time.trim())),
+        _ if text.contains(':') => (None, Some(text)),
+        _ => (Some(text), None),
+    };
+
+    let mut serial = 0.0;
+
+    if let Some(date) = date_part {
+        let parts: Vec<&str> = date.split(['-', '/']).collect();
+        if parts.len() != 3 {
+            return None;
+        }
+
+        let month_from_name = |name: &str| {
+            const MONTHS: [&str; 12] = [
+                "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+            ];
+            let name = name.to_lowercase();
+            MONTHS
+                .iter()
+                .position(|month| name.starts_with(month))
+                .map(|index| index as i64 + 1)
+        };
+
+        let numbers: Vec<Option<i64>> = parts.iter().map(|part| part.parse().ok()).collect();
+
+        let (year, month, day) = match (numbers[0], numbers[1], numbers[2]) {
+            // ISO 8601 style: yyyy-mm-dd.
+            (Some(year), Some(month), Some(day)) if parts[0].len() == 4 => (year, month, day),
+            // US style: mm/dd/yyyy.
+            (Some(month), Some(day), Some(year)) => (year, month, day),
+            // dd-mmm-yyyy.
+            (Some(day), None, Some(year)) => (year, month_from_name(parts[1])?, day),
+            _ => return None,
+        };
+
+        let year = if year < 30 {
+            year + 2000
+        } else if year < 100 {
+            year + 1900
+        } else {
+            year
+        };
+
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month)? {
+            return None;
+        }
+
+        serial = date_to_serial(year, month, day, is_1904_date)?;
+    }
+
+    if let Some(time) = time_part {
+        let upper = time.to_uppercase();
+        let (time, meridian) = if let Some(time) = upper.strip_suffix("AM") {
+            (time.trim().to_string(), Some(false))
+        } else if let Some(time) = upper.strip_suffix("PM") {
+            (time.trim().to_string(), Some(true))
+        } else {
+            (upper, None)
+        };
+
+        let parts: Vec<&str> = time.split(':').collect();
+        if parts.len() < 2 || parts.len() > 3 {
+            return None;
+        }
+
+        let mut hour: f64 = parts[0].parse().ok()?;
+        let minute: f64 = parts[1].parse().ok()?;
+        let second: f64 = match parts.get(2) {
+            Some(second) => second.parse().ok()?,
+            None => 0.0,
+        };
+
+        match meridian {
+            Some(is_pm) => {
+                if !(1.0..=12.0).contains(&hour) {
+                    return None;
+                }
+                hour %= 12.0;
+                if is_pm {
+                    hour += 12.0;
+                }
+            }
+            None if hour >= 24.0 => return None,
+            None => {}
+        }
+
+        if minute >= 60.0 || second >= 60.0 {
+            return None;
+        }
+
+        serial += (hour * 3600.0 + minute * 60.0 + second) / SECONDS_PER_DAY;
+    }
+
+    Some(serial)
+}
+
+// Get the current date and time as a serial date.
+fn current_serial(is_1904_date: bool) -> f64 {
+    let timestamp = ExcelDateTime::system_now() as i64;
+    let serial = ExcelDateTime::from_timestamp(timestamp).map_or(0.0, |now| now.to_excel());
+
+    if is_1904_date {
+        ExcelDateTime::serial_to_1904_epoch(serial)
+    } else {
+        serial
+    }
+}
+
+// -----------------------------------------------------------------------
+// Number formatting for the TEXT() function.
+// -----------------------------------------------------------------------
+
+// Format a number with a simplified version of Excel's number formatting.
+// This supports the common number, percentage, scientific, date and time
+// format codes.
+fn format_number(number: f64, format: &str, is_1904_date: bool) -> String {
+    let sections = split_format_sections(format);
+
+    let (section, number, add_sign) = match sections.len() {
+        0 => return number_to_text(number),
+        1 => (sections[0].as_str(), number, false),
+        2 if number < 0.0 => (sections[1].as_str(), -number, false),
+        2 => (sections[0].as_str(), number, false),
+        _ if number < 0.0 => (sections[1].as_str(), -number, false),
+        _ if number == 0.0 => (sections[2].as_str(), number, false),
+        _ => (sections[0].as_str(), number, false),
+    };
+
+    let _ = add_sign;
+
+    if section.is_empty() || section.eq_ignore_ascii_case("General") {
+        return number_to_text(number);
+    }
+
+    if is_date_format(section) {
+        format_date(number, section, is_1904_date)
+    } else {
+        format_decimal(number, section)
+    }
+}
+
+// Split a format into its ";" separated sections, ignoring quoted strings.
+fn split_format_sections(format: &str) -> Vec<String> {
+    let mut sections = vec![String::new()];
+    let mut in_quotes = false;
+    let mut is_escaped = false;
+
+    for char in format.chars() {
+        if is_escaped {
+            is_escaped = false;
+        } else if char == '\\' {
+            is_escaped = true;
+        } else if char == '"' {
+            in_quotes = !in_quotes;
+        } else if char == ';' && !in_quotes {
+            sections.push(String::new());
+            continue;
+        }
+
+        if let Some(section) = sections.last_mut() {
+            section.push(char);
+        }
+    }
+
+    sections
+}
+
+// Split a format section into format codes and literal strings.
+fn format_tokens(section: &str) -> Vec<(bool, String)> {
+    let mut tokens: Vec<(bool, String)> = vec![];
+    let mut chars = section.chars();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '"' => {
+                let literal: String = chars.by_ref().take_while(|char| *char != '"').collect();
+                tokens.push((true, literal));
+            }
+            '\\' => {
+                if let Some(char) = chars.next() {
+                    tokens.push((true, char.to_string()));
+                }
+            }
+            '_' => {
+                // Padding for the width of the next character.
+                chars.next();
+                tokens.push((true, " ".to_string()));
+            }
+            '*' => {
+                // Repeated fill characters are ignored.
+                chars.next();
+            }
+            '[' => {
+                // Colors and conditions are ignored.
+                for char in chars.by_ref() {
+                    if char == ']' {
+                        break;
+                    }
+                }
+            }
+            char => match tokens.last_mut() {
+                Some((false, code)) => code.push(char),
+                _ => tokens.push((false, char.to_string())),
+            },
+        }
+    }
+
+    tokens
+}
+
+fn is_date_format(section: &str) -> bool {
+    format_tokens(section).iter().any(|(is_literal, code)| {
+        !is_literal
+            && code
+                .chars()
+                .any(|char| matches!(char.to_ascii_lowercase(), 'y' | 'm' | 'd' | 'h' | 's'))
+    })
+}
+
+// Format a number using decimal format codes like "#,##0.00", "0%" or
+// "0.00E+00". Literal strings are added before or after the number.
+fn format_decimal(number: f64, section: &str) -> String {
+    let tokens = format_tokens(section);
+    let code: String = tokens
+        .iter()
+        .filter(|(is_literal, _)| !is_literal)
+        .map(|(_, code)| code.as_str())
+        .collect();
+
+    let mut number = number;
+    for _ in code.matches('%') {
+        number *= 100.0;
+    }
+
+    // Trailing commas scale the number by 1000.
+    let placeholders = code.trim_end_matches(|char: char| !matches!(char, '0' | '#' | '?' | ','));
+    let mut digits_code =
+        placeholders.trim_start_matches(|char: char| !matches!(char, '0' | '#' | '?' | '.'));
+    while let Some(code) = digits_code.strip_suffix(',') {
+        number /= 1000.0;
+        digits_code = code;
+    }
+
+    let has_thousands = digits_code.contains(',');
+    let is_scientific = code.to_uppercase().contains("E+") || code.to_uppercase().contains("E-");
+
+    let (integer_code, fraction_code) = match digits_code.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (digits_code, ""),
+    };
+    let fraction_code: String = fraction_code
+        .chars()
+        .take_while(|char| matches!(char, '0' | '#' | '?'))
+        .collect();
+
+    let min_decimals = fraction_code.chars().filter(|char| *char == '0').count();
+    let max_decimals = fraction_code.len();
+    let min_integers = integer_code.chars().filter(|char| *char == '0').count();
+
+    let formatted = if is_scientific {
+        let exponent_digits = code
+            .to_uppercase()
+            .split(['+', '-'])
+            .nth(1)
+            .map_or(2, |digits| {
+                digits.chars().filter(|char| *char == '0').count()
+            });
+        let text = format!("{:.*e}", max_decimals, number.abs());
+        let (mantissa, exponent) = text.split_once('e').unwrap_or((&text, "0"));
+        let exponent: i32 = exponent.parse().unwrap_or(0);
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!(
+            "{mantissa}E{sign}{:0width$}",
+            exponent.abs(),
+            width = exponent_digits
+        )
+    } else {
+        let text = format!("{:.*}", max_decimals, number.abs());
+        let (integer, fraction) = match text.split_once('.') {
+            Some((integer, fraction)) => (integer.to_string(), fraction.to_string()),
+            None => (text, String::new()),
+        };
+
+        let mut fraction = fraction;
+        while fraction.len() > min_decimals && fraction.ends_with('0') {
+            fraction.pop();
+        }
+
+        let mut integer = integer.trim_start_matches('0').to_string();
+        while integer.len() < min_integers {
+            integer.insert(0, '0');
+        }
+
+        if has_thousands {
+            let chars: Vec<char> = integer.chars().collect();
+            let mut grouped = String::new();
+            for (index, char) in chars.iter().enumerate() {
+                if index > 0 && (chars.len() - index).is_multiple_of(3) {
+                    grouped.push(',');
+                }
+                grouped.push(*char);
+            }
+            integer = grouped;
+        }
+
+        if fraction.is_empty() && !fraction_code.contains('0') {
+            if code.contains('.') && max_decimals > 0 {
+                format!("{integer}.")
+            } else {
+                integer
+            }
+        } else {
+            format!("{integer}.{fraction}")
+        }
+    };
+
+    // Add the sign and any literal strings and non-digit codes.
+    let mut result = String::new();
+    if number < 0.0 && formatted.chars().any(|char| matches!(char, '1'..='9')) {
+        result.push('-');
+    }
+
+    let mut number_written = false;
+    for (is_literal, code) in &tokens {
+        if *is_literal {
+            result.push_str(code);
+            continue;
+        }
+
+        for char in code.chars() {
+            match char {
+                '0' | '#' | '?' | '.' | ',' | 'E' | 'e' | '+' | '-' => {
+                    if !(number_written || char == '-' || char == '+') {
+                        result.push_str(&formatted);
+                        number_written = true;
+                    } else if !number_written {
+                        result.push(char);
+                    }
+                }
+                _ => result.push(char),
+            }
+        }
+    }
+
+    if !number_written {
+        result.push_str(&formatted);
+    }
+
+    result
+}
+
+// Format a serial date using date and time format codes.
+fn format_date(number: f64, section: &str, is_1904_date: bool) -> String {
+    const MONTHS: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+    const DAYS: [&str; 7] = [
+        "Sunday",
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+    ];
+
+    let (year, month, day, hour, minute, second) =
+        ExcelDateTime::serial_to_date_parts(number, is_1904_date);
+    let weekday = weekday_index(number, is_1904_date) as usize;
+
+    let tokens = format_tokens(section);
+    let has_meridian = tokens.iter().any(|(is_literal, code)| {
+        !is_literal
+            && (code.to_uppercase().contains("AM/PM") || code.to_uppercase().contains("A/P"))
+    });
+
+    // Split the codes into runs of the same character, like "yyyy" or "mm".
+    let mut parts: Vec<(bool, String)> = vec![];
+    for (is_literal, code) in tokens {
+        if is_literal {
+            parts.push((true, code));
+            continue;
+        }
+
+        let chars: Vec<char> = code.chars().collect();
+        let mut index = 0;
+        while index < chars.len() {
+            let rest: String = chars[index..].iter().collect();
+            let upper = rest.to_uppercase();
+
+            if upper.starts_with("AM/PM") {
+                parts.push((false, "AM/PM".to_string()));
+                index += 5;
+                continue;
+            }
+            if upper.starts_with("A/P") {
+                parts.push((false, "A/P".to_string()));
+                index += 3;
+                continue;
+            }
+
+            let char = chars[index].to_ascii_lowercase();
+            let mut end = index + 1;
+            if matches!(char, 'y' | 'm' | 'd' | 'h' | 's') {
+                while end < chars.len() && chars[end].to_ascii_lowercase() == char {
+                    end += 1;
+                }
+            } else if char == '.' && end < chars.len() && chars[end] == '0' {
+                // Fractional seconds.
+                while end < chars.len() && chars[end] == '0' {
+                    end += 1;
+                }
+            }
+
+            parts.push((
+                false,
+                chars[index..end].iter().collect::<String>().to_lowercase(),
+            ));
+            index = end;
+        }
+    }
+
+    // Determine which "m" codes are minutes. They follow an hour code or
+    // precede a seconds code.
+    let code_indices: Vec<usize> = parts
+        .iter()
+        .enumerate()
+        .filter(|(_, (is_literal, code))| {
+            !is_literal && code.starts_with(['y', 'm', 'd', 'h', 's'])
+        })
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut is_minute = vec![false; parts.len()];
+    for (position, index) in code_indices.iter().enumerate() {
+        let code = &parts[*index].1;
+        if !code.starts_with('m') || code.len() > 2 {
+            continue;
+        }
+
+        let previous = position
+            .checked_sub(1)
+            .map(|previous| parts[code_indices[previous]].1.as_str());
+        let next = code_indices
+            .get(position + 1)
+            .map(|next| parts[*next].1.as_str());
+
+        if previous.is_some_and(|code| code.starts_with('h'))
+            || next.is_some_and(|code| code.starts_with('s'))
+        {
+            is_minute[*index] = true;
+        }
+    }
+
+    let hour_12 = match hour % 12 {
+        0 => 12,
+        hour => hour,
+    };
+    let display_hour = if has_meridian { hour_12 } else { hour };
+
+    let mut result = String::new();
+    for (index, (is_literal, code)) in parts.iter().enumerate() {
+        if *is_literal {
+            result.push_str(code);
+            continue;
+        }
+
+        let text = match code.as_str() {
+            "yy" => format!("{:02}", year % 100),
+            code if code.starts_with('y') => format!("{year:04}"),
+            "m" if is_minute[index] => minute.to_string(),
+            "mm" if is_minute[index] => format!("{minute:02}"),
+            "m" => month.to_string(),
+            "mm" => format!("{month:02}"),
+            "mmm" => MONTHS[usize::from(month) - 1][..3].to_string(),
+            "mmmmm" => MONTHS[usize::from(month) - 1][..1].to_string(),
+            code if code.starts_with('m') => MONTHS[usize::from(month) - 1].to_string(),
+            "d" => day.to_string(),
+            "dd" => format!("{day:02}"),
+            "ddd" => DAYS[weekday][..3].to_string(),
+            code if code.starts_with('d') => DAYS[weekday].to_string(),
+            "h" => display_hour.to_string(),
+            code if code.starts_with('h') => format!("{display_hour:02}"),
+            "s" => (second.trunc() as u32).to_string(),
+            code if code.starts_with('s') => format!("{:02}", second.trunc() as u32),
+            code if code.starts_with(".0") => {
+                let digits = code.len() - 1;
+                let fraction = format!("{:.*}", digits, second.fract());
+                fraction.trim_start_matches('0').to_string()
+            }
+            "AM/PM" | "am/pm" => if hour < 12 { "AM" } else { "PM" }.to_string(),
+            "A/P" | "a/p" => if hour < 12 { "A" } else { "P" }.to_string(),
+            code => code.to_string(),
+        };
+
+        result.push_str(&text);
+    }
+
+    result
+}
+
+fn take_drop_range(is_take: bool, count: f64, size: usize, is_set: bool) -> std::ops::Range<usize> {
+    if !is_set {
+        return 0..size;
+    }
+
+    let amount = (count.abs() as usize).min(size);
+
+    match (is_take, count >= 0.0) {
+        (true, true) => 0..amount,
+        (true, false) => size - amount..size,
+        (false, true) => amount..size,
+        (false, false) => 0..size - amount,
+    }
+}
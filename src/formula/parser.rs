@@ -0,0 +1,1497 @@
+// formula parser - A module for tokenizing and parsing Excel formulas.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::{ColNum, ExcelError, Formula, RowNum, COL_MAX, ROW_MAX};
+
+// -----------------------------------------------------------------------
+// Formula AST types.
+// -----------------------------------------------------------------------
+
+/// A node in the expression tree of a parsed formula.
+///
+/// The `FormulaExpression` enum represents the abstract syntax tree (AST)
+/// returned by [`Formula::parse()`](crate::Formula::parse). It can be used to
+/// inspect the structure of a formula, for example to find the functions or
+/// cell references that it uses.
+///
+/// Function names are stored in uppercase and without any `_xlfn.` or
+/// `_xlws.` future function prefixes. Full column ranges like `A:A` and full
+/// row ranges like `1:1` are stored as references that extend to the last row
+/// or column of the worksheet.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormulaExpression {
+    /// A number literal like `1.5`.
+    Number(f64),
+
+    /// A string literal like `"Hello"`, without the surrounding quotes.
+    String(String),
+
+    /// A boolean literal, `TRUE` or `FALSE`.
+    Boolean(bool),
+
+    /// An error literal like `#N/A` or `#DIV/0!`.
+    Error(String),
+
+    /// A cell or range reference like `A1`, `Sheet1!$B$2:C10` or `A:A`.
+    Reference(FormulaReference),
+
+    /// A structured table reference like `Table1[Column1]`.
+    StructuredReference(String),
+
+    /// A defined name, or a `LET()`/`LAMBDA()` parameter name.
+    Name(String),
+
+    /// A function call with its arguments.
+    Function {
+        /// The uppercase function name, like `SUM`.
+        name: String,
+        /// The function arguments.
+        args: Vec<FormulaExpression>,
+    },
+
+    /// A unary operation like `-A1` or `10%`.
+    Unary {
+        /// The unary operator.
+        op: FormulaUnaryOperator,
+        /// The operand.
+        expr: Box<FormulaExpression>,
+    },
+
+    /// A binary operation like `A1+B1`.
+    Binary {
+        /// The binary operator.
+        op: FormulaBinaryOperator,
+        /// The left hand operand.
+        left: Box<FormulaExpression>,
+        /// The right hand operand.
+        right: Box<FormulaExpression>,
+    },
+
+    /// An array constant like `{1,2;3,4}`, stored as rows of values.
+    Array(Vec<Vec<FormulaExpression>>),
+
+    /// An omitted function argument, like the second argument in
+    /// `IF(A1,,1)`.
+    Empty,
+}
+
+/// A cell or range reference in a parsed formula.
+///
+/// The `FormulaReference` struct represents a cell, range, row or column
+/// reference in a [`FormulaExpression`]. The row and column values are zero
+/// indexed. Single cell references have the same first and last row and
+/// column.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormulaReference {
+    /// The worksheet name, if the reference has one. Quoted names like
+    /// `'Sheet 1'` are stored without the quotes.
+    pub sheet: Option<String>,
+
+    /// The last worksheet name in a 3D reference like `Sheet1:Sheet3!A1`.
+    pub last_sheet: Option<String>,
+
+    /// The first row of the reference.
+    pub first_row: RowNum,
+
+    /// The first column of the reference.
+    pub first_col: ColNum,
+
+    /// The last row of the reference.
+    pub last_row: RowNum,
+
+    /// The last column of the reference.
+    pub last_col: ColNum,
+}
+
+/// The unary operators in a parsed formula.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FormulaUnaryOperator {
+    /// The unary plus operator, `+`.
+    Plus,
+
+    /// The negation operator, `-`.
+    Minus,
+
+    /// The postfix percent operator, `%`.
+    Percent,
+
+    /// The implicit intersection operator, `@`.
+    ImplicitIntersection,
+
+    /// The postfix spilled range operator, `#`.
+    Spill,
+}
+
+/// The binary operators in a parsed formula.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FormulaBinaryOperator {
+    /// The addition operator, `+`.
+    Add,
+
+    /// The subtraction operator, `-`.
+    Subtract,
+
+    /// The multiplication operator, `*`.
+    Multiply,
+
+    /// The division operator, `/`.
+    Divide,
+
+    /// The exponentiation operator, `^`.
+    Power,
+
+    /// The string concatenation operator, `&`.
+    Concat,
+
+    /// The equal to comparison operator, `=`.
+    Equal,
+
+    /// The not equal to comparison operator, `<>`.
+    NotEqual,
+
+    /// The less than comparison operator, `<`.
+    Less,
+
+    /// The less than or equal to comparison operator, `<=`.
+    LessEqual,
+
+    /// The greater than comparison operator, `>`.
+    Greater,
+
+    /// The greater than or equal to comparison operator, `>=`.
+    GreaterEqual,
+
+    /// The range operator, `:`.
+    Range,
+}
+
+// The Excel error values that are valid literals in formulas. The dynamic
+// array errors, such as #SPILL!, can be returned by functions but they aren't
+// valid in formulas.
+const ERROR_LITERALS: [ExcelError; 8] = [
+    ExcelError::Null,
+    ExcelError::Div0,
+    ExcelError::Value,
+    ExcelError::Ref,
+    ExcelError::Name,
+    ExcelError::Num,
+    ExcelError::NA,
+    ExcelError::GettingData,
+];
+
+// A formula syntax error and the character position where it occurred.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ParseError {
+    pub(crate) message: String,
+    pub(crate) position: usize,
+}
+
+impl ParseError {
+    fn new(message: impl Into<String>, position: usize) -> ParseError {
+        ParseError {
+            message: message.into(),
+            position,
+        }
+    }
+
+    // Get a description of the error for use in an XlsxError.
+    pub(crate) fn description(&self, formula: &str) -> String {
+        format!(
+            "{} at position {} in formula '{formula}'",
+            self.message,
+            self.position + 1
+        )
+    }
+}
+
+// Parse a formula string, without a leading "=", into an expression tree.
+pub(crate) fn parse(formula: &str) -> Result<FormulaExpression, ParseError> {
+    let tokens = Tokenizer::new(formula).tokenize()?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        end: formula.chars().count(),
+    };
+
+    if parser.tokens.is_empty() {
+        return Err(ParseError::new("Formula is empty", 0));
+    }
+
+    let expr = parser.parse_expression(0)?;
+
+    match parser.peek() {
+        None => Ok(expr),
+        Some((Token::RightParen, position)) => Err(ParseError::new(
+            "Unbalanced parentheses: unexpected ')'",
+            *position,
+        )),
+        Some((token, position)) => Err(ParseError::new(
+            format!("Unexpected {token} in formula"),
+            *position,
+        )),
+    }
+}
+
+// -----------------------------------------------------------------------
+// Tokenizer.
+// -----------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    String(String),
+    Boolean(bool),
+    Error(ExcelError),
+    Reference(FormulaReference),
+    StructuredReference(String),
+    Name(String),
+    Function(String),
+    Operator(&'static str),
+    Spill,
+    LeftParen,
+    RightParen,
+    LeftBrace,
+    RightBrace,
+    Comma,
+    Semicolon,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "number '{number}'"),
+            Token::String(string) => write!(f, "string \"{string}\""),
+            Token::Boolean(boolean) => write!(f, "boolean '{}'", boolean_name(*boolean)),
+            Token::Error(error) => write!(f, "error '{error}'"),
+            Token::Reference(_) | Token::StructuredReference(_) => write!(f, "reference"),
+            Token::Name(name) => write!(f, "name '{name}'"),
+            Token::Function(name) => write!(f, "function '{name}'"),
+            Token::Operator(operator) => write!(f, "operator '{operator}'"),
+            Token::Spill => write!(f, "'#'"),
+            Token::LeftParen => write!(f, "'('"),
+            Token::RightParen => write!(f, "')'"),
+            Token::LeftBrace => write!(f, "'{{'"),
+            Token::RightBrace => write!(f, "'}}'"),
+            Token::Comma => write!(f, "','"),
+            Token::Semicolon => write!(f, "';'"),
+        }
+    }
+}
+
+fn boolean_name(boolean: bool) -> &'static str {
+    if boolean {
+        "TRUE"
+    } else {
+        "FALSE"
+    }
+}
+
+struct Tokenizer {
+    chars: Vec<char>,
+    position: usize,
+    tokens: Vec<(Token, usize)>,
+}
+
+impl Tokenizer {
+    fn new(formula: &str) -> Tokenizer {
+        Tokenizer {
+            chars: formula.chars().collect(),
+            position: 0,
+            tokens: vec![],
+        }
+    }
+
+    fn tokenize(mut self) -> Result<Vec<(Token, usize)>, ParseError> {
+        while let Some(char) = self.peek(0) {
+            let start = self.position;
+
+            let token = match char {
+                ' ' | '\t' | '\r' | '\n' => {
+                    self.position += 1;
+                    continue;
+                }
+                '"' => self.read_string()?,
+                '#' => self.read_error_or_spill()?,
+                '\'' => self.read_quoted_reference()?,
+                '[' => self.read_structured_reference(String::new())?,
+                '0'..='9' | '.' => self.read_number_or_row_range()?,
+                '(' => self.single(Token::LeftParen),
+                ')' => self.single(Token::RightParen),
+                '{' => self.single(Token::LeftBrace),
+                '}' => self.single(Token::RightBrace),
+                ',' => self.single(Token::Comma),
+                ';' => self.single(Token::Semicolon),
+                '<' if self.peek(1) == Some('=') => self.operator("<=", 2),
+                '<' if self.peek(1) == Some('>') => self.operator("<>", 2),
+                '>' if self.peek(1) == Some('=') => self.operator(">=", 2),
+                '<' => self.operator("<", 1),
+                '>' => self.operator(">", 1),
+                '=' => self.operator("=", 1),
+                '+' => self.operator("+", 1),
+                '-' => self.operator("-", 1),
+                '*' => self.operator("*", 1),
+                '/' => self.operator("/", 1),
+                '^' => self.operator("^", 1),
+                '&' => self.operator("&", 1),
+                '%' => self.operator("%", 1),
+                ':' => self.operator(":", 1),
+                '@' => self.operator("@", 1),
+                _ if is_word_char(char) => self.read_word()?,
+                _ => {
+                    return Err(ParseError::new(
+                        format!("Invalid character '{char}' in formula"),
+                        start,
+                    ))
+                }
+            };
+
+            self.tokens.push((token, start));
+        }
+
+        Ok(self.tokens)
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.position + offset).copied()
+    }
+
+    fn single(&mut self, token: Token) -> Token {
+        self.position += 1;
+        token
+    }
+
+    fn operator(&mut self, operator: &'static str, length: usize) -> Token {
+        self.position += length;
+        Token::Operator(operator)
+    }
+
+    // Read a string literal. Double quotes are escaped by doubling them.
+    fn read_string(&mut self) -> Result<Token, ParseError> {
+        let start = self.position;
+        let mut string = String::new();
+        self.position += 1;
+
+        loop {
+            match self.peek(0) {
+                None => return Err(ParseError::new("Unterminated string literal", start)),
+                Some('"') if self.peek(1) == Some('"') => {
+                    string.push('"');
+                    self.position += 2;
+                }
+                Some('"') => {
+                    self.position += 1;
+                    return Ok(Token::String(string));
+                }
+                Some(char) => {
+                    string.push(char);
+                    self.position += 1;
+                }
+            }
+        }
+    }
+
+    // Read an error literal like "#N/A" or a spilled range operator like the
+    // "#" in "A1#".
+    fn read_error_or_spill(&mut self) -> Result<Token, ParseError> {
+        let start = self.position;
+
+        for error in ERROR_LITERALS {
+            let length = error.as_str().chars().count();
+            let text: String = self.chars.iter().skip(start).take(length).collect();
+
+            if text.eq_ignore_ascii_case(error.as_str()) {
+                self.position += length;
+                return Ok(Token::Error(error));
+            }
+        }
+
+        let follows_reference = start > 0
+            && !self.chars[start - 1].is_whitespace()
+            && matches!(self.tokens.last(), Some((Token::Reference(_), _)));
+
+        if follows_reference {
+            self.position += 1;
+            return Ok(Token::Spill);
+        }
+
+        Err(ParseError::new("Invalid error value in formula", start))
+    }
+
+    // Read a number or a row range like "1:5".
+    fn read_number_or_row_range(&mut self) -> Result<Token, ParseError> {
+        let start = self.position;
+
+        if let Some(token) = self.try_read_range(None, None) {
+            return Ok(token);
+        }
+        self.position = start;
+
+        while matches!(self.peek(0), Some('0'..='9')) {
+            self.position += 1;
+        }
+        if self.peek(0) == Some('.') {
+            self.position += 1;
+            while matches!(self.peek(0), Some('0'..='9')) {
+                self.position += 1;
+            }
+        }
+        if matches!(self.peek(0), Some('e' | 'E')) {
+            let mark = self.position;
+            self.position += 1;
+            if matches!(self.peek(0), Some('+' | '-')) {
+                self.position += 1;
+            }
+            if matches!(self.peek(0), Some('0'..='9')) {
+                while matches!(self.peek(0), Some('0'..='9')) {
+                    self.position += 1;
+                }
+            } else {
+                self.position = mark;
+            }
+        }
+
+        let text: String = self.chars[start..self.position].iter().collect();
+
+        match text.parse::<f64>() {
+            Ok(number) => Ok(Token::Number(number)),
+            Err(_) => Err(ParseError::new(format!("Invalid number '{text}'"), start)),
+        }
+    }
+
+    // Read a quoted sheet name and the reference that follows it, like
+    // "'Sheet 1'!A1".
+    fn read_quoted_reference(&mut self) -> Result<Token, ParseError> {
+        let start = self.position;
+        let mut sheet = String::new();
+        self.position += 1;
+
+        loop {
+            match self.peek(0) {
+                None => return Err(ParseError::new("Unterminated quoted sheet name", start)),
+                Some('\'') if self.peek(1) == Some('\'') => {
+                    sheet.push('\'');
+                    self.position += 2;
+                }
+                Some('\'') => {
+                    self.position += 1;
+                    break;
+                }
+                Some(char) => {
+                    sheet.push(char);
+                    self.position += 1;
+                }
+            }
+        }
+
+        if self.peek(0) != Some('!') {
+            return Err(ParseError::new(
+                "Quoted sheet name must be followed by '!'",
+                self.position,
+            ));
+        }
+        self.position += 1;
+
+        let (sheet, last_sheet) = match sheet.split_once(':') {
+            Some((first, last)) => (first.to_string(), Some(last.to_string())),
+            None => (sheet, None),
+        };
+
+        self.read_sheet_reference(sheet, last_sheet, start)
+    }
+
+    // Read the reference part after a "Sheet!" prefix.
+    fn read_sheet_reference(
+        &mut self,
+        sheet: String,
+        last_sheet: Option<String>,
+        start: usize,
+    ) -> Result<Token, ParseError> {
+        if self.peek(0) == Some('#') {
+            return self.read_error_or_spill();
+        }
+
+        if let Some(token) = self.try_read_range(Some(sheet.clone()), last_sheet) {
+            return Ok(token);
+        }
+
+        // A sheet qualified defined name like "Sheet1!Name".
+        let name = self.read_word_text();
+        if name.is_empty() {
+            return Err(ParseError::new(
+                format!("Invalid reference after sheet name '{sheet}'"),
+                start,
+            ));
+        }
+
+        Ok(Token::Name(format!("{sheet}!{name}")))
+    }
+
+    // Try to read a cell, range, row range or column range reference at the
+    // current position. The position is unchanged if there isn't one.
+    fn try_read_range(
+        &mut self,
+        sheet: Option<String>,
+        last_sheet: Option<String>,
+    ) -> Option<Token> {
+        let start = self.position;
+        let first = self.read_word_text();
+        let first_part = parse_ref_part(&first);
+
+        let Some(first_part) = first_part else {
+            self.position = start;
+            return None;
+        };
+
+        // Check for a range like "A1:B2", "A:C" or "1:3".
+        if self.peek(0) == Some(':') {
+            let mark = self.position;
+            self.position += 1;
+            let second = self.read_word_text();
+
+            if !self.next_is_word_continuation() {
+                if let Some(reference) =
+                    make_range(first_part, parse_ref_part(&second), &sheet, &last_sheet)
+                {
+                    return Some(Token::Reference(reference));
+                }
+            }
+
+            self.position = mark;
+        }
+
+        // A single cell reference.
+        if let RefPart::Cell(row, col) = first_part {
+            if !self.next_is_word_continuation() && self.peek(0) != Some('(') {
+                return Some(Token::Reference(FormulaReference {
+                    sheet,
+                    last_sheet,
+                    first_row: row,
+                    first_col: col,
+                    last_row: row,
+                    last_col: col,
+                }));
+            }
+        }
+
+        self.position = start;
+        None
+    }
+
+    fn next_is_word_continuation(&self) -> bool {
+        matches!(self.peek(0), Some('[' | '!'))
+    }
+
+    // Read a run of identifier characters.
+    fn read_word_text(&mut self) -> String {
+        let start = self.position;
+
+        while let Some(char) = self.peek(0) {
+            if is_word_char(char) || char == '$' {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+
+        self.chars[start..self.position].iter().collect()
+    }
+
+    // Read a word token which may be a function, name, boolean, reference or
+    // sheet name prefix.
+    fn read_word(&mut self) -> Result<Token, ParseError> {
+        let start = self.position;
+
+        if let Some(token) = self.try_read_range(None, None) {
+            return Ok(token);
+        }
+
+        let word = self.read_word_text();
+
+        match self.peek(0) {
+            Some('!') => {
+                self.position += 1;
+                return self.read_sheet_reference(word, None, start);
+            }
+            Some(':') => {
+                // Check for a 3D sheet range like "Sheet1:Sheet3!A1".
+                let mark = self.position;
+                self.position += 1;
+                let last_sheet = self.read_word_text();
+
+                if !last_sheet.is_empty() && self.peek(0) == Some('!') {
+                    self.position += 1;
+                    return self.read_sheet_reference(word, Some(last_sheet), start);
+                }
+
+                self.position = mark;
+            }
+            Some('(') => {
+                return Ok(Token::Function(
+                    strip_function_prefixes(&word).to_uppercase(),
+                ));
+            }
+            Some('[') => return self.read_structured_reference(word),
+            _ => {}
+        }
+
+        if word.eq_ignore_ascii_case("TRUE") {
+            return Ok(Token::Boolean(true));
+        }
+        if word.eq_ignore_ascii_case("FALSE") {
+            return Ok(Token::Boolean(false));
+        }
+
+        // Words like "XFE1" or "A0" look like cell references but are outside
+        // Excel's row and column limits.
+        if is_reference_like(&word) {
+            return Err(ParseError::new(
+                format!("Invalid cell reference '{word}'"),
+                start,
+            ));
+        }
+
+        if word.contains('$') {
+            return Err(ParseError::new(
+                format!("Invalid reference '{word}'"),
+                start,
+            ));
+        }
+
+        Ok(Token::Name(
+            word.strip_prefix("_xlpm.").unwrap_or(&word).to_string(),
+        ))
+    }
+
+    // Read a structured table reference like "Table1[Column]" or "[@Column]".
+    fn read_structured_reference(&mut self, table: String) -> Result<Token, ParseError> {
+        let start = self.position;
+        let mut depth = 0;
+        let mut text = table;
+
+        while let Some(char) = self.peek(0) {
+            text.push(char);
+            self.position += 1;
+
+            match char {
+                // Special characters in column names are escaped with "'".
+                '\'' => {
+                    if let Some(char) = self.peek(0) {
+                        text.push(char);
+                        self.position += 1;
+                    }
+                }
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(Token::StructuredReference(text));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Err(ParseError::new(
+            "Unbalanced brackets in structured reference",
+            start,
+        ))
+    }
+}
+
+// Check for characters that can be part of an identifier.
+fn is_word_char(char: char) -> bool {
+    char.is_alphanumeric() || matches!(char, '_' | '.' | '\\' | '?')
+}
+
+// Check for a word that has the form of a cell reference, 1 to 3 letters
+// followed by digits.
+fn is_reference_like(word: &str) -> bool {
+    let letters = word.chars().take_while(char::is_ascii_alphabetic).count();
+    let digits = &word[letters..];
+
+    (1..=3).contains(&letters)
+        && !digits.is_empty()
+        && digits.chars().all(|char| char.is_ascii_digit())
+}
+
+// Strip the "_xlfn." and "_xlws." prefixes added to future functions.
+fn strip_function_prefixes(name: &str) -> &str {
+    let name = name.strip_prefix("_xlfn.").unwrap_or(name);
+    name.strip_prefix("_xlws.").unwrap_or(name)
+}
+
+// The parts of a range reference.
+#[derive(Clone, Copy)]
+enum RefPart {
+    Cell(RowNum, ColNum),
+    Column(ColNum),
+    Row(RowNum),
+}
+
+// Parse a cell, column or row reference part like "$A$1", "B" or "3".
+fn parse_ref_part(text: &str) -> Option<RefPart> {
+    let text = text.strip_prefix('$').unwrap_or(text);
+    let letters: String = text.chars().take_while(char::is_ascii_alphabetic).collect();
+    let rest = &text[letters.len()..];
+    let rest = rest.strip_prefix('$').unwrap_or(rest);
+
+    if rest.contains('$') && !letters.is_empty() {
+        return None;
+    }
+
+    let col = if letters.is_empty() {
+        None
+    } else if letters.len() <= 3 {
+        let col = letters.chars().fold(0_u32, |col, char| {
+            col * 26 + u32::from(char.to_ascii_uppercase()) - 64
+        });
+        if col > u32::from(COL_MAX) {
+            return None;
+        }
+        Some(col as ColNum - 1)
+    } else {
+        return None;
+    };
+
+    let row = if rest.is_empty() {
+        None
+    } else if rest.chars().all(|char| char.is_ascii_digit()) {
+        match rest.parse::<RowNum>() {
+            Ok(row) if (1..=ROW_MAX).contains(&row) => Some(row - 1),
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
+
+    // Rows can't have an absolute marker between the letters and digits
+    // unless there are letters, e.g. "$1" is valid but "1$2" isn't.
+    if letters.is_empty() && text.contains('$') {
+        return None;
+    }
+
+    match (col, row) {
+        (Some(col), Some(row)) => Some(RefPart::Cell(row, col)),
+        (Some(col), None) => Some(RefPart::Column(col)),
+        (None, Some(row)) => Some(RefPart::Row(row)),
+        (None, None) => None,
+    }
+}
+
+// Combine two reference parts into a range.
+fn make_range(
+    first: RefPart,
+    second: Option<RefPart>,
+    sheet: &Option<String>,
+    last_sheet: &Option<String>,
+) -> Option<FormulaReference> {
+    let (first_row, first_col, last_row, last_col) = match (first, second?) {
+        (RefPart::Cell(row1, col1), RefPart::Cell(row2, col2)) => (row1, col1, row2, col2),
+        (RefPart::Column(col1), RefPart::Column(col2)) => (0, col1, ROW_MAX - 1, col2),
+        (RefPart::Row(row1), RefPart::Row(row2)) => (row1, 0, row2, COL_MAX - 1),
+        _ => return None,
+    };
+
+    Some(FormulaReference {
+        sheet: sheet.clone(),
+        last_sheet: last_sheet.clone(),
+        first_row: first_row.min(last_row),
+        first_col: first_col.min(last_col),
+        last_row: first_row.max(last_row),
+        last_col: first_col.max(last_col),
+    })
+}
+
+// -----------------------------------------------------------------------
+// Parser.
+// -----------------------------------------------------------------------
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&(Token, usize)> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    // Get the character position of the current token, for error messages.
+    fn current_position(&self) -> usize {
+        self.peek().map_or(self.end, |(_, position)| *position)
+    }
+
+    // Parse an expression using precedence climbing. The Excel operator
+    // precedence, from lowest to highest, is: comparison, "&", "+/-", "*//",
+    // "^", "%", unary "-", and the ":" range operator.
+    fn parse_expression(&mut self, min_precedence: u8) -> Result<FormulaExpression, ParseError> {
+        let mut left = self.parse_prefix()?;
+
+        while let Some((token, _)) = self.peek() {
+            match token {
+                Token::Operator("%") => {
+                    self.position += 1;
+                    left = FormulaExpression::Unary {
+                        op: FormulaUnaryOperator::Percent,
+                        expr: Box::new(left),
+                    };
+                }
+                Token::Spill => {
+                    self.position += 1;
+                    left = FormulaExpression::Unary {
+                        op: FormulaUnaryOperator::Spill,
+                        expr: Box::new(left),
+                    };
+                }
+                Token::Operator(operator) => {
+                    let Some((op, precedence)) = binary_operator(operator) else {
+                        break;
+                    };
+
+                    if precedence < min_precedence {
+                        break;
+                    }
+
+                    self.position += 1;
+
+                    // All Excel binary operators are left associative.
+                    let right = self.parse_expression(precedence + 1)?;
+
+                    left = FormulaExpression::Binary {
+                        op,
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    };
+                }
+                _ => break,
+            }
+        }
+
+        Ok(left)
+    }
+
+    fn parse_prefix(&mut self) -> Result<FormulaExpression, ParseError> {
+        let position = self.current_position();
+
+        let Some((token, position)) = self.next() else {
+            return Err(ParseError::new("Unexpected end of formula", position));
+        };
+
+        match token {
+            Token::Number(number) => Ok(FormulaExpression::Number(number)),
+            Token::String(string) => Ok(FormulaExpression::String(string)),
+            Token::Boolean(boolean) => Ok(FormulaExpression::Boolean(boolean)),
+            Token::Error(error) => Ok(FormulaExpression::Error(error.as_str().to_string())),
+            Token::Reference(reference) => Ok(FormulaExpression::Reference(reference)),
+            Token::StructuredReference(reference) => {
+                Ok(FormulaExpression::StructuredReference(reference))
+            }
+            Token::Name(name) => Ok(FormulaExpression::Name(name)),
+            Token::Function(name) => self.parse_function(name),
+            Token::LeftParen => {
+                let expr = self.parse_expression(0)?;
+
+                match self.next() {
+                    Some((Token::RightParen, _)) => Ok(expr),
+                    _ => Err(ParseError::new(
+                        "Unbalanced parentheses: missing ')'",
+                        position,
+                    )),
+                }
+            }
+            Token::LeftBrace => self.parse_array(position),
+            Token::Operator(operator @ ("-" | "+" | "@")) => {
+                let op = match operator {
+                    "-" => FormulaUnaryOperator::Minus,
+                    "+" => FormulaUnaryOperator::Plus,
+                    _ => FormulaUnaryOperator::ImplicitIntersection,
+                };
+
+                // Unary operators bind more tightly than all binary operators
+                // apart from the range operator.
+                let expr = self.parse_expression(PRECEDENCE_UNARY)?;
+
+                Ok(FormulaExpression::Unary {
+                    op,
+                    expr: Box::new(expr),
+                })
+            }
+            Token::RightParen => Err(ParseError::new(
+                "Unbalanced parentheses: unexpected ')'",
+                position,
+            )),
+            token => Err(ParseError::new(
+                format!("Unexpected {token} in formula"),
+                position,
+            )),
+        }
+    }
+
+    // Parse the arguments of a function. Arguments can be empty, as in
+    // "IF(A1,,1)".
+    fn parse_function(&mut self, name: String) -> Result<FormulaExpression, ParseError> {
+        let start = self.current_position();
+
+        // Skip the opening parenthesis.
+        self.position += 1;
+
+        let mut args = vec![];
+
+        if matches!(self.peek(), Some((Token::RightParen, _))) {
+            self.position += 1;
+            return Ok(FormulaExpression::Function { name, args });
+        }
+
+        loop {
+            let arg = match self.peek() {
+                Some((Token::Comma | Token::RightParen, _)) => FormulaExpression::Empty,
+                _ => self.parse_expression(0)?,
+            };
+            args.push(arg);
+
+            match self.next() {
+                Some((Token::Comma, _)) => {}
+                Some((Token::RightParen, _)) => break,
+                Some((token, position)) => {
+                    return Err(ParseError::new(
+                        format!("Unexpected {token} in arguments to function '{name}'"),
+                        position,
+                    ))
+                }
+                None => {
+                    return Err(ParseError::new(
+                        format!("Unbalanced parentheses: missing ')' for function '{name}'"),
+                        start,
+                    ))
+                }
+            }
+        }
+
+        Ok(FormulaExpression::Function { name, args })
+    }
+
+    // Parse an array constant like "{1,2;3,4}".
+    fn parse_array(&mut self, start: usize) -> Result<FormulaExpression, ParseError> {
+        let mut rows = vec![];
+        let mut row = vec![];
+
+        loop {
+            let position = self.current_position();
+
+            let value = match self.next() {
+                Some((Token::Number(number), _)) => FormulaExpression::Number(number),
+                Some((Token::String(string), _)) => FormulaExpression::String(string),
+                Some((Token::Boolean(boolean), _)) => FormulaExpression::Boolean(boolean),
+                Some((Token::Error(error), _)) => {
+                    FormulaExpression::Error(error.as_str().to_string())
+                }
+                Some((Token::Operator("-"), _)) => match self.next() {
+                    Some((Token::Number(number), _)) => FormulaExpression::Number(-number),
+                    _ => {
+                        return Err(ParseError::new(
+                            "Array constants can only contain constant values",
+                            position,
+                        ))
+                    }
+                },
+                None => return Err(ParseError::new("Unbalanced braces: missing '}'", start)),
+                _ => {
+                    return Err(ParseError::new(
+                        "Array constants can only contain constant values",
+                        position,
+                    ))
+                }
+            };
+            row.push(value);
+
+            match self.next() {
+                Some((Token::Comma, _)) => {}
+                Some((Token::Semicolon, _)) => rows.push(std::mem::take(&mut row)),
+                Some((Token::RightBrace, _)) => {
+                    rows.push(row);
+                    break;
+                }
+                _ => return Err(ParseError::new("Unbalanced braces: missing '}'", start)),
+            }
+        }
+
+        if rows.iter().any(|row| row.len() != rows[0].len()) {
+            return Err(ParseError::new(
+                "Array constant rows must have the same number of values",
+                start,
+            ));
+        }
+
+        Ok(FormulaExpression::Array(rows))
+    }
+}
+
+const PRECEDENCE_UNARY: u8 = 7;
+
+// Get the binary operator type and precedence for an operator string.
+fn binary_operator(operator: &str) -> Option<(FormulaBinaryOperator, u8)> {
+    let operator = match operator {
+        "=" => (FormulaBinaryOperator::Equal, 1),
+        "<>" => (FormulaBinaryOperator::NotEqual, 1),
+        "<" => (FormulaBinaryOperator::Less, 1),
+        "<=" => (FormulaBinaryOperator::LessEqual, 1),
+        ">" => (FormulaBinaryOperator::Greater, 1),
+        ">=" => (FormulaBinaryOperator::GreaterEqual, 1),
+        "&" => (FormulaBinaryOperator::Concat, 2),
+        "+" => (FormulaBinaryOperator::Add, 3),
+        "-" => (FormulaBinaryOperator::Subtract, 3),
+        "*" => (FormulaBinaryOperator::Multiply, 4),
+        "/" => (FormulaBinaryOperator::Divide, 4),
+        "^" => (FormulaBinaryOperator::Power, 5),
+        ":" => (FormulaBinaryOperator::Range, 8),
+        _ => return None,
+    };
+
+    Some(operator)
+}
+
+// -----------------------------------------------------------------------
+// Validation.
+// -----------------------------------------------------------------------
+
+impl FormulaExpression {
+    // Call a function for each node in the expression tree, parents first.
+    pub(crate) fn walk(&self, visitor: &mut dyn FnMut(&FormulaExpression)) {
+        visitor(self);
+
+        match self {
+            FormulaExpression::Function { args, .. } => {
+                for arg in args {
+                    arg.walk(visitor);
+                }
+            }
+            FormulaExpression::Unary { expr, .. } => expr.walk(visitor),
+            FormulaExpression::Binary { left, right, .. } => {
+                left.walk(visitor);
+                right.walk(visitor);
+            }
+            FormulaExpression::Array(rows) => {
+                for expr in rows.iter().flatten() {
+                    expr.walk(visitor);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Get the first function in the expression that isn't a known Excel
+    // function or one of the additional allowed names.
+    pub(crate) fn unknown_function(&self, allowed_names: &[&str]) -> Option<String> {
+        let mut unknown = None;
+
+        self.walk(&mut |expr| {
+            if let FormulaExpression::Function { name, .. } = expr {
+                if unknown.is_none()
+                    && !is_excel_function(name)
+                    && !allowed_names
+                        .iter()
+                        .any(|allowed| allowed.eq_ignore_ascii_case(name))
+                {
+                    unknown = Some(name.clone());
+                }
+            }
+        });
+
+        unknown
+    }
+}
+
+// Check if a function name, without future function prefixes, is a known
+// Excel function. User defined functions with the "_xludf." prefix are also
+// allowed.
+pub(crate) fn is_excel_function(name: &str) -> bool {
+    let name = name.to_uppercase();
+
+    name.starts_with("_XLUDF.")
+        || Formula::future_functions(&name).is_some()
+        || excel_functions().contains(name.as_str())
+}
+
+// The standard Excel worksheet functions. Functions added in Excel 2010 and
+// later are in the `Formula` future function table.
+#[allow(clippy::too_many_lines)]
+fn excel_functions() -> &'static HashSet<&'static str> {
+    static EXCEL_FUNCTIONS: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    EXCEL_FUNCTIONS.get_or_init(|| {
+        HashSet::from([
+            "ABS",
+            "ACCRINT",
+            "ACCRINTM",
+            "ACOS",
+            "ACOSH",
+            "ADDRESS",
+            "AMORDEGRC",
+            "AMORLINC",
+            "AND",
+            "AREAS",
+            "ASC",
+            "ASIN",
+            "ASINH",
+            "ATAN",
+            "ATAN2",
+            "ATANH",
+            "AVEDEV",
+            "AVERAGE",
+            "AVERAGEA",
+            "AVERAGEIF",
+            "AVERAGEIFS",
+            "BAHTTEXT",
+            "BESSELI",
+            "BESSELJ",
+            "BESSELK",
+            "BESSELY",
+            "BETADIST",
+            "BETAINV",
+            "BIN2DEC",
+            "BIN2HEX",
+            "BIN2OCT",
+            "BINOMDIST",
+            "CALL",
+            "CEILING",
+            "CELL",
+            "CHAR",
+            "CHIDIST",
+            "CHIINV",
+            "CHITEST",
+            "CHOOSE",
+            "CLEAN",
+            "CODE",
+            "COLUMN",
+            "COLUMNS",
+            "COMBIN",
+            "COMPLEX",
+            "CONCATENATE",
+            "CONFIDENCE",
+            "CONVERT",
+            "CORREL",
+            "COS",
+            "COSH",
+            "COUNT",
+            "COUNTA",
+            "COUNTBLANK",
+            "COUNTIF",
+            "COUNTIFS",
+            "COUPDAYBS",
+            "COUPDAYS",
+            "COUPDAYSNC",
+            "COUPNCD",
+            "COUPNUM",
+            "COUPPCD",
+            "COVAR",
+            "CRITBINOM",
+            "CUBEKPIMEMBER",
+            "CUBEMEMBER",
+            "CUBEMEMBERPROPERTY",
+            "CUBERANKEDMEMBER",
+            "CUBESET",
+            "CUBESETCOUNT",
+            "CUBEVALUE",
+            "CUMIPMT",
+            "CUMPRINC",
+            "DATE",
+            "DATEDIF",
+            "DATEVALUE",
+            "DAVERAGE",
+            "DAY",
+            "DAYS360",
+            "DB",
+            "DBCS",
+            "DCOUNT",
+            "DCOUNTA",
+            "DDB",
+            "DEC2BIN",
+            "DEC2HEX",
+            "DEC2OCT",
+            "DEGREES",
+            "DELTA",
+            "DEVSQ",
+            "DGET",
+            "DISC",
+            "DMAX",
+            "DMIN",
+            "DOLLAR",
+            "DOLLARDE",
+            "DOLLARFR",
+            "DPRODUCT",
+            "DSTDEV",
+            "DSTDEVP",
+            "DSUM",
+            "DURATION",
+            "DVAR",
+            "DVARP",
+            "EDATE",
+            "EFFECT",
+            "EOMONTH",
+            "ERF",
+            "ERFC",
+            "ERROR.TYPE",
+            "EUROCONVERT",
+            "EVEN",
+            "EXACT",
+            "EXP",
+            "EXPONDIST",
+            "FACT",
+            "FACTDOUBLE",
+            "FALSE",
+            "FDIST",
+            "FIND",
+            "FINDB",
+            "FINV",
+            "FISHER",
+            "FISHERINV",
+            "FIXED",
+            "FLOOR",
+            "FORECAST",
+            "FREQUENCY",
+            "FTEST",
+            "FV",
+            "FVSCHEDULE",
+            "GAMMADIST",
+            "GAMMAINV",
+            "GAMMALN",
+            "GCD",
+            "GEOMEAN",
+            "GESTEP",
+            "GETPIVOTDATA",
+            "GROWTH",
+            "HARMEAN",
+            "HEX2BIN",
+            "HEX2DEC",
+            "HEX2OCT",
+            "HLOOKUP",
+            "HOUR",
+            "HYPERLINK",
+            "HYPGEOMDIST",
+            "IF",
+            "IFERROR",
+            "IMABS",
+            "IMAGINARY",
+            "IMARGUMENT",
+            "IMCONJUGATE",
+            "IMCOS",
+            "IMDIV",
+            "IMEXP",
+            "IMLN",
+            "IMLOG10",
+            "IMLOG2",
+            "IMPOWER",
+            "IMPRODUCT",
+            "IMREAL",
+            "IMSIN",
+            "IMSQRT",
+            "IMSUB",
+            "IMSUM",
+            "INDEX",
+            "INDIRECT",
+            "INFO",
+            "INT",
+            "INTERCEPT",
+            "INTRATE",
+            "IPMT",
+            "IRR",
+            "ISBLANK",
+            "ISERR",
+            "ISERROR",
+            "ISEVEN",
+            "ISLOGICAL",
+            "ISNA",
+            "ISNONTEXT",
+            "ISNUMBER",
+            "ISODD",
+            "ISPMT",
+            "ISREF",
+            "ISTEXT",
+            "JIS",
+            "KURT",
+            "LARGE",
+            "LCM",
+            "LEFT",
+            "LEFTB",
+            "LEN",
+            "LENB",
+            "LINEST",
+            "LN",
+            "LOG",
+            "LOG10",
+            "LOGEST",
+            "LOGINV",
+            "LOGNORMDIST",
+            "LOOKUP",
+            "LOWER",
+            "MATCH",
+            "MAX",
+            "MAXA",
+            "MDETERM",
+            "MDURATION",
+            "MEDIAN",
+            "MID",
+            "MIDB",
+            "MIN",
+            "MINA",
+            "MINUTE",
+            "MINVERSE",
+            "MIRR",
+            "MMULT",
+            "MOD",
+            "MODE",
+            "MONTH",
+            "MROUND",
+            "MULTINOMIAL",
+            "N",
+            "NA",
+            "NEGBINOMDIST",
+            "NETWORKDAYS",
+            "NOMINAL",
+            "NORMDIST",
+            "NORMINV",
+            "NORMSDIST",
+            "NORMSINV",
+            "NOT",
+            "NOW",
+            "NPER",
+            "NPV",
+            "OCT2BIN",
+            "OCT2DEC",
+            "OCT2HEX",
+            "ODD",
+            "ODDFPRICE",
+            "ODDFYIELD",
+            "ODDLPRICE",
+            "ODDLYIELD",
+            "OFFSET",
+            "OR",
+            "PEARSON",
+            "PERCENTILE",
+            "PERCENTRANK",
+            "PERMUT",
+            "PHONETIC",
+            "PI",
+            "PMT",
+            "POISSON",
+            "POWER",
+            "PPMT",
+            "PRICE",
+            "PRICEDISC",
+            "PRICEMAT",
+            "PROB",
+            "PRODUCT",
+            "PROPER",
+            "PV",
+            "QUARTILE",
+            "QUOTIENT",
+            "RADIANS",
+            "RAND",
+            "RANDBETWEEN",
+            "RANK",
+            "RATE",
+            "RECEIVED",
+            "REGISTER.ID",
+            "REPLACE",
+            "REPLACEB",
+            "REPT",
+            "RIGHT",
+            "RIGHTB",
+            "ROMAN",
+            "ROUND",
+            "ROUNDDOWN",
+            "ROUNDUP",
+            "ROW",
+            "ROWS",
+            "RSQ",
+            "RTD",
+            "SEARCH",
+            "SEARCHB",
+            "SECOND",
+            "SERIESSUM",
+            "SIGN",
+            "SIN",
+            "SINH",
+            "SKEW",
+            "SLN",
+            "SLOPE",
+            "SMALL",
+            "SQL.REQUEST",
+            "SQRT",
+            "SQRTPI",
+            "STANDARDIZE",
+            "STDEV",
+            "STDEVA",
+            "STDEVP",
+            "STDEVPA",
+            "STEYX",
+            "SUBSTITUTE",
+            "SUBTOTAL",
+            "SUM",
+            "SUMIF",
+            "SUMIFS",
+            "SUMPRODUCT",
+            "SUMSQ",
+            "SUMX2MY2",
+            "SUMX2PY2",
+            "SUMXMY2",
+            "SYD",
+            "T",
+            "TAN",
+            "TANH",
+            "TBILLEQ",
+            "TBILLPRICE",
+            "TBILLYIELD",
+            "TDIST",
+            "TEXT",
+            "TIME",
+            "TIMEVALUE",
+            "TINV",
+            "TODAY",
+            "TRANSPOSE",
+            "TREND",
+            "TRIM",
+            "TRIMMEAN",
+            "TRUE",
+            "TRUNC",
+            "TTEST",
+            "TYPE",
+            "UPPER",
+            "USDOLLAR",
+            "VALUE",
+            "VAR",
+            "VARA",
+            "VARP",
+            "VARPA",
+            "VDB",
+            "VLOOKUP",
+            "WEEKDAY",
+            "WEEKNUM",
+            "WEIBULL",
+            "WORKDAY",
+            "XIRR",
+            "XNPV",
+            "YEAR",
+            "YEARFRAC",
+            "YIELD",
+            "YIELDDISC",
+            "YIELDMAT",
+            "ZTEST",
+        ])
+    })
+}
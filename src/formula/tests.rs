@@ -307,3 +307,439 @@ fn test_escape_table_functions() {
         assert_eq!(formula.formula_string, expected_formula);
     }
 }
+
+#[test]
+fn test_parse_formulas() {
+    use crate::formula::parser::{
+        parse, FormulaBinaryOperator, FormulaExpression, FormulaUnaryOperator,
+    };
+
+    // Check operator precedence and associativity.
+    let expr = parse("1+2*3").unwrap();
+    let FormulaExpression::Binary { op, right, .. } = expr else {
+        panic!("expected a binary expression");
+    };
+    assert_eq!(op, FormulaBinaryOperator::Add);
+    assert!(matches!(
+        *right,
+        FormulaExpression::Binary {
+            op: FormulaBinaryOperator::Multiply,
+            ..
+        }
+    ));
+
+    let expr = parse("-2^2").unwrap();
+    assert!(matches!(
+        expr,
+        FormulaExpression::Binary {
+            op: FormulaBinaryOperator::Power,
+            ..
+        }
+    ));
+
+    let expr = parse("A1:B2#").unwrap();
+    assert!(matches!(
+        expr,
+        FormulaExpression::Unary {
+            op: FormulaUnaryOperator::Spill,
+            ..
+        }
+    ));
+
+    // Check formulas that should parse.
+    let formulas = [
+        "SUM(A1:A10)",
+        "'Sheet 1'!A1+Sheet2!$B$2",
+        "SUM(A:A,1:1)",
+        "_xlfn.XLOOKUP(1,A1:A3,B1:B3)",
+        "IF(A1,,)",
+        "{1,2;-3,4}",
+        r#""a""b"&"c""#,
+        "10%",
+        "Table1[Column1]",
+        "MyName*2",
+        "#N/A",
+    ];
+
+    for formula in formulas {
+        assert!(parse(formula).is_ok(), "failed to parse: {formula}");
+    }
+
+    // Check formulas with syntax errors.
+    let formulas = ["SUM(A1", "1+", "{1,2;3}", "A1 B1)", r#""abc"#];
+
+    for formula in formulas {
+        assert!(parse(formula).is_err(), "unexpected parse: {formula}");
+    }
+}
+
+#[cfg(test)]
+fn calculate_results(
+    workbook: &crate::Workbook,
+) -> std::collections::HashMap<(usize, crate::RowNum, crate::ColNum), String> {
+    use crate::formula::evaluator::calculate_formula_results;
+
+    calculate_formula_results(&workbook.worksheets, &[], false)
+        .into_iter()
+        .map(|(sheet, row, col, result)| ((sheet, row, col), result))
+        .collect()
+}
+
+#[test]
+fn test_calculate_formulas() {
+    use crate::Workbook;
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_column(0, 0, [10, 20, 30, 40]).unwrap();
+    worksheet
+        .write_column(0, 1, ["Apple", "Pear", "Apple", "Plum"])
+        .unwrap();
+
+    let formulas = [
+        ("=1+2*3", "7"),
+        ("=-2^2", "4"),
+        ("=2^3^2", "64"),
+        ("=10/4", "2.5"),
+        ("=1/0", "#DIV/0!"),
+        ("=50%", "0.5"),
+        (r#"="a"&1&TRUE"#, "a1TRUE"),
+        ("=1<2", "TRUE"),
+        (r#"="abc"="ABC""#, "TRUE"),
+        ("=SUM(A1:A4)", "100"),
+        ("=SUM(A:A)", "100"),
+        ("=AVERAGE(A1:A4)", "25"),
+        ("=MAX(A1:A4)-MIN(A1:A4)", "30"),
+        ("=COUNT(A1:B4)", "4"),
+        ("=COUNTA(A1:B4)", "8"),
+        ("=MEDIAN(A1:A4)", "25"),
+        ("=ROUND(2.345,2)", "2.35"),
+        ("=MOD(-3,2)", "1"),
+        ("=SQRT(-1)", "#NUM!"),
+        ("=SUMPRODUCT(A1:A4,A1:A4)", "3000"),
+        (r#"=COUNTIF(B1:B4,"Apple")"#, "2"),
+        (r#"=SUMIF(B1:B4,"Apple",A1:A4)"#, "40"),
+        (r#"=SUMIFS(A1:A4,B1:B4,"P*",A1:A4,">20")"#, "40"),
+        (r#"=AVERAGEIF(A1:A4,">=20")"#, "30"),
+        (r#"=IF(A1>5,"Big","Small")"#, "Big"),
+        ("=IF(FALSE,1)", "FALSE"),
+        ("=IFERROR(1/0,-1)", "-1"),
+        ("=IFNA(NA(),2)", "2"),
+        ("=AND(TRUE,A1>5)", "TRUE"),
+        ("=CHOOSE(2,\"a\",\"b\")", "b"),
+        (r#"=SWITCH(B2,"Apple",1,"Pear",2,0)"#, "2"),
+        ("=LET(x,5,y,x*2,x+y)", "15"),
+        ("=VLOOKUP(30,A1:B4,2,FALSE)", "Apple"),
+        ("=VLOOKUP(35,A1:B4,2)", "Apple"),
+        (r#"=MATCH("Plum",B1:B4,0)"#, "4"),
+        ("=MATCH(25,A1:A4)", "2"),
+        ("=INDEX(A1:B4,3,2)", "Apple"),
+        (r#"=XLOOKUP("Plum",B1:B4,A1:A4)"#, "40"),
+        (r#"=XLOOKUP("Kiwi",B1:B4,A1:A4,"None")"#, "None"),
+        ("=ROWS(A1:B4)*COLUMNS(A1:B4)", "8"),
+        ("=LEN(B1)", "5"),
+        ("=LEFT(B1,3)&RIGHT(B2,2)", "Appar"),
+        (r#"=MID("Hello",2,3)"#, "ell"),
+        (r#"=UPPER("abc")&PROPER("hello world")"#, "ABCHello World"),
+        (r#"=TRIM("  a   b ")"#, "a b"),
+        (r#"=TEXTJOIN(",",TRUE,B1:B4)"#, "Apple,Pear,Apple,Plum"),
+        (r#"=SUBSTITUTE("a-b-c","-","+",2)"#, "a-b+c"),
+        (r#"=FIND("l","Hello")"#, "3"),
+        (r#"=SEARCH("L?O","Hello")"#, "3"),
+        (r##"=TEXT(1234.567,"#,##0.00")"##, "1,234.57"),
+        (r#"=TEXT(0.25,"0%")"#, "25%"),
+        (r#"=TEXT(DATE(2024,1,31),"yyyy-mm-dd")"#, "2024-01-31"),
+        (r#"=TEXT(TIME(13,5,0),"h:mm AM/PM")"#, "1:05 PM"),
+        (r#"=VALUE("1,000")"#, "1000"),
+        ("=1/3&\"\"", "0.333333333333333"),
+        ("=DATE(2024,1,31)", "45322"),
+        ("=DATE(1900,3,1)", "61"),
+        (
+            "=YEAR(45322)&\"-\"&MONTH(45322)&\"-\"&DAY(45322)",
+            "2024-1-31",
+        ),
+        ("=EOMONTH(DATE(2024,1,15),1)", "45351"),
+        ("=EDATE(DATE(2024,1,31),1)", "45351"),
+        ("=WEEKDAY(DATE(2024,1,31))", "4"),
+        ("=NETWORKDAYS(DATE(2024,1,1),DATE(2024,1,31))", "23"),
+        (r#"=DATEDIF(DATE(2020,5,15),DATE(2024,1,31),"Y")"#, "3"),
+        (r#"=DATEVALUE("2024-01-31")"#, "45322"),
+        ("=SUM(SEQUENCE(10))", "55"),
+        ("=INDEX(SORT(A1:A4,1,-1),1)", "40"),
+        ("=ROWS(UNIQUE(B1:B4))", "3"),
+        ("=SUM(FILTER(A1:A4,A1:A4>15))", "90"),
+        ("=FILTER(A1:A4,A1:A4>100)", "#CALC!"),
+        ("=SUM(TAKE(A1:A4,-2))", "70"),
+        ("=SUM(A1:A4*2)", "200"),
+        ("=UNKNOWNFUNCTION(1)", ""),
+        ("=SUM(Table1[Column1])", ""),
+    ];
+
+    for (row, (formula, _)) in formulas.iter().enumerate() {
+        worksheet.write_formula(row as u32, 3, *formula).unwrap();
+    }
+
+    let results = calculate_results(&workbook);
+
+    for (row, (formula, expected)) in formulas.iter().enumerate() {
+        let result = results.get(&(0, row as u32, 3)).map_or("", String::as_str);
+        assert_eq!(result, *expected, "formula: {formula}");
+    }
+}
+
+#[test]
+fn test_calculate_date_functions() {
+    use crate::formula::evaluator::calculate_formula_results;
+    use crate::Workbook;
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // The results in the 1900 and 1904 epochs.
+    let formulas = [
+        ("=DATE(1900,1,1)", "1", "#NUM!"),
+        ("=DATE(1900,1,0)", "0", "#NUM!"),
+        ("=DATE(1900,2,29)", "60", "#NUM!"),
+        ("=DATE(1900,3,0)", "60", "#NUM!"),
+        ("=DATE(1900,3,1)", "61", "#NUM!"),
+        ("=DATE(1904,1,1)", "1462", "0"),
+        ("=DATE(2024,14,1)", "45689", "44227"),
+        ("=DATE(2024,1,-30)", "45261", "43799"),
+        ("=DATE(9999,12,31)", "2958465", "2957003"),
+        ("=DATE(9999,12,32)", "#NUM!", "#NUM!"),
+        ("=EOMONTH(DATE(1900,1,15),1)", "60", "#NUM!"),
+        ("=EOMONTH(DATE(2024,1,15),1)", "45351", "43889"),
+        (
+            r#"=DATEDIF(DATE(2020,5,15),DATE(2024,1,31),"YD")"#,
+            "261",
+            "261",
+        ),
+        (
+            r#"=DATEDIF(DATE(2024,1,15),DATE(2024,3,10),"MD")"#,
+            "24",
+            "24",
+        ),
+        ("=TODAY()>44000", "TRUE", "TRUE"),
+        ("=NOW()-TODAY()<1", "TRUE", "TRUE"),
+    ];
+
+    for (row, (formula, _, _)) in formulas.iter().enumerate() {
+        worksheet.write_formula(row as u32, 0, *formula).unwrap();
+    }
+
+    for is_1904_date in [false, true] {
+        let results: std::collections::HashMap<_, _> =
+            calculate_formula_results(&workbook.worksheets, &[], is_1904_date)
+                .into_iter()
+                .map(|(_, row, _, result)| (row, result))
+                .collect();
+
+        for (row, (formula, expected_1900, expected_1904)) in formulas.iter().enumerate() {
+            let expected = if is_1904_date {
+                expected_1904
+            } else {
+                expected_1900
+            };
+            let result = results.get(&(row as u32)).map_or("", String::as_str);
+            assert_eq!(
+                result, *expected,
+                "formula: {formula}, 1904: {is_1904_date}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_calculate_error_values() {
+    use crate::{ExcelError, Workbook};
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write(0, 0, ExcelError::NA).unwrap();
+    worksheet.write(1, 0, ExcelError::Div0).unwrap();
+    worksheet.write(2, 0, 5).unwrap();
+
+    let formulas = [
+        ("=ISNA(A1)", "TRUE"),
+        ("=ISNA(A2)", "FALSE"),
+        ("=ISERR(A2)", "TRUE"),
+        ("=ISERROR(A1)", "TRUE"),
+        ("=IFERROR(A2,0)", "0"),
+        ("=IFNA(A1,\"Missing\")", "Missing"),
+        ("=A2", "#DIV/0!"),
+        ("=SUM(A3,A1)", "#N/A"),
+    ];
+
+    for (row, (formula, _)) in formulas.iter().enumerate() {
+        worksheet.write_formula(row as u32, 3, *formula).unwrap();
+    }
+
+    let results = calculate_results(&workbook);
+
+    for (row, (formula, expected)) in formulas.iter().enumerate() {
+        let result = results.get(&(0, row as u32, 3)).map_or("", String::as_str);
+        assert_eq!(result, *expected, "formula: {formula}");
+    }
+}
+
+#[test]
+fn test_calculate_formula_dependencies() {
+    use crate::formula::evaluator::calculate_formula_results;
+    use crate::worksheet::{DefinedName, DefinedNameType};
+    use crate::Workbook;
+    use std::collections::HashMap;
+
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_formula(0, 0, "=Sheet2!A1*2").unwrap();
+    worksheet.write_formula(1, 0, "=A1+1").unwrap();
+    worksheet.write_formula(2, 0, "=A4").unwrap();
+    worksheet.write_formula(3, 0, "=A3").unwrap();
+    worksheet
+        .write_dynamic_formula(4, 0, "=SEQUENCE(3)")
+        .unwrap();
+    worksheet.write_formula(4, 1, "=SUM(A5#)").unwrap();
+    worksheet.write_formula(5, 1, "=MissingSheet!A1").unwrap();
+    worksheet.write_formula(6, 1, "=Rate*10").unwrap();
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_formula(0, 0, "=5+5").unwrap();
+    worksheet.write_formula(1, 0, "=Rate*10").unwrap();
+
+    // Add a global and a worksheet local defined name.
+    let mut global_name = DefinedName::new();
+    global_name.name = "Rate".to_string();
+    global_name.range = "0.25".to_string();
+
+    let mut local_name = DefinedName::new();
+    local_name.name = "Rate".to_string();
+    local_name.range = "0.5".to_string();
+    local_name.quoted_sheet_name = "Sheet1".to_string();
+    local_name.name_type = DefinedNameType::Local;
+
+    let defined_names = [global_name, local_name];
+
+    let results = calculate_formula_results(&workbook.worksheets, &defined_names, false);
+    let results: HashMap<_, _> = results
+        .into_iter()
+        .map(|(sheet, row, col, result)| ((sheet, row, col), result))
+        .collect();
+
+    assert_eq!(results[&(0, 0, 0)], "20");
+    assert_eq!(results[&(0, 1, 0)], "21");
+    assert_eq!(results[&(0, 2, 0)], "0");
+    assert_eq!(results[&(0, 3, 0)], "0");
+    assert_eq!(results[&(0, 4, 0)], "1");
+    assert_eq!(results[&(0, 4, 1)], "6");
+    assert_eq!(results[&(0, 5, 1)], "#REF!");
+    assert_eq!(results[&(0, 6, 1)], "5");
+    assert_eq!(results[&(1, 0, 0)], "10");
+    assert_eq!(results[&(1, 1, 0)], "2.5");
+}
+
+#[test]
+fn test_calculate_long_formula_chain() {
+    use crate::Workbook;
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    // Each formula refers to the cell below it, which is calculated later.
+    for row in 0..20_000 {
+        let formula = format!("=A{}+1", row + 2);
+        worksheet.write_formula(row, 0, formula.as_str()).unwrap();
+    }
+    worksheet.write_number(20_000, 0, 1).unwrap();
+
+    // A long circular reference.
+    for row in 0..200 {
+        let formula = format!("=B{}+1", (row + 1) % 200 + 1);
+        worksheet.write_formula(row, 1, formula.as_str()).unwrap();
+    }
+
+    let results = calculate_results(&workbook);
+
+    assert_eq!(results[&(0, 0, 0)], "20001");
+    assert_eq!(results[&(0, 10_000, 0)], "10001");
+    assert_eq!(results[&(0, 19_999, 0)], "2");
+}
+
+#[test]
+fn test_formula_validate() {
+    use crate::Formula;
+
+    assert!(Formula::new("=SUM(A1:A10)").validate().is_ok());
+    assert!(Formula::new("=_xlfn.XLOOKUP(A1,B:B,C:C)")
+        .validate()
+        .is_ok());
+    assert!(Formula::new("=XLOOKUP(A1,B:B,C:C)").validate().is_ok());
+    assert!(Formula::new("=_xludf.MYFUNC(A1)").validate().is_ok());
+    assert!(Formula::new("='Sheet 2'!A1+1").validate().is_ok());
+
+    assert!(Formula::new("=SUM(A1:A10").validate().is_err());
+    assert!(Formula::new("=SUM(A1:XFE1)").validate().is_err());
+    assert!(Formula::new("=XFE1+1").validate().is_err());
+    assert!(Formula::new("=SUMM(A1:A10)").validate().is_err());
+    assert!(Formula::new("=\"abc").validate().is_err());
+}
+
+#[test]
+fn test_strict_formulas() {
+    use crate::{Workbook, XlsxError};
+
+    // Syntax errors are reported when the formula is written.
+    let mut workbook = Workbook::new();
+    workbook.use_strict_formulas(true);
+    let worksheet = workbook.add_worksheet();
+
+    assert!(worksheet.write_formula(0, 0, "=SUM(B1:B5)").is_ok());
+
+    match worksheet.write_formula(1, 0, "=SUM(B1:B5") {
+        Err(XlsxError::FormulaError(message)) => assert!(message.starts_with("Sheet1!A2: ")),
+        _ => panic!("expected a formula error"),
+    }
+
+    assert!(worksheet
+        .write_array_formula(2, 0, 2, 0, "{=A1:A2*}")
+        .is_err());
+
+    // Unknown functions are reported when the workbook is saved.
+    let mut workbook = Workbook::new();
+    workbook.use_strict_formulas(true);
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_formula(0, 0, "=SUMM(B1:B5)").unwrap();
+
+    match workbook.save_to_buffer() {
+        Err(XlsxError::FormulaError(message)) => assert!(message.contains("SUMM")),
+        _ => panic!("expected a formula error"),
+    }
+
+    // References to unknown worksheets are reported when the workbook is saved.
+    let mut workbook = Workbook::new();
+    workbook.use_strict_formulas(true);
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_formula(0, 0, "=Sheet2!A1").unwrap();
+
+    assert!(workbook.save_to_buffer().is_err());
+
+    workbook.add_worksheet();
+    assert!(workbook.save_to_buffer().is_ok());
+
+    // Defined names are allowed as functions, for LAMBDA().
+    let mut workbook = Workbook::new();
+    workbook.use_strict_formulas(true);
+    workbook.define_name("Double", "=LAMBDA(x, x*2)").unwrap();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_formula(0, 0, "=Double(2)").unwrap();
+
+    assert!(workbook.save_to_buffer().is_ok());
+
+    // Strict mode is off by default.
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    assert!(worksheet.write_formula(0, 0, "=SUM(B1:B5").is_ok());
+    assert!(workbook.save_to_buffer().is_ok());
+}
@@ -67,6 +67,7 @@ pub struct Image {
     pub(crate) alt_text: String,
     pub(crate) vml_name: String,
     pub(crate) header_position: HeaderImagePosition,
+    pub(crate) header_page: HeaderFooterPage,
     pub(crate) object_movement: ObjectMovement,
     pub(crate) is_header: bool,
     pub(crate) decorative: bool,
@@ -256,6 +257,7 @@ impl Image {
             alt_text: String::new(),
             vml_name: "image".to_string(),
             header_position: HeaderImagePosition::Center,
+            header_page: HeaderFooterPage::Odd,
             object_movement: ObjectMovement::MoveButDontSizeWithCells,
             is_header: true,
             decorative: false,
@@ -883,18 +885,24 @@ impl Image {
 
     // Get the image position string as used by header/footer VML.
     fn vml_position(&self) -> String {
-        if self.is_header {
+        let position = if self.is_header {
             match self.header_position {
-                HeaderImagePosition::Left => "LH".to_string(),
-                HeaderImagePosition::Right => "RH".to_string(),
-                HeaderImagePosition::Center => "CH".to_string(),
+                HeaderImagePosition::Left => "LH",
+                HeaderImagePosition::Right => "RH",
+                HeaderImagePosition::Center => "CH",
             }
         } else {
             match self.header_position {
-                HeaderImagePosition::Left => "LF".to_string(),
-                HeaderImagePosition::Right => "RF".to_string(),
-                HeaderImagePosition::Center => "CF".to_string(),
+                HeaderImagePosition::Left => "LF",
+                HeaderImagePosition::Right => "RF",
+                HeaderImagePosition::Center => "CF",
             }
+        };
+
+        match self.header_page {
+            HeaderFooterPage::Odd => position.to_string(),
+            HeaderFooterPage::First => format!("{position}FIRST"),
+            HeaderFooterPage::Even => format!("{position}EVEN"),
         }
     }
 
@@ -1221,6 +1229,34 @@ pub enum HeaderImagePosition {
     Right,
 }
 
+/// The `HeaderFooterPage` enum defines the printed pages that a header or
+/// footer applies to.
+///
+/// Used with the
+/// [`Worksheet::set_header_with_page()`](crate::Worksheet::set_header_with_page),
+/// [`Worksheet::set_footer_with_page()`](crate::Worksheet::set_footer_with_page),
+/// [`Worksheet::set_header_image_with_page()`](crate::Worksheet::set_header_image_with_page)
+/// and
+/// [`Worksheet::set_footer_image_with_page()`](crate::Worksheet::set_footer_image_with_page)
+/// methods.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HeaderFooterPage {
+    /// The header/footer applies to the odd pages if there is an even page
+    /// header/footer, otherwise it applies to all pages. This is the default
+    /// and is the same as the header/footer set by
+    /// [`Worksheet::set_header()`](crate::Worksheet::set_header) and
+    /// [`Worksheet::set_footer()`](crate::Worksheet::set_footer).
+    #[default]
+    Odd,
+
+    /// The header/footer applies to the first page only.
+    First,
+
+    /// The header/footer applies to the even pages.
+    Even,
+}
+
 #[derive(Clone, Debug)]
 pub(crate) enum XlsxImageType {
     Unknown,
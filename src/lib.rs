@@ -280,6 +280,10 @@
 //! - `constant_memory`: Keeps memory usage to a minimum when writing large
 //!   files. See [Constant Memory
 //!   Mode](performance/index.html#constant-memory-mode).
+//! - `encryption`: Adds support for encrypting the xlsx file with a password
+//!   that is required to open it. See `Workbook::set_open_password()`.
+//! - `protection_hash`: Adds support for storing protection passwords as a
+//!   salted SHA-512 hash. See `Worksheet::set_protection_hash()`.
 //! - `enhanced_autofit`: Adds support for autofitting formatted numbers and
 //!   dates via the [`ssfmt`](https://crates.io/crates/ssfmt) crate.
 //! - `serde`: Adds support for Serde serialization.
@@ -289,8 +293,12 @@
 //!   [`IntoExcelDateTime`].
 //! - `zlib`: Improves performance of the `zlib` crate but adds a dependency on
 //!   zlib and a C compiler. This can be up to 1.5 times faster for large files.
-//! - `polars`: Adds support for mapping between `PolarsError` and
-//!   `rust_xlsxwriter::XlsxError` to make code that handles both types of
+//! - `arrow`: Adds support for writing Apache Arrow `RecordBatch` data with
+//!   [`Worksheet::write_record_batch()`] and
+//!   [`Worksheet::write_record_batches()`].
+//! - `polars`: Adds support for writing Polars dataframes with
+//!   [`Worksheet::write_dataframe()`] and for mapping between `PolarsError`
+//!   and `rust_xlsxwriter::XlsxError` to make code that handles both types of
 //!   errors easier to write. See also
 //!   [`polars_excel_writer`](https://crates.io/crates/polars_excel_writer).
 //! - `wasm`: Adds a dependency on `js-sys` and `wasm-bindgen` to allow
@@ -311,6 +319,7 @@
 //!
 mod app;
 mod button;
+mod calculation;
 mod color;
 mod comment;
 mod content_types;
@@ -328,6 +337,7 @@ mod image;
 mod metadata;
 mod note;
 mod packager;
+mod pivot_table;
 mod properties;
 mod protection;
 mod relationship;
@@ -338,13 +348,29 @@ mod rich_value_types;
 mod shape;
 mod shared_strings;
 mod shared_strings_table;
+mod slicer;
 mod styles;
 mod table;
+mod template;
 mod theme;
+mod threaded_comment;
 mod url;
 mod vml;
+mod workbook_view;
 mod xmlwriter;
 
+#[cfg(feature = "encryption")]
+mod encryption;
+
+#[cfg(any(feature = "polars", feature = "arrow"))]
+mod dataframe;
+
+#[cfg(feature = "serde")]
+mod deserializer;
+
+#[cfg(feature = "arrow")]
+mod record_batch;
+
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 pub mod serializer;
@@ -366,6 +392,7 @@ mod test_functions;
 
 // Re-export the public APIs.
 pub use button::*;
+pub use calculation::*;
 pub use color::*;
 pub use data_validation::*;
 pub use datetime::*;
@@ -375,11 +402,15 @@ pub use format::*;
 pub use formula::*;
 pub use image::*;
 pub use note::*;
+pub use pivot_table::*;
 pub use properties::*;
 pub use protection::*;
 pub use shape::*;
+pub use slicer::*;
 pub use table::*;
+pub use threaded_comment::*;
 pub use url::*;
+pub use workbook_view::*;
 
 #[doc(hidden)]
 pub use chart::*;
@@ -406,6 +437,10 @@ pub use utility::*;
 #[doc(hidden)]
 pub use serializer::*;
 
+#[cfg(any(feature = "polars", feature = "arrow"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "polars", feature = "arrow"))))]
+pub use dataframe::*;
+
 #[cfg(feature = "serde")]
 extern crate rust_xlsxwriter_derive;
 
@@ -71,9 +71,8 @@ use crate::{ColNum, Color, FontScheme, Format, ObjectMovement, RowNum, COL_MAX,
 /// [The difference between threaded comments and notes]:
 ///     https://support.microsoft.com/en-us/office/the-difference-between-threaded-comments-and-notes-75a51eec-4092-42ab-abf8-7669077b7be3
 ///
-/// Note, the newer Threaded Comments are unlikely to be added to
-/// `rust_xlsxwriter` due to the fact that the feature relies on company
-/// specific metadata to identify the comment author.
+/// The newer style threaded comments are supported via
+/// [`ThreadedComment`](crate::ThreadedComment).
 ///
 pub struct Note {
     height: f64,
@@ -40,7 +40,7 @@
 // The Packager struct coordinates the classes that represent the elements of
 // the package and writes them into the xlsx file.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::io::Write;
 
 #[cfg(feature = "constant_memory")]
@@ -67,12 +67,15 @@ use crate::rich_value_structure::RichValueStructure;
 use crate::rich_value_types::RichValueTypes;
 use crate::shared_strings::SharedStrings;
 use crate::shared_strings_table::SharedStringsTable;
+use crate::slicer::Slicers;
 use crate::styles::Styles;
+use crate::template::{relationships_xml, TemplateRelationship};
 use crate::theme::Theme;
+use crate::threaded_comment::{Persons, ThreadedComments};
 use crate::vml::Vml;
 use crate::workbook::Workbook;
 use crate::worksheet::Worksheet;
-use crate::{xmlwriter, Comment, DocProperties, Visible, NUM_IMAGE_FORMATS};
+use crate::{xmlwriter, Comment, DocProperties, Slicer, Visible, NUM_IMAGE_FORMATS};
 
 // Packager struct to assemble the xlsx file.
 pub struct Packager<W: Write> {
@@ -113,8 +116,8 @@ impl<W: Write + Send> Packager<W> {
         options: &PackagerOptions,
     ) -> Result<(), XlsxError> {
         // Write the sub-component files.
-        self.write_content_types_file(options)?;
-        self.write_root_rels_file(options)?;
+        self.write_content_types_file(workbook, options)?;
+        self.write_root_rels_file(workbook, options)?;
         self.write_workbook_rels_file(workbook, options)?;
         self.write_theme_file(&workbook.theme_xml)?;
         self.write_styles_file(workbook)?;
@@ -151,7 +154,12 @@ impl<W: Write + Send> Packager<W> {
             }
 
             self.write_worksheet_file(worksheet, index)?;
-            if worksheet.has_relationships() {
+            if let Some(template) = &worksheet.template {
+                if !template.relationships.is_empty() {
+                    let filename = format!("xl/worksheets/_rels/sheet{index}.xml.rels");
+                    self.write_template_rels_file(&template.relationships, filename)?;
+                }
+            } else if worksheet.has_relationships() {
                 self.write_worksheet_rels_file(worksheet, index)?;
             }
 
@@ -166,7 +174,12 @@ impl<W: Write + Send> Packager<W> {
             }
 
             self.write_chartsheet_file(worksheet, index)?;
-            if worksheet.has_relationships() {
+            if let Some(template) = &worksheet.template {
+                if !template.relationships.is_empty() {
+                    let filename = format!("xl/chartsheets/_rels/sheet{index}.xml.rels");
+                    self.write_template_rels_file(&template.relationships, filename)?;
+                }
+            } else if worksheet.has_relationships() {
                 self.write_chartsheet_rels_file(worksheet, index)?;
             }
 
@@ -185,10 +198,14 @@ impl<W: Write + Send> Packager<W> {
         self.write_drawing_files(workbook)?;
         self.write_vml_files(workbook)?;
         self.write_comment_files(workbook)?;
+        self.write_threaded_comment_files(workbook)?;
         self.write_image_files(workbook)?;
         self.write_chart_files(workbook)?;
         self.write_table_files(workbook)?;
+        self.write_pivot_table_files(workbook)?;
+        self.write_slicer_files(workbook)?;
         self.write_vba_project(workbook)?;
+        self.write_template_files(workbook)?;
 
         let mut rel_index = 0;
 
@@ -226,7 +243,11 @@ impl<W: Write + Send> Packager<W> {
     // -----------------------------------------------------------------------
 
     // Write the [Content_Types].xml file.
-    fn write_content_types_file(&mut self, options: &PackagerOptions) -> Result<(), XlsxError> {
+    fn write_content_types_file(
+        &mut self,
+        workbook: &Workbook,
+        options: &PackagerOptions,
+    ) -> Result<(), XlsxError> {
         let mut content_types = ContentTypes::new();
 
         // Change the workbook application types based on whether it is an xlsx
@@ -266,14 +287,38 @@ impl<W: Write + Send> Packager<W> {
             content_types.add_chart_name(i + 1);
         }
 
+        for i in 0..options.num_chart_exs {
+            content_types.add_chart_ex_name(i + 1);
+        }
+
         for i in 0..options.num_tables {
             content_types.add_table_name(i + 1);
         }
 
+        for i in 0..options.num_pivot_tables {
+            content_types.add_pivot_table_name(i + 1);
+        }
+
+        for i in 0..options.num_slicer_parts {
+            content_types.add_slicer_name(i + 1);
+        }
+
+        for i in 0..options.num_slicer_caches {
+            content_types.add_slicer_cache_name(i + 1);
+        }
+
         for i in 0..options.num_comments {
             content_types.add_comments_name(i + 1);
         }
 
+        for i in 0..options.num_threaded_comments {
+            content_types.add_threaded_comments_name(i + 1);
+        }
+
+        if options.num_threaded_comments > 0 {
+            content_types.add_persons();
+        }
+
         if options.has_sst_table {
             content_types.add_share_strings();
         }
@@ -319,6 +364,17 @@ impl<W: Write + Send> Packager<W> {
             content_types.add_default("bin", "application/vnd.ms-office.vbaProject");
         }
 
+        // Add the content types for the parts passed through from a template.
+        if let Some(template) = &workbook.template {
+            for (extension, content_type) in &template.content_type_defaults {
+                content_types.add_default(extension, content_type);
+            }
+
+            for (part_name, content_type) in &template.content_type_overrides {
+                content_types.add_override(part_name, content_type);
+            }
+        }
+
         self.zip
             .start_file("[Content_Types].xml", self.zip_options)?;
 
@@ -329,7 +385,11 @@ impl<W: Write + Send> Packager<W> {
     }
 
     // Write the root-level _rels/.rels XML file.
-    fn write_root_rels_file(&mut self, options: &PackagerOptions) -> Result<(), XlsxError> {
+    fn write_root_rels_file(
+        &mut self,
+        workbook: &Workbook,
+        options: &PackagerOptions,
+    ) -> Result<(), XlsxError> {
         let mut rels = Relationship::new();
 
         rels.add_document_relationship("officeDocument", "xl/workbook.xml", "");
@@ -339,6 +399,18 @@ impl<W: Write + Send> Packager<W> {
         if !options.properties.custom_properties.is_empty() {
             rels.add_document_relationship("custom-properties", "docProps/custom.xml", "");
         }
+
+        // Add the root relationships passed through from a template, such as
+        // a thumbnail image.
+        if let Some(template) = &workbook.template {
+            for relationship in &template.root_relationships {
+                rels.add_template_relationship(
+                    &relationship.rel_type,
+                    &relationship.target,
+                    &relationship.target_mode,
+                );
+            }
+        }
         self.zip.start_file("_rels/.rels", self.zip_options)?;
 
         rels.assemble_xml_file();
@@ -377,6 +449,23 @@ impl<W: Write + Send> Packager<W> {
             }
         }
 
+        for i in 0..options.num_pivot_tables {
+            rels.add_document_relationship(
+                "pivotCacheDefinition",
+                format!("pivotCache/pivotCacheDefinition{}.xml", i + 1).as_str(),
+                "",
+            );
+        }
+
+        for i in 0..options.num_slicer_caches {
+            rels.add_office_relationship(
+                "2007",
+                "slicerCache",
+                format!("slicerCaches/slicerCache{}.xml", i + 1).as_str(),
+                "",
+            );
+        }
+
         rels.add_document_relationship("theme", "theme/theme1.xml", "");
         rels.add_document_relationship("styles", "styles.xml", "");
 
@@ -426,6 +515,29 @@ impl<W: Write + Send> Packager<W> {
             );
         }
 
+        if options.num_threaded_comments > 0 {
+            rels.add_office_relationship("2017/10", "person", "persons/person.xml", "");
+        }
+
+        // Add the workbook relationships passed through from a template and
+        // store the new relationship ids so that the references to them in
+        // the workbook.xml file can be updated.
+        if let Some(template) = &mut workbook.template {
+            template.workbook_rel_ids.clear();
+
+            for relationship in &template.workbook_relationships {
+                let rel_id = rels.add_template_relationship(
+                    &relationship.rel_type,
+                    &relationship.target,
+                    &relationship.target_mode,
+                );
+
+                template
+                    .workbook_rel_ids
+                    .insert(relationship.id.clone(), rel_id);
+            }
+        }
+
         self.zip
             .start_file("xl/_rels/workbook.xml.rels", self.zip_options)?;
 
@@ -511,10 +623,27 @@ impl<W: Write + Send> Packager<W> {
             rels.add_document_relationship(&relationship.0, &relationship.1, &relationship.2);
         }
 
+        for relationship in &worksheet.slicer_relationships {
+            rels.add_office_relationship("2007", &relationship.0, &relationship.1, &relationship.2);
+        }
+
         for relationship in &worksheet.comment_relationships {
             rels.add_document_relationship(&relationship.0, &relationship.1, &relationship.2);
         }
 
+        for relationship in &worksheet.threaded_comment_relationships {
+            rels.add_office_relationship(
+                "2017/10",
+                &relationship.0,
+                &relationship.1,
+                &relationship.2,
+            );
+        }
+
+        for relationship in &worksheet.pivot_table_relationships {
+            rels.add_document_relationship(&relationship.0, &relationship.1, &relationship.2);
+        }
+
         let filename = format!("xl/worksheets/_rels/sheet{index}.xml.rels");
 
         self.zip.start_file(filename, self.zip_options)?;
@@ -547,6 +676,19 @@ impl<W: Write + Send> Packager<W> {
         Ok(())
     }
 
+    // Write a rels file for a template part, keeping the relationship ids used
+    // by the part.
+    fn write_template_rels_file(
+        &mut self,
+        relationships: &[TemplateRelationship],
+        filename: String,
+    ) -> Result<(), XlsxError> {
+        self.zip.start_file(filename, self.zip_options)?;
+        self.zip.write_all(&relationships_xml(relationships))?;
+
+        Ok(())
+    }
+
     // Write a drawing rels file.
     pub(crate) fn write_drawing_rels_file(
         &mut self,
@@ -556,7 +698,16 @@ impl<W: Write + Send> Packager<W> {
         let mut rels = Relationship::new();
 
         for relationship in relationships {
-            rels.add_document_relationship(&relationship.0, &relationship.1, &relationship.2);
+            if relationship.0 == "chartEx" {
+                rels.add_office_relationship(
+                    "2014",
+                    &relationship.0,
+                    &relationship.1,
+                    &relationship.2,
+                );
+            } else {
+                rels.add_document_relationship(&relationship.0, &relationship.1, &relationship.2);
+            }
         }
 
         let filename = format!("xl/drawings/_rels/drawing{index}.xml.rels");
@@ -672,7 +823,15 @@ impl<W: Write + Send> Packager<W> {
         self.zip.start_file("xl/styles.xml", self.zip_options)?;
 
         styles.assemble_xml_file();
-        self.zip.write_all(styles.writer.get_ref())?;
+
+        // Merge the workbook formats into the styles of a template file.
+        if let Some(template) = &workbook.template {
+            let generated = xmlwriter::cursor_to_string(&styles.writer);
+            let merged = template.styles.merge(&generated);
+            self.zip.write_all(merged.as_bytes())?;
+        } else {
+            self.zip.write_all(styles.writer.get_ref())?;
+        }
 
         Ok(())
     }
@@ -949,6 +1108,51 @@ impl<W: Write + Send> Packager<W> {
         Ok(())
     }
 
+    // Write the threaded comment files and the workbook persons file.
+    fn write_threaded_comment_files(&mut self, workbook: &mut Workbook) -> Result<(), XlsxError> {
+        if workbook.persons.is_empty() {
+            return Ok(());
+        }
+
+        let person_ids: BTreeMap<String, String> = workbook
+            .persons
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (name.clone(), Persons::person_id(index)))
+            .collect();
+
+        let mut index = 1;
+        for worksheet in &workbook.worksheets {
+            if worksheet.threaded_comments.is_empty() {
+                continue;
+            }
+
+            let filename = format!("xl/threadedComments/threadedComment{index}.xml");
+            self.zip.start_file(filename, self.zip_options)?;
+
+            let mut threaded_comments = ThreadedComments::new();
+            threaded_comments
+                .comments
+                .clone_from(&worksheet.threaded_comments);
+            threaded_comments.person_ids.clone_from(&person_ids);
+
+            threaded_comments.assemble_xml_file();
+            self.zip.write_all(threaded_comments.writer.get_ref())?;
+            index += 1;
+        }
+
+        self.zip
+            .start_file("xl/persons/person.xml", self.zip_options)?;
+
+        let mut persons = Persons::new();
+        persons.persons.clone_from(&workbook.persons);
+
+        persons.assemble_xml_file();
+        self.zip.write_all(persons.writer.get_ref())?;
+
+        Ok(())
+    }
+
     // Write the image files.
     fn write_image_files(&mut self, workbook: &mut Workbook) -> Result<(), XlsxError> {
         let mut index = 1;
@@ -1012,14 +1216,26 @@ impl<W: Write + Send> Packager<W> {
     // Write the chart files.
     fn write_chart_files(&mut self, workbook: &mut Workbook) -> Result<(), XlsxError> {
         let mut index = 1;
+        let mut chart_ex_index = 1;
 
         for worksheet in &mut workbook.worksheets {
             for chart in worksheet.charts.values_mut() {
-                let filename = format!("xl/charts/chart{index}.xml");
+                // The Excel 2016 chartex charts are numbered separately.
+                let filename = if chart.is_chart_ex() {
+                    format!("xl/charts/chartEx{chart_ex_index}.xml")
+                } else {
+                    format!("xl/charts/chart{index}.xml")
+                };
+
                 self.zip.start_file(filename, self.zip_options)?;
                 chart.assemble_xml_file();
                 self.zip.write_all(chart.writer.get_ref())?;
-                index += 1;
+
+                if chart.is_chart_ex() {
+                    chart_ex_index += 1;
+                } else {
+                    index += 1;
+                }
             }
         }
 
@@ -1043,6 +1259,103 @@ impl<W: Write + Send> Packager<W> {
         Ok(())
     }
 
+    // Write the pivot table and pivot cache files and their rel files.
+    fn write_pivot_table_files(&mut self, workbook: &mut Workbook) -> Result<(), XlsxError> {
+        let mut index = 1;
+
+        for worksheet in &mut workbook.worksheets {
+            for pivot_table in &mut worksheet.pivot_tables {
+                let filename = format!("xl/pivotTables/pivotTable{index}.xml");
+                self.zip.start_file(filename, self.zip_options)?;
+                pivot_table.assemble_xml_file();
+                self.zip.write_all(pivot_table.writer.get_ref())?;
+
+                let mut rels = Relationship::new();
+                rels.add_document_relationship(
+                    "pivotCacheDefinition",
+                    format!("../pivotCache/pivotCacheDefinition{index}.xml").as_str(),
+                    "",
+                );
+
+                let filename = format!("xl/pivotTables/_rels/pivotTable{index}.xml.rels");
+                self.zip.start_file(filename, self.zip_options)?;
+                rels.assemble_xml_file();
+                self.zip.write_all(rels.writer.get_ref())?;
+
+                let filename = format!("xl/pivotCache/pivotCacheDefinition{index}.xml");
+                self.zip.start_file(filename, self.zip_options)?;
+                pivot_table.assemble_cache_xml_file();
+                self.zip.write_all(pivot_table.cache_writer.get_ref())?;
+
+                let mut rels = Relationship::new();
+                rels.add_document_relationship(
+                    "pivotCacheRecords",
+                    format!("pivotCacheRecords{index}.xml").as_str(),
+                    "",
+                );
+
+                let filename = format!("xl/pivotCache/_rels/pivotCacheDefinition{index}.xml.rels");
+                self.zip.start_file(filename, self.zip_options)?;
+                rels.assemble_xml_file();
+                self.zip.write_all(rels.writer.get_ref())?;
+
+                let filename = format!("xl/pivotCache/pivotCacheRecords{index}.xml");
+                self.zip.start_file(filename, self.zip_options)?;
+                pivot_table.assemble_records_xml_file();
+                self.zip.write_all(pivot_table.records_writer.get_ref())?;
+
+                index += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Write the slicer and slicer cache files.
+    fn write_slicer_files(&mut self, workbook: &mut Workbook) -> Result<(), XlsxError> {
+        let mut slicer_index = 1;
+        let mut cache_index = 1;
+
+        for worksheet in &mut workbook.worksheets {
+            if worksheet.slicers.is_empty() {
+                continue;
+            }
+
+            let slicers: Vec<Slicer> = worksheet.slicers.values().cloned().collect();
+
+            let filename = format!("xl/slicers/slicer{slicer_index}.xml");
+            self.zip.start_file(filename, self.zip_options)?;
+            let mut slicer_part = Slicers::new();
+            slicer_part.assemble_xml_file(&slicers);
+            self.zip.write_all(slicer_part.writer.get_ref())?;
+            slicer_index += 1;
+
+            for slicer in worksheet.slicers.values_mut() {
+                let filename = format!("xl/slicerCaches/slicerCache{cache_index}.xml");
+                self.zip.start_file(filename, self.zip_options)?;
+                slicer.assemble_cache_xml_file();
+                self.zip.write_all(slicer.writer.get_ref())?;
+                cache_index += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Write the parts passed through from a template file.
+    fn write_template_files(&mut self, workbook: &Workbook) -> Result<(), XlsxError> {
+        let Some(template) = &workbook.template else {
+            return Ok(());
+        };
+
+        for (filename, data) in &template.parts {
+            self.zip.start_file(filename, self.zip_options)?;
+            self.zip.write_all(data)?;
+        }
+
+        Ok(())
+    }
+
     // Write the VBA project file.
     fn write_vba_project(&mut self, workbook: &mut Workbook) -> Result<(), XlsxError> {
         if !workbook.is_xlsm_file {
@@ -1110,7 +1423,12 @@ pub(crate) struct PackagerOptions {
     pub(crate) num_chartsheets: u16,
     pub(crate) num_drawings: u16,
     pub(crate) num_charts: u16,
+    pub(crate) num_chart_exs: u16,
     pub(crate) num_tables: u16,
+    pub(crate) num_pivot_tables: u16,
+    pub(crate) num_slicer_parts: u16,
+    pub(crate) num_slicer_caches: u16,
+    pub(crate) num_threaded_comments: u16,
     pub(crate) num_comments: u16,
     pub(crate) doc_security: u8,
     pub(crate) worksheet_names: Vec<String>,
@@ -1137,7 +1455,12 @@ impl PackagerOptions {
             num_chartsheets: 0,
             num_drawings: 0,
             num_charts: 0,
+            num_chart_exs: 0,
             num_tables: 0,
+            num_pivot_tables: 0,
+            num_slicer_parts: 0,
+            num_slicer_caches: 0,
+            num_threaded_comments: 0,
             num_comments: 0,
             doc_security: 0,
             worksheet_names: vec![],
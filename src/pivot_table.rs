@@ -0,0 +1,2089 @@
+// pivot_table - A module for creating the Excel pivotTable.xml and
+// pivotCache*.xml files.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+mod tests;
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::io::Cursor;
+
+use crate::xmlwriter::{
+    xml_declaration, xml_empty_tag, xml_empty_tag_only, xml_end_tag, xml_start_tag,
+    xml_start_tag_only,
+};
+use crate::{
+    utility::ToXmlBoolean, CellRange, ChartRange, ExcelDateTime, ExcelError, IntoChartRange,
+    XlsxError,
+};
+
+/// The `PivotTable` struct represents a worksheet pivot table.
+///
+/// Pivot tables in Excel are used to summarize, group, sort and aggregate the
+/// data in a worksheet range or [`Table`](crate::Table). The summary data is
+/// arranged by row, column and filter (page) fields, and the values are
+/// aggregated into data fields using functions such as Sum, Count or Average.
+///
+/// A pivot table is added to a worksheet via the
+/// [`Worksheet::add_pivot_table()`](crate::Worksheet::add_pivot_table) method.
+/// The source data for the pivot table should be written to a worksheet using
+/// the standard [`Worksheet::write()`](crate::Worksheet::write) methods. The
+/// first row of the source range is used for the field names.
+///
+/// The pivot table "cache" of the source data is generated from the worksheet
+/// data when the file is saved. The file is also flagged so that Excel
+/// refreshes the pivot table when it is opened, which generates the
+/// summarized values and layout of the table.
+///
+/// ```
+/// # // This code is available in examples/doc_pivot_table_intro.rs
+/// #
+/// use rust_xlsxwriter::{PivotDataField, PivotField, PivotFunction, PivotTable, Workbook, XlsxError};
+///
+/// fn main() -> Result<(), XlsxError> {
+///     // Create a new Excel file object.
+///     let mut workbook = Workbook::new();
+///
+///     // Add a worksheet with some source data for the pivot table.
+///     let data = workbook.add_worksheet().set_name("Data")?;
+///
+///     data.write_row(0, 0, ["Region", "Product", "Quarter", "Sales"])?;
+///     data.write_row(1, 0, ["East", "Apples", "Q1"])?;
+///     data.write_row(2, 0, ["West", "Apples", "Q1"])?;
+///     data.write_row(3, 0, ["East", "Pears", "Q2"])?;
+///     data.write_row(4, 0, ["West", "Pears", "Q2"])?;
+///     data.write_column(1, 3, [1000, 2500, 3000, 1500])?;
+///
+///     // Create a new pivot table based on the source data.
+///     let pivot_table = PivotTable::new()
+///         .set_source_range(("Data", 0, 0, 4, 3))
+///         .set_row_fields(&[PivotField::new("Region")])
+///         .set_column_fields(&[PivotField::new("Quarter")])
+///         .set_data_fields(&[PivotDataField::new("Sales").set_function(PivotFunction::Sum)]);
+///
+///     // Add the pivot table to another worksheet.
+///     let worksheet = workbook.add_worksheet();
+///     worksheet.add_pivot_table(2, 0, &pivot_table)?;
+///
+///     // Save the file to disk.
+///     workbook.save("pivot_table.xlsx")?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// For more information on pivot tables see the Microsoft documentation on
+/// [Overview of PivotTables and PivotCharts].
+///
+/// [Overview of PivotTables and PivotCharts]:
+///     https://support.microsoft.com/en-us/office/overview-of-pivottables-and-pivotcharts-527c8fa3-02c0-445a-a2db-7794676bce96
+///
+#[derive(Clone)]
+pub struct PivotTable {
+    pub(crate) writer: Cursor<Vec<u8>>,
+    pub(crate) cache_writer: Cursor<Vec<u8>>,
+    pub(crate) records_writer: Cursor<Vec<u8>>,
+
+    pub(crate) index: u32,
+    pub(crate) name: String,
+    pub(crate) source: PivotTableSource,
+    pub(crate) cell_range: CellRange,
+
+    row_fields: Vec<PivotField>,
+    column_fields: Vec<PivotField>,
+    page_fields: Vec<PivotField>,
+    data_fields: Vec<PivotDataField>,
+
+    style: PivotTableStyle,
+    show_row_headers: bool,
+    show_column_headers: bool,
+    show_banded_rows: bool,
+    show_banded_columns: bool,
+    show_row_grand_totals: bool,
+    show_column_grand_totals: bool,
+
+    cache: PivotCache,
+    row_field_ids: Vec<usize>,
+    column_field_ids: Vec<usize>,
+    page_field_ids: Vec<usize>,
+    data_field_ids: Vec<usize>,
+    row_items: Vec<PivotItem>,
+    column_items: Vec<PivotItem>,
+}
+
+impl PivotTable {
+    // -----------------------------------------------------------------------
+    // Public (and crate public) methods.
+    // -----------------------------------------------------------------------
+
+    /// Create a new `PivotTable` struct instance.
+    ///
+    /// Create a pivot table that can be added to a worksheet via
+    /// [`Worksheet::add_pivot_table()`](crate::Worksheet::add_pivot_table).
+    /// The source data, fields and other properties can be set via the
+    /// `PivotTable::*` methods shown below.
+    ///
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> PivotTable {
+        PivotTable {
+            writer: Cursor::new(Vec::with_capacity(2048)),
+            cache_writer: Cursor::new(Vec::with_capacity(2048)),
+            records_writer: Cursor::new(Vec::with_capacity(2048)),
+            index: 0,
+            name: String::new(),
+            source: PivotTableSource::None,
+            cell_range: CellRange::default(),
+            row_fields: vec![],
+            column_fields: vec![],
+            page_fields: vec![],
+            data_fields: vec![],
+            style: PivotTableStyle::Light16,
+            show_row_headers: true,
+            show_column_headers: true,
+            show_banded_rows: false,
+            show_banded_columns: false,
+            show_row_grand_totals: true,
+            show_column_grand_totals: true,
+            cache: PivotCache::default(),
+            row_field_ids: vec![],
+            column_field_ids: vec![],
+            page_field_ids: vec![],
+            data_field_ids: vec![],
+            row_items: vec![],
+            column_items: vec![],
+        }
+    }
+
+    /// Set the worksheet range for the pivot table source data.
+    ///
+    /// The source range should include a header row with the names of the
+    /// fields in the pivot table followed by at least one row of data. The
+    /// header names must be unique and non-blank.
+    ///
+    /// # Parameters
+    ///
+    /// - `range`: A 2D range that contains the source data for the pivot
+    ///   table. This can specified in different ways, see [`IntoChartRange`]
+    ///   for details.
+    ///
+    pub fn set_source_range<T>(mut self, range: T) -> PivotTable
+    where
+        T: IntoChartRange,
+    {
+        self.source = PivotTableSource::Range(range.new_chart_range());
+        self
+    }
+
+    /// Set a worksheet [`Table`](crate::Table) as the pivot table source data.
+    ///
+    /// The table is referred to by its name, which is either the name set via
+    /// [`Table::set_name()`](crate::Table::set_name) or the default Excel name
+    /// such as `Table1`, `Table2`, etc. The table must have a header row. Any
+    /// total row is excluded from the source data.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of a worksheet table in the workbook.
+    ///
+    pub fn set_source_table(mut self, name: impl Into<String>) -> PivotTable {
+        self.source = PivotTableSource::Table(name.into());
+        self
+    }
+
+    /// Set the name of the pivot table.
+    ///
+    /// Excel uses default names such as `PivotTable1`, `PivotTable2`, etc.
+    /// This method can be used to set a user defined name.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the pivot table.
+    ///
+    pub fn set_name(mut self, name: impl Into<String>) -> PivotTable {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the fields that are displayed as row labels in the pivot table.
+    ///
+    /// Multiple fields are nested in the order they are specified.
+    ///
+    /// # Parameters
+    ///
+    /// - `fields`: A slice of [`PivotField`] structs that refer to the source
+    ///   data fields by name.
+    ///
+    pub fn set_row_fields(mut self, fields: &[PivotField]) -> PivotTable {
+        self.row_fields = fields.to_vec();
+        self
+    }
+
+    /// Set the fields that are displayed as column labels in the pivot table.
+    ///
+    /// Multiple fields are nested in the order they are specified.
+    ///
+    /// # Parameters
+    ///
+    /// - `fields`: A slice of [`PivotField`] structs that refer to the source
+    ///   data fields by name.
+    ///
+    pub fn set_column_fields(mut self, fields: &[PivotField]) -> PivotTable {
+        self.column_fields = fields.to_vec();
+        self
+    }
+
+    /// Set the page fields that are used to filter the pivot table.
+    ///
+    /// Page fields are shown as "Filters" in Excel's Pivot Table field list.
+    /// They are displayed in the rows above the pivot table, with a blank row
+    /// between them and the table. The worksheet row of the pivot table must
+    /// leave room for them.
+    ///
+    /// # Parameters
+    ///
+    /// - `fields`: A slice of [`PivotField`] structs that refer to the source
+    ///   data fields by name.
+    ///
+    pub fn set_page_fields(mut self, fields: &[PivotField]) -> PivotTable {
+        self.page_fields = fields.to_vec();
+        self
+    }
+
+    /// Set the data fields that are aggregated in the body of the pivot table.
+    ///
+    /// If there is more than one data field Excel adds a virtual "Values"
+    /// field as the innermost column field.
+    ///
+    /// # Parameters
+    ///
+    /// - `fields`: A slice of [`PivotDataField`] structs that refer to the
+    ///   source data fields by name.
+    ///
+    pub fn set_data_fields(mut self, fields: &[PivotDataField]) -> PivotTable {
+        self.data_fields = fields.to_vec();
+        self
+    }
+
+    /// Set the style of the pivot table.
+    ///
+    /// Excel supports 85 different styles for pivot tables divided into Light,
+    /// Medium and Dark categories. You can set one of these styles using a
+    /// [`PivotTableStyle`] enum value. The default style in Excel is
+    /// [`PivotTableStyle::Light16`].
+    ///
+    /// # Parameters
+    ///
+    /// - `style`: a [`PivotTableStyle`] enum value.
+    ///
+    pub fn set_style(mut self, style: PivotTableStyle) -> PivotTable {
+        self.style = style;
+        self
+    }
+
+    /// Turn on/off the row header styling of the pivot table.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_row_headers(mut self, enable: bool) -> PivotTable {
+        self.show_row_headers = enable;
+        self
+    }
+
+    /// Turn on/off the column header styling of the pivot table.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_column_headers(mut self, enable: bool) -> PivotTable {
+        self.show_column_headers = enable;
+        self
+    }
+
+    /// Turn on/off banded rows in the pivot table style.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    pub fn set_banded_rows(mut self, enable: bool) -> PivotTable {
+        self.show_banded_rows = enable;
+        self
+    }
+
+    /// Turn on/off banded columns in the pivot table style.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    pub fn set_banded_columns(mut self, enable: bool) -> PivotTable {
+        self.show_banded_columns = enable;
+        self
+    }
+
+    /// Turn on/off the grand totals for the rows of the pivot table.
+    ///
+    /// The row grand totals are displayed in the last column of the pivot
+    /// table. This is equivalent to Excel's "On for Rows" grand total option.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_row_grand_totals(mut self, enable: bool) -> PivotTable {
+        self.show_row_grand_totals = enable;
+        self
+    }
+
+    /// Turn on/off the grand totals for the columns of the pivot table.
+    ///
+    /// The column grand totals are displayed in the last row of the pivot
+    /// table. This is equivalent to Excel's "On for Columns" grand total
+    /// option.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_column_grand_totals(mut self, enable: bool) -> PivotTable {
+        self.show_column_grand_totals = enable;
+        self
+    }
+
+    // Check that the pivot table has a source.
+    pub(crate) fn validate(&self) -> Result<(), XlsxError> {
+        match &self.source {
+            PivotTableSource::None => Err(XlsxError::PivotTableError(
+                "Pivot table doesn't have a source range or table".to_string(),
+            )),
+            PivotTableSource::Range(range) => {
+                if !range.has_data() || range.key().0 == crate::UNPARSED_SHEET_RANGE {
+                    return Err(XlsxError::PivotTableError(format!(
+                        "Pivot table source range '{}' isn't a valid worksheet range",
+                        range.error_range()
+                    )));
+                }
+
+                range.validate()?;
+
+                let (_, first_row, _, last_row, _) = range.key();
+                if first_row == last_row {
+                    return Err(XlsxError::PivotTableError(format!(
+                        "Pivot table source range '{}' must have a header row and at least one row of data",
+                        range.error_range()
+                    )));
+                }
+
+                Ok(())
+            }
+            PivotTableSource::Table(name) => {
+                if name.is_empty() {
+                    return Err(XlsxError::PivotTableError(
+                        "Pivot table source table name cannot be blank".to_string(),
+                    ));
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    // Get the number of page fields, which are written above the table.
+    pub(crate) fn num_page_fields(&self) -> usize {
+        self.page_fields.len()
+    }
+
+    // Create the pivot cache from the source data, map the pivot fields to the
+    // cache fields and calculate the layout of the pivot table. The first row
+    // of the data contains the field names.
+    pub(crate) fn initialize(
+        &mut self,
+        source_ref: PivotCacheSource,
+        data: Vec<Vec<PivotCacheValue>>,
+    ) -> Result<(), XlsxError> {
+        self.cache = PivotCache::new(source_ref, data)?;
+
+        self.row_field_ids = self.field_ids(&self.row_fields)?;
+        self.column_field_ids = self.field_ids(&self.column_fields)?;
+        self.page_field_ids = self.field_ids(&self.page_fields)?;
+
+        self.data_field_ids = vec![];
+        for field in &self.data_fields {
+            let id = self.cache.field_id(&field.name)?;
+            self.data_field_ids.push(id);
+        }
+
+        // A field can only be used once as a row, column or page field.
+        let mut seen_ids = BTreeSet::new();
+        for id in self
+            .row_field_ids
+            .iter()
+            .chain(&self.column_field_ids)
+            .chain(&self.page_field_ids)
+        {
+            if !seen_ids.insert(*id) {
+                return Err(XlsxError::PivotTableError(format!(
+                    "Pivot field '{}' is used more than once as a row, column or page field",
+                    self.cache.fields[*id].name
+                )));
+            }
+        }
+
+        // Fields used on an axis need their items stored in the cache.
+        for id in seen_ids {
+            self.cache.fields[id].is_axis_field = true;
+        }
+
+        // Set the sorted order of the items in each field.
+        let descending_ids: Vec<usize> = self
+            .row_fields
+            .iter()
+            .zip(&self.row_field_ids)
+            .chain(self.column_fields.iter().zip(&self.column_field_ids))
+            .chain(self.page_fields.iter().zip(&self.page_field_ids))
+            .filter(|(field, _)| field.sort_descending)
+            .map(|(_, id)| *id)
+            .collect();
+
+        for (id, field) in self.cache.fields.iter_mut().enumerate() {
+            field.sort_items(descending_ids.contains(&id));
+        }
+
+        self.row_items = self.calculate_row_items();
+        self.column_items = self.calculate_column_items();
+        self.calculate_location();
+
+        Ok(())
+    }
+
+    // Map pivot fields to the cache field index.
+    fn field_ids(&self, fields: &[PivotField]) -> Result<Vec<usize>, XlsxError> {
+        let mut ids = vec![];
+        for field in fields {
+            ids.push(self.cache.field_id(&field.name)?);
+        }
+
+        Ok(ids)
+    }
+
+    // Check if Excel adds the virtual "Values" field to the column axis, which
+    // happens when there is more than one data field.
+    fn has_values_field(&self) -> bool {
+        self.data_fields.len() > 1
+    }
+
+    // Calculate the items displayed in the rows of the pivot table. Excel's
+    // default compact form displays each level of nested item in a separate
+    // row.
+    fn calculate_row_items(&self) -> Vec<PivotItem> {
+        let mut items = vec![];
+
+        if self.row_field_ids.is_empty() {
+            if !self.data_fields.is_empty() {
+                items.push(PivotItem::new(PivotItemType::Data, 0, vec![], 0));
+            }
+
+            return items;
+        }
+
+        let paths = self.cache.unique_paths(&self.row_field_ids);
+        let mut previous: &[usize] = &[];
+        for path in &paths {
+            let common = common_prefix_len(previous, path);
+            for (depth, position) in path.iter().enumerate().skip(common) {
+                items.push(PivotItem::new(
+                    PivotItemType::Data,
+                    depth,
+                    vec![*position],
+                    0,
+                ));
+            }
+            previous = path;
+        }
+
+        if self.show_column_grand_totals {
+            items.push(PivotItem::new(PivotItemType::Grand, 0, vec![0], 0));
+        }
+
+        items
+    }
+
+    // Calculate the items displayed in the columns of the pivot table. Excel
+    // displays column items in a tabular form with subtotals after each group
+    // of nested items.
+    fn calculate_column_items(&self) -> Vec<PivotItem> {
+        let mut items = vec![];
+        let num_data_fields = self.data_fields.len();
+        let num_levels = self.column_field_ids.len();
+
+        if num_levels == 0 {
+            if self.has_values_field() {
+                for data_index in 0..num_data_fields {
+                    items.push(PivotItem::new(
+                        PivotItemType::Data,
+                        0,
+                        vec![data_index],
+                        data_index,
+                    ));
+                }
+            } else if !self.data_fields.is_empty() {
+                items.push(PivotItem::new(PivotItemType::Data, 0, vec![], 0));
+            }
+
+            return items;
+        }
+
+        let data_indices: Vec<usize> = (0..num_data_fields.max(1)).collect();
+        let paths = self.cache.unique_paths(&self.column_field_ids);
+        let mut previous: Vec<usize> = vec![];
+
+        for (index, path) in paths.iter().enumerate() {
+            // Write the leaf items, one per data field if there is a "Values"
+            // field.
+            for data_index in &data_indices {
+                let mut full_path = path.clone();
+                if self.has_values_field() {
+                    full_path.push(*data_index);
+                }
+
+                let common = common_prefix_len(&previous, &full_path);
+                items.push(PivotItem::new(
+                    PivotItemType::Data,
+                    common,
+                    full_path[common..].to_vec(),
+                    *data_index,
+                ));
+                previous = full_path;
+            }
+
+            // Write the subtotals for any groups of nested items that end
+            // with this item, from the innermost group outwards.
+            let next_path = paths.get(index + 1);
+            for depth in (0..num_levels - 1).rev() {
+                let group_ends = match next_path {
+                    Some(next_path) => common_prefix_len(path, next_path) <= depth,
+                    None => true,
+                };
+
+                if group_ends && self.column_fields[depth].show_subtotals {
+                    for data_index in &data_indices {
+                        items.push(PivotItem::new(
+                            PivotItemType::Default,
+                            depth,
+                            vec![path[depth]],
+                            *data_index,
+                        ));
+                    }
+                }
+            }
+        }
+
+        if self.show_row_grand_totals {
+            for data_index in &data_indices {
+                items.push(PivotItem::new(
+                    PivotItemType::Grand,
+                    0,
+                    vec![0],
+                    *data_index,
+                ));
+            }
+        }
+
+        items
+    }
+
+    // Calculate the worksheet range of the pivot table, excluding any page
+    // fields, from the row and column items.
+    fn calculate_location(&mut self) {
+        let first_row = self.cell_range.first_row;
+        let first_col = self.cell_range.first_col;
+
+        // An empty pivot table uses the same placeholder area as Excel.
+        if self.row_fields.is_empty()
+            && self.column_fields.is_empty()
+            && self.data_fields.is_empty()
+        {
+            self.cell_range = CellRange::new(first_row, first_col, first_row + 17, first_col + 2);
+            return;
+        }
+
+        let num_header_rows = if self.column_fields.is_empty() {
+            1
+        } else {
+            1 + self.column_fields.len() + usize::from(self.has_values_field())
+        };
+
+        let num_label_cols =
+            usize::from(!self.row_fields.is_empty() || !self.column_fields.is_empty());
+
+        let num_rows = num_header_rows + self.row_items.len().max(1);
+        let num_cols = num_label_cols + self.column_items.len().max(1);
+
+        self.cell_range = CellRange::new(
+            first_row,
+            first_col,
+            first_row + num_rows as u32 - 1,
+            first_col + num_cols as u16 - 1,
+        );
+    }
+
+    // Get the offsets of the first header row, first data row and first data
+    // column within the pivot table range.
+    fn location_offsets(&self) -> (usize, usize, usize) {
+        let has_fields = !self.row_fields.is_empty() || !self.column_fields.is_empty();
+
+        if !has_fields && self.data_fields.is_empty() {
+            return (1, 1, 0);
+        }
+
+        if self.column_fields.is_empty() {
+            let first_header_row = usize::from(!self.has_values_field());
+            (first_header_row, 1, usize::from(has_fields))
+        } else {
+            let first_data_row =
+                1 + self.column_fields.len() + usize::from(self.has_values_field());
+            (1, first_data_row, 1)
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // XML assembly methods.
+    // -----------------------------------------------------------------------
+
+    // Assemble and generate the pivotTable XML file.
+    pub(crate) fn assemble_xml_file(&mut self) {
+        xml_declaration(&mut self.writer);
+
+        // Write the pivotTableDefinition element.
+        self.write_pivot_table_definition();
+
+        // Write the location element.
+        self.write_location();
+
+        // Write the pivotFields element.
+        self.write_pivot_fields();
+
+        // Write the rowFields and rowItems elements.
+        if !self.row_field_ids.is_empty() {
+            let fields = self.row_field_ids.iter().map(|id| *id as i32).collect();
+            self.write_fields("rowFields", fields);
+        }
+
+        if !self.row_items.is_empty() {
+            let items = self.row_items.clone();
+            self.write_items("rowItems", &items);
+        }
+
+        // Write the colFields and colItems elements.
+        let mut column_field_ids: Vec<i32> =
+            self.column_field_ids.iter().map(|id| *id as i32).collect();
+        if self.has_values_field() {
+            column_field_ids.push(-2);
+        }
+
+        if !column_field_ids.is_empty() {
+            self.write_fields("colFields", column_field_ids);
+        }
+
+        if !self.column_items.is_empty() {
+            let items = self.column_items.clone();
+            self.write_items("colItems", &items);
+        }
+
+        // Write the pageFields element.
+        if !self.page_field_ids.is_empty() {
+            self.write_page_fields();
+        }
+
+        // Write the dataFields element.
+        if !self.data_fields.is_empty() {
+            self.write_data_fields();
+        }
+
+        // Write the pivotTableStyleInfo element.
+        self.write_pivot_table_style_info();
+
+        // Close the pivotTableDefinition tag.
+        xml_end_tag(&mut self.writer, "pivotTableDefinition");
+    }
+
+    // Assemble and generate the pivotCacheDefinition XML file.
+    pub(crate) fn assemble_cache_xml_file(&mut self) {
+        xml_declaration(&mut self.cache_writer);
+
+        // Write the pivotCacheDefinition element.
+        self.write_pivot_cache_definition();
+
+        // Write the cacheSource element.
+        self.write_cache_source();
+
+        // Write the cacheFields element.
+        self.write_cache_fields();
+
+        // Close the pivotCacheDefinition tag.
+        xml_end_tag(&mut self.cache_writer, "pivotCacheDefinition");
+    }
+
+    // Assemble and generate the pivotCacheRecords XML file.
+    pub(crate) fn assemble_records_xml_file(&mut self) {
+        xml_declaration(&mut self.records_writer);
+
+        // Write the pivotCacheRecords element.
+        self.write_pivot_cache_records();
+
+        for row in 0..self.cache.num_records {
+            // Write the r element.
+            self.write_record(row);
+        }
+
+        // Close the pivotCacheRecords tag.
+        xml_end_tag(&mut self.records_writer, "pivotCacheRecords");
+    }
+
+    // Write the <pivotTableDefinition> element.
+    fn write_pivot_table_definition(&mut self) {
+        let schema = "http://schemas.openxmlformats.org/spreadsheetml/2006/main".to_string();
+
+        let mut attributes = vec![
+            ("xmlns", schema),
+            ("name", self.name.clone()),
+            ("cacheId", self.index.to_string()),
+            ("applyNumberFormats", "0".to_string()),
+            ("applyBorderFormats", "0".to_string()),
+            ("applyFontFormats", "0".to_string()),
+            ("applyPatternFormats", "0".to_string()),
+            ("applyAlignmentFormats", "0".to_string()),
+            ("applyWidthHeightFormats", "1".to_string()),
+            ("dataCaption", "Values".to_string()),
+            ("updatedVersion", "6".to_string()),
+            ("minRefreshableVersion", "3".to_string()),
+            ("useAutoFormatting", "1".to_string()),
+        ];
+
+        if !self.show_row_grand_totals {
+            attributes.push(("rowGrandTotals", "0".to_string()));
+        }
+
+        if !self.show_column_grand_totals {
+            attributes.push(("colGrandTotals", "0".to_string()));
+        }
+
+        attributes.push(("itemPrintTitles", "1".to_string()));
+        attributes.push(("createdVersion", "6".to_string()));
+        attributes.push(("indent", "0".to_string()));
+        attributes.push(("outline", "1".to_string()));
+        attributes.push(("outlineData", "1".to_string()));
+        attributes.push(("multipleFieldFilters", "0".to_string()));
+
+        xml_start_tag(&mut self.writer, "pivotTableDefinition", &attributes);
+    }
+
+    // Write the <location> element.
+    fn write_location(&mut self) {
+        let (first_header_row, first_data_row, first_data_col) = self.location_offsets();
+
+        let mut attributes = vec![
+            ("ref", self.cell_range.to_range_string()),
+            ("firstHeaderRow", first_header_row.to_string()),
+            ("firstDataRow", first_data_row.to_string()),
+            ("firstDataCol", first_data_col.to_string()),
+        ];
+
+        if !self.page_field_ids.is_empty() {
+            attributes.push(("rowPageCount", self.page_field_ids.len().to_string()));
+            attributes.push(("colPageCount", "1".to_string()));
+        }
+
+        xml_empty_tag(&mut self.writer, "location", &attributes);
+    }
+
+    // Write the <pivotFields> element.
+    fn write_pivot_fields(&mut self) {
+        let attributes = [("count", self.cache.fields.len().to_string())];
+
+        xml_start_tag(&mut self.writer, "pivotFields", &attributes);
+
+        for id in 0..self.cache.fields.len() {
+            // Write the pivotField element.
+            self.write_pivot_field(id);
+        }
+
+        xml_end_tag(&mut self.writer, "pivotFields");
+    }
+
+    // Write the <pivotField> element.
+    fn write_pivot_field(&mut self, id: usize) {
+        let mut attributes = vec![];
+        let mut field = None;
+
+        if let Some(position) = self.row_field_ids.iter().position(|x| *x == id) {
+            attributes.push(("axis", "axisRow".to_string()));
+            field = Some(self.row_fields[position].clone());
+        } else if let Some(position) = self.column_field_ids.iter().position(|x| *x == id) {
+            attributes.push(("axis", "axisCol".to_string()));
+            field = Some(self.column_fields[position].clone());
+        } else if let Some(position) = self.page_field_ids.iter().position(|x| *x == id) {
+            attributes.push(("axis", "axisPage".to_string()));
+            field = Some(self.page_fields[position].clone());
+        }
+
+        if self.data_field_ids.contains(&id) {
+            attributes.push(("dataField", "1".to_string()));
+        }
+
+        attributes.push(("showAll", "0".to_string()));
+
+        let Some(field) = field else {
+            xml_empty_tag(&mut self.writer, "pivotField", &attributes);
+            return;
+        };
+
+        if field.sort_descending {
+            attributes.push(("sortType", "descending".to_string()));
+        }
+
+        if !field.show_subtotals {
+            attributes.push(("defaultSubtotal", "0".to_string()));
+        }
+
+        xml_start_tag(&mut self.writer, "pivotField", &attributes);
+
+        // Write the items element.
+        let item_order = self.cache.fields[id].item_order.clone();
+        let count = item_order.len() + usize::from(field.show_subtotals);
+        let attributes = [("count", count.to_string())];
+
+        xml_start_tag(&mut self.writer, "items", &attributes);
+
+        for shared_index in item_order {
+            let attributes = [("x", shared_index.to_string())];
+            xml_empty_tag(&mut self.writer, "item", &attributes);
+        }
+
+        if field.show_subtotals {
+            let attributes = [("t", "default")];
+            xml_empty_tag(&mut self.writer, "item", &attributes);
+        }
+
+        xml_end_tag(&mut self.writer, "items");
+        xml_end_tag(&mut self.writer, "pivotField");
+    }
+
+    // Write the <rowFields> or <colFields> elements.
+    fn write_fields(&mut self, tag: &str, field_ids: Vec<i32>) {
+        let attributes = [("count", field_ids.len().to_string())];
+
+        xml_start_tag(&mut self.writer, tag, &attributes);
+
+        for id in field_ids {
+            let attributes = [("x", id.to_string())];
+            xml_empty_tag(&mut self.writer, "field", &attributes);
+        }
+
+        xml_end_tag(&mut self.writer, tag);
+    }
+
+    // Write the <rowItems> or <colItems> elements.
+    fn write_items(&mut self, tag: &str, items: &[PivotItem]) {
+        let attributes = [("count", items.len().to_string())];
+
+        xml_start_tag(&mut self.writer, tag, &attributes);
+
+        for item in items {
+            let mut attributes = vec![];
+
+            match item.item_type {
+                PivotItemType::Data => {}
+                PivotItemType::Default => attributes.push(("t", "default".to_string())),
+                PivotItemType::Grand => attributes.push(("t", "grand".to_string())),
+            }
+
+            if item.repeat > 0 {
+                attributes.push(("r", item.repeat.to_string()));
+            }
+
+            if item.data_index > 0 {
+                attributes.push(("i", item.data_index.to_string()));
+            }
+
+            if item.indices.is_empty() {
+                xml_empty_tag(&mut self.writer, "i", &attributes);
+                continue;
+            }
+
+            xml_start_tag(&mut self.writer, "i", &attributes);
+
+            for index in &item.indices {
+                if *index == 0 {
+                    xml_empty_tag_only(&mut self.writer, "x");
+                } else {
+                    let attributes = [("v", index.to_string())];
+                    xml_empty_tag(&mut self.writer, "x", &attributes);
+                }
+            }
+
+            xml_end_tag(&mut self.writer, "i");
+        }
+
+        xml_end_tag(&mut self.writer, tag);
+    }
+
+    // Write the <pageFields> element.
+    fn write_page_fields(&mut self) {
+        let attributes = [("count", self.page_field_ids.len().to_string())];
+
+        xml_start_tag(&mut self.writer, "pageFields", &attributes);
+
+        for id in self.page_field_ids.clone() {
+            let attributes = [("fld", id.to_string()), ("hier", "-1".to_string())];
+            xml_empty_tag(&mut self.writer, "pageField", &attributes);
+        }
+
+        xml_end_tag(&mut self.writer, "pageFields");
+    }
+
+    // Write the <dataFields> element.
+    fn write_data_fields(&mut self) {
+        let attributes = [("count", self.data_fields.len().to_string())];
+
+        xml_start_tag(&mut self.writer, "dataFields", &attributes);
+
+        for (data_field, id) in self
+            .data_fields
+            .clone()
+            .iter()
+            .zip(self.data_field_ids.clone())
+        {
+            let name = if data_field.caption.is_empty() {
+                format!(
+                    "{} of {}",
+                    data_field.function.caption(),
+                    self.cache.fields[id].name
+                )
+            } else {
+                data_field.caption.clone()
+            };
+
+            let mut attributes = vec![("name", name), ("fld", id.to_string())];
+
+            if data_field.function != PivotFunction::Sum {
+                attributes.push(("subtotal", data_field.function.to_string()));
+            }
+
+            attributes.push(("baseField", "0".to_string()));
+            attributes.push(("baseItem", "0".to_string()));
+
+            xml_empty_tag(&mut self.writer, "dataField", &attributes);
+        }
+
+        xml_end_tag(&mut self.writer, "dataFields");
+    }
+
+    // Write the <pivotTableStyleInfo> element.
+    fn write_pivot_table_style_info(&mut self) {
+        let mut attributes = vec![];
+
+        if self.style != PivotTableStyle::None {
+            attributes.push(("name", self.style.to_string()));
+        }
+
+        attributes.push(("showRowHeaders", self.show_row_headers.to_xml_bool()));
+        attributes.push(("showColHeaders", self.show_column_headers.to_xml_bool()));
+        attributes.push(("showRowStripes", self.show_banded_rows.to_xml_bool()));
+        attributes.push(("showColStripes", self.show_banded_columns.to_xml_bool()));
+        attributes.push(("showLastColumn", "1".to_string()));
+
+        xml_empty_tag(&mut self.writer, "pivotTableStyleInfo", &attributes);
+    }
+
+    // Write the <pivotCacheDefinition> element.
+    fn write_pivot_cache_definition(&mut self) {
+        let schema = "http://schemas.openxmlformats.org/spreadsheetml/2006/main".to_string();
+        let schema_r =
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships".to_string();
+
+        let attributes = [
+            ("xmlns", schema),
+            ("xmlns:r", schema_r),
+            ("r:id", "rId1".to_string()),
+            ("refreshOnLoad", "1".to_string()),
+            ("createdVersion", "6".to_string()),
+            ("refreshedVersion", "6".to_string()),
+            ("minRefreshableVersion", "3".to_string()),
+            ("recordCount", self.cache.num_records.to_string()),
+        ];
+
+        xml_start_tag(&mut self.cache_writer, "pivotCacheDefinition", &attributes);
+    }
+
+    // Write the <cacheSource> element.
+    fn write_cache_source(&mut self) {
+        let attributes = [("type", "worksheet")];
+
+        xml_start_tag(&mut self.cache_writer, "cacheSource", &attributes);
+
+        // Write the worksheetSource element.
+        let attributes = match &self.cache.source {
+            PivotCacheSource::Range(sheet_name, range) => {
+                vec![("ref", range.clone()), ("sheet", sheet_name.clone())]
+            }
+            PivotCacheSource::Table(name) => vec![("name", name.clone())],
+        };
+
+        xml_empty_tag(&mut self.cache_writer, "worksheetSource", &attributes);
+
+        xml_end_tag(&mut self.cache_writer, "cacheSource");
+    }
+
+    // Write the <cacheFields> element.
+    fn write_cache_fields(&mut self) {
+        let attributes = [("count", self.cache.fields.len().to_string())];
+
+        xml_start_tag(&mut self.cache_writer, "cacheFields", &attributes);
+
+        for field in &self.cache.fields {
+            let num_format_id = if field.has_dates && !field.has_numbers && !field.has_strings {
+                "14"
+            } else {
+                "0"
+            };
+
+            let attributes = [
+                ("name", field.name.clone()),
+                ("numFmtId", num_format_id.to_string()),
+            ];
+
+            xml_start_tag(&mut self.cache_writer, "cacheField", &attributes);
+
+            // Write the sharedItems element.
+            let attributes = field.shared_items_attributes();
+
+            if field.has_shared_items() {
+                xml_start_tag(&mut self.cache_writer, "sharedItems", &attributes);
+
+                for value in &field.shared_items {
+                    Self::write_cache_value(&mut self.cache_writer, value);
+                }
+
+                xml_end_tag(&mut self.cache_writer, "sharedItems");
+            } else {
+                xml_empty_tag(&mut self.cache_writer, "sharedItems", &attributes);
+            }
+
+            xml_end_tag(&mut self.cache_writer, "cacheField");
+        }
+
+        xml_end_tag(&mut self.cache_writer, "cacheFields");
+    }
+
+    // Write the <pivotCacheRecords> element.
+    fn write_pivot_cache_records(&mut self) {
+        let schema = "http://schemas.openxmlformats.org/spreadsheetml/2006/main".to_string();
+        let schema_r =
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships".to_string();
+
+        let attributes = [
+            ("xmlns", schema),
+            ("xmlns:r", schema_r),
+            ("count", self.cache.num_records.to_string()),
+        ];
+
+        xml_start_tag(&mut self.records_writer, "pivotCacheRecords", &attributes);
+    }
+
+    // Write the <r> record element.
+    fn write_record(&mut self, row: usize) {
+        xml_start_tag_only(&mut self.records_writer, "r");
+
+        for field in &self.cache.fields {
+            if field.has_shared_items() {
+                let attributes = [("v", field.record_indices[row].to_string())];
+                xml_empty_tag(&mut self.records_writer, "x", &attributes);
+            } else {
+                Self::write_cache_value(&mut self.records_writer, &field.values[row]);
+            }
+        }
+
+        xml_end_tag(&mut self.records_writer, "r");
+    }
+
+    // Write a cache <m>, <b>, <n>, <d>, <e> or <s> value element.
+    fn write_cache_value(writer: &mut Cursor<Vec<u8>>, value: &PivotCacheValue) {
+        match value {
+            PivotCacheValue::Blank => xml_empty_tag_only(writer, "m"),
+            PivotCacheValue::Boolean(boolean) => {
+                let attributes = [("v", boolean.to_xml_bool())];
+                xml_empty_tag(writer, "b", &attributes);
+            }
+            PivotCacheValue::Number(number) => {
+                let attributes = [("v", number.to_string())];
+                xml_empty_tag(writer, "n", &attributes);
+            }
+            PivotCacheValue::DateTime(number) => {
+                let attributes = [("v", serial_to_iso8601(*number))];
+                xml_empty_tag(writer, "d", &attributes);
+            }
+            PivotCacheValue::Error(error) => {
+                let attributes = [("v", error.as_str())];
+                xml_empty_tag(writer, "e", &attributes);
+            }
+            PivotCacheValue::String(string) => {
+                let attributes = [("v", string.clone())];
+                xml_empty_tag(writer, "s", &attributes);
+            }
+        }
+    }
+}
+
+/// Convert a [`PivotTable`] ref to a [`PivotTable`] object.
+///
+/// This is used as a syntactic shortcut to allow either `&PivotTable` or
+/// `PivotTable`.
+///
+impl From<&PivotTable> for PivotTable {
+    fn from(value: &PivotTable) -> PivotTable {
+        (*value).clone()
+    }
+}
+
+/// The `PivotField` struct represents a row, column or page field in a pivot
+/// table.
+///
+/// The `PivotField` struct refers to a field in the pivot table source data by
+/// the name of its header. It is used in conjunction with the
+/// [`PivotTable::set_row_fields()`], [`PivotTable::set_column_fields()`] and
+/// [`PivotTable::set_page_fields()`] methods.
+///
+#[derive(Clone)]
+pub struct PivotField {
+    pub(crate) name: String,
+    pub(crate) sort_descending: bool,
+    pub(crate) show_subtotals: bool,
+}
+
+impl PivotField {
+    /// Create a new `PivotField` to refer to a source data field.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the field in the source data header row. The
+    ///   match is case insensitive, like Excel.
+    ///
+    pub fn new(name: impl Into<String>) -> PivotField {
+        PivotField {
+            name: name.into(),
+            sort_descending: false,
+            show_subtotals: true,
+        }
+    }
+
+    /// Sort the items of the field in descending order.
+    ///
+    /// By default the items in a field are sorted in ascending order.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    pub fn set_sort_descending(mut self, enable: bool) -> PivotField {
+        self.sort_descending = enable;
+        self
+    }
+
+    /// Turn on/off the subtotals for the items in the field.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_subtotals(mut self, enable: bool) -> PivotField {
+        self.show_subtotals = enable;
+        self
+    }
+}
+
+/// The `PivotDataField` struct represents a data/value field in a pivot table.
+///
+/// The `PivotDataField` struct refers to a field in the pivot table source data
+/// by the name of its header and sets the function used to aggregate the
+/// values. It is used in conjunction with the
+/// [`PivotTable::set_data_fields()`] method.
+///
+#[derive(Clone)]
+pub struct PivotDataField {
+    pub(crate) name: String,
+    pub(crate) caption: String,
+    pub(crate) function: PivotFunction,
+}
+
+impl PivotDataField {
+    /// Create a new `PivotDataField` to refer to a source data field.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the field in the source data header row. The
+    ///   match is case insensitive, like Excel.
+    ///
+    pub fn new(name: impl Into<String>) -> PivotDataField {
+        PivotDataField {
+            name: name.into(),
+            caption: String::new(),
+            function: PivotFunction::Sum,
+        }
+    }
+
+    /// Set the aggregation function for the data field.
+    ///
+    /// The default function is [`PivotFunction::Sum`].
+    ///
+    /// # Parameters
+    ///
+    /// - `function`: A [`PivotFunction`] enum value.
+    ///
+    pub fn set_function(mut self, function: PivotFunction) -> PivotDataField {
+        self.function = function;
+        self
+    }
+
+    /// Set the caption of the data field.
+    ///
+    /// Excel uses default captions like "Sum of Sales" or "Count of Sales"
+    /// based on the function and field name. This method can be used to set a
+    /// user defined caption. It must be different from the source field names.
+    ///
+    /// # Parameters
+    ///
+    /// - `caption`: The caption of the data field.
+    ///
+    pub fn set_name(mut self, caption: impl Into<String>) -> PivotDataField {
+        self.caption = caption.into();
+        self
+    }
+}
+
+/// The `PivotFunction` enum defines the aggregation functions for a
+/// [`PivotDataField`].
+///
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PivotFunction {
+    /// Sum the values. This is the default.
+    Sum,
+
+    /// Count the number of non-blank values.
+    Count,
+
+    /// Average the values.
+    Average,
+
+    /// Get the maximum value.
+    Max,
+
+    /// Get the minimum value.
+    Min,
+
+    /// Get the product of the values.
+    Product,
+
+    /// Count the number of numeric values.
+    CountNumbers,
+
+    /// Get the standard deviation of a sample population.
+    StdDev,
+
+    /// Get the standard deviation of the entire population.
+    StdDevP,
+
+    /// Get the variance of a sample population.
+    Var,
+
+    /// Get the variance of the entire population.
+    VarP,
+}
+
+impl PivotFunction {
+    // The caption used in the default data field name.
+    fn caption(self) -> &'static str {
+        match self {
+            Self::Sum => "Sum",
+            Self::Count => "Count",
+            Self::Average => "Average",
+            Self::Max => "Max",
+            Self::Min => "Min",
+            Self::Product => "Product",
+            Self::CountNumbers => "Count",
+            Self::StdDev => "StdDev",
+            Self::StdDevP => "StdDevp",
+            Self::Var => "Var",
+            Self::VarP => "Varp",
+        }
+    }
+}
+
+impl fmt::Display for PivotFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sum => write!(f, "sum"),
+            Self::Count => write!(f, "count"),
+            Self::Average => write!(f, "average"),
+            Self::Max => write!(f, "max"),
+            Self::Min => write!(f, "min"),
+            Self::Product => write!(f, "product"),
+            Self::CountNumbers => write!(f, "countNums"),
+            Self::StdDev => write!(f, "stdDev"),
+            Self::StdDevP => write!(f, "stdDevp"),
+            Self::Var => write!(f, "var"),
+            Self::VarP => write!(f, "varp"),
+        }
+    }
+}
+
+/// The `PivotTableStyle` enum defines the worksheet pivot table styles.
+///
+/// Excel supports 85 different styles for pivot tables divided into Light,
+/// Medium and Dark categories. You can set one of these styles using a
+/// `PivotTableStyle` enum value.
+///
+/// The style is set via the [`PivotTable::set_style()`] method. The default
+/// pivot table style in Excel is equivalent to [`PivotTableStyle::Light16`].
+///
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PivotTableStyle {
+    /// No pivot table style.
+    None,
+
+    /// Pivot Style Light 1.
+    Light1,
+
+    /// Pivot Style Light 2.
+    Light2,
+
+    /// Pivot Style Light 3.
+    Light3,
+
+    /// Pivot Style Light 4.
+    Light4,
+
+    /// Pivot Style Light 5.
+    Light5,
+
+    /// Pivot Style Light 6.
+    Light6,
+
+    /// Pivot Style Light 7.
+    Light7,
+
+    /// Pivot Style Light 8.
+    Light8,
+
+    /// Pivot Style Light 9.
+    Light9,
+
+    /// Pivot Style Light 10.
+    Light10,
+
+    /// Pivot Style Light 11.
+    Light11,
+
+    /// Pivot Style Light 12.
+    Light12,
+
+    /// Pivot Style Light 13.
+    Light13,
+
+    /// Pivot Style Light 14.
+    Light14,
+
+    /// Pivot Style Light 15.
+    Light15,
+
+    /// Pivot Style Light 16. This is the default style.
+    Light16,
+
+    /// Pivot Style Light 17.
+    Light17,
+
+    /// Pivot Style Light 18.
+    Light18,
+
+    /// Pivot Style Light 19.
+    Light19,
+
+    /// Pivot Style Light 20.
+    Light20,
+
+    /// Pivot Style Light 21.
+    Light21,
+
+    /// Pivot Style Light 22.
+    Light22,
+
+    /// Pivot Style Light 23.
+    Light23,
+
+    /// Pivot Style Light 24.
+    Light24,
+
+    /// Pivot Style Light 25.
+    Light25,
+
+    /// Pivot Style Light 26.
+    Light26,
+
+    /// Pivot Style Light 27.
+    Light27,
+
+    /// Pivot Style Light 28.
+    Light28,
+
+    /// Pivot Style Medium 1.
+    Medium1,
+
+    /// Pivot Style Medium 2.
+    Medium2,
+
+    /// Pivot Style Medium 3.
+    Medium3,
+
+    /// Pivot Style Medium 4.
+    Medium4,
+
+    /// Pivot Style Medium 5.
+    Medium5,
+
+    /// Pivot Style Medium 6.
+    Medium6,
+
+    /// Pivot Style Medium 7.
+    Medium7,
+
+    /// Pivot Style Medium 8.
+    Medium8,
+
+    /// Pivot Style Medium 9.
+    Medium9,
+
+    /// Pivot Style Medium 10.
+    Medium10,
+
+    /// Pivot Style Medium 11.
+    Medium11,
+
+    /// Pivot Style Medium 12.
+    Medium12,
+
+    /// Pivot Style Medium 13.
+    Medium13,
+
+    /// Pivot Style Medium 14.
+    Medium14,
+
+    /// Pivot Style Medium 15.
+    Medium15,
+
+    /// Pivot Style Medium 16.
+    Medium16,
+
+    /// Pivot Style Medium 17.
+    Medium17,
+
+    /// Pivot Style Medium 18.
+    Medium18,
+
+    /// Pivot Style Medium 19.
+    Medium19,
+
+    /// Pivot Style Medium 20.
+    Medium20,
+
+    /// Pivot Style Medium 21.
+    Medium21,
+
+    /// Pivot Style Medium 22.
+    Medium22,
+
+    /// Pivot Style Medium 23.
+    Medium23,
+
+    /// Pivot Style Medium 24.
+    Medium24,
+
+    /// Pivot Style Medium 25.
+    Medium25,
+
+    /// Pivot Style Medium 26.
+    Medium26,
+
+    /// Pivot Style Medium 27.
+    Medium27,
+
+    /// Pivot Style Medium 28.
+    Medium28,
+
+    /// Pivot Style Dark 1.
+    Dark1,
+
+    /// Pivot Style Dark 2.
+    Dark2,
+
+    /// Pivot Style Dark 3.
+    Dark3,
+
+    /// Pivot Style Dark 4.
+    Dark4,
+
+    /// Pivot Style Dark 5.
+    Dark5,
+
+    /// Pivot Style Dark 6.
+    Dark6,
+
+    /// Pivot Style Dark 7.
+    Dark7,
+
+    /// Pivot Style Dark 8.
+    Dark8,
+
+    /// Pivot Style Dark 9.
+    Dark9,
+
+    /// Pivot Style Dark 10.
+    Dark10,
+
+    /// Pivot Style Dark 11.
+    Dark11,
+
+    /// Pivot Style Dark 12.
+    Dark12,
+
+    /// Pivot Style Dark 13.
+    Dark13,
+
+    /// Pivot Style Dark 14.
+    Dark14,
+
+    /// Pivot Style Dark 15.
+    Dark15,
+
+    /// Pivot Style Dark 16.
+    Dark16,
+
+    /// Pivot Style Dark 17.
+    Dark17,
+
+    /// Pivot Style Dark 18.
+    Dark18,
+
+    /// Pivot Style Dark 19.
+    Dark19,
+
+    /// Pivot Style Dark 20.
+    Dark20,
+
+    /// Pivot Style Dark 21.
+    Dark21,
+
+    /// Pivot Style Dark 22.
+    Dark22,
+
+    /// Pivot Style Dark 23.
+    Dark23,
+
+    /// Pivot Style Dark 24.
+    Dark24,
+
+    /// Pivot Style Dark 25.
+    Dark25,
+
+    /// Pivot Style Dark 26.
+    Dark26,
+
+    /// Pivot Style Dark 27.
+    Dark27,
+
+    /// Pivot Style Dark 28.
+    Dark28,
+}
+
+impl fmt::Display for PivotTableStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "PivotStyleNone"),
+            Self::Light1 => write!(f, "PivotStyleLight1"),
+            Self::Light2 => write!(f, "PivotStyleLight2"),
+            Self::Light3 => write!(f, "PivotStyleLight3"),
+            Self::Light4 => write!(f, "PivotStyleLight4"),
+            Self::Light5 => write!(f, "PivotStyleLight5"),
+            Self::Light6 => write!(f, "PivotStyleLight6"),
+            Self::Light7 => write!(f, "PivotStyleLight7"),
+            Self::Light8 => write!(f, "PivotStyleLight8"),
+            Self::Light9 => write!(f, "PivotStyleLight9"),
+            Self::Light10 => write!(f, "PivotStyleLight10"),
+            Self::Light11 => write!(f, "PivotStyleLight11"),
+            Self::Light12 => write!(f, "PivotStyleLight12"),
+            Self::Light13 => write!(f, "PivotStyleLight13"),
+            Self::Light14 => write!(f, "PivotStyleLight14"),
+            Self::Light15 => write!(f, "PivotStyleLight15"),
+            Self::Light16 => write!(f, "PivotStyleLight16"),
+            Self::Light17 => write!(f, "PivotStyleLight17"),
+            Self::Light18 => write!(f, "PivotStyleLight18"),
+            Self::Light19 => write!(f, "PivotStyleLight19"),
+            Self::Light20 => write!(f, "PivotStyleLight20"),
+            Self::Light21 => write!(f, "PivotStyleLight21"),
+            Self::Light22 => write!(f, "PivotStyleLight22"),
+            Self::Light23 => write!(f, "PivotStyleLight23"),
+            Self::Light24 => write!(f, "PivotStyleLight24"),
+            Self::Light25 => write!(f, "PivotStyleLight25"),
+            Self::Light26 => write!(f, "PivotStyleLight26"),
+            Self::Light27 => write!(f, "PivotStyleLight27"),
+            Self::Light28 => write!(f, "PivotStyleLight28"),
+            Self::Medium1 => write!(f, "PivotStyleMedium1"),
+            Self::Medium2 => write!(f, "PivotStyleMedium2"),
+            Self::Medium3 => write!(f, "PivotStyleMedium3"),
+            Self::Medium4 => write!(f, "PivotStyleMedium4"),
+            Self::Medium5 => write!(f, "PivotStyleMedium5"),
+            Self::Medium6 => write!(f, "PivotStyleMedium6"),
+            Self::Medium7 => write!(f, "PivotStyleMedium7"),
+            Self::Medium8 => write!(f, "PivotStyleMedium8"),
+            Self::Medium9 => write!(f, "PivotStyleMedium9"),
+            Self::Medium10 => write!(f, "PivotStyleMedium10"),
+            Self::Medium11 => write!(f, "PivotStyleMedium11"),
+            Self::Medium12 => write!(f, "PivotStyleMedium12"),
+            Self::Medium13 => write!(f, "PivotStyleMedium13"),
+            Self::Medium14 => write!(f, "PivotStyleMedium14"),
+            Self::Medium15 => write!(f, "PivotStyleMedium15"),
+            Self::Medium16 => write!(f, "PivotStyleMedium16"),
+            Self::Medium17 => write!(f, "PivotStyleMedium17"),
+            Self::Medium18 => write!(f, "PivotStyleMedium18"),
+            Self::Medium19 => write!(f, "PivotStyleMedium19"),
+            Self::Medium20 => write!(f, "PivotStyleMedium20"),
+            Self::Medium21 => write!(f, "PivotStyleMedium21"),
+            Self::Medium22 => write!(f, "PivotStyleMedium22"),
+            Self::Medium23 => write!(f, "PivotStyleMedium23"),
+            Self::Medium24 => write!(f, "PivotStyleMedium24"),
+            Self::Medium25 => write!(f, "PivotStyleMedium25"),
+            Self::Medium26 => write!(f, "PivotStyleMedium26"),
+            Self::Medium27 => write!(f, "PivotStyleMedium27"),
+            Self::Medium28 => write!(f, "PivotStyleMedium28"),
+            Self::Dark1 => write!(f, "PivotStyleDark1"),
+            Self::Dark2 => write!(f, "PivotStyleDark2"),
+            Self::Dark3 => write!(f, "PivotStyleDark3"),
+            Self::Dark4 => write!(f, "PivotStyleDark4"),
+            Self::Dark5 => write!(f, "PivotStyleDark5"),
+            Self::Dark6 => write!(f, "PivotStyleDark6"),
+            Self::Dark7 => write!(f, "PivotStyleDark7"),
+            Self::Dark8 => write!(f, "PivotStyleDark8"),
+            Self::Dark9 => write!(f, "PivotStyleDark9"),
+            Self::Dark10 => write!(f, "PivotStyleDark10"),
+            Self::Dark11 => write!(f, "PivotStyleDark11"),
+            Self::Dark12 => write!(f, "PivotStyleDark12"),
+            Self::Dark13 => write!(f, "PivotStyleDark13"),
+            Self::Dark14 => write!(f, "PivotStyleDark14"),
+            Self::Dark15 => write!(f, "PivotStyleDark15"),
+            Self::Dark16 => write!(f, "PivotStyleDark16"),
+            Self::Dark17 => write!(f, "PivotStyleDark17"),
+            Self::Dark18 => write!(f, "PivotStyleDark18"),
+            Self::Dark19 => write!(f, "PivotStyleDark19"),
+            Self::Dark20 => write!(f, "PivotStyleDark20"),
+            Self::Dark21 => write!(f, "PivotStyleDark21"),
+            Self::Dark22 => write!(f, "PivotStyleDark22"),
+            Self::Dark23 => write!(f, "PivotStyleDark23"),
+            Self::Dark24 => write!(f, "PivotStyleDark24"),
+            Self::Dark25 => write!(f, "PivotStyleDark25"),
+            Self::Dark26 => write!(f, "PivotStyleDark26"),
+            Self::Dark27 => write!(f, "PivotStyleDark27"),
+            Self::Dark28 => write!(f, "PivotStyleDark28"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------
+// Internal pivot cache structs.
+// -----------------------------------------------------------------------
+
+// The source of the pivot table data as defined by the user.
+#[derive(Clone)]
+pub(crate) enum PivotTableSource {
+    None,
+    Range(ChartRange),
+    Table(String),
+}
+
+// The resolved source of the pivot table data as written to the cache.
+#[derive(Clone)]
+pub(crate) enum PivotCacheSource {
+    Range(String, String),
+    Table(String),
+}
+
+// A single value from the source data, as stored in the pivot cache.
+#[derive(Clone, PartialEq)]
+pub(crate) enum PivotCacheValue {
+    Blank,
+    Boolean(bool),
+    Number(f64),
+    DateTime(f64),
+    Error(ExcelError),
+    String(String),
+}
+
+impl PivotCacheValue {
+    // Get a key to identify unique items. Excel pivot items are case
+    // insensitive.
+    fn key(&self) -> String {
+        match self {
+            Self::Blank => String::from("m"),
+            Self::Boolean(boolean) => format!("b:{boolean}"),
+            Self::Number(number) => format!("n:{number}"),
+            Self::DateTime(number) => format!("d:{number}"),
+            Self::Error(error) => format!("e:{error}"),
+            Self::String(string) => format!("s:{}", string.to_lowercase()),
+        }
+    }
+
+    // Get the sort order of the item types. Excel sorts numbers and dates
+    // before strings, then booleans, errors and then blanks.
+    fn type_order(&self) -> u8 {
+        match self {
+            Self::Number(_) | Self::DateTime(_) => 0,
+            Self::String(_) => 1,
+            Self::Boolean(_) => 2,
+            Self::Error(_) => 3,
+            Self::Blank => 4,
+        }
+    }
+
+    // Compare two values for sorting.
+    fn sort_cmp(&self, other: &PivotCacheValue) -> std::cmp::Ordering {
+        match (self, other) {
+            (Self::Number(a) | Self::DateTime(a), Self::Number(b) | Self::DateTime(b)) => {
+                a.total_cmp(b)
+            }
+            (Self::String(a), Self::String(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+            (Self::Boolean(a), Self::Boolean(b)) => a.cmp(b),
+            (Self::Error(a), Self::Error(b)) => a.as_str().cmp(b.as_str()),
+            _ => self.type_order().cmp(&other.type_order()),
+        }
+    }
+}
+
+// The pivot cache of the source data.
+#[derive(Clone)]
+struct PivotCache {
+    source: PivotCacheSource,
+    fields: Vec<PivotCacheField>,
+    num_records: usize,
+}
+
+impl Default for PivotCache {
+    fn default() -> Self {
+        PivotCache {
+            source: PivotCacheSource::Table(String::new()),
+            fields: vec![],
+            num_records: 0,
+        }
+    }
+}
+
+impl PivotCache {
+    // Create a new pivot cache from row ordered data, including the header.
+    fn new(
+        source: PivotCacheSource,
+        data: Vec<Vec<PivotCacheValue>>,
+    ) -> Result<PivotCache, XlsxError> {
+        let mut rows = data.into_iter();
+        let headers = rows.next().unwrap_or_default();
+        let records: Vec<Vec<PivotCacheValue>> = rows.collect();
+
+        let mut fields = vec![];
+        let mut seen_names = BTreeSet::new();
+
+        for (col, header) in headers.iter().enumerate() {
+            let name = match header {
+                PivotCacheValue::String(string) => string.clone(),
+                PivotCacheValue::Number(number) | PivotCacheValue::DateTime(number) => {
+                    number.to_string()
+                }
+                PivotCacheValue::Boolean(boolean) => boolean.to_string().to_uppercase(),
+                PivotCacheValue::Error(error) => error.to_string(),
+                PivotCacheValue::Blank => String::new(),
+            };
+
+            if name.is_empty() {
+                return Err(XlsxError::PivotTableError(format!(
+                    "Pivot table source data header in column {} cannot be blank",
+                    col + 1
+                )));
+            }
+
+            if !seen_names.insert(name.to_lowercase()) {
+                return Err(XlsxError::PivotTableError(format!(
+                    "Pivot table source data header '{name}' is used more than once"
+                )));
+            }
+
+            let values = records.iter().map(|record| record[col].clone()).collect();
+            fields.push(PivotCacheField::new(name, values));
+        }
+
+        Ok(PivotCache {
+            source,
+            fields,
+            num_records: records.len(),
+        })
+    }
+
+    // Get the index of a cache field from its name.
+    fn field_id(&self, name: &str) -> Result<usize, XlsxError> {
+        let name = name.to_lowercase();
+
+        self.fields
+            .iter()
+            .position(|field| field.name.to_lowercase() == name)
+            .ok_or_else(|| {
+                XlsxError::PivotTableError(format!(
+                    "Pivot field '{name}' isn't in the pivot table source data"
+                ))
+            })
+    }
+
+    // Get the unique, sorted, combinations of item positions for a set of
+    // nested fields.
+    fn unique_paths(&self, field_ids: &[usize]) -> Vec<Vec<usize>> {
+        let mut paths = BTreeSet::new();
+
+        for row in 0..self.num_records {
+            let path: Vec<usize> = field_ids
+                .iter()
+                .map(|id| {
+                    let field = &self.fields[*id];
+                    field.item_positions[field.record_indices[row]]
+                })
+                .collect();
+
+            paths.insert(path);
+        }
+
+        paths.into_iter().collect()
+    }
+}
+
+// A field in the pivot cache.
+#[derive(Clone)]
+struct PivotCacheField {
+    name: String,
+    values: Vec<PivotCacheValue>,
+    shared_items: Vec<PivotCacheValue>,
+    record_indices: Vec<usize>,
+    item_order: Vec<usize>,
+    item_positions: Vec<usize>,
+    is_axis_field: bool,
+    has_blanks: bool,
+    has_booleans: bool,
+    has_errors: bool,
+    has_numbers: bool,
+    has_dates: bool,
+    has_strings: bool,
+    has_non_integers: bool,
+    min_value: f64,
+    max_value: f64,
+}
+
+impl PivotCacheField {
+    // Create a new cache field and collect its unique items.
+    fn new(name: String, values: Vec<PivotCacheValue>) -> PivotCacheField {
+        let mut field = PivotCacheField {
+            name,
+            values: vec![],
+            shared_items: vec![],
+            record_indices: vec![],
+            item_order: vec![],
+            item_positions: vec![],
+            is_axis_field: false,
+            has_blanks: false,
+            has_booleans: false,
+            has_errors: false,
+            has_numbers: false,
+            has_dates: false,
+            has_strings: false,
+            has_non_integers: false,
+            min_value: f64::MAX,
+            max_value: f64::MIN,
+        };
+
+        let mut unique_items: HashMap<String, usize> = HashMap::new();
+
+        for value in &values {
+            match value {
+                PivotCacheValue::Blank => field.has_blanks = true,
+                PivotCacheValue::Boolean(_) => field.has_booleans = true,
+                PivotCacheValue::Error(_) => field.has_errors = true,
+                PivotCacheValue::String(_) => field.has_strings = true,
+                PivotCacheValue::Number(number) => {
+                    field.has_numbers = true;
+                    field.has_non_integers |= number.fract() != 0.0;
+                    field.min_value = field.min_value.min(*number);
+                    field.max_value = field.max_value.max(*number);
+                }
+                PivotCacheValue::DateTime(number) => {
+                    field.has_dates = true;
+                    field.min_value = field.min_value.min(*number);
+                    field.max_value = field.max_value.max(*number);
+                }
+            }
+
+            let index = *unique_items.entry(value.key()).or_insert_with(|| {
+                field.shared_items.push(value.clone());
+                field.shared_items.len() - 1
+            });
+
+            field.record_indices.push(index);
+        }
+
+        field.values = values;
+        field
+    }
+
+    // Fields that are used as row/column/page fields, or that contain strings,
+    // booleans or errors, store their unique items in the cache and reference
+    // them from the records. Other fields store the values in the records.
+    fn has_shared_items(&self) -> bool {
+        self.is_axis_field || self.has_strings || self.has_booleans || self.has_errors
+    }
+
+    // Sort the unique items for display in the pivot table. The item order is
+    // a list of shared item indices in the sorted order and the item positions
+    // map from a shared item index to its sorted position.
+    fn sort_items(&mut self, descending: bool) {
+        let mut order: Vec<usize> = (0..self.shared_items.len()).collect();
+        order.sort_by(|a, b| self.shared_items[*a].sort_cmp(&self.shared_items[*b]));
+
+        if descending {
+            order.reverse();
+        }
+
+        self.item_positions = vec![0; order.len()];
+        for (position, index) in order.iter().enumerate() {
+            self.item_positions[*index] = position;
+        }
+
+        self.item_order = order;
+    }
+
+    // Get the attributes of the <sharedItems> element that describe the types
+    // of data in the field.
+    fn shared_items_attributes(&self) -> Vec<(&'static str, String)> {
+        let mut attributes = vec![];
+        let num_types = usize::from(self.has_strings)
+            + usize::from(self.has_booleans)
+            + usize::from(self.has_errors)
+            + usize::from(self.has_numbers)
+            + usize::from(self.has_dates);
+
+        if !self.has_strings && !self.has_blanks && !self.has_booleans && !self.has_errors {
+            attributes.push(("containsSemiMixedTypes", "0".to_string()));
+        }
+
+        if self.has_dates
+            && !self.has_strings
+            && !self.has_numbers
+            && !self.has_booleans
+            && !self.has_errors
+        {
+            attributes.push(("containsNonDate", "0".to_string()));
+        }
+
+        if self.has_dates {
+            attributes.push(("containsDate", "1".to_string()));
+        }
+
+        if !self.has_strings {
+            attributes.push(("containsString", "0".to_string()));
+        }
+
+        if self.has_blanks {
+            attributes.push(("containsBlank", "1".to_string()));
+        }
+
+        if num_types > 1 {
+            attributes.push(("containsMixedTypes", "1".to_string()));
+        }
+
+        if self.has_numbers {
+            attributes.push(("containsNumber", "1".to_string()));
+
+            if !self.has_non_integers && !self.has_dates {
+                attributes.push(("containsInteger", "1".to_string()));
+            }
+        }
+
+        if self.has_numbers && !self.has_dates {
+            attributes.push(("minValue", self.min_value.to_string()));
+            attributes.push(("maxValue", self.max_value.to_string()));
+        }
+
+        if self.has_dates && !self.has_numbers {
+            attributes.push(("minDate", serial_to_iso8601(self.min_value)));
+            attributes.push(("maxDate", serial_to_iso8601(self.max_value)));
+        }
+
+        if self.has_shared_items() {
+            attributes.push(("count", self.shared_items.len().to_string()));
+        }
+
+        attributes
+    }
+}
+
+// A row or column item in the pivot table layout.
+#[derive(Clone, Debug, PartialEq)]
+struct PivotItem {
+    item_type: PivotItemType,
+    repeat: usize,
+    indices: Vec<usize>,
+    data_index: usize,
+}
+
+impl PivotItem {
+    fn new(
+        item_type: PivotItemType,
+        repeat: usize,
+        indices: Vec<usize>,
+        data_index: usize,
+    ) -> PivotItem {
+        PivotItem {
+            item_type,
+            repeat,
+            indices,
+            data_index,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PivotItemType {
+    Data,
+    Default,
+    Grand,
+}
+
+// Get the number of leading elements that two paths have in common.
+fn common_prefix_len(a: &[usize], b: &[usize]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+// Convert an Excel serial date to the ISO 8601 format used in the pivot
+// cache.
+fn serial_to_iso8601(number: f64) -> String {
+    let (year, month, day, hour, min, sec) = ExcelDateTime::serial_to_date_parts(number, false);
+    let sec = sec.trunc();
+
+    format!("{year}-{month:02}-{day:02}T{hour:02}:{min:02}:{sec:02}")
+}
@@ -0,0 +1,431 @@
+// Pivot table unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod pivot_table_tests {
+
+    use crate::pivot_table::{PivotCacheSource, PivotCacheValue};
+    use crate::test_functions::xml_to_vec;
+    use crate::{
+        xmlwriter, CellRange, ExcelError, PivotDataField, PivotField, PivotFunction, PivotTable,
+        XlsxError,
+    };
+    use pretty_assertions::assert_eq;
+
+    // Create some sample source data for the tests.
+    fn sample_data() -> Vec<Vec<PivotCacheValue>> {
+        let rows = [
+            ("East", "Apples", "Q1", 1000.0),
+            ("West", "Apples", "Q1", 2500.0),
+            ("East", "Pears", "Q2", 3000.0),
+            ("West", "Pears", "Q2", 1500.0),
+        ];
+
+        let mut data = vec![vec![
+            PivotCacheValue::String("Region".to_string()),
+            PivotCacheValue::String("Product".to_string()),
+            PivotCacheValue::String("Quarter".to_string()),
+            PivotCacheValue::String("Sales".to_string()),
+        ]];
+
+        for (region, product, quarter, sales) in rows {
+            data.push(vec![
+                PivotCacheValue::String(region.to_string()),
+                PivotCacheValue::String(product.to_string()),
+                PivotCacheValue::String(quarter.to_string()),
+                PivotCacheValue::Number(sales),
+            ]);
+        }
+
+        data
+    }
+
+    fn sample_source() -> PivotCacheSource {
+        PivotCacheSource::Range("Data".to_string(), "A1:D5".to_string())
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut pivot_table = PivotTable::new().set_row_fields(&[PivotField::new("Unknown")]);
+
+        let result = pivot_table.validate();
+        assert!(matches!(result, Err(XlsxError::PivotTableError(_))));
+
+        let result = pivot_table.initialize(sample_source(), sample_data());
+        assert!(matches!(result, Err(XlsxError::PivotTableError(_))));
+
+        // Field used twice.
+        let mut pivot_table = PivotTable::new()
+            .set_row_fields(&[PivotField::new("Region")])
+            .set_column_fields(&[PivotField::new("region")]);
+
+        let result = pivot_table.initialize(sample_source(), sample_data());
+        assert!(matches!(result, Err(XlsxError::PivotTableError(_))));
+
+        // Duplicate header.
+        let mut data = sample_data();
+        data[0][3] = PivotCacheValue::String("REGION".to_string());
+
+        let result = PivotTable::new().initialize(sample_source(), data);
+        assert!(matches!(result, Err(XlsxError::PivotTableError(_))));
+
+        // Blank header.
+        let mut data = sample_data();
+        data[0][3] = PivotCacheValue::Blank;
+
+        let result = PivotTable::new().initialize(sample_source(), data);
+        assert!(matches!(result, Err(XlsxError::PivotTableError(_))));
+    }
+
+    #[test]
+    fn test_assemble1() {
+        let mut pivot_table = PivotTable::new()
+            .set_row_fields(&[PivotField::new("Region")])
+            .set_column_fields(&[PivotField::new("Quarter")])
+            .set_data_fields(&[PivotDataField::new("Sales")]);
+
+        pivot_table.name = "PivotTable1".to_string();
+        pivot_table.index = 1;
+        pivot_table.cell_range = CellRange::new(2, 0, 2, 0);
+        pivot_table
+            .initialize(sample_source(), sample_data())
+            .unwrap();
+
+        pivot_table.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&pivot_table.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <pivotTableDefinition xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" name="PivotTable1" cacheId="1" applyNumberFormats="0" applyBorderFormats="0" applyFontFormats="0" applyPatternFormats="0" applyAlignmentFormats="0" applyWidthHeightFormats="1" dataCaption="Values" updatedVersion="6" minRefreshableVersion="3" useAutoFormatting="1" itemPrintTitles="1" createdVersion="6" indent="0" outline="1" outlineData="1" multipleFieldFilters="0">
+                  <location ref="A3:D7" firstHeaderRow="1" firstDataRow="2" firstDataCol="1"/>
+                  <pivotFields count="4">
+                    <pivotField axis="axisRow" showAll="0">
+                      <items count="3">
+                        <item x="0"/>
+                        <item x="1"/>
+                        <item t="default"/>
+                      </items>
+                    </pivotField>
+                    <pivotField showAll="0"/>
+                    <pivotField axis="axisCol" showAll="0">
+                      <items count="3">
+                        <item x="0"/>
+                        <item x="1"/>
+                        <item t="default"/>
+                      </items>
+                    </pivotField>
+                    <pivotField dataField="1" showAll="0"/>
+                  </pivotFields>
+                  <rowFields count="1">
+                    <field x="0"/>
+                  </rowFields>
+                  <rowItems count="3">
+                    <i>
+                      <x/>
+                    </i>
+                    <i>
+                      <x v="1"/>
+                    </i>
+                    <i t="grand">
+                      <x/>
+                    </i>
+                  </rowItems>
+                  <colFields count="1">
+                    <field x="2"/>
+                  </colFields>
+                  <colItems count="3">
+                    <i>
+                      <x/>
+                    </i>
+                    <i>
+                      <x v="1"/>
+                    </i>
+                    <i t="grand">
+                      <x/>
+                    </i>
+                  </colItems>
+                  <dataFields count="1">
+                    <dataField name="Sum of Sales" fld="3" baseField="0" baseItem="0"/>
+                  </dataFields>
+                  <pivotTableStyleInfo name="PivotStyleLight16" showRowHeaders="1" showColHeaders="1" showRowStripes="0" showColStripes="0" showLastColumn="1"/>
+                </pivotTableDefinition>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_cache() {
+        let mut pivot_table = PivotTable::new()
+            .set_row_fields(&[PivotField::new("Region")])
+            .set_data_fields(&[PivotDataField::new("Sales")]);
+
+        pivot_table.index = 1;
+        pivot_table.cell_range = CellRange::new(2, 0, 2, 0);
+        pivot_table
+            .initialize(sample_source(), sample_data())
+            .unwrap();
+
+        pivot_table.assemble_cache_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&pivot_table.cache_writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <pivotCacheDefinition xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" r:id="rId1" refreshOnLoad="1" createdVersion="6" refreshedVersion="6" minRefreshableVersion="3" recordCount="4">
+                  <cacheSource type="worksheet">
+                    <worksheetSource ref="A1:D5" sheet="Data"/>
+                  </cacheSource>
+                  <cacheFields count="4">
+                    <cacheField name="Region" numFmtId="0">
+                      <sharedItems count="2">
+                        <s v="East"/>
+                        <s v="West"/>
+                      </sharedItems>
+                    </cacheField>
+                    <cacheField name="Product" numFmtId="0">
+                      <sharedItems count="2">
+                        <s v="Apples"/>
+                        <s v="Pears"/>
+                      </sharedItems>
+                    </cacheField>
+                    <cacheField name="Quarter" numFmtId="0">
+                      <sharedItems count="2">
+                        <s v="Q1"/>
+                        <s v="Q2"/>
+                      </sharedItems>
+                    </cacheField>
+                    <cacheField name="Sales" numFmtId="0">
+                      <sharedItems containsSemiMixedTypes="0" containsString="0" containsNumber="1" containsInteger="1" minValue="1000" maxValue="3000"/>
+                    </cacheField>
+                  </cacheFields>
+                </pivotCacheDefinition>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        pivot_table.assemble_records_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&pivot_table.records_writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <pivotCacheRecords xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" count="4">
+                  <r>
+                    <x v="0"/>
+                    <x v="0"/>
+                    <x v="0"/>
+                    <n v="1000"/>
+                  </r>
+                  <r>
+                    <x v="1"/>
+                    <x v="0"/>
+                    <x v="0"/>
+                    <n v="2500"/>
+                  </r>
+                  <r>
+                    <x v="0"/>
+                    <x v="1"/>
+                    <x v="1"/>
+                    <n v="3000"/>
+                  </r>
+                  <r>
+                    <x v="1"/>
+                    <x v="1"/>
+                    <x v="1"/>
+                    <n v="1500"/>
+                  </r>
+                </pivotCacheRecords>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_cache_errors() {
+        let mut pivot_table = PivotTable::new()
+            .set_row_fields(&[PivotField::new("Region")])
+            .set_data_fields(&[PivotDataField::new("Sales")]);
+
+        let mut data = sample_data();
+        data[2][3] = PivotCacheValue::Error(ExcelError::NA);
+        data[4][3] = PivotCacheValue::Error(ExcelError::Spill);
+
+        pivot_table.index = 1;
+        pivot_table.cell_range = CellRange::new(2, 0, 2, 0);
+        pivot_table.initialize(sample_source(), data).unwrap();
+
+        pivot_table.assemble_cache_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&pivot_table.cache_writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r##"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <pivotCacheDefinition xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" r:id="rId1" refreshOnLoad="1" createdVersion="6" refreshedVersion="6" minRefreshableVersion="3" recordCount="4">
+                  <cacheSource type="worksheet">
+                    <worksheetSource ref="A1:D5" sheet="Data"/>
+                  </cacheSource>
+                  <cacheFields count="4">
+                    <cacheField name="Region" numFmtId="0">
+                      <sharedItems count="2">
+                        <s v="East"/>
+                        <s v="West"/>
+                      </sharedItems>
+                    </cacheField>
+                    <cacheField name="Product" numFmtId="0">
+                      <sharedItems count="2">
+                        <s v="Apples"/>
+                        <s v="Pears"/>
+                      </sharedItems>
+                    </cacheField>
+                    <cacheField name="Quarter" numFmtId="0">
+                      <sharedItems count="2">
+                        <s v="Q1"/>
+                        <s v="Q2"/>
+                      </sharedItems>
+                    </cacheField>
+                    <cacheField name="Sales" numFmtId="0">
+                      <sharedItems containsString="0" containsMixedTypes="1" containsNumber="1" containsInteger="1" minValue="1000" maxValue="3000" count="4">
+                        <n v="1000"/>
+                        <e v="#N/A"/>
+                        <n v="3000"/>
+                        <e v="#SPILL!"/>
+                      </sharedItems>
+                    </cacheField>
+                  </cacheFields>
+                </pivotCacheDefinition>
+            "##,
+        );
+
+        assert_eq!(expected, got);
+
+        pivot_table.assemble_records_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&pivot_table.records_writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <pivotCacheRecords xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" count="4">
+                  <r>
+                    <x v="0"/>
+                    <x v="0"/>
+                    <x v="0"/>
+                    <x v="0"/>
+                  </r>
+                  <r>
+                    <x v="1"/>
+                    <x v="0"/>
+                    <x v="0"/>
+                    <x v="1"/>
+                  </r>
+                  <r>
+                    <x v="0"/>
+                    <x v="1"/>
+                    <x v="1"/>
+                    <x v="2"/>
+                  </r>
+                  <r>
+                    <x v="1"/>
+                    <x v="1"/>
+                    <x v="1"/>
+                    <x v="3"/>
+                  </r>
+                </pivotCacheRecords>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_multiple_data_fields() {
+        let mut pivot_table = PivotTable::new()
+            .set_row_fields(&[PivotField::new("Region")])
+            .set_data_fields(&[
+                PivotDataField::new("Sales"),
+                PivotDataField::new("Sales").set_function(PivotFunction::Average),
+            ]);
+
+        pivot_table.name = "PivotTable1".to_string();
+        pivot_table.index = 1;
+        pivot_table.cell_range = CellRange::new(2, 0, 2, 0);
+        pivot_table
+            .initialize(sample_source(), sample_data())
+            .unwrap();
+
+        pivot_table.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&pivot_table.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+                <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+                <pivotTableDefinition xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" name="PivotTable1" cacheId="1" applyNumberFormats="0" applyBorderFormats="0" applyFontFormats="0" applyPatternFormats="0" applyAlignmentFormats="0" applyWidthHeightFormats="1" dataCaption="Values" updatedVersion="6" minRefreshableVersion="3" useAutoFormatting="1" itemPrintTitles="1" createdVersion="6" indent="0" outline="1" outlineData="1" multipleFieldFilters="0">
+                  <location ref="A3:C6" firstHeaderRow="0" firstDataRow="1" firstDataCol="1"/>
+                  <pivotFields count="4">
+                    <pivotField axis="axisRow" showAll="0">
+                      <items count="3">
+                        <item x="0"/>
+                        <item x="1"/>
+                        <item t="default"/>
+                      </items>
+                    </pivotField>
+                    <pivotField showAll="0"/>
+                    <pivotField showAll="0"/>
+                    <pivotField dataField="1" showAll="0"/>
+                  </pivotFields>
+                  <rowFields count="1">
+                    <field x="0"/>
+                  </rowFields>
+                  <rowItems count="3">
+                    <i>
+                      <x/>
+                    </i>
+                    <i>
+                      <x v="1"/>
+                    </i>
+                    <i t="grand">
+                      <x/>
+                    </i>
+                  </rowItems>
+                  <colFields count="1">
+                    <field x="-2"/>
+                  </colFields>
+                  <colItems count="2">
+                    <i>
+                      <x/>
+                    </i>
+                    <i i="1">
+                      <x v="1"/>
+                    </i>
+                  </colItems>
+                  <dataFields count="2">
+                    <dataField name="Sum of Sales" fld="3" baseField="0" baseItem="0"/>
+                    <dataField name="Average of Sales" fld="3" subtotal="average" baseField="0" baseItem="0"/>
+                  </dataFields>
+                  <pivotTableStyleInfo name="PivotStyleLight16" showRowHeaders="1" showColHeaders="1" showRowStripes="0" showColStripes="0" showLastColumn="1"/>
+                </pivotTableDefinition>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+}
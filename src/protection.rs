@@ -6,6 +6,8 @@
 
 #![warn(missing_docs)]
 
+use crate::utility;
+
 /// The `ProtectionOptions` struct is used to set protected elements in a worksheet.
 ///
 /// You can specify which worksheet elements should be protected or unprotected via
@@ -156,3 +158,118 @@ impl ProtectionOptions {
         }
     }
 }
+
+/// The `ProtectionHash` enum defines the algorithm used to hash protection
+/// passwords.
+///
+/// Excel stores a hash of the password used to protect a workbook or worksheet
+/// rather than the password itself. Older versions of Excel used a 16 bit hash
+/// which is easy to reverse. Excel 2013 and later use a salted SHA-512 hash
+/// that is iterated 100,000 times. The SHA-512 options require the
+/// `protection_hash` feature.
+///
+/// Note, protection passwords only stop a user from accidentally changing the
+/// structure or data of a file. The data isn't encrypted and the protection
+/// can be removed by editing the file. To encrypt the file see
+/// [`Workbook::set_open_password()`](crate::Workbook::set_open_password),
+/// which requires the `encryption` feature.
+///
+/// See [`Workbook::set_protection_hash()`](crate::Workbook::set_protection_hash)
+/// and [`Worksheet::set_protection_hash()`](crate::Worksheet::set_protection_hash).
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ProtectionHash {
+    /// The legacy 16 bit password hash. This is the default and is supported
+    /// by all versions of Excel.
+    #[default]
+    Legacy,
+
+    /// A SHA-512 password hash with a random salt and a spin count of
+    /// 100,000, like Excel 2013 and later. The salt is generated when the file
+    /// is saved, see [`Workbook::save()`](crate::Workbook::save).
+    #[cfg(feature = "protection_hash")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "protection_hash")))]
+    Sha512,
+
+    /// A SHA-512 password hash with a user defined salt and spin count. This
+    /// is mainly useful for creating reproducible output files, for example
+    /// when comparing files in tests.
+    #[cfg(feature = "protection_hash")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "protection_hash")))]
+    Sha512WithSalt {
+        /// The salt bytes added to the password before hashing. Excel uses a
+        /// 16 byte salt.
+        salt: Vec<u8>,
+
+        /// The number of times the hash is iterated. Excel uses 100,000.
+        spin_count: u32,
+    },
+}
+
+impl ProtectionHash {
+    // Get the XML attributes for the hash of a password. The workbookProtection
+    // element uses prefixed attribute names like "workbookPassword". The random
+    // salt for the `Sha512` hash is generated before the file is saved.
+    #[cfg_attr(not(feature = "protection_hash"), allow(unused_variables))]
+    pub(crate) fn password_attributes(
+        &self,
+        password: &str,
+        is_workbook: bool,
+        random_salt: &[u8],
+    ) -> Vec<(&'static str, String)> {
+        let names = if is_workbook {
+            [
+                "workbookPassword",
+                "workbookAlgorithmName",
+                "workbookHashValue",
+                "workbookSaltValue",
+                "workbookSpinCount",
+            ]
+        } else {
+            [
+                "password",
+                "algorithmName",
+                "hashValue",
+                "saltValue",
+                "spinCount",
+            ]
+        };
+
+        if password.is_empty() {
+            return vec![];
+        }
+
+        match self {
+            ProtectionHash::Legacy => {
+                let hash = utility::hash_password(password);
+                vec![(names[0], format!("{hash:04X}"))]
+            }
+            #[cfg(feature = "protection_hash")]
+            ProtectionHash::Sha512 => {
+                Self::sha512_attributes(&names, password, random_salt, 100_000)
+            }
+            #[cfg(feature = "protection_hash")]
+            ProtectionHash::Sha512WithSalt { salt, spin_count } => {
+                Self::sha512_attributes(&names, password, salt, *spin_count)
+            }
+        }
+    }
+
+    // Get the XML attributes for a salted SHA-512 password hash.
+    #[cfg(feature = "protection_hash")]
+    fn sha512_attributes(
+        names: &[&'static str; 5],
+        password: &str,
+        salt: &[u8],
+        spin_count: u32,
+    ) -> Vec<(&'static str, String)> {
+        let hash = utility::hash_password_sha512(password, salt, spin_count);
+
+        vec![
+            (names[1], "SHA-512".to_string()),
+            (names[2], utility::base64_encode(&hash)),
+            (names[3], utility::base64_encode(salt)),
+            (names[4], spin_count.to_string()),
+        ]
+    }
+}
@@ -0,0 +1,176 @@
+// record_batch - A module for converting Apache Arrow record batch columns to
+// Excel cell data.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use arrow::array::{Array, ArrayRef, AsArray, BooleanArray, Float64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Float64Type, TimeUnit};
+
+use crate::datetime::{column_decimal_format, unix_days_to_serial, ColumnTimeUnit};
+use crate::{DataFrameOptions, Format, XlsxError};
+
+// A record batch column converted to a limited number of array types that map
+// to the Excel cell types. The date and time data is converted to Excel serial
+// datetimes. The default formats for the data type are taken from the
+// `DataFrameOptions`.
+pub(crate) enum RecordBatchColumn {
+    Null,
+    Boolean(BooleanArray),
+    Number(Float64Array, Option<Format>),
+    String(StringArray),
+    DateTime(Float64Array, Format),
+}
+
+impl RecordBatchColumn {
+    // Convert an Arrow array to one of the supported column types.
+    pub(crate) fn new(
+        array: &ArrayRef,
+        field_name: &str,
+        options: &DataFrameOptions,
+    ) -> Result<RecordBatchColumn, XlsxError> {
+        let column = match array.data_type() {
+            DataType::Null => RecordBatchColumn::Null,
+
+            DataType::Boolean => RecordBatchColumn::Boolean(array.as_boolean().clone()),
+
+            DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float16
+            | DataType::Float32
+            | DataType::Float64 => RecordBatchColumn::Number(Self::to_f64(array, None)?, None),
+
+            // Decimals are written as numbers with a format that displays the
+            // same number of decimal places as the decimal scale.
+            DataType::Decimal32(_, scale)
+            | DataType::Decimal64(_, scale)
+            | DataType::Decimal128(_, scale)
+            | DataType::Decimal256(_, scale) => {
+                let format = usize::try_from(*scale).ok().and_then(column_decimal_format);
+
+                RecordBatchColumn::Number(Self::to_f64(array, None)?, format)
+            }
+
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+                RecordBatchColumn::String(cast(array, &DataType::Utf8)?.as_string().clone())
+            }
+
+            DataType::Dictionary(_, value_type)
+                if matches!(
+                    value_type.as_ref(),
+                    DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+                ) =>
+            {
+                RecordBatchColumn::String(cast(array, &DataType::Utf8)?.as_string().clone())
+            }
+
+            DataType::Date32 => {
+                let days = Self::to_f64(array, Some(DataType::Int32))?;
+                let dates = days.unary(unix_days_to_serial);
+
+                RecordBatchColumn::DateTime(dates, options.date_format.clone())
+            }
+
+            DataType::Date64 => {
+                let unit = ColumnTimeUnit::Milliseconds;
+                let milliseconds = Self::to_f64(array, Some(DataType::Int64))?;
+                let dates = milliseconds.unary(|ms| unit.unix_time_to_serial(ms));
+
+                RecordBatchColumn::DateTime(dates, options.date_format.clone())
+            }
+
+            // Datetimes with a timezone are stored, and written, as UTC values.
+            DataType::Timestamp(unit, _) => {
+                let unit = ColumnTimeUnit::from(unit);
+                let values = Self::to_f64(array, Some(DataType::Int64))?;
+                let datetimes = values.unary(|value| unit.unix_time_to_serial(value));
+
+                RecordBatchColumn::DateTime(datetimes, options.datetime_format.clone())
+            }
+
+            DataType::Time32(unit) | DataType::Time64(unit) => {
+                let unit = ColumnTimeUnit::from(unit);
+                let via_type = match array.data_type() {
+                    DataType::Time32(_) => DataType::Int32,
+                    _ => DataType::Int64,
+                };
+                let values = Self::to_f64(array, Some(via_type))?;
+                let times = values.unary(|value| unit.to_days(value));
+
+                RecordBatchColumn::DateTime(times, options.time_format.clone())
+            }
+
+            // Durations are written as numbers, in days, with a time format
+            // since they may be negative or outside the Excel date range.
+            DataType::Duration(unit) => {
+                let unit = ColumnTimeUnit::from(unit);
+                let values = Self::to_f64(array, Some(DataType::Int64))?;
+                let days = values.unary(|value| unit.to_days(value));
+
+                RecordBatchColumn::Number(days, Some(options.duration_format.clone()))
+            }
+
+            data_type => {
+                return Err(XlsxError::ParameterError(format!(
+                    "Arrow data type '{data_type}' in field '{field_name}' is not supported"
+                )))
+            }
+        };
+
+        Ok(column)
+    }
+
+    // Check if a value in the column is null.
+    pub(crate) fn is_null(&self, index: usize) -> bool {
+        match self {
+            RecordBatchColumn::Null => true,
+            RecordBatchColumn::Boolean(array) => array.is_null(index),
+            RecordBatchColumn::String(array) => array.is_null(index),
+            RecordBatchColumn::Number(array, _) | RecordBatchColumn::DateTime(array, _) => {
+                array.is_null(index)
+            }
+        }
+    }
+
+    // Get the default format for the column data type, if any.
+    pub(crate) fn format(&self) -> Option<&Format> {
+        match self {
+            RecordBatchColumn::Number(_, format) => format.as_ref(),
+            RecordBatchColumn::DateTime(_, format) => Some(format),
+            _ => None,
+        }
+    }
+
+    // Cast an array to f64 values, via an intermediate integer type for the
+    // temporal types that can't be cast directly.
+    fn to_f64(array: &ArrayRef, via_type: Option<DataType>) -> Result<Float64Array, XlsxError> {
+        let array = match via_type {
+            Some(via_type) => cast(array, &via_type)?,
+            None => array.clone(),
+        };
+
+        Ok(cast(&array, &DataType::Float64)?
+            .as_primitive::<Float64Type>()
+            .clone())
+    }
+}
+
+// Map the Arrow time units to the units used for the date conversions.
+impl From<&TimeUnit> for ColumnTimeUnit {
+    fn from(unit: &TimeUnit) -> ColumnTimeUnit {
+        match unit {
+            TimeUnit::Second => ColumnTimeUnit::Seconds,
+            TimeUnit::Millisecond => ColumnTimeUnit::Milliseconds,
+            TimeUnit::Microsecond => ColumnTimeUnit::Microseconds,
+            TimeUnit::Nanosecond => ColumnTimeUnit::Nanoseconds,
+        }
+    }
+}
@@ -76,6 +76,23 @@ impl Relationship {
         ));
     }
 
+    // Add a relationship with a fully qualified type from a template file and
+    // return the relationship id that it will be written with.
+    pub(crate) fn add_template_relationship(
+        &mut self,
+        rel_type: &str,
+        target: &str,
+        target_mode: &str,
+    ) -> String {
+        self.relationships.push((
+            rel_type.to_string(),
+            target.to_string(),
+            target_mode.to_string(),
+        ));
+
+        format!("rId{}", self.relationships.len())
+    }
+
     // -----------------------------------------------------------------------
     // XML assembly methods.
     // -----------------------------------------------------------------------
@@ -31,6 +31,8 @@
 //!   attributes](#controlling-excel-output-via-xlsxserialize-and-struct-attributes)
 //!   - [Container `xlsx` attributes](#container-xlsx-attributes)
 //!   - [Field `xlsx` attributes](#field-xlsx-attributes)
+//! - [Serializing maps](#serializing-maps)
+//! - [Deserializing worksheet data](#deserializing-worksheet-data)
 //! - [Limitations of serializing to
 //!   Excel](#limitations-of-serializing-to-excel)
 //!
@@ -1929,6 +1931,103 @@
 //!
 //!
 //!
+//! ## Serializing maps
+//!
+//! Maps such as `BTreeMap<String, T>` and `HashMap<String, T>`, or JSON
+//! objects such as `serde_json::Value`, can be serialized as worksheet rows
+//! where each map key is mapped to a column. Structs that use the Serde
+//! [`flatten`] attribute are also handled as maps since Serde converts their
+//! fields into a map.
+//!
+//! Maps don't have a fixed set of keys so the headers are discovered from the
+//! map instance, or vector of map instances, passed to
+//! [`Worksheet::serialize_headers_with_options()`] or
+//! [`Worksheet::serialize_headers()`]. For a vector of records the headers are
+//! the union of the keys of all the records, in the order they are first seen.
+//! Alternatively the keys, and their column order, can be set explicitly with
+//! [`SerializeFieldOptions::set_map_keys()`]. This is required with
+//! [`Worksheet::deserialize_headers_with_options()`] since the keys can't be
+//! determined via deserialization.
+//!
+//! Keys that aren't in the headers raise an [`XlsxError::SerdeError`] error
+//! when the data is serialized, unless the
+//! [`SerializeFieldOptions::add_unknown_keys()`] option is turned on, in which
+//! case they are added as new columns. Keys that are missing from a record are
+//! left as blank cells. The keys can be renamed, formatted or skipped with
+//! [`CustomSerializeField`] like struct fields.
+//!
+//! Maps don't have a struct name so the Rust type name, such as `BTreeMap`, is
+//! used instead, for example with [`Worksheet::get_serialize_dimensions()`].
+//!
+//! The following example demonstrates serializing map records to a worksheet.
+//! The headers are discovered from the keys of all the records and an unknown
+//! key in a later record is added as a new column.
+//!
+//! ```
+//! # // This code is available in examples/doc_worksheet_serialize_maps.rs
+//! #
+//! use std::collections::BTreeMap;
+//!
+//! use rust_xlsxwriter::{Format, SerializeFieldOptions, Workbook, XlsxError};
+//!
+//! fn main() -> Result<(), XlsxError> {
+//!     let mut workbook = Workbook::new();
+//!
+//!     // Add a worksheet to the workbook.
+//!     let worksheet = workbook.add_worksheet();
+//!
+//!     // Add a format for the headers.
+//!     let header_format = Format::new().set_bold();
+//!
+//!     // Create some map records. Not all the records have the same keys.
+//!     let records = vec![
+//!         BTreeMap::from([("Apple", 10), ("Pear", 5)]),
+//!         BTreeMap::from([("Apple", 8), ("Orange", 7)]),
+//!     ];
+//!
+//!     // Set the serialization location and headers. The headers are the union
+//!     // of the keys of all the records.
+//!     let header_options = SerializeFieldOptions::new()
+//!         .set_header_format(&header_format)
+//!         .add_unknown_keys(true);
+//!
+//!     worksheet.serialize_headers_with_options(0, 0, &records, &header_options)?;
+//!
+//!     // Serialize the data.
+//!     worksheet.serialize(&records)?;
+//!
+//!     // Serialize a record with a new key. It is added as a new column.
+//!     let record = BTreeMap::from([("Apple", 6), ("Pear", 4), ("Plum", 12)]);
+//!     worksheet.serialize(&record)?;
+//!
+//!     // Save the file.
+//!     workbook.save("serialize.xlsx")?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//!
+//!
+//!
+//! ## Deserializing worksheet data
+//!
+//! Worksheet data can be read back into structs that derive the Serde
+//! [`Deserialize`] trait with [`Worksheet::deserialize_rows()`] or
+//! [`Worksheet::deserialize_rows_with_options()`]. These read a header row
+//! and the data rows beneath it and return an iterator of deserialized
+//! structs. The headers are mapped to the struct fields in the same way as for
+//! serialization, including fields renamed via Serde attributes,
+//! [`CustomSerializeField::rename()`] or `#[xlsx()]` attributes. This is
+//! useful for round trip tests and for reading data that users have entered
+//! into a worksheet. Data in an existing xlsx file can be read by loading it
+//! with [`Workbook::from_template()`](crate::Workbook::from_template).
+//!
+//! [`Deserialize`]: serde::Deserialize
+//!
+//!
+//!
+//!
 //! ## Limitations of serializing to Excel
 //!
 //! The cell/grid format of Excel sets a physical limitation on what can be
@@ -1938,16 +2037,21 @@
 //! serializing data to Excel via `rust_xlsxwriter`, it is best to consider what
 //! that data will look like while designing your serialization.
 //!
-//! It is only possible to serialize structs or a vector of struct values.
-//! Within a struct most native Rust types are supported (where there is some
-//! mapping to an Excel cell type), as well as the Date/Time types listed above.
-//!
-//! Maps are not supported because they don't have a fixed schema that can be
-//! mapped to a 2D grid of cells and the order of the fields is not guaranteed.
-//! As a side effect of this it also not possible to serialize structs that use
-//! the Serde [`flatten`] attribute, since Serde converts the fixed set of named
-//! fields into a dynamically sized map. However, a struct that contains a map
-//! field can be serialized if the field is omitted via one of the [field
+//! It is only possible to serialize structs, maps with string keys, or a
+//! vector of these values. Within a struct most native Rust types are supported
+//! (where there is some mapping to an Excel cell type), as well as the
+//! Date/Time types listed above.
+//!
+//! Nested structs aren't mapped to worksheet columns by default. However, they
+//! can be flattened into columns with prefixed header names such as
+//! `customer.name` via the
+//! [`SerializeFieldOptions::flatten_nested_structs()`] option.
+//!
+//! Maps are supported as records, see [Serializing maps](#serializing-maps)
+//! above, and as nested fields when they are flattened. Nested maps that aren't
+//! flattened aren't supported because they don't have a fixed schema that can
+//! be mapped to a 2D grid of cells. A struct that contains a map field can
+//! still be serialized if the field is omitted via one of the [field
 //! skipping](#skipping-fields-when-serializing) methods such as the
 //! `#[serde(skip)]`,`#[xlsx(skip)] or [`CustomSerializeField::skip()`]; or by
 //! excluding it via [`SerializeFieldOptions::use_custom_headers_only()`].
@@ -1971,13 +2075,35 @@
 //!
 #![warn(missing_docs)]
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use crate::{ColNum, Format, RowNum, Table, TableStyle, Worksheet, XlsxError};
+use crate::{ColNum, ExcelDateTime, Format, RowNum, Table, TableStyle, Worksheet, XlsxError};
 use serde::de::Visitor;
 use serde::{ser, Deserialize, Deserializer, Serialize};
 
+// The newtype struct name used to mark serialized dates/times so that they can
+// be written as Excel datetimes.
+pub(crate) const SERDE_DATETIME_NAME: &str = "rust_xlsxwriter::ExcelDateTime";
+
+// Serialize an Excel serial datetime. When the target is a worksheet the value
+// is marked with a newtype struct so that it can be converted to the workbook
+// date epoch. Other serializers get a plain f64 value, as in previous versions,
+// since formats like RON would otherwise write the newtype struct wrapper.
+pub(crate) fn serialize_excel_datetime<S>(
+    serializer: S,
+    serial_datetime: f64,
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    if std::any::type_name::<S>() == std::any::type_name::<&mut Worksheet>() {
+        serializer.serialize_newtype_struct(SERDE_DATETIME_NAME, &serial_datetime)
+    } else {
+        serializer.serialize_f64(serial_datetime)
+    }
+}
+
 // Convenience tuple struct Table data used for serialization formatting.
 pub(crate) struct TableData(
     pub(crate) RowNum,
@@ -1996,6 +2122,21 @@ pub(crate) struct SerializerState {
     pub(crate) structs: HashMap<String, SerializationHeaderConfig>,
     pub(crate) current_struct: String,
     pub(crate) current_field: String,
+    pub(crate) is_datetime: bool,
+    pub(crate) levels: Vec<SerializerLevel>,
+    pub(crate) map_struct: String,
+}
+
+// The type of struct, or map, at each level of nesting during serialization.
+pub(crate) enum SerializerLevel {
+    // A top level struct, or a nested struct that isn't flattened.
+    Struct,
+
+    // A flattened nested struct or map, with the prefix for its field names.
+    Nested(String),
+
+    // A map in a skipped field.
+    Skipped,
 }
 
 impl SerializerState {
@@ -2005,6 +2146,9 @@ impl SerializerState {
             structs: HashMap::new(),
             current_struct: String::new(),
             current_field: String::new(),
+            is_datetime: false,
+            levels: vec![],
+            map_struct: String::new(),
         }
     }
 
@@ -2050,6 +2194,70 @@ impl SerializerState {
         header_config.max_row += 1;
     }
 
+    // Start serializing a struct. Nested structs are flattened into prefixed
+    // fields of the parent struct if that option is on, otherwise they are
+    // handled as a new struct.
+    pub(crate) fn start_struct(&mut self, struct_name: &str) {
+        if !self.levels.is_empty() && self.nested_separator().is_some() {
+            self.levels
+                .push(SerializerLevel::Nested(self.current_field.clone()));
+        } else {
+            self.set_current_struct(struct_name);
+            self.levels.push(SerializerLevel::Struct);
+        }
+    }
+
+    // Start serializing a map. A top level map is a map record, or a struct
+    // that uses `#[serde(flatten)]`, and it is mapped to the struct whose
+    // headers were discovered from a map. Nested maps are flattened like nested structs, if
+    // that option is on, and maps in skipped fields are ignored. Returns false
+    // for maps that can't be serialized.
+    pub(crate) fn start_map(&mut self) -> bool {
+        if self.levels.is_empty() {
+            if !self.map_struct.is_empty() {
+                let struct_name = self.map_struct.clone();
+                self.set_current_struct(&struct_name);
+                self.levels.push(SerializerLevel::Struct);
+                return true;
+            }
+        } else if self.nested_separator().is_some() {
+            self.levels
+                .push(SerializerLevel::Nested(self.current_field.clone()));
+            return true;
+        }
+
+        if self.is_unselected_field() {
+            self.levels.push(SerializerLevel::Skipped);
+            return true;
+        }
+
+        false
+    }
+
+    // Check if the current map is in a skipped field.
+    pub(crate) fn is_skipped_map(&self) -> bool {
+        matches!(self.levels.last(), Some(SerializerLevel::Skipped))
+    }
+
+    // Store the name of the current field, with the prefix of any flattened
+    // parent structs.
+    pub(crate) fn set_current_field(&mut self, field_name: &str) {
+        self.current_field = match (self.levels.last(), self.nested_separator()) {
+            (Some(SerializerLevel::Nested(prefix)), Some(separator)) => {
+                format!("{prefix}{separator}{field_name}")
+            }
+            _ => field_name.to_string(),
+        };
+    }
+
+    // Get the field name separator if the current struct flattens nested
+    // structs.
+    fn nested_separator(&self) -> Option<&str> {
+        self.structs
+            .get(&self.current_struct)
+            .and_then(|header_config| header_config.nested_separator.as_deref())
+    }
+
     // Get dimensions of a serialization area. This is the internal function for
     // worksheet.get_serialize_dimensions().
     pub(crate) fn get_dimensions(
@@ -2122,6 +2330,11 @@ pub(crate) struct SerializationHeaderConfig {
     pub(crate) max_row: RowNum,
     pub(crate) max_col: ColNum,
     pub(crate) table: Option<Table>,
+    pub(crate) nested_separator: Option<String>,
+    pub(crate) known_fields: HashSet<String>,
+    pub(crate) has_headers: bool,
+    pub(crate) header_format: Option<Format>,
+    pub(crate) add_unknown_keys: bool,
 }
 
 impl SerializationHeaderConfig {
@@ -2250,6 +2463,10 @@ pub struct SerializeFieldOptions {
     pub(crate) custom_headers: Vec<CustomSerializeField>,
     pub(crate) use_custom_headers_only: bool,
     pub(crate) table: Option<Table>,
+    pub(crate) flatten_nested_structs: bool,
+    pub(crate) nested_separator: String,
+    pub(crate) map_keys: Vec<String>,
+    pub(crate) add_unknown_keys: bool,
 }
 
 impl Default for SerializeFieldOptions {
@@ -2277,6 +2494,10 @@ impl SerializeFieldOptions {
             custom_headers: vec![],
             use_custom_headers_only: false,
             table: None,
+            flatten_nested_structs: false,
+            nested_separator: ".".to_string(),
+            map_keys: vec![],
+            add_unknown_keys: false,
         }
     }
 
@@ -2917,6 +3138,188 @@ impl SerializeFieldOptions {
         self
     }
 
+    /// Set the option to flatten nested structs into prefixed columns.
+    ///
+    /// By default only the fields of the serialized struct are mapped to
+    /// worksheet columns. With this option turned on the fields of nested
+    /// structs, and of structs that use the Serde `#[serde(flatten)]`
+    /// attribute, are also mapped to columns.
+    ///
+    /// The header names for nested struct fields are prefixed with the name of
+    /// the parent field and a separator, for example `customer.name` for the
+    /// `name` field of a `customer` field. The separator can be changed with
+    /// [`SerializeFieldOptions::set_nested_separator()`] and the prefixed
+    /// names can be used with [`CustomSerializeField`] to rename, format or
+    /// skip the nested fields. The fields of `#[serde(flatten)]` structs
+    /// aren't prefixed since Serde merges them with the parent fields.
+    ///
+    /// The nested fields are discovered from the struct instance passed to
+    /// [`Worksheet::serialize_headers_with_options()`] so any `Option` fields
+    /// that contain nested structs should have a `Some` value in that
+    /// instance. The option isn't supported by
+    /// [`Worksheet::deserialize_headers_with_options()`] since the nested
+    /// field types can't be determined via deserialization.
+    ///
+    /// Structs that use `#[serde(flatten)]` are serialized by Serde as maps
+    /// without a struct name so they are handled like other map records, see
+    /// [Serializing maps](crate::serializer#serializing-maps).
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates serializing a struct with a nested
+    /// struct to a worksheet with the nested fields flattened into columns.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_serialize_nested.rs
+    /// #
+    /// # use rust_xlsxwriter::{CustomSerializeField, SerializeFieldOptions, Workbook, XlsxError};
+    /// # use serde::Serialize;
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Create serializable structs with a nested struct.
+    ///     #[derive(Serialize)]
+    ///     struct Customer {
+    ///         name: &'static str,
+    ///         id: u32,
+    ///     }
+    ///
+    ///     #[derive(Serialize)]
+    ///     struct Order {
+    ///         item: &'static str,
+    ///         customer: Customer,
+    ///         cost: f64,
+    ///     }
+    ///
+    ///     // Create some data instances.
+    ///     let orders = [
+    ///         Order {
+    ///             item: "Peach",
+    ///             customer: Customer {
+    ///                 name: "Alice",
+    ///                 id: 101,
+    ///             },
+    ///             cost: 1.05,
+    ///         },
+    ///         Order {
+    ///             item: "Plum",
+    ///             customer: Customer {
+    ///                 name: "Bob",
+    ///                 id: 102,
+    ///             },
+    ///             cost: 0.15,
+    ///         },
+    ///     ];
+    ///
+    ///     // Flatten the nested struct and rename one of the nested fields. The
+    ///     // other nested field uses the default "customer.id" header.
+    ///     let header_options = SerializeFieldOptions::new()
+    ///         .flatten_nested_structs(true)
+    ///         .set_custom_headers(&[CustomSerializeField::new("customer.name").rename("Customer")]);
+    ///
+    ///     // Set the serialization location and headers.
+    ///     worksheet.serialize_headers_with_options(0, 0, &orders[0], &header_options)?;
+    ///
+    ///     // Serialize the data.
+    ///     worksheet.serialize(&orders)?;
+    /// #
+    /// #     // Save the file.
+    /// #     workbook.save("serialize.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn flatten_nested_structs(mut self, enable: bool) -> SerializeFieldOptions {
+        self.flatten_nested_structs = enable;
+        self
+    }
+
+    /// Set the separator used in the header names of flattened nested struct
+    /// fields.
+    ///
+    /// See [`SerializeFieldOptions::flatten_nested_structs()`] above.
+    ///
+    /// # Parameters
+    ///
+    /// - `separator`: The separator between the parent field name and the
+    ///   nested field name. The default is `"."`.
+    ///
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn set_nested_separator(mut self, separator: impl Into<String>) -> SerializeFieldOptions {
+        self.nested_separator = separator.into();
+        self
+    }
+
+    /// Set the keys, and column order, of serialized map records.
+    ///
+    /// Maps such as `BTreeMap<String, T>`, `HashMap<String, T>` or JSON
+    /// objects like `serde_json::Value` can be serialized as worksheet rows
+    /// where each map key is mapped to a column. By default the keys are
+    /// discovered from the map instance, or vector of map instances, passed to
+    /// [`Worksheet::serialize_headers_with_options()`]. This option can be used
+    /// to set the keys and column order explicitly instead. It is also
+    /// required with [`Worksheet::deserialize_headers_with_options()`] since
+    /// the map keys can't be determined via deserialization.
+    ///
+    /// Maps don't have a struct name so the Rust type name, such as `BTreeMap`,
+    /// is used instead, for example with
+    /// [`Worksheet::get_serialize_dimensions()`]. Only one map type can be
+    /// serialized to a worksheet at a time.
+    ///
+    /// See [Serializing maps](crate::serializer#serializing-maps) for an
+    /// example.
+    ///
+    /// # Parameters
+    ///
+    /// - `keys`: An iterator of the map keys, in column order.
+    ///
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn set_map_keys<I, S>(mut self, keys: I) -> SerializeFieldOptions
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.map_keys = keys.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Add unknown map keys as new columns.
+    ///
+    /// When serializing map records the keys are mapped to the columns that
+    /// were set up with the serialization headers. By default a key that
+    /// doesn't match one of those columns raises an
+    /// [`XlsxError::SerdeError`] error. With this option turned on unknown
+    /// keys are added as new columns to the right of the existing columns,
+    /// with the key as the header.
+    ///
+    /// Adding columns isn't supported in `constant_memory` mode since the
+    /// header row may already have been written to disk. Setting up the
+    /// serialization headers with this option on a `constant_memory`
+    /// worksheet raises an [`XlsxError::ParameterError`] error.
+    ///
+    /// See [Serializing maps](crate::serializer#serializing-maps) for an
+    /// example.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn add_unknown_keys(mut self, enable: bool) -> SerializeFieldOptions {
+        self.add_unknown_keys = enable;
+        self
+    }
+
     /// Set the name of the struct to be serialized.
     ///
     /// Note, this is a semi public method. End users won't and shouldn't need
@@ -3618,6 +4021,14 @@ impl ser::Serializer for &mut Worksheet {
 
     #[doc(hidden)]
     fn serialize_f64(self, data: f64) -> Result<(), XlsxError> {
+        // Write dates/times marked by the datetime serialization functions as
+        // Excel datetimes so that they can be converted to the workbook epoch.
+        if self.serializer_state.is_datetime {
+            if let Ok(datetime) = ExcelDateTime::from_serial_datetime(data) {
+                return self.serialize_to_worksheet_cell(datetime);
+            }
+        }
+
         self.serialize_to_worksheet_cell(data)
     }
 
@@ -3680,12 +4091,21 @@ impl ser::Serializer for &mut Worksheet {
         variant.serialize(self)
     }
 
-    // Try to handle this as a single value.
+    // Try to handle this as a single value. Serialized dates/times are marked
+    // with a newtype struct name.
     #[doc(hidden)]
-    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<(), XlsxError>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<(), XlsxError>
     where
         T: ?Sized + Serialize,
     {
+        if name == SERDE_DATETIME_NAME {
+            self.serializer_state.is_datetime = true;
+            let result = value.serialize(&mut *self);
+            self.serializer_state.is_datetime = false;
+
+            return result;
+        }
+
         value.serialize(self)
     }
 
@@ -3716,7 +4136,7 @@ impl ser::Serializer for &mut Worksheet {
         _len: usize,
     ) -> Result<Self::SerializeStruct, XlsxError> {
         // Store the struct type name to check against user defined structs.
-        self.serializer_state.set_current_struct(name);
+        self.serializer_state.start_struct(name);
 
         Ok(self)
     }
@@ -3757,18 +4177,21 @@ impl ser::Serializer for &mut Worksheet {
 
     // Maps, and structs that use `#[serde(flatten)]`, are serialized by Serde
     // as maps. These don't carry a struct name or a static list of field names
-    // so they can't be mapped onto Excel's fixed 2D grid of cells. We reject
-    // them with an explicit error rather than silently writing nothing. However
-    // we also check if the map can be explicitly skipped by the user.
+    // so they can only be mapped onto Excel's fixed 2D grid of cells as top
+    // level records whose headers were set from a map, or as nested fields
+    // with the `flatten_nested_structs()` option. We reject other maps with an
+    // explicit error rather than silently writing nothing. However we also
+    // check if the map can be explicitly skipped by the user.
     #[doc(hidden)]
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, XlsxError> {
-        if self.serializer_state.is_unselected_field() {
+        if self.serializer_state.start_map() {
             return Ok(self);
         }
 
         Err(XlsxError::SerdeError(
-            "Map types and structs that use `#[serde(flatten)]` cannot be serialized to Excel. \
-            See the limitations section in the `rust_xlsxwriter::serializer` documentation."
+            "Map types can only be serialized to Excel as records after setting the headers \
+            from a map, or as nested fields with the `SerializeFieldOptions::flatten_nested_structs()` \
+            option. See the limitations section in the `rust_xlsxwriter::serializer` documentation."
                 .to_string(),
         ))
     }
@@ -3801,12 +4224,13 @@ impl ser::SerializeStruct for &mut Worksheet {
         T: ?Sized + Serialize,
     {
         // Store field name to allow us to map to the correct header/column.
-        self.serializer_state.current_field = key.to_string();
+        self.serializer_state.set_current_field(key);
 
         value.serialize(&mut **self)
     }
 
     fn end(self) -> Result<(), XlsxError> {
+        self.serializer_state.levels.pop();
         Ok(())
     }
 }
@@ -3884,28 +4308,43 @@ impl ser::SerializeTupleVariant for &mut Worksheet {
     }
 }
 
-// Maps only get to this point if they are in a skipped/deselected field, see
-// `serialize_map()` above. Ignore the keys and values without traversing them.
+// Maps only get to this point if they are records, flattened fields or if they
+// are in a skipped/deselected field, see `serialize_map()` above. The keys of
+// records and flattened maps are handled like struct field names, and unknown
+// keys are checked. Skipped maps are ignored without traversing the keys and
+// values.
 #[doc(hidden)]
 impl ser::SerializeMap for &mut Worksheet {
     type Ok = ();
     type Error = XlsxError;
 
-    fn serialize_key<T>(&mut self, _key: &T) -> Result<(), XlsxError>
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), XlsxError>
     where
         T: ?Sized + Serialize,
     {
-        Ok(())
+        if self.serializer_state.is_skipped_map() {
+            return Ok(());
+        }
+
+        let key = map_key_to_string(key)?;
+        self.serializer_state.set_current_field(&key);
+
+        self.check_serialized_map_key()
     }
 
-    fn serialize_value<T>(&mut self, _value: &T) -> Result<(), XlsxError>
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), XlsxError>
     where
         T: ?Sized + Serialize,
     {
-        Ok(())
+        if self.serializer_state.is_skipped_map() {
+            return Ok(());
+        }
+
+        value.serialize(&mut **self)
     }
 
     fn end(self) -> Result<(), XlsxError> {
+        self.serializer_state.levels.pop();
         Ok(())
     }
 }
@@ -3936,6 +4375,82 @@ impl ser::SerializeStructVariant for &mut Worksheet {
 pub(crate) struct SerializerHeader {
     pub(crate) struct_name: String,
     pub(crate) field_names: Vec<String>,
+    pub(crate) seen_field_names: HashSet<String>,
+    pub(crate) nested_separator: Option<String>,
+    pub(crate) is_map: bool,
+    pub(crate) is_nested: bool,
+    pub(crate) map_key: String,
+}
+
+impl SerializerHeader {
+    // Create a new SerializerHeader struct.
+    pub(crate) fn new() -> SerializerHeader {
+        SerializerHeader {
+            struct_name: String::new(),
+            field_names: vec![],
+            seen_field_names: HashSet::new(),
+            nested_separator: None,
+            is_map: false,
+            is_nested: false,
+            map_key: String::new(),
+        }
+    }
+
+    // Set the struct name and field names of map records. Maps don't have a
+    // name so the type name is used instead, and the field names discovered
+    // from the map keys are replaced by any keys set by the user.
+    pub(crate) fn set_map_headers<T>(&mut self, map_keys: &[String])
+    where
+        T: ?Sized,
+    {
+        if !self.is_map {
+            return;
+        }
+
+        if self.struct_name.is_empty() {
+            self.struct_name = struct_type_name::<T>();
+        }
+
+        if !map_keys.is_empty() {
+            self.field_names = map_keys.to_vec();
+            self.seen_field_names = map_keys.iter().cloned().collect();
+        }
+    }
+
+    // Store a struct field name. If nested structs are being flattened then
+    // the value is also serialized to check if it is a struct, or map, and if
+    // so its field names are stored with a prefix.
+    fn store_field_name<T>(&mut self, field_name: &str, value: &T) -> Result<(), XlsxError>
+    where
+        T: ?Sized + Serialize,
+    {
+        if let Some(separator) = self.nested_separator.clone() {
+            let mut nested_headers = SerializerHeader::new();
+            nested_headers.nested_separator = Some(separator.clone());
+            nested_headers.is_nested = true;
+
+            value.serialize(&mut nested_headers)?;
+
+            if !nested_headers.struct_name.is_empty() || nested_headers.is_map {
+                for nested_field_name in nested_headers.field_names {
+                    self.push_field_name(format!("{field_name}{separator}{nested_field_name}"));
+                }
+
+                return Ok(());
+            }
+        }
+
+        self.push_field_name(field_name.to_string());
+        Ok(())
+    }
+
+    // Store a field name if it hasn't been seen already. Map records can have
+    // repeated, or different, keys in each record.
+    fn push_field_name(&mut self, field_name: String) {
+        if self.seen_field_names.insert(field_name.clone()) {
+            self.field_names.push(field_name);
+        }
+    }
 }
 
 // -----------------------------------------------------------------------
@@ -4024,11 +4539,12 @@ impl ser::Serializer for &mut SerializerHeader {
         Ok(())
     }
 
+    // Serialize Some(T) values to check for nested structs.
     fn serialize_some<T>(self, data: &T) -> Result<(), XlsxError>
     where
         T: ?Sized + Serialize,
     {
-        Ok(())
+        data.serialize(self)
     }
 
     fn serialize_none(self) -> Result<(), XlsxError> {
@@ -4099,13 +4615,11 @@ impl ser::Serializer for &mut SerializerHeader {
         Ok(self)
     }
 
-    // See the comment on the equivalent `Worksheet` method above.
+    // Map keys are stored as field names, for map records and for nested maps
+    // when flattening nested structs.
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, XlsxError> {
-        Err(XlsxError::SerdeError(
-            "Map types and structs that use `#[serde(flatten)]` cannot be serialized to Excel. \
-            See the limitations section in the `rust_xlsxwriter::serializer` documentation."
-                .to_string(),
-        ))
+        self.is_map = true;
+        Ok(self)
     }
 
     fn serialize_struct_variant(
@@ -4125,12 +4639,13 @@ impl ser::SerializeStruct for &mut SerializerHeader {
     type Ok = ();
     type Error = XlsxError;
 
-    fn serialize_field<T>(&mut self, key: &'static str, _value: &T) -> Result<(), XlsxError>
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), XlsxError>
     where
         T: ?Sized + Serialize,
     {
-        // Serialize the key/field name but ignore the values.
-        key.serialize(&mut **self)
+        // Store the key/field name. The values are ignored unless they are
+        // nested structs that are being flattened.
+        self.store_field_name(key, value)
     }
 
     fn end(self) -> Result<(), XlsxError> {
@@ -4138,15 +4653,21 @@ impl ser::SerializeStruct for &mut SerializerHeader {
     }
 }
 
+// The elements of a top level sequence are serialized to get the union of the
+// field names of all the map records in it. Nested sequences are ignored.
 impl ser::SerializeSeq for &mut SerializerHeader {
     type Ok = ();
     type Error = XlsxError;
 
-    fn serialize_element<T>(&mut self, _value: &T) -> Result<(), XlsxError>
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), XlsxError>
     where
         T: ?Sized + Serialize,
     {
-        Ok(())
+        if self.is_nested {
+            return Ok(());
+        }
+
+        value.serialize(&mut **self)
     }
 
     fn end(self) -> Result<(), XlsxError> {
@@ -4202,22 +4723,25 @@ impl ser::SerializeTupleVariant for &mut SerializerHeader {
     }
 }
 
+// Map keys are handled like struct field names.
 impl ser::SerializeMap for &mut SerializerHeader {
     type Ok = ();
     type Error = XlsxError;
 
-    fn serialize_key<T>(&mut self, _key: &T) -> Result<(), XlsxError>
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), XlsxError>
     where
         T: ?Sized + Serialize,
     {
+        self.map_key = map_key_to_string(key)?;
         Ok(())
     }
 
-    fn serialize_value<T>(&mut self, _value: &T) -> Result<(), XlsxError>
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), XlsxError>
     where
         T: ?Sized + Serialize,
     {
-        Ok(())
+        let field_name = std::mem::take(&mut self.map_key);
+        self.store_field_name(&field_name, value)
     }
 
     fn end(self) -> Result<(), XlsxError> {
@@ -4248,6 +4772,7 @@ impl ser::SerializeStructVariant for &mut SerializerHeader {
 pub(crate) struct DeSerializerHeader<'a> {
     pub(crate) struct_name: &'a mut &'static str,
     pub(crate) field_names: &'a mut &'static [&'static str],
+    pub(crate) is_map: &'a mut bool,
 }
 
 impl<'de> Deserializer<'de> for DeSerializerHeader<'_> {
@@ -4267,6 +4792,16 @@ impl<'de> Deserializer<'de> for DeSerializerHeader<'_> {
         Err(XlsxError::SerdeError("Deserialization error".to_string()))
     }
 
+    // Maps don't have field names but we note the type so that the keys can
+    // be set by the user.
+    fn deserialize_map<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        *self.is_map = true;
+        Err(XlsxError::SerdeError("Deserialization error".to_string()))
+    }
+
     fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
@@ -4277,7 +4812,7 @@ impl<'de> Deserializer<'de> for DeSerializerHeader<'_> {
     serde::forward_to_deserialize_any! {
         bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes
         byte_buf option unit unit_struct newtype_struct seq tuple
-        tuple_struct map enum identifier ignored_any
+        tuple_struct enum identifier ignored_any
     }
 }
 
@@ -4287,20 +4822,71 @@ where
 {
     let mut struct_name = "";
     let mut field_names: &[&str] = &[""];
+    let mut is_map = false;
 
     // Ignore the deserialization return since we have set up all the
     // Deserializer methods (above) to return quickly/with an error.
     let _ = T::deserialize(DeSerializerHeader {
         struct_name: &mut struct_name,
         field_names: &mut field_names,
+        is_map: &mut is_map,
     });
 
     let struct_name = struct_name.to_string();
-    let field_names = field_names.iter().map(|&s| s.to_string()).collect();
+    let field_names = if is_map {
+        vec![]
+    } else {
+        field_names.iter().map(|&s| s.to_string()).collect()
+    };
 
     SerializerHeader {
         struct_name,
         field_names,
+        is_map,
+        ..SerializerHeader::new()
+    }
+}
+
+// Convert a serialized map key to a string. Only string keys are supported
+// since the keys are used as field names.
+pub(crate) fn map_key_to_string<T>(key: &T) -> Result<String, XlsxError>
+where
+    T: ?Sized + Serialize,
+{
+    let mut headers = SerializerHeader::new();
+    key.serialize(&mut headers)?;
+
+    match headers.field_names.pop() {
+        Some(key) if headers.struct_name.is_empty() => Ok(key),
+        _ => Err(XlsxError::SerdeError(
+            "Only maps with string keys can be serialized to Excel".to_string(),
+        )),
+    }
+}
+
+// Get the name of a type, without the module path or generic parameters. This
+// is used as the struct name for maps, and structs that use `#[serde(flatten)]`,
+// since Serde serializes them without a name. References, arrays, slices and
+// vectors are unwrapped to get the name of the element type.
+pub(crate) fn struct_type_name<T>() -> String
+where
+    T: ?Sized,
+{
+    let mut type_name = std::any::type_name::<T>();
+
+    loop {
+        type_name = type_name.trim_start_matches(['&', '[']);
+        type_name = type_name.strip_prefix("mut ").unwrap_or(type_name);
+
+        let end = type_name
+            .find(['<', '>', ';', ',', ']'])
+            .unwrap_or(type_name.len());
+        let name = type_name[..end].rsplit("::").next().unwrap_or_default();
+
+        match type_name[end..].strip_prefix('<') {
+            Some(element_type) if name == "Vec" || name == "VecDeque" => type_name = element_type,
+            _ => return name.to_string(),
+        }
     }
 }
 
@@ -0,0 +1,491 @@
+// slicer - A module for creating the Excel slicer.xml and slicerCache.xml
+// files.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+mod tests;
+
+use std::fmt;
+use std::io::Cursor;
+
+use crate::drawing::{DrawingObject, DrawingType};
+use crate::xmlwriter::{
+    xml_declaration, xml_empty_tag, xml_end_tag, xml_start_tag, xml_start_tag_only,
+};
+use crate::{ObjectMovement, XlsxError};
+
+/// The `Slicer` struct represents a worksheet table slicer.
+///
+/// Slicers are a visual filtering control in Excel. A table slicer displays
+/// the unique values in a worksheet [`Table`](crate::Table) column as a set of
+/// buttons that can be clicked to filter the table.
+///
+/// A slicer refers to a table and column by name. The table name is either the
+/// name set via [`Table::set_name()`](crate::Table::set_name) or the default
+/// Excel name such as `Table1`, `Table2`, etc. The table can be in any
+/// worksheet in the workbook.
+///
+/// A slicer is added to a worksheet via the
+/// [`Worksheet::insert_slicer()`](crate::Worksheet::insert_slicer) method.
+///
+/// ```
+/// # // This code is available in examples/doc_slicer_intro.rs
+/// #
+/// use rust_xlsxwriter::{Slicer, Table, Workbook, XlsxError};
+///
+/// fn main() -> Result<(), XlsxError> {
+///     // Create a new Excel file object.
+///     let mut workbook = Workbook::new();
+///     let worksheet = workbook.add_worksheet();
+///
+///     // Write some data for the table.
+///     worksheet.write_row(0, 0, ["Region", "Product", "Sales"])?;
+///     worksheet.write_row(1, 0, ["East", "Apples"])?;
+///     worksheet.write_row(2, 0, ["West", "Apples"])?;
+///     worksheet.write_row(3, 0, ["East", "Pears"])?;
+///     worksheet.write_row(4, 0, ["West", "Pears"])?;
+///     worksheet.write_column(1, 2, [1000, 2500, 3000, 1500])?;
+///
+///     // Add a named table over the data.
+///     let table = Table::new().set_name("Sales");
+///     worksheet.add_table(0, 0, 4, 2, &table)?;
+///
+///     // Add a slicer to filter the table by region.
+///     let slicer = Slicer::new("Sales", "Region").set_caption("Sales Region");
+///     worksheet.insert_slicer(1, 4, &slicer)?;
+///
+///     // Save the file to disk.
+///     workbook.save("slicer.xlsx")?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+#[derive(Clone)]
+pub struct Slicer {
+    pub(crate) writer: Cursor<Vec<u8>>,
+    pub(crate) table_name: String,
+    pub(crate) column_name: String,
+    pub(crate) name: String,
+    pub(crate) cache_name: String,
+    pub(crate) table_id: u32,
+    pub(crate) table_column: u16,
+    pub(crate) x_offset: u32,
+    pub(crate) y_offset: u32,
+    caption: String,
+    style: SlicerStyle,
+    column_count: u16,
+    width: f64,
+    height: f64,
+    object_movement: ObjectMovement,
+}
+
+impl Slicer {
+    // -----------------------------------------------------------------------
+    // Public (and crate public) methods.
+    // -----------------------------------------------------------------------
+
+    /// Create a new `Slicer` struct instance.
+    ///
+    /// # Parameters
+    ///
+    /// - `table_name`: The name of a worksheet table in the workbook.
+    /// - `column_name`: The name of the table column header to filter on.
+    ///
+    pub fn new(table_name: impl Into<String>, column_name: impl Into<String>) -> Slicer {
+        Slicer {
+            writer: Cursor::new(Vec::with_capacity(2048)),
+            table_name: table_name.into(),
+            column_name: column_name.into(),
+            name: String::new(),
+            cache_name: String::new(),
+            table_id: 0,
+            table_column: 0,
+            x_offset: 0,
+            y_offset: 0,
+            caption: String::new(),
+            style: SlicerStyle::Light1,
+            column_count: 1,
+            width: 183.0,
+            height: 251.0,
+            object_movement: ObjectMovement::MoveButDontSizeWithCells,
+        }
+    }
+
+    /// Set the caption displayed in the slicer header.
+    ///
+    /// The default caption is the name of the table column.
+    ///
+    /// # Parameters
+    ///
+    /// - `caption`: The slicer caption.
+    ///
+    pub fn set_caption(mut self, caption: impl Into<String>) -> Slicer {
+        self.caption = caption.into();
+        self
+    }
+
+    /// Set the style of the slicer.
+    ///
+    /// Excel supports 14 built-in slicer styles, see [`SlicerStyle`]. The
+    /// default style is [`SlicerStyle::Light1`].
+    ///
+    /// # Parameters
+    ///
+    /// - `style`: A [`SlicerStyle`] enum value.
+    ///
+    pub fn set_style(mut self, style: SlicerStyle) -> Slicer {
+        self.style = style;
+        self
+    }
+
+    /// Set the number of columns of buttons in the slicer.
+    ///
+    /// # Parameters
+    ///
+    /// - `column_count`: The number of columns, in the Excel range 1 to
+    ///   20,000. The default is 1. Other values are ignored.
+    ///
+    pub fn set_column_count(mut self, column_count: u16) -> Slicer {
+        if (1..=20_000).contains(&column_count) {
+            self.column_count = column_count;
+        }
+
+        self
+    }
+
+    /// Set the width of the slicer in pixels.
+    ///
+    /// The default width is 183 pixels, which is the Excel default.
+    ///
+    /// # Parameters
+    ///
+    /// - `width`: The slicer width in pixels. Values less than 5 pixels are
+    ///   ignored.
+    ///
+    pub fn set_width(mut self, width: u32) -> Slicer {
+        if width < 5 {
+            return self;
+        }
+
+        self.width = f64::from(width);
+        self
+    }
+
+    /// Set the height of the slicer in pixels.
+    ///
+    /// The default height is 251 pixels, which is the Excel default.
+    ///
+    /// # Parameters
+    ///
+    /// - `height`: The slicer height in pixels. Values less than 5 pixels are
+    ///   ignored.
+    ///
+    pub fn set_height(mut self, height: u32) -> Slicer {
+        if height < 5 {
+            return self;
+        }
+
+        self.height = f64::from(height);
+        self
+    }
+
+    /// Set the object movement options for a worksheet slicer.
+    ///
+    /// Set the option to define how a slicer will behave in Excel if the
+    /// cells under the slicer are moved, deleted, or have their size changed.
+    /// The default is [`ObjectMovement::MoveButDontSizeWithCells`]. See
+    /// [`ObjectMovement`] for details.
+    ///
+    /// # Parameters
+    ///
+    /// - `option`: An [`ObjectMovement`] enum value.
+    ///
+    pub fn set_object_movement(mut self, option: ObjectMovement) -> Slicer {
+        self.object_movement = option;
+        self
+    }
+
+    // Check that the slicer refers to a table and column.
+    pub(crate) fn validate(&self) -> Result<(), XlsxError> {
+        if self.table_name.is_empty() {
+            return Err(XlsxError::SlicerError(
+                "Slicer table name cannot be blank".to_string(),
+            ));
+        }
+
+        if self.column_name.is_empty() {
+            return Err(XlsxError::SlicerError(
+                "Slicer column name cannot be blank".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Get the default base name for the slicer cache, which must be a valid
+    // Excel defined name.
+    pub(crate) fn cache_base_name(&self) -> String {
+        let name: String = self
+            .column_name
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+
+        format!("Slicer_{name}")
+    }
+
+    // -----------------------------------------------------------------------
+    // XML assembly methods.
+    // -----------------------------------------------------------------------
+
+    // Assemble and generate the slicerCache XML file.
+    pub(crate) fn assemble_cache_xml_file(&mut self) {
+        xml_declaration(&mut self.writer);
+
+        // Write the slicerCacheDefinition element.
+        self.write_slicer_cache_definition();
+
+        // Write the extLst element.
+        self.write_cache_ext_lst();
+
+        // Close the slicerCacheDefinition tag.
+        xml_end_tag(&mut self.writer, "slicerCacheDefinition");
+    }
+
+    // Write the <slicerCacheDefinition> element.
+    fn write_slicer_cache_definition(&mut self) {
+        let attributes = [
+            (
+                "xmlns",
+                "http://schemas.microsoft.com/office/spreadsheetml/2009/9/main".to_string(),
+            ),
+            (
+                "xmlns:mc",
+                "http://schemas.openxmlformats.org/markup-compatibility/2006".to_string(),
+            ),
+            ("mc:Ignorable", "x".to_string()),
+            (
+                "xmlns:x",
+                "http://schemas.openxmlformats.org/spreadsheetml/2006/main".to_string(),
+            ),
+            ("name", self.cache_name.clone()),
+            ("sourceName", self.column_name.clone()),
+        ];
+
+        xml_start_tag(&mut self.writer, "slicerCacheDefinition", &attributes);
+    }
+
+    // Write the <extLst> element with the table slicer cache extension.
+    fn write_cache_ext_lst(&mut self) {
+        xml_start_tag_only(&mut self.writer, "extLst");
+
+        let attributes = [
+            ("uri", "{2F2917AC-EB37-4324-AD4E-5DD8C200BD13}"),
+            (
+                "xmlns:x15",
+                "http://schemas.microsoft.com/office/spreadsheetml/2010/11/main",
+            ),
+        ];
+
+        xml_start_tag(&mut self.writer, "x:ext", &attributes);
+
+        // Write the x15:tableSlicerCache element.
+        let attributes = [
+            ("tableId", self.table_id.to_string()),
+            ("column", self.table_column.to_string()),
+        ];
+
+        xml_empty_tag(&mut self.writer, "x15:tableSlicerCache", &attributes);
+
+        xml_end_tag(&mut self.writer, "x:ext");
+        xml_end_tag(&mut self.writer, "extLst");
+    }
+
+    // Write the <slicer> element in the parent slicers file.
+    fn write_slicer(&self, writer: &mut Cursor<Vec<u8>>) {
+        let caption = if self.caption.is_empty() {
+            self.column_name.clone()
+        } else {
+            self.caption.clone()
+        };
+
+        let mut attributes = vec![
+            ("name", self.name.clone()),
+            ("cache", self.cache_name.clone()),
+            ("caption", caption),
+        ];
+
+        if self.column_count > 1 {
+            attributes.push(("columnCount", self.column_count.to_string()));
+        }
+
+        if self.style != SlicerStyle::Light1 {
+            attributes.push(("style", self.style.to_string()));
+        }
+
+        attributes.push(("rowHeight", "241300".to_string()));
+
+        xml_empty_tag(writer, "slicer", &attributes);
+    }
+}
+
+impl DrawingObject for Slicer {
+    fn x_offset(&self) -> u32 {
+        self.x_offset
+    }
+
+    fn y_offset(&self) -> u32 {
+        self.y_offset
+    }
+
+    fn width_scaled(&self) -> f64 {
+        self.width
+    }
+
+    fn height_scaled(&self) -> f64 {
+        self.height
+    }
+
+    fn object_movement(&self) -> ObjectMovement {
+        self.object_movement
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn alt_text(&self) -> String {
+        String::new()
+    }
+
+    fn decorative(&self) -> bool {
+        false
+    }
+
+    fn drawing_type(&self) -> DrawingType {
+        DrawingType::Slicer
+    }
+}
+
+// A struct to represent the slicers.xml file, which contains all the slicers
+// in a worksheet.
+pub(crate) struct Slicers {
+    pub(crate) writer: Cursor<Vec<u8>>,
+}
+
+impl Slicers {
+    // Create a new Slicers struct.
+    pub(crate) fn new() -> Slicers {
+        Slicers {
+            writer: Cursor::new(Vec::with_capacity(2048)),
+        }
+    }
+
+    // Assemble and generate the slicers XML file.
+    pub(crate) fn assemble_xml_file(&mut self, slicers: &[Slicer]) {
+        xml_declaration(&mut self.writer);
+
+        // Write the slicers element.
+        let attributes = [
+            (
+                "xmlns",
+                "http://schemas.microsoft.com/office/spreadsheetml/2009/9/main",
+            ),
+            (
+                "xmlns:mc",
+                "http://schemas.openxmlformats.org/markup-compatibility/2006",
+            ),
+            ("mc:Ignorable", "x"),
+            (
+                "xmlns:x",
+                "http://schemas.openxmlformats.org/spreadsheetml/2006/main",
+            ),
+        ];
+
+        xml_start_tag(&mut self.writer, "slicers", &attributes);
+
+        for slicer in slicers {
+            // Write the slicer element.
+            slicer.write_slicer(&mut self.writer);
+        }
+
+        xml_end_tag(&mut self.writer, "slicers");
+    }
+}
+
+/// The `SlicerStyle` enum defines the worksheet slicer styles.
+///
+/// Excel supports 14 built-in styles for slicers divided into Light, Other
+/// and Dark categories. The style is set via the [`Slicer::set_style()`]
+/// method. The default slicer style in Excel is [`SlicerStyle::Light1`].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlicerStyle {
+    /// Slicer Style Light 1. This is the default style.
+    Light1,
+
+    /// Slicer Style Light 2.
+    Light2,
+
+    /// Slicer Style Light 3.
+    Light3,
+
+    /// Slicer Style Light 4.
+    Light4,
+
+    /// Slicer Style Light 5.
+    Light5,
+
+    /// Slicer Style Light 6.
+    Light6,
+
+    /// Slicer Style Other 1.
+    Other1,
+
+    /// Slicer Style Other 2.
+    Other2,
+
+    /// Slicer Style Dark 1.
+    Dark1,
+
+    /// Slicer Style Dark 2.
+    Dark2,
+
+    /// Slicer Style Dark 3.
+    Dark3,
+
+    /// Slicer Style Dark 4.
+    Dark4,
+
+    /// Slicer Style Dark 5.
+    Dark5,
+
+    /// Slicer Style Dark 6.
+    Dark6,
+}
+
+impl fmt::Display for SlicerStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Light1 => write!(f, "SlicerStyleLight1"),
+            Self::Light2 => write!(f, "SlicerStyleLight2"),
+            Self::Light3 => write!(f, "SlicerStyleLight3"),
+            Self::Light4 => write!(f, "SlicerStyleLight4"),
+            Self::Light5 => write!(f, "SlicerStyleLight5"),
+            Self::Light6 => write!(f, "SlicerStyleLight6"),
+            Self::Other1 => write!(f, "SlicerStyleOther1"),
+            Self::Other2 => write!(f, "SlicerStyleOther2"),
+            Self::Dark1 => write!(f, "SlicerStyleDark1"),
+            Self::Dark2 => write!(f, "SlicerStyleDark2"),
+            Self::Dark3 => write!(f, "SlicerStyleDark3"),
+            Self::Dark4 => write!(f, "SlicerStyleDark4"),
+            Self::Dark5 => write!(f, "SlicerStyleDark5"),
+            Self::Dark6 => write!(f, "SlicerStyleDark6"),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// Slicer unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod slicer_tests {
+
+    use crate::slicer::Slicers;
+    use crate::test_functions::xml_to_vec;
+    use crate::{xmlwriter, Slicer, SlicerStyle, XlsxError};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_validation() {
+        let slicer = Slicer::new("", "Region");
+        let result = slicer.validate();
+        assert!(matches!(result, Err(XlsxError::SlicerError(_))));
+
+        let slicer = Slicer::new("Table1", "");
+        let result = slicer.validate();
+        assert!(matches!(result, Err(XlsxError::SlicerError(_))));
+
+        let slicer = Slicer::new("Table1", "Region");
+        assert!(slicer.validate().is_ok());
+    }
+
+    #[test]
+    fn test_cache_base_name() {
+        let slicer = Slicer::new("Table1", "Region");
+        assert_eq!("Slicer_Region", slicer.cache_base_name());
+
+        let slicer = Slicer::new("Table1", "Sales Region 2");
+        assert_eq!("Slicer_Sales_Region_2", slicer.cache_base_name());
+    }
+
+    #[test]
+    fn test_assemble_cache() {
+        let mut slicer = Slicer::new("Table1", "Region");
+        slicer.cache_name = "Slicer_Region".to_string();
+        slicer.table_id = 1;
+        slicer.table_column = 2;
+
+        slicer.assemble_cache_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&slicer.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <slicerCacheDefinition xmlns="http://schemas.microsoft.com/office/spreadsheetml/2009/9/main" xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006" mc:Ignorable="x" xmlns:x="http://schemas.openxmlformats.org/spreadsheetml/2006/main" name="Slicer_Region" sourceName="Region">
+              <extLst>
+                <x:ext uri="{2F2917AC-EB37-4324-AD4E-5DD8C200BD13}" xmlns:x15="http://schemas.microsoft.com/office/spreadsheetml/2010/11/main">
+                  <x15:tableSlicerCache tableId="1" column="2"/>
+                </x:ext>
+              </extLst>
+            </slicerCacheDefinition>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_slicers() {
+        let mut slicer1 = Slicer::new("Table1", "Region");
+        slicer1.name = "Region".to_string();
+        slicer1.cache_name = "Slicer_Region".to_string();
+
+        let mut slicer2 = Slicer::new("Table1", "Region")
+            .set_caption("Sales Region")
+            .set_column_count(2)
+            .set_style(SlicerStyle::Dark3);
+        slicer2.name = "Region 1".to_string();
+        slicer2.cache_name = "Slicer_Region1".to_string();
+
+        let mut slicers = Slicers::new();
+        slicers.assemble_xml_file(&[slicer1, slicer2]);
+
+        let got = xmlwriter::cursor_to_str(&slicers.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <slicers xmlns="http://schemas.microsoft.com/office/spreadsheetml/2009/9/main" xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006" mc:Ignorable="x" xmlns:x="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+              <slicer name="Region" cache="Slicer_Region" caption="Region" rowHeight="241300"/>
+              <slicer name="Region 1" cache="Slicer_Region1" caption="Sales Region" columnCount="2" style="SlicerStyleDark3" rowHeight="241300"/>
+            </slicers>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+}
@@ -127,7 +127,11 @@ pub struct Table {
     pub(crate) show_banded_rows: bool,
     pub(crate) show_banded_columns: bool,
     pub(crate) show_autofilter: bool,
-    pub(crate) is_serde_table: bool,
+
+    // The header cells have already been written to the worksheet, with any
+    // formatting, by serialization or the dataframe writers so the table
+    // shouldn't overwrite them.
+    pub(crate) has_header_cells: bool,
 
     pub(crate) alt_text: String,
     pub(crate) alt_text_title: String,
@@ -215,7 +219,7 @@ impl Table {
             show_autofilter: true,
             show_header_row: true,
             show_total_row: false,
-            is_serde_table: false,
+            has_header_cells: false,
             alt_text: String::new(),
             alt_text_title: String::new(),
         }
@@ -0,0 +1,1811 @@
+// template - A module for reading an existing xlsx file to use as the
+// template for a new workbook.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+// The parts of a template file are split into three categories when the file
+// is read:
+//
+// - Parts that are regenerated from the workbook data when the file is saved,
+//   such as the workbook, content types, relationships, document properties,
+//   theme and shared strings. Any information that needs to be preserved from
+//   these parts, such as the sheet names or defined names, is extracted when
+//   the template is read.
+//
+// - The styles and the worksheets. These are kept in a raw form and are merged
+//   with the formats and cell data added by the user when the file is saved.
+//
+// - Everything else, such as drawings, images, charts, pivot caches and any
+//   unknown parts. These are passed through as-is. The passed through parts
+//   in the "xl" directory are renamed with a "template_" prefix so that they
+//   don't clash with the parts generated for new objects, and the targets of
+//   the relationships that refer to them are updated to match.
+
+mod tests;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::{Cursor, Read, Seek};
+use std::sync::Arc;
+
+use zip::ZipArchive;
+
+#[cfg(feature = "serde")]
+use crate::deserializer::CellValue;
+use crate::shared_strings_table::SharedStringsTable;
+use crate::xmlwriter::{xml_declaration, xml_empty_tag, xml_end_tag, xml_start_tag};
+use crate::{utility, ColNum, RowNum, Visible, XlsxError};
+
+// The workbook level parts of a template file that are written when the file
+// is saved.
+pub(crate) struct Template {
+    pub(crate) parts: Vec<(String, Vec<u8>)>,
+    pub(crate) content_type_defaults: Vec<(String, String)>,
+    pub(crate) content_type_overrides: Vec<(String, String)>,
+    pub(crate) root_relationships: Vec<TemplateRelationship>,
+    pub(crate) workbook_relationships: Vec<TemplateRelationship>,
+    pub(crate) workbook_rel_ids: HashMap<String, String>,
+    pub(crate) external_references: Vec<String>,
+    pub(crate) pivot_caches: Vec<String>,
+    pub(crate) extensions: Vec<String>,
+    pub(crate) styles: TemplateStyles,
+}
+
+impl Template {
+    // -----------------------------------------------------------------------
+    // Crate public methods.
+    // -----------------------------------------------------------------------
+
+    // Update the relationship ids in a raw workbook.xml element to match the
+    // ids of the template relationships in the new workbook.xml.rels file.
+    pub(crate) fn remap_relationship_ids(&self, xml: &str) -> String {
+        rewrite_tags(xml, |_, attributes| {
+            let mut changed = false;
+            for (name, value) in attributes.iter_mut() {
+                if name.ends_with(":id") {
+                    if let Some(rel_id) = self.workbook_rel_ids.get(value.as_str()) {
+                        value.clone_from(rel_id);
+                        changed = true;
+                    }
+                }
+            }
+            changed
+        })
+    }
+
+    // Check if the template contains worksheet tables. New tables would use
+    // table ids that clash with the template tables.
+    pub(crate) fn has_tables(&self) -> bool {
+        self.content_type_overrides
+            .iter()
+            .any(|(_, content_type)| content_type.ends_with(".table+xml"))
+    }
+
+    // Check if the template contains slicer caches.
+    pub(crate) fn has_slicer_caches(&self) -> bool {
+        self.extensions
+            .iter()
+            .any(|extension| extension.contains("slicerCache"))
+    }
+}
+
+// The data read from a template file. The workbook level data is stored in
+// the `Template` struct and the other data is used to initialize the
+// workbook.
+pub(crate) struct TemplateData {
+    pub(crate) template: Template,
+    pub(crate) sheets: Vec<TemplateSheetData>,
+    pub(crate) defined_names: Vec<TemplateDefinedName>,
+    pub(crate) theme_xml: Option<String>,
+    pub(crate) active_tab: usize,
+    pub(crate) vba_project: Vec<u8>,
+    pub(crate) vba_signature: Vec<u8>,
+    pub(crate) vba_codename: Option<String>,
+    pub(crate) is_1904_date: bool,
+}
+
+// The workbook level data for a template worksheet or chartsheet.
+pub(crate) struct TemplateSheetData {
+    pub(crate) name: String,
+    pub(crate) visible: Visible,
+    pub(crate) is_chartsheet: bool,
+    pub(crate) sheet: TemplateSheet,
+}
+
+// A defined name from the template workbook.xml file.
+pub(crate) struct TemplateDefinedName {
+    pub(crate) name: String,
+    pub(crate) local_sheet_id: Option<usize>,
+    pub(crate) formula: String,
+}
+
+// A relationship from a template .rels file. For relationships to parts in
+// the package the target is stored as an absolute path while the template is
+// being read and is then converted to a path relative to the new location of
+// the source part.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TemplateRelationship {
+    pub(crate) id: String,
+    pub(crate) rel_type: String,
+    pub(crate) target: String,
+    pub(crate) target_mode: String,
+}
+
+// -----------------------------------------------------------------------
+// Template reading.
+// -----------------------------------------------------------------------
+
+// Read an xlsx template file and split it into the parts that are passed
+// through, merged or regenerated.
+#[allow(clippy::too_many_lines)]
+pub(crate) fn read_template<R: Read + Seek>(reader: R) -> Result<TemplateData, XlsxError> {
+    let mut archive = ZipArchive::new(reader)?;
+
+    // Read all the files in the package.
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if file.is_dir() {
+            continue;
+        }
+
+        let mut data = vec![];
+        file.read_to_end(&mut data)?;
+        files.insert(file.name().to_string(), data);
+    }
+
+    // The parts that are regenerated from the workbook data.
+    let mut regenerated: HashSet<String> = [
+        "[Content_Types].xml",
+        "_rels/.rels",
+        "docProps/app.xml",
+        "docProps/core.xml",
+        "docProps/custom.xml",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect();
+
+    // Find the workbook part from the root relationships.
+    let root_relationships = read_relationships(&files, "")?;
+    let Some(workbook_path) = root_relationships
+        .iter()
+        .find(|relationship| relationship_kind(relationship) == "officeDocument")
+        .map(|relationship| relationship.target.clone())
+    else {
+        return Err(XlsxError::TemplateError(
+            "Couldn't find the workbook part in the template file".to_string(),
+        ));
+    };
+
+    let workbook_xml = read_string(&files, &workbook_path, Some("workbook"))?;
+    let workbook_relationships = read_relationships(&files, &workbook_path)?;
+    regenerated.insert(workbook_path.clone());
+    regenerated.insert(relationships_path(&workbook_path));
+
+    // Find the workbook level parts that are regenerated or merged.
+    let mut styles_xml = None;
+    let mut theme_xml = None;
+    let mut strings = vec![];
+    let mut vba_project = vec![];
+    let mut vba_signature = vec![];
+
+    for relationship in &workbook_relationships {
+        if relationship.target_mode == "External" {
+            continue;
+        }
+
+        let target = &relationship.target;
+
+        match relationship_kind(relationship) {
+            "worksheet" | "chartsheet" | "calcChain" => {}
+            "styles" => styles_xml = Some(read_string(&files, target, Some("styleSheet"))?),
+            "theme" => theme_xml = Some(read_string(&files, target, None)?),
+            "sharedStrings" => {
+                strings = read_shared_strings(&read_string(&files, target, Some("sst"))?)
+            }
+            "vbaProject" => {
+                vba_project = files.get(target).cloned().unwrap_or_default();
+
+                for signature in read_relationships(&files, target)? {
+                    if let Some(data) = files.get(&signature.target) {
+                        vba_signature.clone_from(data);
+                        regenerated.insert(signature.target.clone());
+                    }
+                }
+            }
+            "dialogsheet" | "xlMacrosheet" | "xlIntlMacrosheet" => {
+                return Err(XlsxError::TemplateError(format!(
+                    "Unsupported sheet type in template file: '{target}'"
+                )));
+            }
+            _ => continue,
+        }
+
+        regenerated.insert(target.clone());
+        regenerated.insert(relationships_path(target));
+    }
+
+    let Some(styles_xml) = styles_xml else {
+        return Err(XlsxError::TemplateError(
+            "Couldn't find the styles part in the template file".to_string(),
+        ));
+    };
+
+    // All the other parts are passed through.
+    let passthrough: HashSet<String> = files
+        .keys()
+        .filter(|name| !regenerated.contains(*name) && !is_relationships_part(name))
+        .cloned()
+        .collect();
+
+    // Read the worksheets and chartsheets.
+    let mut sheets = vec![];
+    if let Some(element) = find_element(&workbook_xml, "sheets", 0) {
+        for sheet_xml in children(element.inner(&workbook_xml)) {
+            if tag_name(sheet_xml) != "sheet" {
+                continue;
+            }
+
+            let tag = start_tag(sheet_xml);
+            let name = attribute(tag, "name").unwrap_or_default();
+
+            let visible = match attribute(tag, "state").as_deref() {
+                Some("hidden") => Visible::Hidden,
+                Some("veryHidden") => Visible::VeryHidden,
+                _ => Visible::Default,
+            };
+
+            let rel_id = attributes(tag)
+                .into_iter()
+                .find(|(name, _)| name.ends_with(":id"))
+                .map(|(_, value)| value)
+                .unwrap_or_default();
+
+            let Some(relationship) = workbook_relationships
+                .iter()
+                .find(|relationship| relationship.id == rel_id)
+            else {
+                return Err(XlsxError::TemplateError(format!(
+                    "Couldn't find the part for sheet '{name}' in the template file"
+                )));
+            };
+
+            let is_chartsheet = relationship_kind(relationship) == "chartsheet";
+            let (directory, root_name) = if is_chartsheet {
+                ("xl/chartsheets", "chartsheet")
+            } else {
+                ("xl/worksheets", "worksheet")
+            };
+
+            let sheet_xml = read_string(&files, &relationship.target, Some(root_name))?;
+            let mut sheet = TemplateSheet::new(&sheet_xml, &strings)?;
+
+            sheet.relationships = convert_relationships(
+                read_relationships(&files, &relationship.target)?,
+                directory,
+                &passthrough,
+                &regenerated,
+            );
+
+            sheets.push(TemplateSheetData {
+                name,
+                visible,
+                is_chartsheet,
+                sheet,
+            });
+        }
+    }
+
+    // Read the defined names.
+    let mut defined_names = vec![];
+    if let Some(element) = find_element(&workbook_xml, "definedNames", 0) {
+        for name_xml in children(element.inner(&workbook_xml)) {
+            if tag_name(name_xml) != "definedName" {
+                continue;
+            }
+
+            let tag = start_tag(name_xml);
+            defined_names.push(TemplateDefinedName {
+                name: attribute(tag, "name").unwrap_or_default(),
+                local_sheet_id: attribute(tag, "localSheetId").and_then(|id| id.parse().ok()),
+                formula: text_content(name_xml),
+            });
+        }
+    }
+
+    // Read the workbook.xml elements that refer to the passed through parts.
+    // These are written back to the new workbook.xml file.
+    let raw_children = |name: &str| -> Vec<String> {
+        find_element(&workbook_xml, name, 0)
+            .map(|element| {
+                children(element.inner(&workbook_xml))
+                    .into_iter()
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let external_references = raw_children("externalReferences");
+    let pivot_caches = raw_children("pivotCaches");
+    let extensions = raw_children("extLst");
+
+    let active_tab = find_element(&workbook_xml, "workbookView", 0)
+        .and_then(|element| attribute(element.start_tag(&workbook_xml), "activeTab"))
+        .and_then(|index| index.parse().ok())
+        .unwrap_or_default();
+
+    let vba_codename = find_element(&workbook_xml, "workbookPr", 0)
+        .and_then(|element| attribute(element.start_tag(&workbook_xml), "codeName"));
+
+    let is_1904_date = find_element(&workbook_xml, "workbookPr", 0)
+        .and_then(|element| attribute(element.start_tag(&workbook_xml), "date1904"))
+        .is_some_and(|value| value == "1" || value == "true");
+
+    // Store the passed through parts, and their relationships, under their
+    // new names.
+    let mut parts = vec![];
+    for (name, data) in &files {
+        if passthrough.contains(name) {
+            parts.push((template_part_name(name), data.clone()));
+        } else if is_relationships_part(name) {
+            let source = relationships_source(name);
+            if passthrough.contains(&source) {
+                let new_source = template_part_name(&source);
+                let relationships = convert_relationships(
+                    read_relationships(&files, &source)?,
+                    directory_name(&new_source),
+                    &passthrough,
+                    &regenerated,
+                );
+
+                parts.push((
+                    relationships_path(&new_source),
+                    relationships_xml(&relationships),
+                ));
+            }
+        }
+    }
+
+    // Read the content types for the passed through parts.
+    let mut content_type_defaults = vec![];
+    let mut content_type_overrides = vec![];
+    let content_types_xml = read_string(&files, "[Content_Types].xml", Some("Types"))?;
+    if let Some(element) = find_element(&content_types_xml, "Types", 0) {
+        for child in children(element.inner(&content_types_xml)) {
+            let tag = start_tag(child);
+            match tag_name(child) {
+                "Default" => {
+                    if let (Some(extension), Some(content_type)) =
+                        (attribute(tag, "Extension"), attribute(tag, "ContentType"))
+                    {
+                        content_type_defaults.push((extension, content_type));
+                    }
+                }
+                "Override" => {
+                    if let (Some(part_name), Some(content_type)) =
+                        (attribute(tag, "PartName"), attribute(tag, "ContentType"))
+                    {
+                        let part_name = part_name.trim_start_matches('/');
+                        if passthrough.contains(part_name) {
+                            let part_name = format!("/{}", template_part_name(part_name));
+                            content_type_overrides.push((part_name, content_type));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let template = Template {
+        parts,
+        content_type_defaults,
+        content_type_overrides,
+        root_relationships: convert_relationships(
+            root_relationships,
+            "",
+            &passthrough,
+            &regenerated,
+        ),
+        workbook_relationships: convert_relationships(
+            workbook_relationships,
+            "xl",
+            &passthrough,
+            &regenerated,
+        ),
+        workbook_rel_ids: HashMap::new(),
+        external_references,
+        pivot_caches,
+        extensions,
+        styles: TemplateStyles::new(styles_xml),
+    };
+
+    Ok(TemplateData {
+        template,
+        sheets,
+        defined_names,
+        theme_xml,
+        active_tab,
+        vba_project,
+        vba_signature,
+        vba_codename,
+        is_1904_date,
+    })
+}
+
+// Read an XML part from the package as a UTF-8 string and check that it can be
+// read by the XML scanning functions, and optionally that it has the expected
+// root element.
+fn read_string(
+    files: &BTreeMap<String, Vec<u8>>,
+    path: &str,
+    root_name: Option<&str>,
+) -> Result<String, XlsxError> {
+    let Some(data) = files.get(path) else {
+        return Err(XlsxError::TemplateError(format!(
+            "Couldn't find part '{path}' in the template file"
+        )));
+    };
+
+    let Ok(xml) = String::from_utf8(data.clone()) else {
+        return Err(XlsxError::TemplateError(format!(
+            "Couldn't read part '{path}' in the template file as UTF-8"
+        )));
+    };
+
+    let xml = xml.trim_start_matches('\u{feff}').to_string();
+    check_xml(&xml, path, root_name)?;
+
+    Ok(xml)
+}
+
+// Read the relationships for a part, if any. The targets of the relationships
+// to other parts in the package are converted to absolute paths.
+fn read_relationships(
+    files: &BTreeMap<String, Vec<u8>>,
+    source: &str,
+) -> Result<Vec<TemplateRelationship>, XlsxError> {
+    let path = relationships_path(source);
+    if !files.contains_key(&path) {
+        return Ok(vec![]);
+    }
+
+    let xml = read_string(files, &path, Some("Relationships"))?;
+    let mut relationships = vec![];
+
+    let Some(element) = find_element(&xml, "Relationships", 0) else {
+        return Ok(relationships);
+    };
+
+    for child in children(element.inner(&xml)) {
+        if tag_name(child) != "Relationship" {
+            continue;
+        }
+
+        let tag = start_tag(child);
+        let target_mode = attribute(tag, "TargetMode").unwrap_or_default();
+        let mut target = attribute(tag, "Target").unwrap_or_default();
+
+        if target_mode != "External" {
+            target = resolve_path(directory_name(source), &target);
+        }
+
+        relationships.push(TemplateRelationship {
+            id: attribute(tag, "Id").unwrap_or_default(),
+            rel_type: attribute(tag, "Type").unwrap_or_default(),
+            target,
+            target_mode,
+        });
+    }
+
+    Ok(relationships)
+}
+
+// Convert the absolute targets of relationships to paths relative to the new
+// location of the source part, taking into account the renaming of the passed
+// through parts. Relationships to regenerated parts are removed.
+fn convert_relationships(
+    relationships: Vec<TemplateRelationship>,
+    directory: &str,
+    passthrough: &HashSet<String>,
+    regenerated: &HashSet<String>,
+) -> Vec<TemplateRelationship> {
+    let mut converted = vec![];
+
+    for mut relationship in relationships {
+        if relationship.target_mode != "External" {
+            if regenerated.contains(&relationship.target) {
+                continue;
+            }
+
+            let target = if passthrough.contains(&relationship.target) {
+                template_part_name(&relationship.target)
+            } else {
+                relationship.target.clone()
+            };
+
+            relationship.target = relative_path(directory, &target);
+        }
+
+        converted.push(relationship);
+    }
+
+    converted
+}
+
+// Generate the XML for a .rels file, keeping the original relationship ids.
+pub(crate) fn relationships_xml(relationships: &[TemplateRelationship]) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::with_capacity(2048));
+
+    xml_declaration(&mut writer);
+
+    let xmlns = "http://schemas.openxmlformats.org/package/2006/relationships";
+    xml_start_tag(&mut writer, "Relationships", &[("xmlns", xmlns)]);
+
+    for relationship in relationships {
+        let mut attributes = vec![
+            ("Id", relationship.id.as_str()),
+            ("Type", relationship.rel_type.as_str()),
+            ("Target", relationship.target.as_str()),
+        ];
+
+        if !relationship.target_mode.is_empty() {
+            attributes.push(("TargetMode", relationship.target_mode.as_str()));
+        }
+
+        xml_empty_tag(&mut writer, "Relationship", &attributes);
+    }
+
+    xml_end_tag(&mut writer, "Relationships");
+
+    writer.into_inner()
+}
+
+// Read the strings from a sharedStrings.xml file. Rich strings are stored in
+// the raw "<r>...</r>" format used by the shared string table.
+fn read_shared_strings(xml: &str) -> Vec<Arc<str>> {
+    let Some(element) = find_element(xml, "sst", 0) else {
+        return vec![];
+    };
+
+    let mut strings = vec![];
+    for si in children(element.inner(xml)) {
+        if tag_name(si) != "si" {
+            continue;
+        }
+
+        let elements = children(element_inner(si));
+        let runs: Vec<&str> = elements
+            .iter()
+            .filter(|child| tag_name(child) == "r")
+            .copied()
+            .collect();
+
+        let string = if runs.is_empty() {
+            // Ignore any phonetic runs and use the text element only.
+            elements
+                .iter()
+                .filter(|child| tag_name(child) == "t")
+                .map(|child| unescape_excel_string(&text_content(child)))
+                .collect::<String>()
+        } else {
+            runs.concat()
+        };
+
+        strings.push(Arc::from(string));
+    }
+
+    strings
+}
+
+// -----------------------------------------------------------------------
+// Template worksheets.
+// -----------------------------------------------------------------------
+
+// A worksheet, or chartsheet, from the template file. The XML before and
+// after the <sheetData> element is stored as-is and the cell data is stored
+// by row and column so that it can be merged with the user data.
+pub(crate) struct TemplateSheet {
+    pub(crate) head: String,
+    pub(crate) tail: String,
+    pub(crate) rows: BTreeMap<RowNum, TemplateRow>,
+    pub(crate) relationships: Vec<TemplateRelationship>,
+    pub(crate) has_sheet_data: bool,
+}
+
+// A row of cells in a template worksheet.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct TemplateRow {
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) cells: BTreeMap<ColNum, TemplateCell>,
+}
+
+// A cell in a template worksheet. Shared string cells are stored with the
+// string so that they can be added to the new shared string table.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum TemplateCell {
+    Raw(String),
+    String {
+        start_tag: String,
+        string: Arc<str>,
+        string_id: u32,
+    },
+}
+
+impl TemplateSheet {
+    // -----------------------------------------------------------------------
+    // Crate public methods.
+    // -----------------------------------------------------------------------
+
+    // Create a new TemplateSheet from the worksheet XML.
+    pub(crate) fn new(xml: &str, strings: &[Arc<str>]) -> Result<TemplateSheet, XlsxError> {
+        let sheet = match find_element(xml, "sheetData", 0) {
+            Some(element) => TemplateSheet {
+                head: xml[..element.start].to_string(),
+                tail: xml[element.end..].to_string(),
+                rows: read_rows(element.inner(xml), Some(strings))?,
+                relationships: vec![],
+                has_sheet_data: true,
+            },
+            None => TemplateSheet {
+                head: xml.to_string(),
+                tail: String::new(),
+                rows: BTreeMap::new(),
+                relationships: vec![],
+                has_sheet_data: false,
+            },
+        };
+
+        Ok(sheet)
+    }
+
+    // Add the template strings to the workbook shared string table.
+    pub(crate) fn update_string_ids(&mut self, string_table: &mut SharedStringsTable) {
+        for row in self.rows.values_mut() {
+            for cell in row.cells.values_mut() {
+                if let TemplateCell::String {
+                    string, string_id, ..
+                } = cell
+                {
+                    *string_id = string_table.shared_string_index(Arc::clone(string));
+                }
+            }
+        }
+    }
+
+    // Get the value of a template cell, for deserialization. Cells without a
+    // value, such as formatted blank cells, return None. Numbers in a 1904
+    // epoch template are marked so that dates can be converted to the 1900
+    // epoch.
+    #[cfg(feature = "serde")]
+    pub(crate) fn cell_value(
+        &self,
+        row: RowNum,
+        col: ColNum,
+        is_1904_date: bool,
+    ) -> Option<CellValue> {
+        let cell = self.rows.get(&row)?.cells.get(&col)?;
+
+        let cell_xml = match cell {
+            TemplateCell::String { string, .. } => {
+                // Rich strings are stored in the raw "<r>...</r>" format so we
+                // extract the text of the runs.
+                if !string.starts_with("<r>") {
+                    return Some(CellValue::String(string.to_string()));
+                }
+
+                let text = children(string)
+                    .into_iter()
+                    .flat_map(|run| children(element_inner(run)))
+                    .filter(|child| tag_name(child) == "t")
+                    .map(|child| unescape_excel_string(&text_content(child)))
+                    .collect();
+
+                return Some(CellValue::String(text));
+            }
+            TemplateCell::Raw(cell_xml) => cell_xml,
+        };
+
+        let cell_type = attribute(start_tag(cell_xml), "t").unwrap_or_default();
+        let elements = children(element_inner(cell_xml));
+
+        // Inline strings are stored in <is> elements, like shared strings.
+        if cell_type == "inlineStr" {
+            let text = elements
+                .iter()
+                .filter(|child| tag_name(child) == "is")
+                .flat_map(|child| children(element_inner(child)))
+                .flat_map(|child| match tag_name(child) {
+                    "r" => children(element_inner(child)),
+                    _ => vec![child],
+                })
+                .filter(|child| tag_name(child) == "t")
+                .map(|child| unescape_excel_string(&text_content(child)))
+                .collect();
+
+            return Some(CellValue::String(text));
+        }
+
+        let value = elements
+            .iter()
+            .find(|child| tag_name(child) == "v")
+            .map(|child| text_content(child))?;
+
+        let value = match cell_type.as_str() {
+            "b" => CellValue::Boolean(value.trim() == "1"),
+            "e" => CellValue::Error(value),
+            "str" | "d" => CellValue::String(unescape_excel_string(&value)),
+            _ => CellValue::number(value.trim().parse().ok()?, is_1904_date),
+        };
+
+        Some(value)
+    }
+
+    // Merge the <row> elements generated from the user data with the template
+    // data and return the complete worksheet XML. User cells replace template
+    // cells at the same position.
+    pub(crate) fn assemble_xml(&self, generated_rows: &str, is_selected: bool) -> String {
+        if !self.has_sheet_data {
+            return Self::update_sheet_view(&self.head, is_selected);
+        }
+
+        let user_rows = read_rows(generated_rows, None).unwrap_or_default();
+
+        let row_nums: BTreeSet<RowNum> =
+            self.rows.keys().chain(user_rows.keys()).copied().collect();
+
+        let mut sheet_data = String::with_capacity(generated_rows.len() * 2);
+        let mut first_cell: Option<(RowNum, ColNum)> = None;
+        let mut last_cell: (RowNum, ColNum) = (0, 0);
+
+        for row_num in row_nums {
+            let template_row = self.rows.get(&row_num);
+            let user_row = user_rows.get(&row_num);
+
+            // Use the template row attributes, overridden by any user row
+            // options such as the row height or format.
+            let mut attributes = template_row.map_or_else(Vec::new, |row| row.attributes.clone());
+            if let Some(user_row) = user_row {
+                for (name, value) in &user_row.attributes {
+                    set_attribute(&mut attributes, name, value);
+                }
+            }
+
+            let col_nums: BTreeSet<ColNum> = template_row
+                .iter()
+                .chain(user_row.iter())
+                .flat_map(|row| row.cells.keys())
+                .copied()
+                .collect();
+
+            if col_nums.is_empty() {
+                sheet_data.push_str(&build_start_tag("row", &attributes, true));
+                continue;
+            }
+
+            sheet_data.push_str(&build_start_tag("row", &attributes, false));
+
+            for col_num in col_nums {
+                let cell = user_row
+                    .and_then(|row| row.cells.get(&col_num))
+                    .or_else(|| template_row.and_then(|row| row.cells.get(&col_num)));
+
+                match cell {
+                    Some(TemplateCell::Raw(xml)) => sheet_data.push_str(xml),
+                    Some(TemplateCell::String {
+                        start_tag,
+                        string_id,
+                        ..
+                    }) => {
+                        sheet_data.push_str(&format!("{start_tag}<v>{string_id}</v></c>"));
+                    }
+                    None => {}
+                }
+
+                let (first_row, first_col) = first_cell.unwrap_or((row_num, col_num));
+                first_cell = Some((first_row.min(row_num), first_col.min(col_num)));
+                last_cell = (last_cell.0.max(row_num), last_cell.1.max(col_num));
+            }
+
+            sheet_data.push_str("</row>");
+        }
+
+        let dimension = match first_cell {
+            None => "A1".to_string(),
+            Some(first_cell) if first_cell == last_cell => {
+                utility::row_col_to_cell(first_cell.0, first_cell.1)
+            }
+            Some(first_cell) => {
+                utility::cell_range(first_cell.0, first_cell.1, last_cell.0, last_cell.1)
+            }
+        };
+
+        let mut head = Self::update_sheet_view(&self.head, is_selected);
+        if let Some(element) = find_element(&head, "dimension", 0) {
+            head.replace_range(
+                element.start..element.end,
+                &format!(r#"<dimension ref="{dimension}"/>"#),
+            );
+        }
+
+        if sheet_data.is_empty() {
+            format!("{head}<sheetData/>{}", self.tail)
+        } else {
+            format!("{head}<sheetData>{sheet_data}</sheetData>{}", self.tail)
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Internal functions/methods.
+    // -----------------------------------------------------------------------
+
+    // Set the "tabSelected" attribute of the first sheet view to match the
+    // sheet selection in the new workbook.
+    fn update_sheet_view(head: &str, is_selected: bool) -> String {
+        let mut head = head.to_string();
+
+        if let Some(element) = find_element(&head, "sheetView", 0) {
+            let mut attributes = attributes(element.start_tag(&head));
+            attributes.retain(|(name, _)| name != "tabSelected");
+
+            if is_selected {
+                attributes.insert(0, ("tabSelected".to_string(), "1".to_string()));
+            }
+
+            let tag = build_start_tag("sheetView", &attributes, element.is_empty);
+            head.replace_range(element.start..element.tag_end, &tag);
+        }
+
+        head
+    }
+}
+
+// Read the <row> elements from the <sheetData> element of a worksheet. If the
+// strings from the template shared string table are supplied then shared
+// string cells are stored with their string value. The "spans" attribute is
+// removed from the rows since it may no longer be valid when the data is
+// merged.
+fn read_rows(
+    xml: &str,
+    strings: Option<&[Arc<str>]>,
+) -> Result<BTreeMap<RowNum, TemplateRow>, XlsxError> {
+    let mut rows = BTreeMap::new();
+    let mut next_row_num = 0;
+
+    for row_xml in children(xml) {
+        if tag_name(row_xml) != "row" {
+            continue;
+        }
+
+        let mut row_attributes = attributes(start_tag(row_xml));
+        row_attributes.retain(|(name, _)| name != "spans");
+
+        let row_num = match get_attribute(&row_attributes, "r").map(str::parse::<RowNum>) {
+            Some(Ok(row_num)) if row_num > 0 => row_num - 1,
+            _ => next_row_num,
+        };
+        next_row_num = row_num + 1;
+
+        set_attribute(&mut row_attributes, "r", &(row_num + 1).to_string());
+
+        let mut cells = BTreeMap::new();
+        let mut next_col_num = 0;
+
+        for cell_xml in children(element_inner(row_xml)) {
+            if tag_name(cell_xml) != "c" {
+                continue;
+            }
+
+            let tag = start_tag(cell_xml);
+            let mut cell_attributes = attributes(tag);
+
+            let col_num = match get_attribute(&cell_attributes, "r").and_then(cell_reference) {
+                Some((_, col_num)) => col_num,
+                None => next_col_num,
+            };
+            next_col_num = col_num + 1;
+
+            let cell_ref = utility::row_col_to_cell(row_num, col_num);
+            set_attribute(&mut cell_attributes, "r", &cell_ref);
+
+            let is_shared_string = get_attribute(&cell_attributes, "t") == Some("s");
+
+            let cell = match strings {
+                Some(strings) if is_shared_string => {
+                    let value = children(element_inner(cell_xml))
+                        .into_iter()
+                        .find(|child| tag_name(child) == "v")
+                        .map(element_inner)
+                        .unwrap_or_default();
+
+                    let Some(string) = value
+                        .trim()
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| strings.get(index))
+                    else {
+                        return Err(XlsxError::TemplateError(format!(
+                            "Invalid shared string index '{value}' in template cell '{cell_ref}'"
+                        )));
+                    };
+
+                    TemplateCell::String {
+                        start_tag: build_start_tag("c", &cell_attributes, false),
+                        string: Arc::clone(string),
+                        string_id: 0,
+                    }
+                }
+                _ => {
+                    let is_empty = tag.ends_with("/>");
+                    TemplateCell::Raw(format!(
+                        "{}{}",
+                        build_start_tag("c", &cell_attributes, is_empty),
+                        &cell_xml[tag.len()..]
+                    ))
+                }
+            };
+
+            cells.insert(col_num, cell);
+        }
+
+        rows.insert(
+            row_num,
+            TemplateRow {
+                attributes: row_attributes,
+                cells,
+            },
+        );
+    }
+
+    Ok(rows)
+}
+
+// Convert an "A1" style cell reference to a zero indexed row and column.
+fn cell_reference(cell: &str) -> Option<(RowNum, ColNum)> {
+    let cell = cell.replace('$', "");
+    let position = cell.find(|c: char| c.is_ascii_digit())?;
+    let (column, row) = cell.split_at(position);
+
+    if column.is_empty() || !column.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let row_num = row.parse::<RowNum>().ok()?.checked_sub(1)?;
+    let col_num = utility::column_name_to_number(&column.to_ascii_uppercase());
+
+    Some((row_num, col_num))
+}
+
+// -----------------------------------------------------------------------
+// Template styles.
+// -----------------------------------------------------------------------
+
+// The styles from the template file. The formats added by the user are
+// appended to the template styles when the file is saved so the template
+// format indices remain unchanged.
+pub(crate) struct TemplateStyles {
+    xml: String,
+    num_format_base: u32,
+    font_count: u32,
+    fill_count: u32,
+    border_count: u32,
+    cell_style_xf_count: u32,
+    xf_count: u32,
+    dxf_count: u32,
+    hyperlink_xf_id: Option<u32>,
+}
+
+impl TemplateStyles {
+    // -----------------------------------------------------------------------
+    // Crate public methods.
+    // -----------------------------------------------------------------------
+
+    // Create a new TemplateStyles struct from the template styles.xml file.
+    pub(crate) fn new(xml: String) -> TemplateStyles {
+        let count = |name: &str| section_children(&xml, name).len() as u32;
+
+        // User defined number formats start at 164 in Excel. New formats are
+        // added after the highest number format id in the template.
+        let num_format_base = section_children(&xml, "numFmts")
+            .iter()
+            .filter_map(|child| attribute(start_tag(child), "numFmtId"))
+            .filter_map(|id| id.parse::<u32>().ok())
+            .map(|id| id + 1)
+            .fold(164, u32::max);
+
+        let hyperlink_xf_id = section_children(&xml, "cellStyles")
+            .iter()
+            .map(|child| start_tag(child))
+            .find(|tag| attribute(tag, "builtinId").as_deref() == Some("8"))
+            .and_then(|tag| attribute(tag, "xfId"))
+            .and_then(|id| id.parse().ok());
+
+        TemplateStyles {
+            num_format_base,
+            font_count: count("fonts"),
+            fill_count: count("fills"),
+            border_count: count("borders"),
+            cell_style_xf_count: count("cellStyleXfs"),
+            xf_count: count("cellXfs"),
+            dxf_count: count("dxfs"),
+            hyperlink_xf_id,
+            xml,
+        }
+    }
+
+    // Map a workbook cell format index to its index after the template
+    // formats. The default format maps to the template default format.
+    pub(crate) fn xf_index(&self, index: u32) -> u32 {
+        if index == 0 {
+            0
+        } else {
+            self.xf_count + index - 1
+        }
+    }
+
+    // Map a workbook differential format index to its index after the
+    // template formats.
+    pub(crate) fn dxf_index(&self, index: u32) -> u32 {
+        self.dxf_count + index
+    }
+
+    // Merge the styles.xml file generated for the workbook formats into the
+    // template styles.
+    pub(crate) fn merge(&self, generated: &str) -> String {
+        let mut xml = self.xml.clone();
+
+        let cell_style_xfs = section_children(generated, "cellStyleXfs");
+        let cell_xfs: Vec<&str> = section_children(generated, "cellXfs")
+            .into_iter()
+            .skip(1)
+            .collect();
+
+        // Add a hyperlink cell style if it is used by the new formats and
+        // there isn't one in the template.
+        let uses_hyperlink_style = cell_xfs
+            .iter()
+            .any(|xf| attribute(start_tag(xf), "xfId").as_deref() == Some("1"));
+
+        let mut hyperlink_xf_id = self.hyperlink_xf_id.unwrap_or(0);
+        let mut new_cell_style_xfs = vec![];
+        let mut new_cell_styles = vec![];
+
+        if uses_hyperlink_style && self.hyperlink_xf_id.is_none() {
+            if let Some(style_xf) = cell_style_xfs.get(1) {
+                hyperlink_xf_id = self.cell_style_xf_count;
+                new_cell_style_xfs.push(self.remap_ids(style_xf, hyperlink_xf_id));
+                new_cell_styles.push(format!(
+                    r#"<cellStyle name="Hyperlink" xfId="{hyperlink_xf_id}" builtinId="8"/>"#
+                ));
+            }
+        }
+
+        let remap = |elements: Vec<&str>| -> Vec<String> {
+            elements
+                .into_iter()
+                .map(|element| self.remap_ids(element, hyperlink_xf_id))
+                .collect()
+        };
+
+        // The default font, fill and border elements are replaced by the
+        // template defaults.
+        let num_fmts = remap(section_children(generated, "numFmts"));
+        let fonts = remap(
+            section_children(generated, "fonts")
+                .into_iter()
+                .skip(1)
+                .collect(),
+        );
+        let fills = remap(
+            section_children(generated, "fills")
+                .into_iter()
+                .skip(2)
+                .collect(),
+        );
+        let borders = remap(
+            section_children(generated, "borders")
+                .into_iter()
+                .skip(1)
+                .collect(),
+        );
+        let cell_xfs = remap(cell_xfs);
+        let dxfs = remap(section_children(generated, "dxfs"));
+
+        append_children(&mut xml, "numFmts", &num_fmts, &["fonts"]);
+        append_children(&mut xml, "fonts", &fonts, &["fills"]);
+        append_children(&mut xml, "fills", &fills, &["borders"]);
+        append_children(&mut xml, "borders", &borders, &["cellStyleXfs", "cellXfs"]);
+        append_children(&mut xml, "cellStyleXfs", &new_cell_style_xfs, &["cellXfs"]);
+        append_children(
+            &mut xml,
+            "cellXfs",
+            &cell_xfs,
+            &["cellStyles", "dxfs", "tableStyles", "colors", "extLst"],
+        );
+        append_children(
+            &mut xml,
+            "cellStyles",
+            &new_cell_styles,
+            &["dxfs", "tableStyles", "colors", "extLst"],
+        );
+        append_children(
+            &mut xml,
+            "dxfs",
+            &dxfs,
+            &["tableStyles", "colors", "extLst"],
+        );
+
+        xml
+    }
+
+    // -----------------------------------------------------------------------
+    // Internal functions/methods.
+    // -----------------------------------------------------------------------
+
+    // Update the number format, font, fill, border and cell style ids in a
+    // generated style element to their indices after the template styles.
+    fn remap_ids(&self, element: &str, hyperlink_xf_id: u32) -> String {
+        rewrite_tags(element, |name, attributes| {
+            let mut changed = false;
+
+            for (attribute_name, value) in attributes.iter_mut() {
+                let Ok(index) = value.parse::<u32>() else {
+                    continue;
+                };
+
+                let new_index = match attribute_name.as_str() {
+                    "numFmtId" if index >= 164 => self.num_format_base + index - 164,
+                    "fontId" if index > 0 => self.font_count + index - 1,
+                    "fillId" if index > 1 => self.fill_count + index - 2,
+                    "borderId" if index > 0 => self.border_count + index - 1,
+                    "xfId" if name == "xf" && index == 1 => hyperlink_xf_id,
+                    _ => continue,
+                };
+
+                *value = new_index.to_string();
+                changed = true;
+            }
+
+            changed
+        })
+    }
+}
+
+// Get the child elements of a styles.xml section such as <fonts>.
+fn section_children<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    find_element(xml, name, 0)
+        .map(|element| children(element.inner(xml)))
+        .unwrap_or_default()
+}
+
+// Append child elements to a styles.xml section and update its count. If the
+// section doesn't exist it is inserted before the first of the `insert_before`
+// elements that exists.
+fn append_children(xml: &mut String, name: &str, new_children: &[String], insert_before: &[&str]) {
+    if new_children.is_empty() {
+        return;
+    }
+
+    let content = new_children.concat();
+
+    if let Some(element) = find_element(xml, name, 0) {
+        let inner = element.inner(xml);
+        let count = children(inner).len() + new_children.len();
+
+        let mut attributes = attributes(element.start_tag(xml));
+        set_attribute(&mut attributes, "count", &count.to_string());
+
+        let section = format!(
+            "{}{inner}{content}</{name}>",
+            build_start_tag(name, &attributes, false)
+        );
+        xml.replace_range(element.start..element.end, &section);
+    } else {
+        let position = insert_before
+            .iter()
+            .find_map(|before| find_element(xml, before, 0).map(|element| element.start))
+            .or_else(|| xml.rfind("</styleSheet>"))
+            .unwrap_or(xml.len());
+
+        let section = format!(
+            r#"<{name} count="{}">{content}</{name}>"#,
+            new_children.len()
+        );
+        xml.insert_str(position, &section);
+    }
+}
+
+// -----------------------------------------------------------------------
+// Package path functions.
+// -----------------------------------------------------------------------
+
+// Get the name of a passed through part in the new package.
+pub(crate) fn template_part_name(name: &str) -> String {
+    if !name.starts_with("xl/") {
+        return name.to_string();
+    }
+
+    match name.rsplit_once('/') {
+        Some((directory, file)) => format!("{directory}/template_{file}"),
+        None => name.to_string(),
+    }
+}
+
+// Get the path of the .rels file for a part. The root relationships are
+// represented by an empty part name.
+fn relationships_path(part: &str) -> String {
+    if part.is_empty() {
+        return "_rels/.rels".to_string();
+    }
+
+    match part.rsplit_once('/') {
+        Some((directory, file)) => format!("{directory}/_rels/{file}.rels"),
+        None => format!("_rels/{part}.rels"),
+    }
+}
+
+// Check if a part is a .rels file.
+fn is_relationships_part(name: &str) -> bool {
+    name.ends_with(".rels") && (name.starts_with("_rels/") || name.contains("/_rels/"))
+}
+
+// Get the source part of a .rels file.
+fn relationships_source(name: &str) -> String {
+    let name = name.trim_end_matches(".rels");
+
+    match name.rsplit_once("_rels/") {
+        Some((directory, file)) => format!("{directory}{file}"),
+        None => name.to_string(),
+    }
+}
+
+// Get the directory of a part.
+fn directory_name(part: &str) -> &str {
+    part.rsplit_once('/').map_or("", |(directory, _)| directory)
+}
+
+// Resolve a relationship target relative to a directory into an absolute
+// part name.
+fn resolve_path(directory: &str, target: &str) -> String {
+    let mut segments: Vec<&str> = vec![];
+
+    let path = match target.strip_prefix('/') {
+        Some(target) => target.to_string(),
+        None if directory.is_empty() => target.to_string(),
+        None => format!("{directory}/{target}"),
+    };
+
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    segments.join("/")
+}
+
+// Get the relative path from a directory to a part.
+fn relative_path(directory: &str, part: &str) -> String {
+    let from: Vec<&str> = directory.split('/').filter(|s| !s.is_empty()).collect();
+    let to: Vec<&str> = part.split('/').collect();
+
+    let common = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut segments = vec![".."; from.len() - common];
+    segments.extend(&to[common..]);
+
+    segments.join("/")
+}
+
+// Get the last segment of a relationship type such as "worksheet".
+fn relationship_kind(relationship: &TemplateRelationship) -> &str {
+    relationship.rel_type.rsplit('/').next().unwrap_or_default()
+}
+
+// -----------------------------------------------------------------------
+// Minimal XML scanning functions. These are sufficient for reading the well
+// formed XML in xlsx files without a full XML parser. The supported input is
+// restricted to the XML that Excel, and similar applications, write:
+//
+// - The parts must be UTF-8 with a single root element and balanced start
+//   and end tags. This is checked by check_xml() when the part is read.
+// - Elements are matched by their full tag name. The SpreadsheetML and
+//   package elements must use the default namespace, so a part with a
+//   namespace prefixed root element, such as "<x:worksheet>", is rejected.
+//   Elements from extension namespaces, such as "<x14:sparklineGroups>", are
+//   passed through as-is.
+// - Comments, processing instructions and CDATA sections are skipped when
+//   scanning for elements. CDATA sections are read as text in text content.
+// - DOCTYPE declarations, and therefore custom entities, aren't supported.
+// -----------------------------------------------------------------------
+
+// The byte positions of an element in an XML string.
+#[derive(Clone, Copy, Debug)]
+struct XmlElement {
+    start: usize,
+    tag_end: usize,
+    inner_end: usize,
+    end: usize,
+    is_empty: bool,
+}
+
+impl XmlElement {
+    // Get the content of the element.
+    fn inner<'a>(&self, xml: &'a str) -> &'a str {
+        &xml[self.tag_end..self.inner_end]
+    }
+
+    // Get the start tag of the element.
+    fn start_tag<'a>(&self, xml: &'a str) -> &'a str {
+        &xml[self.start..self.tag_end]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TagType {
+    Start,
+    End,
+    Empty,
+    Other,
+}
+
+// Find the next tag, or other markup such as a comment, starting from a
+// position in an XML string.
+fn next_tag(xml: &str, from: usize) -> Option<(usize, usize, TagType)> {
+    let start = from + xml.get(from..)?.find('<')?;
+    let rest = &xml[start..];
+
+    let (length, tag_type) = if rest.starts_with("<!--") {
+        (rest.find("-->")? + 3, TagType::Other)
+    } else if rest.starts_with("<![CDATA[") {
+        (rest.find("]]>")? + 3, TagType::Other)
+    } else if rest.starts_with("<?") {
+        (rest.find("?>")? + 2, TagType::Other)
+    } else if rest.starts_with("<!") {
+        (rest.find('>')? + 1, TagType::Other)
+    } else if rest.starts_with("</") {
+        (rest.find('>')? + 1, TagType::End)
+    } else {
+        let length = tag_length(rest)?;
+        if rest[..length].ends_with("/>") {
+            (length, TagType::Empty)
+        } else {
+            (length, TagType::Start)
+        }
+    };
+
+    Some((start, start + length, tag_type))
+}
+
+// Check that an XML part is in the subset of XML that can be read by the
+// scanning functions, and optionally that it has the expected root element.
+// See the notes at the start of this section.
+fn check_xml(xml: &str, path: &str, root_name: Option<&str>) -> Result<(), XlsxError> {
+    let error = |reason: String| {
+        Err(XlsxError::TemplateError(format!(
+            "Couldn't read part '{path}' in the template file: {reason}"
+        )))
+    };
+
+    let mut open_elements: Vec<&str> = vec![];
+    let mut has_root = false;
+    let mut position = 0;
+
+    while xml[position..].contains('<') {
+        let Some((start, end, tag_type)) = next_tag(xml, position) else {
+            return error("unterminated XML markup".to_string());
+        };
+
+        let tag = &xml[start..end];
+        let name = tag_name(tag);
+
+        match tag_type {
+            TagType::Other if tag.starts_with("<!DOCTYPE") => {
+                return error("DOCTYPE declarations aren't supported".to_string());
+            }
+            TagType::Other => {}
+            TagType::Start | TagType::Empty => {
+                if name.is_empty() {
+                    return error(format!("invalid XML tag '{tag}'"));
+                }
+
+                if open_elements.is_empty() {
+                    if has_root {
+                        return error(format!("unexpected second root element '<{name}>'"));
+                    }
+                    if root_name.is_some_and(|root_name| root_name != name) {
+                        return error(format!("unexpected root element '<{name}>'"));
+                    }
+                    has_root = true;
+                }
+
+                if tag_type == TagType::Start {
+                    open_elements.push(name);
+                }
+            }
+            TagType::End => {
+                if open_elements.pop() != Some(name) {
+                    return error(format!("unexpected XML end tag '{tag}'"));
+                }
+            }
+        }
+
+        position = end;
+    }
+
+    if let Some(name) = open_elements.last() {
+        return error(format!("unclosed XML element '<{name}>'"));
+    }
+
+    if !has_root {
+        return error("missing XML root element".to_string());
+    }
+
+    Ok(())
+}
+
+// Get the length of a tag, ignoring any '>' characters in quoted attribute
+// values.
+fn tag_length(tag: &str) -> Option<usize> {
+    let mut quote = None;
+
+    for (i, ch) in tag.char_indices() {
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == '>' => return Some(i + 1),
+            None => {}
+        }
+    }
+
+    None
+}
+
+// Get the name of a tag or element.
+fn tag_name(tag: &str) -> &str {
+    let name = tag.trim_start_matches('<').trim_start_matches('/');
+    let end = name
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .unwrap_or(name.len());
+
+    &name[..end]
+}
+
+// Find the first element with a given name starting from a position in an XML
+// string.
+fn find_element(xml: &str, name: &str, from: usize) -> Option<XmlElement> {
+    let mut position = from;
+    let mut start = None;
+    let mut tag_end = 0;
+    let mut depth = 0;
+
+    while let Some((tag_start, end, tag_type)) = next_tag(xml, position) {
+        position = end;
+
+        if tag_type == TagType::Other || tag_name(&xml[tag_start..end]) != name {
+            continue;
+        }
+
+        match (start, tag_type) {
+            (None, TagType::Empty) => {
+                return Some(XmlElement {
+                    start: tag_start,
+                    tag_end: end,
+                    inner_end: end,
+                    end,
+                    is_empty: true,
+                });
+            }
+            (None, TagType::Start) => {
+                start = Some(tag_start);
+                tag_end = end;
+                depth = 1;
+            }
+            (Some(_), TagType::Start) => depth += 1,
+            (Some(element_start), TagType::End) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(XmlElement {
+                        start: element_start,
+                        tag_end,
+                        inner_end: tag_start,
+                        end,
+                        is_empty: false,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+// Get the top level child elements of an XML fragment.
+fn children(xml: &str) -> Vec<&str> {
+    let mut children = vec![];
+    let mut position = 0;
+    let mut start = 0;
+    let mut depth = 0;
+
+    while let Some((tag_start, end, tag_type)) = next_tag(xml, position) {
+        position = end;
+
+        match tag_type {
+            TagType::Start => {
+                if depth == 0 {
+                    start = tag_start;
+                }
+                depth += 1;
+            }
+            TagType::End if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    children.push(&xml[start..end]);
+                }
+            }
+            TagType::Empty if depth == 0 => children.push(&xml[tag_start..end]),
+            _ => {}
+        }
+    }
+
+    children
+}
+
+// Get the start tag of an element.
+fn start_tag(element: &str) -> &str {
+    &element[..tag_length(element).unwrap_or(element.len())]
+}
+
+// Get the content of an element.
+fn element_inner(element: &str) -> &str {
+    let tag = start_tag(element);
+    if tag.ends_with("/>") {
+        return "";
+    }
+
+    let inner = &element[tag.len()..];
+    inner.rfind("</").map_or(inner, |end| &inner[..end])
+}
+
+// Get the unescaped text content of an element. CDATA sections are read as
+// literal text and comments and processing instructions are ignored.
+fn text_content(element: &str) -> String {
+    let inner = element_inner(element);
+    let mut text = String::with_capacity(inner.len());
+    let mut position = 0;
+
+    while let Some((start, end, _)) = next_tag(inner, position) {
+        text.push_str(&unescape(&inner[position..start]));
+
+        if let Some(cdata) = inner[start..end]
+            .strip_prefix("<![CDATA[")
+            .and_then(|cdata| cdata.strip_suffix("]]>"))
+        {
+            text.push_str(cdata);
+        }
+
+        position = end;
+    }
+
+    text.push_str(&unescape(&inner[position..]));
+    text
+}
+
+// Get the attributes of a start tag. The values are returned in their escaped
+// form.
+fn attributes(tag: &str) -> Vec<(String, String)> {
+    let mut attributes = vec![];
+    let name = tag_name(tag);
+    let body = tag
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .trim_end_matches('/');
+    let mut rest = &body[name.len()..];
+
+    loop {
+        rest = rest.trim_start();
+
+        let Some(equals) = rest.find('=') else {
+            break;
+        };
+
+        let attribute_name = rest[..equals].trim().to_string();
+        rest = rest[equals + 1..].trim_start();
+
+        let Some(quote) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+
+        let Some(length) = rest[1..].find(quote) else {
+            break;
+        };
+
+        // Double quotes in single quoted values need to be escaped since the
+        // attributes are rewritten with double quotes.
+        let value = rest[1..=length].replace('"', "&quot;");
+        attributes.push((attribute_name, value));
+
+        rest = &rest[length + 2..];
+    }
+
+    attributes
+}
+
+// Get the unescaped value of an attribute in a start tag.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    attributes(tag)
+        .into_iter()
+        .find(|(attribute_name, _)| attribute_name == name)
+        .map(|(_, value)| unescape(&value))
+}
+
+// Get the escaped value of an attribute from a list of attributes.
+fn get_attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(attribute_name, _)| attribute_name == name)
+        .map(|(_, value)| value.as_str())
+}
+
+// Set, or add, the escaped value of an attribute in a list of attributes.
+fn set_attribute(attributes: &mut Vec<(String, String)>, name: &str, value: &str) {
+    match attributes
+        .iter_mut()
+        .find(|(attribute_name, _)| attribute_name == name)
+    {
+        Some((_, old_value)) => *old_value = value.to_string(),
+        None => attributes.push((name.to_string(), value.to_string())),
+    }
+}
+
+// Create a start tag from a name and a list of escaped attributes.
+fn build_start_tag(name: &str, attributes: &[(String, String)], is_empty: bool) -> String {
+    let mut tag = format!("<{name}");
+
+    for (attribute_name, value) in attributes {
+        tag.push_str(&format!(r#" {attribute_name}="{value}""#));
+    }
+
+    if is_empty {
+        tag.push_str("/>");
+    } else {
+        tag.push('>');
+    }
+
+    tag
+}
+
+// Rewrite the start tags in an XML fragment. The function is called with the
+// tag name and attributes of each start tag and returns true if the attributes
+// have changed.
+fn rewrite_tags<F>(xml: &str, mut function: F) -> String
+where
+    F: FnMut(&str, &mut Vec<(String, String)>) -> bool,
+{
+    let mut rewritten = String::with_capacity(xml.len());
+    let mut position = 0;
+
+    while let Some((start, end, tag_type)) = next_tag(xml, position) {
+        rewritten.push_str(&xml[position..start]);
+
+        let tag = &xml[start..end];
+        let mut attributes = attributes(tag);
+
+        if matches!(tag_type, TagType::Start | TagType::Empty)
+            && function(tag_name(tag), &mut attributes)
+        {
+            let is_empty = tag_type == TagType::Empty;
+            rewritten.push_str(&build_start_tag(tag_name(tag), &attributes, is_empty));
+        } else {
+            rewritten.push_str(tag);
+        }
+
+        position = end;
+    }
+
+    rewritten.push_str(&xml[position..]);
+    rewritten
+}
+
+// Unescape the standard XML entities and character references. Literal
+// carriage returns are normalized to newlines, as they would be by an XML
+// parser.
+fn unescape(text: &str) -> String {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    if !text.contains('&') {
+        return text;
+    }
+
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text.as_str();
+
+    while let Some(position) = rest.find('&') {
+        unescaped.push_str(&rest[..position]);
+        rest = &rest[position..];
+
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+
+        let entity = &rest[1..end];
+        let ch = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                if let Some(hex) = entity.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                } else if let Some(decimal) = entity.strip_prefix('#') {
+                    decimal.parse().ok().and_then(char::from_u32)
+                } else {
+                    None
+                }
+            }
+        };
+
+        match ch {
+            Some(ch) => {
+                unescaped.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+    unescaped
+}
+
+// Unescape the "_xHHHH_" escapes that Excel uses for control characters in
+// strings.
+fn unescape_excel_string(text: &str) -> String {
+    if !text.contains("_x") {
+        return text.to_string();
+    }
+
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(position) = rest.find("_x") {
+        unescaped.push_str(&rest[..position]);
+        rest = &rest[position..];
+
+        let ch = rest
+            .get(2..6)
+            .filter(|_| rest.get(6..7) == Some("_"))
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(char::from_u32);
+
+        match ch {
+            Some(ch) => {
+                unescaped.push(ch);
+                rest = &rest[7..];
+            }
+            None => {
+                unescaped.push_str("_x");
+                rest = &rest[2..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+    unescaped
+}
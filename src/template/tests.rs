@@ -0,0 +1,503 @@
+// Template unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod template_tests {
+
+    use std::sync::Arc;
+
+    use crate::shared_strings_table::SharedStringsTable;
+    use crate::template::{
+        attributes, check_xml, children, find_element, read_shared_strings, read_template,
+        relationships_path, relationships_source, relative_path, resolve_path, template_part_name,
+        text_content, unescape, unescape_excel_string, TemplateSheet, TemplateStyles,
+    };
+    use crate::test_functions::xml_to_vec;
+    use crate::{Workbook, XlsxError};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_package_paths() {
+        assert_eq!(
+            "xl/drawings/drawing1.xml",
+            resolve_path("xl/worksheets", "../drawings/drawing1.xml")
+        );
+        assert_eq!(
+            "xl/media/image1.png",
+            resolve_path("xl", "/xl/media/image1.png")
+        );
+        assert_eq!("xl/workbook.xml", resolve_path("", "xl/workbook.xml"));
+
+        assert_eq!(
+            "../drawings/template_drawing1.xml",
+            relative_path("xl/worksheets", "xl/drawings/template_drawing1.xml")
+        );
+        assert_eq!(
+            "pivotCache/template_pivotCacheDefinition1.xml",
+            relative_path("xl", "xl/pivotCache/template_pivotCacheDefinition1.xml")
+        );
+        assert_eq!(
+            "docProps/thumbnail.jpeg",
+            relative_path("", "docProps/thumbnail.jpeg")
+        );
+
+        assert_eq!("_rels/.rels", relationships_path(""));
+        assert_eq!(
+            "xl/_rels/workbook.xml.rels",
+            relationships_path("xl/workbook.xml")
+        );
+
+        assert_eq!(
+            "xl/drawings/drawing1.xml",
+            relationships_source("xl/drawings/_rels/drawing1.xml.rels")
+        );
+
+        assert_eq!(
+            "xl/media/template_image1.png",
+            template_part_name("xl/media/image1.png")
+        );
+        assert_eq!(
+            "customXml/item1.xml",
+            template_part_name("customXml/item1.xml")
+        );
+    }
+
+    #[test]
+    fn test_xml_scanning() {
+        let xml = r#"<a><b x="1>2" y='say "hi"'/><!-- <b/> --><c><c/></c><d>text</d></a>"#;
+
+        let element = find_element(xml, "c", 0).unwrap();
+        assert_eq!("<c><c/></c>", &xml[element.start..element.end]);
+        assert_eq!("<c/>", element.inner(xml));
+
+        let element = find_element(xml, "a", 0).unwrap();
+        let children = children(element.inner(xml));
+        assert_eq!(
+            vec![r#"<b x="1>2" y='say "hi"'/>"#, "<c><c/></c>", "<d>text</d>"],
+            children
+        );
+
+        assert_eq!(
+            vec![
+                ("x".to_string(), "1>2".to_string()),
+                ("y".to_string(), "say &quot;hi&quot;".to_string())
+            ],
+            attributes(children[0])
+        );
+
+        assert!(find_element(xml, "e", 0).is_none());
+
+        assert_eq!("<a & 'b'>", unescape("&lt;a &amp; &apos;b&apos;&gt;"));
+        assert_eq!("A & B", unescape("&#65; &amp; &#x42;"));
+        assert_eq!("a\nb\nc\r", unescape("a\r\nb\rc&#13;"));
+        assert_eq!("a\rb_x", unescape_excel_string("a_x000D_b_x"));
+    }
+
+    #[test]
+    fn test_xml_markup() {
+        let xml = r#"<?xml version="1.0"?><a><?pi a>b?><!-- <b> --><b><![CDATA[<c/>]]></b></a>"#;
+
+        let element = find_element(xml, "b", 0).unwrap();
+        assert_eq!("<![CDATA[<c/>]]>", element.inner(xml));
+        assert!(find_element(xml, "c", 0).is_none());
+
+        let element = find_element(xml, "a", 0).unwrap();
+        assert_eq!(
+            vec!["<b><![CDATA[<c/>]]></b>"],
+            children(element.inner(xml))
+        );
+
+        assert_eq!(
+            "1 < 2 & <3>",
+            text_content("<t>1 &lt; 2<!-- x --> &amp; <![CDATA[<3>]]></t>")
+        );
+        assert_eq!("", text_content("<t/>"));
+
+        assert!(check_xml(xml, "a.xml", Some("a")).is_ok());
+        assert!(check_xml(xml, "a.xml", Some("b")).is_err());
+        assert!(check_xml(r#"<x:a xmlns:x="x"/>"#, "a.xml", None).is_ok());
+        assert!(check_xml(r#"<x:a xmlns:x="x"/>"#, "a.xml", Some("a")).is_err());
+        assert!(check_xml(r#"<a><x:b xmlns:x="x"/></a>"#, "a.xml", Some("a")).is_ok());
+    }
+
+    #[test]
+    fn test_malformed_xml() {
+        let malformed = [
+            "",
+            "text",
+            "<a>",
+            "<a><b></a>",
+            "<a></b>",
+            "<a></a></a>",
+            "<a/><b/>",
+            r#"<a x="1>"#,
+            "<a><!-- </a>",
+            "<a><![CDATA[</a>",
+            "<a><?pi </a>",
+            "<>",
+            "<!DOCTYPE a [<!ENTITY b 'c'>]><a>&b;</a>",
+            r#"<x:worksheet xmlns:x="main"/>"#,
+            "<chartsheet/>",
+        ];
+
+        for xml in malformed {
+            let result = check_xml(xml, "xl/worksheets/sheet1.xml", Some("worksheet"));
+            assert!(
+                matches!(result, Err(XlsxError::TemplateError(_))),
+                "Expected an error for: {xml}"
+            );
+        }
+
+        // Malformed parts are reported when the template is read.
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet().write(0, 0, "Hello").unwrap();
+        let buffer = workbook.save_to_buffer().unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(vec![]));
+        let options = zip::write::SimpleFileOptions::default();
+
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).unwrap();
+            let mut data = vec![];
+            std::io::Read::read_to_end(&mut file, &mut data).unwrap();
+
+            if file.name() == "xl/worksheets/sheet1.xml" {
+                data.truncate(data.len() - "</worksheet>".len());
+            }
+
+            writer.start_file(file.name(), options).unwrap();
+            std::io::Write::write_all(&mut writer, &data).unwrap();
+        }
+
+        let buffer = writer.finish().unwrap().into_inner();
+        let result = read_template(std::io::Cursor::new(buffer));
+        assert!(matches!(result, Err(XlsxError::TemplateError(_))));
+    }
+
+    #[test]
+    fn test_read_shared_strings() {
+        let xml = r#"
+            <sst xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" count="3" uniqueCount="3">
+              <si><t>Hello &amp; goodbye</t></si>
+              <si><t>Tokyo</t><rPh sb="0" eb="2"><t>トウキョウ</t></rPh></si>
+              <si><r><rPr><b/></rPr><t>Bold</t></r><r><t xml:space="preserve"> text</t></r></si>
+            </sst>"#;
+
+        let strings = read_shared_strings(xml);
+
+        assert_eq!(
+            vec![
+                Arc::from("Hello & goodbye"),
+                Arc::from("Tokyo"),
+                Arc::from(
+                    r#"<r><rPr><b/></rPr><t>Bold</t></r><r><t xml:space="preserve"> text</t></r>"#
+                ),
+            ],
+            strings
+        );
+    }
+
+    #[test]
+    fn test_assemble_sheet() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+              <dimension ref="A1:B2"/>
+              <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0"/>
+              </sheetViews>
+              <sheetFormatPr defaultRowHeight="15"/>
+              <sheetData>
+                <row r="1" spans="1:2" ht="20" customHeight="1">
+                  <c r="A1" s="1" t="s"><v>1</v></c>
+                  <c r="B1" s="2"><v>123</v></c>
+                </row>
+                <row r="2" spans="1:2">
+                  <c r="A2" s="1" t="s"><v>0</v></c>
+                  <c r="B2"><f>B1*2</f><v>246</v></c>
+                </row>
+              </sheetData>
+              <pageMargins left="0.7" right="0.7" top="0.75" bottom="0.75" header="0.3" footer="0.3"/>
+            </worksheet>"#;
+
+        let strings = vec![Arc::from("Region"), Arc::from("Title")];
+        let mut sheet = TemplateSheet::new(xml, &strings).unwrap();
+
+        let mut string_table = SharedStringsTable::new();
+        string_table.shared_string_index(Arc::from("User"));
+        sheet.update_string_ids(&mut string_table);
+
+        // The user data overwrites B1 and adds C2 and A4.
+        let generated_rows = r#"<row r="1" spans="2:2"><c r="B1" s="5"><v>99</v></c></row><row r="2" spans="3:3"><c r="C2" t="s"><v>0</v></c></row><row r="4" spans="1:1" ht="30" customHeight="1"><c r="A4"><v>1</v></c></row>"#;
+
+        let got = sheet.assemble_xml(generated_rows, false);
+        let got = xml_to_vec(&got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+              <dimension ref="A1:C4"/>
+              <sheetViews>
+                <sheetView workbookViewId="0"/>
+              </sheetViews>
+              <sheetFormatPr defaultRowHeight="15"/>
+              <sheetData>
+                <row r="1" ht="20" customHeight="1">
+                  <c r="A1" s="1" t="s"><v>1</v></c>
+                  <c r="B1" s="5"><v>99</v></c>
+                </row>
+                <row r="2">
+                  <c r="A2" s="1" t="s"><v>2</v></c>
+                  <c r="B2"><f>B1*2</f><v>246</v></c>
+                  <c r="C2" t="s"><v>0</v></c>
+                </row>
+                <row r="4" ht="30" customHeight="1">
+                  <c r="A4"><v>1</v></c>
+                </row>
+              </sheetData>
+              <pageMargins left="0.7" right="0.7" top="0.75" bottom="0.75" header="0.3" footer="0.3"/>
+            </worksheet>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_template_cell_values() {
+        use crate::deserializer::CellValue;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+              <sheetData>
+                <row r="1">
+                  <c r="A1" t="s"><v>0</v></c>
+                  <c r="B1" t="s"><v>1</v></c>
+                  <c r="C1" t="inlineStr"><is><t>Inline &amp; text</t></is></c>
+                  <c r="D1" t="str"><f>"a"&amp;"b"</f><v>ab</v></c>
+                </row>
+                <row r="2">
+                  <c r="A2"><v>1.5</v></c>
+                  <c r="B2" t="b"><v>1</v></c>
+                  <c r="C2" t="e"><v>#N/A</v></c>
+                  <c r="D2" s="1"/>
+                </row>
+              </sheetData>
+            </worksheet>"#;
+
+        let strings = vec![
+            Arc::from("Plain"),
+            Arc::from("<r><t>Rich</t></r><r><t> text</t></r>"),
+        ];
+        let sheet = TemplateSheet::new(xml, &strings).unwrap();
+
+        assert_eq!(
+            Some(CellValue::String("Plain".to_string())),
+            sheet.cell_value(0, 0, false)
+        );
+        assert_eq!(
+            Some(CellValue::String("Rich text".to_string())),
+            sheet.cell_value(0, 1, false)
+        );
+        assert_eq!(
+            Some(CellValue::String("Inline & text".to_string())),
+            sheet.cell_value(0, 2, false)
+        );
+        assert_eq!(
+            Some(CellValue::String("ab".to_string())),
+            sheet.cell_value(0, 3, false)
+        );
+        assert_eq!(Some(CellValue::Number(1.5)), sheet.cell_value(1, 0, false));
+        assert_eq!(
+            Some(CellValue::Boolean(true)),
+            sheet.cell_value(1, 1, false)
+        );
+        assert_eq!(
+            Some(CellValue::Error("#N/A".to_string())),
+            sheet.cell_value(1, 2, false)
+        );
+        assert_eq!(None, sheet.cell_value(1, 3, false));
+        assert_eq!(None, sheet.cell_value(2, 0, false));
+
+        // Numbers in a 1904 epoch template are marked for date conversion.
+        assert_eq!(
+            Some(CellValue::Number1904(1.5)),
+            sheet.cell_value(1, 0, true)
+        );
+    }
+
+    #[test]
+    fn test_merge_styles() {
+        let template_xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+              <numFmts count="1">
+                <numFmt numFmtId="170" formatCode="0.000"/>
+              </numFmts>
+              <fonts count="2">
+                <font><sz val="10"/><name val="Arial"/></font>
+                <font><b/><sz val="10"/><name val="Arial"/></font>
+              </fonts>
+              <fills count="2">
+                <fill><patternFill patternType="none"/></fill>
+                <fill><patternFill patternType="gray125"/></fill>
+              </fills>
+              <borders count="1">
+                <border><left/><right/><top/><bottom/><diagonal/></border>
+              </borders>
+              <cellStyleXfs count="1">
+                <xf numFmtId="0" fontId="0" fillId="0" borderId="0"/>
+              </cellStyleXfs>
+              <cellXfs count="2">
+                <xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/>
+                <xf numFmtId="170" fontId="1" fillId="0" borderId="0" xfId="0" applyNumberFormat="1" applyFont="1"/>
+              </cellXfs>
+              <cellStyles count="1">
+                <cellStyle name="Normal" xfId="0" builtinId="0"/>
+              </cellStyles>
+              <dxfs count="0"/>
+              <tableStyles count="0" defaultTableStyle="TableStyleMedium2" defaultPivotStyle="PivotStyleLight16"/>
+            </styleSheet>"#;
+
+        let generated_xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+              <numFmts count="1">
+                <numFmt numFmtId="164" formatCode="0.0"/>
+              </numFmts>
+              <fonts count="3">
+                <font><sz val="11"/><color theme="1"/><name val="Calibri"/><family val="2"/><scheme val="minor"/></font>
+                <font><i/><sz val="11"/><color theme="1"/><name val="Calibri"/><family val="2"/><scheme val="minor"/></font>
+                <font><u/><sz val="11"/><color theme="10"/><name val="Calibri"/><family val="2"/><scheme val="minor"/></font>
+              </fonts>
+              <fills count="3">
+                <fill><patternFill patternType="none"/></fill>
+                <fill><patternFill patternType="gray125"/></fill>
+                <fill><patternFill patternType="solid"><fgColor rgb="FFFFFF00"/><bgColor indexed="64"/></patternFill></fill>
+              </fills>
+              <borders count="1">
+                <border><left/><right/><top/><bottom/><diagonal/></border>
+              </borders>
+              <cellStyleXfs count="2">
+                <xf numFmtId="0" fontId="0" fillId="0" borderId="0"/>
+                <xf numFmtId="0" fontId="2" fillId="0" borderId="0" applyNumberFormat="0" applyFill="0" applyBorder="0" applyAlignment="0" applyProtection="0"/>
+              </cellStyleXfs>
+              <cellXfs count="4">
+                <xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/>
+                <xf numFmtId="164" fontId="1" fillId="2" borderId="0" xfId="0" applyNumberFormat="1" applyFont="1" applyFill="1"/>
+                <xf numFmtId="0" fontId="2" fillId="0" borderId="0" xfId="1"/>
+              </cellXfs>
+              <cellStyles count="2">
+                <cellStyle name="Normal" xfId="0" builtinId="0"/>
+                <cellStyle name="Hyperlink" xfId="1" builtinId="8"/>
+              </cellStyles>
+              <dxfs count="1">
+                <dxf><font><b/></font><numFmt numFmtId="164" formatCode="0.0"/></dxf>
+              </dxfs>
+              <tableStyles count="0" defaultTableStyle="TableStyleMedium9" defaultPivotStyle="PivotStyleLight16"/>
+            </styleSheet>"#;
+
+        let styles = TemplateStyles::new(template_xml.to_string());
+        assert_eq!(0, styles.xf_index(0));
+        assert_eq!(2, styles.xf_index(1));
+        assert_eq!(3, styles.xf_index(2));
+        assert_eq!(0, styles.dxf_index(0));
+
+        let got = styles.merge(generated_xml);
+        let got = xml_to_vec(&got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+              <numFmts count="2">
+                <numFmt numFmtId="170" formatCode="0.000"/>
+                <numFmt numFmtId="171" formatCode="0.0"/>
+              </numFmts>
+              <fonts count="4">
+                <font><sz val="10"/><name val="Arial"/></font>
+                <font><b/><sz val="10"/><name val="Arial"/></font>
+                <font><i/><sz val="11"/><color theme="1"/><name val="Calibri"/><family val="2"/><scheme val="minor"/></font>
+                <font><u/><sz val="11"/><color theme="10"/><name val="Calibri"/><family val="2"/><scheme val="minor"/></font>
+              </fonts>
+              <fills count="3">
+                <fill><patternFill patternType="none"/></fill>
+                <fill><patternFill patternType="gray125"/></fill>
+                <fill><patternFill patternType="solid"><fgColor rgb="FFFFFF00"/><bgColor indexed="64"/></patternFill></fill>
+              </fills>
+              <borders count="1">
+                <border><left/><right/><top/><bottom/><diagonal/></border>
+              </borders>
+              <cellStyleXfs count="2">
+                <xf numFmtId="0" fontId="0" fillId="0" borderId="0"/>
+                <xf numFmtId="0" fontId="3" fillId="0" borderId="0" applyNumberFormat="0" applyFill="0" applyBorder="0" applyAlignment="0" applyProtection="0"/>
+              </cellStyleXfs>
+              <cellXfs count="4">
+                <xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/>
+                <xf numFmtId="170" fontId="1" fillId="0" borderId="0" xfId="0" applyNumberFormat="1" applyFont="1"/>
+                <xf numFmtId="171" fontId="2" fillId="2" borderId="0" xfId="0" applyNumberFormat="1" applyFont="1" applyFill="1"/>
+                <xf numFmtId="0" fontId="3" fillId="0" borderId="0" xfId="1"/>
+              </cellXfs>
+              <cellStyles count="2">
+                <cellStyle name="Normal" xfId="0" builtinId="0"/>
+                <cellStyle name="Hyperlink" xfId="1" builtinId="8"/>
+              </cellStyles>
+              <dxfs count="1">
+                <dxf><font><b/></font><numFmt numFmtId="171" formatCode="0.0"/></dxf>
+              </dxfs>
+              <tableStyles count="0" defaultTableStyle="TableStyleMedium2" defaultPivotStyle="PivotStyleLight16"/>
+            </styleSheet>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_template_errors() {
+        let mut workbook = Workbook::from_template("tests/input/themes/technic.xlsx").unwrap();
+
+        // Template worksheets can't have new objects that require a
+        // relationship.
+        let worksheet = workbook.worksheet_from_name("Sheet1").unwrap();
+        worksheet
+            .write_url(0, 4, "https://www.rust-lang.org")
+            .unwrap();
+
+        let result = workbook.save_to_buffer();
+        assert!(matches!(result, Err(XlsxError::TemplateError(_))));
+
+        // New worksheets can.
+        let mut workbook = Workbook::from_template("tests/input/themes/technic.xlsx").unwrap();
+        let worksheet = workbook.add_worksheet();
+        worksheet
+            .write_url(0, 4, "https://www.rust-lang.org")
+            .unwrap();
+
+        let result = workbook.save_to_buffer();
+        assert!(result.is_ok());
+
+        // The file must be a valid xlsx file.
+        let result = Workbook::from_template("tests/input/themes/Technic.thmx");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_template_1904_epoch() {
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet();
+
+        let buffer = workbook.save_to_buffer().unwrap();
+        let data = read_template(std::io::Cursor::new(buffer)).unwrap();
+        assert!(!data.is_1904_date);
+
+        workbook.use_1904_epoch(true);
+
+        let buffer = workbook.save_to_buffer().unwrap();
+        let data = read_template(std::io::Cursor::new(buffer)).unwrap();
+        assert!(data.is_1904_date);
+    }
+}
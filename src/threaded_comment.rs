@@ -0,0 +1,467 @@
+// threaded_comment - A module for creating the Excel threadedComment.xml and
+// person.xml files.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+mod tests;
+
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use crate::xmlwriter::{
+    xml_data_element_only, xml_declaration, xml_empty_tag, xml_end_tag, xml_start_tag,
+    xml_start_tag_only,
+};
+use crate::{utility, ColNum, ExcelDateTime, IntoCustomDateTime, RowNum, XlsxError};
+
+/// The `ThreadedComment` struct represents a worksheet threaded comment.
+///
+/// Threaded comments are the newer style of Excel cell comment that allow
+/// conversations with replies, @mentions of other people and a resolved state.
+/// They are displayed in Excel as "Comments", while the older non-threaded
+/// version is displayed as "Notes", see [`Note`](crate::Note).
+///
+/// Threaded comments are added to a worksheet cell with the
+/// [`Worksheet::insert_threaded_comment()`](crate::Worksheet::insert_threaded_comment)
+/// method.
+///
+/// The author names of the comments, replies and mentions are stored in the
+/// workbook as a list of people. In Excel these are usually linked to an Office
+/// 365 or Active Directory user account. Since `rust_xlsxwriter` doesn't have
+/// access to that information the names are stored without an identity
+/// provider and are displayed as plain names.
+///
+/// Older versions of Excel that don't support threaded comments display a
+/// legacy "Note" with the text of the comment and replies instead.
+///
+/// ```
+/// # // This code is available in examples/doc_threaded_comment_intro.rs
+/// #
+/// use rust_xlsxwriter::{ThreadedComment, Workbook, XlsxError};
+///
+/// fn main() -> Result<(), XlsxError> {
+///     // Create a new Excel file object.
+///     let mut workbook = Workbook::new();
+///     let worksheet = workbook.add_worksheet();
+///
+///     worksheet.write(0, 0, "Budget")?;
+///     worksheet.write(0, 1, 12000)?;
+///
+///     // Create a threaded comment with a reply.
+///     let comment = ThreadedComment::new("Is this figure final? @Jane Doe")
+///         .set_author("John Smith")
+///         .add_mention("Jane Doe")
+///         .add_reply(&ThreadedComment::new("Yes, it is approved.").set_author("Jane Doe"));
+///
+///     // Add the comment to a cell.
+///     worksheet.insert_threaded_comment(0, 1, &comment)?;
+///
+///     // Save the file to disk.
+///     workbook.save("threaded_comments.xlsx")?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+#[derive(Clone)]
+pub struct ThreadedComment {
+    pub(crate) text: String,
+    pub(crate) author: String,
+    pub(crate) datetime: String,
+    pub(crate) is_resolved: bool,
+    pub(crate) mentions: Vec<String>,
+    pub(crate) replies: Vec<ThreadedComment>,
+    pub(crate) id: String,
+}
+
+impl ThreadedComment {
+    // -----------------------------------------------------------------------
+    // Public (and crate public) methods.
+    // -----------------------------------------------------------------------
+
+    /// Create a new `ThreadedComment` object to represent an Excel threaded
+    /// comment.
+    ///
+    /// The text of the comment is added in the constructor.
+    ///
+    /// # Parameters
+    ///
+    /// - `text`: The text that will appear in the comment.
+    ///
+    pub fn new(text: impl Into<String>) -> ThreadedComment {
+        ThreadedComment {
+            text: text.into(),
+            author: "Author".to_string(),
+            datetime: String::new(),
+            is_resolved: false,
+            mentions: vec![],
+            replies: vec![],
+            id: String::new(),
+        }
+    }
+
+    /// Set the comment author name.
+    ///
+    /// The author name is displayed in the comment thread. If no name is
+    /// specified, the default name "Author" will be used.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The comment author name. Must be less than or equal to the
+    ///   Excel limit of 52 characters.
+    ///
+    pub fn set_author(mut self, name: impl Into<String>) -> ThreadedComment {
+        let author = name.into();
+        if author.is_empty() || author.chars().count() > 52 {
+            eprintln!("Author name is blank or greater than Excel's limit of 52 characters.");
+            return self;
+        }
+
+        self.author = author;
+        self
+    }
+
+    /// Set the date and time of the comment.
+    ///
+    /// Excel displays the comment timestamp in the thread. The date and time
+    /// should be in UTC. If it isn't set, the current UTC time is used when the
+    /// file is saved.
+    ///
+    /// # Parameters
+    ///
+    /// - `datetime`: The comment timestamp. A type that implements
+    ///   [`IntoCustomDateTime`] such as [`ExcelDateTime`].
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates adding a threaded comment with an
+    /// explicit timestamp and resolved state.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_threaded_comment_set_datetime.rs
+    /// #
+    /// # use rust_xlsxwriter::{ExcelDateTime, ThreadedComment, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     let datetime = ExcelDateTime::from_ymd(2025, 3, 14)?.and_hms(9, 30, 0)?;
+    ///
+    ///     // Create a resolved threaded comment with a timestamp.
+    ///     let comment = ThreadedComment::new("Please check the totals.")
+    ///         .set_author("John Smith")
+    ///         .set_datetime(&datetime)
+    ///         .set_resolved(true);
+    ///
+    ///     // Add the comment to a worksheet cell.
+    ///     worksheet.insert_threaded_comment(2, 1, &comment)?;
+    /// #
+    /// #     // Save the file to disk.
+    /// #     workbook.save("threaded_comments.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_datetime(mut self, datetime: impl IntoCustomDateTime) -> ThreadedComment {
+        self.datetime = datetime.utc_datetime();
+        self
+    }
+
+    /// Mark the comment thread as resolved.
+    ///
+    /// Resolved comment threads are displayed in Excel in a greyed out state
+    /// with a "Resolved" label. This property only applies to the top level
+    /// comment and is ignored for replies.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    pub fn set_resolved(mut self, enable: bool) -> ThreadedComment {
+        self.is_resolved = enable;
+        self
+    }
+
+    /// Add an @mention of a person to the comment.
+    ///
+    /// Excel links an @mention in the comment text to a person in the workbook.
+    /// The comment text must contain the name prefixed with `@`, for example
+    /// `"Can you check this @Jane Doe"`, and the mention is added to the
+    /// first occurrence of that text. The person is added to the workbook list
+    /// of people if required.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the person to mention, without the `@` prefix.
+    ///
+    pub fn add_mention(mut self, name: impl Into<String>) -> ThreadedComment {
+        self.mentions.push(name.into());
+        self
+    }
+
+    /// Add a reply to the comment thread.
+    ///
+    /// Replies are displayed in the comment thread in the order that they are
+    /// added. The author, timestamp, text and mentions of the reply are used
+    /// but any replies to the reply itself are ignored since Excel only
+    /// supports a single level of replies.
+    ///
+    /// # Parameters
+    ///
+    /// - `reply`: A [`ThreadedComment`] to add as a reply.
+    ///
+    pub fn add_reply(mut self, reply: &ThreadedComment) -> ThreadedComment {
+        let mut reply = reply.clone();
+        reply.replies.clear();
+        reply.is_resolved = false;
+
+        self.replies.push(reply);
+        self
+    }
+
+    // Check that the comment and reply text is within Excel's limits and that
+    // any mentions refer to text in the comment.
+    pub(crate) fn validate(&self) -> Result<(), XlsxError> {
+        for comment in std::iter::once(self).chain(self.replies.iter()) {
+            if comment.text.chars().count() > 32_767 {
+                return Err(XlsxError::MaxStringLengthExceeded);
+            }
+
+            for name in &comment.mentions {
+                if comment.mention_position(name).is_none() {
+                    return Err(XlsxError::ParameterError(format!(
+                        "Mention '@{name}' not found in threaded comment text '{}'",
+                        comment.text
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Get the start index and length of an @mention in the comment text. Excel
+    // measures these in UTF-16 units.
+    pub(crate) fn mention_position(&self, name: &str) -> Option<(usize, usize)> {
+        let mention = format!("@{name}");
+        let start = self.text.find(&mention)?;
+
+        let start_index = self.text[..start].encode_utf16().count();
+        let length = mention.encode_utf16().count();
+
+        Some((start_index, length))
+    }
+
+    // Get the timestamp in the format used by Excel in the threadedComment
+    // file, like "2025-03-14T09:30:00.00".
+    pub(crate) fn excel_datetime(&self) -> String {
+        let datetime = if self.datetime.is_empty() {
+            ExcelDateTime::utc_now()
+        } else {
+            self.datetime.clone()
+        };
+
+        format!("{}.00", datetime.trim_end_matches('Z'))
+    }
+
+    // Get the text of the legacy note that Excel displays as a fallback in
+    // versions that don't support threaded comments.
+    pub(crate) fn fallback_text(&self) -> String {
+        let mut text = "[Threaded comment]\n\n\
+            Your version of Excel allows you to read this threaded comment; \
+            however, any edits to it will get removed if the file is opened in \
+            a newer version of Excel. \
+            Learn more: https://go.microsoft.com/fwlink/?linkid=870924\n\n"
+            .to_string();
+
+        text.push_str(&format!("Comment:\n    {}", self.text));
+
+        for reply in &self.replies {
+            text.push_str(&format!("\nReply:\n    {}", reply.text));
+        }
+
+        text
+    }
+}
+
+// A struct to represent the threadedCommentN.xml file for a worksheet.
+pub(crate) struct ThreadedComments {
+    pub(crate) writer: Cursor<Vec<u8>>,
+    pub(crate) comments: BTreeMap<(RowNum, ColNum), ThreadedComment>,
+    pub(crate) person_ids: BTreeMap<String, String>,
+}
+
+impl ThreadedComments {
+    // Create a new ThreadedComments struct.
+    pub(crate) fn new() -> ThreadedComments {
+        ThreadedComments {
+            writer: Cursor::new(Vec::with_capacity(2048)),
+            comments: BTreeMap::new(),
+            person_ids: BTreeMap::new(),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // XML assembly methods.
+    // -----------------------------------------------------------------------
+
+    // Assemble and generate the XML file.
+    pub(crate) fn assemble_xml_file(&mut self) {
+        xml_declaration(&mut self.writer);
+
+        // Write the ThreadedComments element.
+        let attributes = [
+            (
+                "xmlns",
+                "http://schemas.microsoft.com/office/spreadsheetml/2018/threadedcomments",
+            ),
+            (
+                "xmlns:x",
+                "http://schemas.openxmlformats.org/spreadsheetml/2006/main",
+            ),
+        ];
+
+        xml_start_tag(&mut self.writer, "ThreadedComments", &attributes);
+
+        for ((row, col), comment) in &self.comments.clone() {
+            let cell = utility::row_col_to_cell(*row, *col);
+
+            // Write the parent comment and then the replies.
+            self.write_threaded_comment(&cell, comment, None);
+
+            for reply in &comment.replies {
+                self.write_threaded_comment(&cell, reply, Some(&comment.id));
+            }
+        }
+
+        xml_end_tag(&mut self.writer, "ThreadedComments");
+    }
+
+    // Write the <threadedComment> element.
+    fn write_threaded_comment(
+        &mut self,
+        cell: &str,
+        comment: &ThreadedComment,
+        parent_id: Option<&String>,
+    ) {
+        let mut attributes = vec![
+            ("ref", cell.to_string()),
+            ("dT", comment.excel_datetime()),
+            ("personId", self.person_id(&comment.author)),
+            ("id", comment.id.clone()),
+        ];
+
+        if let Some(parent_id) = parent_id {
+            attributes.push(("parentId", parent_id.clone()));
+        } else if comment.is_resolved {
+            attributes.push(("done", "1".to_string()));
+        }
+
+        xml_start_tag(&mut self.writer, "threadedComment", &attributes);
+
+        // Write the text element.
+        xml_data_element_only(&mut self.writer, "text", &comment.text);
+
+        // Write the mentions element.
+        if !comment.mentions.is_empty() {
+            self.write_mentions(comment);
+        }
+
+        xml_end_tag(&mut self.writer, "threadedComment");
+    }
+
+    // Write the <mentions> element.
+    fn write_mentions(&mut self, comment: &ThreadedComment) {
+        xml_start_tag_only(&mut self.writer, "mentions");
+
+        for (index, name) in comment.mentions.iter().enumerate() {
+            let Some((start_index, length)) = comment.mention_position(name) else {
+                continue;
+            };
+
+            // The mention id is a variant of the comment pseudo GUID.
+            let mention_id = format!("{{3E7110E5-{:04X}-{}", index + 1, &comment.id[15..]);
+
+            let attributes = [
+                ("mentionpersonId", self.person_id(name)),
+                ("mentionId", mention_id),
+                ("startIndex", start_index.to_string()),
+                ("length", length.to_string()),
+            ];
+
+            xml_empty_tag(&mut self.writer, "mention", &attributes);
+        }
+
+        xml_end_tag(&mut self.writer, "mentions");
+    }
+
+    // Get the workbook person id for an author or mention name.
+    fn person_id(&self, name: &str) -> String {
+        self.person_ids.get(name).cloned().unwrap_or_default()
+    }
+}
+
+// A struct to represent the person.xml file that contains the list of people
+// who authored or are mentioned in threaded comments.
+pub(crate) struct Persons {
+    pub(crate) writer: Cursor<Vec<u8>>,
+    pub(crate) persons: Vec<String>,
+}
+
+impl Persons {
+    // Create a new Persons struct.
+    pub(crate) fn new() -> Persons {
+        Persons {
+            writer: Cursor::new(Vec::with_capacity(2048)),
+            persons: vec![],
+        }
+    }
+
+    // Get a pseudo GUID for a person based on their position in the list.
+    pub(crate) fn person_id(index: usize) -> String {
+        format!("{{9E450A1D-AAAA-BBBB-0000-{:012X}}}", index + 1)
+    }
+
+    // -----------------------------------------------------------------------
+    // XML assembly methods.
+    // -----------------------------------------------------------------------
+
+    // Assemble and generate the XML file.
+    pub(crate) fn assemble_xml_file(&mut self) {
+        xml_declaration(&mut self.writer);
+
+        // Write the personList element.
+        let attributes = [
+            (
+                "xmlns",
+                "http://schemas.microsoft.com/office/spreadsheetml/2018/threadedcomments",
+            ),
+            (
+                "xmlns:x",
+                "http://schemas.openxmlformats.org/spreadsheetml/2006/main",
+            ),
+        ];
+
+        xml_start_tag(&mut self.writer, "personList", &attributes);
+
+        for (index, name) in self.persons.clone().iter().enumerate() {
+            // Write the person element.
+            let attributes = [
+                ("displayName", name.clone()),
+                ("id", Self::person_id(index)),
+                ("userId", name.clone()),
+                ("providerId", "None".to_string()),
+            ];
+
+            xml_empty_tag(&mut self.writer, "person", &attributes);
+        }
+
+        xml_end_tag(&mut self.writer, "personList");
+    }
+}
@@ -0,0 +1,138 @@
+// Threaded comment unit tests.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+#[cfg(test)]
+mod threaded_comment_tests {
+
+    use std::collections::BTreeMap;
+
+    use crate::test_functions::xml_to_vec;
+    use crate::threaded_comment::{Persons, ThreadedComments};
+    use crate::{xmlwriter, ExcelDateTime, ThreadedComment, XlsxError};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_validation() {
+        let comment = ThreadedComment::new("Hello @Jane").add_mention("John");
+        let result = comment.validate();
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let reply = ThreadedComment::new("Hello").add_mention("John");
+        let comment = ThreadedComment::new("Hello").add_reply(&reply);
+        let result = comment.validate();
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let comment = ThreadedComment::new("Hello @Jane").add_mention("Jane");
+        assert!(comment.validate().is_ok());
+    }
+
+    #[test]
+    fn test_mention_position() {
+        let comment = ThreadedComment::new("Hi @Jane Doe");
+        assert_eq!(Some((3, 9)), comment.mention_position("Jane Doe"));
+
+        // Positions are in UTF-16 units.
+        let comment = ThreadedComment::new("\u{1F600} @Jane");
+        assert_eq!(Some((3, 5)), comment.mention_position("Jane"));
+
+        let comment = ThreadedComment::new("Hi Jane");
+        assert_eq!(None, comment.mention_position("Jane"));
+    }
+
+    #[test]
+    fn test_fallback_text() {
+        let comment = ThreadedComment::new("Question")
+            .add_reply(&ThreadedComment::new("Answer 1"))
+            .add_reply(&ThreadedComment::new("Answer 2"));
+
+        let expected = "[Threaded comment]\n\n\
+            Your version of Excel allows you to read this threaded comment; \
+            however, any edits to it will get removed if the file is opened in \
+            a newer version of Excel. \
+            Learn more: https://go.microsoft.com/fwlink/?linkid=870924\n\n\
+            Comment:\n    Question\n\
+            Reply:\n    Answer 1\n\
+            Reply:\n    Answer 2";
+
+        assert_eq!(expected, comment.fallback_text());
+    }
+
+    #[test]
+    fn test_assemble() {
+        let datetime = ExcelDateTime::from_ymd(2025, 3, 14)
+            .unwrap()
+            .and_hms(9, 30, 0)
+            .unwrap();
+
+        let mut reply = ThreadedComment::new("Thanks @Jane Doe")
+            .set_author("John Smith")
+            .set_datetime(&datetime)
+            .add_mention("Jane Doe")
+            .set_resolved(true);
+        reply.id = "{C033E475-AAAA-BBBB-0001-000000000002}".to_string();
+
+        let mut comment = ThreadedComment::new("Is this correct?")
+            .set_author("John Smith")
+            .set_datetime(&datetime)
+            .set_resolved(true)
+            .add_reply(&reply);
+        comment.id = "{C033E475-AAAA-BBBB-0001-000000000001}".to_string();
+
+        let mut threaded_comments = ThreadedComments::new();
+        threaded_comments.comments = BTreeMap::from([((1, 1), comment)]);
+        threaded_comments.person_ids = BTreeMap::from([
+            ("John Smith".to_string(), Persons::person_id(0)),
+            ("Jane Doe".to_string(), Persons::person_id(1)),
+        ]);
+
+        threaded_comments.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&threaded_comments.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <ThreadedComments xmlns="http://schemas.microsoft.com/office/spreadsheetml/2018/threadedcomments" xmlns:x="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+              <threadedComment ref="B2" dT="2025-03-14T09:30:00.00" personId="{9E450A1D-AAAA-BBBB-0000-000000000001}" id="{C033E475-AAAA-BBBB-0001-000000000001}" done="1">
+                <text>Is this correct?</text>
+              </threadedComment>
+              <threadedComment ref="B2" dT="2025-03-14T09:30:00.00" personId="{9E450A1D-AAAA-BBBB-0000-000000000001}" id="{C033E475-AAAA-BBBB-0001-000000000002}" parentId="{C033E475-AAAA-BBBB-0001-000000000001}">
+                <text>Thanks @Jane Doe</text>
+                <mentions>
+                  <mention mentionpersonId="{9E450A1D-AAAA-BBBB-0000-000000000002}" mentionId="{3E7110E5-0001-BBBB-0001-000000000002}" startIndex="7" length="9"/>
+                </mentions>
+              </threadedComment>
+            </ThreadedComments>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_persons() {
+        let mut persons = Persons::new();
+        persons.persons = vec!["John Smith".to_string(), "Jane Doe".to_string()];
+
+        persons.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&persons.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <personList xmlns="http://schemas.microsoft.com/office/spreadsheetml/2018/threadedcomments" xmlns:x="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+              <person displayName="John Smith" id="{9E450A1D-AAAA-BBBB-0000-000000000001}" userId="John Smith" providerId="None"/>
+              <person displayName="Jane Doe" id="{9E450A1D-AAAA-BBBB-0000-000000000002}" userId="Jane Doe" providerId="None"/>
+            </personList>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+}
@@ -34,6 +34,9 @@ use crate::ROW_MAX;
 #[cfg(feature = "serde")]
 use crate::IntoExcelDateTime;
 
+#[cfg(feature = "serde")]
+use crate::serializer::serialize_excel_datetime;
+
 #[cfg(feature = "serde")]
 use serde::Serializer;
 
@@ -448,7 +451,7 @@ pub fn serialize_datetime_to_excel<S>(
 where
     S: Serializer,
 {
-    serializer.serialize_f64(datetime.to_excel_serial_date())
+    serialize_excel_datetime(serializer, datetime.to_excel_serial_date())
 }
 
 /// Serialize an `Option` naive/civil date/time to an Excel value.
@@ -543,7 +546,7 @@ where
     S: Serializer,
 {
     match datetime {
-        Some(datetime) => serializer.serialize_f64(datetime.to_excel_serial_date()),
+        Some(datetime) => serialize_excel_datetime(serializer, datetime.to_excel_serial_date()),
         None => serializer.serialize_none(),
     }
 }
@@ -572,7 +575,7 @@ pub fn serialize_chrono_naive_to_excel<S>(
 where
     S: Serializer,
 {
-    serializer.serialize_f64(datetime.to_excel_serial_date())
+    serialize_excel_datetime(serializer, datetime.to_excel_serial_date())
 }
 
 /// Serialize an `Option` chrono naive date/time to an Excel value.
@@ -604,7 +607,7 @@ where
     S: Serializer,
 {
     match datetime {
-        Some(datetime) => serializer.serialize_f64(datetime.to_excel_serial_date()),
+        Some(datetime) => serialize_excel_datetime(serializer, datetime.to_excel_serial_date()),
         None => serializer.serialize_none(),
     }
 }
@@ -1456,6 +1459,50 @@ pub(crate) fn hash_password(password: &str) -> u16 {
     hash
 }
 
+// Hash a worksheet or workbook password using a salted and iterated SHA-512
+// hash. Based on the algorithm in ECMA-376-1:2016, Office Open XML File
+// Formats, the sheetProtection element (Part 1, §18.3.1.85). The password is
+// hashed as UTF-16LE and the iterator is appended to the hash in each round.
+#[cfg(feature = "protection_hash")]
+pub(crate) fn hash_password_sha512(password: &str, salt: &[u8], spin_count: u32) -> Vec<u8> {
+    use sha2::{Digest, Sha512};
+
+    let password: Vec<u8> = password.encode_utf16().flat_map(u16::to_le_bytes).collect();
+
+    let mut hash = Sha512::new()
+        .chain_update(salt)
+        .chain_update(&password)
+        .finalize();
+
+    for iterator in 0..spin_count {
+        hash = Sha512::new()
+            .chain_update(hash)
+            .chain_update(iterator.to_le_bytes())
+            .finalize();
+    }
+
+    hash.to_vec()
+}
+
+// Get a vector of cryptographically secure random bytes.
+#[cfg(any(feature = "encryption", feature = "protection_hash"))]
+pub(crate) fn random_bytes(size: usize) -> Result<Vec<u8>, XlsxError> {
+    let mut buffer = vec![0; size];
+
+    getrandom::fill(&mut buffer)
+        .map_err(|error| XlsxError::IoError(std::io::Error::other(error.to_string())))?;
+
+    Ok(buffer)
+}
+
+// Encode data as standard Base64 with padding.
+#[cfg(any(feature = "encryption", feature = "protection_hash"))]
+pub(crate) fn base64_encode(data: &[u8]) -> String {
+    use base64::Engine;
+
+    base64::engine::general_purpose::STANDARD.encode(data)
+}
+
 // Clone and strip the leading '=' from formulas, if present.
 pub(crate) fn formula_to_string(formula: &str) -> String {
     let mut formula = formula.to_string();
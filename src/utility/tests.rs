@@ -10,6 +10,34 @@ mod utility_tests {
     use crate::{utility, XlsxError};
     use pretty_assertions::assert_eq;
 
+    #[test]
+    #[cfg(feature = "protection_hash")]
+    fn test_hash_password_sha512() {
+        let salt: Vec<u8> = (0..16).collect();
+        let hash = utility::hash_password_sha512("password", &salt, 100_000);
+
+        assert_eq!(
+            "x01qKaF9y9cQwPxHrE46zKhOLAHXLgmWjpZRPwqjkl6tpT1Lq9JXlHzPvHxsy/q0gWkWsUumW+mgF2sVqd4VXQ==",
+            utility::base64_encode(&hash)
+        );
+
+        let salt = utility::random_bytes(16).unwrap();
+        assert_ne!(salt, utility::random_bytes(16).unwrap());
+        assert_eq!(salt.len(), 16);
+    }
+
+    #[test]
+    #[cfg(any(feature = "encryption", feature = "protection_hash"))]
+    fn test_base64_encode() {
+        assert_eq!(utility::base64_encode(b""), "");
+        assert_eq!(utility::base64_encode(b"f"), "Zg==");
+        assert_eq!(utility::base64_encode(b"fo"), "Zm8=");
+        assert_eq!(utility::base64_encode(b"foo"), "Zm9v");
+        assert_eq!(utility::base64_encode(b"foob"), "Zm9vYg==");
+        assert_eq!(utility::base64_encode(b"fooba"), "Zm9vYmE=");
+        assert_eq!(utility::base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
     #[test]
     fn test_hash_password() {
         let tests = vec![
@@ -233,19 +233,25 @@ use std::io::BufWriter;
 #[cfg(feature = "constant_memory")]
 use std::path::PathBuf;
 
+#[cfg(feature = "encryption")]
+use crate::encryption::Encryptor;
+
 use crate::error::XlsxError;
 use crate::feature_property_bag::FeaturePropertyBagTypes;
 use crate::format::Format;
+use crate::formula::evaluator::calculate_formula_results;
 use crate::packager::Packager;
 use crate::packager::PackagerOptions;
 use crate::shared_strings_table::SharedStringsTable;
+use crate::template::{read_template, Template};
 use crate::theme::{THEME_XML_2007, THEME_XML_2023};
 use crate::worksheet::Worksheet;
 
 use crate::{
-    utility, Border, Chart, ChartRange, ChartRangeCacheData, ColNum, Color, DefinedName,
-    DefinedNameType, DocProperties, Fill, Font, FontScheme, FormatPattern, Image, RowNum, Visible,
-    NUM_IMAGE_FORMATS, UNPARSED_SHEET_RANGE,
+    utility, Border, CalculationOptions, Chart, ChartRange, ChartRangeCacheData, ColNum, Color,
+    DefinedName, DefinedNameType, DocProperties, Fill, Font, FontScheme, FormatPattern, Image,
+    PivotCacheSource, PivotCacheValue, PivotTable, PivotTableSource, ProtectionHash, RowNum,
+    Visible, WorkbookView, NUM_IMAGE_FORMATS, UNPARSED_SHEET_RANGE,
 };
 
 use crate::xmlwriter::{
@@ -325,6 +331,8 @@ use crate::xmlwriter::{
 pub struct Workbook {
     pub(crate) writer: Cursor<Vec<u8>>,
     pub(crate) properties: DocProperties,
+    calculation_options: CalculationOptions,
+    workbook_view: WorkbookView,
     pub(crate) worksheets: Vec<Worksheet>,
     pub(crate) xf_formats: Vec<Format>,
     pub(crate) dxf_formats: Vec<Format>,
@@ -339,9 +347,11 @@ pub struct Workbook {
     pub(crate) vba_codename: Option<String>,
     pub(crate) is_xlsm_file: bool,
     pub(crate) has_comments: bool,
+    pub(crate) persons: Vec<String>,
     pub(crate) string_table: Arc<Mutex<SharedStringsTable>>,
     pub(crate) feature_property_bags: HashSet<FeaturePropertyBagTypes>,
     pub(crate) theme_xml: String,
+    pub(crate) template: Option<Template>,
 
     xf_indices: Arc<RwLock<HashMap<Format, u32>>>,
     dxf_indices: HashMap<Format, u32>,
@@ -350,6 +360,14 @@ pub struct Workbook {
     defined_names: Vec<DefinedName>,
     user_defined_names: Vec<DefinedName>,
     read_only_mode: u8,
+    protect_structure: bool,
+    protect_windows: bool,
+    protection_password: String,
+    protection_hash: ProtectionHash,
+    protection_salt: Vec<u8>,
+    calculate_formulas: bool,
+    use_strict_formulas: bool,
+    is_1904_date: bool,
     num_worksheets: u16,
     num_chartsheets: u16,
     use_large_file: bool,
@@ -363,6 +381,9 @@ pub struct Workbook {
 
     #[cfg(feature = "constant_memory")]
     tempdir: Option<PathBuf>,
+
+    #[cfg(feature = "encryption")]
+    open_password: String,
 }
 
 impl Default for Workbook {
@@ -384,7 +405,8 @@ impl Workbook {
     /// [`Workbook::save_to_buffer()`].
     ///
     /// **Note**: `rust_xlsxwriter` can only create new files. It cannot read or
-    /// modify existing files.
+    /// modify existing files. However, an existing file can be used as the
+    /// starting point for a new file, see [`Workbook::from_template()`].
     ///
     /// # Examples
     ///
@@ -417,6 +439,8 @@ impl Workbook {
         Workbook {
             writer,
             properties: DocProperties::new(),
+            calculation_options: CalculationOptions::new(),
+            workbook_view: WorkbookView::new(),
             font_count: 0,
             active_tab: 0,
             fill_count: 0,
@@ -424,6 +448,14 @@ impl Workbook {
             border_count: 0,
             num_formats: vec![],
             read_only_mode: 0,
+            protect_structure: false,
+            protect_windows: false,
+            protection_password: String::new(),
+            protection_hash: ProtectionHash::Legacy,
+            protection_salt: vec![],
+            calculate_formulas: false,
+            use_strict_formulas: false,
+            is_1904_date: false,
             has_hyperlink_style: false,
             worksheets: vec![],
             xf_formats: vec![],
@@ -439,6 +471,7 @@ impl Workbook {
             vba_signature: vec![],
             vba_codename: None,
             has_comments: false,
+            persons: vec![],
             num_worksheets: 0,
             num_chartsheets: 0,
             use_large_file: false,
@@ -451,10 +484,185 @@ impl Workbook {
             max_col_width: 1790,
             theme_xml: String::from(THEME_XML_2007),
             default_theme_version: String::from("124226"),
+            template: None,
 
             #[cfg(feature = "constant_memory")]
             tempdir: None,
+
+            #[cfg(feature = "encryption")]
+            open_password: String::new(),
+        }
+    }
+
+    /// Create a new Workbook object from an existing xlsx template file.
+    ///
+    /// The `Workbook::from_template()` constructor creates a new workbook based
+    /// on an existing Excel xlsx file. The worksheets, theme, styles, defined
+    /// names, drawings, charts and any other parts of the template file are
+    /// preserved and new data can be written to the existing worksheets, or to
+    /// new worksheets, before the file is saved with [`Workbook::save()`] in
+    /// the normal way. The template file itself isn't changed.
+    ///
+    /// The worksheets in the template are added to the workbook with their
+    /// existing names and can be accessed with
+    /// [`Workbook::worksheet_from_name()`] or
+    /// [`Workbook::worksheet_from_index()`]. Data written to these worksheets
+    /// is merged with the existing cell data, overwriting any cells that are
+    /// written to. Parts of the template that `rust_xlsxwriter` doesn't
+    /// understand are passed through to the output file unchanged.
+    ///
+    /// The existing cell formats in the template are retained and new formats
+    /// are added after them. However, the formats of existing cells can't be
+    /// read back so cells that are overwritten will use the format, or lack of
+    /// format, of the new data.
+    ///
+    /// Some limitations apply:
+    ///
+    /// - Only cell data and row properties can be added to the existing
+    ///   worksheets. The column, view and page setup properties of these
+    ///   worksheets are taken from the template.
+    /// - Objects that require a relationship to another part of the file, such
+    ///   as images, charts, tables, notes, hyperlinks or header/footer images,
+    ///   can only be added to new worksheets.
+    /// - Tables, pivot tables and slicers can't be added if the template
+    ///   already contains objects of the same type.
+    /// - Constant memory and low memory worksheets aren't supported.
+    /// - Templates that contain dialog sheets or Excel 4 macro sheets aren't
+    ///   supported.
+    ///
+    /// These limitations are checked and reported as an
+    /// [`XlsxError::TemplateError`] when the file is saved.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path of the xlsx template file.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::IoError`] - A wrapper for various IO errors when
+    ///   reading the template file.
+    /// - [`XlsxError::ZipError`] - The template file isn't a valid zip file.
+    /// - [`XlsxError::TemplateError`] - The template isn't a valid xlsx file
+    ///   or contains parts that aren't supported.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates creating a new workbook from a
+    /// template file that contains a chart, and updating the chart data.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_from_template.rs
+    /// #
+    /// # use rust_xlsxwriter::{Format, Workbook, XlsxError};
+    /// #
+    /// fn main() -> Result<(), XlsxError> {
+    ///     // Create a new workbook from an existing file.
+    ///     let mut workbook = Workbook::from_template("examples/template.xlsx")?;
+    ///
+    ///     // Update the chart data in the existing worksheet.
+    ///     let worksheet = workbook.worksheet_from_name("Sheet1")?;
+    ///     let data = [3, 5, 2, 6, 4];
+    ///     worksheet.write_column(0, 0, data)?;
+    ///
+    ///     // Add a new worksheet with a summary.
+    ///     let bold = Format::new().set_bold();
+    ///     let worksheet = workbook.add_worksheet().set_name("Summary")?;
+    ///     worksheet.write_with_format(0, 0, "Total", &bold)?;
+    ///     worksheet.write_formula(0, 1, "=SUM(Sheet1!A1:C5)")?;
+    ///
+    ///     // Save the file to disk.
+    ///     workbook.save("template_output.xlsx")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub fn from_template<P: AsRef<Path>>(path: P) -> Result<Workbook, XlsxError> {
+        let file = File::open(path)?;
+        let data = read_template(BufReader::new(file))?;
+
+        let mut workbook = Workbook::new();
+
+        // Add the template worksheets and chartsheets.
+        for sheet_data in data.sheets {
+            let worksheet = if sheet_data.is_chartsheet {
+                workbook.add_chartsheet()
+            } else {
+                workbook.add_worksheet()
+            };
+
+            worksheet.set_name(&sheet_data.name)?;
+
+            match sheet_data.visible {
+                Visible::Default => {}
+                Visible::Hidden => {
+                    worksheet.set_hidden(true);
+                }
+                Visible::VeryHidden => {
+                    worksheet.set_very_hidden(true);
+                }
+            }
+
+            worksheet.template = Some(sheet_data.sheet);
+        }
+
+        if let Some(worksheet) = workbook.worksheets.get_mut(data.active_tab) {
+            if worksheet.visible == Visible::Default {
+                worksheet.set_active(true);
+            }
+        }
+
+        // Convert the template defined names to the equivalent workbook
+        // defined names so that they can be sorted and written with any new
+        // names.
+        for template_name in data.defined_names {
+            let mut defined_name = DefinedName::new();
+
+            if let Some(sheet_index) = template_name.local_sheet_id {
+                let Some(worksheet) = workbook.worksheets.get(sheet_index) else {
+                    continue;
+                };
+
+                defined_name.quoted_sheet_name = utility::quote_sheet_name(&worksheet.name);
+                defined_name.name_type = match template_name.name.as_str() {
+                    "_xlnm.Print_Area" => DefinedNameType::PrintArea,
+                    "_xlnm._FilterDatabase" => DefinedNameType::Autofilter,
+                    "_xlnm.Print_Titles" => DefinedNameType::PrintTitles,
+                    _ => DefinedNameType::Local,
+                };
+            }
+
+            defined_name.name = template_name.name;
+            defined_name.range = template_name.formula;
+            defined_name.set_sort_name();
+
+            workbook.user_defined_names.push(defined_name);
+        }
+
+        if let Some(theme_xml) = data.theme_xml {
+            workbook.theme_xml = theme_xml;
+            workbook.default_theme_version = String::new();
+        }
+
+        if !data.vba_project.is_empty() {
+            workbook.vba_project = data.vba_project;
+            workbook.vba_signature = data.vba_signature;
+            workbook.is_xlsm_file = true;
+            workbook.vba_codename = Some(
+                data.vba_codename
+                    .unwrap_or_else(|| "ThisWorkbook".to_string()),
+            );
         }
+
+        // Keep the date epoch of the template so that its existing dates are
+        // displayed correctly.
+        if data.is_1904_date {
+            workbook.use_1904_epoch(true);
+        }
+
+        workbook.template = Some(data.template);
+
+        Ok(workbook)
     }
 
     /// Add a new worksheet to a workbook.
@@ -1678,6 +1886,97 @@ impl Workbook {
         self
     }
 
+    /// Set the formula calculation options for a workbook.
+    ///
+    /// The `set_calculation_options()` method is used to set the Excel
+    /// formula calculation properties of the workbook such as the calculation
+    /// mode, iterative calculation for intentional circular references and
+    /// "Precision as displayed". See [`CalculationOptions`] for details.
+    ///
+    /// # Parameters
+    ///
+    /// - `options`: A [`CalculationOptions`] struct reference.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting a workbook to manual
+    /// calculation mode.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_calculation_options.rs
+    /// #
+    /// # use rust_xlsxwriter::{CalculationMode, CalculationOptions, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     // Turn off automatic calculation.
+    ///     let options = CalculationOptions::new().set_mode(CalculationMode::Manual);
+    ///     workbook.set_calculation_options(&options);
+    ///
+    ///     // Store the formula results since they aren't recalculated when the
+    ///     // file is opened.
+    ///     workbook.calculate_formulas();
+    ///
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.write_number(0, 0, 10)?;
+    ///     worksheet.write_formula(1, 0, "=A1*2")?;
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_calculation_options(&mut self, options: &CalculationOptions) -> &mut Workbook {
+        self.calculation_options = options.clone();
+        self
+    }
+
+    /// Set the window view properties for a workbook.
+    ///
+    /// The `set_workbook_view()` method is used to set the properties of the
+    /// Excel application window that are stored in the workbook such as the
+    /// initial window position and size, the width of the worksheet tab bar,
+    /// and whether the worksheet tabs and scrollbars are displayed. See
+    /// [`WorkbookView`] for details.
+    ///
+    /// # Parameters
+    ///
+    /// - `view`: A [`WorkbookView`] struct reference.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates widening the worksheet tab bar for a
+    /// workbook with a large number of worksheets.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_workbook_view.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, WorkbookView, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     // Use 80% of the horizontal space for the worksheet tabs.
+    ///     let view = WorkbookView::new().set_tab_ratio(80.0);
+    ///     workbook.set_workbook_view(&view);
+    ///
+    ///     for _ in 0..20 {
+    ///         workbook.add_worksheet();
+    ///     }
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_workbook_view(&mut self, view: &WorkbookView) -> &mut Workbook {
+        self.workbook_view = view.clone();
+        self
+    }
+
     /// Set the default cell format for a workbook.
     ///
     /// The `rust_xlsxwriter` library uses the Excel 2007 default cell format of
@@ -2330,58 +2629,513 @@ impl Workbook {
         self
     }
 
-    /// Set the order/index for the format.
+    /// Protect the structure of the workbook.
     ///
-    /// This is currently only used in testing to ensure the same format order
-    /// as target Excel files. It can also be used in multi-threaded constant
-    /// memory programs to pre-compute the format index so that all uses of the
-    /// format only involve a `RwLock` `read()` and not a `write()`.
+    /// The `protect_structure()` method protects the structure of a workbook
+    /// to prevent users from adding, deleting, renaming, moving, hiding or
+    /// unhiding worksheets. It corresponds to the "Protect Workbook" option in
+    /// Excel.
     ///
-    /// # Parameters
+    /// This is different from worksheet protection, see
+    /// [`Worksheet::protect()`], which protects the data in a worksheet.
     ///
-    /// `format` - The [`Format`] instance to register.
+    /// See also [`Workbook::protect_with_password()`] to add a password that
+    /// is required to unprotect the structure.
     ///
-    #[doc(hidden)]
-    pub fn register_format(&mut self, format: &Format) {
-        self.format_xf_index(format);
+    /// # Examples
+    ///
+    /// The following example demonstrates protecting the structure of a
+    /// workbook so that worksheets can't be added, deleted or renamed.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_protect_structure.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let _worksheet1 = workbook.add_worksheet();
+    ///     let _worksheet2 = workbook.add_worksheet();
+    ///
+    ///     // Prevent the worksheets from being added, deleted or renamed.
+    ///     workbook.protect_structure();
+    ///
+    ///     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn protect_structure(&mut self) -> &mut Workbook {
+        self.protect_structure = true;
+        self
     }
 
-    /// Set the order/index for shared string table strings.
+    /// Protect the structure of the workbook with a password.
     ///
-    /// This is currently only used in testing to ensure the same string order
-    /// as target Excel files.
+    /// The `protect_with_password()` method is like the
+    /// [`Workbook::protect_structure()`] method, see above, except that the
+    /// user is prompted for a password to unprotect the workbook structure.
+    ///
+    /// By default the password is stored as a legacy 16 bit hash. Use
+    /// `Workbook::set_protection_hash()` to store it as a salted SHA-512 hash
+    /// instead.
+    ///
+    /// Note, the workbook data isn't encrypted. The protection is only intended
+    /// to prevent accidental changes to the file and it can be removed by
+    /// editing the file.
     ///
     /// # Parameters
     ///
-    /// `string` - The string to add to the shared string table.
-    /// `index` - The index in the shared string table.
+    /// - `password`: The password string. Note, only ascii text passwords are
+    ///   supported with the legacy hash. Passing the empty string "" is the same
+    ///   as turning on protection without a password.
     ///
-    #[doc(hidden)]
-    pub fn populate_string_table(&mut self, string: &str, index: u32) {
-        let mut string_table = self.string_table.lock().unwrap();
-
-        string_table.populate_string_index(Arc::from(string), index);
+    /// # Examples
+    ///
+    /// The following example demonstrates protecting the structure of a
+    /// workbook with a password.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_protect_with_password.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let _worksheet1 = workbook.add_worksheet();
+    ///     let _worksheet2 = workbook.add_worksheet();
+    ///
+    ///     // Protect the workbook structure with a password.
+    ///     workbook.protect_with_password("abc123");
+    ///
+    ///     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn protect_with_password(&mut self, password: &str) -> &mut Workbook {
+        self.protect_structure = true;
+        self.protection_password = password.to_string();
+        self
     }
 
-    // -----------------------------------------------------------------------
-    // Internal function/methods.
-    // -----------------------------------------------------------------------
+    /// Protect the window size and position of the workbook.
+    ///
+    /// The `protect_windows()` method prevents users from moving, resizing or
+    /// closing the workbook window. It can be used on its own or with
+    /// [`Workbook::protect_structure()`] or
+    /// [`Workbook::protect_with_password()`], in which case the password also
+    /// applies to the window protection.
+    ///
+    /// Note, this option is only supported by Excel 2007 to 2013 on Windows
+    /// and Excel for Mac 2011. It is ignored by more recent versions of Excel.
+    ///
+    pub fn protect_windows(&mut self) -> &mut Workbook {
+        self.protect_windows = true;
+        self
+    }
 
-    // Reset workbook between saves.
-    fn reset(&mut self) {
-        xmlwriter::reset(&mut self.writer);
+    /// Set the algorithm used to hash the workbook protection password.
+    ///
+    /// By default the password set with [`Workbook::protect_with_password()`]
+    /// is stored as a legacy 16 bit hash, which is supported by all versions of
+    /// Excel. The `set_protection_hash()` method can be used to store a salted
+    /// SHA-512 hash instead, like Excel 2013 and later. See [`ProtectionHash`]
+    /// for the available options.
+    ///
+    /// This method requires the `protection_hash` feature.
+    ///
+    /// # Parameters
+    ///
+    /// - `hash`: A [`ProtectionHash`] enum value.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates protecting the structure of a
+    /// workbook with a password that is stored as a SHA-512 hash.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_protection_hash.rs
+    /// #
+    /// # use rust_xlsxwriter::{ProtectionHash, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let _worksheet = workbook.add_worksheet();
+    ///
+    ///     // Protect the workbook structure with a SHA-512 password hash.
+    ///     workbook.protect_with_password("abc123");
+    ///     workbook.set_protection_hash(ProtectionHash::Sha512);
+    ///
+    ///     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    #[cfg(feature = "protection_hash")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "protection_hash")))]
+    pub fn set_protection_hash(&mut self, hash: ProtectionHash) -> &mut Workbook {
+        self.protection_hash = hash;
+        self
+    }
 
-        for worksheet in &mut self.worksheets {
-            worksheet.reset();
+    /// Encrypt the workbook with a password that is required to open it.
+    ///
+    /// The `set_open_password()` method encrypts the xlsx file so that Excel
+    /// prompts for a password when the file is opened. This is different from
+    /// worksheet protection, see [`Worksheet::protect_with_password()`], which
+    /// only prevents a user from editing the worksheet and which can be easily
+    /// removed since the data isn't encrypted.
+    ///
+    /// The file is encrypted using the ECMA-376 Agile Encryption scheme with
+    /// AES-256 and SHA-512, which is the default used by Excel 2010 and later.
+    /// The encrypted file is an OLE Compound File that contains the encrypted
+    /// xlsx data rather than a zip file. The encryption applies to all of the
+    /// save methods: [`Workbook::save()`], [`Workbook::save_to_buffer()`] and
+    /// [`Workbook::save_to_writer()`].
+    ///
+    /// Note, the encryption requires the xlsx file to be assembled in memory
+    /// before it is encrypted so it increases the memory used when saving,
+    /// even in [constant memory mode](crate::performance#constant-memory-mode).
+    ///
+    /// This method requires the `encryption` feature to be enabled.
+    ///
+    /// # Parameters
+    ///
+    /// - `password`: The password required to open the file. Excel limits
+    ///   passwords to 255 characters. An empty string turns off encryption.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::ParameterError`] - The password is longer than Excel's
+    ///   limit of 255 characters.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates creating a workbook that is encrypted
+    /// and requires a password to open it.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_set_open_password.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.write_string(0, 0, "Confidential")?;
+    ///
+    ///     // Excel will prompt for this password when the file is opened.
+    ///     workbook.set_open_password("Pa55w0rd")?;
+    ///
+    ///     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    #[cfg(feature = "encryption")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+    pub fn set_open_password(&mut self, password: &str) -> Result<&mut Workbook, XlsxError> {
+        if password.chars().count() > 255 {
+            return Err(XlsxError::ParameterError(
+                "Open password must be 255 characters or less.".to_string(),
+            ));
         }
+
+        self.open_password = password.to_string();
+
+        Ok(self)
     }
 
-    // Initialize the default format for a worksheet.
-    fn initialize_default_format(&mut self, worksheet: &mut Worksheet) {
-        worksheet.initialize_default_format(
-            &self.default_format,
-            self.default_row_height,
-            self.default_col_width,
+    /// Calculate the results of the worksheet formulas when the file is saved.
+    ///
+    /// `rust_xlsxwriter` doesn't calculate the result of a formula and instead
+    /// stores the value 0 as the formula result. It then sets a global flag in
+    /// the XLSX file to say that all formulas and functions should be
+    /// recalculated when the file is opened. This works for Excel and most
+    /// other spreadsheet applications but applications that don't recalculate
+    /// formulas, such as file viewers, mobile apps or libraries that read the
+    /// file, will display the 0 results. See the [Formula
+    /// Results](crate::Formula#formula-results) section of the `Formula`
+    /// documentation for more details.
+    ///
+    /// The `calculate_formulas()` method adds a formula evaluator that
+    /// calculates the formula results when the file is saved and stores them
+    /// in the file. The evaluator supports the Excel operators, cell and range
+    /// references, defined names and the more commonly used Excel functions
+    /// including:
+    ///
+    /// - Math and statistical functions such as `SUM()`, `AVERAGE()`,
+    ///   `ROUND()`, `SUMIFS()`, `COUNTIFS()` and `MEDIAN()`.
+    /// - Logical functions such as `IF()`, `IFERROR()`, `AND()` and `LET()`.
+    /// - Lookup functions such as `VLOOKUP()`, `INDEX()`, `MATCH()` and
+    ///   `XLOOKUP()`.
+    /// - Text functions such as `LEFT()`, `CONCAT()`, `TEXTJOIN()` and
+    ///   `TEXT()`.
+    /// - Date and time functions such as `DATE()`, `YEAR()`, `EOMONTH()` and
+    ///   `NETWORKDAYS()`.
+    /// - Dynamic array functions such as `FILTER()`, `SORT()`, `UNIQUE()` and
+    ///   `SEQUENCE()`.
+    ///
+    /// Formulas that use unsupported functions or features such as structured
+    /// table references, 3D references, `OFFSET()`, `INDIRECT()` or `LAMBDA()`
+    /// are left unchanged and keep any result set with
+    /// [`Worksheet::set_formula_result()`] or the default result. Array and
+    /// dynamic array formulas only store the result for the first cell in the
+    /// range. Formulas in worksheets that use "constant memory" mode aren't
+    /// calculated since the data has already been written.
+    ///
+    /// Excel will still recalculate the formulas when the file is opened so
+    /// any differences between the calculated results and Excel's results will
+    /// be corrected at that point.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates calculating the results of
+    /// formulas when the file is saved.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_calculate_formulas.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     // Write some data and formulas that refer to it.
+    ///     worksheet.write_column(0, 0, [10, 20, 30, 40])?;
+    ///     worksheet.write_formula(4, 0, "=SUM(A1:A4)")?;
+    ///     worksheet.write_formula(5, 0, "=AVERAGE(A1:A4)")?;
+    ///     worksheet.write_formula(6, 0, r#"=IF(A5>50,"Over","Under")"#)?;
+    ///
+    ///     // Store the calculated results, 100, 25 and "Over", in the file.
+    ///     workbook.calculate_formulas();
+    ///
+    ///     workbook.save("formulas.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn calculate_formulas(&mut self) -> &mut Workbook {
+        self.calculate_formulas = true;
+        self
+    }
+
+    /// Check the syntax of worksheet formulas when they are written.
+    ///
+    /// By default `rust_xlsxwriter` doesn't check the formulas written with
+    /// methods like [`Worksheet::write_formula()`]. A formula with an error
+    /// such as unbalanced parentheses or an invalid range will only be
+    /// reported by Excel when the file is opened, usually as a warning that
+    /// the file is corrupt and needs to be repaired.
+    ///
+    /// The `use_strict_formulas()` method turns on a strict mode where
+    /// formulas are parsed and checked, see
+    /// [`Formula::validate()`](crate::Formula::validate). The checks are:
+    ///
+    /// - Syntax errors such as unbalanced parentheses, unterminated strings or
+    ///   invalid cell references like `XFE1`. These are checked when the
+    ///   formula is written, and the worksheet method returns an error.
+    /// - Unknown functions and references to worksheets that don't exist in
+    ///   the workbook. These are checked when the workbook is saved since the
+    ///   worksheets and defined names may be added after the formula is
+    ///   written. Functions with the same name as a workbook defined name are
+    ///   allowed, for `LAMBDA()` functions.
+    ///
+    /// In each case the error is an [`XlsxError::FormulaError`] with the
+    /// worksheet name and cell location of the formula, like `Sheet1!B3`.
+    ///
+    /// The strict mode applies to cell formulas in worksheets added to the
+    /// workbook after it is turned on and to all formulas when the workbook is
+    /// saved. It doesn't apply to formulas in conditional formats, data
+    /// validations or charts.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates turning on strict formula checking
+    /// to catch formula errors when they are written.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_use_strict_formulas.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     // Turn on strict formula checking.
+    ///     workbook.use_strict_formulas(true);
+    ///
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     // Write a valid formula.
+    ///     worksheet.write_formula(0, 0, "=SUM(B1:B5)")?;
+    ///
+    ///     // Write a formula with a missing closing parenthesis.
+    ///     let result = worksheet.write_formula(1, 0, "=SUM(B1:B5");
+    ///
+    ///     if let Err(error) = result {
+    ///         // Formula error: 'Sheet1!A2: Unbalanced parentheses: ...'.
+    ///         println!("{error}");
+    ///     }
+    /// #
+    /// #     workbook.save("formulas.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn use_strict_formulas(&mut self, enable: bool) -> &mut Workbook {
+        self.use_strict_formulas = enable;
+
+        for worksheet in &mut self.worksheets {
+            worksheet.use_strict_formulas = enable;
+        }
+
+        self
+    }
+
+    /// Use the 1904 date epoch for dates and times in the workbook.
+    ///
+    /// Excel supports two date systems: the default 1900 system where the
+    /// serial date 1 is 1900-01-01 and the 1904 system where the serial date 0
+    /// is 1904-01-01. The 1904 system was the default in older versions of
+    /// Excel for Mac and it is still used in some files generated on that
+    /// platform. It is also occasionally used to allow negative time values.
+    ///
+    /// The `use_1904_epoch()` method sets the workbook to the 1904 date system.
+    /// Dates and times written with [`Worksheet::write_datetime()`],
+    /// [`Worksheet::write()`] or via Serde serialization, from
+    /// [`ExcelDateTime`](crate::ExcelDateTime) or the `chrono` and `jiff`
+    /// types, are converted to the 1904 epoch when the file is saved. The same
+    /// applies to dates in data validations and conditional formats.
+    ///
+    /// Raw serial numbers written with [`Worksheet::write_number()`] aren't
+    /// converted since they aren't known to be dates.
+    ///
+    /// Dates from 1900-01-01 to 1903-12-31 can't be represented in the 1904
+    /// epoch. Writing them to a workbook that uses the 1904 epoch returns a
+    /// [`XlsxError::DateTimeRangeError`] error, as does saving a workbook that
+    /// contains them. Times without a date part aren't affected.
+    ///
+    /// Note, in constant memory mode, see
+    /// [`Workbook::add_worksheet_with_constant_memory()`], the rows are written
+    /// to disk as the data is added so this method should be called before
+    /// any dates are written.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting a workbook to use the 1904
+    /// date epoch.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_workbook_use_1904_epoch.rs
+    /// #
+    /// # use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    ///     let mut workbook = Workbook::new();
+    ///
+    ///     // Use the 1904 date system.
+    ///     workbook.use_1904_epoch(true);
+    ///
+    ///     let worksheet = workbook.add_worksheet();
+    ///     let date_format = Format::new().set_num_format("yyyy-mm-dd");
+    ///
+    ///     // The date is stored as the 1904 serial number 43830.
+    ///     let date = ExcelDateTime::from_ymd(2024, 1, 1)?;
+    ///     worksheet.write_datetime_with_format(0, 0, &date, &date_format)?;
+    /// #
+    /// #     workbook.save("workbook.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn use_1904_epoch(&mut self, enable: bool) -> &mut Workbook {
+        self.is_1904_date = enable;
+
+        for worksheet in &mut self.worksheets {
+            worksheet.is_1904_date = enable;
+        }
+
+        self
+    }
+
+    /// Set the order/index for the format.
+    ///
+    /// This is currently only used in testing to ensure the same format order
+    /// as target Excel files. It can also be used in multi-threaded constant
+    /// memory programs to pre-compute the format index so that all uses of the
+    /// format only involve a `RwLock` `read()` and not a `write()`.
+    ///
+    /// # Parameters
+    ///
+    /// `format` - The [`Format`] instance to register.
+    ///
+    #[doc(hidden)]
+    pub fn register_format(&mut self, format: &Format) {
+        self.format_xf_index(format);
+    }
+
+    /// Set the order/index for shared string table strings.
+    ///
+    /// This is currently only used in testing to ensure the same string order
+    /// as target Excel files.
+    ///
+    /// # Parameters
+    ///
+    /// `string` - The string to add to the shared string table.
+    /// `index` - The index in the shared string table.
+    ///
+    #[doc(hidden)]
+    pub fn populate_string_table(&mut self, string: &str, index: u32) {
+        let mut string_table = self.string_table.lock().unwrap();
+
+        string_table.populate_string_index(Arc::from(string), index);
+    }
+
+    // -----------------------------------------------------------------------
+    // Internal function/methods.
+    // -----------------------------------------------------------------------
+
+    // Reset workbook between saves.
+    fn reset(&mut self) {
+        xmlwriter::reset(&mut self.writer);
+
+        for worksheet in &mut self.worksheets {
+            worksheet.reset();
+        }
+    }
+
+    // Initialize the default format for a worksheet.
+    fn initialize_default_format(&mut self, worksheet: &mut Worksheet) {
+        // Also pass on the workbook formula checking mode and date epoch.
+        worksheet.use_strict_formulas = self.use_strict_formulas;
+        worksheet.is_1904_date = self.is_1904_date;
+
+        worksheet.initialize_default_format(
+            &self.default_format,
+            self.default_row_height,
+            self.default_col_width,
             self.max_digit_width,
             self.cell_padding,
             self.max_col_width,
@@ -2469,7 +3223,10 @@ impl Workbook {
 
         // Check that chartsheets have a chart.
         for worksheet in &self.worksheets {
-            if worksheet.is_chartsheet && worksheet.charts.is_empty() {
+            if worksheet.is_chartsheet
+                && worksheet.charts.is_empty()
+                && worksheet.template.is_none()
+            {
                 return Err(XlsxError::ChartError(format!(
                     "Chartsheet '{}' doesn't contain a chart",
                     worksheet.name
@@ -2477,12 +3234,28 @@ impl Workbook {
             }
         }
 
+        // Generate the random salts for SHA-512 protection password hashes.
+        #[cfg(feature = "protection_hash")]
+        self.prepare_protection_salts()?;
+
+        // Set the date epoch for all the worksheets, including any that were
+        // created separately and pushed to the workbook.
+        for worksheet in &mut self.worksheets {
+            worksheet.is_1904_date = self.is_1904_date;
+            worksheet.prepare_1904_dates()?;
+        }
+
         // Write any Tables associated with serialization areas.
         #[cfg(feature = "serde")]
         for worksheet in &mut self.worksheets {
             worksheet.store_serialized_tables()?;
         }
 
+        // Check the worksheet formulas in strict mode.
+        if self.use_strict_formulas {
+            self.check_strict_formulas()?;
+        }
+
         // Convert any worksheet local formats to workbook/global formats. At
         // the worksheet level each unique format will have an index like 0, 1,
         // 2, etc., starting from 0 for each worksheet. However, at a workbook
@@ -2528,6 +3301,17 @@ impl Workbook {
 
         drop(xf_indices);
 
+        // The workbook formats are stored after the formats in a template file
+        // so the global indices need to be offset to match.
+        if let Some(template) = &self.template {
+            for index in worksheet_xf_indices.iter_mut().flatten() {
+                *index = template.styles.xf_index(*index);
+            }
+            for index in worksheet_dxf_indices.iter_mut().flatten() {
+                *index = template.styles.dxf_index(*index);
+            }
+        }
+
         // Map worksheet/local format indices to the workbook/global values.
         for (i, worksheet) in self.worksheets.iter_mut().enumerate() {
             worksheet.set_global_xf_indices(&worksheet_xf_indices[i]);
@@ -2558,24 +3342,45 @@ impl Workbook {
         // Generate a global array of embedded images from the worksheets.
         self.prepare_embedded_images();
 
+        // Prepare worksheet tables. This needs to be done before the slicers
+        // are linked to the table names and ids.
+        self.prepare_tables();
+
+        // Link the slicers to their tables and set the slicer names. This needs
+        // to be done before the slicers are added to the drawings.
+        self.prepare_slicers()?;
+
         // Convert the images in the workbooks into drawing files and rel links.
         self.prepare_drawings();
 
+        // Prepare the threaded comments and their fallback notes. This needs to
+        // be done before the notes are converted to VML.
+        self.prepare_threaded_comments();
+
         // Prepare the worksheet VML elements such as buttons and header images.
         self.prepare_vml();
 
+        // Calculate the formula results, if required. This needs to be done
+        // before the chart caches are filled so that they use the results.
+        if self.calculate_formulas {
+            self.prepare_formula_results();
+        }
+
         // Fill the chart data caches from worksheet data.
         self.prepare_chart_cache_data()?;
 
         // Prepare the formats for writing with styles.rs.
         self.prepare_format_properties();
 
-        // Prepare worksheet tables.
-        self.prepare_tables();
+        // Prepare worksheet pivot tables and their data caches.
+        self.prepare_pivot_tables()?;
 
         // Check the table and defined names for duplicates.
         self.prepare_names()?;
 
+        // Check that the workbook data can be merged with the template file.
+        self.prepare_template()?;
+
         // Update the shared string table in each worksheet.
         for worksheet in &mut self.worksheets {
             if !worksheet.has_workbook_global_sst {
@@ -2588,6 +3393,22 @@ impl Workbook {
         let mut package_options = PackagerOptions::new();
         package_options = self.set_package_options(package_options)?;
 
+        // Assemble the xlsx file in memory and encrypt it, if required.
+        #[cfg(feature = "encryption")]
+        if !self.open_password.is_empty() {
+            let mut xlsx_data = vec![];
+            let packager = Packager::new(Cursor::new(&mut xlsx_data), self.use_large_file);
+            packager.assemble_file(self, &package_options)?;
+
+            let mut encryptor = Encryptor::new(&self.open_password)?;
+            let encrypted_data = encryptor.encrypt(&xlsx_data)?;
+
+            let mut writer = writer;
+            writer.write_all(&encrypted_data)?;
+
+            return Ok(());
+        }
+
         // Create the Packager object that will assemble the zip/xlsx file.
         let packager = Packager::new(writer, self.use_large_file);
         packager.assemble_file(self, &package_options)?;
@@ -2643,6 +3464,34 @@ impl Workbook {
         }
     }
 
+    // Collect the people who author or are mentioned in threaded comments into
+    // the workbook list of persons, and set up the worksheet threaded comments.
+    fn prepare_threaded_comments(&mut self) {
+        let mut persons: Vec<String> = vec![];
+        let mut threaded_comment_id = 1;
+
+        for worksheet in &mut self.worksheets {
+            if worksheet.threaded_comments.is_empty() {
+                continue;
+            }
+
+            for comment in worksheet.threaded_comments.values() {
+                for comment in std::iter::once(comment).chain(comment.replies.iter()) {
+                    for name in std::iter::once(&comment.author).chain(comment.mentions.iter()) {
+                        if !persons.contains(name) {
+                            persons.push(name.clone());
+                        }
+                    }
+                }
+            }
+
+            worksheet.prepare_threaded_comments(threaded_comment_id);
+            threaded_comment_id += 1;
+        }
+
+        self.persons = persons;
+    }
+
     // Convert any embedded images in the worksheets to a global reference. Each
     // worksheet will have a local index to an embedded cell image. We need to
     // map these local references to a workbook/global id that takes into
@@ -2681,8 +3530,10 @@ impl Workbook {
     // Convert the images in the workbooks into drawing files and rel links.
     fn prepare_drawings(&mut self) {
         let mut chart_id = 1;
+        let mut chart_ex_id = 1;
         let mut drawing_id = 1;
         let mut shape_id = 1;
+        let mut slicer_id = 1;
         let mut image_id = self.embedded_images.len() as u32;
 
         // These are the image ids for each unique image file.
@@ -2715,8 +3566,7 @@ impl Workbook {
             }
 
             if !worksheet.charts.is_empty() {
-                worksheet.prepare_worksheet_charts(chart_id, drawing_id);
-                chart_id += worksheet.charts.len() as u32;
+                worksheet.prepare_worksheet_charts(&mut chart_id, &mut chart_ex_id, drawing_id);
             }
 
             if !worksheet.shapes.is_empty() {
@@ -2724,10 +3574,16 @@ impl Workbook {
                 shape_id += worksheet.shapes.len() as u32;
             }
 
+            if !worksheet.slicers.is_empty() {
+                worksheet.prepare_worksheet_slicers(slicer_id, drawing_id);
+                slicer_id += 1;
+            }
+
             // Increase the drawing number/id for image/chart file.
             if !worksheet.images.is_empty()
                 || !worksheet.charts.is_empty()
                 || !worksheet.shapes.is_empty()
+                || !worksheet.slicers.is_empty()
             {
                 drawing_id += 1;
             }
@@ -2738,6 +3594,98 @@ impl Workbook {
         }
     }
 
+    // Generate the random salts for SHA-512 protection password hashes. The
+    // salts are created before the file is written so that any error from the
+    // random number generator can be returned.
+    #[cfg(feature = "protection_hash")]
+    fn prepare_protection_salts(&mut self) -> Result<(), XlsxError> {
+        if self.protection_hash == ProtectionHash::Sha512 {
+            self.protection_salt = utility::random_bytes(16)?;
+        }
+
+        for worksheet in &mut self.worksheets {
+            worksheet.prepare_protection_salts()?;
+        }
+
+        Ok(())
+    }
+
+    // Link each slicer to the id and column of its source table and set the
+    // unique slicer and slicer cache names. The table names and ids are set in
+    // prepare_tables().
+    fn prepare_slicers(&mut self) -> Result<(), XlsxError> {
+        if self.worksheets.iter().all(|ws| ws.slicers.is_empty()) {
+            return Ok(());
+        }
+
+        // Map the lowercase table names to the table id and column names.
+        let mut tables: HashMap<String, (u32, Vec<String>)> = HashMap::new();
+        for worksheet in &self.worksheets {
+            for table in &worksheet.tables {
+                let columns = table
+                    .columns
+                    .iter()
+                    .map(|column| column.name.to_lowercase())
+                    .collect();
+
+                tables.insert(table.name.to_lowercase(), (table.index, columns));
+            }
+        }
+
+        let mut slicer_names: HashMap<String, u32> = HashMap::new();
+        let mut cache_names: HashMap<String, u32> = HashMap::new();
+
+        for worksheet in &mut self.worksheets {
+            for slicer in worksheet.slicers.values_mut() {
+                let Some((table_id, columns)) = tables.get(&slicer.table_name.to_lowercase())
+                else {
+                    return Err(XlsxError::SlicerError(format!(
+                        "Unknown table name '{}' in slicer",
+                        slicer.table_name
+                    )));
+                };
+
+                let Some(position) = columns
+                    .iter()
+                    .position(|name| *name == slicer.column_name.to_lowercase())
+                else {
+                    return Err(XlsxError::SlicerError(format!(
+                        "Unknown column name '{}' in slicer table '{}'",
+                        slicer.column_name, slicer.table_name
+                    )));
+                };
+
+                slicer.table_id = *table_id;
+                slicer.table_column = position as u16 + 1;
+
+                // Slicer names are based on the column name with a number
+                // suffix for duplicates, like Excel.
+                let count = slicer_names
+                    .entry(slicer.column_name.to_lowercase())
+                    .or_insert(0);
+                slicer.name = if *count == 0 {
+                    slicer.column_name.clone()
+                } else {
+                    format!("{} {count}", slicer.column_name)
+                };
+                *count += 1;
+
+                // Slicer cache names are also defined names so they use a
+                // different naming scheme.
+                let base_name = slicer.cache_base_name();
+                let count = cache_names.entry(base_name.to_lowercase()).or_insert(0);
+                slicer.cache_name = if *count == 0 {
+                    base_name
+                } else {
+                    format!("{base_name}{count}")
+                };
+                *count += 1;
+            }
+        }
+
+        Ok(())
+    }
+
     // Prepare and check each table in the workbook.
     fn prepare_tables(&mut self) {
         let mut table_id = 1;
@@ -2751,6 +3699,115 @@ impl Workbook {
         }
     }
 
+    // Set the pivot table ids and rel linkages and create the pivot caches from
+    // the source data. The source data may not be in the same worksheet as the
+    // pivot table so we need to do the lookup at the workbook level.
+    fn prepare_pivot_tables(&mut self) -> Result<(), XlsxError> {
+        let mut pivot_table_id = 1;
+
+        for worksheet in &mut self.worksheets {
+            if !worksheet.pivot_tables.is_empty() {
+                pivot_table_id = worksheet.prepare_worksheet_pivot_tables(pivot_table_id);
+            }
+        }
+
+        for sheet_index in 0..self.worksheets.len() {
+            if self.worksheets[sheet_index].pivot_tables.is_empty() {
+                continue;
+            }
+
+            let mut pivot_tables = std::mem::take(&mut self.worksheets[sheet_index].pivot_tables);
+
+            let result = pivot_tables.iter_mut().try_for_each(|pivot_table| {
+                let (source, data) = self.pivot_table_source_data(pivot_table)?;
+                pivot_table.initialize(source, data)
+            });
+
+            self.worksheets[sheet_index].pivot_tables = pivot_tables;
+            result?;
+        }
+
+        Ok(())
+    }
+
+    // Get the source reference and the source data, including the header row,
+    // for a pivot table.
+    fn pivot_table_source_data(
+        &self,
+        pivot_table: &PivotTable,
+    ) -> Result<(PivotCacheSource, Vec<Vec<PivotCacheValue>>), XlsxError> {
+        match &pivot_table.source {
+            PivotTableSource::None => Err(XlsxError::PivotTableError(
+                "Pivot table doesn't have a source range or table".to_string(),
+            )),
+            PivotTableSource::Range(range) => {
+                let (sheet_name, first_row, first_col, last_row, last_col) = range.key();
+
+                let Some(worksheet) = self.worksheets.iter().find(|ws| ws.name == sheet_name)
+                else {
+                    let error = format!(
+                        "Unknown worksheet name '{sheet_name}' in pivot table range '{}'",
+                        range.error_range()
+                    );
+                    return Err(XlsxError::UnknownWorksheetNameOrIndex(error));
+                };
+
+                if worksheet.use_constant_memory {
+                    return Err(XlsxError::PivotTableError(format!(
+                        "Pivot table source worksheet '{sheet_name}' cannot use constant memory mode"
+                    )));
+                }
+
+                let source = PivotCacheSource::Range(
+                    sheet_name,
+                    utility::cell_range(first_row, first_col, last_row, last_col),
+                );
+                let data = worksheet.get_pivot_cache_data(first_row, first_col, last_row, last_col);
+
+                Ok((source, data))
+            }
+            PivotTableSource::Table(name) => {
+                for worksheet in &self.worksheets {
+                    for table in &worksheet.tables {
+                        if table.name.to_lowercase() != name.to_lowercase() {
+                            continue;
+                        }
+
+                        if !table.show_header_row {
+                            return Err(XlsxError::PivotTableError(format!(
+                                "Pivot table source table '{name}' must have a header row"
+                            )));
+                        }
+
+                        if worksheet.use_constant_memory {
+                            return Err(XlsxError::PivotTableError(format!(
+                                "Pivot table source worksheet '{}' cannot use constant memory mode",
+                                worksheet.name
+                            )));
+                        }
+
+                        let range = &table.cell_range;
+                        let last_row = range.last_row - u32::from(table.show_total_row);
+
+                        let source = PivotCacheSource::Table(table.name.clone());
+                        let data = worksheet.get_pivot_cache_data(
+                            range.first_row,
+                            range.first_col,
+                            last_row,
+                            range.last_col,
+                        );
+
+                        return Ok((source, data));
+                    }
+                }
+
+                Err(XlsxError::PivotTableError(format!(
+                    "Unknown table name '{name}' in pivot table source"
+                )))
+            }
+        }
+    }
+
     // Check the Table and Defined names in the workbook for duplicates. Defined
     // names can be duplicated across scopes so "Sales", "Sheet1!Sales" and
     // "Sheet2!Sales" are all valid within the same workbook. Table names,
@@ -2791,9 +3848,101 @@ impl Workbook {
         Ok(())
     }
 
+    // Check for workbook and worksheet features that can't be combined with a
+    // template file.
+    fn prepare_template(&self) -> Result<(), XlsxError> {
+        let Some(template) = &self.template else {
+            return Ok(());
+        };
+
+        for worksheet in &self.worksheets {
+            if worksheet.use_constant_memory || worksheet.has_workbook_global_xfs {
+                return Err(XlsxError::TemplateError(format!(
+                    "Worksheet '{}' uses constant or low memory mode which isn't supported with templates",
+                    worksheet.name
+                )));
+            }
+
+            if worksheet.template.is_some()
+                && (worksheet.has_relationships()
+                    || !worksheet.hyperlinks.is_empty()
+                    || !worksheet.drawing_relationships.is_empty()
+                    || !worksheet.comment_relationships.is_empty()
+                    || !worksheet.threaded_comment_relationships.is_empty()
+                    || !worksheet.vml_drawing_relationships.is_empty())
+            {
+                return Err(XlsxError::TemplateError(format!(
+                    "Objects such as images, charts, tables, notes or hyperlinks can't be added to template worksheet '{}'",
+                    worksheet.name
+                )));
+            }
+        }
+
+        let has_pivot_tables = self.worksheets.iter().any(|ws| !ws.pivot_tables.is_empty());
+        if has_pivot_tables && !template.pivot_caches.is_empty() {
+            return Err(XlsxError::TemplateError(
+                "Pivot tables can't be added to a template file that contains pivot tables"
+                    .to_string(),
+            ));
+        }
+
+        let has_slicers = self.worksheets.iter().any(|ws| !ws.slicers.is_empty());
+        if has_slicers && template.has_slicer_caches() {
+            return Err(XlsxError::TemplateError(
+                "Slicers can't be added to a template file that contains slicers".to_string(),
+            ));
+        }
+
+        let has_tables = self.worksheets.iter().any(|ws| !ws.tables.is_empty());
+        if has_tables && template.has_tables() {
+            return Err(XlsxError::TemplateError(
+                "Tables can't be added to a template file that contains tables".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
     // Add worksheet number/string cache data to chart ranges. This isn't
     // strictly necessary, but it helps non-Excel apps to render charts
     // correctly.
+    // Check the worksheet formulas for syntax errors, unknown functions and
+    // references to unknown worksheets. Defined names are allowed as function
+    // names since they can refer to LAMBDA() functions.
+    fn check_strict_formulas(&self) -> Result<(), XlsxError> {
+        let sheet_names: Vec<&str> = self
+            .worksheets
+            .iter()
+            .map(|worksheet| worksheet.name.as_str())
+            .collect();
+
+        let function_names: Vec<&str> = self
+            .user_defined_names
+            .iter()
+            .map(|defined_name| defined_name.name.as_str())
+            .collect();
+
+        for worksheet in &self.worksheets {
+            worksheet.check_strict_formulas(&sheet_names, &function_names)?;
+        }
+
+        Ok(())
+    }
+
+    // Calculate the results of the worksheet formulas and store them in the
+    // formula cells.
+    fn prepare_formula_results(&mut self) {
+        let results = calculate_formula_results(
+            &self.worksheets,
+            &self.user_defined_names,
+            self.is_1904_date,
+        );
+
+        for (sheet, row, col, result) in results {
+            self.worksheets[sheet].set_formula_result(row, col, result);
+        }
+    }
+
     fn prepare_chart_cache_data(&mut self) -> Result<(), XlsxError> {
         // First build up a hash of the chart data ranges. The data may not be
         // in the same worksheet as the chart so we need to do the lookup at the
@@ -2817,21 +3966,7 @@ impl Workbook {
         }
 
         // Populate the caches with data from the worksheet ranges.
-        for (key, cache) in &mut chart_caches {
-            if let Ok(worksheet) = self.worksheet_from_name(&key.0) {
-                *cache = worksheet.get_cache_data(key.1, key.2, key.3, key.4);
-            } else {
-                let sheet_name = key.0.clone();
-
-                if sheet_name != UNPARSED_SHEET_RANGE {
-                    let range = utility::chart_range_abs(&key.0, key.1, key.2, key.3, key.4);
-                    let error =
-                        format!("Unknown worksheet name '{sheet_name}' in chart range '{range}'");
-
-                    return Err(XlsxError::UnknownWorksheetNameOrIndex(error));
-                }
-            }
-        }
+        self.populate_chart_caches(&mut chart_caches)?;
 
         // Fill the caches back into the chart ranges.
         for worksheet in &mut self.worksheets {
@@ -2849,6 +3984,55 @@ impl Workbook {
         Ok(())
     }
 
+    // Populate the chart cache data for a standalone chart, such as a chart
+    // that is being rendered outside of a worksheet.
+    pub(crate) fn update_chart_cache_data(&self, chart: &mut Chart) -> Result<(), XlsxError> {
+        let mut chart_caches: HashMap<
+            (String, RowNum, ColNum, RowNum, ColNum),
+            ChartRangeCacheData,
+        > = HashMap::new();
+
+        Self::insert_chart_ranges_to_cache(chart, &mut chart_caches);
+
+        if let Some(chart) = &chart.combined_chart {
+            Self::insert_chart_ranges_to_cache(chart, &mut chart_caches);
+        }
+
+        self.populate_chart_caches(&mut chart_caches)?;
+
+        Self::update_chart_ranges_from_cache(chart, &mut chart_caches);
+
+        if let Some(chart) = &mut chart.combined_chart {
+            Self::update_chart_ranges_from_cache(chart, &mut chart_caches);
+        }
+
+        Ok(())
+    }
+
+    // Populate the chart range caches with data from the worksheet ranges.
+    fn populate_chart_caches(
+        &self,
+        chart_caches: &mut HashMap<(String, RowNum, ColNum, RowNum, ColNum), ChartRangeCacheData>,
+    ) -> Result<(), XlsxError> {
+        for (key, cache) in chart_caches {
+            if let Some(worksheet) = self.worksheets.iter().find(|ws| ws.name == key.0) {
+                *cache = worksheet.get_cache_data(key.1, key.2, key.3, key.4);
+            } else {
+                let sheet_name = key.0.clone();
+
+                if sheet_name != UNPARSED_SHEET_RANGE {
+                    let range = utility::chart_range_abs(&key.0, key.1, key.2, key.3, key.4);
+                    let error =
+                        format!("Unknown worksheet name '{sheet_name}' in chart range '{range}'");
+
+                    return Err(XlsxError::UnknownWorksheetNameOrIndex(error));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     // Insert all the various chart ranges into the lookup range cache.
     fn insert_chart_ranges_to_cache(
         chart: &Chart,
@@ -2857,11 +4041,13 @@ impl Workbook {
         Self::insert_to_chart_cache(&chart.title.range, chart_caches);
         Self::insert_to_chart_cache(&chart.x_axis.title.range, chart_caches);
         Self::insert_to_chart_cache(&chart.y_axis.title.range, chart_caches);
+        Self::insert_to_chart_cache(&chart.z_axis.title.range, chart_caches);
 
         for series in &chart.series {
             Self::insert_to_chart_cache(&series.title.range, chart_caches);
             Self::insert_to_chart_cache(&series.value_range, chart_caches);
             Self::insert_to_chart_cache(&series.category_range, chart_caches);
+            Self::insert_to_chart_cache(&series.bubble_size_range, chart_caches);
 
             for data_label in &series.custom_data_labels {
                 Self::insert_to_chart_cache(&data_label.title.range, chart_caches);
@@ -2887,11 +4073,13 @@ impl Workbook {
         Self::update_range_cache(&mut chart.title.range, chart_caches);
         Self::update_range_cache(&mut chart.x_axis.title.range, chart_caches);
         Self::update_range_cache(&mut chart.y_axis.title.range, chart_caches);
+        Self::update_range_cache(&mut chart.z_axis.title.range, chart_caches);
 
         for series in &mut chart.series {
             Self::update_range_cache(&mut series.title.range, chart_caches);
             Self::update_range_cache(&mut series.value_range, chart_caches);
             Self::update_range_cache(&mut series.category_range, chart_caches);
+            Self::update_range_cache(&mut series.bubble_size_range, chart_caches);
 
             for data_label in &mut series.custom_data_labels {
                 if let Some(cache) = chart_caches.get(&data_label.title.range.key()) {
@@ -3205,22 +4393,49 @@ impl Workbook {
                 package_options.num_drawings += 1;
             }
 
-            if !worksheet.charts.is_empty() {
-                package_options.num_charts += worksheet.charts.len() as u16;
+            for chart in worksheet.charts.values() {
+                if chart.is_chart_ex() {
+                    package_options.num_chart_exs += 1;
+                } else {
+                    package_options.num_charts += 1;
+                }
             }
 
             if !worksheet.tables.is_empty() {
                 package_options.num_tables += worksheet.tables.len() as u16;
             }
 
+            if !worksheet.pivot_tables.is_empty() {
+                package_options.num_pivot_tables += worksheet.pivot_tables.len() as u16;
+            }
+
+            // Slicer caches are stored as a category of defined name.
+            if !worksheet.slicers.is_empty() {
+                package_options.num_slicer_parts += 1;
+                package_options.num_slicer_caches += worksheet.slicers.len() as u16;
+
+                for slicer in worksheet.slicers.values() {
+                    let mut defined_name = DefinedName::new();
+                    defined_name.name.clone_from(&slicer.cache_name);
+                    defined_name.range = "#N/A".to_string();
+                    defined_name.set_sort_name();
+                    defined_names.push(defined_name);
+                }
+            }
+
             if !worksheet.notes.is_empty() {
                 package_options.num_comments += 1;
             }
 
+            if !worksheet.threaded_comments.is_empty() {
+                package_options.num_threaded_comments += 1;
+            }
+
             // Store the autofilter areas which are a category of defined name.
             if worksheet.autofilter_defined_name.in_use {
                 let mut defined_name = worksheet.autofilter_defined_name.clone();
                 defined_name.initialize(&quoted_sheet_name);
+                Self::remove_template_name(&mut defined_names, &defined_name);
                 defined_names.push(defined_name);
             }
 
@@ -3228,6 +4443,7 @@ impl Workbook {
             if worksheet.print_area_defined_name.in_use {
                 let mut defined_name = worksheet.print_area_defined_name.clone();
                 defined_name.initialize(&quoted_sheet_name);
+                Self::remove_template_name(&mut defined_names, &defined_name);
                 defined_names.push(defined_name);
             }
 
@@ -3236,6 +4452,7 @@ impl Workbook {
             if worksheet.repeat_row_cols_defined_name.in_use {
                 let mut defined_name = worksheet.repeat_row_cols_defined_name.clone();
                 defined_name.initialize(&quoted_sheet_name);
+                Self::remove_template_name(&mut defined_names, &defined_name);
                 defined_names.push(defined_name);
             }
 
@@ -3282,6 +4499,15 @@ impl Workbook {
         Ok(package_options)
     }
 
+    // Remove any defined name from a template file, such as a print area, that
+    // is replaced by an equivalent worksheet defined name.
+    fn remove_template_name(defined_names: &mut Vec<DefinedName>, defined_name: &DefinedName) {
+        defined_names.retain(|name| {
+            name.name() != defined_name.name()
+                || name.quoted_sheet_name != defined_name.quoted_sheet_name
+        });
+    }
+
     // -----------------------------------------------------------------------
     // XML assembly methods.
     // -----------------------------------------------------------------------
@@ -3304,12 +4530,24 @@ impl Workbook {
         // Write the workbookPr element.
         self.write_workbook_pr();
 
+        // Write the workbookProtection element.
+        if self.protect_structure || self.protect_windows {
+            self.write_workbook_protection();
+        }
+
         // Write the bookViews element.
         self.write_book_views();
 
         // Write the sheets element.
         self.write_sheets();
 
+        // Write the externalReferences element from a template file.
+        if let Some(template) = &self.template {
+            if !template.external_references.is_empty() {
+                self.write_external_references();
+            }
+        }
+
         // Write the definedNames element.
         if !self.defined_names.is_empty() {
             self.write_defined_names();
@@ -3318,6 +4556,26 @@ impl Workbook {
         // Write the calcPr element.
         self.write_calc_pr();
 
+        // Write the pivotCaches element.
+        if self.worksheets.iter().any(|ws| !ws.pivot_tables.is_empty())
+            || self
+                .template
+                .as_ref()
+                .is_some_and(|template| !template.pivot_caches.is_empty())
+        {
+            self.write_pivot_caches();
+        }
+
+        // Write the extLst element.
+        if self.worksheets.iter().any(|ws| !ws.slicers.is_empty())
+            || self
+                .template
+                .as_ref()
+                .is_some_and(|template| !template.extensions.is_empty())
+        {
+            self.write_extensions();
+        }
+
         // Close the workbook tag.
         xml_end_tag(&mut self.writer, "workbook");
     }
@@ -3355,6 +4613,25 @@ impl Workbook {
         xml_empty_tag(&mut self.writer, "fileSharing", &attributes);
     }
 
+    // Write the <workbookProtection> element.
+    fn write_workbook_protection(&mut self) {
+        let mut attributes = self.protection_hash.password_attributes(
+            &self.protection_password,
+            true,
+            &self.protection_salt,
+        );
+
+        if self.protect_structure {
+            attributes.push(("lockStructure", "1".to_string()));
+        }
+
+        if self.protect_windows {
+            attributes.push(("lockWindows", "1".to_string()));
+        }
+
+        xml_empty_tag(&mut self.writer, "workbookProtection", &attributes);
+    }
+
     // Write the <workbookPr> element.
     fn write_workbook_pr(&mut self) {
         let mut attributes = vec![];
@@ -3363,6 +4640,10 @@ impl Workbook {
             attributes.push(("codeName", codename.clone()));
         }
 
+        if self.is_1904_date {
+            attributes.push(("date1904", "1".to_string()));
+        }
+
         if !self.default_theme_version.is_empty() {
             attributes.push(("defaultThemeVersion", self.default_theme_version.clone()));
         }
@@ -3382,12 +4663,7 @@ impl Workbook {
 
     // Write the <workbookView> element.
     fn write_workbook_view(&mut self) {
-        let mut attributes = vec![
-            ("xWindow", "240".to_string()),
-            ("yWindow", "15".to_string()),
-            ("windowWidth", "16095".to_string()),
-            ("windowHeight", "9660".to_string()),
-        ];
+        let mut attributes = self.workbook_view.attributes();
 
         // Store the firstSheet attribute when it isn't the first sheet.
         if self.first_sheet > 0 {
@@ -3469,8 +4745,117 @@ impl Workbook {
 
     // Write the <calcPr> element.
     fn write_calc_pr(&mut self) {
-        let attributes = [("calcId", "124519"), ("fullCalcOnLoad", "1")];
+        let attributes = self.calculation_options.attributes();
 
         xml_empty_tag(&mut self.writer, "calcPr", &attributes);
     }
+
+    // Write the <externalReferences> element from a template file.
+    fn write_external_references(&mut self) {
+        let Some(template) = &self.template else {
+            return;
+        };
+
+        xml_start_tag_only(&mut self.writer, "externalReferences");
+
+        for reference in &template.external_references {
+            xmlwriter::xml_raw_string(
+                &mut self.writer,
+                &template.remap_relationship_ids(reference),
+            );
+        }
+
+        xml_end_tag(&mut self.writer, "externalReferences");
+    }
+
+    // Write the <pivotCaches> element.
+    fn write_pivot_caches(&mut self) {
+        xml_start_tag_only(&mut self.writer, "pivotCaches");
+
+        // The pivot cache rel ids follow the worksheet rel ids.
+        let mut rel_id = self.worksheets.len();
+        for worksheet in &self.worksheets {
+            for pivot_table in &worksheet.pivot_tables {
+                rel_id += 1;
+
+                let attributes = [
+                    ("cacheId", pivot_table.index.to_string()),
+                    ("r:id", format!("rId{rel_id}")),
+                ];
+
+                xml_empty_tag(&mut self.writer, "pivotCache", &attributes);
+            }
+        }
+
+        // Write any pivot caches from a template file.
+        if let Some(template) = &self.template {
+            for pivot_cache in &template.pivot_caches {
+                xmlwriter::xml_raw_string(
+                    &mut self.writer,
+                    &template.remap_relationship_ids(pivot_cache),
+                );
+            }
+        }
+
+        xml_end_tag(&mut self.writer, "pivotCaches");
+    }
+
+    // Write the <extLst> element.
+    fn write_extensions(&mut self) {
+        xml_start_tag_only(&mut self.writer, "extLst");
+
+        if self.worksheets.iter().any(|ws| !ws.slicers.is_empty()) {
+            // Write the x15:slicerCaches element.
+            let attributes = [
+                ("uri", "{46BE6895-7355-4a93-B00E-2C351335B9C9}"),
+                (
+                    "xmlns:x15",
+                    "http://schemas.microsoft.com/office/spreadsheetml/2010/11/main",
+                ),
+            ];
+            xml_start_tag(&mut self.writer, "ext", &attributes);
+
+            self.write_slicer_caches();
+
+            xml_end_tag(&mut self.writer, "ext");
+        }
+
+        // Write any extensions from a template file.
+        if let Some(template) = &self.template {
+            for extension in &template.extensions {
+                xmlwriter::xml_raw_string(
+                    &mut self.writer,
+                    &template.remap_relationship_ids(extension),
+                );
+            }
+        }
+
+        xml_end_tag(&mut self.writer, "extLst");
+    }
+
+    // Write the <x15:slicerCaches> element.
+    fn write_slicer_caches(&mut self) {
+        let attributes = [(
+            "xmlns:x14",
+            "http://schemas.microsoft.com/office/spreadsheetml/2009/9/main",
+        )];
+        xml_start_tag(&mut self.writer, "x15:slicerCaches", &attributes);
+
+        // The slicer cache rel ids follow the worksheet and pivot cache rel ids.
+        let mut rel_id = self.worksheets.len();
+        for worksheet in &self.worksheets {
+            rel_id += worksheet.pivot_tables.len();
+        }
+
+        for worksheet in &self.worksheets {
+            for _ in worksheet.slicers.values() {
+                rel_id += 1;
+
+                let attributes = [("r:id", format!("rId{rel_id}"))];
+                xml_empty_tag(&mut self.writer, "x14:slicerCache", &attributes);
+            }
+        }
+
+        xml_end_tag(&mut self.writer, "x15:slicerCaches");
+    }
 }
@@ -8,7 +8,11 @@
 mod workbook_tests {
 
     use crate::{test_functions::xml_to_vec, XlsxError};
-    use crate::{xmlwriter, Table, Workbook};
+    use crate::{
+        xmlwriter, CalculationMode, CalculationOptions, ConditionalFormatCell,
+        ConditionalFormatCellRule, DataValidation, DataValidationRule, ExcelDateTime, Format,
+        Table, Workbook, WorkbookView,
+    };
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -41,6 +45,113 @@ mod workbook_tests {
         assert_eq!(expected, got);
     }
 
+    #[test]
+    fn test_assemble_workbook_protection() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.protect_with_password("password");
+        workbook.protect_windows();
+
+        workbook.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&workbook.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <fileVersion appName="xl" lastEdited="4" lowestEdited="4" rupBuild="4505"/>
+              <workbookPr defaultThemeVersion="124226"/>
+              <workbookProtection workbookPassword="83AF" lockStructure="1" lockWindows="1"/>
+              <bookViews>
+                <workbookView xWindow="240" yWindow="15" windowWidth="16095" windowHeight="9660"/>
+              </bookViews>
+              <sheets>
+                <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+              </sheets>
+              <calcPr calcId="124519" fullCalcOnLoad="1"/>
+            </workbook>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    #[cfg(feature = "protection_hash")]
+    fn test_assemble_workbook_protection_sha512() {
+        use crate::ProtectionHash;
+
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.protect_with_password("password");
+        workbook.set_protection_hash(ProtectionHash::Sha512WithSalt {
+            salt: (0..16).collect(),
+            spin_count: 100_000,
+        });
+
+        workbook.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&workbook.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <fileVersion appName="xl" lastEdited="4" lowestEdited="4" rupBuild="4505"/>
+              <workbookPr defaultThemeVersion="124226"/>
+              <workbookProtection workbookAlgorithmName="SHA-512" workbookHashValue="x01qKaF9y9cQwPxHrE46zKhOLAHXLgmWjpZRPwqjkl6tpT1Lq9JXlHzPvHxsy/q0gWkWsUumW+mgF2sVqd4VXQ==" workbookSaltValue="AAECAwQFBgcICQoLDA0ODw==" workbookSpinCount="100000" lockStructure="1"/>
+              <bookViews>
+                <workbookView xWindow="240" yWindow="15" windowWidth="16095" windowHeight="9660"/>
+              </bookViews>
+              <sheets>
+                <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+              </sheets>
+              <calcPr calcId="124519" fullCalcOnLoad="1"/>
+            </workbook>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    #[cfg(feature = "protection_hash")]
+    fn test_protect_structure_without_password() {
+        use crate::ProtectionHash;
+
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.protect_structure();
+        workbook.set_protection_hash(ProtectionHash::Sha512);
+
+        workbook.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&workbook.writer);
+        assert!(got.contains(r#"<workbookProtection lockStructure="1"/>"#));
+    }
+
+    #[test]
+    #[cfg(feature = "protection_hash")]
+    fn test_assemble_workbook_protection_random_salt() {
+        use crate::ProtectionHash;
+
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.protect_with_password("password");
+        workbook.set_protection_hash(ProtectionHash::Sha512);
+
+        workbook.prepare_protection_salts().unwrap();
+        assert_eq!(16, workbook.protection_salt.len());
+
+        workbook.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&workbook.writer);
+        assert!(got.contains(r#"workbookSpinCount="100000""#));
+    }
+
     #[test]
     fn test_define_name() {
         let mut workbook = Workbook::default();
@@ -237,4 +348,409 @@ mod workbook_tests {
 
         assert!(matches!(result, Err(XlsxError::ThemeError(_))));
     }
+
+    #[test]
+    fn test_assemble_1904_epoch() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+        workbook.use_1904_epoch(true);
+
+        workbook.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&workbook.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <fileVersion appName="xl" lastEdited="4" lowestEdited="4" rupBuild="4505"/>
+              <workbookPr date1904="1" defaultThemeVersion="124226"/>
+              <bookViews>
+                <workbookView xWindow="240" yWindow="15" windowWidth="16095" windowHeight="9660"/>
+              </bookViews>
+              <sheets>
+                <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+              </sheets>
+              <calcPr calcId="124519" fullCalcOnLoad="1"/>
+            </workbook>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    // Read the worksheet XML from a saved workbook.
+    fn read_sheet1_xml(workbook: &mut Workbook) -> String {
+        let buffer = workbook.save_to_buffer().unwrap();
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+        let mut xml = String::new();
+        std::io::Read::read_to_string(
+            &mut zip.by_name("xl/worksheets/sheet1.xml").unwrap(),
+            &mut xml,
+        )
+        .unwrap();
+
+        xml
+    }
+
+    #[test]
+    fn test_1904_epoch_dates() {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+
+        let date = ExcelDateTime::from_ymd(2024, 1, 1).unwrap();
+        let time = ExcelDateTime::from_hms(12, 0, 0).unwrap();
+        worksheet.write_datetime(0, 0, &date).unwrap();
+        worksheet.write(1, 0, &time).unwrap();
+        worksheet.write_number(2, 0, 45292).unwrap();
+        worksheet.write_formula(3, 0, "=YEAR(A1)").unwrap();
+
+        let data_validation = DataValidation::new().allow_date(DataValidationRule::Between(
+            ExcelDateTime::from_ymd(2024, 1, 1).unwrap(),
+            ExcelDateTime::from_ymd(2024, 12, 31).unwrap(),
+        ));
+        worksheet
+            .add_data_validation(0, 1, 0, 1, &data_validation)
+            .unwrap();
+
+        let conditional_format = ConditionalFormatCell::new()
+            .set_rule(ConditionalFormatCellRule::GreaterThan(date))
+            .set_format(Format::new());
+        worksheet
+            .add_conditional_format(0, 0, 9, 0, &conditional_format)
+            .unwrap();
+
+        // The epoch can be set after the data is written.
+        workbook.use_1904_epoch(true);
+        workbook.calculate_formulas();
+
+        let xml = read_sheet1_xml(&mut workbook);
+
+        assert!(xml.contains(r#"<c r="A1"><v>43830</v></c>"#));
+        assert!(xml.contains(r#"<c r="A2"><v>0.5</v></c>"#));
+        assert!(xml.contains(r#"<c r="A3"><v>45292</v></c>"#));
+        assert!(xml.contains(r#"<f>YEAR(A1)</f><v>2024</v>"#));
+        assert!(xml.contains(r#"<formula1>43830</formula1><formula2>44195</formula2>"#));
+        assert!(xml.contains(r#"<formula>43830</formula>"#));
+
+        // Saving again, without the 1904 epoch, restores the 1900 values.
+        workbook.use_1904_epoch(false);
+
+        let xml = read_sheet1_xml(&mut workbook);
+
+        assert!(xml.contains(r#"<c r="A1"><v>45292</v></c>"#));
+        assert!(xml.contains(r#"<formula1>45292</formula1><formula2>45657</formula2>"#));
+        assert!(xml.contains(r#"<formula>45292</formula>"#));
+    }
+
+    #[test]
+    fn test_1904_epoch_pre_1904_dates() {
+        let before_1904 = ExcelDateTime::from_ymd(1903, 12, 31).unwrap();
+        let epoch_1904 = ExcelDateTime::from_ymd(1904, 1, 1).unwrap();
+        let time = ExcelDateTime::from_hms(12, 0, 0).unwrap();
+
+        // Dates before 1904 are rejected when they are written.
+        let mut workbook = Workbook::new();
+        workbook.use_1904_epoch(true);
+        let worksheet = workbook.add_worksheet();
+
+        let result = worksheet.write_datetime(0, 0, &before_1904);
+        assert!(matches!(result, Err(XlsxError::DateTimeRangeError(_))));
+
+        worksheet.write_datetime(0, 0, &epoch_1904).unwrap();
+        worksheet.write_datetime(1, 0, &time).unwrap();
+
+        let xml = read_sheet1_xml(&mut workbook);
+
+        assert!(xml.contains(r#"<c r="A1"><v>0</v></c>"#));
+        assert!(xml.contains(r#"<c r="A2"><v>0.5</v></c>"#));
+
+        // Dates before 1904 are rejected when the file is saved if the epoch
+        // is set after the data is written.
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_datetime(0, 0, &before_1904).unwrap();
+        workbook.use_1904_epoch(true);
+
+        let result = workbook.save_to_buffer();
+        assert!(matches!(result, Err(XlsxError::DateTimeRangeError(_))));
+
+        // The same applies to data validation dates.
+        let mut workbook = Workbook::new();
+        workbook.use_1904_epoch(true);
+        let worksheet = workbook.add_worksheet();
+
+        let data_validation =
+            DataValidation::new().allow_date(DataValidationRule::GreaterThan(before_1904.clone()));
+        worksheet
+            .add_data_validation(0, 0, 0, 0, &data_validation)
+            .unwrap();
+
+        let result = workbook.save_to_buffer();
+        assert!(matches!(result, Err(XlsxError::DateTimeRangeError(_))));
+
+        // And to conditional format dates.
+        let mut workbook = Workbook::new();
+        workbook.use_1904_epoch(true);
+        let worksheet = workbook.add_worksheet();
+
+        let conditional_format = ConditionalFormatCell::new()
+            .set_rule(ConditionalFormatCellRule::LessThan(before_1904))
+            .set_format(Format::new());
+        worksheet
+            .add_conditional_format(0, 0, 0, 0, &conditional_format)
+            .unwrap();
+
+        let result = workbook.save_to_buffer();
+        assert!(matches!(result, Err(XlsxError::DateTimeRangeError(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_1904_epoch_serde_dates() {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        struct Record {
+            name: &'static str,
+            date: ExcelDateTime,
+            value: f64,
+        }
+
+        let mut workbook = Workbook::new();
+        workbook.use_1904_epoch(true);
+        let worksheet = workbook.add_worksheet();
+
+        let record = Record {
+            name: "Apple",
+            date: ExcelDateTime::from_ymd(2024, 1, 1).unwrap(),
+            value: 45292.0,
+        };
+
+        worksheet.serialize_headers(0, 0, &record).unwrap();
+        worksheet.serialize(&record).unwrap();
+
+        let xml = read_sheet1_xml(&mut workbook);
+
+        assert!(xml.contains(r#"<c r="B2"><v>43830</v></c>"#));
+        assert!(xml.contains(r#"<c r="C2"><v>45292</v></c>"#));
+    }
+
+    #[test]
+    fn test_assemble_calculation_options1() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+
+        let options = CalculationOptions::new()
+            .set_mode(CalculationMode::Manual)
+            .set_calculate_on_save(false);
+        workbook.set_calculation_options(&options);
+
+        workbook.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&workbook.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <fileVersion appName="xl" lastEdited="4" lowestEdited="4" rupBuild="4505"/>
+              <workbookPr defaultThemeVersion="124226"/>
+              <bookViews>
+                <workbookView xWindow="240" yWindow="15" windowWidth="16095" windowHeight="9660"/>
+              </bookViews>
+              <sheets>
+                <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+              </sheets>
+              <calcPr calcId="124519" calcMode="manual" fullCalcOnLoad="1" calcOnSave="0"/>
+            </workbook>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_calculation_options2() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+
+        let options = CalculationOptions::new()
+            .set_mode(CalculationMode::AutomaticExceptTables)
+            .set_iterative(true)
+            .set_max_iterations(500)
+            .set_max_change(0.0001)
+            .set_precision_as_displayed(true);
+        workbook.set_calculation_options(&options);
+
+        workbook.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&workbook.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <fileVersion appName="xl" lastEdited="4" lowestEdited="4" rupBuild="4505"/>
+              <workbookPr defaultThemeVersion="124226"/>
+              <bookViews>
+                <workbookView xWindow="240" yWindow="15" windowWidth="16095" windowHeight="9660"/>
+              </bookViews>
+              <sheets>
+                <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+              </sheets>
+              <calcPr calcId="124519" calcMode="autoNoTable" fullCalcOnLoad="1" iterate="1" iterateCount="500" iterateDelta="0.0001" fullPrecision="0"/>
+            </workbook>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_calculation_options3() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+
+        // Iteration defaults and out of range values are ignored.
+        let options = CalculationOptions::new()
+            .set_iterative(true)
+            .set_max_iterations(0)
+            .set_max_change(-1.0)
+            .set_calculate_on_save(false);
+        workbook.set_calculation_options(&options);
+
+        workbook.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&workbook.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <fileVersion appName="xl" lastEdited="4" lowestEdited="4" rupBuild="4505"/>
+              <workbookPr defaultThemeVersion="124226"/>
+              <bookViews>
+                <workbookView xWindow="240" yWindow="15" windowWidth="16095" windowHeight="9660"/>
+              </bookViews>
+              <sheets>
+                <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+              </sheets>
+              <calcPr calcId="124519" fullCalcOnLoad="1" iterate="1"/>
+            </workbook>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_workbook_view1() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+
+        let view = WorkbookView::new()
+            .set_sheet_tabs(false)
+            .set_horizontal_scrollbar(false)
+            .set_vertical_scrollbar(false);
+        workbook.set_workbook_view(&view);
+
+        workbook.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&workbook.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <fileVersion appName="xl" lastEdited="4" lowestEdited="4" rupBuild="4505"/>
+              <workbookPr defaultThemeVersion="124226"/>
+              <bookViews>
+                <workbookView showHorizontalScroll="0" showVerticalScroll="0" showSheetTabs="0" xWindow="240" yWindow="15" windowWidth="16095" windowHeight="9660"/>
+              </bookViews>
+              <sheets>
+                <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+              </sheets>
+              <calcPr calcId="124519" fullCalcOnLoad="1"/>
+            </workbook>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_workbook_view2() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+
+        let view = WorkbookView::new()
+            .set_position(10, 20)
+            .set_size(1200, 800)
+            .set_tab_ratio(85.5);
+        workbook.set_workbook_view(&view);
+
+        workbook.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&workbook.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <fileVersion appName="xl" lastEdited="4" lowestEdited="4" rupBuild="4505"/>
+              <workbookPr defaultThemeVersion="124226"/>
+              <bookViews>
+                <workbookView xWindow="150" yWindow="300" windowWidth="18000" windowHeight="12000" tabRatio="855"/>
+              </bookViews>
+              <sheets>
+                <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+              </sheets>
+              <calcPr calcId="124519" fullCalcOnLoad="1"/>
+            </workbook>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_workbook_view3() {
+        let mut workbook = Workbook::default();
+        workbook.add_worksheet();
+
+        // Out of range values are ignored.
+        let view = WorkbookView::new().set_size(0, 800).set_tab_ratio(101.0);
+        workbook.set_workbook_view(&view);
+
+        workbook.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&workbook.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <fileVersion appName="xl" lastEdited="4" lowestEdited="4" rupBuild="4505"/>
+              <workbookPr defaultThemeVersion="124226"/>
+              <bookViews>
+                <workbookView xWindow="240" yWindow="15" windowWidth="16095" windowHeight="9660"/>
+              </bookViews>
+              <sheets>
+                <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+              </sheets>
+              <calcPr calcId="124519" fullCalcOnLoad="1"/>
+            </workbook>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
 }
@@ -0,0 +1,223 @@
+// workbook_view - A module for representing workbook window view settings.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+#![warn(missing_docs)]
+
+/// The `WorkbookView` struct is used to set the window view properties of a
+/// workbook.
+///
+/// The `WorkbookView` struct represents the properties of the Excel
+/// application window that are stored in the workbook file such as the
+/// initial window position and size, the width of the worksheet tab bar, and
+/// whether the worksheet tabs and scrollbars are displayed.
+///
+/// It is used in conjunction with the
+/// [`Workbook::set_workbook_view()`](crate::Workbook::set_workbook_view)
+/// method.
+///
+/// # Examples
+///
+/// The following example demonstrates setting the window view properties of
+/// a workbook to hide the worksheet tabs and scrollbars.
+///
+/// ```
+/// # // This code is available in examples/doc_workbook_view.rs
+/// #
+/// use rust_xlsxwriter::{Workbook, WorkbookView, XlsxError};
+///
+/// fn main() -> Result<(), XlsxError> {
+///     let mut workbook = Workbook::new();
+///
+///     let view = WorkbookView::new()
+///         .set_size(1200, 800)
+///         .set_sheet_tabs(false)
+///         .set_horizontal_scrollbar(false)
+///         .set_vertical_scrollbar(false);
+///
+///     workbook.set_workbook_view(&view);
+///
+///     let worksheet = workbook.add_worksheet();
+///     worksheet.write_string(0, 0, "Dashboard")?;
+///
+///     workbook.save("workbook_view.xlsx")?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkbookView {
+    pub(crate) x_window: u32,
+    pub(crate) y_window: u32,
+    pub(crate) window_width: u32,
+    pub(crate) window_height: u32,
+    pub(crate) tab_ratio: u16,
+    pub(crate) show_sheet_tabs: bool,
+    pub(crate) show_horizontal_scroll: bool,
+    pub(crate) show_vertical_scroll: bool,
+}
+
+impl Default for WorkbookView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkbookView {
+    /// Create a new `WorkbookView` struct.
+    ///
+    /// The defaults are a 1073 x 644 pixel window with the worksheet tabs and
+    /// scrollbars displayed.
+    ///
+    pub fn new() -> WorkbookView {
+        WorkbookView {
+            x_window: 240,
+            y_window: 15,
+            window_width: 16095,
+            window_height: 9660,
+            tab_ratio: 600,
+            show_sheet_tabs: true,
+            show_horizontal_scroll: true,
+            show_vertical_scroll: true,
+        }
+    }
+
+    /// Set the initial position of the Excel application window.
+    ///
+    /// Set the position of the top left corner of the window, relative to the
+    /// screen, when the file is opened.
+    ///
+    /// Note, Excel may ignore the stored position, or adjust it, if it is
+    /// outside the visible screen area.
+    ///
+    /// # Parameters
+    ///
+    /// - `x`: The horizontal position in pixels. The default is 16.
+    /// - `y`: The vertical position in pixels. The default is 1.
+    ///
+    pub fn set_position(mut self, x: u32, y: u32) -> WorkbookView {
+        self.x_window = Self::pixels_to_twips(x);
+        self.y_window = Self::pixels_to_twips(y);
+        self
+    }
+
+    /// Set the initial size of the Excel application window.
+    ///
+    /// Set the width and height of the window, in pixels, when the file is
+    /// opened. This is generally only useful on Windows since Excel on macOS
+    /// uses its own window sizing.
+    ///
+    /// # Parameters
+    ///
+    /// - `width`: The window width in pixels. The default is 1073.
+    /// - `height`: The window height in pixels. The default is 644.
+    ///
+    pub fn set_size(mut self, width: u32, height: u32) -> WorkbookView {
+        if width == 0 || height == 0 {
+            eprintln!("Window width and height must be greater than 0.");
+            return self;
+        }
+
+        self.window_width = Self::pixels_to_twips(width);
+        self.window_height = Self::pixels_to_twips(height);
+        self
+    }
+
+    /// Set the ratio between the worksheet tab bar and the horizontal
+    /// scrollbar.
+    ///
+    /// Set the percentage of the horizontal area at the bottom of the window
+    /// that is used by the worksheet tab bar. Increasing it allows more
+    /// worksheet tabs to be displayed, at the expense of the width of the
+    /// horizontal scrollbar.
+    ///
+    /// # Parameters
+    ///
+    /// - `ratio`: The tab bar width as a percentage in the range 0 to 100.
+    ///   The default is 60.
+    ///
+    pub fn set_tab_ratio(mut self, ratio: f64) -> WorkbookView {
+        if !(0.0..=100.0).contains(&ratio) {
+            eprintln!("Tab ratio {ratio} outside Excel range: 0 <= ratio <= 100.");
+            return self;
+        }
+
+        self.tab_ratio = (ratio * 10.0).round() as u16;
+        self
+    }
+
+    /// Display or hide the worksheet tabs.
+    ///
+    /// Hiding the worksheet tabs is useful for dashboard style workbooks
+    /// where the user navigates via hyperlinks or where there is only one
+    /// worksheet.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_sheet_tabs(mut self, enable: bool) -> WorkbookView {
+        self.show_sheet_tabs = enable;
+        self
+    }
+
+    /// Display or hide the horizontal scrollbar.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_horizontal_scrollbar(mut self, enable: bool) -> WorkbookView {
+        self.show_horizontal_scroll = enable;
+        self
+    }
+
+    /// Display or hide the vertical scrollbar.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_vertical_scrollbar(mut self, enable: bool) -> WorkbookView {
+        self.show_vertical_scroll = enable;
+        self
+    }
+
+    // Convert screen pixels to the twips (1/20 of a point) units used by the
+    // window properties, at the default 96 dpi, i.e. 1440 / 96.
+    fn pixels_to_twips(pixels: u32) -> u32 {
+        pixels.saturating_mul(15)
+    }
+
+    // Get the attributes for the <workbookView> element. The firstSheet and
+    // activeTab attributes are added by the workbook.
+    pub(crate) fn attributes(&self) -> Vec<(&'static str, String)> {
+        let mut attributes = vec![];
+
+        if !self.show_horizontal_scroll {
+            attributes.push(("showHorizontalScroll", "0".to_string()));
+        }
+
+        if !self.show_vertical_scroll {
+            attributes.push(("showVerticalScroll", "0".to_string()));
+        }
+
+        if !self.show_sheet_tabs {
+            attributes.push(("showSheetTabs", "0".to_string()));
+        }
+
+        attributes.push(("xWindow", self.x_window.to_string()));
+        attributes.push(("yWindow", self.y_window.to_string()));
+        attributes.push(("windowWidth", self.window_width.to_string()));
+        attributes.push(("windowHeight", self.window_height.to_string()));
+
+        if self.tab_ratio != 600 {
+            attributes.push(("tabRatio", self.tab_ratio.to_string()));
+        }
+
+        attributes
+    }
+}
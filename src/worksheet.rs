@@ -451,8 +451,9 @@
 //! Headers and footers are explained in more detail in a subsequent section
 //! below on [Adding Headers and Footers](#adding-headers-and-footers).
 //!
-//! Note, the options for different first, odd and even pages are not supported
-//! in `rust_xlsxwriter`.
+//! The options for different first, odd and even pages are set using
+//! [`Worksheet::set_header_with_page()`] and
+//! [`Worksheet::set_footer_with_page()`].
 //!
 //! ## Page Setup - Sheet
 //!
@@ -1373,7 +1374,10 @@ use std::fs::File;
 use rust_decimal::prelude::{Decimal, ToPrimitive};
 
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::deserializer::{CellValue, RowDeserializer};
 
 #[cfg(feature = "serde")]
 use crate::{
@@ -1384,12 +1388,30 @@ use crate::{
 #[cfg(feature = "enhanced_autofit")]
 use ssfmt::{FormatOptions, NumberFormat};
 
+#[cfg(feature = "polars")]
+use polars::prelude::{AnyValue, DataFrame, DataType};
+
+#[cfg(feature = "polars")]
+use crate::datetime::{column_decimal_format, unix_days_to_serial, ColumnTimeUnit};
+
+#[cfg(any(feature = "polars", feature = "arrow"))]
+use crate::DataFrameOptions;
+
+#[cfg(feature = "arrow")]
+use arrow::{error::ArrowError, record_batch::RecordBatch};
+
+#[cfg(feature = "arrow")]
+use crate::record_batch::RecordBatchColumn;
+
 use crate::drawing::{Drawing, DrawingCoordinates, DrawingInfo, DrawingObject, DrawingType};
 use crate::error::XlsxError;
 use crate::format::Format;
-use crate::formula::Formula;
+use crate::formula::evaluator::CellInput;
+use crate::formula::parser;
+use crate::formula::{Formula, FormulaExpression};
 use crate::shared_strings_table::SharedStringsTable;
 use crate::styles::Styles;
+use crate::template::TemplateSheet;
 use crate::vml::VmlInfo;
 use crate::xmlwriter::{
     xml_data_element, xml_data_element_only, xml_declaration, xml_empty_tag, xml_empty_tag_only,
@@ -1399,8 +1421,10 @@ use crate::{
     utility, xmlwriter, Button, Chart, ChartEmptyCells, ChartRangeCacheData,
     ChartRangeCacheDataType, Color, ConditionalFormat, DataValidation, DataValidationErrorStyle,
     DataValidationRuleInternal, DataValidationType, ExcelDateTime, FilterCondition, FilterCriteria,
-    FilterData, FilterDataType, HeaderImagePosition, HyperlinkType, Image, IntoExcelDateTime, Note,
-    ObjectMovement, ProtectionOptions, Shape, Sparkline, SparklineType, Table, TableFunction, Url,
+    FilterData, FilterDataType, HeaderFooterPage, HeaderImagePosition, HyperlinkType, Image,
+    IntoExcelDateTime, Note, ObjectMovement, PivotCacheValue, PivotTable, ProtectionHash,
+    ProtectionOptions, Shape, Slicer, Sparkline, SparklineType, Table, TableFunction,
+    ThreadedComment, Url,
 };
 
 /// Integer type to represent a zero indexed row number. Excel's limit for rows
@@ -1512,12 +1536,15 @@ pub struct Worksheet {
     pub(crate) comments_vml_info: Vec<VmlInfo>,
     pub(crate) drawing: Drawing,
     pub(crate) image_types: [bool; NUM_IMAGE_FORMATS],
-    pub(crate) header_footer_images: [Option<Image>; 6],
+    pub(crate) header_footer_images: [Option<Image>; 18],
     pub(crate) charts: BTreeMap<(RowNum, ColNum, u32, u32), Chart>,
     pub(crate) buttons: BTreeMap<(RowNum, ColNum, u32, u32), Button>,
     pub(crate) notes: BTreeMap<RowNum, BTreeMap<ColNum, Note>>,
+    pub(crate) threaded_comments: BTreeMap<(RowNum, ColNum), ThreadedComment>,
     pub(crate) shapes: BTreeMap<(RowNum, ColNum, u32, u32), Shape>,
+    pub(crate) slicers: BTreeMap<(RowNum, ColNum, u32, u32), Slicer>,
     pub(crate) tables: Vec<Table>,
+    pub(crate) pivot_tables: Vec<PivotTable>,
     pub(crate) has_embedded_image_descriptions: bool,
     pub(crate) embedded_images: Vec<Image>,
     pub(crate) global_embedded_image_indices: Vec<u32>,
@@ -1528,6 +1555,8 @@ pub struct Worksheet {
     pub(crate) is_chartsheet: bool,
     pub(crate) use_constant_memory: bool,
     pub(crate) use_inline_strings: bool,
+    pub(crate) use_strict_formulas: bool,
+    pub(crate) is_1904_date: bool,
     pub(crate) current_row: RowNum,
     pub(crate) workbook_xf_indices: Arc<RwLock<HashMap<Format, u32>>>,
     pub(crate) string_table: Arc<Mutex<SharedStringsTable>>,
@@ -1535,15 +1564,19 @@ pub struct Worksheet {
     pub(crate) has_workbook_global_xfs: bool,
     pub(crate) has_workbook_global_sst: bool,
     pub(crate) background_image: Option<Image>,
+    pub(crate) template: Option<TemplateSheet>,
 
     // These collections need to be reset on resave.
     drawing_rel_ids: HashMap<String, u32>,
     pub(crate) comment_relationships: Vec<(String, String, String)>,
+    pub(crate) threaded_comment_relationships: Vec<(String, String, String)>,
     pub(crate) drawing_object_relationships: Vec<(String, String, String)>,
     pub(crate) drawing_relationships: Vec<(String, String, String)>,
     pub(crate) header_footer_vml_info: Vec<VmlInfo>,
     pub(crate) hyperlink_relationships: Vec<(String, String, String)>,
     pub(crate) table_relationships: Vec<(String, String, String)>,
+    pub(crate) slicer_relationships: Vec<(String, String, String)>,
+    pub(crate) pivot_table_relationships: Vec<(String, String, String)>,
     pub(crate) vml_drawing_relationships: Vec<(String, String, String)>,
     pub(crate) background_relationships: Vec<(String, String, String)>,
 
@@ -1578,12 +1611,23 @@ pub struct Worksheet {
     center_horizontally: bool,
     center_vertically: bool,
     screen_gridlines: bool,
+    screen_headings: bool,
+    screen_zeros: bool,
+    screen_formulas: bool,
+    screen_ruler: bool,
+    screen_outline_symbols: bool,
     print_gridlines: bool,
     print_black_and_white: bool,
     print_draft: bool,
     print_headings: bool,
     header: String,
     footer: String,
+    first_header: String,
+    first_footer: String,
+    even_header: String,
+    even_footer: String,
+    header_footer_different_first: bool,
+    header_footer_different_odd_even: bool,
     head_footer_changed: bool,
     header_footer_scale_with_doc: bool,
     header_footer_align_with_page: bool,
@@ -1596,13 +1640,16 @@ pub struct Worksheet {
     first_page_number: u16,
     default_result: Box<str>,
     panes: Panes,
-    hyperlinks: BTreeMap<(RowNum, ColNum), Url>,
+    pub(crate) hyperlinks: BTreeMap<(RowNum, ColNum), Url>,
     rel_count: u32,
     protection_on: bool,
-    protection_hash: u16,
+    protection_password: String,
+    protection_hash: ProtectionHash,
+    protection_salts: Vec<Vec<u8>>,
     protection_options: ProtectionOptions,
-    unprotected_ranges: Vec<(String, String, u16)>,
-    selected_range: (String, String),
+    unprotected_ranges: Vec<(String, String, String)>,
+    selection: Selection,
+    pane_selections: Vec<(WorksheetPane, Selection)>,
     top_left_cell: String,
     horizontal_breaks: Vec<u32>,
     vertical_breaks: Vec<u32>,
@@ -1736,6 +1783,8 @@ impl Worksheet {
         let panes = Panes {
             freeze_cell: (0, 0),
             top_cell: (0, 0),
+            split_height: 0.0,
+            split_width: 0.0,
         };
 
         #[cfg(feature = "constant_memory")]
@@ -1762,6 +1811,7 @@ impl Worksheet {
             merged_ranges: vec![],
             merged_cells: HashMap::new(),
             tables: vec![],
+            pivot_tables: vec![],
             table_ranges: vec![],
             table_cells: HashMap::new(),
             default_format: Format::default(),
@@ -1790,12 +1840,23 @@ impl Worksheet {
             center_horizontally: false,
             center_vertically: false,
             screen_gridlines: true,
+            screen_headings: true,
+            screen_zeros: true,
+            screen_formulas: false,
+            screen_ruler: true,
+            screen_outline_symbols: true,
             print_gridlines: false,
             print_black_and_white: false,
             print_draft: false,
             print_headings: false,
             header: String::new(),
             footer: String::new(),
+            first_header: String::new(),
+            first_footer: String::new(),
+            even_header: String::new(),
+            even_footer: String::new(),
+            header_footer_different_first: false,
+            header_footer_different_odd_even: false,
             head_footer_changed: false,
             header_footer_scale_with_doc: true,
             header_footer_align_with_page: true,
@@ -1812,17 +1873,21 @@ impl Worksheet {
             hyperlinks: BTreeMap::new(),
             images: BTreeMap::new(),
             shapes: BTreeMap::new(),
+            slicers: BTreeMap::new(),
             drawing: Drawing::new(),
             image_types: [false; NUM_IMAGE_FORMATS],
-            header_footer_images: [None, None, None, None, None, None],
+            header_footer_images: [const { None }; 18],
             buttons_vml_info: vec![],
             comments_vml_info: vec![],
             rel_count: 0,
             protection_on: false,
-            protection_hash: 0,
+            protection_password: String::new(),
+            protection_hash: ProtectionHash::Legacy,
+            protection_salts: vec![],
             protection_options: ProtectionOptions::new(),
             unprotected_ranges: vec![],
-            selected_range: (String::new(), String::new()),
+            selection: Selection::default(),
+            pane_selections: vec![],
             top_left_cell: String::new(),
             horizontal_breaks: vec![],
             vertical_breaks: vec![],
@@ -1831,6 +1896,7 @@ impl Worksheet {
             charts: BTreeMap::new(),
             buttons: BTreeMap::new(),
             notes: BTreeMap::new(),
+            threaded_comments: BTreeMap::new(),
             has_drawing_object_linkage: false,
             cells_with_autofilter: HashMap::new(),
             conditional_formats: BTreeMap::new(),
@@ -1867,21 +1933,27 @@ impl Worksheet {
             outline_symbols_above: false,
             outline_symbols_left: false,
             background_image: None,
+            template: None,
             max_autofit_width: MAX_AUTOFIT_WIDTH_PIXELS,
             max_autofit_row: ROW_MAX - 1,
 
             // These collections need to be reset on resave.
             comment_relationships: vec![],
+            threaded_comment_relationships: vec![],
             drawing_object_relationships: vec![],
             drawing_rel_ids: HashMap::new(),
             drawing_relationships: vec![],
             header_footer_vml_info: vec![],
             hyperlink_relationships: vec![],
             table_relationships: vec![],
+            slicer_relationships: vec![],
+            pivot_table_relationships: vec![],
             vml_drawing_relationships: vec![],
             background_relationships: vec![],
             is_chartsheet: false,
             use_constant_memory: false,
+            use_strict_formulas: false,
+            is_1904_date: false,
             use_inline_strings: false,
             has_sheet_data: false,
             current_row: 0,
@@ -2102,6 +2174,7 @@ impl Worksheet {
     ///   999,999,999,999,999 (15 digits).
     /// - [`bool`]
     /// - [`ExcelDateTime`].
+    /// - [`ExcelError`].
     /// - [`Formula`].
     /// - [`Url`].
     /// - [`Option<T>`]: If `T` is a supported type then the [`Some<T>`] value
@@ -2168,6 +2241,7 @@ impl Worksheet {
     ///   999,999,999,999,999 (15 digits).
     /// - [`bool`]
     /// - [`ExcelDateTime`].
+    /// - [`ExcelError`].
     /// - [`Formula`].
     /// - [`Url`].
     /// - [`Option<T>`]: If `T` is a supported type then [`Some<T>`] is written
@@ -4339,6 +4413,8 @@ impl Worksheet {
     ///
     /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
     ///   worksheet limits.
+    /// - [`XlsxError::DateTimeRangeError`] - The date is before 1904-01-01
+    ///   and the workbook uses the 1904 epoch.
     ///
     /// # Examples
     ///
@@ -4524,6 +4600,8 @@ impl Worksheet {
     ///
     /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
     ///   worksheet limits.
+    /// - [`XlsxError::DateTimeRangeError`] - The date is before 1904-01-01
+    ///   and the workbook uses the 1904 epoch.
     ///
     /// # Examples
     ///
@@ -4897,6 +4975,121 @@ impl Worksheet {
         self.store_boolean(row, col, boolean, Some(format))
     }
 
+    /// Write an Excel error value to a worksheet cell.
+    ///
+    /// Write an [`ExcelError`] value such as `#N/A` or `#DIV/0!` to a
+    /// worksheet cell. These are stored in the file as error types rather than
+    /// strings so that they are handled by Excel like the result of a formula
+    /// error. For example they are detected by functions such as `ISNA()` and
+    /// `IFERROR()` and `#N/A` values can be displayed as gaps in charts, see
+    /// [`Chart::show_na_as_empty_cell()`](crate::Chart::show_na_as_empty_cell).
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The zero indexed row number.
+    /// - `col`: The zero indexed column number.
+    /// - `error`: The [`ExcelError`] value to write to the cell.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing Excel error values to a
+    /// worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_write_error.rs
+    /// #
+    /// # use rust_xlsxwriter::{ExcelError, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     worksheet.write_error(0, 0, ExcelError::NA)?;
+    ///     worksheet.write_error(1, 0, ExcelError::Div0)?;
+    ///
+    ///     // The error values can be detected by Excel functions.
+    ///     worksheet.write_formula(0, 1, "=ISNA(A1)")?;
+    ///     worksheet.write_formula(1, 1, r#"=IFERROR(A2, "Error")"#)?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn write_error(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        error: ExcelError,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Store the cell data.
+        self.store_error(row, col, error, None)
+    }
+
+    /// Write a formatted Excel error value to a worksheet cell.
+    ///
+    /// Write an [`ExcelError`] value with formatting to a worksheet cell. The
+    /// format is set via a [`Format`] struct which can control the visual
+    /// format of the cell, such as the font color or alignment.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The zero indexed row number.
+    /// - `col`: The zero indexed column number.
+    /// - `error`: The [`ExcelError`] value to write to the cell.
+    /// - `format`: The [`Format`] property for the cell.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing formatted Excel error values
+    /// to a worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_write_error_with_format.rs
+    /// #
+    /// # use rust_xlsxwriter::{ExcelError, Format, FormatAlign, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     let center = Format::new().set_align(FormatAlign::Center);
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     worksheet.write_error_with_format(0, 0, ExcelError::NA, &center)?;
+    ///     worksheet.write_error_with_format(1, 0, ExcelError::Ref, &center)?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn write_error_with_format(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        error: ExcelError,
+        format: &Format,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Store the cell data.
+        self.store_error(row, col, error, Some(format))
+    }
+
     /// Merge a range of cells.
     ///
     /// The `merge_range()` method allows cells to be merged together so that
@@ -6050,6 +6243,93 @@ impl Worksheet {
         Ok(self)
     }
 
+    /// Insert a table slicer into a worksheet.
+    ///
+    /// This method can be used to insert a [`Slicer`] that filters a
+    /// worksheet [`Table`] column. The table is referred to by name and can be
+    /// in any worksheet in the workbook. The table and column names are
+    /// checked when the file is saved.
+    ///
+    /// See the [`Slicer`] documentation for the methods that can be used to
+    /// configure the caption, size and appearance of the slicer.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The zero indexed row number.
+    /// - `col`: The zero indexed column number.
+    /// - `slicer`: The [`Slicer`] to insert into the cell.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// - [`XlsxError::SlicerError`] - The slicer table or column name is blank.
+    ///
+    /// # Examples
+    ///
+    /// This example demonstrates adding a slicer with several columns of
+    /// buttons to a worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_insert_slicer.rs
+    /// #
+    /// # use rust_xlsxwriter::{Slicer, SlicerStyle, Table, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    /// #     // Write some data for the table.
+    /// #     worksheet.write_row(0, 0, ["Quarter", "Sales"])?;
+    /// #     worksheet.write_column(1, 0, ["Q1", "Q2", "Q3", "Q4"])?;
+    /// #     worksheet.write_column(1, 1, [1000, 2500, 3000, 1500])?;
+    /// #
+    /// #     // Add a table over the data. It will have the default name "Table1".
+    /// #     worksheet.add_table(0, 0, 4, 1, &Table::new())?;
+    /// #
+    ///     // Create a slicer with a 4 column button layout.
+    ///     let slicer = Slicer::new("Table1", "Quarter")
+    ///         .set_column_count(4)
+    ///         .set_style(SlicerStyle::Dark2)
+    ///         .set_width(300)
+    ///         .set_height(80);
+    ///
+    ///     // Insert the slicer in a cell.
+    ///     worksheet.insert_slicer(1, 3, &slicer)?;
+    /// #
+    /// #     // Save the file to disk.
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn insert_slicer(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        slicer: &Slicer,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Check row and columns are in the allowed range.
+        if !self.check_dimensions_only(row, col) {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        slicer.validate()?;
+
+        // Store workbook objects in row by column position order.
+        self.slicers
+            .insert((row, col, slicer.y_offset, slicer.x_offset), slicer.clone());
+
+        // Slicers are written to the worksheet extLst.
+        self.use_x14_extensions = true;
+
+        Ok(self)
+    }
+
     /// Make all worksheet notes visible when the file loads.
     ///
     /// By default Excel hides cell notes until the user mouses over the parent
@@ -6171,6 +6451,94 @@ impl Worksheet {
         self
     }
 
+    /// Add a threaded comment to a cell.
+    ///
+    /// A threaded comment is the newer style of Excel comment that supports a
+    /// conversation of replies, @mentions and a resolved state. In Excel they
+    /// are displayed as "Comments" to distinguish them from the older
+    /// non-threaded "Notes", see [`Worksheet::insert_note()`].
+    ///
+    /// Excel also requires a legacy note in the same cell as a fallback for
+    /// older versions of Excel. This is generated automatically from the text
+    /// of the comment and its replies and will replace any [`Note`] already
+    /// inserted in the cell.
+    ///
+    /// See [`ThreadedComment`] for details on the properties of threaded
+    /// comments.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The zero indexed row number.
+    /// - `col`: The zero indexed column number.
+    /// - `comment`: The [`ThreadedComment`] to insert into the cell.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// - [`XlsxError::MaxStringLengthExceeded`] - Text exceeds Excel's limit of
+    ///   32,767 characters.
+    /// - [`XlsxError::ParameterError`] - An @mention name isn't found in the
+    ///   comment text.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates adding a threaded comment with
+    /// replies to a worksheet cell.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_insert_threaded_comment.rs
+    /// #
+    /// # use rust_xlsxwriter::{ThreadedComment, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Create some replies to a comment.
+    ///     let reply1 = ThreadedComment::new("Looks fine to me.").set_author("Jane Doe");
+    ///     let reply2 = ThreadedComment::new("Thanks @Jane Doe, closing this.")
+    ///         .set_author("John Smith")
+    ///         .add_mention("Jane Doe");
+    ///
+    ///     // Create a resolved threaded comment with the replies.
+    ///     let comment = ThreadedComment::new("Can someone review this value?")
+    ///         .set_author("John Smith")
+    ///         .add_reply(&reply1)
+    ///         .add_reply(&reply2)
+    ///         .set_resolved(true);
+    ///
+    ///     // Add the comment to a worksheet cell.
+    ///     worksheet.insert_threaded_comment(1, 1, &comment)?;
+    /// #
+    /// #     // Save the file to disk.
+    /// #     workbook.save("threaded_comments.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn insert_threaded_comment(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        comment: &ThreadedComment,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Check row and columns are in the allowed range.
+        if !self.check_dimensions(row, col) {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        comment.validate()?;
+
+        self.threaded_comments.insert((row, col), comment.clone());
+
+        // The fallback notes are written to a VML file.
+        self.has_vml = true;
+
+        Ok(self)
+    }
+
     /// Add a Excel Form Control button object to a worksheet.
     ///
     /// Add a [`Button`] to a worksheet at a cell location. The worksheet button
@@ -9015,8 +9383,9 @@ impl Worksheet {
         for (offset, column) in table.columns.iter_mut().enumerate() {
             let col = first_col + offset as u16;
 
-            // Write the header. We skip this when writing serde headers.
-            if table.show_header_row && !table.is_serde_table {
+            // Write the header, unless the header cells have already been
+            // written by serialization or the dataframe writers.
+            if table.show_header_row && !table.has_header_cells {
                 match &column.header_format {
                     Some(header_format) => {
                         self.write_string_with_format(first_row, col, &column.name, header_format)?;
@@ -9124,6 +9493,119 @@ impl Worksheet {
         Ok(self)
     }
 
+    /// Add a pivot table to a worksheet.
+    ///
+    /// Pivot tables are a feature of Excel that allow you to summarize,
+    /// group, sort and aggregate the data in a worksheet range or table. See
+    /// the [`PivotTable`] struct for more details.
+    ///
+    /// The pivot table data and layout are calculated from the source data
+    /// when the file is saved. Excel refreshes the pivot table when the file is
+    /// opened so the summarized values don't need to be written by the user.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The zero indexed row of the top left cell of the pivot table.
+    /// - `col`: The zero indexed column of the top left cell of the pivot
+    ///   table.
+    /// - `pivot_table`: A [`PivotTable`] struct reference.
+    ///
+    /// Note, any page (filter) fields are displayed above the pivot table
+    /// with a blank row between them and the table. The `row` must leave room
+    /// for them.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// - [`XlsxError::PivotTableError`] - A general error that is raised when
+    ///   a pivot table parameter is incorrect or a pivot table is configured
+    ///   incorrectly.
+    /// - [`XlsxError::NameError`] - The pivot table name doesn't meet one of
+    ///   Excel's criteria for names.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates adding a pivot table to a worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_add_pivot_table.rs
+    /// #
+    /// # use rust_xlsxwriter::{PivotDataField, PivotField, PivotFunction, PivotTable, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     // Create a new Excel file object.
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet with some source data for the pivot table.
+    /// #     let data = workbook.add_worksheet().set_name("Data")?;
+    /// #
+    /// #     data.write_row(0, 0, ["Region", "Product", "Sales"])?;
+    /// #     data.write_row(1, 0, ["East", "Apples"])?;
+    /// #     data.write_row(2, 0, ["West", "Apples"])?;
+    /// #     data.write_row(3, 0, ["East", "Pears"])?;
+    /// #     data.write_row(4, 0, ["West", "Pears"])?;
+    /// #     data.write_column(1, 2, [1000, 2500, 3000, 1500])?;
+    /// #
+    ///     // Create a pivot table with a page (filter) field.
+    ///     let pivot_table = PivotTable::new()
+    ///         .set_source_range(("Data", 0, 0, 4, 2))
+    ///         .set_page_fields(&[PivotField::new("Product")])
+    ///         .set_row_fields(&[PivotField::new("Region")])
+    ///         .set_data_fields(&[
+    ///             PivotDataField::new("Sales"),
+    ///             PivotDataField::new("Sales").set_function(PivotFunction::Average),
+    ///         ]);
+    ///
+    ///     // Add the pivot table to a worksheet, leaving room for the filter.
+    ///     let worksheet = workbook.add_worksheet();
+    ///     worksheet.add_pivot_table(2, 0, &pivot_table)?;
+    /// #
+    /// #     // Save the file to disk.
+    /// #     workbook.save("pivot_table.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn add_pivot_table(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        pivot_table: &PivotTable,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Check row and col are in the allowed range.
+        if !self.check_dimensions_only(row, col) {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        pivot_table.validate()?;
+
+        // Check that a user defined pivot table name meets Excel's naming
+        // rules. Empty names are allowed since a default name is generated
+        // later.
+        if !pivot_table.name.is_empty() {
+            utility::check_name(&pivot_table.name)?;
+        }
+
+        // Check that there is room for the page fields, and a blank row,
+        // above the pivot table.
+        let num_page_fields = pivot_table.num_page_fields() as u32;
+        if num_page_fields > 0 && row < num_page_fields + 1 {
+            return Err(XlsxError::PivotTableError(format!(
+                "Pivot table at row {row} requires {} rows above it for the page fields",
+                num_page_fields + 1
+            )));
+        }
+
+        let mut pivot_table = pivot_table.clone();
+        pivot_table.cell_range = CellRange::new(row, col, row, col);
+
+        self.pivot_tables.push(pivot_table);
+
+        Ok(self)
+    }
+
     /// Add a conditional format to highlight cells based on rules.
     ///
     /// Conditional formatting is a feature of Excel which allows you to apply a
@@ -9785,22 +10267,19 @@ impl Worksheet {
     /// optional, weak, password to prevent modification.
     ///
     /// **Note**: Worksheet level passwords in Excel offer very weak protection.
-    /// They do not encrypt your data and are very easy to deactivate. Full
-    /// workbook encryption is not supported by `rust_xlsxwriter`. However, it
-    /// is possible to encrypt an `rust_xlsxwriter` file using a third party
-    /// open source tool called
-    /// [msoffice-crypt](https://github.com/herumi/msoffice). This works for
-    /// macOS, Linux and Windows:
-    ///
-    /// ```text
-    /// msoffice-crypt.exe -e -p password clear.xlsx encrypted.xlsx
-    /// ```
+    /// They do not encrypt your data and are very easy to deactivate. By
+    /// default the password is stored as a legacy 16 bit hash. A stronger
+    /// salted SHA-512 hash can be used instead, see
+    /// `Worksheet::set_protection_hash()`, but the data still isn't
+    /// encrypted. To encrypt the file with a password that is required to open
+    /// it use `Workbook::set_open_password()`, which requires the `encryption`
+    /// feature.
     ///
     /// # Parameters
     ///
     /// - `password`: The password string. Note, only ascii text passwords are
-    ///   supported. Passing the empty string "" is the same as turning on
-    ///   protection without a password.
+    ///   supported with the legacy hash. Passing the empty string "" is the
+    ///   same as turning on protection without a password.
     ///
     /// # Examples
     ///
@@ -9836,27 +10315,82 @@ impl Worksheet {
     ///
     pub fn protect_with_password(&mut self, password: &str) -> &mut Worksheet {
         self.protection_on = true;
-        self.protection_hash = utility::hash_password(password);
+        self.protection_password = password.to_string();
 
         self
     }
 
-    /// Specify which worksheet elements should, or shouldn't, be protected.
+    /// Set the algorithm used to hash worksheet protection passwords.
     ///
-    /// The `protect_with_password()` method is like the
-    /// [`Worksheet::protect()`] method, see above, except it also specifies
-    /// which worksheet elements should, or shouldn't, be protected.
+    /// By default the passwords set with [`Worksheet::protect_with_password()`]
+    /// and [`Worksheet::unprotect_range_with_options()`] are stored as a legacy
+    /// 16 bit hash, which is supported by all versions of Excel but which is
+    /// trivial to reverse. The `set_protection_hash()` method can be used to
+    /// store a salted SHA-512 hash instead, like Excel 2013 and later. See
+    /// [`ProtectionHash`] for the available options.
     ///
-    /// You can specify which worksheet elements protection should be on or off
-    /// via a [`ProtectionOptions`] struct reference. The Excel options with
-    /// their default states are shown below:
+    /// This method requires the `protection_hash` feature.
     ///
-    /// <img
-    /// src="https://rustxlsxwriter.github.io/images/worksheet_protect_with_options1.png">
+    /// The [`ProtectionHash::Sha512WithSalt`] option can be used to set a fixed
+    /// salt to create reproducible output, for example for comparing files in
+    /// tests.
     ///
     /// # Parameters
     ///
-    /// `options` - Worksheet protection options as defined by a
+    /// - `hash`: A [`ProtectionHash`] enum value.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates protecting a worksheet with a
+    /// password that is stored as a SHA-512 hash.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_protection_hash.rs
+    /// #
+    /// # use rust_xlsxwriter::{ProtectionHash, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     // Add a worksheet to the workbook.
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     // Protect the worksheet with a SHA-512 password hash.
+    ///     worksheet.protect_with_password("abc123");
+    ///     worksheet.set_protection_hash(ProtectionHash::Sha512);
+    ///
+    ///     // The hash also applies to unprotected range passwords.
+    ///     worksheet.unprotect_range_with_options(0, 0, 9, 0, "Inputs", "xyz")?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    #[cfg(feature = "protection_hash")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "protection_hash")))]
+    pub fn set_protection_hash(&mut self, hash: ProtectionHash) -> &mut Worksheet {
+        self.protection_hash = hash;
+        self
+    }
+
+    /// Specify which worksheet elements should, or shouldn't, be protected.
+    ///
+    /// The `protect_with_password()` method is like the
+    /// [`Worksheet::protect()`] method, see above, except it also specifies
+    /// which worksheet elements should, or shouldn't, be protected.
+    ///
+    /// You can specify which worksheet elements protection should be on or off
+    /// via a [`ProtectionOptions`] struct reference. The Excel options with
+    /// their default states are shown below:
+    ///
+    /// <img
+    /// src="https://rustxlsxwriter.github.io/images/worksheet_protect_with_options1.png">
+    ///
+    /// # Parameters
+    ///
+    /// `options` - Worksheet protection options as defined by a
     /// [`ProtectionOptions`] struct reference.
     ///
     /// # Examples
@@ -10058,13 +10592,13 @@ impl Worksheet {
 
         let range = utility::cell_range(first_row, first_col, last_row, last_col);
         let mut name = name.to_string();
-        let password_hash = utility::hash_password(password);
 
         if name.is_empty() {
             name = format!("Range{}", 1 + self.unprotected_ranges.len());
         }
 
-        self.unprotected_ranges.push((range, name, password_hash));
+        self.unprotected_ranges
+            .push((range, name, password.to_string()));
 
         Ok(self)
     }
@@ -10079,8 +10613,9 @@ impl Worksheet {
     /// The active cell within a selected range is determined by the order in
     /// which `first_` and `last_` are specified.
     ///
-    /// Only one range of cells can be selected. The default cell selection is
-    /// (0, 0, 0, 0), "A1".
+    /// Only one range of cells can be selected with this method. To select
+    /// several ranges see [`Worksheet::set_selection_ranges()`]. The default
+    /// cell selection is (0, 0, 0, 0), "A1".
     ///
     /// # Parameters
     ///
@@ -10159,11 +10694,210 @@ impl Worksheet {
 
         let range = utility::cell_range(first_row, first_col, last_row, last_col);
 
-        self.selected_range = (active_cell, range);
+        self.selection = Selection {
+            active_cell,
+            sqref: range,
+            active_cell_id: 0,
+        };
+
+        Ok(self)
+    }
+
+    /// Set several selected ranges of cells in a worksheet.
+    ///
+    /// The `set_selection_ranges()` method is similar to the
+    /// [`Worksheet::set_selection()`] method, see above, except that it can be
+    /// used to select several, non-contiguous, ranges of cells, like using the
+    /// Ctrl key in Excel to add ranges to a selection. The active cell is set
+    /// separately and must be in one of the ranges.
+    ///
+    /// If the worksheet has freeze or split panes the selection applies to the
+    /// active pane, which is the bottom right pane in the case of panes that
+    /// are split in both directions. See also
+    /// [`Worksheet::set_pane_selection_ranges()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `active_row`: The zero indexed row number of the active cell.
+    /// - `active_col`: The zero indexed column number of the active cell.
+    /// - `ranges`: A slice of `(first_row, first_col, last_row, last_col)`
+    ///   tuples for the ranges of selected cells.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// - [`XlsxError::ParameterError`] - The list of ranges is empty or the
+    ///   active cell isn't in any of the ranges.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates selecting several ranges of cells in
+    /// a worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_selection_ranges.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     // Select the ranges B2:C4, E2:F4 and B7, with E2 as the active cell.
+    ///     worksheet.set_selection_ranges(1, 4, &[(1, 1, 3, 2), (1, 4, 3, 5), (6, 1, 6, 1)])?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_selection_ranges(
+        &mut self,
+        active_row: RowNum,
+        active_col: ColNum,
+        ranges: &[(RowNum, ColNum, RowNum, ColNum)],
+    ) -> Result<&mut Worksheet, XlsxError> {
+        self.selection = self.selection_from_ranges(active_row, active_col, ranges)?;
+
+        Ok(self)
+    }
+
+    /// Set the selected cells in one of the panes of a worksheet.
+    ///
+    /// When a worksheet has freeze or split panes, see
+    /// [`Worksheet::set_freeze_panes()`] and [`Worksheet::set_split_panes()`],
+    /// each pane has its own cell selection. The selection set with
+    /// [`Worksheet::set_selection()`] or [`Worksheet::set_selection_ranges()`]
+    /// applies to the active pane and the other panes default to the cell at
+    /// the top left of the pane. The `set_pane_selection_ranges()` method can
+    /// be used to set the selection in a specific pane.
+    ///
+    /// The selection is ignored if the worksheet doesn't have a corresponding
+    /// pane.
+    ///
+    /// # Parameters
+    ///
+    /// - `pane`: A [`WorksheetPane`] enum value.
+    /// - `active_row`: The zero indexed row number of the active cell.
+    /// - `active_col`: The zero indexed column number of the active cell.
+    /// - `ranges`: A slice of `(first_row, first_col, last_row, last_col)`
+    ///   tuples for the ranges of selected cells.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// - [`XlsxError::ParameterError`] - The list of ranges is empty or the
+    ///   active cell isn't in any of the ranges.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the selections in the panes
+    /// of a worksheet with frozen panes.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_pane_selection_ranges.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, WorksheetPane, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    ///     let worksheet = workbook.add_worksheet();
+    ///
+    ///     // Freeze the top row and the first column.
+    ///     worksheet.set_freeze_panes(1, 1)?;
+    ///
+    ///     // Select cells in the header row pane.
+    ///     worksheet.set_pane_selection_ranges(WorksheetPane::TopRight, 0, 2, &[(0, 2, 0, 4)])?;
+    ///
+    ///     // Select cells in the main, active, pane.
+    ///     worksheet.set_selection_ranges(4, 2, &[(4, 2, 9, 4), (14, 2, 19, 4)])?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_pane_selection_ranges(
+        &mut self,
+        pane: WorksheetPane,
+        active_row: RowNum,
+        active_col: ColNum,
+        ranges: &[(RowNum, ColNum, RowNum, ColNum)],
+    ) -> Result<&mut Worksheet, XlsxError> {
+        let selection = self.selection_from_ranges(active_row, active_col, ranges)?;
+
+        self.pane_selections
+            .retain(|(position, _)| *position != pane);
+        self.pane_selections.push((pane, selection));
 
         Ok(self)
     }
 
+    // Create a selection from an active cell and a list of ranges.
+    fn selection_from_ranges(
+        &mut self,
+        active_row: RowNum,
+        active_col: ColNum,
+        ranges: &[(RowNum, ColNum, RowNum, ColNum)],
+    ) -> Result<Selection, XlsxError> {
+        if ranges.is_empty() {
+            let error = "At least one selection range must be specified.".to_string();
+            return Err(XlsxError::ParameterError(error));
+        }
+
+        if !self.check_dimensions_only(active_row, active_col) {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        let mut sqref = vec![];
+        let mut active_cell_id = None;
+
+        for (index, &(first_row, first_col, last_row, last_col)) in ranges.iter().enumerate() {
+            // Check rows and cols are in the allowed range.
+            if !self.check_dimensions_only(first_row, first_col)
+                || !self.check_dimensions_only(last_row, last_col)
+            {
+                return Err(XlsxError::RowColumnLimitError);
+            }
+
+            let (first_row, last_row) = (first_row.min(last_row), first_row.max(last_row));
+            let (first_col, last_col) = (first_col.min(last_col), first_col.max(last_col));
+
+            // Excel stores the index of the range that contains the active
+            // cell.
+            if active_cell_id.is_none()
+                && (first_row..=last_row).contains(&active_row)
+                && (first_col..=last_col).contains(&active_col)
+            {
+                active_cell_id = Some(index);
+            }
+
+            sqref.push(utility::cell_range(
+                first_row, first_col, last_row, last_col,
+            ));
+        }
+
+        let Some(active_cell_id) = active_cell_id else {
+            let error = format!(
+                "Active cell '{}' must be in one of the selection ranges.",
+                utility::row_col_to_cell(active_row, active_col)
+            );
+            return Err(XlsxError::ParameterError(error));
+        };
+
+        Ok(Selection {
+            active_cell: utility::row_col_to_cell(active_row, active_col),
+            sqref: sqref.join(" "),
+            active_cell_id,
+        })
+    }
+
     /// Set the first visible cell at the top left of a worksheet.
     ///
     /// This `set_top_left_cell()` method can be used to set the top leftmost
@@ -11281,12 +12015,10 @@ impl Worksheet {
         T: Serialize,
     {
         // Serialize the struct to determine the type name and the fields.
-        let mut headers = SerializerHeader {
-            struct_name: String::new(),
-            field_names: vec![],
-        };
+        let mut headers = SerializerHeader::new();
 
         data_structure.serialize(&mut headers)?;
+        headers.set_map_headers::<T>(&[]);
 
         self.store_serialization_headers(row, col, &headers, format)
     }
@@ -11422,13 +12154,16 @@ impl Worksheet {
     where
         T: Serialize,
     {
-        // Serialize the struct to determine the type name and the fields.
-        let mut headers = SerializerHeader {
-            struct_name: String::new(),
-            field_names: vec![],
-        };
+        // Serialize the struct to determine the type name and the fields,
+        // including the fields of any nested structs that are flattened.
+        let mut headers = SerializerHeader::new();
+        if header_options.flatten_nested_structs {
+            headers.nested_separator = Some(header_options.nested_separator.clone());
+        }
 
         data_structure.serialize(&mut headers)?;
+        headers.set_map_headers::<T>(&header_options.map_keys);
+
         self.store_serialization_headers_with_options(row, col, &headers, header_options)
     }
 
@@ -11639,7 +12374,8 @@ impl Worksheet {
         T: Deserialize<'de>,
     {
         // Deserialize the struct to determine the type name and the fields.
-        let headers = deserialize_headers::<T>();
+        let mut headers = deserialize_headers::<T>();
+        headers.set_map_headers::<T>(&[]);
 
         self.store_serialization_headers(row, col, &headers, format)
     }
@@ -11774,7 +12510,8 @@ impl Worksheet {
         T: Deserialize<'de>,
     {
         // Deserialize the struct to determine the type name and the fields.
-        let headers = deserialize_headers::<T>();
+        let mut headers = deserialize_headers::<T>();
+        headers.set_map_headers::<T>(&header_options.map_keys);
 
         self.store_serialization_headers_with_options(row, col, &headers, header_options)
     }
@@ -12123,69 +12860,393 @@ impl Worksheet {
             .get_column_dimensions(struct_name, field_name)
     }
 
-    // Store serialization headers and options.
+    /// Deserialize worksheet rows into structs.
+    ///
+    /// The `deserialize_rows()` method is the reader side counterpart of
+    /// [`Worksheet::serialize()`]. It reads the header row at the specified
+    /// cell, and the rows of data beneath it, and returns an iterator that
+    /// deserializes each row into a struct of type `T`. The header names are
+    /// mapped to the struct field names in the same way as for serialization,
+    /// so fields renamed with `#[serde(rename)]` are matched by their renamed
+    /// header. See [`Worksheet::deserialize_rows_with_options()`] for headers
+    /// that were renamed via [`SerializeFieldOptions`] or `#[xlsx()]`
+    /// attributes.
+    ///
+    /// The header row extends from the start cell to the first blank cell to
+    /// the right of it. The data rows extend to the first row that is blank
+    /// in all the header columns, or to the end of the worksheet data. Columns
+    /// whose header doesn't match a struct field are ignored by Serde, unless
+    /// the struct uses `#[serde(deny_unknown_fields)]`.
+    ///
+    /// The cell data is converted to the struct field types as follows:
+    ///
+    /// - Blank cells are omitted so they are read as `None` for `Option`
+    ///   fields, or as the default value for fields with `#[serde(default)]`.
+    ///   They are an error for other fields.
+    /// - Numbers can be read into any numeric type, as long as the value fits,
+    ///   or into a `String`. Strings of digits can also be read into numeric
+    ///   types.
+    /// - Booleans can be read from boolean cells, numbers, or the strings
+    ///   `"true"` and `"false"`.
+    /// - Dates and times are read as Excel serial numbers. These can be read
+    ///   into an [`ExcelDateTime`] or `f64` field.
+    /// - Formulas are read via their stored result value, if any. See
+    ///   [`Workbook::calculate_formulas()`](crate::Workbook::calculate_formulas).
+    /// - Unit enum variants are read from their name.
+    ///
+    /// The data can come from cells written to the worksheet, or from a
+    /// worksheet in an existing xlsx file that was loaded via
+    /// [`Workbook::from_template()`](crate::Workbook::from_template). Cells
+    /// written to the worksheet replace template cells at the same position.
+    /// Reading isn't supported in `constant_memory` mode since the worksheet
+    /// data is written to disk as it is added.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The zero indexed row number of the header row.
+    /// - `col`: The zero indexed column number of the first header.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// - [`XlsxError::ParameterError`] - No header found at the start cell.
+    /// - [`XlsxError::SerdeError`] - A row couldn't be deserialized into the
+    ///   struct, for example due to a missing field or a type mismatch. This
+    ///   error is returned by the iterator for the row.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates serializing data to a worksheet and
+    /// reading it back into structs.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_deserialize_rows.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Create a serializable and deserializable struct.
+    ///     #[derive(Debug, Deserialize, Serialize)]
+    ///     #[serde(rename_all = "PascalCase")]
+    ///     struct Produce {
+    ///         fruit: String,
+    ///         cost: f64,
+    ///         in_stock: Option<bool>,
+    ///     }
+    ///
+    ///     // Create some data instances.
+    ///     let items = [
+    ///         Produce {
+    ///             fruit: "Peach".to_string(),
+    ///             cost: 1.05,
+    ///             in_stock: Some(true),
+    ///         },
+    ///         Produce {
+    ///             fruit: "Plum".to_string(),
+    ///             cost: 0.15,
+    ///             in_stock: None,
+    ///         },
+    ///     ];
+    ///
+    ///     // Serialize the data to the worksheet.
+    ///     worksheet.deserialize_headers::<Produce>(0, 0)?;
+    ///     worksheet.serialize(&items)?;
+    ///
+    ///     // Read the data back from the worksheet.
+    ///     for item in worksheet.deserialize_rows::<Produce>(0, 0)? {
+    ///         let item = item?;
+    ///         println!("{item:?}");
+    ///     }
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
     #[cfg(feature = "serde")]
-    fn store_serialization_headers_with_options(
-        &mut self,
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn deserialize_rows<T>(
+        &self,
         row: RowNum,
         col: ColNum,
-        headers: &SerializerHeader,
-        header_options: &SerializeFieldOptions,
-    ) -> Result<&mut Worksheet, XlsxError> {
-        // Check that any custom field names match the actual field names.
-        let field_names: HashSet<String> = HashSet::from_iter(headers.field_names.clone());
-        for custom_header in &header_options.custom_headers {
-            if !field_names.contains(&custom_header.field_name) {
-                return Err(XlsxError::ParameterError(format!(
-                    "No custom field name '{}' found for struct '{}'",
-                    custom_header.field_name, headers.struct_name
-                )));
-            }
-        }
-
-        // Create a map of the user defined custom field settings to overwrite
-        // the default field settings.
-        let mut custom_fields: HashMap<&String, &CustomSerializeField> = HashMap::new();
-        for custom_header in &header_options.custom_headers {
-            custom_fields.insert(&custom_header.field_name, custom_header);
-        }
-
-        // Clone the header options to modify it and store it internally.
-        let mut header_options = header_options.clone();
-        header_options.struct_name.clone_from(&headers.struct_name);
-
-        // Create a "custom" header for default fields or replace them with user
-        // specified custom fields. The "use_custom_headers_only" overrides the
-        // default headers to allow users to skip fields.
-        if !header_options.use_custom_headers_only {
-            let mut custom_headers: Vec<CustomSerializeField> = vec![];
-
-            for field_name in &headers.field_names {
-                match custom_fields.get(field_name) {
-                    Some(custom_field) => {
-                        if !custom_field.skip {
-                            custom_headers.push((*custom_field).clone());
-                        }
-                    }
-                    None => custom_headers.push(CustomSerializeField::new(field_name)),
-                }
-            }
-
-            header_options.custom_headers = custom_headers;
-        }
-
-        self.store_custom_serialization_headers(row, col, &header_options)
+    ) -> Result<impl Iterator<Item = Result<T, XlsxError>> + '_, XlsxError>
+    where
+        T: DeserializeOwned,
+    {
+        self.deserialize_rows_with_options(row, col, &SerializeFieldOptions::new())
     }
 
-    // Store serialization headers with default options.
-    #[cfg(feature = "serde")]
-    fn store_serialization_headers(
-        &mut self,
-        row: RowNum,
-        col: ColNum,
-        headers: &SerializerHeader,
-        header_format: &Format,
-    ) -> Result<&mut Worksheet, XlsxError> {
+    /// Deserialize worksheet rows into structs, with additional options.
+    ///
+    /// This method is similar to [`Worksheet::deserialize_rows()`] except that
+    /// the header names are mapped to the struct field names via the
+    /// [`CustomSerializeField`] headers in the [`SerializeFieldOptions`]. This
+    /// is the reverse of the mapping used by
+    /// [`Worksheet::serialize_headers_with_options()`] so headers renamed with
+    /// [`CustomSerializeField::rename()`] are matched to their field and
+    /// columns of fields with [`CustomSerializeField::skip()`] are ignored.
+    ///
+    /// For structs that derive [`XlsxSerialize`] the options for the
+    /// `#[xlsx()]` attributes can be used via
+    /// [`XlsxSerialize::to_serialize_field_options()`], see the example below.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The zero indexed row number of the header row.
+    /// - `col`: The zero indexed column number of the first header.
+    /// - `header_options`: A [`SerializeFieldOptions`] instance.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// - [`XlsxError::ParameterError`] - No header found at the start cell.
+    /// - [`XlsxError::SerdeError`] - A row couldn't be deserialized into the
+    ///   struct, for example due to a missing field or a type mismatch. This
+    ///   error is returned by the iterator for the row.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates reading worksheet data into a struct
+    /// that uses `#[xlsx()]` attributes to rename the headers.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_deserialize_rows_with_options.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError, XlsxSerialize};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Create a struct with renamed headers.
+    ///     #[derive(Debug, Deserialize, Serialize, XlsxSerialize)]
+    ///     struct Produce {
+    ///         #[xlsx(rename = "Item")]
+    ///         fruit: String,
+    ///
+    ///         #[xlsx(rename = "Price")]
+    ///         cost: f64,
+    ///     }
+    ///
+    ///     // Write some data to the worksheet, such as a user might enter.
+    ///     worksheet.write_row(0, 0, ["Item", "Price"])?;
+    ///     worksheet.write(1, 0, "Peach")?;
+    ///     worksheet.write(1, 1, 1.05)?;
+    ///     worksheet.write(2, 0, "Plum")?;
+    ///     worksheet.write(2, 1, 0.15)?;
+    ///
+    ///     // Read the data using the header options from the `xlsx` attributes.
+    ///     let header_options = Produce::to_serialize_field_options();
+    ///
+    ///     for item in worksheet.deserialize_rows_with_options::<Produce>(0, 0, &header_options)? {
+    ///         let item = item?;
+    ///         println!("{item:?}");
+    ///     }
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn deserialize_rows_with_options<T>(
+        &self,
+        row: RowNum,
+        col: ColNum,
+        header_options: &SerializeFieldOptions,
+    ) -> Result<impl Iterator<Item = Result<T, XlsxError>> + '_, XlsxError>
+    where
+        T: DeserializeOwned,
+    {
+        if row >= ROW_MAX || col >= COL_MAX {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        // Read the header row and map the header names to the field names.
+        // Columns of skipped fields are ignored.
+        let mut columns = vec![];
+        for header_col in col..COL_MAX {
+            let Some(header) = self.read_cell_value(row, header_col) else {
+                break;
+            };
+            let header = header.to_string_value();
+
+            let custom_header = header_options
+                .custom_headers
+                .iter()
+                .find(|custom_header| custom_header.header_name == header);
+
+            match custom_header {
+                Some(custom_header) if custom_header.skip => {}
+                Some(custom_header) => columns.push((header_col, custom_header.field_name.clone())),
+                None => columns.push((header_col, header)),
+            }
+        }
+
+        if columns.is_empty() {
+            return Err(XlsxError::ParameterError(format!(
+                "No headers found in cell '{}'",
+                utility::row_col_to_cell(row, col)
+            )));
+        }
+
+        // Get the last row of the worksheet data, including any template data.
+        let last_row = self
+            .data_table
+            .keys()
+            .chain(
+                self.template
+                    .iter()
+                    .flat_map(|template| template.rows.keys()),
+            )
+            .max()
+            .copied()
+            .unwrap_or(row);
+
+        let rows = (row + 1..=last_row)
+            .map(move |data_row| {
+                let cells: Vec<(String, CellValue)> = columns
+                    .iter()
+                    .filter_map(|(data_col, field_name)| {
+                        self.read_cell_value(data_row, *data_col)
+                            .map(|value| (field_name.clone(), value))
+                    })
+                    .collect();
+
+                (data_row, cells)
+            })
+            .take_while(|(_, cells)| !cells.is_empty())
+            .map(|(data_row, cells)| {
+                T::deserialize(RowDeserializer::new(cells)).map_err(|error| match error {
+                    XlsxError::SerdeError(message) => XlsxError::SerdeError(format!(
+                        "{message} in worksheet row {}",
+                        data_row + 1
+                    )),
+                    error => error,
+                })
+            });
+
+        Ok(rows)
+    }
+
+    // Read the value of a worksheet cell for deserialization. Cells written to
+    // the worksheet take precedence over template cells.
+    #[cfg(feature = "serde")]
+    fn read_cell_value(&self, row: RowNum, col: ColNum) -> Option<CellValue> {
+        let Some(cell) = self
+            .data_table
+            .get(&row)
+            .and_then(|columns| columns.get(&col))
+        else {
+            return self
+                .template
+                .as_ref()
+                .and_then(|template| template.cell_value(row, col, self.is_1904_date));
+        };
+
+        match cell {
+            CellType::Blank { .. } => None,
+            CellType::Boolean { boolean, .. } => Some(CellValue::Boolean(*boolean)),
+            CellType::Error { error, .. } => Some(CellValue::Error(error.to_string())),
+            CellType::Number { number, .. } | CellType::DateTime { number, .. } => {
+                Some(CellValue::Number(*number))
+            }
+            CellType::String { string, .. }
+            | CellType::RichString {
+                raw_string: string, ..
+            }
+            | CellType::InlineString { string, .. } => Some(CellValue::String(string.to_string())),
+            CellType::Formula { result, .. } | CellType::ArrayFormula { result, .. } => {
+                CellValue::from_formula_result(result, self.is_1904_date)
+            }
+        }
+    }
+
+    // Store serialization headers and options.
+    #[cfg(feature = "serde")]
+    fn store_serialization_headers_with_options(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        headers: &SerializerHeader,
+        header_options: &SerializeFieldOptions,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Check that any custom field names match the actual field names.
+        let field_names: HashSet<String> = HashSet::from_iter(headers.field_names.clone());
+        for custom_header in &header_options.custom_headers {
+            if !field_names.contains(&custom_header.field_name) {
+                return Err(XlsxError::ParameterError(format!(
+                    "No custom field name '{}' found for struct '{}'",
+                    custom_header.field_name, headers.struct_name
+                )));
+            }
+        }
+
+        // Create a map of the user defined custom field settings to overwrite
+        // the default field settings.
+        let mut custom_fields: HashMap<&String, &CustomSerializeField> = HashMap::new();
+        for custom_header in &header_options.custom_headers {
+            custom_fields.insert(&custom_header.field_name, custom_header);
+        }
+
+        // Clone the header options to modify it and store it internally.
+        let mut header_options = header_options.clone();
+        header_options.struct_name.clone_from(&headers.struct_name);
+
+        // Create a "custom" header for default fields or replace them with user
+        // specified custom fields. The "use_custom_headers_only" overrides the
+        // default headers to allow users to skip fields.
+        if !header_options.use_custom_headers_only {
+            let mut custom_headers: Vec<CustomSerializeField> = vec![];
+
+            for field_name in &headers.field_names {
+                match custom_fields.get(field_name) {
+                    Some(custom_field) => {
+                        if !custom_field.skip {
+                            custom_headers.push((*custom_field).clone());
+                        }
+                    }
+                    None => custom_headers.push(CustomSerializeField::new(field_name)),
+                }
+            }
+
+            header_options.custom_headers = custom_headers;
+        }
+
+        self.store_custom_serialization_headers(row, col, &header_options)?;
+
+        // Store all the field names, including any that weren't selected, to
+        // distinguish them from unknown map keys.
+        if let Some(header_config) = self.serializer_state.structs.get_mut(&headers.struct_name) {
+            header_config
+                .known_fields
+                .extend(headers.field_names.iter().cloned());
+        }
+
+        self.store_map_struct(headers);
+
+        Ok(self)
+    }
+
+    // Store serialization headers with default options.
+    #[cfg(feature = "serde")]
+    fn store_serialization_headers(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        headers: &SerializerHeader,
+        header_format: &Format,
+    ) -> Result<&mut Worksheet, XlsxError> {
         // Convert the field names to custom header structs.
         let custom_headers: Vec<CustomSerializeField> = headers
             .field_names
@@ -12201,7 +13262,20 @@ impl Worksheet {
             ..Default::default()
         };
 
-        self.store_custom_serialization_headers(row, col, &header_options)
+        self.store_custom_serialization_headers(row, col, &header_options)?;
+        self.store_map_struct(headers);
+
+        Ok(self)
+    }
+
+    // Store the name of the struct that is serialized as a map, if any.
+    #[cfg(feature = "serde")]
+    fn store_map_struct(&mut self, headers: &SerializerHeader) {
+        if headers.is_map {
+            self.serializer_state
+                .map_struct
+                .clone_from(&headers.struct_name);
+        }
     }
 
     // Write serialization headers to the worksheet.
@@ -12228,6 +13302,15 @@ impl Worksheet {
             ));
         }
 
+        // Adding columns for unknown map keys isn't supported in constant
+        // memory mode since the header row may already have been flushed.
+        if header_options.add_unknown_keys && self.use_constant_memory {
+            return Err(XlsxError::ParameterError(
+                "`SerializeFieldOptions::add_unknown_keys()` isn't supported in constant memory mode"
+                    .to_string(),
+            ));
+        }
+
         // Check for empty struct members.
         if header_options.custom_headers.is_empty() {
             return Err(XlsxError::ParameterError(format!(
@@ -12292,114 +13375,879 @@ impl Worksheet {
                 }
             }
 
-            fields.insert(custom_header.field_name.clone(), custom_header);
-        }
+            fields.insert(custom_header.field_name.clone(), custom_header);
+        }
+
+        // Start the data serialization one row down if headers were written.
+        if write_headers {
+            max_row += 1;
+        }
+
+        // If a previous serialization was carried out with the same struct name
+        // then write the previous table formatting.
+        if let Some(header_config) = self
+            .serializer_state
+            .structs
+            .get_mut(&header_options.struct_name)
+        {
+            if let Some(table_data) = header_config.get_table() {
+                self.write_serialized_table(&table_data)?;
+            }
+        }
+
+        // Clone the new user defined table format, if present.
+        let table = match &header_options.table {
+            Some(table) => {
+                let mut table = table.clone();
+                if !header_options.has_headers {
+                    table.show_header_row = false;
+                }
+                table.has_header_cells = true;
+                Some(table)
+            }
+            None => None,
+        };
+
+        // Store meta data for the struct/headers.
+        self.serializer_state.structs.insert(
+            header_options.struct_name.clone(),
+            SerializationHeaderConfig {
+                fields,
+                min_row,
+                min_col,
+                max_row,
+                max_col,
+                table,
+                nested_separator: header_options
+                    .flatten_nested_structs
+                    .then(|| header_options.nested_separator.clone()),
+                known_fields: header_options
+                    .custom_headers
+                    .iter()
+                    .map(|custom_header| custom_header.field_name.clone())
+                    .collect(),
+                has_headers: header_options.has_headers,
+                header_format: header_options.header_format.clone(),
+                add_unknown_keys: header_options.add_unknown_keys,
+            },
+        );
+
+        Ok(self)
+    }
+
+    // Serialize the parent data structure to the worksheet.
+    #[cfg(feature = "serde")]
+    fn serialize_data_structure<T>(&mut self, data_structure: &T) -> Result<(), XlsxError>
+    where
+        T: Serialize,
+    {
+        // Reset the struct nesting state in case of a previous error.
+        self.serializer_state.levels.clear();
+
+        data_structure.serialize(self)?;
+        Ok(())
+    }
+
+    // Check that a serialized map key matches a known field. Unknown keys are
+    // added as new columns after the existing columns, if that option is on,
+    // or else they raise an error.
+    #[cfg(feature = "serde")]
+    pub(crate) fn check_serialized_map_key(&mut self) -> Result<(), XlsxError> {
+        let struct_name = &self.serializer_state.current_struct;
+        let field_name = &self.serializer_state.current_field;
+
+        let Some(header_config) = self.serializer_state.structs.get(struct_name) else {
+            return Ok(());
+        };
+
+        if header_config.fields.contains_key(field_name)
+            || header_config.known_fields.contains(field_name)
+        {
+            return Ok(());
+        }
+
+        // Keys of flattened nested structs and maps are the prefix of the
+        // known nested field names.
+        if let Some(separator) = &header_config.nested_separator {
+            let prefix = format!("{field_name}{separator}");
+            if header_config
+                .known_fields
+                .iter()
+                .any(|known_field| known_field.starts_with(&prefix))
+            {
+                return Ok(());
+            }
+        }
+
+        if !header_config.add_unknown_keys {
+            return Err(XlsxError::SerdeError(format!(
+                "Unknown map key '{field_name}' for struct '{struct_name}'. \
+                Use `SerializeFieldOptions::add_unknown_keys()` to add unknown keys as columns."
+            )));
+        }
+
+        let row = header_config.min_row;
+        let col = header_config.max_col + 1;
+        let has_headers = header_config.has_headers;
+        let header_format = header_config.header_format.clone();
+
+        let struct_name = struct_name.clone();
+        let field_name = field_name.clone();
+
+        if !self.check_dimensions_only(row, col) {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        // Write the key as the header of the new column.
+        if has_headers {
+            if let Some(format) = &header_format {
+                self.write_string_with_format(row, col, &field_name, format)?;
+            } else {
+                self.write_string(row, col, &field_name)?;
+            }
+        }
+
+        let mut custom_header = CustomSerializeField::new(&field_name);
+        custom_header.col = col;
+
+        if let Some(header_config) = self.serializer_state.structs.get_mut(&struct_name) {
+            header_config.max_col = col;
+            header_config.known_fields.insert(field_name.clone());
+            header_config.fields.insert(field_name, custom_header);
+        }
+
+        Ok(())
+    }
+
+    // Serialize individual data items to a worksheet cell.
+    #[cfg(feature = "serde")]
+    pub(crate) fn serialize_to_worksheet_cell(
+        &mut self,
+        data: impl IntoExcelData,
+    ) -> Result<(), XlsxError> {
+        let result = self.serializer_state.current_state();
+
+        match result {
+            Ok(result) => {
+                let (row, col, value_format) = result;
+                match &*value_format {
+                    Some(format) => self.write_with_format(row, col, data, format).map(|_| ()),
+                    None => self.write(row, col, data).map(|_| ()),
+                }
+            }
+            Err(()) => Ok(()),
+        }
+    }
+
+    // Add any tables that were added as part of serialization formatting.
+    #[cfg(feature = "serde")]
+    pub(crate) fn store_serialized_tables(&mut self) -> Result<&mut Worksheet, XlsxError> {
+        let tables = self.serializer_state.get_tables();
+
+        for table_data in tables {
+            self.write_serialized_table(&table_data)?;
+        }
+
+        Ok(self)
+    }
+
+    // Write a table that is part of serialization formatting.
+    #[cfg(feature = "serde")]
+    pub(crate) fn write_serialized_table(
+        &mut self,
+        table_data: &TableData,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        let min_col = table_data.1;
+        let max_col = table_data.3;
+        let min_row = table_data.0;
+        let mut max_row = table_data.2;
+        let table = &table_data.4;
+
+        if table.show_total_row {
+            max_row += 1;
+        }
+
+        self.add_table(min_row, min_col, max_row, max_col, table)
+    }
+
+    // -----------------------------------------------------------------------
+    // Worksheet dataframe methods.
+    // -----------------------------------------------------------------------
+
+    /// Write a Polars dataframe to a worksheet.
+    ///
+    /// The `write_dataframe()` method writes the data in a Polars
+    /// [`DataFrame`] to a worksheet, starting at the specified cell, with
+    /// the column names as a header row. The layout and formatting of the
+    /// data can be controlled via the [`DataFrameOptions`] struct. This
+    /// includes adding a worksheet [`Table`] around the data, autofitting the
+    /// columns and setting formats for the data by column name or data type.
+    ///
+    /// The Polars data types are mapped to Excel cell types as follows:
+    ///
+    /// | Polars data type            | Excel cell type                        |
+    /// | :-------------------------- | :------------------------------------- |
+    /// | `Boolean`                   | Boolean.                               |
+    /// | Integers and floats         | Number.                                |
+    /// | `Decimal`                   | Number with a `0.00` style format to match the scale. |
+    /// | `String`                    | String.                                |
+    /// | `Categorical` and `Enum`    | String.                                |
+    /// | `Date`                      | Date with a `yyyy-mm-dd` format.       |
+    /// | `Datetime`                  | Datetime with a `yyyy-mm-dd hh:mm:ss` format. |
+    /// | `Time`                      | Time with a `hh:mm:ss` format.         |
+    /// | `Duration`                  | Number with a `[h]:mm:ss` format.      |
+    /// | Null values                 | Blank cell, or a user defined string.  |
+    ///
+    /// The date and time formats can be changed via [`DataFrameOptions`].
+    /// Datetimes with a timezone are written as UTC values. Other data types
+    /// such as lists, structs and binary data aren't supported and will raise
+    /// an error.
+    ///
+    /// This requires the `polars` feature to be enabled.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The zero indexed row number.
+    /// - `col`: The zero indexed column number.
+    /// - `dataframe`: A reference to a Polars [`DataFrame`].
+    /// - `options`: A [`DataFrameOptions`] struct reference.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// - [`XlsxError::ParameterError`] - Unsupported Polars data type.
+    /// - [`XlsxError::MaxStringLengthExceeded`] - String exceeds Excel's limit
+    ///   of 32,767 characters.
+    /// - [`XlsxError::DateTimeRangeError`] - Date or datetime outside of the
+    ///   Excel range of 1900-01-01 to 9999-12-31.
+    /// - [`XlsxError::TableError`] - Table errors, see
+    ///   [`Worksheet::add_table()`].
+    /// - [`XlsxError::PolarsError`] - Polars errors when reading the
+    ///   dataframe data.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing a Polars dataframe with
+    /// several different data types to a worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_write_dataframe.rs
+    /// #
+    /// # use polars::prelude::*;
+    /// # use rust_xlsxwriter::{DataFrameOptions, Format, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Create a dataframe with a null value and a date column. Polars dates
+    ///     // are stored as days since 1970-01-01.
+    ///     let mut df = df!(
+    ///         "Region" => &["East", "West", "North"],
+    ///         "Sales" => &[Some(1250), None, Some(1810)],
+    ///     )?;
+    ///
+    ///     let dates = Column::new("Date".into(), &[19723, 19724, 19725]);
+    ///     df.with_column(dates.cast(&DataType::Date)?)?;
+    ///
+    ///     // Write the dataframe with a bold header and autofit the columns.
+    ///     let options = DataFrameOptions::new()
+    ///         .set_header_format(&Format::new().set_bold())
+    ///         .set_autofit(true);
+    ///
+    ///     worksheet.write_dataframe(0, 0, &df, &options)?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    #[cfg(feature = "polars")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "polars")))]
+    pub fn write_dataframe(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        dataframe: &DataFrame,
+        options: &DataFrameOptions,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        let num_rows = dataframe.height();
+        let num_cols = dataframe.width();
+
+        if num_cols == 0 {
+            return Ok(self);
+        }
+
+        // The table header row setting takes precedence, if there is one.
+        let has_header = match &options.table {
+            Some(table) => table.show_header_row,
+            None => options.has_header,
+        };
+
+        // Check that the data, and at least one row for a table, fits in the
+        // worksheet.
+        let first_data_row = row as usize + usize::from(has_header);
+        let last_row = first_data_row + num_rows.max(1) - 1;
+        let last_col = col as usize + num_cols - 1;
+        let (Ok(last_row), Ok(last_col)) = (RowNum::try_from(last_row), ColNum::try_from(last_col))
+        else {
+            return Err(XlsxError::RowColumnLimitError);
+        };
+
+        if !self.check_dimensions_only(last_row, last_col) {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        // Check for unsupported data types before writing any data.
+        for column in dataframe.columns() {
+            let dtype = column.dtype();
+            let is_supported = dtype.is_primitive_numeric()
+                || matches!(
+                    dtype,
+                    DataType::Boolean
+                        | DataType::String
+                        | DataType::Date
+                        | DataType::Datetime(..)
+                        | DataType::Duration(_)
+                        | DataType::Time
+                        | DataType::Decimal(..)
+                        | DataType::Categorical(..)
+                        | DataType::Enum(..)
+                        | DataType::Null
+                );
+
+            if !is_supported {
+                return Err(XlsxError::ParameterError(format!(
+                    "Polars data type '{dtype}' in column '{}' is not supported",
+                    column.name()
+                )));
+            }
+        }
+
+        for (offset, column) in dataframe.columns().iter().enumerate() {
+            let col = col + offset as ColNum;
+            let name = column.name().as_str();
+
+            if has_header {
+                match &options.header_format {
+                    Some(format) => self.write_string_with_format(row, col, name, format)?,
+                    None => self.write_string(row, col, name)?,
+                };
+            }
+
+            // Write categoricals as strings and decimals as numbers with a
+            // format to match the decimal scale.
+            let series = column.as_materialized_series();
+            let (series, decimal_format) = match series.dtype() {
+                DataType::Categorical(..) | DataType::Enum(..) => {
+                    (series.cast(&DataType::String)?, None)
+                }
+                DataType::Decimal(_, scale) => (
+                    series.cast(&DataType::Float64)?,
+                    column_decimal_format(*scale),
+                ),
+                _ => (series.clone(), None),
+            };
+
+            let column_format = options.column_formats.get(name);
+            let value_format = column_format.or(decimal_format.as_ref());
+
+            for (index, value) in series.iter().enumerate() {
+                let row = (first_data_row + index) as RowNum;
+
+                if value.is_null() {
+                    self.write_dataframe_null(row, col, column_format, options)?;
+                } else {
+                    self.write_dataframe_value(row, col, &value, value_format, options)?;
+                }
+            }
+        }
+
+        if let Some(table) = &options.table {
+            let mut table = table.clone();
+            let mut last_row = last_row;
+
+            if table.show_total_row {
+                last_row += 1;
+            }
+
+            // The headers have already been written, with any header format.
+            table.has_header_cells = true;
+
+            self.add_table(row, col, last_row, last_col, &table)?;
+        }
+
+        if options.autofit {
+            self.autofit_range(row, col, last_row, last_col);
+        }
+
+        Ok(self)
+    }
+
+    // Write a Polars dataframe value to a worksheet cell, mapping it to the
+    // equivalent Excel type.
+    #[cfg(feature = "polars")]
+    fn write_dataframe_value(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        value: &AnyValue,
+        column_format: Option<&Format>,
+        options: &DataFrameOptions,
+    ) -> Result<(), XlsxError> {
+        match value {
+            AnyValue::Boolean(boolean) => {
+                self.write_dataframe_cell(row, col, *boolean, column_format)
+            }
+            AnyValue::String(string) => self.write_dataframe_cell(row, col, *string, column_format),
+            AnyValue::StringOwned(string) => {
+                self.write_dataframe_cell(row, col, string.as_str(), column_format)
+            }
+            AnyValue::Date(days) => {
+                let datetime =
+                    ExcelDateTime::from_serial_datetime(unix_days_to_serial(f64::from(*days)))?;
+                let format = column_format.unwrap_or(&options.date_format);
+                self.write_dataframe_cell(row, col, datetime, Some(format))
+            }
+            AnyValue::Datetime(value, unit, _) | AnyValue::DatetimeOwned(value, unit, _) => {
+                let serial = ColumnTimeUnit::from(unit).unix_time_to_serial(*value as f64);
+                let datetime = ExcelDateTime::from_serial_datetime(serial)?;
+                let format = column_format.unwrap_or(&options.datetime_format);
+                self.write_dataframe_cell(row, col, datetime, Some(format))
+            }
+            AnyValue::Time(nanoseconds) => {
+                let time = ColumnTimeUnit::Nanoseconds.to_days(*nanoseconds as f64);
+                let datetime = ExcelDateTime::from_serial_datetime(time)?;
+                let format = column_format.unwrap_or(&options.time_format);
+                self.write_dataframe_cell(row, col, datetime, Some(format))
+            }
+            AnyValue::Duration(value, unit) => {
+                let days = ColumnTimeUnit::from(unit).to_days(*value as f64);
+                let format = column_format.unwrap_or(&options.duration_format);
+                self.write_dataframe_cell(row, col, days, Some(format))
+            }
+            _ => match value.extract::<f64>() {
+                Some(number) => self.write_dataframe_cell(row, col, number, column_format),
+                None => Err(XlsxError::ParameterError(format!(
+                    "Polars value '{value}' is not supported"
+                ))),
+            },
+        }
+    }
+
+    // Write a null dataframe or record batch value as the user defined null
+    // string, or as a blank cell if the column has a format.
+    #[cfg(any(feature = "polars", feature = "arrow"))]
+    fn write_dataframe_null(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        column_format: Option<&Format>,
+        options: &DataFrameOptions,
+    ) -> Result<(), XlsxError> {
+        match &options.null_value {
+            Some(null_value) => {
+                self.write_dataframe_cell(row, col, null_value.as_str(), column_format)
+            }
+            None => match column_format {
+                Some(format) => self.write_blank(row, col, format).map(|_| ()),
+                None => Ok(()),
+            },
+        }
+    }
+
+    // Write a dataframe or record batch value with an optional format.
+    #[cfg(any(feature = "polars", feature = "arrow"))]
+    fn write_dataframe_cell(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        data: impl IntoExcelData,
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        match format {
+            Some(format) => self.write_with_format(row, col, data, format).map(|_| ()),
+            None => self.write(row, col, data).map(|_| ()),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Worksheet record batch methods.
+    // -----------------------------------------------------------------------
+
+    /// Write an Apache Arrow record batch to a worksheet.
+    ///
+    /// The `write_record_batch()` method writes the data in an Arrow
+    /// [`RecordBatch`] to a worksheet, starting at the specified cell, with
+    /// the schema field names as a header row. This is similar to the way
+    /// that [`Worksheet::serialize_headers()`] writes the struct field names
+    /// as headers for serialized data.
+    ///
+    /// The layout and formatting of the data can be controlled via the
+    /// [`DataFrameOptions`] struct, in the same way as for Polars dataframes
+    /// written with `Worksheet::write_dataframe()`. This includes turning off
+    /// or formatting the header row, adding a worksheet [`Table`] around the
+    /// data, autofitting the columns and setting formats for the data by field
+    /// name or data type.
+    ///
+    /// The Arrow data types are mapped to Excel cell types as follows:
+    ///
+    /// | Arrow data type                 | Excel cell type                    |
+    /// | :------------------------------ | :--------------------------------- |
+    /// | `Boolean`                       | Boolean.                           |
+    /// | Integers and floats             | Number.                            |
+    /// | `Decimal*`                      | Number with a `0.00` style format to match the scale. |
+    /// | `Utf8`, `LargeUtf8`, `Utf8View` | String.                            |
+    /// | `Dictionary` of strings         | String.                            |
+    /// | `Date32` and `Date64`           | Date with a `yyyy-mm-dd` format.   |
+    /// | `Timestamp`                     | Datetime with a `yyyy-mm-dd hh:mm:ss` format. |
+    /// | `Time32` and `Time64`           | Time with a `hh:mm:ss` format.     |
+    /// | `Duration`                      | Number with a `[h]:mm:ss` format.  |
+    /// | Null values                     | Blank cell, or a user defined string. |
+    ///
+    /// The date and time formats can be changed via [`DataFrameOptions`].
+    /// Timestamps with a timezone are written as UTC values. Other data types
+    /// such as lists, structs and binary data aren't supported and will raise
+    /// an error.
+    ///
+    /// See [`Worksheet::write_record_batches()`] for writing a sequence of
+    /// record batches, for example from a Parquet or Arrow IPC reader.
+    ///
+    /// This requires the `arrow` feature to be enabled.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The zero indexed row number.
+    /// - `col`: The zero indexed column number.
+    /// - `batch`: A reference to an Arrow [`RecordBatch`].
+    /// - `options`: A [`DataFrameOptions`] struct reference.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// - [`XlsxError::ParameterError`] - Unsupported Arrow data type.
+    /// - [`XlsxError::MaxStringLengthExceeded`] - String exceeds Excel's limit
+    ///   of 32,767 characters.
+    /// - [`XlsxError::DateTimeRangeError`] - Date or datetime outside of the
+    ///   Excel range of 1900-01-01 to 9999-12-31.
+    /// - [`XlsxError::TableError`] - Table errors, see
+    ///   [`Worksheet::add_table()`].
+    /// - [`XlsxError::ArrowError`] - Arrow errors when converting the record
+    ///   batch data.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing an Arrow record batch with
+    /// several different data types to a worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_write_record_batch.rs
+    /// #
+    /// # use std::sync::Arc;
+    /// #
+    /// # use arrow::array::{ArrayRef, Date32Array, Int32Array, StringArray};
+    /// # use arrow::record_batch::RecordBatch;
+    /// # use rust_xlsxwriter::{DataFrameOptions, Format, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Create a record batch with a null value and a date column. Arrow
+    ///     // dates are stored as days since 1970-01-01.
+    ///     let regions: ArrayRef = Arc::new(StringArray::from(vec!["East", "West", "North"]));
+    ///     let sales: ArrayRef = Arc::new(Int32Array::from(vec![Some(1250), None, Some(1810)]));
+    ///     let dates: ArrayRef = Arc::new(Date32Array::from(vec![19723, 19724, 19725]));
+    ///
+    ///     let batch = RecordBatch::try_from_iter([
+    ///         ("Region", regions),
+    ///         ("Sales", sales),
+    ///         ("Date", dates),
+    ///     ])?;
+    ///
+    ///     // Write the record batch with a bold header and autofit the columns.
+    ///     let options = DataFrameOptions::new()
+    ///         .set_header_format(&Format::new().set_bold())
+    ///         .set_autofit(true);
+    ///
+    ///     worksheet.write_record_batch(0, 0, &batch, &options)?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    #[cfg(feature = "arrow")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    pub fn write_record_batch(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        batch: &RecordBatch,
+        options: &DataFrameOptions,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        self.write_record_batches(row, col, [Ok(batch.clone())], options)
+    }
+
+    /// Write a sequence of Apache Arrow record batches to a worksheet.
+    ///
+    /// The `write_record_batches()` method writes the data from an iterator
+    /// of Arrow [`RecordBatch`] results, such as a `RecordBatchReader` from a
+    /// Parquet or Arrow IPC file, to a worksheet. The field names of the
+    /// first record batch are written as a header row and the data from each
+    /// batch is written in the following rows. The record batches must all
+    /// have the same schema.
+    ///
+    /// The data is written row by row, and one batch at a time, so this
+    /// method can be used with a worksheet created with
+    /// [`Workbook::add_worksheet_with_constant_memory()`] to write large
+    /// amounts of data without holding all of it in memory. Note, in constant
+    /// memory mode the autofit option only uses the data in the last row, see
+    /// [`Worksheet::autofit()`].
+    ///
+    /// See [`Worksheet::write_record_batch()`] for details of how the Arrow
+    /// data types are mapped to Excel cell types and [`DataFrameOptions`] for
+    /// the options that control the layout and formatting of the data. Any
+    /// table is added, and the columns autofitted, after all the record
+    /// batches are written.
+    ///
+    /// [`Workbook::add_worksheet_with_constant_memory()`]:
+    ///     crate::Workbook::add_worksheet_with_constant_memory
+    ///
+    /// This requires the `arrow` feature to be enabled.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The zero indexed row number.
+    /// - `col`: The zero indexed column number.
+    /// - `batches`: An iterator of `Result<RecordBatch, ArrowError>` items.
+    /// - `options`: A [`DataFrameOptions`] struct reference.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    /// - [`XlsxError::ParameterError`] - Unsupported Arrow data type or a
+    ///   record batch with a different schema to the first batch.
+    /// - [`XlsxError::MaxStringLengthExceeded`] - String exceeds Excel's limit
+    ///   of 32,767 characters.
+    /// - [`XlsxError::DateTimeRangeError`] - Date or datetime outside of the
+    ///   Excel range of 1900-01-01 to 9999-12-31.
+    /// - [`XlsxError::TableError`] - Table errors, see
+    ///   [`Worksheet::add_table()`].
+    /// - [`XlsxError::ArrowError`] - Arrow errors when reading or converting
+    ///   the record batch data.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates writing several Arrow record
+    /// batches to a worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_write_record_batches.rs
+    /// #
+    /// # use std::sync::Arc;
+    /// #
+    /// # use arrow::array::{ArrayRef, Float64Array, UInt32Array};
+    /// # use arrow::record_batch::RecordBatch;
+    /// # use rust_xlsxwriter::{DataFrameOptions, Table, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     // Create an iterator of record batches, similar to the output of a
+    ///     // Parquet or Arrow IPC reader.
+    ///     let batches = (0..4).map(|batch_num| {
+    ///         let ids: ArrayRef = Arc::new(UInt32Array::from_iter_values(
+    ///             (0..100).map(|n| batch_num * 100 + n),
+    ///         ));
+    ///         let values: ArrayRef = Arc::new(Float64Array::from_iter_values(
+    ///             (0..100).map(|n| f64::from(n) * 0.5),
+    ///         ));
+    ///
+    ///         RecordBatch::try_from_iter([("Id", ids), ("Value", values)])
+    ///     });
+    ///
+    ///     // Write the record batches to the worksheet as a table.
+    ///     let options = DataFrameOptions::new().set_table(&Table::new());
+    ///
+    ///     worksheet.write_record_batches(0, 0, batches, &options)?;
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    #[cfg(feature = "arrow")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    pub fn write_record_batches<I>(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        batches: I,
+        options: &DataFrameOptions,
+    ) -> Result<&mut Worksheet, XlsxError>
+    where
+        I: IntoIterator<Item = Result<RecordBatch, ArrowError>>,
+    {
+        // The table header row setting takes precedence, if there is one.
+        let has_header = match &options.table {
+            Some(table) => table.show_header_row,
+            None => options.has_header,
+        };
+
+        let start_data_row = row as usize + usize::from(has_header);
+        let mut first_data_row = start_data_row;
+        let mut first_schema = None;
+
+        for batch in batches {
+            let batch = batch?;
+            let schema = batch.schema();
+            let num_cols = batch.num_columns();
+            let num_rows = batch.num_rows();
+
+            if num_cols == 0 {
+                continue;
+            }
+
+            // Convert the columns before writing any data so that unsupported
+            // data types raise an error.
+            let columns = schema
+                .fields()
+                .iter()
+                .zip(batch.columns())
+                .map(|(field, array)| RecordBatchColumn::new(array, field.name(), options))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Check that the data, and at least one row for a table, fits in
+            // the worksheet.
+            let last_row = first_data_row + num_rows.max(1) - 1;
+            let last_col = col as usize + num_cols - 1;
+            let (Ok(last_row), Ok(last_col)) =
+                (RowNum::try_from(last_row), ColNum::try_from(last_col))
+            else {
+                return Err(XlsxError::RowColumnLimitError);
+            };
+
+            if !self.check_dimensions_only(last_row, last_col) {
+                return Err(XlsxError::RowColumnLimitError);
+            }
+
+            // Write the field names of the first batch as headers and check
+            // that subsequent batches have the same fields.
+            match &first_schema {
+                None => {
+                    if has_header {
+                        for (offset, field) in schema.fields().iter().enumerate() {
+                            let col = col + offset as ColNum;
+
+                            match &options.header_format {
+                                Some(format) => {
+                                    self.write_string_with_format(row, col, field.name(), format)?
+                                }
+                                None => self.write_string(row, col, field.name())?,
+                            };
+                        }
+                    }
+
+                    first_schema = Some(schema.clone());
+                }
+                Some(first_schema) => {
+                    if first_schema.fields() != schema.fields() {
+                        return Err(XlsxError::ParameterError(
+                            "Arrow record batches must have the same schema".to_string(),
+                        ));
+                    }
+                }
+            }
+
+            let column_formats: Vec<Option<&Format>> = schema
+                .fields()
+                .iter()
+                .map(|field| options.column_formats.get(field.name()))
+                .collect();
 
-        // Start the data serialization one row down if headers were written.
-        if write_headers {
-            max_row += 1;
-        }
+            for index in 0..num_rows {
+                let row = (first_data_row + index) as RowNum;
 
-        // If a previous serialization was carried out with the same struct name
-        // then write the previous table formatting.
-        if let Some(header_config) = self
-            .serializer_state
-            .structs
-            .get_mut(&header_options.struct_name)
-        {
-            if let Some(table_data) = header_config.get_table() {
-                self.write_serialized_table(&table_data)?;
-            }
-        }
+                for (offset, column) in columns.iter().enumerate() {
+                    let col = col + offset as ColNum;
+                    let column_format = column_formats[offset];
 
-        // Clone the new user defined table format, if present.
-        let table = match &header_options.table {
-            Some(table) => {
-                let mut table = table.clone();
-                if !header_options.has_headers {
-                    table.show_header_row = false;
+                    if column.is_null(index) {
+                        self.write_dataframe_null(row, col, column_format, options)?;
+                    } else {
+                        let format = column_format.or(column.format());
+                        self.write_record_batch_value(row, col, column, index, format)?;
+                    }
                 }
-                table.is_serde_table = true;
-                Some(table)
             }
-            None => None,
-        };
 
-        // Store meta data for the struct/headers.
-        self.serializer_state.structs.insert(
-            header_options.struct_name.clone(),
-            SerializationHeaderConfig {
-                fields,
-                min_row,
-                min_col,
-                max_row,
-                max_col,
-                table,
-            },
-        );
+            first_data_row += num_rows;
+        }
 
-        Ok(self)
-    }
+        let Some(schema) = first_schema else {
+            return Ok(self);
+        };
 
-    // Serialize the parent data structure to the worksheet.
-    #[cfg(feature = "serde")]
-    fn serialize_data_structure<T>(&mut self, data_structure: &T) -> Result<(), XlsxError>
-    where
-        T: Serialize,
-    {
-        data_structure.serialize(self)?;
-        Ok(())
-    }
+        // The range of the data, with at least one row for a table. This has
+        // already been checked against the worksheet limits.
+        let num_rows = first_data_row - start_data_row;
+        let mut last_row = (start_data_row + num_rows.max(1) - 1) as RowNum;
+        let last_col = col + (schema.fields().len() - 1) as ColNum;
 
-    // Serialize individual data items to a worksheet cell.
-    #[cfg(feature = "serde")]
-    pub(crate) fn serialize_to_worksheet_cell(
-        &mut self,
-        data: impl IntoExcelData,
-    ) -> Result<(), XlsxError> {
-        let result = self.serializer_state.current_state();
+        if let Some(table) = &options.table {
+            let mut table = table.clone();
 
-        match result {
-            Ok(result) => {
-                let (row, col, value_format) = result;
-                match &*value_format {
-                    Some(format) => self.write_with_format(row, col, data, format).map(|_| ()),
-                    None => self.write(row, col, data).map(|_| ()),
-                }
+            if table.show_total_row {
+                last_row += 1;
             }
-            Err(()) => Ok(()),
-        }
-    }
 
-    // Add any tables that were added as part of serialization formatting.
-    #[cfg(feature = "serde")]
-    pub(crate) fn store_serialized_tables(&mut self) -> Result<&mut Worksheet, XlsxError> {
-        let tables = self.serializer_state.get_tables();
+            // The headers have already been written, with any header format.
+            table.has_header_cells = true;
 
-        for table_data in tables {
-            self.write_serialized_table(&table_data)?;
+            self.add_table(row, col, last_row, last_col, &table)?;
+        }
+
+        if options.autofit {
+            self.autofit_range(row, col, last_row, last_col);
         }
 
         Ok(self)
     }
 
-    // Write a table that is part of serialization formatting.
-    #[cfg(feature = "serde")]
-    pub(crate) fn write_serialized_table(
+    // Write a non-null Arrow record batch value to a worksheet cell.
+    #[cfg(feature = "arrow")]
+    fn write_record_batch_value(
         &mut self,
-        table_data: &TableData,
-    ) -> Result<&mut Worksheet, XlsxError> {
-        let min_col = table_data.1;
-        let max_col = table_data.3;
-        let min_row = table_data.0;
-        let mut max_row = table_data.2;
-        let table = &table_data.4;
-
-        if table.show_total_row {
-            max_row += 1;
+        row: RowNum,
+        col: ColNum,
+        column: &RecordBatchColumn,
+        index: usize,
+        format: Option<&Format>,
+    ) -> Result<(), XlsxError> {
+        match column {
+            RecordBatchColumn::Null => Ok(()),
+            RecordBatchColumn::Boolean(array) => {
+                self.write_dataframe_cell(row, col, array.value(index), format)
+            }
+            RecordBatchColumn::Number(array, _) => {
+                self.write_dataframe_cell(row, col, array.value(index), format)
+            }
+            RecordBatchColumn::String(array) => {
+                self.write_dataframe_cell(row, col, array.value(index), format)
+            }
+            RecordBatchColumn::DateTime(array, _) => {
+                let datetime = ExcelDateTime::from_serial_datetime(array.value(index))?;
+                self.write_dataframe_cell(row, col, datetime, format)
+            }
         }
-
-        self.add_table(min_row, min_col, max_row, max_col, table)
     }
 
     // -----------------------------------------------------------------------
@@ -13185,8 +15033,8 @@ impl Worksheet {
     /// Also, you can set one of the row and col parameters as 0 if you do not
     /// want either the vertical or horizontal split. See the example below.
     ///
-    /// In Excel it is also possible to set "split" panes without freezing them.
-    /// That feature isn't currently supported by `rust_xlsxwriter`.
+    /// In Excel it is also possible to set "split" panes without freezing them,
+    /// see [`Worksheet::set_split_panes()`].
     ///
     /// # Parameters
     ///
@@ -13251,7 +15099,15 @@ impl Worksheet {
             return Err(XlsxError::RowColumnLimitError);
         }
 
+        // Clear any top cell set for split panes when switching to freeze panes.
+        if self.panes.is_split() {
+            self.panes.top_cell = (0, 0);
+        }
+
         self.panes.freeze_cell = (row, col);
+        self.panes.split_height = 0.0;
+        self.panes.split_width = 0.0;
+
         Ok(self)
     }
 
@@ -13321,6 +15177,128 @@ impl Worksheet {
         Ok(self)
     }
 
+    /// Split a worksheet into panes.
+    ///
+    /// The `set_split_panes()` method can be used to divide a worksheet into
+    /// horizontal or vertical regions known as panes. This is similar to the
+    /// [`Worksheet::set_freeze_panes()`] method except that the splits between
+    /// the panes are visible and can be dragged by the user, like the panes
+    /// created by the Excel "View -> Split" option. Each pane can be scrolled
+    /// independently.
+    ///
+    /// The parameters are the vertical and horizontal positions of the split
+    /// in the same units as [`Worksheet::set_row_height()`] and
+    /// [`Worksheet::set_column_width()`]. For example, to split the worksheet
+    /// below the first row and to the right of the first column, with the
+    /// default row height and column width, you would use `(15, 8.43)`. Either
+    /// parameter can be set to 0 if you do not want a horizontal or vertical
+    /// split.
+    ///
+    /// By default the top left cell of the bottom right pane is estimated from
+    /// the split position based on the default row height and column width.
+    /// It can be set explicitly with [`Worksheet::set_split_panes_top_cell()`].
+    /// The top left cell of the top left pane can be set with
+    /// [`Worksheet::set_top_left_cell()`].
+    ///
+    /// Split panes and freeze panes can't be used together. The last method
+    /// called takes precedence.
+    ///
+    /// # Parameters
+    ///
+    /// - `height`: The vertical position of the split in character/point
+    ///   units, like a row height.
+    /// - `width`: The horizontal position of the split in character units,
+    ///   like a column width.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates splitting the worksheet into panes.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_split_panes.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, Worksheet, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     let mut worksheet1 = Worksheet::new();
+    /// #     let mut worksheet2 = Worksheet::new();
+    /// #     let mut worksheet3 = Worksheet::new();
+    /// #
+    /// #     worksheet1.write_string(0, 0, "Scroll down")?;
+    /// #     worksheet2.write_string(0, 0, "Scroll across")?;
+    /// #     worksheet3.write_string(0, 0, "Scroll down or across")?;
+    /// #
+    ///     // Split the worksheet below the first row.
+    ///     worksheet1.set_split_panes(15, 0);
+    ///
+    ///     // Split the worksheet to the right of the first column.
+    ///     worksheet2.set_split_panes(0, 8.43);
+    ///
+    ///     // Split the worksheet below the third row and to the right of the
+    ///     // second column, and scroll the bottom right pane to cell E20.
+    ///     worksheet3.set_split_panes(45, 16.86);
+    ///     worksheet3.set_split_panes_top_cell(19, 4)?;
+    /// #
+    /// #     workbook.push_worksheet(worksheet1);
+    /// #     workbook.push_worksheet(worksheet2);
+    /// #     workbook.push_worksheet(worksheet3);
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_split_panes(
+        &mut self,
+        height: impl Into<f64>,
+        width: impl Into<f64>,
+    ) -> &mut Worksheet {
+        // Clear any top cell set for freeze panes when switching to split panes.
+        if self.panes.freeze_cell != (0, 0) {
+            self.panes.top_cell = (0, 0);
+        }
+
+        self.panes.split_height = height.into().max(0.0);
+        self.panes.split_width = width.into().max(0.0);
+        self.panes.freeze_cell = (0, 0);
+
+        self
+    }
+
+    /// Set the top left cell of the bottom right pane of a split pane.
+    ///
+    /// This method is used in conjunction with the
+    /// [`Worksheet::set_split_panes()`] method to set the top left cell of the
+    /// scrolled pane, to the bottom and/or right of the split. If this isn't
+    /// set it is estimated from the position of the split.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The zero indexed row number.
+    /// - `col`: The zero indexed column number.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::RowColumnLimitError`] - Row or column exceeds Excel's
+    ///   worksheet limits.
+    ///
+    pub fn set_split_panes_top_cell(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Check row and col are in the allowed range.
+        if !self.check_dimensions_only(row, col) {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        self.panes.top_cell = (row, col);
+        Ok(self)
+    }
+
     /// Set the printed page header caption.
     ///
     /// The `set_header()` method can be used to set the header for a worksheet.
@@ -13454,39 +15432,117 @@ impl Worksheet {
     /// worksheet3.set_header(r#"&C&"Times New Roman,Regular"Hello"#);
     /// ```
     ///
-    /// It is possible to combine all of these features together to create
-    /// complex headers and footers. If you set up a complex header in Excel you
-    /// can transfer it to `rust_xlsxwriter` by inspecting the string in the
-    /// Excel file. For example the following shows how unzip and grep the Excel
-    /// XML sub-files on a Linux system. The example uses libxml's xmllint to
-    /// format the XML for clarity:
+    /// It is possible to combine all of these features together to create
+    /// complex headers and footers. If you set up a complex header in Excel you
+    /// can transfer it to `rust_xlsxwriter` by inspecting the string in the
+    /// Excel file. For example the following shows how unzip and grep the Excel
+    /// XML sub-files on a Linux system. The example uses libxml's xmllint to
+    /// format the XML for clarity:
+    ///
+    /// ```text
+    /// $ unzip myfile.xlsm -d myfile
+    /// $ xmllint --format `find myfile -name "*.xml" | xargs` | \
+    ///     egrep "Header|Footer" | sed 's/&amp;/\&/g'
+    ///
+    ///  <headerFooter scaleWithDoc="0">
+    ///    <oddHeader>&L&P</oddHeader>
+    ///  </headerFooter>
+    /// ```
+    ///
+    /// Note: Excel requires that the header or footer string be less than 256
+    /// characters, including the control characters. Strings longer than this
+    /// will not be written, and a warning will be output.
+    ///
+    /// # Parameters
+    ///
+    /// - `header`: The header string with optional control characters.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates setting the worksheet header.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_header.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.set_header("&CPage &P of &N");
+    /// #
+    /// #     worksheet.write_string(0, 0, "Hello")?;
+    /// #     worksheet.write_string(200, 0, "Hello")?;
+    /// #     worksheet.set_view_page_layout();
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Output file:
+    ///
+    /// <img
+    /// src="https://rustxlsxwriter.github.io/images/worksheet_set_header.png">
+    ///
+    pub fn set_header(&mut self, header: impl Into<String>) -> &mut Worksheet {
+        self.set_header_with_page(header, HeaderFooterPage::Odd)
+    }
+
+    /// Set the printed page footer caption.
+    ///
+    /// The `set_footer()` method can be used to set the footer for a worksheet.
+    ///
+    /// See the documentation for [`Worksheet::set_header()`] for more details
+    /// on the syntax of the header/footer string.
+    ///
+    /// # Parameters
+    ///
+    /// - `footer`: The footer string with optional control characters.
     ///
-    /// ```text
-    /// $ unzip myfile.xlsm -d myfile
-    /// $ xmllint --format `find myfile -name "*.xml" | xargs` | \
-    ///     egrep "Header|Footer" | sed 's/&amp;/\&/g'
+    pub fn set_footer(&mut self, footer: impl Into<String>) -> &mut Worksheet {
+        self.set_footer_with_page(footer, HeaderFooterPage::Odd)
+    }
+
+    /// Set the printed page header caption for the first, odd or even pages.
     ///
-    ///  <headerFooter scaleWithDoc="0">
-    ///    <oddHeader>&L&P</oddHeader>
-    ///  </headerFooter>
-    /// ```
+    /// The `set_header_with_page()` method is used to set a different header
+    /// for the first page of the printed worksheet, or for the even pages, for
+    /// example for a cover page or for mirrored headers in duplex printing.
     ///
-    /// Note: Excel requires that the header or footer string be less than 256
-    /// characters, including the control characters. Strings longer than this
-    /// will not be written, and a warning will be output.
+    /// Setting a [`HeaderFooterPage::First`] header/footer turns on the Excel
+    /// "Different first page" option. Setting a [`HeaderFooterPage::Even`]
+    /// header/footer turns on the "Different odd and even pages" option. In
+    /// that case the [`HeaderFooterPage::Odd`] header, which is the one set
+    /// by [`Worksheet::set_header()`], only applies to the odd pages.
+    ///
+    /// The options are also turned on by an empty header/footer string. For
+    /// example `set_header_with_page("", HeaderFooterPage::First)` can be used
+    /// to add a cover page without a header.
+    ///
+    /// See the documentation for [`Worksheet::set_header()`] for more details
+    /// on the syntax of the header/footer string.
     ///
     /// # Parameters
     ///
     /// - `header`: The header string with optional control characters.
+    /// - `page`: The pages that the header applies to as defined by the
+    ///   [`HeaderFooterPage`] enum.
     ///
     /// # Examples
     ///
-    /// The following example demonstrates setting the worksheet header.
+    /// The following example demonstrates setting different headers and
+    /// footers for the first page and for the odd and even pages of a
+    /// worksheet.
     ///
     /// ```
-    /// # // This code is available in examples/doc_worksheet_set_header.rs
+    /// # // This code is available in examples/doc_worksheet_set_header_with_page.rs
     /// #
-    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// # use rust_xlsxwriter::{HeaderFooterPage, Workbook, XlsxError};
     /// #
     /// # fn main() -> Result<(), XlsxError> {
     /// #     let mut workbook = Workbook::new();
@@ -13494,7 +15550,12 @@ impl Worksheet {
     /// #     // Add a worksheet to the workbook.
     /// #     let worksheet = workbook.add_worksheet();
     /// #
-    ///     worksheet.set_header("&CPage &P of &N");
+    ///     // Add a header for the cover page only.
+    ///     worksheet.set_header_with_page("&CAccount Statement", HeaderFooterPage::First);
+    ///
+    ///     // Add mirrored footers for duplex printing.
+    ///     worksheet.set_footer_with_page("&RPage &P", HeaderFooterPage::Odd);
+    ///     worksheet.set_footer_with_page("&LPage &P", HeaderFooterPage::Even);
     /// #
     /// #     worksheet.write_string(0, 0, "Hello")?;
     /// #     worksheet.write_string(200, 0, "Hello")?;
@@ -13506,63 +15567,70 @@ impl Worksheet {
     /// # }
     /// ```
     ///
-    /// Output file:
-    ///
-    /// <img
-    /// src="https://rustxlsxwriter.github.io/images/worksheet_set_header.png">
-    ///
-    pub fn set_header(&mut self, header: impl Into<String>) -> &mut Worksheet {
+    pub fn set_header_with_page(
+        &mut self,
+        header: impl Into<String>,
+        page: HeaderFooterPage,
+    ) -> &mut Worksheet {
         let header = header.into();
-        let header_expanded = header
-            .replace("&[Tab]", "&A")
-            .replace("&[Date]", "&D")
-            .replace("&[File]", "&F")
-            .replace("&[Page]", "&P")
-            .replace("&[Path]", "&Z")
-            .replace("&[Time]", "&T")
-            .replace("&[Pages]", "&N")
-            .replace("&[Picture]", "&G");
 
-        if header_expanded.chars().count() > 255 {
+        if Self::expand_header_footer(&header).chars().count() > 255 {
             eprintln!("Header string exceeds Excel's limit of 255 characters.");
             return self;
         }
 
-        self.header = header;
+        match page {
+            HeaderFooterPage::Odd => self.header = header,
+            HeaderFooterPage::First => {
+                self.first_header = header;
+                self.header_footer_different_first = true;
+            }
+            HeaderFooterPage::Even => {
+                self.even_header = header;
+                self.header_footer_different_odd_even = true;
+            }
+        }
+
         self.page_setup_changed = true;
         self.head_footer_changed = true;
         self
     }
 
-    /// Set the printed page footer caption.
+    /// Set the printed page footer caption for the first, odd or even pages.
     ///
-    /// The `set_footer()` method can be used to set the footer for a worksheet.
-    ///
-    /// See the documentation for [`Worksheet::set_header()`] for more details
-    /// on the syntax of the header/footer string.
+    /// See the documentation for [`Worksheet::set_header_with_page()`] for
+    /// more details.
     ///
     /// # Parameters
     ///
     /// - `footer`: The footer string with optional control characters.
+    /// - `page`: The pages that the footer applies to as defined by the
+    ///   [`HeaderFooterPage`] enum.
     ///
-    pub fn set_footer(&mut self, footer: impl Into<String>) -> &mut Worksheet {
+    pub fn set_footer_with_page(
+        &mut self,
+        footer: impl Into<String>,
+        page: HeaderFooterPage,
+    ) -> &mut Worksheet {
         let footer = footer.into();
-        let footer_expanded = footer
-            .replace("&[Tab]", "&A")
-            .replace("&[Date]", "&D")
-            .replace("&[File]", "&F")
-            .replace("&[Page]", "&P")
-            .replace("&[Path]", "&Z")
-            .replace("&[Time]", "&T")
-            .replace("&[Pages]", "&N")
-            .replace("&[Picture]", "&G");
 
-        if footer_expanded.chars().count() > 255 {
+        if Self::expand_header_footer(&footer).chars().count() > 255 {
             eprintln!("Footer string exceeds Excel's limit of 255 characters.");
             return self;
         }
 
-        self.footer = footer;
+        match page {
+            HeaderFooterPage::Odd => self.footer = footer,
+            HeaderFooterPage::First => {
+                self.first_footer = footer;
+                self.header_footer_different_first = true;
+            }
+            HeaderFooterPage::Even => {
+                self.even_footer = footer;
+                self.header_footer_different_odd_even = true;
+            }
+        }
+
         self.page_setup_changed = true;
         self.head_footer_changed = true;
         self
@@ -13673,58 +15741,166 @@ impl Worksheet {
         image: &Image,
         position: HeaderImagePosition,
     ) -> Result<&mut Worksheet, XlsxError> {
+        self.set_header_image_with_page(image, position, HeaderFooterPage::Odd)
+    }
+
+    /// Insert an image in a worksheet footer.
+    ///
+    /// See the documentation for [`Worksheet::set_header_image()`] for more
+    /// details.
+    ///
+    /// # Parameters
+    ///
+    /// - `position`: The image position as defined by the
+    ///   [`HeaderImagePosition`] enum.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::ParameterError`] - Parameter error if there isn't a
+    ///   corresponding `&[Picture]`/`&[G]` variable in the header string.
+    ///
+    pub fn set_footer_image(
+        &mut self,
+        image: &Image,
+        position: HeaderImagePosition,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        self.set_footer_image_with_page(image, position, HeaderFooterPage::Odd)
+    }
+
+    /// Insert an image in a worksheet header for the first, odd or even pages.
+    ///
+    /// This needs to be preceded by a call to
+    /// [`Worksheet::set_header_with_page()`] for the same `page` where a
+    /// corresponding `&[Picture]` element is added to the header formatting
+    /// string. See the documentation for [`Worksheet::set_header_image()`]
+    /// for more details.
+    ///
+    /// # Parameters
+    ///
+    /// - `position`: The image position as defined by the
+    ///   [`HeaderImagePosition`] enum.
+    /// - `page`: The pages that the header applies to as defined by the
+    ///   [`HeaderFooterPage`] enum.
+    ///
+    /// # Errors
+    ///
+    /// - [`XlsxError::ParameterError`] - Parameter error if there isn't a
+    ///   corresponding `&[Picture]`/`&[G]` variable in the header string.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates adding a header image to the first
+    /// page of a worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_header_image_with_page.rs
+    /// #
+    /// # use rust_xlsxwriter::{HeaderFooterPage, HeaderImagePosition, Image, Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     let image = Image::new("examples/rust_logo.png")?
+    ///         .set_scale_height(0.5)
+    ///         .set_scale_width(0.5);
+    ///
+    ///     // Insert the logo in the header of the first page only.
+    ///     worksheet.set_header_with_page("&L&[Picture]", HeaderFooterPage::First);
+    ///     worksheet.set_header_image_with_page(
+    ///         &image,
+    ///         HeaderImagePosition::Left,
+    ///         HeaderFooterPage::First,
+    ///     )?;
+    /// #
+    /// #     // Increase the top margin to 1.2 for clarity. The -1.0 values are ignored.
+    /// #     worksheet.set_margins(-1.0, -1.0, 1.2, -1.0, -1.0, -1.0);
+    /// #
+    /// #     worksheet.write_string(0, 0, "Hello")?;
+    /// #     worksheet.write_string(200, 0, "Hello")?;
+    /// #     worksheet.set_view_page_layout();
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_header_image_with_page(
+        &mut self,
+        image: &Image,
+        position: HeaderImagePosition,
+        page: HeaderFooterPage,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        let header = match page {
+            HeaderFooterPage::Odd => &self.header,
+            HeaderFooterPage::First => &self.first_header,
+            HeaderFooterPage::Even => &self.even_header,
+        };
+
         // Check that there is a matching  &[Picture]/&[G] variable in the
         // header string.
-        if !Self::verify_header_footer_image(&self.header, &position) {
+        if !Self::verify_header_footer_image(header, &position) {
             let error = format!(
-                "No &[Picture] or &[G] variable in header string: '{}' for position = '{:?}'",
-                self.header, position
+                "No &[Picture] or &[G] variable in header string: '{header}' for position = '{position:?}'"
             );
             return Err(XlsxError::ParameterError(error));
         }
 
         let mut image = image.clone();
         image.header_position = position.clone();
+        image.header_page = page;
         image.is_header = true;
-        self.header_footer_images[position as usize] = Some(image);
+        self.header_footer_images[6 * page as usize + position as usize] = Some(image);
 
         Ok(self)
     }
 
-    /// Insert an image in a worksheet footer.
+    /// Insert an image in a worksheet footer for the first, odd or even pages.
     ///
-    /// See the documentation for [`Worksheet::set_header_image()`] for more
-    /// details.
+    /// See the documentation for [`Worksheet::set_header_image_with_page()`]
+    /// for more details.
     ///
     /// # Parameters
     ///
     /// - `position`: The image position as defined by the
     ///   [`HeaderImagePosition`] enum.
+    /// - `page`: The pages that the footer applies to as defined by the
+    ///   [`HeaderFooterPage`] enum.
     ///
     /// # Errors
     ///
     /// - [`XlsxError::ParameterError`] - Parameter error if there isn't a
-    ///   corresponding `&[Picture]`/`&[G]` variable in the header string.
+    ///   corresponding `&[Picture]`/`&[G]` variable in the footer string.
     ///
-    pub fn set_footer_image(
+    pub fn set_footer_image_with_page(
         &mut self,
         image: &Image,
         position: HeaderImagePosition,
+        page: HeaderFooterPage,
     ) -> Result<&mut Worksheet, XlsxError> {
+        let footer = match page {
+            HeaderFooterPage::Odd => &self.footer,
+            HeaderFooterPage::First => &self.first_footer,
+            HeaderFooterPage::Even => &self.even_footer,
+        };
+
         // Check that there is a matching  &[Picture]/&[G] variable in the
         // footer string.
-        if !Self::verify_header_footer_image(&self.footer, &position) {
+        if !Self::verify_header_footer_image(footer, &position) {
             let error = format!(
-                "No &[Picture] or &[G] variable in footer string: '{}' for position = '{:?}'",
-                self.footer, position
+                "No &[Picture] or &[G] variable in footer string: '{footer}' for position = '{position:?}'"
             );
             return Err(XlsxError::ParameterError(error));
         }
 
         let mut image = image.clone();
         image.header_position = position.clone();
+        image.header_page = page;
         image.is_header = false;
-        self.header_footer_images[3 + position as usize] = Some(image);
+        self.header_footer_images[6 * page as usize + 3 + position as usize] = Some(image);
 
         Ok(self)
     }
@@ -14067,25 +16243,171 @@ impl Worksheet {
     ///
     /// # Parameters
     ///
-    /// - `enable`: Turn the property on/off. It is off by default.
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    pub fn set_print_center_vertically(&mut self, enable: bool) -> &mut Worksheet {
+        self.center_vertically = enable;
+
+        if enable {
+            self.print_options_changed = true;
+            self.page_setup_changed = true;
+        }
+        self
+    }
+
+    /// Set the option to turn on/off the screen gridlines.
+    ///
+    /// The `set_screen_gridlines()` method is use to turn on/off gridlines on
+    /// displayed worksheet. It is on by default.
+    ///
+    /// To turn on/off the printed gridlines see the
+    /// [`Worksheet::set_print_gridlines()`] method below.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates turn off the worksheet worksheet screen
+    /// gridlines.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_screen_gridlines.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write(0, 0, "Hello")?;
+    ///
+    ///     // Turn off the screen gridlines.
+    ///     worksheet.set_screen_gridlines(false);
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Output file:
+    ///
+    /// <img src="https://rustxlsxwriter.github.io/images/worksheet_set_screen_gridlines.png">
+    ///
+    pub fn set_screen_gridlines(&mut self, enable: bool) -> &mut Worksheet {
+        self.screen_gridlines = enable;
+
+        self
+    }
+
+    /// Set the option to turn on/off the display of zero values.
+    ///
+    /// The `set_screen_zeros()` method is used to turn on/off the display of
+    /// cells with a zero value on the displayed worksheet. This is generally
+    /// used in financial statements to make the non-zero values stand out.
+    /// Zero values are displayed as blank cells when this option is off.
+    ///
+    /// Note, this option affects all zero values in the worksheet. To hide
+    /// zero values in specific cells use a number format such as
+    /// `"0;-0;;@"`.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates turning off the display of zero values
+    /// in a worksheet.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_screen_zeros.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write(0, 0, 100)?;
+    ///     worksheet.write(1, 0, 0)?;
+    ///     worksheet.write(2, 0, 50)?;
+    ///
+    ///     // Turn off the display of zero values.
+    ///     worksheet.set_screen_zeros(false);
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_screen_zeros(&mut self, enable: bool) -> &mut Worksheet {
+        self.screen_zeros = enable;
+
+        self
+    }
+
+    /// Set the option to display formulas instead of their results.
+    ///
+    /// The `set_screen_formulas()` method is used to display the formulas in
+    /// the worksheet cells instead of their calculated results. This is the
+    /// same as the Excel "Show Formulas" option and is useful for auditing
+    /// worksheets.
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is off by default.
+    ///
+    /// # Examples
+    ///
+    /// The following example demonstrates displaying the formulas in a worksheet
+    /// instead of their results.
+    ///
+    /// ```
+    /// # // This code is available in examples/doc_worksheet_set_screen_formulas.rs
+    /// #
+    /// # use rust_xlsxwriter::{Workbook, XlsxError};
+    /// #
+    /// # fn main() -> Result<(), XlsxError> {
+    /// #     let mut workbook = Workbook::new();
+    /// #
+    /// #     // Add a worksheet to the workbook.
+    /// #     let worksheet = workbook.add_worksheet();
+    /// #
+    ///     worksheet.write(0, 0, 10)?;
+    ///     worksheet.write(1, 0, 20)?;
+    ///     worksheet.write_formula(2, 0, "=SUM(A1:A2)")?;
+    ///
+    ///     // Display the formulas instead of the results.
+    ///     worksheet.set_screen_formulas(true);
+    /// #
+    /// #     workbook.save("worksheet.xlsx")?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
     ///
-    pub fn set_print_center_vertically(&mut self, enable: bool) -> &mut Worksheet {
-        self.center_vertically = enable;
+    pub fn set_screen_formulas(&mut self, enable: bool) -> &mut Worksheet {
+        self.screen_formulas = enable;
 
-        if enable {
-            self.print_options_changed = true;
-            self.page_setup_changed = true;
-        }
         self
     }
 
-    /// Set the option to turn on/off the screen gridlines.
+    /// Set the option to turn on/off the row and column headings.
     ///
-    /// The `set_screen_gridlines()` method is use to turn on/off gridlines on
-    /// displayed worksheet. It is on by default.
+    /// The `set_screen_headings()` method is used to turn on/off the row
+    /// number and column letter headings on the displayed worksheet. Turning
+    /// them off can be useful for form-like worksheets.
     ///
-    /// To turn on/off the printed gridlines see the
-    /// [`Worksheet::set_print_gridlines()`] method below.
+    /// To turn on/off the printed headings see the
+    /// [`Worksheet::set_print_headings()`] method.
     ///
     /// # Parameters
     ///
@@ -14093,11 +16415,11 @@ impl Worksheet {
     ///
     /// # Examples
     ///
-    /// The following example demonstrates turn off the worksheet worksheet screen
-    /// gridlines.
+    /// The following example demonstrates turning off the worksheet row and
+    /// column headings.
     ///
     /// ```
-    /// # // This code is available in examples/doc_worksheet_set_screen_gridlines.rs
+    /// # // This code is available in examples/doc_worksheet_set_screen_headings.rs
     /// #
     /// # use rust_xlsxwriter::{Workbook, XlsxError};
     /// #
@@ -14109,8 +16431,8 @@ impl Worksheet {
     /// #
     ///     worksheet.write(0, 0, "Hello")?;
     ///
-    ///     // Turn off the screen gridlines.
-    ///     worksheet.set_screen_gridlines(false);
+    ///     // Turn off the row and column headings.
+    ///     worksheet.set_screen_headings(false);
     /// #
     /// #     workbook.save("worksheet.xlsx")?;
     /// #
@@ -14118,12 +16440,41 @@ impl Worksheet {
     /// # }
     /// ```
     ///
-    /// Output file:
+    pub fn set_screen_headings(&mut self, enable: bool) -> &mut Worksheet {
+        self.screen_headings = enable;
+
+        self
+    }
+
+    /// Set the option to turn on/off the ruler in page layout view.
     ///
-    /// <img src="https://rustxlsxwriter.github.io/images/worksheet_set_screen_gridlines.png">
+    /// The `set_screen_ruler()` method is used to turn on/off the ruler that
+    /// is displayed when the worksheet is in page layout view, see
+    /// [`Worksheet::set_view_page_layout()`].
     ///
-    pub fn set_screen_gridlines(&mut self, enable: bool) -> &mut Worksheet {
-        self.screen_gridlines = enable;
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_screen_ruler(&mut self, enable: bool) -> &mut Worksheet {
+        self.screen_ruler = enable;
+
+        self
+    }
+
+    /// Set the option to turn on/off the outline symbols.
+    ///
+    /// The `set_screen_outline_symbols()` method is used to turn on/off the
+    /// display of the outline group expand/collapse symbols and level bars
+    /// for worksheets with grouped rows or columns, see
+    /// [`Worksheet::group_rows()`] and [`Worksheet::group_columns()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `enable`: Turn the property on/off. It is on by default.
+    ///
+    pub fn set_screen_outline_symbols(&mut self, enable: bool) -> &mut Worksheet {
+        self.screen_outline_symbols = enable;
 
         self
     }
@@ -15357,6 +17708,12 @@ impl Worksheet {
             None => self.row_col_format(row, col),
         };
 
+        // Dates before 1904 can't be stored in the 1904 epoch. This is also
+        // checked when the file is saved in case the epoch is changed later.
+        if is_datetime && self.is_1904_date {
+            ExcelDateTime::check_1904_epoch(number)?;
+        }
+
         // Create the appropriate cell type to hold the data.
         let cell = if is_datetime {
             CellType::DateTime { number, xf_index }
@@ -15511,6 +17868,11 @@ impl Worksheet {
             return Err(XlsxError::RowColumnLimitError);
         }
 
+        // Check the formula syntax in strict mode.
+        if self.use_strict_formulas {
+            self.check_formula_syntax(row, col, &formula)?;
+        }
+
         // Get the index of the format object, if any.
         let xf_index = match format {
             Some(format) => self.format_xf_index(format),
@@ -15560,6 +17922,11 @@ impl Worksheet {
             return Err(XlsxError::RowColumnOrderError);
         }
 
+        // Check the formula syntax in strict mode.
+        if self.use_strict_formulas {
+            self.check_formula_syntax(first_row, first_col, &formula)?;
+        }
+
         // Get the index of the format object, if any.
         let xf_index = match format {
             Some(format) => self.format_xf_index(format),
@@ -15676,6 +18043,37 @@ impl Worksheet {
         Ok(self)
     }
 
+    // Store an Excel error value cell in the worksheet data table structure.
+    fn store_error(
+        &mut self,
+        row: RowNum,
+        col: ColNum,
+        error: ExcelError,
+        format: Option<&Format>,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        // Check row and col are in the allowed range.
+        if !self.check_dimensions(row, col) {
+            return Err(XlsxError::RowColumnLimitError);
+        }
+
+        // Get the index of the format object, if any.
+        let xf_index = match format {
+            Some(format) => self.format_xf_index(format),
+            None => self.row_col_format(row, col),
+        };
+
+        // Create the appropriate cell type to hold the data.
+        let cell = CellType::Error {
+            error,
+            xf_index,
+            image_id: None,
+        };
+
+        self.insert_cell(row, col, cell);
+
+        Ok(self)
+    }
+
     // Store a url and associated properties. Urls in Excel are stored in a
     // number of places: they are written as a string similar to
     // write_string_with_format(), they are written in the <hyperlinks> element
@@ -15761,8 +18159,9 @@ impl Worksheet {
 
         // Create the appropriate cell type to hold the data.
         let cell = CellType::Error {
+            error: ExcelError::Value,
             xf_index,
-            value: image_id,
+            image_id: Some(image_id),
         };
 
         // Store the cell error value.
@@ -16363,6 +18762,78 @@ impl Worksheet {
         }
     }
 
+    // Generate the random salts for SHA-512 protection password hashes, for
+    // the worksheet password and any unprotected range passwords.
+    #[cfg(feature = "protection_hash")]
+    pub(crate) fn prepare_protection_salts(&mut self) -> Result<(), XlsxError> {
+        self.protection_salts.clear();
+
+        if self.protection_hash == ProtectionHash::Sha512 {
+            for _ in 0..=self.unprotected_ranges.len() {
+                self.protection_salts.push(utility::random_bytes(16)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Convert any conditional format dates to the worksheet date epoch and
+    // check that the cell and data validation dates are in the range supported
+    // by the 1904 epoch.
+    pub(crate) fn prepare_1904_dates(&mut self) -> Result<(), XlsxError> {
+        let is_1904_date = self.is_1904_date;
+        for conditional_format in self.conditional_formats.values_mut().flatten() {
+            conditional_format.set_1904_epoch(is_1904_date)?;
+        }
+
+        if !is_1904_date {
+            return Ok(());
+        }
+
+        for columns in self.data_table.values() {
+            for cell in columns.values() {
+                if let CellType::DateTime { number, .. } = cell {
+                    ExcelDateTime::check_1904_epoch(*number)?;
+                }
+            }
+        }
+
+        for data_validation in self.data_validations.values() {
+            if data_validation.validation_type != DataValidationType::Date {
+                continue;
+            }
+
+            let values = match &data_validation.rule {
+                DataValidationRuleInternal::Between(min, max)
+                | DataValidationRuleInternal::NotBetween(min, max) => vec![min, max],
+                DataValidationRuleInternal::EqualTo(value)
+                | DataValidationRuleInternal::NotEqualTo(value)
+                | DataValidationRuleInternal::LessThan(value)
+                | DataValidationRuleInternal::LessThanOrEqualTo(value)
+                | DataValidationRuleInternal::GreaterThan(value)
+                | DataValidationRuleInternal::GreaterThanOrEqualTo(value) => vec![value],
+                _ => vec![],
+            };
+
+            for value in values {
+                if let Ok(number) = value.parse::<f64>() {
+                    ExcelDateTime::check_1904_epoch(number)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Convert a stored 1900 epoch serial datetime to the worksheet date epoch.
+    fn datetime_to_epoch(&self, number: f64) -> f64 {
+        if self.is_1904_date {
+            ExcelDateTime::serial_to_1904_epoch(number)
+        } else {
+            number
+        }
+    }
+
     // Get the remapped local to global format index.
     fn get_global_xf_index(&self, xf_index: u32) -> u32 {
         if self.has_workbook_global_xfs {
@@ -16709,6 +19180,31 @@ impl Worksheet {
         ));
     }
 
+    // Convert the slicer dimensions into drawing dimensions and add them to
+    // the Drawing object. Also set the rel linkages between the files.
+    pub(crate) fn prepare_worksheet_slicers(&mut self, slicer_id: u32, drawing_id: u32) {
+        for (cell, slicer) in &self.slicers.clone() {
+            let drawing_info = self.position_object_emus(cell.0, cell.1, slicer);
+            self.drawing.drawings.push(drawing_info);
+        }
+
+        // Store the linkage to the worksheets rels file.
+        if self.drawing_object_relationships.is_empty() {
+            let drawing_name = format!("../drawings/drawing{drawing_id}.xml");
+            self.drawing_object_relationships.push((
+                "drawing".to_string(),
+                drawing_name,
+                String::new(),
+            ));
+        }
+
+        self.slicer_relationships.push((
+            "slicer".to_string(),
+            format!("../slicers/slicer{slicer_id}.xml"),
+            String::new(),
+        ));
+    }
+
     // Convert buttons into VML objects.
     pub(crate) fn prepare_vml_objects(&mut self, vml_data_id: u32, vml_shape_id: u32) -> u32 {
         let mut button_id = 1;
@@ -16796,6 +19292,40 @@ impl Worksheet {
         note_count
     }
 
+    // Set the pseudo GUID ids of the threaded comments and add the legacy notes
+    // that older versions of Excel display as a fallback. Also set the rel
+    // linkage to the threadedCommentN.xml file.
+    pub(crate) fn prepare_threaded_comments(&mut self, threaded_comment_id: u32) {
+        let sheet_id = self.sheet_index + 1;
+        let mut comment_index = 1;
+
+        for comment in self.threaded_comments.values_mut() {
+            comment.id = format!("{{C033E475-AAAA-BBBB-{sheet_id:04X}-{comment_index:012X}}}");
+            comment_index += 1;
+
+            for reply in &mut comment.replies {
+                reply.id = format!("{{C033E475-AAAA-BBBB-{sheet_id:04X}-{comment_index:012X}}}");
+                comment_index += 1;
+            }
+        }
+
+        for ((row, col), comment) in &self.threaded_comments {
+            let mut note = Note::new(comment.fallback_text())
+                .set_author(format!("tc={}", comment.id))
+                .add_author_prefix(false);
+            note.cell_row = *row;
+            note.cell_col = *col;
+
+            self.notes.entry(*row).or_default().insert(*col, note);
+        }
+
+        self.threaded_comment_relationships.push((
+            "threadedComment".to_string(),
+            format!("../threadedComments/threadedComment{threaded_comment_id}.xml"),
+            String::new(),
+        ));
+    }
+
     // Store the commentN.xml file linkage to the worksheets rels file.
     pub(crate) fn add_comment_rel_link(&mut self, comment_id: u32) {
         let comment_name = format!("../comments{comment_id}.xml");
@@ -16805,10 +19335,23 @@ impl Worksheet {
 
     // Convert the chart dimensions into drawing dimensions and add them to the
     // Drawing object. Also set the rel linkages between the files.
-    pub(crate) fn prepare_worksheet_charts(&mut self, chart_id: u32, drawing_id: u32) {
-        for (chart_id, chart) in (chart_id..).zip(self.charts.values_mut()) {
-            chart.id = chart_id;
-            chart.add_axis_ids(chart_id);
+    pub(crate) fn prepare_worksheet_charts(
+        &mut self,
+        chart_id: &mut u32,
+        chart_ex_id: &mut u32,
+        drawing_id: u32,
+    ) {
+        // The Excel 2016 chartex charts are numbered separately from the
+        // standard charts.
+        for chart in self.charts.values_mut() {
+            if chart.is_chart_ex() {
+                chart.id = *chart_ex_id;
+                *chart_ex_id += 1;
+            } else {
+                chart.id = *chart_id;
+                chart.add_axis_ids(*chart_id);
+                *chart_id += 1;
+            }
         }
 
         let mut rel_id = self.drawing_relationships.len() as u32;
@@ -16821,9 +19364,15 @@ impl Worksheet {
             let chart_id = chart.id;
 
             // Store the linkage to the charts rels file.
-            let chart_name = format!("../charts/chart{chart_id}.xml");
-            self.drawing_relationships
-                .push(("chart".to_string(), chart_name, String::new()));
+            if chart.is_chart_ex() {
+                let chart_name = format!("../charts/chartEx{chart_id}.xml");
+                self.drawing_relationships
+                    .push(("chartEx".to_string(), chart_name, String::new()));
+            } else {
+                let chart_name = format!("../charts/chart{chart_id}.xml");
+                self.drawing_relationships
+                    .push(("chart".to_string(), chart_name, String::new()));
+            }
 
             // Convert the chart dimensions to drawing dimensions and store the
             // drawing object.
@@ -16886,6 +19435,27 @@ impl Worksheet {
         table_id
     }
 
+    // Set a unique cache id for each pivot table and also set the rel linkage
+    // between the worksheet and pivot table xml files.
+    pub(crate) fn prepare_worksheet_pivot_tables(&mut self, mut pivot_table_id: u32) -> u32 {
+        for pivot_table in &mut self.pivot_tables {
+            pivot_table.index = pivot_table_id;
+            if pivot_table.name.is_empty() {
+                pivot_table.name = format!("PivotTable{pivot_table_id}");
+            }
+
+            self.pivot_table_relationships.push((
+                "pivotTable".to_string(),
+                format!("../pivotTables/pivotTable{pivot_table_id}.xml"),
+                String::new(),
+            ));
+
+            pivot_table_id += 1;
+        }
+
+        pivot_table_id
+    }
+
     // Calculate the vertices that define the position of a graphical object
     // within the worksheet in EMUs. The vertices are expressed as English
     // Metric Units (EMUs). There are 12,700 EMUs per point. Therefore, 12,700 *
@@ -17114,8 +19684,15 @@ impl Worksheet {
             xmlwriter::reset(&mut table.writer);
         }
 
+        for pivot_table in &mut self.pivot_tables {
+            xmlwriter::reset(&mut pivot_table.writer);
+            xmlwriter::reset(&mut pivot_table.cache_writer);
+            xmlwriter::reset(&mut pivot_table.records_writer);
+        }
+
         self.rel_count = 0;
         self.comment_relationships.clear();
+        self.threaded_comment_relationships.clear();
         self.drawing_object_relationships.clear();
         self.drawing_rel_ids.clear();
         self.drawing_relationships.clear();
@@ -17123,6 +19700,8 @@ impl Worksheet {
         self.header_footer_vml_info.clear();
         self.hyperlink_relationships.clear();
         self.table_relationships.clear();
+        self.slicer_relationships.clear();
+        self.pivot_table_relationships.clear();
         self.vml_drawing_relationships.clear();
         self.background_relationships.clear();
     }
@@ -17132,17 +19711,28 @@ impl Worksheet {
         !self.hyperlink_relationships.is_empty()
             || !self.drawing_object_relationships.is_empty()
             || !self.table_relationships.is_empty()
+            || !self.slicer_relationships.is_empty()
+            || !self.pivot_table_relationships.is_empty()
             || !self.background_relationships.is_empty()
     }
 
     // Check if there is a header image.
     pub(crate) fn has_header_footer_images(&self) -> bool {
-        self.header_footer_images[0].is_some()
-            || self.header_footer_images[1].is_some()
-            || self.header_footer_images[2].is_some()
-            || self.header_footer_images[3].is_some()
-            || self.header_footer_images[4].is_some()
-            || self.header_footer_images[5].is_some()
+        self.header_footer_images.iter().any(Option::is_some)
+    }
+
+    // Expand the long form header/footer variables, such as "&[Page]", to the
+    // short form used by Excel.
+    fn expand_header_footer(string: &str) -> String {
+        string
+            .replace("&[Tab]", "&A")
+            .replace("&[Date]", "&D")
+            .replace("&[File]", "&F")
+            .replace("&[Page]", "&P")
+            .replace("&[Path]", "&Z")
+            .replace("&[Time]", "&T")
+            .replace("&[Pages]", "&N")
+            .replace("&[Picture]", "&G")
     }
 
     // Check that there is a header/footer &[Picture] variable in the correct
@@ -17230,7 +19820,7 @@ impl Worksheet {
                                     if cache.cache_type != ChartRangeCacheDataType::String {
                                         cache.cache_type = ChartRangeCacheDataType::Date;
                                     }
-                                    data.push(number.to_string());
+                                    data.push(self.datetime_to_epoch(*number).to_string());
                                 }
 
                                 _ => data.push(String::new()),
@@ -17258,6 +19848,194 @@ impl Worksheet {
         cache
     }
 
+    // Return a range of data from a worksheet to use as the source data for a
+    // pivot table cache. Formulas are stored with their result value, if any.
+    pub(crate) fn get_pivot_cache_data(
+        &self,
+        first_row: RowNum,
+        first_col: ColNum,
+        last_row: RowNum,
+        last_col: ColNum,
+    ) -> Vec<Vec<PivotCacheValue>> {
+        let mut data = vec![];
+
+        for row_num in first_row..=last_row {
+            let mut row_data = vec![];
+            let columns = self.data_table.get(&row_num);
+
+            for col_num in first_col..=last_col {
+                let value = match columns.and_then(|columns| columns.get(&col_num)) {
+                    Some(cell) => match cell {
+                        CellType::String { string, .. }
+                        | CellType::RichString { string, .. }
+                        | CellType::InlineString { string, .. } => {
+                            PivotCacheValue::String(string.to_string())
+                        }
+                        CellType::Number { number, .. } => PivotCacheValue::Number(*number),
+                        CellType::DateTime { number, .. } => PivotCacheValue::DateTime(*number),
+                        CellType::Boolean { boolean, .. } => PivotCacheValue::Boolean(*boolean),
+                        CellType::Formula { result, .. }
+                        | CellType::ArrayFormula { result, .. } => {
+                            if result.is_empty() {
+                                PivotCacheValue::Blank
+                            } else if let Ok(number) = result.parse::<f64>() {
+                                PivotCacheValue::Number(number)
+                            } else if let Some(error) = ExcelError::from_excel_str(result) {
+                                PivotCacheValue::Error(error)
+                            } else {
+                                PivotCacheValue::String(result.to_string())
+                            }
+                        }
+                        CellType::Error { error, .. } => PivotCacheValue::Error(*error),
+                        CellType::Blank { .. } => PivotCacheValue::Blank,
+                    },
+                    None => PivotCacheValue::Blank,
+                };
+
+                row_data.push(value);
+            }
+
+            data.push(row_data);
+        }
+
+        data
+    }
+
+    // Get the value of a cell for use by the formula evaluator.
+    pub(crate) fn formula_input(&self, row: RowNum, col: ColNum) -> CellInput<'_> {
+        let Some(cell) = self
+            .data_table
+            .get(&row)
+            .and_then(|columns| columns.get(&col))
+        else {
+            return CellInput::Blank;
+        };
+
+        match cell {
+            CellType::String { string, .. }
+            | CellType::RichString { string, .. }
+            | CellType::InlineString { string, .. } => CellInput::String(string),
+            CellType::Number { number, .. } => CellInput::Number(*number),
+            CellType::DateTime { number, .. } => CellInput::Number(self.datetime_to_epoch(*number)),
+            CellType::Boolean { boolean, .. } => CellInput::Boolean(*boolean),
+            CellType::Formula { formula, .. } | CellType::ArrayFormula { formula, .. } => {
+                CellInput::Formula(formula)
+            }
+            CellType::Error { error, .. } => CellInput::Error(*error),
+            CellType::Blank { .. } => CellInput::Blank,
+        }
+    }
+
+    // Get the last row and column that contain data, if any. This is used to
+    // limit full row and column ranges in formulas to the populated cells.
+    pub(crate) fn formula_data_extent(&self) -> Option<(RowNum, ColNum)> {
+        let last_row = *self.data_table.keys().next_back()?;
+        let last_col = self
+            .data_table
+            .values()
+            .filter_map(|columns| columns.keys().next_back())
+            .max()
+            .copied()?;
+
+        Some((last_row, last_col))
+    }
+
+    // Get the row and column locations of the formula cells in the worksheet.
+    pub(crate) fn formula_cells(&self) -> Vec<(RowNum, ColNum)> {
+        let mut cells = vec![];
+
+        for (row_num, columns) in &self.data_table {
+            for (col_num, cell) in columns {
+                if matches!(
+                    cell,
+                    CellType::Formula { .. } | CellType::ArrayFormula { .. }
+                ) {
+                    cells.push((*row_num, *col_num));
+                }
+            }
+        }
+
+        cells
+    }
+
+    // Check the syntax of a formula, for strict mode.
+    fn check_formula_syntax(
+        &self,
+        row: RowNum,
+        col: ColNum,
+        formula: &Formula,
+    ) -> Result<(), XlsxError> {
+        match parser::parse(&formula.formula_string) {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                Err(self.formula_error(row, col, &error.description(&formula.formula_string)))
+            }
+        }
+    }
+
+    // Check all the formulas in the worksheet for syntax errors, unknown
+    // functions and references to unknown worksheets, for strict mode. The
+    // function names can include defined names used for LAMBDA() functions.
+    pub(crate) fn check_strict_formulas(
+        &self,
+        sheet_names: &[&str],
+        function_names: &[&str],
+    ) -> Result<(), XlsxError> {
+        for (row, col) in self.formula_cells() {
+            let CellInput::Formula(formula) = self.formula_input(row, col) else {
+                continue;
+            };
+
+            let expression = parser::parse(formula)
+                .map_err(|error| self.formula_error(row, col, &error.description(formula)))?;
+
+            if let Some(name) = expression.unknown_function(function_names) {
+                return Err(self.formula_error(
+                    row,
+                    col,
+                    &format!("Unknown function '{name}' in formula '{formula}'"),
+                ));
+            }
+
+            let mut unknown_sheet = None;
+            expression.walk(&mut |expr| {
+                if let FormulaExpression::Reference(reference) = expr {
+                    for sheet in [&reference.sheet, &reference.last_sheet]
+                        .into_iter()
+                        .flatten()
+                    {
+                        if unknown_sheet.is_none()
+                            && !sheet_names
+                                .iter()
+                                .any(|name| name.eq_ignore_ascii_case(sheet))
+                        {
+                            unknown_sheet = Some(sheet.clone());
+                        }
+                    }
+                }
+            });
+
+            if let Some(sheet) = unknown_sheet {
+                return Err(self.formula_error(
+                    row,
+                    col,
+                    &format!("Unknown worksheet '{sheet}' in formula '{formula}'"),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Create a formula error with the location of the formula cell.
+    fn formula_error(&self, row: RowNum, col: ColNum, message: &str) -> XlsxError {
+        XlsxError::FormulaError(format!(
+            "{}!{}: {message}",
+            utility::quote_sheet_name(&self.name),
+            utility::row_col_to_cell(row, col)
+        ))
+    }
+
     // Get the default header names for a worksheet table. These are generally
     // "Column1", "Column2", etc., unless the user has already specified a
     // string in the cell that will contain the header using
@@ -17302,15 +20080,37 @@ impl Worksheet {
     // functions that Excel has so it simulates autofit by calculating string
     // widths using metrics taken from Excel.
     fn autofit_worksheet(&mut self) -> &mut Worksheet {
+        self.autofit_range(
+            self.dimensions.first_row,
+            self.dimensions.first_col,
+            self.dimensions.last_row,
+            self.dimensions.last_col,
+        )
+    }
+
+    // Autofit the column widths based on the data in a range of cells. This is
+    // used to autofit the columns of a dataframe without changing the widths
+    // of other columns in the worksheet.
+    fn autofit_range(
+        &mut self,
+        first_row: RowNum,
+        first_col: ColNum,
+        last_row: RowNum,
+        last_col: ColNum,
+    ) -> &mut Worksheet {
         let mut max_widths: HashMap<ColNum, u32> = HashMap::new();
 
         #[cfg(feature = "enhanced_autofit")]
         let number_formatters = self.get_number_formatters();
 
+        // In constant memory mode only the current row is available.
         let (first_row, last_row) = if self.use_constant_memory {
-            (self.current_row, self.current_row)
+            (
+                cmp::max(first_row, self.current_row),
+                cmp::min(last_row, self.current_row),
+            )
         } else {
-            (self.dimensions.first_row, self.dimensions.last_row)
+            (first_row, last_row)
         };
 
         // Iterate over all of the data in the worksheet and find the max data
@@ -17322,7 +20122,7 @@ impl Worksheet {
             }
 
             if let Some(columns) = self.data_table.get(&row_num) {
-                for col_num in self.dimensions.first_col..=self.dimensions.last_col {
+                for col_num in first_col..=last_col {
                     if let Some(cell) = columns.get(&col_num) {
                         let mut pixel_width = match cell {
                             // For strings we do a calculation based on
@@ -17395,6 +20195,14 @@ impl Worksheet {
                                 }
                             }
 
+                            // For error values we use the width of the error
+                            // string. Embedded images don't add to the width.
+                            CellType::Error {
+                                error,
+                                image_id: None,
+                                ..
+                            } => utility::pixel_width(error.as_str()),
+
                             // Ignore the following types which don't add to the width.
                             CellType::Blank { .. } | CellType::Error { .. } => 0,
                         };
@@ -17737,6 +20545,10 @@ impl Worksheet {
     // written separately, and to different in-memory and file based buffers, in
     // constant memory mode.
     pub(crate) fn assemble_xml_file(&mut self) {
+        if self.template.is_some() {
+            return self.assemble_template_xml_file();
+        }
+
         if self.is_chartsheet {
             return self.assemble_chartsheet();
         }
@@ -17895,6 +20707,20 @@ impl Worksheet {
         xml_end_tag(&mut self.writer, "worksheet");
     }
 
+    // Assemble and write the XML file for worksheets and chartsheets from a
+    // template file. The user cell data is written in the standard way and is
+    // then merged into the template <sheetData> element.
+    pub(crate) fn assemble_template_xml_file(&mut self) {
+        self.write_data_table();
+        let generated_rows = xmlwriter::cursor_to_string(&self.writer);
+        xmlwriter::reset(&mut self.writer);
+
+        if let Some(template) = &self.template {
+            let xml = template.assemble_xml(&generated_rows, self.selected);
+            xml_raw_string(&mut self.writer, &xml);
+        }
+    }
+
     // Assemble and write the XML file for chartsheets.
     pub(crate) fn assemble_chartsheet(&mut self) {
         xml_declaration(&mut self.writer);
@@ -18124,10 +20950,22 @@ impl Worksheet {
     fn write_sheet_view(&mut self) {
         let mut attributes = vec![];
 
+        if self.screen_formulas {
+            attributes.push(("showFormulas", "1".to_string()));
+        }
+
         if !self.screen_gridlines {
             attributes.push(("showGridLines", "0".to_string()));
         }
 
+        if !self.screen_headings {
+            attributes.push(("showRowColHeaders", "0".to_string()));
+        }
+
+        if !self.screen_zeros {
+            attributes.push(("showZeros", "0".to_string()));
+        }
+
         if self.right_to_left {
             attributes.push(("rightToLeft", "1".to_string()));
         }
@@ -18136,6 +20974,14 @@ impl Worksheet {
             attributes.push(("tabSelected", "1".to_string()));
         }
 
+        if !self.screen_ruler {
+            attributes.push(("showRuler", "0".to_string()));
+        }
+
+        if !self.screen_outline_symbols {
+            attributes.push(("showOutlineSymbols", "0".to_string()));
+        }
+
         match self.page_view {
             PageView::PageLayout => {
                 attributes.push(("view", "pageLayout".to_string()));
@@ -18174,7 +21020,7 @@ impl Worksheet {
             attributes.push(("zoomToFit", "1".to_string()));
         }
 
-        if self.panes.is_empty() && self.selected_range.0.is_empty() {
+        if self.panes.is_empty() && self.selection.active_cell.is_empty() {
             xml_empty_tag(&mut self.writer, "sheetView", &attributes);
         } else {
             xml_start_tag(&mut self.writer, "sheetView", &attributes);
@@ -18191,14 +21037,13 @@ impl Worksheet {
 
     // Write the elements associated with cell selections.
     fn write_selections(&mut self) {
-        if self.selected_range.0.is_empty() {
+        if self.selection.active_cell.is_empty() {
             return;
         }
 
-        let active_cell = self.selected_range.0.clone();
-        let range = self.selected_range.1.clone();
+        let selection = self.selection.clone();
 
-        self.write_selection("", &active_cell, &range);
+        self.write_selection("", &selection);
     }
 
     // Write the elements associated with panes.
@@ -18207,36 +21052,97 @@ impl Worksheet {
             return;
         }
 
-        let row = self.panes.freeze_cell.0;
-        let col = self.panes.freeze_cell.1;
-        let range = self.selected_range.1.clone();
-        let active_cell = self.selected_range.0.clone();
+        let (row, col) = self.pane_cell();
 
-        // Write the pane and selection elements.
-        if row > 0 && col > 0 {
-            self.write_pane("bottomRight");
-            self.write_selection(
-                "topRight",
-                &utility::row_col_to_cell(0, col),
-                &utility::row_col_to_cell(0, col),
-            );
-            self.write_selection(
-                "bottomLeft",
-                &utility::row_col_to_cell(row, 0),
-                &utility::row_col_to_cell(row, 0),
-            );
-            self.write_selection("bottomRight", &active_cell, &range);
-        } else if col > 0 {
-            self.write_pane("topRight");
-            self.write_selection("topRight", &active_cell, &range);
+        let (has_row_split, has_col_split) = if self.panes.is_split() {
+            (self.panes.split_height > 0.0, self.panes.split_width > 0.0)
+        } else {
+            (row > 0, col > 0)
+        };
+
+        // Get the panes and their default selections. The active pane uses
+        // the main worksheet selection.
+        let selections = if has_row_split && has_col_split {
+            vec![
+                (WorksheetPane::TopRight, Selection::from_cell(0, col)),
+                (WorksheetPane::BottomLeft, Selection::from_cell(row, 0)),
+                (WorksheetPane::BottomRight, self.selection.clone()),
+            ]
+        } else if has_col_split {
+            vec![(WorksheetPane::TopRight, self.selection.clone())]
         } else {
-            self.write_pane("bottomLeft");
-            self.write_selection("bottomLeft", &active_cell, &range);
+            vec![(WorksheetPane::BottomLeft, self.selection.clone())]
+        };
+
+        // Write the pane and selection elements.
+        let active_pane = selections[selections.len() - 1].0;
+        self.write_pane(&active_pane.to_string());
+
+        // The top left pane selection is only written if it is set explicitly.
+        let mut selections = selections;
+        selections.insert(0, (WorksheetPane::TopLeft, Selection::default()));
+
+        for (pane, default_selection) in selections {
+            let selection = self
+                .pane_selections
+                .iter()
+                .find(|(position, _)| *position == pane)
+                .map_or(default_selection, |(_, selection)| selection.clone());
+
+            if pane == WorksheetPane::TopLeft && selection.active_cell.is_empty() {
+                continue;
+            }
+
+            self.write_selection(&pane.to_string(), &selection);
+        }
+    }
+
+    // Get the cell at the top left of the bottom right pane, excluding any
+    // scrolling, for frozen panes. For split panes it is the explicit top left
+    // cell or, if that isn't set, an estimate of the cell at the split based
+    // on the default row height and column width.
+    fn pane_cell(&self) -> (RowNum, ColNum) {
+        if !self.panes.is_split() {
+            return self.panes.freeze_cell;
+        }
+
+        if self.panes.top_cell != (0, 0) {
+            return self.panes.top_cell;
         }
+
+        let (width_pixels, _) = self.split_pane_width();
+        let row_height = f64::from(self.default_row_height) * 0.75;
+        let col_width = f64::from(self.default_col_width);
+
+        let row = (self.panes.split_height / row_height).round() as RowNum;
+        let col = (f64::from(width_pixels) / col_width).round() as ColNum;
+
+        (row.min(ROW_MAX - 1), col.min(COL_MAX - 1))
+    }
+
+    // Convert the split pane width from character units to pixels and to the
+    // twips (twentieths of a point) used by Excel, with Excel's padding.
+    fn split_pane_width(&self) -> (u32, f64) {
+        let width = self.panes.split_width;
+
+        let pixels = if width < 1.0 {
+            (width * f64::from(self.max_digit_width + self.cell_padding)).round() as u32
+        } else {
+            (width * f64::from(self.max_digit_width)).round() as u32 + self.cell_padding
+        };
+
+        let twips = f64::from(pixels) * 0.75 * 20.0 + 390.0;
+
+        (pixels, twips)
     }
 
     // Write the <pane> element.
     fn write_pane(&mut self, active_pane: &str) {
+        if self.panes.is_split() {
+            self.write_split_pane(active_pane);
+            return;
+        }
+
         let row = self.panes.freeze_cell.0;
         let col = self.panes.freeze_cell.1;
         let mut attributes = vec![];
@@ -18256,20 +21162,47 @@ impl Worksheet {
         xml_empty_tag(&mut self.writer, "pane", &attributes);
     }
 
+    // Write the <pane> element for split panes. The split positions are in
+    // twips with Excel's padding.
+    fn write_split_pane(&mut self, active_pane: &str) {
+        let (row, col) = self.pane_cell();
+        let mut attributes = vec![];
+
+        if self.panes.split_width > 0.0 {
+            let (_, twips) = self.split_pane_width();
+            attributes.push(("xSplit", twips.to_string()));
+        }
+
+        if self.panes.split_height > 0.0 {
+            let twips = (self.panes.split_height * 20.0 + 300.0).trunc();
+            attributes.push(("ySplit", twips.to_string()));
+        }
+
+        attributes.push(("topLeftCell", utility::row_col_to_cell(row, col)));
+        attributes.push(("activePane", active_pane.to_string()));
+        attributes.push(("state", "split".to_string()));
+
+        xml_empty_tag(&mut self.writer, "pane", &attributes);
+    }
+
     // Write the <selection> element.
-    fn write_selection(&mut self, position: &str, active_cell: &str, range: &str) {
+    fn write_selection(&mut self, position: &str, selection: &Selection) {
         let mut attributes = vec![];
 
         if !position.is_empty() {
             attributes.push(("pane", position.to_string()));
         }
 
-        if !active_cell.is_empty() {
-            attributes.push(("activeCell", active_cell.to_string()));
+        if !selection.active_cell.is_empty() {
+            attributes.push(("activeCell", selection.active_cell.clone()));
+        }
+
+        if selection.active_cell_id > 0 {
+            attributes.push(("activeCellId", selection.active_cell_id.to_string()));
         }
 
-        if !range.is_empty() {
-            attributes.push(("sqref", range.to_string()));
+        if !selection.sqref.is_empty() {
+            attributes.push(("sqref", selection.sqref.clone()));
         }
 
         xml_empty_tag(&mut self.writer, "selection", &attributes);
@@ -18517,6 +21450,7 @@ impl Worksheet {
         xml_start_tag(&mut self.writer, "dataValidation", &attributes);
 
         // Write the <formula1>/<formula2> elements.
+        let is_date = data_validation.validation_type == DataValidationType::Date;
         match &data_validation.rule {
             DataValidationRuleInternal::EqualTo(value)
             | DataValidationRuleInternal::NotEqualTo(value)
@@ -18526,17 +21460,29 @@ impl Worksheet {
             | DataValidationRuleInternal::GreaterThanOrEqualTo(value)
             | DataValidationRuleInternal::ListSource(value)
             | DataValidationRuleInternal::CustomFormula(value) => {
-                xml_data_element_only(&mut self.writer, "formula1", value);
+                let value = self.data_validation_value(value, is_date);
+                xml_data_element_only(&mut self.writer, "formula1", &value);
             }
             DataValidationRuleInternal::Between(min, max)
             | DataValidationRuleInternal::NotBetween(min, max) => {
-                xml_data_element_only(&mut self.writer, "formula1", min);
-                xml_data_element_only(&mut self.writer, "formula2", max);
+                let min = self.data_validation_value(min, is_date);
+                let max = self.data_validation_value(max, is_date);
+                xml_data_element_only(&mut self.writer, "formula1", &min);
+                xml_data_element_only(&mut self.writer, "formula2", &max);
             }
         }
         xml_end_tag(&mut self.writer, "dataValidation");
     }
 
+    // Convert a data validation date value to the workbook date epoch. Cell
+    // references and formulas are left unchanged.
+    fn data_validation_value(&self, value: &str, is_date: bool) -> String {
+        match value.parse::<f64>() {
+            Ok(number) if is_date => self.datetime_to_epoch(number).to_string(),
+            _ => value.to_string(),
+        }
+    }
+
     // Write the <dataValidation> element.
     fn write_data_validation_any(&mut self, range: &str, data_validation: &DataValidation) {
         let mut attributes = vec![];
@@ -18821,6 +21767,11 @@ impl Worksheet {
     // Store unique strings in the SST table and convert them to a string id
     // which is used when writing out the string cells.
     pub(crate) fn update_string_table_ids(&mut self, string_table: Arc<Mutex<SharedStringsTable>>) {
+        // The strings in a template worksheet are always added to the table.
+        if let Some(template) = &mut self.template {
+            template.update_string_ids(&mut string_table.lock().unwrap());
+        }
+
         if !self.has_local_string_table {
             return;
         }
@@ -18886,8 +21837,7 @@ impl Worksheet {
                 };
 
                 match cell {
-                    CellType::Number { number, xf_index }
-                    | CellType::DateTime { number, xf_index } => {
+                    CellType::Number { number, xf_index } => {
                         let xf_index = self.get_cell_xf_index(*xf_index, row_options, col_num);
                         Self::write_number_cell(
                             &mut self.writer,
@@ -18898,6 +21848,18 @@ impl Worksheet {
                         );
                     }
 
+                    CellType::DateTime { number, xf_index } => {
+                        let xf_index = self.get_cell_xf_index(*xf_index, row_options, col_num);
+                        let number = self.datetime_to_epoch(*number);
+                        Self::write_number_cell(
+                            &mut self.writer,
+                            row_num + 1,
+                            col_name,
+                            number,
+                            xf_index,
+                        );
+                    }
+
                     CellType::String {
                         string_id,
                         xf_index,
@@ -18984,13 +21946,19 @@ impl Worksheet {
                         );
                     }
 
-                    CellType::Error { value, xf_index } => {
+                    CellType::Error {
+                        error,
+                        xf_index,
+                        image_id,
+                    } => {
                         let xf_index = self.get_cell_xf_index(*xf_index, row_options, col_num);
-                        let image_id = self.global_embedded_image_indices[*value as usize];
+                        let image_id =
+                            image_id.map(|id| self.global_embedded_image_indices[id as usize]);
                         Self::write_error_cell(
                             &mut self.writer,
                             row_num + 1,
                             col_name,
+                            *error,
                             image_id,
                             xf_index,
                         );
@@ -19114,7 +22082,7 @@ impl Worksheet {
             };
 
             match cell {
-                CellType::Number { number, xf_index } | CellType::DateTime { number, xf_index } => {
+                CellType::Number { number, xf_index } => {
                     let xf_index = self.get_cell_xf_index(*xf_index, row_options, col_num);
                     Self::write_number_cell(
                         &mut self.file_writer,
@@ -19125,6 +22093,18 @@ impl Worksheet {
                     );
                 }
 
+                CellType::DateTime { number, xf_index } => {
+                    let xf_index = self.get_cell_xf_index(*xf_index, row_options, col_num);
+                    let number = self.datetime_to_epoch(*number);
+                    Self::write_number_cell(
+                        &mut self.file_writer,
+                        current_row + 1,
+                        col_name,
+                        number,
+                        xf_index,
+                    );
+                }
+
                 CellType::String {
                     string_id,
                     xf_index,
@@ -19226,13 +22206,19 @@ impl Worksheet {
                     );
                 }
 
-                CellType::Error { value, xf_index } => {
+                CellType::Error {
+                    error,
+                    xf_index,
+                    image_id,
+                } => {
                     let xf_index = self.get_cell_xf_index(*xf_index, row_options, col_num);
-                    let image_id = self.global_embedded_image_indices[*value as usize];
+                    let image_id =
+                        image_id.map(|id| self.global_embedded_image_indices[id as usize]);
                     Self::write_error_cell(
                         &mut self.file_writer,
                         current_row + 1,
                         col_name,
+                        *error,
                         image_id,
                         xf_index,
                     );
@@ -19676,28 +22662,32 @@ impl Worksheet {
         }
     }
 
-    // Write the <c> element for an error cell. We currently only support the
-    // #VALUE! error type which is used for embedded images.
+    // Write the <c> element for an error cell. Embedded images are stored as
+    // #VALUE! errors with a value metadata index.
     fn write_error_cell<W: Write>(
         writer: &mut W,
         row: RowNum,
         col_name: &str,
-        value: u32,
+        error: ExcelError,
+        image_id: Option<u32>,
         xf_index: u32,
     ) {
-        if xf_index > 0 {
-            write!(
-                writer,
-                r#"<c r="{col_name}{row}" s="{xf_index}" t="e" vm="{value}"><v>#VALUE!</v></c>"#
-            )
-            .expect(XML_WRITE_ERROR);
+        let style = if xf_index > 0 {
+            format!(r#" s="{xf_index}""#)
         } else {
-            write!(
-                writer,
-                r#"<c r="{col_name}{row}" t="e" vm="{value}"><v>#VALUE!</v></c>"#
-            )
-            .expect(XML_WRITE_ERROR);
-        }
+            String::new()
+        };
+
+        let metadata = match image_id {
+            Some(image_id) => format!(r#" vm="{image_id}""#),
+            None => String::new(),
+        };
+
+        write!(
+            writer,
+            r#"<c r="{col_name}{row}"{style} t="e"{metadata}><v>{error}</v></c>"#
+        )
+        .expect(XML_WRITE_ERROR);
     }
 
     // Write the <cols> element.
@@ -19800,6 +22790,16 @@ impl Worksheet {
     // Write the <headerFooter> element.
     fn write_header_footer(&mut self) {
         let mut attributes = vec![];
+        let has_even = self.header_footer_different_odd_even;
+        let has_first = self.header_footer_different_first;
+
+        if has_even {
+            attributes.push(("differentOddEven", "1".to_string()));
+        }
+
+        if has_first {
+            attributes.push(("differentFirst", "1".to_string()));
+        }
 
         if !self.header_footer_scale_with_doc {
             attributes.push(("scaleWithDoc", "0".to_string()));
@@ -19809,55 +22809,60 @@ impl Worksheet {
             attributes.push(("alignWithMargins", "0".to_string()));
         }
 
-        if self.header.is_empty() && self.footer.is_empty() {
+        let is_empty = [
+            &self.header,
+            &self.footer,
+            &self.even_header,
+            &self.even_footer,
+            &self.first_header,
+            &self.first_footer,
+        ]
+        .iter()
+        .all(|string| string.is_empty());
+
+        if is_empty {
             xml_empty_tag(&mut self.writer, "headerFooter", &attributes);
         } else {
             xml_start_tag(&mut self.writer, "headerFooter", &attributes);
 
             // Write the oddHeader element.
             if !self.header.is_empty() {
-                self.write_odd_header();
+                let header = Self::expand_header_footer(&self.header);
+                xml_data_element_only(&mut self.writer, "oddHeader", &header);
             }
 
             // Write the oddFooter element.
             if !self.footer.is_empty() {
-                self.write_odd_footer();
+                let footer = Self::expand_header_footer(&self.footer);
+                xml_data_element_only(&mut self.writer, "oddFooter", &footer);
             }
 
-            xml_end_tag(&mut self.writer, "headerFooter");
-        }
-    }
+            // Write the evenHeader element.
+            if !self.even_header.is_empty() {
+                let header = Self::expand_header_footer(&self.even_header);
+                xml_data_element_only(&mut self.writer, "evenHeader", &header);
+            }
 
-    // Write the <oddHeader> element.
-    fn write_odd_header(&mut self) {
-        let header = self
-            .header
-            .replace("&[Tab]", "&A")
-            .replace("&[Date]", "&D")
-            .replace("&[File]", "&F")
-            .replace("&[Page]", "&P")
-            .replace("&[Path]", "&Z")
-            .replace("&[Time]", "&T")
-            .replace("&[Pages]", "&N")
-            .replace("&[Picture]", "&G");
+            // Write the evenFooter element.
+            if !self.even_footer.is_empty() {
+                let footer = Self::expand_header_footer(&self.even_footer);
+                xml_data_element_only(&mut self.writer, "evenFooter", &footer);
+            }
 
-        xml_data_element_only(&mut self.writer, "oddHeader", &header);
-    }
+            // Write the firstHeader element.
+            if !self.first_header.is_empty() {
+                let header = Self::expand_header_footer(&self.first_header);
+                xml_data_element_only(&mut self.writer, "firstHeader", &header);
+            }
 
-    // Write the <oddFooter> element.
-    fn write_odd_footer(&mut self) {
-        let footer = self
-            .footer
-            .replace("&[Tab]", "&A")
-            .replace("&[Date]", "&D")
-            .replace("&[File]", "&F")
-            .replace("&[Page]", "&P")
-            .replace("&[Path]", "&Z")
-            .replace("&[Time]", "&T")
-            .replace("&[Pages]", "&N")
-            .replace("&[Picture]", "&G");
+            // Write the firstFooter element.
+            if !self.first_footer.is_empty() {
+                let footer = Self::expand_header_footer(&self.first_footer);
+                xml_data_element_only(&mut self.writer, "firstFooter", &footer);
+            }
 
-        xml_data_element_only(&mut self.writer, "oddFooter", &footer);
+            xml_end_tag(&mut self.writer, "headerFooter");
+        }
     }
 
     // Write the <drawing> element.
@@ -19919,11 +22924,10 @@ impl Worksheet {
 
     // Write the <sheetProtection> element.
     fn write_sheet_protection(&mut self) {
-        let mut attributes = vec![];
-
-        if self.protection_hash != 0x0000 {
-            attributes.push(("password", format!("{:04X}", self.protection_hash)));
-        }
+        let salt = self.protection_salts.first().map_or(&[][..], Vec::as_slice);
+        let mut attributes =
+            self.protection_hash
+                .password_attributes(&self.protection_password, false, salt);
 
         if self.is_chartsheet {
             if self.protection_options.contents {
@@ -20004,20 +23008,25 @@ impl Worksheet {
     fn write_protected_ranges(&mut self) {
         xml_start_tag_only(&mut self.writer, "protectedRanges");
 
-        for (range, name, hash) in self.unprotected_ranges.clone() {
-            self.write_protected_range(range, name, hash);
+        for (index, (range, name, password)) in
+            self.unprotected_ranges.clone().into_iter().enumerate()
+        {
+            self.write_protected_range(range, name, &password, index + 1);
         }
 
         xml_end_tag(&mut self.writer, "protectedRanges");
     }
 
-    // Write the <protectedRange> element.
-    fn write_protected_range(&mut self, range: String, name: String, hash: u16) {
-        let mut attributes = vec![];
-
-        if hash > 0 {
-            attributes.push(("password", format!("{hash:04X}")));
-        }
+    // Write the <protectedRange> element. The salts for the range passwords
+    // follow the worksheet password salt.
+    fn write_protected_range(&mut self, range: String, name: String, password: &str, index: usize) {
+        let salt = self
+            .protection_salts
+            .get(index)
+            .map_or(&[][..], Vec::as_slice);
+        let mut attributes = self
+            .protection_hash
+            .password_attributes(password, false, salt);
 
         attributes.push(("sqref", range));
         attributes.push(("name", name));
@@ -20111,9 +23120,40 @@ impl Worksheet {
             self.write_sparkline_groups();
         }
 
+        // Write the x14:slicerList element.
+        if !self.slicers.is_empty() {
+            let attributes = [
+                (
+                    "xmlns:x15",
+                    "http://schemas.microsoft.com/office/spreadsheetml/2010/11/main",
+                ),
+                ("uri", "{3A4CF648-6AED-40f4-86FF-DC5316D8AED3}"),
+            ];
+            xml_start_tag(&mut self.writer, "ext", &attributes);
+            self.write_slicer_list();
+        }
+
         xml_end_tag(&mut self.writer, "extLst");
     }
 
+    // Write the <x14:slicerList> element.
+    fn write_slicer_list(&mut self) {
+        let attributes = [(
+            "xmlns:x14",
+            "http://schemas.microsoft.com/office/spreadsheetml/2009/9/main",
+        )];
+
+        xml_start_tag(&mut self.writer, "x14:slicerList", &attributes);
+
+        // The slicer relationship follows the table relationships.
+        self.rel_count += 1;
+        let attributes = [("r:id", format!("rId{}", self.rel_count))];
+        xml_empty_tag(&mut self.writer, "x14:slicer", &attributes);
+
+        xml_end_tag(&mut self.writer, "x14:slicerList");
+        xml_end_tag(&mut self.writer, "ext");
+    }
+
     // Write the <x14:sparklineGroups> element.
     fn write_sparkline_groups(&mut self) {
         let attributes = [(
@@ -20527,6 +23567,27 @@ impl IntoExcelData for bool {
     }
 }
 
+impl IntoExcelData for ExcelError {
+    fn write(
+        self,
+        worksheet: &mut Worksheet,
+        row: RowNum,
+        col: ColNum,
+    ) -> Result<&mut Worksheet, XlsxError> {
+        worksheet.store_error(row, col, self, None)
+    }
+
+    fn write_with_format<'a>(
+        self,
+        worksheet: &'a mut Worksheet,
+        row: RowNum,
+        col: ColNum,
+        format: &Format,
+    ) -> Result<&'a mut Worksheet, XlsxError> {
+        worksheet.store_error(row, col, self, Some(format))
+    }
+}
+
 impl IntoExcelData for &ExcelDateTime {
     fn write(
         self,
@@ -20993,8 +24054,9 @@ enum CellType {
         xf_index: u32,
     },
     Error {
+        error: ExcelError,
         xf_index: u32,
-        value: u32,
+        image_id: Option<u32>,
     },
     Formula {
         formula: Box<str>,
@@ -21043,11 +24105,17 @@ enum FilterType {
 struct Panes {
     freeze_cell: (RowNum, ColNum),
     top_cell: (RowNum, ColNum),
+    split_height: f64,
+    split_width: f64,
 }
 
 impl Panes {
     fn is_empty(&self) -> bool {
-        self.freeze_cell.0 == 0 && self.freeze_cell.1 == 0
+        self.freeze_cell.0 == 0 && self.freeze_cell.1 == 0 && !self.is_split()
+    }
+
+    fn is_split(&self) -> bool {
+        self.split_height > 0.0 || self.split_width > 0.0
     }
 
     fn top_left(&self) -> String {
@@ -21059,6 +24127,27 @@ impl Panes {
     }
 }
 
+// Struct to hold the active cell and the selected range(s) of a worksheet or
+// worksheet pane.
+#[derive(Clone, Default)]
+struct Selection {
+    active_cell: String,
+    sqref: String,
+    active_cell_id: usize,
+}
+
+impl Selection {
+    fn from_cell(row: RowNum, col: ColNum) -> Selection {
+        let cell = utility::row_col_to_cell(row, col);
+
+        Selection {
+            active_cell: cell.clone(),
+            sqref: cell,
+            active_cell_id: 0,
+        }
+    }
+}
+
 // Struct to hold and transform data for the various defined names variants:
 // user defined names, autofilters, print titles and print areas.
 #[derive(Clone)]
@@ -21291,7 +24380,160 @@ impl fmt::Display for IgnoreError {
     }
 }
 
-#[derive(Clone, Debug)]
+/// The `ExcelError` enum defines the Excel error values that can be written
+/// to a worksheet cell.
+///
+/// It is used with the [`Worksheet::write_error()`] and
+/// [`Worksheet::write_error_with_format()`] methods. It also implements
+/// [`IntoExcelData`] so it can be used with [`Worksheet::write()`].
+///
+/// Error values are stored in the file as error types rather than strings so
+/// they behave in Excel like the result of a formula error. For example they
+/// are detected by functions such as `ISNA()`, `ISERROR()` and `IFERROR()`.
+///
+/// # Examples
+///
+/// The following example demonstrates writing Excel error values to a
+/// worksheet.
+///
+/// ```
+/// # // This code is available in examples/doc_excel_error.rs
+/// #
+/// # use rust_xlsxwriter::{ExcelError, Workbook, XlsxError};
+/// #
+/// # fn main() -> Result<(), XlsxError> {
+/// #     let mut workbook = Workbook::new();
+/// #
+///     let worksheet = workbook.add_worksheet();
+///
+///     worksheet.write(0, 0, ExcelError::NA)?;
+///     worksheet.write(1, 0, ExcelError::Value)?;
+///     worksheet.write(2, 0, ExcelError::Div0)?;
+/// #
+/// #     workbook.save("worksheet.xlsx")?;
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExcelError {
+    /// The `#NULL!` error for ranges that don't intersect.
+    Null,
+
+    /// The `#DIV/0!` error for division by zero.
+    Div0,
+
+    /// The `#VALUE!` error for a value of the wrong type.
+    Value,
+
+    /// The `#REF!` error for an invalid cell reference.
+    Ref,
+
+    /// The `#NAME?` error for an unrecognized name or function.
+    Name,
+
+    /// The `#NUM!` error for an invalid numeric value.
+    Num,
+
+    /// The `#N/A` error for a value that isn't available.
+    NA,
+
+    /// The `#GETTING_DATA` error for data that is still being retrieved.
+    GettingData,
+
+    /// The `#SPILL!` error for a dynamic array formula that can't spill its
+    /// results into the worksheet.
+    Spill,
+
+    /// The `#CALC!` error for a calculation that Excel can't evaluate, such
+    /// as a function that returns an empty array.
+    Calc,
+}
+
+impl ExcelError {
+    // Get the Excel string representation of the error.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Null => "#NULL!",
+            Self::Div0 => "#DIV/0!",
+            Self::Value => "#VALUE!",
+            Self::Ref => "#REF!",
+            Self::Name => "#NAME?",
+            Self::Num => "#NUM!",
+            Self::NA => "#N/A",
+            Self::GettingData => "#GETTING_DATA",
+            Self::Spill => "#SPILL!",
+            Self::Calc => "#CALC!",
+        }
+    }
+
+    // Get the error value from its Excel string representation, ignoring case.
+    pub(crate) fn from_excel_str(value: &str) -> Option<ExcelError> {
+        [
+            Self::Null,
+            Self::Div0,
+            Self::Value,
+            Self::Ref,
+            Self::Name,
+            Self::Num,
+            Self::NA,
+            Self::GettingData,
+            Self::Spill,
+            Self::Calc,
+        ]
+        .into_iter()
+        .find(|error| error.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+impl fmt::Display for ExcelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The `WorksheetPane` enum defines the panes of a worksheet with freeze or
+/// split panes.
+///
+/// It is used with the [`Worksheet::set_pane_selection_ranges()`] method to
+/// set the cell selection in a specific pane.
+///
+/// A worksheet that is split horizontally has `TopLeft` and `BottomLeft`
+/// panes, a worksheet that is split vertically has `TopLeft` and `TopRight`
+/// panes, and a worksheet that is split in both directions has all four panes.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorksheetPane {
+    /// The top left pane. This is the pane above and/or to the left of the
+    /// splits.
+    TopLeft,
+
+    /// The top right pane. This is the pane to the right of a vertical split
+    /// and above any horizontal split.
+    TopRight,
+
+    /// The bottom left pane. This is the pane below a horizontal split and to
+    /// the left of any vertical split.
+    BottomLeft,
+
+    /// The bottom right pane. This is the pane below and to the right of the
+    /// splits, when the worksheet is split in both directions.
+    BottomRight,
+}
+
+impl fmt::Display for WorksheetPane {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TopLeft => write!(f, "topLeft"),
+            Self::TopRight => write!(f, "topRight"),
+            Self::BottomLeft => write!(f, "bottomLeft"),
+            Self::BottomRight => write!(f, "bottomRight"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub(crate) enum DefinedNameType {
     Autofilter,
     Global,
@@ -43,6 +43,398 @@ mod worksheet_tests {
         assert_eq!(expected, got);
     }
 
+    #[test]
+    #[cfg(feature = "protection_hash")]
+    fn test_assemble_protection_sha512() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+
+        worksheet.protect_with_password("password");
+        worksheet.set_protection_hash(ProtectionHash::Sha512WithSalt {
+            salt: (0..16).collect(),
+            spin_count: 1000,
+        });
+        worksheet
+            .unprotect_range_with_options(0, 0, 0, 0, "", "xyz")
+            .unwrap();
+        worksheet
+            .unprotect_range_with_options(1, 0, 1, 0, "", "")
+            .unwrap();
+
+        worksheet.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <dimension ref="A1"/>
+              <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0"/>
+              </sheetViews>
+              <sheetFormatPr defaultRowHeight="15"/>
+              <sheetData/>
+              <sheetProtection algorithmName="SHA-512" hashValue="JYm/317hauMFbamnyQb048iQxmvnd9mXAguXAaGG0eGe8afJLHy7BllaDEbZ5EHzHoK5Zkg3PVPsVywxsKL+3w==" saltValue="AAECAwQFBgcICQoLDA0ODw==" spinCount="1000" sheet="1" objects="1" scenarios="1"/>
+              <protectedRanges>
+                <protectedRange algorithmName="SHA-512" hashValue="XzFFa2Iuy8girIQ01dbp9RA5/kzVS4ENQWgrDNVxD92U8ZRSZ0B8HCndbp7zRnHpAHwVHU41J12NI4r0G9tvng==" saltValue="AAECAwQFBgcICQoLDA0ODw==" spinCount="1000" sqref="A1" name="Range1"/>
+                <protectedRange sqref="A2" name="Range2"/>
+              </protectedRanges>
+              <pageMargins left="0.7" right="0.7" top="0.75" bottom="0.75" header="0.3" footer="0.3"/>
+            </worksheet>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_assemble_error_values() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+        let format = Format::new().set_bold();
+        worksheet.global_xf_indices = vec![0, 1];
+
+        worksheet.write_error(0, 0, ExcelError::NA).unwrap();
+        worksheet.write(1, 0, ExcelError::Div0).unwrap();
+        worksheet
+            .write_error_with_format(2, 0, ExcelError::Name, &format)
+            .unwrap();
+        worksheet.write(3, 0, ExcelError::Spill).unwrap();
+        worksheet.write(4, 0, ExcelError::Calc).unwrap();
+
+        worksheet.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+              <dimension ref="A1:A5"/>
+              <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0"/>
+              </sheetViews>
+              <sheetFormatPr defaultRowHeight="15"/>
+              <sheetData>
+                <row r="1" spans="1:1">
+                  <c r="A1" t="e"><v>#N/A</v></c>
+                </row>
+                <row r="2" spans="1:1">
+                  <c r="A2" t="e"><v>#DIV/0!</v></c>
+                </row>
+                <row r="3" spans="1:1">
+                  <c r="A3" s="1" t="e"><v>#NAME?</v></c>
+                </row>
+                <row r="4" spans="1:1">
+                  <c r="A4" t="e"><v>#SPILL!</v></c>
+                </row>
+                <row r="5" spans="1:1">
+                  <c r="A5" t="e"><v>#CALC!</v></c>
+                </row>
+              </sheetData>
+              <pageMargins left="0.7" right="0.7" top="0.75" bottom="0.75" header="0.3" footer="0.3"/>
+            </worksheet>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        let result = worksheet.write_error(ROW_MAX, 0, ExcelError::NA);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+    }
+
+    // Assemble the worksheet and return the <sheetData> element, to test the
+    // conversion of the dataframe and record batch data types.
+    #[cfg(any(feature = "arrow", feature = "polars"))]
+    fn assemble_sheet_data(worksheet: &mut Worksheet) -> Vec<String> {
+        worksheet.assemble_xml_file();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let mut got = xml_to_vec(got);
+
+        let start = got
+            .iter()
+            .position(|element| element.starts_with("<sheetData"));
+        let end = got.iter().position(|element| element == "</sheetData>");
+        match (start, end) {
+            (Some(start), Some(end)) => got.drain(start..=end).collect(),
+            _ => got,
+        }
+    }
+
+    #[cfg(feature = "polars")]
+    #[test]
+    fn test_assemble_dataframe_dtypes() {
+        use polars::prelude::*;
+
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+        worksheet.global_xf_indices = vec![0, 1, 2, 3, 4];
+
+        let categorical = DataType::from_categories(Categories::global());
+        let df = DataFrame::new_infer_height(vec![
+            Column::new("bool".into(), &[true]),
+            Column::new("datetime".into(), &[1_704_110_400_000i64])
+                .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+                .unwrap(),
+            Column::new("time".into(), &[43_200_000_000_000i64])
+                .cast(&DataType::Time)
+                .unwrap(),
+            Column::new("duration".into(), &[129_600_000i64])
+                .cast(&DataType::Duration(TimeUnit::Milliseconds))
+                .unwrap(),
+            Column::new("decimal".into(), &[1.25])
+                .cast(&DataType::Decimal(10, 2))
+                .unwrap(),
+            Column::new("categorical".into(), &["Apple"])
+                .cast(&categorical)
+                .unwrap(),
+            Column::new("null".into(), &[None::<i32>]),
+        ])
+        .unwrap();
+
+        let options = DataFrameOptions::new()
+            .set_header(false)
+            .set_null_value("N/A");
+
+        worksheet.write_dataframe(0, 0, &df, &options).unwrap();
+
+        let got = assemble_sheet_data(&mut worksheet);
+        let expected = xml_to_vec(
+            r#"
+            <sheetData>
+              <row r="1" spans="1:7">
+                <c r="A1" t="b"><v>1</v></c>
+                <c r="B1" s="1"><v>45292.5</v></c>
+                <c r="C1" s="2"><v>0.5</v></c>
+                <c r="D1" s="3"><v>1.5</v></c>
+                <c r="E1" s="4"><v>1.25</v></c>
+              </row>
+            </sheetData>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        // The string cells are only written when the workbook is saved, so
+        // check the stored values instead.
+        for (col, expected) in [(5, "Apple"), (6, "N/A")] {
+            let cell = worksheet.data_table.get(&0).and_then(|row| row.get(&col));
+            assert!(
+                matches!(cell, Some(CellType::String { string, .. }) if string.as_ref() == expected)
+            );
+        }
+
+        // Test an unsupported data type.
+        let df = df!("binary" => &[b"abc".as_slice()]).unwrap();
+        let result = worksheet.write_dataframe(0, 0, &df, &options);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+    }
+
+    #[cfg(feature = "polars")]
+    #[test]
+    fn test_dataframe_table_and_autofit() {
+        use polars::prelude::*;
+
+        let mut worksheet = Worksheet::new();
+
+        // Data outside the dataframe shouldn't be autofitted.
+        worksheet
+            .write_string(0, 0, "A long string that isn't part of the dataframe")
+            .unwrap();
+
+        let df = df!(
+            "Fruit" => &["Apple", "Pear"],
+            "Cost" => &[1.25, 0.75],
+        )
+        .unwrap();
+
+        let header_format = Format::new().set_bold();
+        let options = DataFrameOptions::new()
+            .set_header_format(&header_format)
+            .set_table(&Table::new())
+            .set_autofit(true);
+
+        worksheet.write_dataframe(2, 1, &df, &options).unwrap();
+
+        assert!(!worksheet.changed_cols.contains_key(&0));
+        assert!(worksheet.changed_cols.contains_key(&1));
+        assert!(worksheet.changed_cols.contains_key(&2));
+
+        // The table shouldn't overwrite the formatted header cells.
+        let header_xf_index = worksheet.format_xf_index(&header_format);
+        for col in [1, 2] {
+            let cell = worksheet.data_table.get(&2).and_then(|row| row.get(&col));
+            assert!(
+                matches!(cell, Some(CellType::String { xf_index, .. }) if *xf_index == header_xf_index)
+            );
+        }
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_assemble_record_batch_dtypes() {
+        use arrow::array::*;
+        use std::sync::Arc;
+
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+        worksheet.global_xf_indices = vec![0, 1, 2, 3, 4, 5];
+
+        let batch = RecordBatch::try_from_iter([
+            ("bool", Arc::new(BooleanArray::from(vec![true])) as ArrayRef),
+            ("date", Arc::new(Date32Array::from(vec![19723]))),
+            (
+                "datetime",
+                Arc::new(TimestampMillisecondArray::from(vec![1_704_110_400_000])),
+            ),
+            (
+                "time",
+                Arc::new(Time64NanosecondArray::from(vec![43_200_000_000_000])),
+            ),
+            (
+                "duration",
+                Arc::new(DurationMillisecondArray::from(vec![129_600_000])),
+            ),
+            (
+                "decimal",
+                Arc::new(
+                    Decimal128Array::from(vec![125])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ),
+            ),
+            ("null", Arc::new(Int32Array::from(vec![None]))),
+        ])
+        .unwrap();
+
+        worksheet
+            .write_record_batch(0, 0, &batch, &DataFrameOptions::new())
+            .unwrap();
+
+        let got = assemble_sheet_data(&mut worksheet);
+        let expected = xml_to_vec(
+            r#"
+            <sheetData>
+              <row r="1" spans="1:7"></row>
+              <row r="2" spans="1:7">
+                <c r="A2" t="b"><v>1</v></c>
+                <c r="B2" s="1"><v>45292</v></c>
+                <c r="C2" s="2"><v>45292.5</v></c>
+                <c r="D2" s="3"><v>0.5</v></c>
+                <c r="E2" s="4"><v>1.5</v></c>
+                <c r="F2" s="5"><v>1.25</v></c>
+              </row>
+            </sheetData>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        // The header strings are only written when the workbook is saved, so
+        // check the stored values instead.
+        for (col, expected) in [(0, "bool"), (6, "null")] {
+            let cell = worksheet.data_table.get(&0).and_then(|row| row.get(&col));
+            assert!(
+                matches!(cell, Some(CellType::String { string, .. }) if string.as_ref() == expected)
+            );
+        }
+
+        // Test batches with different schemas.
+        let batch1 =
+            RecordBatch::try_from_iter([("col1", Arc::new(Int32Array::from(vec![1])) as ArrayRef)])
+                .unwrap();
+        let batch2 =
+            RecordBatch::try_from_iter([("col2", Arc::new(Int32Array::from(vec![2])) as ArrayRef)])
+                .unwrap();
+
+        let result = worksheet.write_record_batches(
+            0,
+            0,
+            [Ok(batch1), Ok(batch2)],
+            &DataFrameOptions::new(),
+        );
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        // Test an unsupported data type.
+        let batch = RecordBatch::try_from_iter([(
+            "binary",
+            Arc::new(BinaryArray::from(vec![b"abc".as_slice()])) as ArrayRef,
+        )])
+        .unwrap();
+
+        let result = worksheet.write_record_batch(0, 0, &batch, &DataFrameOptions::new());
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_assemble_record_batch_options() {
+        use arrow::array::*;
+        use std::sync::Arc;
+
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+        worksheet.global_xf_indices = vec![0, 1, 2];
+
+        let batch = RecordBatch::try_from_iter([
+            (
+                "number",
+                Arc::new(Float64Array::from(vec![Some(1.5), None])) as ArrayRef,
+            ),
+            ("date", Arc::new(Date32Array::from(vec![Some(19723), None]))),
+        ])
+        .unwrap();
+
+        // The options have the same meaning as for dataframes.
+        let options = DataFrameOptions::new()
+            .set_header(false)
+            .set_null_value("N/A")
+            .set_column_format("number", &Format::new().set_num_format("0.00"))
+            .set_date_format(&Format::new().set_num_format("dd/mm/yyyy"));
+
+        worksheet
+            .write_record_batch(0, 0, &batch, &options)
+            .unwrap();
+
+        let got = assemble_sheet_data(&mut worksheet);
+        let expected = xml_to_vec(
+            r#"
+            <sheetData>
+              <row r="1" spans="1:2">
+                <c r="A1" s="1"><v>1.5</v></c>
+                <c r="B1" s="2"><v>45292</v></c>
+              </row>
+              <row r="2" spans="1:2"></row>
+            </sheetData>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        // The null string is only written when the workbook is saved, so check
+        // the stored values instead.
+        for col in [0, 1] {
+            let cell = worksheet.data_table.get(&1).and_then(|row| row.get(&col));
+            assert!(
+                matches!(cell, Some(CellType::String { string, .. }) if string.as_ref() == "N/A")
+            );
+        }
+    }
+
     #[test]
     fn verify_header_footer_images() {
         let strings = [
@@ -133,101 +525,426 @@ mod worksheet_tests {
     }
 
     #[test]
-    fn row_matches_list_filter_blanks() {
+    #[cfg(feature = "serde")]
+    fn serialize_nested_structs() {
+        use crate::{CustomSerializeField, SerializeFieldOptions};
+
         let mut worksheet = Worksheet::new();
-        let bold = Format::new().set_bold();
 
-        worksheet.write_string(0, 0, "Header").unwrap();
-        worksheet.write_string(1, 0, "").unwrap();
-        worksheet.write_string(2, 0, " ").unwrap();
-        worksheet.write_string(3, 0, "  ").unwrap();
-        worksheet.write_string_with_format(4, 0, "", &bold).unwrap();
+        #[derive(Serialize)]
+        struct Customer {
+            name: &'static str,
+            id: u32,
+        }
 
-        let filter_condition = FilterCondition::new().add_list_blanks_filter();
+        #[derive(Serialize)]
+        struct Order {
+            item: &'static str,
+            customer: Customer,
+            cost: f64,
+        }
 
-        assert!(!worksheet.row_matches_list_filter(0, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(1, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(2, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(3, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(4, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(5, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(7, 7, &filter_condition));
-    }
+        let data = Order {
+            item: "Peach",
+            customer: Customer {
+                name: "Alice",
+                id: 101,
+            },
+            cost: 1.05,
+        };
 
-    #[test]
-    fn row_matches_list_filter_strings() {
-        let mut worksheet = Worksheet::new();
-        worksheet.write_string(0, 0, "Header").unwrap();
-        worksheet.write_string(1, 0, "South").unwrap();
-        worksheet.write_string(2, 0, "south").unwrap();
-        worksheet.write_string(3, 0, "SOUTH").unwrap();
-        worksheet.write_string(4, 0, "South ").unwrap();
-        worksheet.write_string(5, 0, " South").unwrap();
-        worksheet.write_string(6, 0, " South ").unwrap();
-        worksheet.write_string(7, 0, "Mouth").unwrap();
+        // Nested structs aren't flattened by default.
+        worksheet.serialize_headers(0, 0, &data).unwrap();
+        let result = worksheet.get_serialize_column_dimensions("Order", "customer.name");
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
 
-        let filter_condition = FilterCondition::new().add_list_filter("South");
+        let header_options = SerializeFieldOptions::new()
+            .flatten_nested_structs(true)
+            .set_nested_separator("_")
+            .set_custom_headers(&[CustomSerializeField::new("customer_id").skip(true)]);
 
-        assert!(worksheet.row_matches_list_filter(1, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(2, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(3, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(4, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(5, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(6, 0, &filter_condition));
-        assert!(!worksheet.row_matches_list_filter(7, 0, &filter_condition));
-    }
+        worksheet
+            .serialize_headers_with_options(0, 0, &data, &header_options)
+            .unwrap();
+        worksheet.serialize(&data).unwrap();
+        worksheet.serialize(&data).unwrap();
 
-    #[test]
-    fn row_matches_list_filter_numbers() {
-        let mut worksheet = Worksheet::new();
+        let result = worksheet.get_serialize_dimensions("Order").unwrap();
+        assert_eq!((0, 0, 2, 2), result);
 
-        worksheet.write_string(0, 0, "Header").unwrap();
-        worksheet.write_number(1, 0, 1000).unwrap();
-        worksheet.write_number(2, 0, 1000.0).unwrap();
-        worksheet.write_string(3, 0, "1000").unwrap();
-        worksheet.write_string(4, 0, " 1000 ").unwrap();
-        worksheet.write_number(5, 0, 2000).unwrap();
+        let result = worksheet
+            .get_serialize_column_dimensions("Order", "customer_name")
+            .unwrap();
+        assert_eq!((0, 1, 2, 1), result);
 
-        let filter_condition = FilterCondition::new().add_list_filter(1000);
+        let result = worksheet
+            .get_serialize_column_dimensions("Order", "cost")
+            .unwrap();
+        assert_eq!((0, 2, 2, 2), result);
 
-        assert!(worksheet.row_matches_list_filter(1, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(2, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(3, 0, &filter_condition));
-        assert!(worksheet.row_matches_list_filter(4, 0, &filter_condition));
-        assert!(!worksheet.row_matches_list_filter(5, 0, &filter_condition));
+        // Check the header and the data written to the nested struct column.
+        for (row, expected) in [(0, "customer_name"), (1, "Alice"), (2, "Alice")] {
+            let cell = worksheet.data_table.get(&row).and_then(|row| row.get(&1));
+            assert!(
+                matches!(cell, Some(CellType::String { string, .. }) if string.as_ref() == expected)
+            );
+        }
+
+        let cell = worksheet.data_table.get(&2).and_then(|row| row.get(&2));
+        assert!(matches!(cell, Some(CellType::Number { number, .. }) if *number == 1.05));
     }
 
     #[test]
-    fn process_pagebreaks() {
+    #[cfg(feature = "serde")]
+    fn serialize_flattened_structs() {
+        use crate::SerializeFieldOptions;
+
         let mut worksheet = Worksheet::new();
 
-        // Test removing duplicates.
-        let got = Worksheet::process_pagebreaks(&[1, 1, 1, 1]).unwrap();
-        assert_eq!(vec![1], got);
+        #[derive(Serialize)]
+        struct Customer {
+            name: &'static str,
+            id: u32,
+        }
 
-        // Test removing 0.
-        let got = Worksheet::process_pagebreaks(&[0, 1, 2, 3, 4]).unwrap();
-        assert_eq!(vec![1, 2, 3, 4], got);
+        #[derive(Serialize)]
+        struct Order {
+            item: &'static str,
+            #[serde(flatten)]
+            customer: Customer,
+        }
 
-        // Test sort order.
-        let got = Worksheet::process_pagebreaks(&[1, 12, 2, 13, 3, 4]).unwrap();
-        assert_eq!(vec![1, 2, 3, 4, 12, 13], got);
+        let data = Order {
+            item: "Peach",
+            customer: Customer {
+                name: "Alice",
+                id: 101,
+            },
+        };
 
-        // Exceed the number of allow breaks.
-        let breaks = (1u32..=1024).collect::<Vec<u32>>();
-        let result = Worksheet::process_pagebreaks(&breaks);
-        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+        // Flattened structs are serialized as map records.
+        let result = worksheet.serialize_headers(0, 0, &data);
+        assert!(result.is_ok());
 
-        // Test row and column limits.
-        let result = worksheet.set_page_breaks(&[ROW_MAX]);
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+        let header_options = SerializeFieldOptions::new().flatten_nested_structs(true);
+        worksheet
+            .serialize_headers_with_options(0, 0, &data, &header_options)
+            .unwrap();
+        worksheet.serialize(&data).unwrap();
 
-        let result = worksheet.set_vertical_page_breaks(&[COL_MAX as u32]);
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
-    }
+        // The type name is used as the struct name and the flattened fields
+        // aren't prefixed.
+        let result = worksheet.get_serialize_dimensions("Order").unwrap();
+        assert_eq!((0, 0, 1, 2), result);
 
-    #[test]
-    fn set_header_image() {
+        let result = worksheet
+            .get_serialize_column_dimensions("Order", "id")
+            .unwrap();
+        assert_eq!((0, 2, 1, 2), result);
+
+        let cell = worksheet.data_table.get(&1).and_then(|row| row.get(&2));
+        assert!(matches!(cell, Some(CellType::Number { number, .. }) if *number == 101.0));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serialize_map_records() {
+        use crate::SerializeFieldOptions;
+        use std::collections::BTreeMap;
+
+        let mut worksheet = Worksheet::new();
+
+        let records = vec![
+            BTreeMap::from([("Apple", 10), ("Pear", 5)]),
+            BTreeMap::from([("Apple", 8), ("Orange", 7)]),
+        ];
+
+        // The headers are the union of the keys of all the records.
+        worksheet.serialize_headers(0, 0, &records).unwrap();
+        worksheet.serialize(&records).unwrap();
+
+        let result = worksheet.get_serialize_dimensions("BTreeMap").unwrap();
+        assert_eq!((0, 0, 2, 2), result);
+
+        let result = worksheet
+            .get_serialize_column_dimensions("BTreeMap", "Orange")
+            .unwrap();
+        assert_eq!((0, 2, 2, 2), result);
+
+        let cell = worksheet.data_table.get(&2).and_then(|row| row.get(&2));
+        assert!(matches!(cell, Some(CellType::Number { number, .. }) if *number == 7.0));
+
+        // Missing keys are left blank.
+        let cell = worksheet.data_table.get(&2).and_then(|row| row.get(&1));
+        assert!(cell.is_none());
+
+        // Unknown keys raise an error by default.
+        let record = BTreeMap::from([("Apple", 6), ("Plum", 12)]);
+        let result = worksheet.serialize(&record);
+        assert!(matches!(result, Err(XlsxError::SerdeError(_))));
+
+        // Explicit keys set the columns and their order.
+        let mut worksheet = Worksheet::new();
+        let header_options = SerializeFieldOptions::new().set_map_keys(["Pear", "Apple"]);
+
+        worksheet
+            .deserialize_headers_with_options::<BTreeMap<String, i32>>(0, 0, &header_options)
+            .unwrap();
+        worksheet.serialize(&records[0]).unwrap();
+
+        let cell = worksheet.data_table.get(&1).and_then(|row| row.get(&0));
+        assert!(matches!(cell, Some(CellType::Number { number, .. }) if *number == 5.0));
+
+        // Unknown keys are added as new columns with the option.
+        let mut worksheet = Worksheet::new();
+        let header_options = SerializeFieldOptions::new().add_unknown_keys(true);
+
+        worksheet
+            .serialize_headers_with_options(1, 1, &records, &header_options)
+            .unwrap();
+        worksheet.serialize(&records).unwrap();
+        worksheet.serialize(&record).unwrap();
+
+        let result = worksheet.get_serialize_dimensions("BTreeMap").unwrap();
+        assert_eq!((1, 1, 4, 4), result);
+
+        let cell = worksheet.data_table.get(&1).and_then(|row| row.get(&4));
+        assert!(matches!(cell, Some(CellType::String { string, .. }) if string.as_ref() == "Plum"));
+
+        let cell = worksheet.data_table.get(&4).and_then(|row| row.get(&4));
+        assert!(matches!(cell, Some(CellType::Number { number, .. }) if *number == 12.0));
+
+        // Unknown keys can't be added in constant memory mode.
+        let mut worksheet = Worksheet::new();
+        worksheet.use_constant_memory = true;
+
+        let result = worksheet.serialize_headers_with_options(0, 0, &records, &header_options);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_rows() {
+        use crate::{CustomSerializeField, ExcelDateTime, Format, Formula, SerializeFieldOptions};
+        use serde::Deserialize;
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        enum Status {
+            Open,
+            Closed,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Order {
+            id: u32,
+            item: String,
+            cost: f64,
+            paid: bool,
+            status: Status,
+            note: Option<String>,
+        }
+
+        let mut worksheet = Worksheet::new();
+
+        worksheet
+            .write_row(
+                0,
+                0,
+                ["id", "item", "cost", "paid", "status", "note", "extra"],
+            )
+            .unwrap();
+        worksheet.write(1, 0, 1).unwrap();
+        worksheet.write(1, 1, 123).unwrap();
+        worksheet.write(1, 2, 1.5).unwrap();
+        worksheet.write(1, 3, true).unwrap();
+        worksheet.write(1, 4, "Open").unwrap();
+        worksheet.write(1, 6, "ignored").unwrap();
+
+        // Numbers entered as text, rich strings and formula results.
+        let bold = Format::new().set_bold();
+        worksheet.write(2, 0, " 2").unwrap();
+        worksheet
+            .write_rich_string(2, 1, &[(&bold, "Pl"), (&Format::default(), "um")])
+            .unwrap();
+        worksheet
+            .write_formula(2, 2, Formula::new("=1/4").set_result("0.25"))
+            .unwrap();
+        worksheet.write(2, 3, 0).unwrap();
+        worksheet.write(2, 4, "Closed").unwrap();
+        worksheet.write(2, 5, "Late").unwrap();
+
+        // The data stops at the first blank row.
+        worksheet.write(4, 0, 4).unwrap();
+
+        let orders: Vec<Order> = worksheet
+            .deserialize_rows(0, 0)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let expected = vec![
+            Order {
+                id: 1,
+                item: "123".to_string(),
+                cost: 1.5,
+                paid: true,
+                status: Status::Open,
+                note: None,
+            },
+            Order {
+                id: 2,
+                item: "Plum".to_string(),
+                cost: 0.25,
+                paid: false,
+                status: Status::Closed,
+                note: Some("Late".to_string()),
+            },
+        ];
+
+        assert_eq!(expected, orders);
+
+        // Missing fields are reported with the row number.
+        worksheet.write(5, 0, 5).unwrap();
+        let mut rows = worksheet.deserialize_rows::<Order>(4, 0).unwrap();
+        let result = rows.next();
+        assert!(
+            matches!(result, Some(Err(XlsxError::SerdeError(message))) if message.ends_with("row 6"))
+        );
+
+        // There must be a header at the start cell.
+        let result = worksheet.deserialize_rows::<Order>(3, 0);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        // Renamed and skipped headers, and dates.
+        #[derive(Deserialize)]
+        struct Student {
+            name: String,
+            dob: ExcelDateTime,
+        }
+
+        let mut worksheet = Worksheet::new();
+        let dob = ExcelDateTime::from_ymd(2000, 5, 1).unwrap();
+
+        worksheet
+            .write_row(0, 0, ["Name", "Birthday", "ID"])
+            .unwrap();
+        worksheet.write(1, 0, "Aoife").unwrap();
+        worksheet.write(1, 1, &dob).unwrap();
+        worksheet.write(1, 2, "abc").unwrap();
+
+        let header_options = SerializeFieldOptions::new().set_custom_headers(&[
+            CustomSerializeField::new("name").rename("Name"),
+            CustomSerializeField::new("dob").rename("Birthday"),
+            CustomSerializeField::new("ID").skip(true),
+        ]);
+
+        let students: Vec<Student> = worksheet
+            .deserialize_rows_with_options(0, 0, &header_options)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(1, students.len());
+        assert_eq!("Aoife", students[0].name);
+        assert_eq!(dob.to_excel(), students[0].dob.to_excel());
+    }
+
+    #[test]
+    fn row_matches_list_filter_blanks() {
+        let mut worksheet = Worksheet::new();
+        let bold = Format::new().set_bold();
+
+        worksheet.write_string(0, 0, "Header").unwrap();
+        worksheet.write_string(1, 0, "").unwrap();
+        worksheet.write_string(2, 0, " ").unwrap();
+        worksheet.write_string(3, 0, "  ").unwrap();
+        worksheet.write_string_with_format(4, 0, "", &bold).unwrap();
+
+        let filter_condition = FilterCondition::new().add_list_blanks_filter();
+
+        assert!(!worksheet.row_matches_list_filter(0, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(1, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(2, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(3, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(4, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(5, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(7, 7, &filter_condition));
+    }
+
+    #[test]
+    fn row_matches_list_filter_strings() {
+        let mut worksheet = Worksheet::new();
+        worksheet.write_string(0, 0, "Header").unwrap();
+        worksheet.write_string(1, 0, "South").unwrap();
+        worksheet.write_string(2, 0, "south").unwrap();
+        worksheet.write_string(3, 0, "SOUTH").unwrap();
+        worksheet.write_string(4, 0, "South ").unwrap();
+        worksheet.write_string(5, 0, " South").unwrap();
+        worksheet.write_string(6, 0, " South ").unwrap();
+        worksheet.write_string(7, 0, "Mouth").unwrap();
+
+        let filter_condition = FilterCondition::new().add_list_filter("South");
+
+        assert!(worksheet.row_matches_list_filter(1, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(2, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(3, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(4, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(5, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(6, 0, &filter_condition));
+        assert!(!worksheet.row_matches_list_filter(7, 0, &filter_condition));
+    }
+
+    #[test]
+    fn row_matches_list_filter_numbers() {
+        let mut worksheet = Worksheet::new();
+
+        worksheet.write_string(0, 0, "Header").unwrap();
+        worksheet.write_number(1, 0, 1000).unwrap();
+        worksheet.write_number(2, 0, 1000.0).unwrap();
+        worksheet.write_string(3, 0, "1000").unwrap();
+        worksheet.write_string(4, 0, " 1000 ").unwrap();
+        worksheet.write_number(5, 0, 2000).unwrap();
+
+        let filter_condition = FilterCondition::new().add_list_filter(1000);
+
+        assert!(worksheet.row_matches_list_filter(1, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(2, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(3, 0, &filter_condition));
+        assert!(worksheet.row_matches_list_filter(4, 0, &filter_condition));
+        assert!(!worksheet.row_matches_list_filter(5, 0, &filter_condition));
+    }
+
+    #[test]
+    fn process_pagebreaks() {
+        let mut worksheet = Worksheet::new();
+
+        // Test removing duplicates.
+        let got = Worksheet::process_pagebreaks(&[1, 1, 1, 1]).unwrap();
+        assert_eq!(vec![1], got);
+
+        // Test removing 0.
+        let got = Worksheet::process_pagebreaks(&[0, 1, 2, 3, 4]).unwrap();
+        assert_eq!(vec![1, 2, 3, 4], got);
+
+        // Test sort order.
+        let got = Worksheet::process_pagebreaks(&[1, 12, 2, 13, 3, 4]).unwrap();
+        assert_eq!(vec![1, 2, 3, 4, 12, 13], got);
+
+        // Exceed the number of allow breaks.
+        let breaks = (1u32..=1024).collect::<Vec<u32>>();
+        let result = Worksheet::process_pagebreaks(&breaks);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        // Test row and column limits.
+        let result = worksheet.set_page_breaks(&[ROW_MAX]);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.set_vertical_page_breaks(&[COL_MAX as u32]);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+    }
+
+    #[test]
+    fn set_header_image() {
         let mut worksheet = Worksheet::new();
 
         let image = Image::new("tests/input/images/red.jpg").unwrap();
@@ -238,6 +955,114 @@ mod worksheet_tests {
         assert!(matches!(result, Err(XlsxError::ParameterError(_))));
     }
 
+    #[test]
+    fn set_header_image_with_page() {
+        let mut worksheet = Worksheet::new();
+
+        let image = Image::new("tests/input/images/red.jpg").unwrap();
+        worksheet.set_header("&L&G");
+        worksheet.set_header_with_page("&C&G", HeaderFooterPage::First);
+        worksheet.set_footer_with_page("&R&[Picture]", HeaderFooterPage::Even);
+
+        // Test inserting an image that only matches the odd page header.
+        let result = worksheet.set_header_image_with_page(
+            &image,
+            HeaderImagePosition::Left,
+            HeaderFooterPage::First,
+        );
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        worksheet
+            .set_header_image_with_page(
+                &image,
+                HeaderImagePosition::Center,
+                HeaderFooterPage::First,
+            )
+            .unwrap();
+        worksheet
+            .set_footer_image_with_page(&image, HeaderImagePosition::Right, HeaderFooterPage::Even)
+            .unwrap();
+
+        let positions: Vec<String> = worksheet
+            .header_footer_images
+            .iter()
+            .flatten()
+            .map(|image| image.vml_info().header_position)
+            .collect();
+
+        assert_eq!(vec!["CHFIRST", "RFEVEN"], positions);
+    }
+
+    #[test]
+    fn test_write_header_footer_with_page() {
+        let mut worksheet = Worksheet::new();
+
+        worksheet.set_header("&CPage &[Page]");
+        worksheet.set_footer("&RPage &P");
+        worksheet.set_header_with_page("&CCover", HeaderFooterPage::First);
+        worksheet.set_footer_with_page("&LPage &P", HeaderFooterPage::Even);
+        worksheet.set_header_footer_scale_with_doc(false);
+
+        worksheet.write_header_footer();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <headerFooter differentOddEven="1" differentFirst="1" scaleWithDoc="0">
+                <oddHeader>&amp;CPage &amp;P</oddHeader>
+                <oddFooter>&amp;RPage &amp;P</oddFooter>
+                <evenFooter>&amp;LPage &amp;P</evenFooter>
+                <firstHeader>&amp;CCover</firstHeader>
+            </headerFooter>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_write_header_footer_with_empty_page() {
+        let mut worksheet = Worksheet::new();
+
+        // A cover page without a header.
+        worksheet.set_header("&CPage &P");
+        worksheet.set_header_with_page("", HeaderFooterPage::First);
+
+        worksheet.write_header_footer();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <headerFooter differentFirst="1">
+                <oddHeader>&amp;CPage &amp;P</oddHeader>
+            </headerFooter>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+
+        // Empty even page footers.
+        let mut worksheet = Worksheet::new();
+        worksheet.set_footer_with_page("", HeaderFooterPage::Even);
+
+        worksheet.write_header_footer();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <headerFooter differentOddEven="1"/>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
     #[test]
     fn rich_string() {
         let mut worksheet = Worksheet::new();
@@ -618,118 +1443,564 @@ mod worksheet_tests {
     }
 
     #[test]
-    fn get_name() {
-        let mut worksheet = Worksheet::new();
+    fn get_name() {
+        let mut worksheet = Worksheet::new();
+
+        let got = worksheet.name();
+        assert_eq!("", got);
+
+        let exp = "Sheet1";
+        worksheet.set_name(exp).unwrap();
+        let got = worksheet.name();
+        assert_eq!(exp, got);
+    }
+
+    #[test]
+    fn merge_range() {
+        let mut worksheet = Worksheet::new();
+        let format = Format::default();
+
+        // Test single merge cell.
+        let result = worksheet.merge_range(1, 1, 1, 1, "Foo", &format);
+        assert!(matches!(result, Err(XlsxError::MergeRangeSingleCell)));
+
+        // Test for overlap.
+        let _worksheet = worksheet.merge_range(1, 1, 20, 20, "Foo", &format);
+        let result = worksheet.merge_range(2, 2, 3, 3, "Foo", &format);
+        assert!(matches!(result, Err(XlsxError::MergeRangeOverlaps(_, _))));
+
+        // Test out of range value.
+        let result = worksheet.merge_range(ROW_MAX, 1, 1, 1, "Foo", &format);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        // Test out reversed values
+        let result = worksheet.merge_range(5, 1, 1, 1, "Foo", &format);
+        assert!(matches!(result, Err(XlsxError::RowColumnOrderError)));
+    }
+
+    #[test]
+    fn check_dimensions() {
+        let mut worksheet = Worksheet::new();
+        let format = Format::default();
+
+        assert!(!worksheet.check_dimensions(ROW_MAX, 0));
+        assert!(!worksheet.check_dimensions(0, COL_MAX));
+
+        let result = worksheet.write_string_with_format(ROW_MAX, 0, "Foo", &format);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.write_string(ROW_MAX, 0, "Foo");
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.write_number_with_format(ROW_MAX, 0, 0, &format);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.write_number(ROW_MAX, 0, 0);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.set_row_height(ROW_MAX, 20);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.set_row_height_pixels(ROW_MAX, 20);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.set_row_format(ROW_MAX, &format);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.set_column_width(COL_MAX, 20);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.set_column_width_pixels(COL_MAX, 20);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+
+        let result = worksheet.set_column_format(COL_MAX, &format);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+    }
+
+    #[test]
+    fn long_string() {
+        let mut worksheet = Worksheet::new();
+        let chars: [u8; 32_768] = [64; 32_768];
+        let long_string = std::str::from_utf8(&chars);
+
+        let result = worksheet.write_string(0, 0, long_string.unwrap());
+        assert!(matches!(result, Err(XlsxError::MaxStringLengthExceeded)));
+    }
+
+    #[test]
+    fn ignored_cells_reuse() {
+        let mut worksheet = Worksheet::new();
+
+        // Insert a rule.
+        let result = worksheet.ignore_error(2, 1, IgnoreError::NumberStoredAsText);
+        assert!(result.is_ok());
+
+        // Test reuse of the same cell.
+        let result = worksheet.ignore_error(2, 1, IgnoreError::NumberStoredAsText);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+    }
+
+    #[test]
+    fn assert_sync() {
+        const _: () = {
+            const fn assert_worksheet_sync<S: Sync>() {}
+            assert_worksheet_sync::<Worksheet>();
+        };
+    }
+
+    #[test]
+    fn test_sheet_views2_1() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+
+        worksheet.set_freeze_panes(1, 0).unwrap();
+
+        worksheet.write_sheet_views();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/>
+                    <selection pane="bottomLeft"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_sheet_views2_2() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+
+        worksheet.set_freeze_panes(0, 1).unwrap();
+
+        worksheet.write_sheet_views();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane xSplit="1" topLeftCell="B1" activePane="topRight" state="frozen"/>
+                    <selection pane="topRight"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_sheet_views2_3() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+
+        worksheet.set_freeze_panes(1, 1).unwrap();
+
+        worksheet.write_sheet_views();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane xSplit="1" ySplit="1" topLeftCell="B2" activePane="bottomRight" state="frozen"/>
+                    <selection pane="topRight" activeCell="B1" sqref="B1"/>
+                    <selection pane="bottomLeft" activeCell="A2" sqref="A2"/>
+                    <selection pane="bottomRight"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_sheet_views2_4() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+
+        worksheet.set_freeze_panes(3, 6).unwrap();
+
+        worksheet.write_sheet_views();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane xSplit="6" ySplit="3" topLeftCell="G4" activePane="bottomRight" state="frozen"/>
+                    <selection pane="topRight" activeCell="G1" sqref="G1"/>
+                    <selection pane="bottomLeft" activeCell="A4" sqref="A4"/>
+                    <selection pane="bottomRight"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_sheet_views6_1() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+
+        worksheet.set_selection(1, 0, 1, 0).unwrap();
+        worksheet.set_freeze_panes(1, 0).unwrap();
+
+        worksheet.write_sheet_views();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/>
+                    <selection pane="bottomLeft" activeCell="A2" sqref="A2"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_sheet_views6_2() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+
+        worksheet.set_selection(0, 1, 0, 1).unwrap();
+        worksheet.set_freeze_panes(0, 1).unwrap();
+
+        worksheet.write_sheet_views();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane xSplit="1" topLeftCell="B1" activePane="topRight" state="frozen"/>
+                    <selection pane="topRight" activeCell="B1" sqref="B1"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_sheet_views6_3() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+
+        worksheet.set_selection(3, 6, 3, 6).unwrap();
+        worksheet.set_freeze_panes(3, 6).unwrap();
+
+        worksheet.write_sheet_views();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane xSplit="6" ySplit="3" topLeftCell="G4" activePane="bottomRight" state="frozen"/>
+                    <selection pane="topRight" activeCell="G1" sqref="G1"/>
+                    <selection pane="bottomLeft" activeCell="A4" sqref="A4"/>
+                    <selection pane="bottomRight" activeCell="G4" sqref="G4"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_sheet_views6_4() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+
+        worksheet.set_selection(4, 8, 4, 8).unwrap();
+        worksheet.set_freeze_panes(3, 6).unwrap();
+
+        worksheet.write_sheet_views();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane xSplit="6" ySplit="3" topLeftCell="G4" activePane="bottomRight" state="frozen"/>
+                    <selection pane="topRight" activeCell="G1" sqref="G1"/>
+                    <selection pane="bottomLeft" activeCell="A4" sqref="A4"/>
+                    <selection pane="bottomRight" activeCell="I5" sqref="I5"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_sheet_views3_1() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
+
+        worksheet.set_split_panes(15, 0);
+
+        worksheet.write_sheet_views();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane ySplit="600" topLeftCell="A2" activePane="bottomLeft" state="split"/>
+                    <selection pane="bottomLeft"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn test_sheet_views3_2() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
 
-        let got = worksheet.name();
-        assert_eq!("", got);
+        worksheet.set_split_panes(0, 8.43);
 
-        let exp = "Sheet1";
-        worksheet.set_name(exp).unwrap();
-        let got = worksheet.name();
-        assert_eq!(exp, got);
+        worksheet.write_sheet_views();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane xSplit="1350" topLeftCell="B1" activePane="topRight" state="split"/>
+                    <selection pane="topRight"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
     }
 
     #[test]
-    fn merge_range() {
-        let mut worksheet = Worksheet::new();
-        let format = Format::default();
+    fn test_sheet_views3_3() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
 
-        // Test single merge cell.
-        let result = worksheet.merge_range(1, 1, 1, 1, "Foo", &format);
-        assert!(matches!(result, Err(XlsxError::MergeRangeSingleCell)));
+        worksheet.set_split_panes(15, 8.43);
 
-        // Test for overlap.
-        let _worksheet = worksheet.merge_range(1, 1, 20, 20, "Foo", &format);
-        let result = worksheet.merge_range(2, 2, 3, 3, "Foo", &format);
-        assert!(matches!(result, Err(XlsxError::MergeRangeOverlaps(_, _))));
+        worksheet.write_sheet_views();
 
-        // Test out of range value.
-        let result = worksheet.merge_range(ROW_MAX, 1, 1, 1, "Foo", &format);
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
 
-        // Test out reversed values
-        let result = worksheet.merge_range(5, 1, 1, 1, "Foo", &format);
-        assert!(matches!(result, Err(XlsxError::RowColumnOrderError)));
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane xSplit="1350" ySplit="600" topLeftCell="B2" activePane="bottomRight" state="split"/>
+                    <selection pane="topRight" activeCell="B1" sqref="B1"/>
+                    <selection pane="bottomLeft" activeCell="A2" sqref="A2"/>
+                    <selection pane="bottomRight"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
     }
 
     #[test]
-    fn check_dimensions() {
-        let mut worksheet = Worksheet::new();
-        let format = Format::default();
+    fn test_sheet_views3_4() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
 
-        assert!(!worksheet.check_dimensions(ROW_MAX, 0));
-        assert!(!worksheet.check_dimensions(0, COL_MAX));
+        worksheet.set_split_panes(45, 54.14);
 
-        let result = worksheet.write_string_with_format(ROW_MAX, 0, "Foo", &format);
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+        worksheet.write_sheet_views();
 
-        let result = worksheet.write_string(ROW_MAX, 0, "Foo");
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
 
-        let result = worksheet.write_number_with_format(ROW_MAX, 0, 0, &format);
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane xSplit="6150" ySplit="1200" topLeftCell="G4" activePane="bottomRight" state="split"/>
+                    <selection pane="topRight" activeCell="G1" sqref="G1"/>
+                    <selection pane="bottomLeft" activeCell="A4" sqref="A4"/>
+                    <selection pane="bottomRight"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
 
-        let result = worksheet.write_number(ROW_MAX, 0, 0);
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+        assert_eq!(expected, got);
+    }
 
-        let result = worksheet.set_row_height(ROW_MAX, 20);
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+    #[test]
+    fn test_sheet_views4_1() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
 
-        let result = worksheet.set_row_height_pixels(ROW_MAX, 20);
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+        worksheet.set_selection(4, 8, 4, 8).unwrap();
+        worksheet.set_split_panes(45, 54.14);
 
-        let result = worksheet.set_row_format(ROW_MAX, &format);
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+        worksheet.write_sheet_views();
 
-        let result = worksheet.set_column_width(COL_MAX, 20);
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
 
-        let result = worksheet.set_column_width_pixels(COL_MAX, 20);
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane xSplit="6150" ySplit="1200" topLeftCell="G4" activePane="bottomRight" state="split"/>
+                    <selection pane="topRight" activeCell="G1" sqref="G1"/>
+                    <selection pane="bottomLeft" activeCell="A4" sqref="A4"/>
+                    <selection pane="bottomRight" activeCell="I5" sqref="I5"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
 
-        let result = worksheet.set_column_format(COL_MAX, &format);
-        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
+        assert_eq!(expected, got);
     }
 
     #[test]
-    fn long_string() {
-        let mut worksheet = Worksheet::new();
-        let chars: [u8; 32_768] = [64; 32_768];
-        let long_string = std::str::from_utf8(&chars);
+    fn test_sheet_views4_2() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
+        };
 
-        let result = worksheet.write_string(0, 0, long_string.unwrap());
-        assert!(matches!(result, Err(XlsxError::MaxStringLengthExceeded)));
-    }
+        worksheet.set_top_left_cell(1, 0).unwrap();
+        worksheet.set_split_panes(45, 0);
+        worksheet.set_split_panes_top_cell(20, 0).unwrap();
 
-    #[test]
-    fn ignored_cells_reuse() {
-        let mut worksheet = Worksheet::new();
+        worksheet.write_sheet_views();
 
-        // Insert a rule.
-        let result = worksheet.ignore_error(2, 1, IgnoreError::NumberStoredAsText);
-        assert!(result.is_ok());
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
 
-        // Test reuse of the same cell.
-        let result = worksheet.ignore_error(2, 1, IgnoreError::NumberStoredAsText);
-        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" topLeftCell="A2" workbookViewId="0">
+                    <pane ySplit="1200" topLeftCell="A21" activePane="bottomLeft" state="split"/>
+                    <selection pane="bottomLeft"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
     }
 
     #[test]
-    fn assert_sync() {
-        const _: () = {
-            const fn assert_worksheet_sync<S: Sync>() {}
-            assert_worksheet_sync::<Worksheet>();
+    fn test_sheet_views4_3() {
+        let mut worksheet = Worksheet {
+            selected: true,
+            ..Default::default()
         };
+
+        worksheet.set_split_panes(15, 8.43);
+        worksheet.set_freeze_panes(1, 1).unwrap();
+
+        worksheet.write_sheet_views();
+
+        let got = xmlwriter::cursor_to_str(&worksheet.writer);
+        let got = xml_to_vec(got);
+
+        let expected = xml_to_vec(
+            r#"
+            <sheetViews>
+                <sheetView tabSelected="1" workbookViewId="0">
+                    <pane xSplit="1" ySplit="1" topLeftCell="B2" activePane="bottomRight" state="frozen"/>
+                    <selection pane="topRight" activeCell="B1" sqref="B1"/>
+                    <selection pane="bottomLeft" activeCell="A2" sqref="A2"/>
+                    <selection pane="bottomRight"/>
+                </sheetView>
+            </sheetViews>
+            "#,
+        );
+
+        assert_eq!(expected, got);
     }
 
     #[test]
-    fn test_sheet_views2_1() {
+    fn test_sheet_views4_4() {
         let mut worksheet = Worksheet {
             selected: true,
             ..Default::default()
         };
 
+        worksheet.set_split_panes(45, 0);
+        worksheet.set_split_panes_top_cell(20, 0).unwrap();
         worksheet.set_freeze_panes(1, 0).unwrap();
 
         worksheet.write_sheet_views();
@@ -752,13 +2023,15 @@ mod worksheet_tests {
     }
 
     #[test]
-    fn test_sheet_views2_2() {
+    fn test_sheet_views4_5() {
         let mut worksheet = Worksheet {
             selected: true,
             ..Default::default()
         };
 
-        worksheet.set_freeze_panes(0, 1).unwrap();
+        worksheet.set_freeze_panes(1, 0).unwrap();
+        worksheet.set_freeze_panes_top_cell(20, 0).unwrap();
+        worksheet.set_split_panes(45, 0);
 
         worksheet.write_sheet_views();
 
@@ -769,8 +2042,8 @@ mod worksheet_tests {
             r#"
             <sheetViews>
                 <sheetView tabSelected="1" workbookViewId="0">
-                    <pane xSplit="1" topLeftCell="B1" activePane="topRight" state="frozen"/>
-                    <selection pane="topRight"/>
+                    <pane ySplit="1200" topLeftCell="A4" activePane="bottomLeft" state="split"/>
+                    <selection pane="bottomLeft"/>
                 </sheetView>
             </sheetViews>
             "#,
@@ -780,13 +2053,15 @@ mod worksheet_tests {
     }
 
     #[test]
-    fn test_sheet_views2_3() {
+    fn test_sheet_views5_1() {
         let mut worksheet = Worksheet {
             selected: true,
             ..Default::default()
         };
 
-        worksheet.set_freeze_panes(1, 1).unwrap();
+        worksheet
+            .set_selection_ranges(1, 4, &[(1, 1, 3, 2), (3, 5, 1, 4), (6, 1, 6, 1)])
+            .unwrap();
 
         worksheet.write_sheet_views();
 
@@ -797,10 +2072,7 @@ mod worksheet_tests {
             r#"
             <sheetViews>
                 <sheetView tabSelected="1" workbookViewId="0">
-                    <pane xSplit="1" ySplit="1" topLeftCell="B2" activePane="bottomRight" state="frozen"/>
-                    <selection pane="topRight" activeCell="B1" sqref="B1"/>
-                    <selection pane="bottomLeft" activeCell="A2" sqref="A2"/>
-                    <selection pane="bottomRight"/>
+                    <selection activeCell="E2" activeCellId="1" sqref="B2:C4 E2:F4 B7"/>
                 </sheetView>
             </sheetViews>
             "#,
@@ -810,13 +2082,24 @@ mod worksheet_tests {
     }
 
     #[test]
-    fn test_sheet_views2_4() {
+    fn test_sheet_views5_2() {
         let mut worksheet = Worksheet {
             selected: true,
             ..Default::default()
         };
 
-        worksheet.set_freeze_panes(3, 6).unwrap();
+        // Active cell in the last of the selected ranges.
+        worksheet
+            .set_selection_ranges(6, 1, &[(1, 1, 3, 2), (6, 1, 6, 1)])
+            .unwrap();
+
+        // Active cell outside of the selected ranges.
+        let result = worksheet.set_selection_ranges(9, 9, &[(1, 1, 3, 2), (6, 1, 6, 1)]);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let result =
+            worksheet.set_pane_selection_ranges(WorksheetPane::BottomRight, 9, 9, &[(1, 1, 3, 2)]);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
 
         worksheet.write_sheet_views();
 
@@ -827,27 +2110,35 @@ mod worksheet_tests {
             r#"
             <sheetViews>
                 <sheetView tabSelected="1" workbookViewId="0">
-                    <pane xSplit="6" ySplit="3" topLeftCell="G4" activePane="bottomRight" state="frozen"/>
-                    <selection pane="topRight" activeCell="G1" sqref="G1"/>
-                    <selection pane="bottomLeft" activeCell="A4" sqref="A4"/>
-                    <selection pane="bottomRight"/>
+                    <selection activeCell="B7" activeCellId="1" sqref="B2:C4 B7"/>
                 </sheetView>
             </sheetViews>
             "#,
         );
 
         assert_eq!(expected, got);
+
+        let result = worksheet.set_selection_ranges(0, 0, &[]);
+        assert!(matches!(result, Err(XlsxError::ParameterError(_))));
+
+        let result = worksheet.set_selection_ranges(0, 0, &[(0, 0, ROW_MAX, 0)]);
+        assert!(matches!(result, Err(XlsxError::RowColumnLimitError)));
     }
 
     #[test]
-    fn test_sheet_views6_1() {
+    fn test_sheet_views5_3() {
         let mut worksheet = Worksheet {
             selected: true,
             ..Default::default()
         };
 
-        worksheet.set_selection(1, 0, 1, 0).unwrap();
-        worksheet.set_freeze_panes(1, 0).unwrap();
+        worksheet.set_freeze_panes(1, 1).unwrap();
+        worksheet
+            .set_pane_selection_ranges(WorksheetPane::TopRight, 0, 2, &[(0, 2, 0, 4)])
+            .unwrap();
+        worksheet
+            .set_selection_ranges(4, 2, &[(4, 2, 9, 4), (14, 2, 19, 4)])
+            .unwrap();
 
         worksheet.write_sheet_views();
 
@@ -858,8 +2149,10 @@ mod worksheet_tests {
             r#"
             <sheetViews>
                 <sheetView tabSelected="1" workbookViewId="0">
-                    <pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/>
+                    <pane xSplit="1" ySplit="1" topLeftCell="B2" activePane="bottomRight" state="frozen"/>
+                    <selection pane="topRight" activeCell="C1" sqref="C1:E1"/>
                     <selection pane="bottomLeft" activeCell="A2" sqref="A2"/>
+                    <selection pane="bottomRight" activeCell="C5" sqref="C5:E10 C15:E20"/>
                 </sheetView>
             </sheetViews>
             "#,
@@ -869,14 +2162,24 @@ mod worksheet_tests {
     }
 
     #[test]
-    fn test_sheet_views6_2() {
+    fn test_sheet_views5_4() {
         let mut worksheet = Worksheet {
             selected: true,
             ..Default::default()
         };
 
-        worksheet.set_selection(0, 1, 0, 1).unwrap();
-        worksheet.set_freeze_panes(0, 1).unwrap();
+        // The top left pane selection is written when set. The bottom right
+        // selection is ignored since the worksheet only has a row split.
+        worksheet.set_freeze_panes(2, 0).unwrap();
+        worksheet
+            .set_pane_selection_ranges(WorksheetPane::TopLeft, 0, 1, &[(0, 1, 1, 1)])
+            .unwrap();
+        worksheet
+            .set_pane_selection_ranges(WorksheetPane::BottomRight, 5, 5, &[(5, 5, 5, 5)])
+            .unwrap();
+        worksheet
+            .set_pane_selection_ranges(WorksheetPane::BottomLeft, 3, 0, &[(3, 0, 4, 0)])
+            .unwrap();
 
         worksheet.write_sheet_views();
 
@@ -887,8 +2190,9 @@ mod worksheet_tests {
             r#"
             <sheetViews>
                 <sheetView tabSelected="1" workbookViewId="0">
-                    <pane xSplit="1" topLeftCell="B1" activePane="topRight" state="frozen"/>
-                    <selection pane="topRight" activeCell="B1" sqref="B1"/>
+                    <pane ySplit="2" topLeftCell="A3" activePane="bottomLeft" state="frozen"/>
+                    <selection pane="topLeft" activeCell="B1" sqref="B1:B2"/>
+                    <selection pane="bottomLeft" activeCell="A4" sqref="A4:A5"/>
                 </sheetView>
             </sheetViews>
             "#,
@@ -898,14 +2202,18 @@ mod worksheet_tests {
     }
 
     #[test]
-    fn test_sheet_views6_3() {
+    fn test_sheet_views7_1() {
         let mut worksheet = Worksheet {
             selected: true,
             ..Default::default()
         };
 
-        worksheet.set_selection(3, 6, 3, 6).unwrap();
-        worksheet.set_freeze_panes(3, 6).unwrap();
+        worksheet.set_screen_formulas(true);
+        worksheet.set_screen_gridlines(false);
+        worksheet.set_screen_headings(false);
+        worksheet.set_screen_zeros(false);
+        worksheet.set_screen_ruler(false);
+        worksheet.set_screen_outline_symbols(false);
 
         worksheet.write_sheet_views();
 
@@ -915,12 +2223,7 @@ mod worksheet_tests {
         let expected = xml_to_vec(
             r#"
             <sheetViews>
-                <sheetView tabSelected="1" workbookViewId="0">
-                    <pane xSplit="6" ySplit="3" topLeftCell="G4" activePane="bottomRight" state="frozen"/>
-                    <selection pane="topRight" activeCell="G1" sqref="G1"/>
-                    <selection pane="bottomLeft" activeCell="A4" sqref="A4"/>
-                    <selection pane="bottomRight" activeCell="G4" sqref="G4"/>
-                </sheetView>
+                <sheetView showFormulas="1" showGridLines="0" showRowColHeaders="0" showZeros="0" tabSelected="1" showRuler="0" showOutlineSymbols="0" workbookViewId="0"/>
             </sheetViews>
             "#,
         );
@@ -929,14 +2232,16 @@ mod worksheet_tests {
     }
 
     #[test]
-    fn test_sheet_views6_4() {
+    fn test_sheet_views7_2() {
         let mut worksheet = Worksheet {
             selected: true,
             ..Default::default()
         };
 
-        worksheet.set_selection(4, 8, 4, 8).unwrap();
-        worksheet.set_freeze_panes(3, 6).unwrap();
+        worksheet.set_screen_zeros(false);
+        worksheet.set_right_to_left(true);
+        worksheet.set_view_page_layout();
+        worksheet.set_screen_ruler(false);
 
         worksheet.write_sheet_views();
 
@@ -946,12 +2251,7 @@ mod worksheet_tests {
         let expected = xml_to_vec(
             r#"
             <sheetViews>
-                <sheetView tabSelected="1" workbookViewId="0">
-                    <pane xSplit="6" ySplit="3" topLeftCell="G4" activePane="bottomRight" state="frozen"/>
-                    <selection pane="topRight" activeCell="G1" sqref="G1"/>
-                    <selection pane="bottomLeft" activeCell="A4" sqref="A4"/>
-                    <selection pane="bottomRight" activeCell="I5" sqref="I5"/>
-                </sheetView>
+                <sheetView showZeros="0" rightToLeft="1" tabSelected="1" showRuler="0" view="pageLayout" workbookViewId="0"/>
             </sheetViews>
             "#,
         );
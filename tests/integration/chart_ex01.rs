@@ -0,0 +1,44 @@
+// Test case that compares a file generated by rust_xlsxwriter with a file
+// created by Excel.
+//
+// The test is ignored until the reference file is created and saved in
+// Excel as tests/input/chart_ex01.xlsx.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use crate::common;
+use rust_xlsxwriter::{Chart, ChartType, Workbook, XlsxError};
+
+// Create a rust_xlsxwriter file to compare against an Excel file.
+fn create_new_xlsx_file(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    let data = [12, 17, 21, 23, 28, 31, 35, 36, 41, 44, 47, 52, 58, 63, 71];
+    worksheet.write_column(0, 0, data)?;
+
+    let mut chart = Chart::new(ChartType::Histogram);
+    chart.add_series().set_values(("Sheet1", 0, 0, 14, 0));
+    chart.set_bin_width(20.0);
+
+    worksheet.insert_chart(8, 4, &chart)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "requires the Excel reference file tests/input/chart_ex01.xlsx"]
+fn test_chart_ex01() {
+    let test_runner = common::TestRunner::new()
+        .set_name("chart_ex01")
+        .set_function(create_new_xlsx_file)
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
@@ -0,0 +1,81 @@
+// Test case that compares a file generated by rust_xlsxwriter with a file
+// created by Excel.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use crate::common;
+use polars::prelude::*;
+use rust_xlsxwriter::{DataFrameOptions, Format, Table, Workbook, XlsxError};
+
+// Test case for writing a Polars dataframe with string and date data.
+fn create_new_xlsx_file_1(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_column_width(1, 11)?;
+
+    let mut df = df!("col1" => &["aaa", "bbb", "ccc"])?;
+    let dates = Column::new("col2".into(), &[19723, 19724, 19725]);
+    df.with_column(dates.cast(&DataType::Date)?)?;
+
+    let format = Format::new().set_num_format_index(14);
+    let options = DataFrameOptions::new().set_date_format(&format);
+
+    worksheet.write_dataframe(0, 0, &df, &options)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+// Test case for writing a Polars dataframe with null data in a table.
+fn create_new_xlsx_file_2(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.set_column_width(2, 10.288)?;
+    worksheet.set_column_width(3, 10.288)?;
+    worksheet.set_column_width(4, 10.288)?;
+    worksheet.set_column_width(5, 10.288)?;
+
+    let nulls: [Option<f64>; 10] = [None; 10];
+    let df = df!(
+        "Column1" => &nulls,
+        "Column2" => &nulls,
+        "Column3" => &nulls,
+        "Column4" => &nulls,
+    )?;
+
+    let options = DataFrameOptions::new().set_table(&Table::new());
+
+    worksheet.write_dataframe(2, 2, &df, &options)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn dataframe01_1() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde10")
+        .set_function(create_new_xlsx_file_1)
+        .unique("dataframe1")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
+
+#[test]
+fn dataframe01_2() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde18")
+        .set_function(create_new_xlsx_file_2)
+        .unique("dataframe2")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
@@ -134,6 +134,7 @@ mod button10;
 mod button11;
 mod button12;
 mod button15;
+mod chart_3d01;
 mod chart_area01;
 mod chart_area02;
 mod chart_area03;
@@ -225,6 +226,7 @@ mod chart_blank04;
 mod chart_blank05;
 mod chart_blank06;
 mod chart_blank07;
+mod chart_bubble01;
 mod chart_chartarea01;
 mod chart_chartarea02;
 mod chart_chartarea03;
@@ -353,6 +355,7 @@ mod chart_errorbars05;
 mod chart_errorbars06;
 mod chart_errorbars07;
 mod chart_errorbars10;
+mod chart_ex01;
 mod chart_font01;
 mod chart_font02;
 mod chart_font03;
@@ -494,6 +497,7 @@ mod chart_stock02;
 mod chart_stock03;
 mod chart_str01;
 mod chart_str02;
+mod chart_surface01;
 mod chart_table01;
 mod chart_table02;
 mod chart_table03;
@@ -574,6 +578,8 @@ mod cond_format22;
 mod data_validation01;
 mod data_validation02;
 mod data_validation03;
+#[cfg(feature = "polars")]
+mod dataframe01;
 #[cfg(feature = "rust_decimal")]
 mod decimal01;
 mod default_format01;
@@ -1017,6 +1023,7 @@ mod page_breaks06;
 mod page_view01;
 mod page_view02;
 mod page_view03;
+mod pivot_table01;
 mod print_across01;
 mod print_area01;
 mod print_area02;
@@ -1057,6 +1064,8 @@ mod quote_name08;
 mod quote_name09;
 mod quote_name10;
 mod quote_name11;
+#[cfg(feature = "arrow")]
+mod record_batch01;
 mod repeat01;
 mod repeat02;
 mod repeat03;
@@ -1139,6 +1148,12 @@ mod serde23;
 mod serde24;
 #[cfg(feature = "serde")]
 mod serde25;
+#[cfg(feature = "serde")]
+mod serde_deserialize01;
+#[cfg(feature = "serde")]
+mod serde_map01;
+#[cfg(feature = "serde")]
+mod serde_nested01;
 mod set_row01;
 mod set_row02;
 mod set_row03;
@@ -1148,6 +1163,7 @@ mod set_selection02;
 mod shared_strings01;
 mod shared_strings02;
 mod simple03;
+mod slicer01;
 mod styles01;
 mod styles02;
 mod tab_color01;
@@ -1240,6 +1256,7 @@ mod theme_color01;
 mod theme_color02;
 mod theme_color03;
 mod theme_color04;
+mod threaded_comment01;
 mod top_left_cell01;
 mod top_left_cell02;
 mod top_left_cell03;
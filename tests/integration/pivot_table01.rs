@@ -0,0 +1,52 @@
+// Test case that compares a file generated by rust_xlsxwriter with a file
+// created by Excel.
+//
+// The test is ignored until the reference file is created and saved in
+// Excel as tests/input/pivot_table01.xlsx.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use crate::common;
+use rust_xlsxwriter::{PivotDataField, PivotField, PivotFunction, PivotTable, Workbook, XlsxError};
+
+// Create a rust_xlsxwriter file to compare against an Excel file.
+fn create_new_xlsx_file(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    // Add a worksheet with the source data for the pivot table.
+    let data = workbook.add_worksheet().set_name("Data")?;
+
+    data.write_row(0, 0, ["Region", "Product", "Quarter", "Sales"])?;
+    data.write_row(1, 0, ["East", "Apples", "Q1"])?;
+    data.write_row(2, 0, ["West", "Apples", "Q1"])?;
+    data.write_row(3, 0, ["East", "Pears", "Q2"])?;
+    data.write_row(4, 0, ["West", "Pears", "Q2"])?;
+    data.write_column(1, 3, [1000, 2500, 3000, 1500])?;
+
+    let pivot_table = PivotTable::new()
+        .set_source_range(("Data", 0, 0, 4, 3))
+        .set_row_fields(&[PivotField::new("Region")])
+        .set_column_fields(&[PivotField::new("Quarter")])
+        .set_data_fields(&[PivotDataField::new("Sales").set_function(PivotFunction::Sum)]);
+
+    let worksheet = workbook.add_worksheet();
+    worksheet.add_pivot_table(2, 0, &pivot_table)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "requires the Excel reference file tests/input/pivot_table01.xlsx"]
+fn test_pivot_table01() {
+    let test_runner = common::TestRunner::new()
+        .set_name("pivot_table01")
+        .set_function(create_new_xlsx_file)
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
@@ -0,0 +1,153 @@
+// Test case that compares a file generated by rust_xlsxwriter with a file
+// created by Excel.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use std::sync::Arc;
+
+use crate::common;
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int32Array, Int64Array};
+use arrow::record_batch::RecordBatch;
+use rust_xlsxwriter::{DataFrameOptions, Table, Workbook, XlsxError};
+
+// Test case for writing an Arrow record batch.
+fn create_new_xlsx_file_1(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let batch = RecordBatch::try_from_iter([
+        ("col1", Arc::new(Int32Array::from(vec![1])) as ArrayRef),
+        ("col2", Arc::new(Int32Array::from(vec![-1])) as ArrayRef),
+    ])?;
+
+    worksheet.write_record_batch(0, 0, &batch, &DataFrameOptions::new())?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+// Test case for writing several Arrow record batches.
+fn create_new_xlsx_file_2(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_record_batches(0, 0, record_batches(), &DataFrameOptions::new())?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+// Test case for writing several Arrow record batches in low memory mode.
+#[cfg(feature = "constant_memory")]
+fn create_new_xlsx_file_3(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet_with_low_memory();
+
+    worksheet.write_record_batches(0, 0, record_batches(), &DataFrameOptions::new())?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+// Test case for writing an Arrow record batch with null data in a table.
+fn create_new_xlsx_file_4(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.set_column_width(2, 10.288)?;
+    worksheet.set_column_width(3, 10.288)?;
+    worksheet.set_column_width(4, 10.288)?;
+    worksheet.set_column_width(5, 10.288)?;
+
+    let nulls = || Arc::new(Float64Array::from(vec![None; 10])) as ArrayRef;
+    let batch = RecordBatch::try_from_iter([
+        ("Column1", nulls()),
+        ("Column2", nulls()),
+        ("Column3", nulls()),
+        ("Column4", nulls()),
+    ])?;
+
+    let options = DataFrameOptions::new().set_table(&Table::new());
+
+    worksheet.write_record_batch(2, 2, &batch, &options)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+// Split the test data across two record batches.
+fn record_batches() -> Vec<Result<RecordBatch, arrow::error::ArrowError>> {
+    let batch1 = RecordBatch::try_from_iter([
+        (
+            "col1",
+            Arc::new(Int64Array::from(vec![123, 456])) as ArrayRef,
+        ),
+        (
+            "col2",
+            Arc::new(BooleanArray::from(vec![true, false])) as ArrayRef,
+        ),
+    ]);
+
+    let batch2 = RecordBatch::try_from_iter([
+        ("col1", Arc::new(Int64Array::from(vec![789])) as ArrayRef),
+        ("col2", Arc::new(BooleanArray::from(vec![true])) as ArrayRef),
+    ]);
+
+    vec![batch1, batch2]
+}
+
+#[test]
+fn record_batch01_1() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde01")
+        .set_function(create_new_xlsx_file_1)
+        .unique("record_batch1")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
+
+#[test]
+fn record_batch01_2() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde04")
+        .set_function(create_new_xlsx_file_2)
+        .unique("record_batch2")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
+
+#[cfg(feature = "constant_memory")]
+#[test]
+fn record_batch01_3() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde04")
+        .set_function(create_new_xlsx_file_3)
+        .unique("record_batch3")
+        .ignore_worksheet_spans()
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
+
+#[test]
+fn record_batch01_4() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde18")
+        .set_function(create_new_xlsx_file_4)
+        .unique("record_batch4")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
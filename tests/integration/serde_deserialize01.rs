@@ -0,0 +1,169 @@
+// Test case that compares a file generated by rust_xlsxwriter with a file
+// created by Excel.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use crate::common;
+use rust_xlsxwriter::{
+    CustomSerializeField, ExcelDateTime, SerializeFieldOptions, Workbook, XlsxError,
+};
+use serde::{Deserialize, Serialize};
+
+// Test case for Serde deserialization. The data is read from the Excel file and
+// serialized back to a new file.
+fn create_new_xlsx_file_1(filename: &str) -> Result<(), XlsxError> {
+    #[derive(Deserialize, Serialize)]
+    struct MyStruct {
+        col1: u8,
+        col2: String,
+    }
+
+    let mut template = Workbook::from_template("tests/input/serde07.xlsx")?;
+    let data = template
+        .worksheet_from_index(0)?
+        .deserialize_rows::<MyStruct>(0, 0)?
+        .collect::<Result<Vec<MyStruct>, XlsxError>>()?;
+
+    assert_eq!(3, data.len());
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.deserialize_headers::<MyStruct>(0, 0)?;
+    worksheet.serialize(&data)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+// Test case for Serde deserialization of numbers and booleans.
+fn create_new_xlsx_file_2(filename: &str) -> Result<(), XlsxError> {
+    #[derive(Deserialize, Serialize)]
+    struct MyStruct {
+        col1: u16,
+        col2: bool,
+    }
+
+    let mut template = Workbook::from_template("tests/input/serde04.xlsx")?;
+    let data = template
+        .worksheet_from_index(0)?
+        .deserialize_rows::<MyStruct>(0, 0)?
+        .collect::<Result<Vec<MyStruct>, XlsxError>>()?;
+
+    assert_eq!(3, data.len());
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.deserialize_headers::<MyStruct>(0, 0)?;
+    worksheet.serialize(&data)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+// Test case for Serde deserialization with renamed headers.
+fn create_new_xlsx_file_3(filename: &str) -> Result<(), XlsxError> {
+    #[derive(Deserialize, Serialize)]
+    struct MyStruct {
+        id: u8,
+        name: String,
+    }
+
+    let header_options = SerializeFieldOptions::new().set_custom_headers(&[
+        CustomSerializeField::new("id").rename("col1"),
+        CustomSerializeField::new("name").rename("col2"),
+    ]);
+
+    let mut template = Workbook::from_template("tests/input/serde07.xlsx")?;
+    let data = template
+        .worksheet_from_index(0)?
+        .deserialize_rows_with_options::<MyStruct>(0, 0, &header_options)?
+        .collect::<Result<Vec<MyStruct>, XlsxError>>()?;
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.deserialize_headers_with_options::<MyStruct>(0, 0, &header_options)?;
+    worksheet.serialize(&data)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_serde_deserialize01_1() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde07")
+        .set_function(create_new_xlsx_file_1)
+        .unique("deserialize1")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
+
+#[test]
+fn test_serde_deserialize01_2() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde04")
+        .set_function(create_new_xlsx_file_2)
+        .unique("deserialize2")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
+
+#[test]
+fn test_serde_deserialize01_3() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde07")
+        .set_function(create_new_xlsx_file_3)
+        .unique("deserialize3")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
+
+// Test case for Serde deserialization of dates from a template that uses the
+// 1904 epoch. Dates are converted to the 1900 epoch but numbers aren't.
+#[test]
+fn test_serde_deserialize01_1904_dates() -> Result<(), XlsxError> {
+    #[derive(Deserialize)]
+    struct MyStruct {
+        count: u32,
+        date: ExcelDateTime,
+    }
+
+    let date = ExcelDateTime::from_ymd(2024, 3, 15)?;
+    let filename = std::env::temp_dir().join("rust_xlsxwriter_serde_deserialize01_1904.xlsx");
+
+    let mut workbook = Workbook::new();
+    workbook.use_1904_epoch(true);
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_row(0, 0, ["count", "date"])?;
+    worksheet.write(1, 0, 10)?;
+    worksheet.write_datetime(1, 1, &date)?;
+    workbook.save(&filename)?;
+
+    let mut template = Workbook::from_template(&filename)?;
+    let data = template
+        .worksheet_from_index(0)?
+        .deserialize_rows::<MyStruct>(0, 0)?
+        .collect::<Result<Vec<MyStruct>, XlsxError>>()?;
+
+    std::fs::remove_file(&filename)?;
+
+    assert_eq!(1, data.len());
+    assert_eq!(10, data[0].count);
+    assert_eq!(date.to_excel(), data[0].date.to_excel());
+
+    Ok(())
+}
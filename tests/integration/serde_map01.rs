@@ -0,0 +1,99 @@
+// Test case that compares a file generated by rust_xlsxwriter with a file
+// created by Excel.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use std::collections::BTreeMap;
+
+use crate::common;
+use rust_xlsxwriter::{SerializeFieldOptions, Workbook, XlsxError};
+
+// Test case for Serde serialization. Map records with headers discovered from
+// the keys.
+fn create_new_xlsx_file_1(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let data = vec![BTreeMap::from([("col1", 1), ("col2", -1)])];
+
+    worksheet.serialize_headers(0, 0, &data)?;
+    worksheet.serialize(&data)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+// Test case for Serde serialization. Map records with explicit keys.
+fn create_new_xlsx_file_2(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let data = BTreeMap::from([("col2".to_string(), -1), ("col1".to_string(), 1)]);
+
+    let header_options = SerializeFieldOptions::new().set_map_keys(["col1", "col2"]);
+
+    worksheet.deserialize_headers_with_options::<BTreeMap<String, i8>>(0, 0, &header_options)?;
+    worksheet.serialize(&data)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+// Test case for Serde serialization. Map records with an unknown key added as
+// a new column.
+fn create_new_xlsx_file_3(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let headers = BTreeMap::from([("col1", 0)]);
+    let data = BTreeMap::from([("col1", 1), ("col2", -1)]);
+
+    let header_options = SerializeFieldOptions::new().add_unknown_keys(true);
+
+    worksheet.serialize_headers_with_options(0, 0, &headers, &header_options)?;
+    worksheet.serialize(&data)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_serde_map01_1() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde01")
+        .set_function(create_new_xlsx_file_1)
+        .unique("map1")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
+
+#[test]
+fn test_serde_map01_2() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde01")
+        .set_function(create_new_xlsx_file_2)
+        .unique("map2")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
+
+#[test]
+fn test_serde_map01_3() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde01")
+        .set_function(create_new_xlsx_file_3)
+        .unique("map3")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
@@ -0,0 +1,111 @@
+// Test case that compares a file generated by rust_xlsxwriter with a file
+// created by Excel.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use crate::common;
+use rust_xlsxwriter::{CustomSerializeField, SerializeFieldOptions, Workbook, XlsxError};
+use serde::Serialize;
+
+// Test case for Serde serialization. Nested struct fields flattened into
+// prefixed columns.
+fn create_new_xlsx_file_1(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    #[derive(Serialize)]
+    struct Inner {
+        col2: i8,
+    }
+
+    #[derive(Serialize)]
+    struct MyStruct {
+        col1: i8,
+        inner: Inner,
+    }
+
+    let data = MyStruct {
+        col1: 1,
+        inner: Inner { col2: -1 },
+    };
+
+    let header_options = SerializeFieldOptions::new()
+        .flatten_nested_structs(true)
+        .set_custom_headers(&[CustomSerializeField::new("inner.col2").rename("col2")]);
+
+    worksheet.serialize_headers_with_options(0, 0, &data, &header_options)?;
+    worksheet.serialize(&data)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+// Test case for Serde serialization. Struct with `#[serde(flatten)]` and a
+// nested struct with a custom separator.
+fn create_new_xlsx_file_2(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    #[derive(Serialize)]
+    struct Inner {
+        col2: i8,
+    }
+
+    #[derive(Serialize)]
+    struct Middle {
+        inner: Inner,
+    }
+
+    #[derive(Serialize)]
+    struct MyStruct {
+        col1: i8,
+        #[serde(flatten)]
+        middle: Middle,
+    }
+
+    let data = MyStruct {
+        col1: 1,
+        middle: Middle {
+            inner: Inner { col2: -1 },
+        },
+    };
+
+    let header_options = SerializeFieldOptions::new()
+        .flatten_nested_structs(true)
+        .set_nested_separator("/")
+        .set_custom_headers(&[CustomSerializeField::new("inner/col2").rename("col2")]);
+
+    worksheet.serialize_headers_with_options(0, 0, &data, &header_options)?;
+    worksheet.serialize(&data)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_serde_nested01_1() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde01")
+        .set_function(create_new_xlsx_file_1)
+        .unique("nested1")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
+
+#[test]
+fn test_serde_nested01_2() {
+    let test_runner = common::TestRunner::new()
+        .set_name("serde01")
+        .set_function(create_new_xlsx_file_2)
+        .unique("nested2")
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
@@ -0,0 +1,48 @@
+// Test case that compares a file generated by rust_xlsxwriter with a file
+// created by Excel.
+//
+// The test is ignored until the reference file is created and saved in
+// Excel as tests/input/slicer01.xlsx.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use crate::common;
+use rust_xlsxwriter::{Slicer, Table, Workbook, XlsxError};
+
+// Create a rust_xlsxwriter file to compare against an Excel file.
+fn create_new_xlsx_file(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_row(0, 0, ["Region", "Product", "Sales"])?;
+    worksheet.write_row(1, 0, ["East", "Apples"])?;
+    worksheet.write_row(2, 0, ["West", "Apples"])?;
+    worksheet.write_row(3, 0, ["East", "Pears"])?;
+    worksheet.write_row(4, 0, ["West", "Pears"])?;
+    worksheet.write_column(1, 2, [1000, 2500, 3000, 1500])?;
+
+    let table = Table::new().set_name("Sales");
+    worksheet.add_table(0, 0, 4, 2, &table)?;
+
+    let slicer = Slicer::new("Sales", "Region");
+    worksheet.insert_slicer(1, 4, &slicer)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "requires the Excel reference file tests/input/slicer01.xlsx"]
+fn test_slicer01() {
+    let test_runner = common::TestRunner::new()
+        .set_name("slicer01")
+        .set_function(create_new_xlsx_file)
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}
@@ -0,0 +1,50 @@
+// Test case that compares a file generated by rust_xlsxwriter with a file
+// created by Excel.
+//
+// The test is ignored until the reference file is created and saved in
+// Excel as tests/input/threaded_comment01.xlsx.
+//
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//
+// Copyright 2022-2026, John McNamara, jmcnamara@cpan.org
+
+use crate::common;
+use rust_xlsxwriter::{ExcelDateTime, ThreadedComment, Workbook, XlsxError};
+
+// Create a rust_xlsxwriter file to compare against an Excel file.
+fn create_new_xlsx_file(filename: &str) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+
+    let datetime = ExcelDateTime::parse_from_str("2025-03-14T09:30:00")?;
+    let reply = ThreadedComment::new("Yes, it is approved.")
+        .set_author("Jane Doe")
+        .set_datetime(&datetime);
+
+    let comment = ThreadedComment::new("Is this figure final? @Jane Doe")
+        .set_author("John Smith")
+        .set_datetime(&datetime)
+        .add_mention("Jane Doe")
+        .add_reply(&reply);
+
+    worksheet.write(0, 0, "Budget")?;
+    worksheet.write(0, 1, 12000)?;
+    worksheet.insert_threaded_comment(0, 1, &comment)?;
+
+    workbook.save(filename)?;
+
+    Ok(())
+}
+
+#[test]
+#[ignore = "requires the Excel reference file tests/input/threaded_comment01.xlsx"]
+fn test_threaded_comment01() {
+    let test_runner = common::TestRunner::new()
+        .set_name("threaded_comment01")
+        .set_function(create_new_xlsx_file)
+        .initialize();
+
+    test_runner.assert_eq();
+    test_runner.cleanup();
+}